@@ -3,10 +3,12 @@ use crate::output::{git_diff, json, text};
 use crate::{DiffPresetArg, OutputFormat};
 use anyhow::{bail, Context, Result};
 use excel_diff::{
-    index_to_address, suggest_key_columns, with_default_session, DiffConfig, DiffReport,
-    DiffSummary, Grid, JsonLinesSink, ProgressCallback, SheetKind, Workbook, WorkbookPackage,
+    index_to_address, suggest_key_columns, with_default_session, DatabaseKeys, DiffConfig,
+    DiffReport, DiffSummary, Grid, JsonLinesSink, ProgressCallback, SheetKeyMap, SheetKind,
+    Workbook, WorkbookPackage,
 };
 use license_client::LicenseClient;
+use serde::Deserialize;
 use std::collections::HashMap;
 #[cfg(feature = "perf-metrics")]
 use std::fs::File;
@@ -42,12 +44,17 @@ pub fn run(
     fast: bool,
     precise: bool,
     preset: Option<DiffPresetArg>,
+    ignore_styles: bool,
+    impact: bool,
     quiet: bool,
     verbose: bool,
     database: bool,
     sheet: Option<String>,
     keys: Option<String>,
+    header_row: Option<u32>,
+    keys_file: Option<String>,
     auto_keys: bool,
+    tables: bool,
     progress: bool,
     max_memory: Option<u32>,
     timeout: Option<u32>,
@@ -79,6 +86,10 @@ pub fn run(
         bail!("Cannot use --git-diff with --format json/jsonl/payload/outcome");
     }
 
+    if impact && (git_diff_mode || !matches!(format, OutputFormat::Text | OutputFormat::Json)) {
+        bail!("--impact requires text or JSON output");
+    }
+
     let mut format = format;
 
     let old_path_str = old_path;
@@ -96,16 +107,59 @@ pub fn run(
     }
 
     if old_kind == HostKind::Pbix {
-        if database || sheet.is_some() || keys.is_some() || auto_keys {
+        if database
+            || sheet.is_some()
+            || keys.is_some()
+            || header_row.is_some()
+            || keys_file.is_some()
+            || auto_keys
+        {
             bail!("database mode and sheet/key options are not supported for PBIX/PBIT");
         }
+        if tables {
+            bail!("--tables is not supported for PBIX/PBIT");
+        }
     } else {
-        if !database && (sheet.is_some() || keys.is_some() || auto_keys) {
-            bail!("--sheet, --keys, and --auto-keys require --database flag");
+        let key_options = sheet.is_some()
+            || keys.is_some()
+            || header_row.is_some()
+            || keys_file.is_some()
+            || auto_keys;
+
+        if tables && (database || key_options) {
+            bail!(
+                "--tables cannot be combined with --database, --sheet, --keys, --header-row, --keys-file, or --auto-keys"
+            );
+        }
+
+        if !database && key_options {
+            bail!(
+                "--sheet, --keys, --header-row, --keys-file, and --auto-keys require --database flag"
+            );
+        }
+
+        if keys_file.is_some()
+            && (sheet.is_some() || keys.is_some() || header_row.is_some() || auto_keys)
+        {
+            bail!(
+                "--keys-file cannot be combined with --sheet, --keys, --header-row, or --auto-keys"
+            );
         }
 
-        if database && keys.is_none() && !auto_keys {
-            bail!("Database mode requires either --keys or --auto-keys");
+        if impact && (database || tables) {
+            bail!("--impact cannot be combined with --database or --tables");
+        }
+
+        if header_row.is_some() && keys.is_none() {
+            bail!("--header-row requires --keys");
+        }
+
+        if header_row == Some(0) {
+            bail!("--header-row is 1-based and must be at least 1");
+        }
+
+        if database && keys.is_none() && keys_file.is_none() && !auto_keys {
+            bail!("Database mode requires --keys, --keys-file, or --auto-keys");
         }
 
         if database && keys.is_some() && auto_keys {
@@ -126,12 +180,15 @@ pub fn run(
     config.hardening.max_memory_mb = max_memory;
     config.hardening.timeout_seconds = timeout;
     config.hardening.max_ops = max_ops;
+    if ignore_styles {
+        config.semantic.enable_cell_style_diff = false;
+    }
 
     let old_host = open_host(old_path, old_kind, "old")?;
     let new_host = open_host(new_path, new_kind, "new")?;
 
     let mut estimated_cells: Option<u64> = None;
-    if !database {
+    if !database && !tables {
         estimated_cells = match (&old_host, &new_host) {
             (Host::Workbook(old_pkg), Host::Workbook(new_pkg)) => Some(estimate_diff_cell_volume(
                 &old_pkg.workbook,
@@ -139,8 +196,14 @@ pub fn run(
             )),
             _ => None,
         };
-        let (new_format, switched_cells) =
-            maybe_auto_switch_jsonl(format, force_json, git_diff_mode, estimated_cells, &config);
+        // Impact is only written into whole reports, so `--impact` keeps JSON output.
+        let (new_format, switched_cells) = maybe_auto_switch_jsonl(
+            format,
+            force_json || impact,
+            git_diff_mode,
+            estimated_cells,
+            &config,
+        );
         if let Some(cells) = switched_cells {
             eprintln!(
                 "Warning: estimated {} cells; switching to JSONL output. Use --force-json to keep JSON.",
@@ -150,6 +213,26 @@ pub fn run(
         format = new_format;
     }
 
+    if let (HostKind::Workbook, Some(keys_file)) = (old_kind, keys_file.as_deref()) {
+        let (Host::Workbook(old_pkg), Host::Workbook(new_pkg)) = (&old_host, &new_host) else {
+            unreachable!();
+        };
+        let sheet_keys = load_keys_file(Path::new(keys_file))?;
+        return run_workbook_mode(
+            WorkbookMode::Sheets(&sheet_keys),
+            old_pkg,
+            new_pkg,
+            old_path_str,
+            new_path_str,
+            format,
+            git_diff_mode,
+            &config,
+            preset,
+            verbosity,
+            metrics_json,
+        );
+    }
+
     if old_kind == HostKind::Workbook && database {
         let (Host::Workbook(old_pkg), Host::Workbook(new_pkg)) = (&old_host, &new_host) else {
             unreachable!();
@@ -167,11 +250,31 @@ pub fn run(
             verbosity,
             sheet,
             keys,
+            header_row,
             auto_keys,
             metrics_json,
         );
     }
 
+    if old_kind == HostKind::Workbook && tables {
+        let (Host::Workbook(old_pkg), Host::Workbook(new_pkg)) = (&old_host, &new_host) else {
+            unreachable!();
+        };
+        return run_workbook_mode(
+            WorkbookMode::Tables,
+            old_pkg,
+            new_pkg,
+            old_path_str,
+            new_path_str,
+            format,
+            git_diff_mode,
+            &config,
+            preset,
+            verbosity,
+            metrics_json,
+        );
+    }
+
     let progress = progress.then(CliProgress::new);
 
     if format == OutputFormat::Payload {
@@ -341,6 +444,16 @@ pub fn run(
     if git_diff_mode {
         git_diff::write_git_diff(&mut handle, &report, old_path_str, new_path_str)?;
     } else {
+        let impacts = match &new_host {
+            Host::Workbook(new_pkg) if impact => with_default_session(|session| {
+                new_pkg.formula_impact(&report.ops, &session.strings)
+            }),
+            _ => Vec::new(),
+        };
+        let measure_impacts = match &new_host {
+            Host::Pbix(new_pkg) if impact => new_pkg.measure_impact(&report),
+            _ => Vec::new(),
+        };
         match format {
             OutputFormat::Text => {
                 text::write_text_report(
@@ -349,10 +462,17 @@ pub fn run(
                     old_path_str,
                     new_path_str,
                     verbosity,
+                    &impacts,
+                    &measure_impacts,
                 )?;
             }
             OutputFormat::Json => {
-                json::write_json_report(&mut handle, &report)?;
+                json::write_json_report_with_impact(
+                    &mut handle,
+                    &report,
+                    &impacts,
+                    &measure_impacts,
+                )?;
             }
             OutputFormat::Jsonl => {
                 bail!("Internal error: JSONL format should be handled by the streaming path");
@@ -504,6 +624,7 @@ fn run_database_mode(
     verbosity: Verbosity,
     sheet: Option<String>,
     keys: Option<String>,
+    header_row: Option<u32>,
     auto_keys: bool,
     metrics_json: Option<String>,
 ) -> Result<ExitCode> {
@@ -527,8 +648,11 @@ fn run_database_mode(
     }
     format = new_format;
 
-    let key_columns = if let Some(keys_str) = keys {
-        parse_key_columns(&keys_str)?
+    let keys = if let Some(keys_str) = keys {
+        keys_str
+            .parse::<DatabaseKeys>()
+            .with_context(|| format!("Invalid --keys '{}'", keys_str))?
+            .with_header_row(header_row.unwrap_or(1).saturating_sub(1))
     } else if auto_keys {
         let grid = find_sheet_grid(&old_pkg.workbook, &sheet_name)?;
         let suggested = with_default_session(|session| suggest_key_columns(grid, &session.strings));
@@ -537,12 +661,12 @@ fn run_database_mode(
                 "Warning: Could not auto-detect key columns for sheet '{}'; falling back to spreadsheet mode.",
                 sheet_name
             );
-            Vec::new()
+            DatabaseKeys::default()
         } else {
             let col_letters: Vec<String> =
                 suggested.iter().map(|&c| col_index_to_letters(c)).collect();
             eprintln!("Auto-detected key columns: {}", col_letters.join(","));
-            suggested
+            DatabaseKeys::from_columns(&suggested)
         }
     } else {
         bail!("Database mode requires either --keys or --auto-keys");
@@ -559,7 +683,13 @@ fn run_database_mode(
         let outcome = if use_large_mode {
             let mut sink = SummarySink::new();
             let summary = old_pkg
-                .diff_database_mode_streaming(new_pkg, &sheet_name, &key_columns, config, &mut sink)
+                .diff_database_mode_streaming_with_keys(
+                    new_pkg,
+                    &sheet_name,
+                    &keys,
+                    config,
+                    &mut sink,
+                )
                 .context("Database mode streaming diff failed")?;
 
             if let Some(path) = metrics_json.as_deref() {
@@ -580,7 +710,7 @@ fn run_database_mode(
             }
         } else {
             let report = old_pkg
-                .diff_database_mode(new_pkg, &sheet_name, &key_columns, config)
+                .diff_database_mode_with_keys(new_pkg, &sheet_name, &keys, config)
                 .context("Database mode diff failed")?;
 
             print_warnings_to_stderr(&report);
@@ -626,7 +756,7 @@ fn run_database_mode(
 
     if format == OutputFormat::Payload {
         let report = old_pkg
-            .diff_database_mode(new_pkg, &sheet_name, &key_columns, config)
+            .diff_database_mode_with_keys(new_pkg, &sheet_name, &keys, config)
             .context("Database mode diff failed")?;
 
         print_warnings_to_stderr(&report);
@@ -648,14 +778,14 @@ fn run_database_mode(
             old_pkg,
             new_pkg,
             &sheet_name,
-            &key_columns,
+            &keys,
             config,
             metrics_json.as_deref(),
         );
     }
 
     let report = old_pkg
-        .diff_database_mode(new_pkg, &sheet_name, &key_columns, config)
+        .diff_database_mode_with_keys(new_pkg, &sheet_name, &keys, config)
         .context("Database mode diff failed")?;
 
     print_warnings_to_stderr(&report);
@@ -673,7 +803,15 @@ fn run_database_mode(
     } else {
         match format {
             OutputFormat::Text => {
-                text::write_text_report(&mut handle, &report, old_path, new_path, verbosity)?;
+                text::write_text_report(
+                    &mut handle,
+                    &report,
+                    old_path,
+                    new_path,
+                    verbosity,
+                    &[],
+                    &[],
+                )?;
             }
             OutputFormat::Json => {
                 json::write_json_report(&mut handle, &report)?;
@@ -690,11 +828,218 @@ fn run_database_mode(
     Ok(exit_code_from_report(&report))
 }
 
+/// `--keys-file` contents: key specs per sheet name or glob.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct KeysFile {
+    /// Default 1-based header row for `[Header]` keys.
+    #[serde(default)]
+    header_row: Option<u32>,
+    sheets: Vec<KeysFileSheet>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct KeysFileSheet {
+    sheet: String,
+    keys: String,
+    #[serde(default)]
+    header_row: Option<u32>,
+}
+
+fn load_keys_file(path: &Path) -> Result<SheetKeyMap> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read keys file: {}", path.display()))?;
+    let file: KeysFile = serde_json::from_str(&text)
+        .with_context(|| format!("Invalid keys file: {}", path.display()))?;
+    if file.sheets.is_empty() {
+        bail!("Keys file {} lists no sheets", path.display());
+    }
+
+    let mut sheet_keys = SheetKeyMap::new();
+    for entry in file.sheets {
+        let header_row = entry.header_row.or(file.header_row).unwrap_or(1);
+        if header_row == 0 {
+            bail!(
+                "Keys file {}: header_row for sheet '{}' is 1-based and must be at least 1",
+                path.display(),
+                entry.sheet
+            );
+        }
+        let keys = entry
+            .keys
+            .parse::<DatabaseKeys>()
+            .with_context(|| {
+                format!(
+                    "Keys file {}: invalid keys for sheet '{}'",
+                    path.display(),
+                    entry.sheet
+                )
+            })?
+            .with_header_row(header_row - 1);
+        sheet_keys.insert(entry.sheet, keys);
+    }
+    Ok(sheet_keys)
+}
+
+/// Whole-workbook keyed modes, which share output handling.
+enum WorkbookMode<'a> {
+    /// `--tables`
+    Tables,
+    /// `--database --keys-file`
+    Sheets(&'a SheetKeyMap),
+}
+
+impl WorkbookMode<'_> {
+    fn label(&self) -> &'static str {
+        match self {
+            WorkbookMode::Tables => "Table mode",
+            WorkbookMode::Sheets(_) => "Database mode",
+        }
+    }
+
+    fn diff(
+        &self,
+        old_pkg: &WorkbookPackage,
+        new_pkg: &WorkbookPackage,
+        config: &DiffConfig,
+    ) -> Result<DiffReport> {
+        let report = match self {
+            WorkbookMode::Tables => old_pkg.diff_tables_mode(new_pkg, config),
+            WorkbookMode::Sheets(sheet_keys) => {
+                old_pkg.diff_database_mode_sheets(new_pkg, sheet_keys, config)
+            }
+        };
+        report.with_context(|| format!("{} diff failed", self.label()))
+    }
+
+    fn diff_streaming<W: Write>(
+        &self,
+        old_pkg: &WorkbookPackage,
+        new_pkg: &WorkbookPackage,
+        config: &DiffConfig,
+        sink: &mut JsonLinesSink<W>,
+    ) -> Result<DiffSummary> {
+        let summary = match self {
+            WorkbookMode::Tables => old_pkg.diff_tables_mode_streaming(new_pkg, config, sink),
+            WorkbookMode::Sheets(sheet_keys) => {
+                old_pkg.diff_database_mode_sheets_streaming(new_pkg, sheet_keys, config, sink)
+            }
+        };
+        summary.with_context(|| format!("{} streaming diff failed", self.label()))
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run_workbook_mode(
+    mode: WorkbookMode<'_>,
+    old_pkg: &WorkbookPackage,
+    new_pkg: &WorkbookPackage,
+    old_path: &str,
+    new_path: &str,
+    format: OutputFormat,
+    git_diff_mode: bool,
+    config: &DiffConfig,
+    preset: DiffPreset,
+    verbosity: Verbosity,
+    metrics_json: Option<String>,
+) -> Result<ExitCode> {
+    if format == OutputFormat::Jsonl && !git_diff_mode {
+        let stdout = io::stdout();
+        let handle = stdout.lock();
+        let mut writer = BufWriter::new(handle);
+        let mut sink = JsonLinesSink::new(&mut writer);
+
+        let summary = mode.diff_streaming(old_pkg, new_pkg, config, &mut sink)?;
+
+        writer.flush()?;
+
+        if let Some(path) = metrics_json.as_deref() {
+            write_metrics_json_summary(Path::new(path), &summary)?;
+        }
+
+        for warning in &summary.warnings {
+            eprintln!("Warning: {}", warning);
+        }
+
+        return Ok(if summary.op_count == 0 && summary.complete {
+            ExitCode::from(0)
+        } else {
+            ExitCode::from(1)
+        });
+    }
+
+    let report = mode.diff(old_pkg, new_pkg, config)?;
+
+    print_warnings_to_stderr(&report);
+
+    if let Some(path) = metrics_json.as_deref() {
+        write_metrics_json_report(Path::new(path), &report)?;
+    }
+
+    let stdout = io::stdout();
+    let mut handle = stdout.lock();
+
+    if git_diff_mode {
+        git_diff::write_git_diff(&mut handle, &report, old_path, new_path)?;
+        return Ok(exit_code_from_report(&report));
+    }
+
+    match format {
+        OutputFormat::Text => {
+            text::write_text_report(
+                &mut handle,
+                &report,
+                old_path,
+                new_path,
+                verbosity,
+                &[],
+                &[],
+            )?;
+        }
+        OutputFormat::Json => {
+            json::write_json_report(&mut handle, &report)?;
+        }
+        OutputFormat::Jsonl => {
+            bail!("Internal error: JSONL format should be handled by the streaming path");
+        }
+        OutputFormat::Payload => {
+            let payload = ui_payload::build_payload_from_workbook_report(report, old_pkg, new_pkg);
+            json::write_json_value(&mut handle, &payload)?;
+            return Ok(exit_code_from_report(&payload.report));
+        }
+        OutputFormat::Outcome => {
+            let meta = summary_meta_from_paths(old_path, new_path);
+            let payload = ui_payload::build_payload_from_workbook_report(report, old_pkg, new_pkg);
+            let summary = summarize_report(&payload.report, meta);
+            let outcome = DiffOutcome {
+                diff_id: None,
+                mode: DiffOutcomeMode::Payload,
+                payload: Some(payload),
+                summary: Some(summary),
+                config: Some(DiffOutcomeConfig {
+                    preset: Some(preset),
+                    limits: Some(limits_from_config(config)),
+                }),
+            };
+            json::write_json_value(&mut handle, &outcome)?;
+            let report = &outcome
+                .payload
+                .as_ref()
+                .expect("payload report exists")
+                .report;
+            return Ok(exit_code_from_report(report));
+        }
+    }
+
+    Ok(exit_code_from_report(&report))
+}
+
 fn run_database_streaming(
     old_pkg: &WorkbookPackage,
     new_pkg: &WorkbookPackage,
     sheet_name: &str,
-    key_columns: &[u32],
+    keys: &DatabaseKeys,
     config: &DiffConfig,
     metrics_json: Option<&str>,
 ) -> Result<ExitCode> {
@@ -704,7 +1049,7 @@ fn run_database_streaming(
     let mut sink = JsonLinesSink::new(&mut writer);
 
     let summary = old_pkg
-        .diff_database_mode_streaming(new_pkg, sheet_name, key_columns, config, &mut sink)
+        .diff_database_mode_streaming_with_keys(new_pkg, sheet_name, keys, config, &mut sink)
         .context("Database mode streaming diff failed")?;
 
     writer.flush()?;
@@ -777,50 +1122,6 @@ fn find_sheet_grid<'a>(wb: &'a excel_diff::Workbook, sheet_name: &str) -> Result
     })
 }
 
-fn parse_key_columns(keys_str: &str) -> Result<Vec<u32>> {
-    let mut result = Vec::new();
-    let mut seen = std::collections::HashSet::new();
-
-    for token in keys_str.split(',') {
-        let token = token.trim();
-        if token.is_empty() {
-            bail!("Invalid --keys: empty column token in '{}'", keys_str);
-        }
-        if !token.chars().all(|c| c.is_ascii_alphabetic()) {
-            bail!(
-                "Invalid --keys: '{}' is not a valid column letter (must be letters only, e.g. A, B, AA)",
-                token
-            );
-        }
-        let col_idx = col_letters_to_index(token)?;
-        if !seen.insert(col_idx) {
-            bail!("Invalid --keys: duplicate column '{}'", token);
-        }
-        result.push(col_idx);
-    }
-
-    if result.is_empty() {
-        bail!("Invalid --keys: no columns specified");
-    }
-
-    Ok(result)
-}
-
-fn col_letters_to_index(letters: &str) -> Result<u32> {
-    let mut col: u32 = 0;
-    for ch in letters.chars() {
-        let upper = ch.to_ascii_uppercase();
-        if !upper.is_ascii_uppercase() {
-            bail!("Invalid column letter: '{}'", ch);
-        }
-        col = col
-            .checked_mul(26)
-            .and_then(|c| c.checked_add((upper as u8 - b'A' + 1) as u32))
-            .ok_or_else(|| anyhow::anyhow!("Column '{}' is out of range", letters))?;
-    }
-    Ok(col.saturating_sub(1))
-}
-
 fn col_index_to_letters(col: u32) -> String {
     let addr = index_to_address(0, col);
     addr.trim_end_matches(|c: char| c.is_ascii_digit())
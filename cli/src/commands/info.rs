@@ -1,5 +1,5 @@
 use anyhow::{Context, Result};
-use excel_diff::{build_embedded_queries, build_queries, DataMashup, SheetKind};
+use excel_diff::{build_embedded_queries, build_queries, DataMashup, QueryGraph, SheetKind};
 use std::io::{self, Write};
 use std::path::Path;
 use std::process::ExitCode;
@@ -63,12 +63,20 @@ fn write_power_query_section<W: Write>(w: &mut W, dm_opt: Option<&DataMashup>) -
 
     writeln!(w, "Power Query:")?;
 
-    match build_queries(dm) {
+    let top = build_queries(dm);
+    let mut embedded = build_embedded_queries(dm);
+    let graph = {
+        let mut all = top.as_ref().cloned().unwrap_or_default();
+        all.extend(embedded.iter().cloned());
+        QueryGraph::build(&all)
+    };
+
+    match top {
         Ok(mut top) => {
             top.sort_by(|a, b| a.name.cmp(&b.name));
             writeln!(w, "  Top-level: {}", top.len())?;
             for q in top {
-                write_query_line(w, &q)?;
+                write_query_line(w, &q, &graph)?;
             }
         }
         Err(e) => {
@@ -76,17 +84,16 @@ fn write_power_query_section<W: Write>(w: &mut W, dm_opt: Option<&DataMashup>) -
         }
     }
 
-    let mut embedded = build_embedded_queries(dm);
     embedded.sort_by(|a, b| a.name.cmp(&b.name));
     writeln!(w, "  Embedded: {}", embedded.len())?;
     for q in embedded {
-        write_query_line(w, &q)?;
+        write_query_line(w, &q, &graph)?;
     }
 
     Ok(())
 }
 
-fn write_query_line<W: Write>(w: &mut W, q: &excel_diff::Query) -> Result<()> {
+fn write_query_line<W: Write>(w: &mut W, q: &excel_diff::Query, graph: &QueryGraph) -> Result<()> {
     let load_flags = format_load_flags(&q.metadata);
     let group_path = q
         .metadata
@@ -105,6 +112,21 @@ fn write_query_line<W: Write>(w: &mut W, q: &excel_diff::Query) -> Result<()> {
         )?;
     }
 
+    if let Some(node) = graph.query(&q.name) {
+        if !node.references.is_empty() {
+            writeln!(w, "      references: {}", node.references.join(", "))?;
+        }
+    }
+    let feeds: Vec<&str> = graph
+        .loaded_tables(&q.name)
+        .into_iter()
+        .filter(|node| node.name != q.name)
+        .map(|node| node.member.as_str())
+        .collect();
+    if !feeds.is_empty() {
+        writeln!(w, "      feeds loaded tables: {}", feeds.join(", "))?;
+    }
+
     Ok(())
 }
 
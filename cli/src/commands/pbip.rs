@@ -1,3 +1,5 @@
+use crate::commands::diff::Verbosity;
+use crate::output::text::render_ops;
 use crate::{PbipCommands, PbipProfileArg};
 use anyhow::{bail, Context, Result};
 use license_client::LicenseClient;
@@ -122,6 +124,16 @@ fn diff_dirs(
 
     let report = excel_diff::diff_pbip_snapshots(&old_snap, &new_snap);
 
+    // TMDL is parsed into the same model IR as PBIX, so measure changes get DAX-aware
+    // classification (formatting-only vs semantic) instead of a text hash comparison.
+    let mut model_config = excel_diff::DiffConfig::default();
+    model_config.semantic.enable_dax_semantic_diff = true;
+    let model_report = excel_diff::diff_pbip_models(&old_snap, &new_snap, &model_config);
+    let model_lines = model_report
+        .as_ref()
+        .map(|r| render_ops(r, Verbosity::Normal))
+        .unwrap_or_default();
+
     let mut doc_counts: BTreeMap<&'static str, u64> = BTreeMap::new();
     for doc in &report.docs {
         let key = doc.change_kind.as_str();
@@ -209,6 +221,18 @@ fn diff_dirs(
                 writeln!(out, "- {kind}: +{a} -{r} ~{m}")?;
             }
         }
+
+        if !docs_only && !model_lines.is_empty() {
+            writeln!(out)?;
+            writeln!(out, "## Model changes")?;
+            writeln!(out)?;
+            for line in &model_lines {
+                match line.strip_prefix("  ") {
+                    Some(detail) => writeln!(out, "  - {detail}")?,
+                    None => writeln!(out, "- {line}")?,
+                }
+            }
+        }
     } else {
         writeln!(out, "PBIP Diff")?;
         writeln!(out, "Old: {}", old_path.display())?;
@@ -245,6 +269,13 @@ fn diff_dirs(
                 writeln!(out, "  New error: {}", err)?;
             }
         }
+        if !docs_only && !model_lines.is_empty() {
+            writeln!(out)?;
+            writeln!(out, "Model changes:")?;
+            for line in &model_lines {
+                writeln!(out, "  {line}")?;
+            }
+        }
     }
 
     let has_doc_errors = report.docs.iter().any(|doc| {
@@ -54,6 +54,17 @@ pub enum Commands {
         precise: bool,
         #[arg(long, value_enum, help = "Diff preset")]
         preset: Option<DiffPresetArg>,
+        #[arg(
+            long,
+            help = "Ignore cell formatting changes (number formats, fonts, fills, borders)"
+        )]
+        ignore_styles: bool,
+        #[arg(
+            long,
+            help = "List the formula cells downstream of each edited cell, or for PBIX/PBIT the \
+                    measures and visuals downstream of each changed measure (text output only)"
+        )]
+        impact: bool,
         #[arg(long, short, help = "Quiet mode: only show summary")]
         quiet: bool,
         #[arg(long, help = "Use database mode: align rows by key columns")]
@@ -62,11 +73,31 @@ pub enum Commands {
         sheet: Option<String>,
         #[arg(
             long,
-            help = "Key columns for database mode (comma-separated column letters, e.g. A,B,C)"
+            help = "Key columns for database mode: comma-separated column letters or [Header] \
+                    names, optionally wrapped in trim(), lower() or text(), \
+                    e.g. A,trim([Customer ID])"
         )]
         keys: Option<String>,
+        #[arg(
+            long,
+            value_name = "ROW",
+            help = "Row (1-based) holding the headers named in --keys [default: 1]"
+        )]
+        header_row: Option<u32>,
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "JSON file mapping sheet names or globs to --keys specs, \
+                    for database mode across all sheets"
+        )]
+        keys_file: Option<String>,
         #[arg(long, help = "Auto-detect key columns for database mode")]
         auto_keys: bool,
+        #[arg(
+            long,
+            help = "Diff each Excel table by header-named columns, with auto-detected keys"
+        )]
+        tables: bool,
         #[arg(long, help = "Show a progress bar on stderr")]
         progress: bool,
         #[arg(
@@ -213,11 +244,16 @@ fn main() -> ExitCode {
             fast,
             precise,
             preset,
+            ignore_styles,
+            impact,
             quiet,
             database,
             sheet,
             keys,
+            header_row,
+            keys_file,
             auto_keys,
+            tables,
             progress,
             max_memory,
             timeout,
@@ -232,12 +268,17 @@ fn main() -> ExitCode {
             fast,
             precise,
             preset,
+            ignore_styles,
+            impact,
             quiet,
             cli.verbose,
             database,
             sheet,
             keys,
+            header_row,
+            keys_file,
             auto_keys,
+            tables,
             progress,
             max_memory,
             timeout,
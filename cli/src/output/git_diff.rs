@@ -1,8 +1,12 @@
 use anyhow::Result;
 use excel_diff::{
-    index_to_address, CellValue, DiffOp, DiffReport, ExpressionChangeKind, ModelColumnProperty,
-    QueryChangeKind, QueryMetadataField, RelationshipProperty, StepChange, StepDiff, StepType,
-    StringId,
+    index_to_address, CellComment, CellRange, CellStyle, CellValue, ChartField,
+    ConditionalFormatRule, ConnectionField, DataValidationRule, DiffOp, DiffReport, DrawingAnchor,
+    DrawingObject, DrawingObjectKind, ExpressionChangeKind, FreezePane, MeasureProperty,
+    ModelColumnProperty, ModelObjectField, ModelObjectKind, PbipEntityKind, PivotArea, PivotSource,
+    QueryChangeKind, QueryMetadataField, RelationshipProperty, ReportField, SheetProtection,
+    SheetVisibility, StepChange, StepDiff, StepType, StringId, TextLineKind, VbaProjectProperty,
+    WorkbookProtection, WorkbookSetting,
 };
 use std::collections::BTreeMap;
 use std::io::Write;
@@ -22,7 +26,7 @@ pub fn write_git_diff<W: Write>(
         return Ok(());
     }
 
-    let (workbook_ops, sheet_ops, query_ops, model_ops) = partition_ops(report);
+    let (workbook_ops, sheet_ops, query_ops, model_ops, report_ops) = partition_ops(report);
 
     if !workbook_ops.is_empty() {
         writeln!(w, "@@ Workbook @@")?;
@@ -52,6 +56,13 @@ pub fn write_git_diff<W: Write>(
         }
     }
 
+    if !report_ops.is_empty() {
+        writeln!(w, "@@ Report @@")?;
+        for op in &report_ops {
+            write_op_diff_lines(w, report, op)?;
+        }
+    }
+
     Ok(())
 }
 
@@ -62,17 +73,21 @@ fn partition_ops(
     BTreeMap<String, Vec<&DiffOp>>,
     Vec<&DiffOp>,
     Vec<&DiffOp>,
+    Vec<&DiffOp>,
 ) {
     let mut workbook_ops: Vec<&DiffOp> = Vec::new();
     let mut sheet_ops: BTreeMap<String, Vec<&DiffOp>> = BTreeMap::new();
     let mut query_ops: Vec<&DiffOp> = Vec::new();
     let mut model_ops: Vec<&DiffOp> = Vec::new();
+    let mut report_ops: Vec<&DiffOp> = Vec::new();
 
     for op in &report.ops {
         if op.is_m_op() {
             query_ops.push(op);
         } else if op.is_model_op() {
             model_ops.push(op);
+        } else if op.is_report_op() {
+            report_ops.push(op);
         } else if let Some(sheet_id) = get_sheet_id(op) {
             let sheet_name = report.resolve(sheet_id).unwrap_or("<unknown>").to_string();
             sheet_ops.entry(sheet_name).or_default().push(op);
@@ -81,7 +96,7 @@ fn partition_ops(
         }
     }
 
-    (workbook_ops, sheet_ops, query_ops, model_ops)
+    (workbook_ops, sheet_ops, query_ops, model_ops, report_ops)
 }
 
 fn get_sheet_id(op: &DiffOp) -> Option<excel_diff::StringId> {
@@ -93,6 +108,7 @@ fn get_sheet_id(op: &DiffOp) -> Option<excel_diff::StringId> {
         DiffOp::RowRemoved { sheet, .. } => Some(*sheet),
         DiffOp::RowReplaced { sheet, .. } => Some(*sheet),
         DiffOp::DuplicateKeyCluster { sheet, .. } => Some(*sheet),
+        DiffOp::RecordChanged { sheet, .. } => Some(*sheet),
         DiffOp::ColumnAdded { sheet, .. } => Some(*sheet),
         DiffOp::ColumnRemoved { sheet, .. } => Some(*sheet),
         DiffOp::BlockMovedRows { sheet, .. } => Some(*sheet),
@@ -100,9 +116,45 @@ fn get_sheet_id(op: &DiffOp) -> Option<excel_diff::StringId> {
         DiffOp::BlockMovedRect { sheet, .. } => Some(*sheet),
         DiffOp::RectReplaced { sheet, .. } => Some(*sheet),
         DiffOp::CellEdited { sheet, .. } => Some(*sheet),
+        DiffOp::CellStyleChanged { sheet, .. } => Some(*sheet),
+        DiffOp::CommentAdded { sheet, .. } => Some(*sheet),
+        DiffOp::CommentRemoved { sheet, .. } => Some(*sheet),
+        DiffOp::CommentChanged { sheet, .. } => Some(*sheet),
+        DiffOp::DataValidationAdded { sheet, .. } => Some(*sheet),
+        DiffOp::DataValidationRemoved { sheet, .. } => Some(*sheet),
+        DiffOp::DataValidationChanged { sheet, .. } => Some(*sheet),
+        DiffOp::ConditionalFormatAdded { sheet, .. } => Some(*sheet),
+        DiffOp::ConditionalFormatRemoved { sheet, .. } => Some(*sheet),
+        DiffOp::ConditionalFormatChanged { sheet, .. } => Some(*sheet),
+        DiffOp::MergedRangeAdded { sheet, .. } => Some(*sheet),
+        DiffOp::MergedRangeRemoved { sheet, .. } => Some(*sheet),
+        DiffOp::RowVisibilityChanged { sheet, .. } => Some(*sheet),
+        DiffOp::ColumnVisibilityChanged { sheet, .. } => Some(*sheet),
+        DiffOp::RowOutlineChanged { sheet, .. } => Some(*sheet),
+        DiffOp::ColumnOutlineChanged { sheet, .. } => Some(*sheet),
+        DiffOp::RowHeightChanged { sheet, .. } => Some(*sheet),
+        DiffOp::ColumnWidthChanged { sheet, .. } => Some(*sheet),
+        DiffOp::FreezePaneChanged { sheet, .. } => Some(*sheet),
+        DiffOp::SpillRangeChanged { sheet, .. } => Some(*sheet),
+        DiffOp::SheetVisibilityChanged { sheet, .. } => Some(*sheet),
+        DiffOp::SheetTabColorChanged { sheet, .. } => Some(*sheet),
+        DiffOp::SheetProtectionChanged { sheet, .. } => Some(*sheet),
+        DiffOp::ExcelTableAdded { sheet, .. } => Some(*sheet),
+        DiffOp::ExcelTableRemoved { sheet, .. } => Some(*sheet),
+        DiffOp::ExcelTableRenamed { sheet, .. } => Some(*sheet),
+        DiffOp::ExcelTableResized { sheet, .. } => Some(*sheet),
+        DiffOp::ExcelTableColumnRenamed { sheet, .. } => Some(*sheet),
+        DiffOp::DrawingObjectAdded { sheet, .. } => Some(*sheet),
+        DiffOp::DrawingObjectRemoved { sheet, .. } => Some(*sheet),
+        DiffOp::DrawingObjectMoved { sheet, .. } => Some(*sheet),
+        DiffOp::DrawingObjectChanged { sheet, .. } => Some(*sheet),
         DiffOp::ChartAdded { sheet, .. } => Some(*sheet),
         DiffOp::ChartRemoved { sheet, .. } => Some(*sheet),
         DiffOp::ChartChanged { sheet, .. } => Some(*sheet),
+        DiffOp::PivotTableAdded { sheet, .. } => Some(*sheet),
+        DiffOp::PivotTableRemoved { sheet, .. } => Some(*sheet),
+        DiffOp::PivotTableSourceChanged { sheet, .. } => Some(*sheet),
+        DiffOp::PivotTableLayoutChanged { sheet, .. } => Some(*sheet),
         _ => None,
     }
 }
@@ -154,6 +206,27 @@ fn write_op_diff_lines<W: Write>(w: &mut W, report: &DiffReport, op: &DiffOp) ->
                 right_rows.len()
             )?;
         }
+        DiffOp::RecordChanged {
+            key,
+            new_row,
+            fields,
+            ..
+        } => {
+            let names: Vec<String> = fields
+                .iter()
+                .map(|field| match field.field {
+                    Some(id) => report.resolve(id).unwrap_or("<unknown>").to_string(),
+                    None => col_letter(field.col),
+                })
+                .collect();
+            writeln!(
+                w,
+                "~ Record [{}] (row {}): {}",
+                format_key_values(key, report),
+                new_row + 1,
+                names.join(", ")
+            )?;
+        }
         DiffOp::ColumnAdded { col_idx, .. } => {
             writeln!(w, "+ Column {}: ADDED", col_letter(*col_idx))?;
         }
@@ -242,6 +315,218 @@ fn write_op_diff_lines<W: Write>(w: &mut W, report: &DiffReport, op: &DiffOp) ->
             writeln!(w, "- Cell {}: {}", addr, old_str)?;
             writeln!(w, "+ Cell {}: {}", addr, new_str)?;
         }
+        DiffOp::CellStyleChanged { addr, from, to, .. } => {
+            for (facet, old_str, new_str) in style_facet_changes(report, from, to) {
+                writeln!(w, "- Cell {} {}: {}", addr, facet, old_str)?;
+                writeln!(w, "+ Cell {} {}: {}", addr, facet, new_str)?;
+            }
+        }
+        DiffOp::CommentAdded { addr, comment, .. } => {
+            writeln!(
+                w,
+                "+ Cell {} comment: {}",
+                addr,
+                format_comment(comment, report)
+            )?;
+        }
+        DiffOp::CommentRemoved { addr, comment, .. } => {
+            writeln!(
+                w,
+                "- Cell {} comment: {}",
+                addr,
+                format_comment(comment, report)
+            )?;
+        }
+        DiffOp::CommentChanged { addr, from, to, .. } => {
+            writeln!(
+                w,
+                "- Cell {} comment: {}",
+                addr,
+                format_comment(from, report)
+            )?;
+            writeln!(w, "+ Cell {} comment: {}", addr, format_comment(to, report))?;
+        }
+        DiffOp::DataValidationAdded { rule, .. } => {
+            writeln!(w, "+ Data validation: {}", format_validation(rule, report))?;
+        }
+        DiffOp::DataValidationRemoved { rule, .. } => {
+            writeln!(w, "- Data validation: {}", format_validation(rule, report))?;
+        }
+        DiffOp::DataValidationChanged { from, to, .. } => {
+            writeln!(w, "- Data validation: {}", format_validation(from, report))?;
+            writeln!(w, "+ Data validation: {}", format_validation(to, report))?;
+        }
+        DiffOp::ConditionalFormatAdded { rule, .. } => {
+            writeln!(
+                w,
+                "+ Conditional format: {}",
+                format_conditional_format(rule, report)
+            )?;
+        }
+        DiffOp::ConditionalFormatRemoved { rule, .. } => {
+            writeln!(
+                w,
+                "- Conditional format: {}",
+                format_conditional_format(rule, report)
+            )?;
+        }
+        DiffOp::ConditionalFormatChanged { from, to, .. } => {
+            writeln!(
+                w,
+                "- Conditional format: {}",
+                format_conditional_format(from, report)
+            )?;
+            writeln!(
+                w,
+                "+ Conditional format: {}",
+                format_conditional_format(to, report)
+            )?;
+        }
+        DiffOp::MergedRangeAdded { range, .. } => {
+            writeln!(w, "+ Merged range: {}", range)?;
+        }
+        DiffOp::MergedRangeRemoved { range, .. } => {
+            writeln!(w, "- Merged range: {}", range)?;
+        }
+        DiffOp::RowVisibilityChanged {
+            row_idx, hidden, ..
+        } => {
+            let (old, new) = if *hidden {
+                ("visible", "hidden")
+            } else {
+                ("hidden", "visible")
+            };
+            writeln!(w, "- Row {}: {}", row_idx + 1, old)?;
+            writeln!(w, "+ Row {}: {}", row_idx + 1, new)?;
+        }
+        DiffOp::ColumnVisibilityChanged {
+            col_idx, hidden, ..
+        } => {
+            let (old, new) = if *hidden {
+                ("visible", "hidden")
+            } else {
+                ("hidden", "visible")
+            };
+            writeln!(w, "- Column {}: {}", col_letter(*col_idx), old)?;
+            writeln!(w, "+ Column {}: {}", col_letter(*col_idx), new)?;
+        }
+        DiffOp::RowOutlineChanged {
+            row_idx, from, to, ..
+        } => {
+            writeln!(w, "- Row {} outline level: {}", row_idx + 1, from)?;
+            writeln!(w, "+ Row {} outline level: {}", row_idx + 1, to)?;
+        }
+        DiffOp::ColumnOutlineChanged {
+            col_idx, from, to, ..
+        } => {
+            writeln!(
+                w,
+                "- Column {} outline level: {}",
+                col_letter(*col_idx),
+                from
+            )?;
+            writeln!(w, "+ Column {} outline level: {}", col_letter(*col_idx), to)?;
+        }
+        DiffOp::RowHeightChanged {
+            row_idx, from, to, ..
+        } => {
+            writeln!(
+                w,
+                "- Row {} height: {}",
+                row_idx + 1,
+                format_row_height(*from)
+            )?;
+            writeln!(
+                w,
+                "+ Row {} height: {}",
+                row_idx + 1,
+                format_row_height(*to)
+            )?;
+        }
+        DiffOp::ColumnWidthChanged {
+            col_idx, from, to, ..
+        } => {
+            let col = col_letter(*col_idx);
+            writeln!(w, "- Column {} width: {}", col, format_col_width(*from))?;
+            writeln!(w, "+ Column {} width: {}", col, format_col_width(*to))?;
+        }
+        DiffOp::FreezePaneChanged { from, to, .. } => {
+            writeln!(w, "- Freeze panes: {}", format_freeze_pane(*from))?;
+            writeln!(w, "+ Freeze panes: {}", format_freeze_pane(*to))?;
+        }
+        DiffOp::SpillRangeChanged { addr, from, to, .. } => {
+            writeln!(
+                w,
+                "- Spill range at {}: {}",
+                addr,
+                format_spill_range(*from)
+            )?;
+            writeln!(w, "+ Spill range at {}: {}", addr, format_spill_range(*to))?;
+        }
+        DiffOp::SheetVisibilityChanged { from, to, .. } => {
+            writeln!(w, "- Sheet visibility: {}", format_sheet_visibility(*from))?;
+            writeln!(w, "+ Sheet visibility: {}", format_sheet_visibility(*to))?;
+        }
+        DiffOp::SheetTabColorChanged { from, to, .. } => {
+            writeln!(w, "- Tab color: {}", format_tab_color(*from, report))?;
+            writeln!(w, "+ Tab color: {}", format_tab_color(*to, report))?;
+        }
+        DiffOp::SheetProtectionChanged { from, to, .. } => {
+            let from = format_sheet_protection(from.as_ref(), report);
+            let to = format_sheet_protection(to.as_ref(), report);
+            writeln!(w, "- Sheet protection: {}", from)?;
+            writeln!(w, "+ Sheet protection: {}", to)?;
+        }
+        DiffOp::ExcelTableAdded { name, range, .. } => {
+            let name = report.resolve(*name).unwrap_or("<unknown>");
+            writeln!(w, "+ Table \"{}\": {}", name, range)?;
+        }
+        DiffOp::ExcelTableRemoved { name, range, .. } => {
+            let name = report.resolve(*name).unwrap_or("<unknown>");
+            writeln!(w, "- Table \"{}\": {}", name, range)?;
+        }
+        DiffOp::ExcelTableRenamed { from, to, .. } => {
+            writeln!(
+                w,
+                "- Table: {}",
+                report.resolve(*from).unwrap_or("<unknown>")
+            )?;
+            writeln!(w, "+ Table: {}", report.resolve(*to).unwrap_or("<unknown>"))?;
+        }
+        DiffOp::ExcelTableResized { name, from, to, .. } => {
+            let name = report.resolve(*name).unwrap_or("<unknown>");
+            writeln!(w, "- Table \"{}\": {}", name, from)?;
+            writeln!(w, "+ Table \"{}\": {}", name, to)?;
+        }
+        DiffOp::ExcelTableColumnRenamed {
+            table, from, to, ..
+        } => {
+            let table = report.resolve(*table).unwrap_or("<unknown>");
+            let from = report.resolve(*from).unwrap_or("<unknown>");
+            let to = report.resolve(*to).unwrap_or("<unknown>");
+            writeln!(w, "- Table \"{}\" column: {}", table, from)?;
+            writeln!(w, "+ Table \"{}\" column: {}", table, to)?;
+        }
+        DiffOp::DrawingObjectAdded { object, .. } => {
+            writeln!(w, "+ {}", format_drawing_object(object, report))?;
+        }
+        DiffOp::DrawingObjectRemoved { object, .. } => {
+            writeln!(w, "- {}", format_drawing_object(object, report))?;
+        }
+        DiffOp::DrawingObjectMoved { name, from, to, .. } => {
+            let name = escape_string(report.resolve(*name).unwrap_or("<unknown>"));
+            writeln!(
+                w,
+                "- Drawing \"{}\": {}",
+                name,
+                format_drawing_anchor(*from)
+            )?;
+            writeln!(w, "+ Drawing \"{}\": {}", name, format_drawing_anchor(*to))?;
+        }
+        DiffOp::DrawingObjectChanged { from, to, .. } => {
+            writeln!(w, "- {}", format_drawing_object(from, report))?;
+            writeln!(w, "+ {}", format_drawing_object(to, report))?;
+        }
         DiffOp::QueryAdded { name } => {
             writeln!(
                 w,
@@ -371,12 +656,99 @@ fn write_op_diff_lines<W: Write>(w: &mut W, report: &DiffReport, op: &DiffOp) ->
                 report.resolve(*name).unwrap_or("<unknown>")
             )?;
         }
-        DiffOp::VbaModuleChanged { name } => {
+        DiffOp::VbaReferenceAdded { name, libid } => {
+            writeln!(
+                w,
+                "+ VBA reference \"{}\": {}",
+                report.resolve(*name).unwrap_or("<unknown>"),
+                report.resolve(*libid).unwrap_or("<unknown>")
+            )?;
+        }
+        DiffOp::VbaReferenceRemoved { name, libid } => {
+            writeln!(
+                w,
+                "- VBA reference \"{}\": {}",
+                report.resolve(*name).unwrap_or("<unknown>"),
+                report.resolve(*libid).unwrap_or("<unknown>")
+            )?;
+        }
+        DiffOp::VbaReferenceChanged { name, from, to } => {
+            let name = report.resolve(*name).unwrap_or("<unknown>");
+            writeln!(
+                w,
+                "- VBA reference \"{}\": {}",
+                name,
+                report.resolve(*from).unwrap_or("<unknown>")
+            )?;
+            writeln!(
+                w,
+                "+ VBA reference \"{}\": {}",
+                name,
+                report.resolve(*to).unwrap_or("<unknown>")
+            )?;
+        }
+        DiffOp::VbaProjectChanged { property, from, to } => {
+            let property_name = match property {
+                VbaProjectProperty::Name => "name",
+                VbaProjectProperty::Description => "description",
+                VbaProjectProperty::HelpFile => "help_file",
+                VbaProjectProperty::HelpContext => "help_context",
+                VbaProjectProperty::Constants => "constants",
+                VbaProjectProperty::LockedForViewing => "locked_for_viewing",
+                VbaProjectProperty::Password => "password",
+            };
+            let value = |id: Option<StringId>| match id {
+                Some(id) => report.resolve(id).unwrap_or("<unknown>").to_string(),
+                None => "<none>".to_string(),
+            };
+            writeln!(w, "- VBA project.{}: {}", property_name, value(*from))?;
+            writeln!(w, "+ VBA project.{}: {}", property_name, value(*to))?;
+        }
+        DiffOp::VbaModuleChanged {
+            name,
+            module_type,
+            attributes,
+            hunks,
+            ..
+        } => {
             writeln!(
                 w,
                 "~ VBA module \"{}\": CHANGED",
                 report.resolve(*name).unwrap_or("<unknown>")
             )?;
+            if let Some((from, to)) = module_type {
+                writeln!(w, "- type: {:?}", from)?;
+                writeln!(w, "+ type: {:?}", to)?;
+            }
+            let resolve = |id: StringId| report.resolve(id).unwrap_or("<unknown>");
+            for attribute in attributes {
+                let name = resolve(attribute.name);
+                if let Some(from) = attribute.from {
+                    writeln!(w, "-Attribute {} = {}", name, resolve(from))?;
+                }
+                if let Some(to) = attribute.to {
+                    writeln!(w, "+Attribute {} = {}", name, resolve(to))?;
+                }
+            }
+            for hunk in hunks {
+                write!(
+                    w,
+                    "@@ -{},{} +{},{} @@",
+                    hunk.old_start, hunk.old_len, hunk.new_start, hunk.new_len
+                )?;
+                match hunk.procedure {
+                    Some(procedure) => writeln!(w, " {}", resolve(procedure))?,
+                    None => writeln!(w)?,
+                }
+                for line in &hunk.lines {
+                    let marker = match line.kind {
+                        TextLineKind::Context => ' ',
+                        TextLineKind::Removed => '-',
+                        TextLineKind::Added => '+',
+                    };
+                    writeln!(w, "{}{}", marker, resolve(line.text))?;
+                }
+            }
         }
         DiffOp::NamedRangeAdded { name } => {
             writeln!(
@@ -424,12 +796,231 @@ fn write_op_diff_lines<W: Write>(w: &mut W, report: &DiffReport, op: &DiffOp) ->
                 report.resolve(*name).unwrap_or("<unknown>")
             )?;
         }
-        DiffOp::ChartChanged { name, .. } => {
+        DiffOp::ChartChanged { name, changes, .. } => {
             writeln!(
                 w,
                 "~ Chart \"{}\": CHANGED",
                 report.resolve(*name).unwrap_or("<unknown>")
             )?;
+            let resolve = |id: StringId| report.resolve(id).unwrap_or("<unknown>");
+            for change in changes {
+                let field = match change.field {
+                    ChartField::Title | ChartField::AxisTitle => "title",
+                    ChartField::Legend => "legend",
+                    ChartField::Series | ChartField::Axis => "",
+                    ChartField::SeriesName => "name",
+                    ChartField::SeriesType => "type",
+                    ChartField::SeriesCategories => "categories",
+                    ChartField::SeriesValues => "values",
+                    ChartField::AxisPosition => "position",
+                    ChartField::AxisMin => "min",
+                    ChartField::AxisMax => "max",
+                    ChartField::AxisLogBase => "log_base",
+                    ChartField::AxisOrientation => "orientation",
+                    ChartField::AxisDeleted => "deleted",
+                };
+                let subject = match (change.series, change.axis) {
+                    (Some(series), _) => format!("series {}", series + 1),
+                    (None, Some(axis)) => resolve(axis).to_string(),
+                    (None, None) => String::new(),
+                };
+                let subject = match (subject.is_empty(), field.is_empty()) {
+                    (true, _) => field.to_string(),
+                    (false, true) => subject,
+                    (false, false) => format!("{subject}.{field}"),
+                };
+                if let Some(from) = change.from {
+                    writeln!(w, "- {}: {}", subject, resolve(from))?;
+                }
+                if let Some(to) = change.to {
+                    writeln!(w, "+ {}: {}", subject, resolve(to))?;
+                }
+            }
+        }
+        DiffOp::PivotTableAdded { name, .. } => {
+            writeln!(
+                w,
+                "+ Pivot table \"{}\": ADDED",
+                report.resolve(*name).unwrap_or("<unknown>")
+            )?;
+        }
+        DiffOp::PivotTableRemoved { name, .. } => {
+            writeln!(
+                w,
+                "- Pivot table \"{}\": REMOVED",
+                report.resolve(*name).unwrap_or("<unknown>")
+            )?;
+        }
+        DiffOp::PivotTableSourceChanged {
+            name,
+            from,
+            to,
+            uncovered_rows,
+            ..
+        } => {
+            writeln!(
+                w,
+                "~ Pivot table \"{}\": SOURCE CHANGED",
+                report.resolve(*name).unwrap_or("<unknown>")
+            )?;
+            writeln!(w, "- source: {}", format_pivot_source(report, from))?;
+            writeln!(w, "+ source: {}", format_pivot_source(report, to))?;
+            if *uncovered_rows > 0 {
+                writeln!(w, "+ uncovered_rows: {}", uncovered_rows)?;
+            }
+        }
+        DiffOp::PivotTableLayoutChanged { name, changes, .. } => {
+            writeln!(
+                w,
+                "~ Pivot table \"{}\": LAYOUT CHANGED",
+                report.resolve(*name).unwrap_or("<unknown>")
+            )?;
+            let resolve = |id: StringId| report.resolve(id).unwrap_or("<unknown>");
+            for change in changes {
+                let area = match change.area {
+                    PivotArea::Rows => "rows",
+                    PivotArea::Columns => "columns",
+                    PivotArea::Pages => "filter",
+                    PivotArea::Values => "values",
+                    PivotArea::Filters => "item_filter",
+                    PivotArea::CalculatedFields => "calculated_field",
+                };
+                let subject = match change.field {
+                    Some(field) => format!("{}.{}", area, resolve(field)),
+                    None => area.to_string(),
+                };
+                if let Some(from) = change.from {
+                    writeln!(w, "- {}: {}", subject, resolve(from))?;
+                }
+                if let Some(to) = change.to {
+                    writeln!(w, "+ {}: {}", subject, resolve(to))?;
+                }
+            }
+        }
+        DiffOp::ExternalLinkAdded { index, target } => {
+            writeln!(
+                w,
+                "+ External link [{}]: {}",
+                index,
+                report.resolve(*target).unwrap_or("<unknown>")
+            )?;
+        }
+        DiffOp::ExternalLinkRemoved { index, target } => {
+            writeln!(
+                w,
+                "- External link [{}]: {}",
+                index,
+                report.resolve(*target).unwrap_or("<unknown>")
+            )?;
+        }
+        DiffOp::ExternalLinkChanged { from, to } => {
+            writeln!(w, "~ External link [{}]: CHANGED", to.index)?;
+            let resolve = |id: StringId| report.resolve(id).unwrap_or("<unknown>");
+            let sheets = |names: &[StringId]| {
+                names
+                    .iter()
+                    .map(|id| resolve(*id))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            if from.target != to.target {
+                writeln!(w, "- target: {}", resolve(from.target))?;
+                writeln!(w, "+ target: {}", resolve(to.target))?;
+            }
+            if from.kind != to.kind {
+                writeln!(w, "- kind: {}", resolve(from.kind))?;
+                writeln!(w, "+ kind: {}", resolve(to.kind))?;
+            }
+            if from.sheet_names != to.sheet_names {
+                writeln!(w, "- sheets: {}", sheets(&from.sheet_names))?;
+                writeln!(w, "+ sheets: {}", sheets(&to.sheet_names))?;
+            }
+        }
+        DiffOp::ConnectionAdded { name } => {
+            writeln!(
+                w,
+                "+ Connection \"{}\": ADDED",
+                report.resolve(*name).unwrap_or("<unknown>")
+            )?;
+        }
+        DiffOp::ConnectionRemoved { name } => {
+            writeln!(
+                w,
+                "- Connection \"{}\": REMOVED",
+                report.resolve(*name).unwrap_or("<unknown>")
+            )?;
+        }
+        DiffOp::ConnectionChanged { name, changes } => {
+            writeln!(
+                w,
+                "~ Connection \"{}\": CHANGED",
+                report.resolve(*name).unwrap_or("<unknown>")
+            )?;
+            let resolve = |id: StringId| report.resolve(id).unwrap_or("<unknown>");
+            for change in changes {
+                let field = match change.field {
+                    ConnectionField::Name => "name",
+                    ConnectionField::Kind => "type",
+                    ConnectionField::Connection => "connection",
+                    ConnectionField::Command => "command",
+                    ConnectionField::RefreshOnLoad => "refresh_on_load",
+                    ConnectionField::BackgroundRefresh => "background_refresh",
+                    ConnectionField::RefreshInterval => "refresh_interval",
+                    ConnectionField::SavePassword => "save_password",
+                };
+                if let Some(from) = change.from {
+                    writeln!(w, "- {}: {}", field, resolve(from))?;
+                }
+                if let Some(to) = change.to {
+                    writeln!(w, "+ {}: {}", field, resolve(to))?;
+                }
+            }
+        }
+        DiffOp::WorkbookSettingsChanged { changes } => {
+            writeln!(w, "~ Workbook settings: CHANGED")?;
+            let resolve = |id: StringId| report.resolve(id).unwrap_or("<unknown>");
+            for change in changes {
+                let setting = match change.setting {
+                    WorkbookSetting::Date1904 => "date1904",
+                    WorkbookSetting::CodeName => "code_name",
+                    WorkbookSetting::CalcMode => "calc_mode",
+                    WorkbookSetting::FullCalcOnLoad => "full_calc_on_load",
+                    WorkbookSetting::CalcOnSave => "calc_on_save",
+                    WorkbookSetting::Iterate => "iterate",
+                    WorkbookSetting::IterateCount => "iterate_count",
+                    WorkbookSetting::IterateDelta => "iterate_delta",
+                    WorkbookSetting::FullPrecision => "full_precision",
+                };
+                if let Some(from) = change.from {
+                    writeln!(w, "- {}: {}", setting, resolve(from))?;
+                }
+                if let Some(to) = change.to {
+                    writeln!(w, "+ {}: {}", setting, resolve(to))?;
+                }
+            }
+        }
+        DiffOp::WorkbookProtectionChanged { from, to } => {
+            writeln!(
+                w,
+                "- Workbook protection: {}",
+                format_workbook_protection(*from)
+            )?;
+            writeln!(
+                w,
+                "+ Workbook protection: {}",
+                format_workbook_protection(*to)
+            )?;
+        }
+        DiffOp::DocumentPropertyChanged { name, from, to } => {
+            let name = report.resolve(*name).unwrap_or("<unknown>");
+            if let Some(from) = from {
+                let from = report.resolve(*from).unwrap_or("<unknown>");
+                writeln!(w, "- Document property \"{}\": {}", name, from)?;
+            }
+            if let Some(to) = to {
+                let to = report.resolve(*to).unwrap_or("<unknown>");
+                writeln!(w, "+ Document property \"{}\": {}", name, to)?;
+            }
         }
         DiffOp::TableAdded { name } => {
             writeln!(
@@ -572,6 +1163,144 @@ fn write_op_diff_lines<W: Write>(w: &mut W, report: &DiffReport, op: &DiffOp) ->
                 expression_change_label(*change_kind)
             )?;
         }
+        DiffOp::MeasurePropertyChanged {
+            name,
+            field,
+            old,
+            new,
+        } => {
+            let label = report.resolve(*name).unwrap_or("<unknown>");
+            let old_str = old.and_then(|id| report.resolve(id)).unwrap_or("<none>");
+            let new_str = new.and_then(|id| report.resolve(id)).unwrap_or("<none>");
+            let field_name = match field {
+                MeasureProperty::FormatString => "format_string",
+                MeasureProperty::DisplayFolder => "display_folder",
+                MeasureProperty::Description => "description",
+                MeasureProperty::KpiTarget => "kpi_target",
+                MeasureProperty::KpiStatus => "kpi_status",
+                MeasureProperty::KpiTrend => "kpi_trend",
+            };
+            writeln!(w, "- Measure \"{}\": {}: {}", label, field_name, old_str)?;
+            writeln!(w, "+ Measure \"{}\": {}: {}", label, field_name, new_str)?;
+        }
+        DiffOp::ModelObjectAdded {
+            object,
+            table,
+            name,
+        } => {
+            writeln!(
+                w,
+                "+ {}: ADDED",
+                model_object_label(report, *object, *table, *name)
+            )?;
+        }
+        DiffOp::ModelObjectRemoved {
+            object,
+            table,
+            name,
+        } => {
+            writeln!(
+                w,
+                "- {}: REMOVED",
+                model_object_label(report, *object, *table, *name)
+            )?;
+        }
+        DiffOp::ModelObjectChanged {
+            object,
+            table,
+            name,
+            changes,
+        } => {
+            writeln!(
+                w,
+                "~ {}: CHANGED",
+                model_object_label(report, *object, *table, *name)
+            )?;
+            let resolve = |id: StringId| report.resolve(id).unwrap_or("<unknown>");
+            for change in changes {
+                let field = match change.field {
+                    ModelObjectField::Description => "description",
+                    ModelObjectField::ModelPermission => "model_permission",
+                    ModelObjectField::RowFilter => "row_filter",
+                    ModelObjectField::ObjectPermission => "object_permission",
+                    ModelObjectField::Member => "member",
+                    ModelObjectField::Hidden => "hidden",
+                    ModelObjectField::DisplayFolder => "display_folder",
+                    ModelObjectField::Level => "level",
+                    ModelObjectField::LevelOrder => "level_order",
+                    ModelObjectField::Mode => "mode",
+                    ModelObjectField::SourceType => "source_type",
+                    ModelObjectField::Source => "source",
+                    ModelObjectField::Precedence => "precedence",
+                    ModelObjectField::Expression => "expression",
+                    ModelObjectField::FormatStringExpression => "format_string_expression",
+                    ModelObjectField::Ordinal => "ordinal",
+                    ModelObjectField::Translation => "translation",
+                };
+                let label = match change.item {
+                    Some(item) => format!("{} {}", field, resolve(item)),
+                    None => field.to_string(),
+                };
+                if let Some(from) = change.from {
+                    for line in resolve(from).lines() {
+                        writeln!(w, "- {}: {}", label, line)?;
+                    }
+                }
+                if let Some(to) = change.to {
+                    for line in resolve(to).lines() {
+                        writeln!(w, "+ {}: {}", label, line)?;
+                    }
+                }
+            }
+        }
+        DiffOp::ReportObjectAdded { object, page, name } => {
+            writeln!(
+                w,
+                "+ {}: ADDED",
+                report_object_label(report, *object, *page, *name)
+            )?;
+        }
+        DiffOp::ReportObjectRemoved { object, page, name } => {
+            writeln!(
+                w,
+                "- {}: REMOVED",
+                report_object_label(report, *object, *page, *name)
+            )?;
+        }
+        DiffOp::ReportObjectChanged {
+            object,
+            page,
+            name,
+            changes,
+        } => {
+            writeln!(
+                w,
+                "~ {}: CHANGED",
+                report_object_label(report, *object, *page, *name)
+            )?;
+            let resolve = |id: StringId| report.resolve(id).unwrap_or("<unknown>");
+            for change in changes {
+                let field = match change.field {
+                    ReportField::DisplayName => "display_name",
+                    ReportField::Order => "order",
+                    ReportField::Size => "size",
+                    ReportField::Hidden => "hidden",
+                    ReportField::Theme => "theme",
+                    ReportField::VisualType => "visual_type",
+                    ReportField::Title => "title",
+                    ReportField::Field => "field",
+                    ReportField::Filter => "filter",
+                    ReportField::Position => "position",
+                    ReportField::Page => "page",
+                };
+                if let Some(from) = change.from {
+                    writeln!(w, "- {}: {}", field, resolve(from))?;
+                }
+                if let Some(to) = change.to {
+                    writeln!(w, "+ {}: {}", field, resolve(to))?;
+                }
+            }
+        }
         _ => {
             writeln!(w, "~ {:?}", op)?;
         }
@@ -579,6 +1308,45 @@ fn write_op_diff_lines<W: Write>(w: &mut W, report: &DiffReport, op: &DiffOp) ->
     Ok(())
 }
 
+fn report_object_label(
+    report: &DiffReport,
+    object: PbipEntityKind,
+    page: Option<StringId>,
+    name: StringId,
+) -> String {
+    let name = report.resolve(name).unwrap_or("<unknown>");
+    let label = match object {
+        PbipEntityKind::Report => return "Report".to_string(),
+        kind => format!("{} \"{}\"", kind.as_str(), name),
+    };
+    match page.and_then(|id| report.resolve(id)) {
+        Some(page) => format!("{} on page \"{}\"", label, page),
+        None => label,
+    }
+}
+
+fn model_object_label(
+    report: &DiffReport,
+    object: ModelObjectKind,
+    table: Option<StringId>,
+    name: StringId,
+) -> String {
+    let name = match table {
+        Some(table) => format_column_ref(report, table, name),
+        None => report.resolve(name).unwrap_or("<unknown>").to_string(),
+    };
+    let kind = match object {
+        ModelObjectKind::Role => "Role",
+        ModelObjectKind::Hierarchy => "Hierarchy",
+        ModelObjectKind::Partition => "Partition",
+        ModelObjectKind::CalculationGroup => "Calculation group",
+        ModelObjectKind::CalculationItem => "Calculation item",
+        ModelObjectKind::Perspective => "Perspective",
+        ModelObjectKind::Culture => "Culture",
+    };
+    format!("{} \"{}\"", kind, name)
+}
+
 fn col_letter(col: u32) -> String {
     index_to_address(0, col)
         .chars()
@@ -612,6 +1380,256 @@ fn format_cell_value(value: &Option<CellValue>, report: &DiffReport) -> String {
     }
 }
 
+fn style_facet_changes(
+    report: &DiffReport,
+    from: &CellStyle,
+    to: &CellStyle,
+) -> Vec<(&'static str, String, String)> {
+    [
+        ("number format", from.number_format, to.number_format),
+        ("font", from.font, to.font),
+        ("fill", from.fill, to.fill),
+        ("border", from.border, to.border),
+        ("alignment", from.alignment, to.alignment),
+        ("protection", from.protection, to.protection),
+    ]
+    .into_iter()
+    .filter(|(_, old, new)| old != new)
+    .map(|(label, old, new)| {
+        (
+            label,
+            format_style_facet(old, report),
+            format_style_facet(new, report),
+        )
+    })
+    .collect()
+}
+
+fn format_style_facet(value: Option<StringId>, report: &DiffReport) -> String {
+    match value {
+        None => "<default>".to_string(),
+        Some(id) => format!(
+            "\"{}\"",
+            escape_string(report.resolve(id).unwrap_or("<unknown>"))
+        ),
+    }
+}
+
+fn format_comment(comment: &CellComment, report: &DiffReport) -> String {
+    let mut out = format!(
+        "\"{}\"",
+        escape_string(report.resolve(comment.text).unwrap_or("<unknown>"))
+    );
+    if let Some(author) = comment.author.and_then(|id| report.resolve(id)) {
+        out.push_str(&format!(" by {}", author));
+    }
+    if comment.resolved {
+        out.push_str(" (resolved)");
+    }
+    match comment.replies.len() {
+        0 => {}
+        1 => out.push_str(" +1 reply"),
+        n => out.push_str(&format!(" +{} replies", n)),
+    }
+    out
+}
+
+/// One line per object, e.g. `Form control "Button 1" at B2:C3, text "Run", macro "Go"`.
+fn format_pivot_source(report: &DiffReport, source: &PivotSource) -> String {
+    let resolve = |id: StringId| report.resolve(id).unwrap_or("<unknown>");
+    match (
+        source.sheet,
+        source.range,
+        source.name,
+        source.connection_id,
+    ) {
+        (Some(sheet), Some(range), _, _) => format!("{}!{}", resolve(sheet), range),
+        (_, _, Some(name), _) => resolve(name).to_string(),
+        (_, _, None, Some(id)) => format!("connection {}", id),
+        _ => resolve(source.kind).to_string(),
+    }
+}
+
+fn format_drawing_object(object: &DrawingObject, report: &DiffReport) -> String {
+    let kind = match object.kind {
+        DrawingObjectKind::Shape => "Shape",
+        DrawingObjectKind::TextBox => "Text box",
+        DrawingObjectKind::Picture => "Picture",
+        DrawingObjectKind::Connector => "Connector",
+        DrawingObjectKind::Group => "Group",
+        DrawingObjectKind::FormControl => "Form control",
+    };
+    let quoted = |id: StringId| {
+        format!(
+            "\"{}\"",
+            escape_string(report.resolve(id).unwrap_or("<unknown>"))
+        )
+    };
+    let mut out = format!(
+        "{} {} at {}",
+        kind,
+        quoted(object.name),
+        format_drawing_anchor(object.anchor)
+    );
+    if let Some(text) = object.text {
+        out.push_str(&format!(", text {}", quoted(text)));
+    }
+    if let Some(macro_name) = object.macro_name {
+        out.push_str(&format!(", macro {}", quoted(macro_name)));
+    }
+    if let Some(hash) = object.image_hash {
+        out.push_str(&format!(", image {:016x}", hash));
+    }
+    out
+}
+
+fn format_drawing_anchor(anchor: DrawingAnchor) -> String {
+    let from = index_to_address(anchor.from.row, anchor.from.col);
+    match anchor.to {
+        Some(to) => format!("{}:{}", from, index_to_address(to.row, to.col)),
+        None => from,
+    }
+}
+
+fn format_rule_ranges(ranges: &[CellRange]) -> String {
+    let parts: Vec<String> = ranges.iter().map(CellRange::to_a1).collect();
+    parts.join(" ")
+}
+
+fn format_rule_head(
+    ranges: &[CellRange],
+    kind: StringId,
+    operator: Option<StringId>,
+    report: &DiffReport,
+) -> String {
+    let mut out = format!(
+        "{} {}",
+        format_rule_ranges(ranges),
+        report.resolve(kind).unwrap_or("<unknown>")
+    );
+    if let Some(op) = operator.and_then(|id| report.resolve(id)) {
+        out.push_str(&format!(" {}", op));
+    }
+    out
+}
+
+fn format_rule_formulas(formulas: &[StringId], report: &DiffReport) -> String {
+    let parts: Vec<String> = formulas
+        .iter()
+        .map(|id| format!("={}", report.resolve(*id).unwrap_or("<unknown>")))
+        .collect();
+    parts.join(", ")
+}
+
+fn format_validation(rule: &DataValidationRule, report: &DiffReport) -> String {
+    let mut out = format_rule_head(&rule.ranges, rule.kind, rule.operator, report);
+    let formulas: Vec<StringId> = rule.formula1.into_iter().chain(rule.formula2).collect();
+    if !formulas.is_empty() {
+        out.push_str(&format!(": {}", format_rule_formulas(&formulas, report)));
+    }
+    if rule.allow_blank {
+        out.push_str(" (allow blank)");
+    }
+    out
+}
+
+fn format_conditional_format(rule: &ConditionalFormatRule, report: &DiffReport) -> String {
+    let mut out = format_rule_head(&rule.ranges, rule.kind, rule.operator, report);
+    if !rule.formulas.is_empty() {
+        out.push_str(&format!(
+            ": {}",
+            format_rule_formulas(&rule.formulas, report)
+        ));
+    }
+    if let Some(priority) = rule.priority {
+        out.push_str(&format!(" priority {}", priority));
+    }
+    if rule.stop_if_true {
+        out.push_str(" (stop if true)");
+    }
+    out
+}
+
+fn format_row_height(twips: Option<u32>) -> String {
+    match twips {
+        None => "default".to_string(),
+        Some(twips) => format!("{}pt", format_number(f64::from(twips) / 20.0)),
+    }
+}
+
+fn format_col_width(width: Option<u32>) -> String {
+    match width {
+        None => "default".to_string(),
+        Some(width) => format_number(f64::from(width) / 256.0),
+    }
+}
+
+fn format_freeze_pane(pane: Option<FreezePane>) -> String {
+    match pane {
+        None => "none".to_string(),
+        Some(pane) => format!("rows {}, columns {}", pane.rows, pane.cols),
+    }
+}
+
+fn format_spill_range(range: Option<CellRange>) -> String {
+    match range {
+        None => "none".to_string(),
+        Some(range) => range.to_string(),
+    }
+}
+
+fn format_sheet_visibility(visibility: SheetVisibility) -> &'static str {
+    match visibility {
+        SheetVisibility::Visible => "visible",
+        SheetVisibility::Hidden => "hidden",
+        SheetVisibility::VeryHidden => "very hidden",
+    }
+}
+
+fn format_tab_color(color: Option<StringId>, report: &DiffReport) -> String {
+    match color {
+        None => "none".to_string(),
+        Some(id) => report.resolve(id).unwrap_or("<unknown>").to_string(),
+    }
+}
+
+fn format_sheet_protection(protection: Option<&SheetProtection>, report: &DiffReport) -> String {
+    let Some(protection) = protection else {
+        return "off".to_string();
+    };
+    let allowed: Vec<&str> = protection
+        .allowed
+        .iter()
+        .map(|id| report.resolve(*id).unwrap_or("<unknown>"))
+        .collect();
+    format!(
+        "on, password {}, allows [{}]",
+        if protection.has_password {
+            "set"
+        } else {
+            "none"
+        },
+        allowed.join(", ")
+    )
+}
+
+fn format_workbook_protection(protection: Option<WorkbookProtection>) -> String {
+    let Some(protection) = protection else {
+        return "off".to_string();
+    };
+    format!(
+        "structure {}, windows {}, revisions {}, password {}",
+        protection.lock_structure,
+        protection.lock_windows,
+        protection.lock_revision,
+        if protection.has_password {
+            "set"
+        } else {
+            "none"
+        }
+    )
+}
+
 fn format_key_values(values: &[Option<CellValue>], report: &DiffReport) -> String {
     let parts: Vec<String> = values
         .iter()
@@ -1,5 +1,5 @@
 use anyhow::Result;
-use excel_diff::DiffReport;
+use excel_diff::{CellImpact, DiffReport, MeasureImpact};
 use serde::Serialize;
 use std::io::Write;
 
@@ -7,6 +7,33 @@ pub fn write_json_report<W: Write>(w: &mut W, report: &DiffReport) -> Result<()>
     write_json_value(w, report)
 }
 
+/// A report with the `--impact` results beside its ops; each list is omitted when empty.
+#[derive(Serialize)]
+struct ImpactReport<'a> {
+    #[serde(flatten)]
+    report: &'a DiffReport,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    formula_impact: &'a [CellImpact],
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    measure_impact: &'a [MeasureImpact],
+}
+
+pub fn write_json_report_with_impact<W: Write>(
+    w: &mut W,
+    report: &DiffReport,
+    formula_impact: &[CellImpact],
+    measure_impact: &[MeasureImpact],
+) -> Result<()> {
+    write_json_value(
+        w,
+        &ImpactReport {
+            report,
+            formula_impact,
+            measure_impact,
+        },
+    )
+}
+
 pub fn write_json_value<W: Write, T: Serialize>(w: &mut W, value: &T) -> Result<()> {
     serde_json::to_writer_pretty(&mut *w, value)?;
     writeln!(w)?;
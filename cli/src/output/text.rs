@@ -1,8 +1,14 @@
 use crate::commands::diff::Verbosity;
 use anyhow::Result;
 use excel_diff::{
-    index_to_address, CellValue, DiffOp, DiffReport, ExpressionChangeKind, QueryChangeKind,
-    QueryMetadataField, StepChange, StepDiff, StepType, StringId,
+    index_to_address, CellComment, CellImpact, CellRange, CellStyle, CellValue, ChartChange,
+    ChartField, ConditionalFormatRule, ConnectionField, DataValidationRule, DaxEdit, DaxEditKind,
+    DiffOp, DiffReport, DrawingAnchor, DrawingObjectKind, ExpressionChangeKind, FormulaEdit,
+    FormulaEditKind, FreezePane, MeasureImpact, MeasureProperty, ModelObjectField, ModelObjectKind,
+    PbipEntityKind, PivotArea, PivotLayoutChange, PivotSource, QueryChangeKind, QueryMetadataField,
+    ReportField, SheetProtection, SheetVisibility, StepChange, StepDiff, StepType, StringId,
+    TextHunk, TextLineKind, VbaModuleType, VbaProcedureChangeKind, VbaProcedureKind,
+    VbaProjectProperty, WorkbookProtection, WorkbookSetting,
 };
 use std::collections::BTreeMap;
 use std::io::Write;
@@ -13,6 +19,8 @@ pub fn write_text_report<W: Write>(
     old_path: &str,
     new_path: &str,
     verbosity: Verbosity,
+    impacts: &[CellImpact],
+    measure_impacts: &[MeasureImpact],
 ) -> Result<()> {
     if verbosity != Verbosity::Quiet {
         let old_name = std::path::Path::new(old_path)
@@ -38,7 +46,7 @@ pub fn write_text_report<W: Write>(
         return Ok(());
     }
 
-    let (workbook_ops, sheet_ops, query_ops, model_ops) = partition_ops(report);
+    let (workbook_ops, sheet_ops, query_ops, model_ops, report_ops) = partition_ops(report);
 
     if !workbook_ops.is_empty() {
         writeln!(w, "Workbook:")?;
@@ -84,10 +92,111 @@ pub fn write_text_report<W: Write>(
         writeln!(w)?;
     }
 
+    if !report_ops.is_empty() {
+        writeln!(w, "Report:")?;
+        for op in &report_ops {
+            let lines = render_op(report, op, verbosity);
+            for line in lines {
+                writeln!(w, "  {}", line)?;
+            }
+        }
+        writeln!(w)?;
+    }
+
+    if !impacts.is_empty() {
+        writeln!(w, "Formula impact:")?;
+        for impact in impacts {
+            for line in render_impact(report, impact, verbosity) {
+                writeln!(w, "  {}", line)?;
+            }
+        }
+        writeln!(w)?;
+    }
+
+    if !measure_impacts.is_empty() {
+        writeln!(w, "Measure impact:")?;
+        for impact in measure_impacts {
+            for line in render_measure_impact(impact) {
+                writeln!(w, "  {}", line)?;
+            }
+        }
+        writeln!(w)?;
+    }
+
     write_summary(w, report)?;
     Ok(())
 }
 
+/// Render every op of `report` as text lines, without section headers or summary.
+pub fn render_ops(report: &DiffReport, verbosity: Verbosity) -> Vec<String> {
+    report
+        .ops
+        .iter()
+        .flat_map(|op| render_op(report, op, verbosity))
+        .collect()
+}
+
+/// One line per edited measure with its dependent counts, then the dependents themselves.
+fn render_measure_impact(impact: &MeasureImpact) -> Vec<String> {
+    let plural = |count: usize| if count == 1 { "" } else { "s" };
+    let measures = impact.dependent_measures.len();
+    let visuals = impact.visuals.len();
+    let mut lines = vec![format!(
+        "Measure \"{}\" affects {} measure{} and {} visual{}",
+        impact.measure,
+        measures,
+        plural(measures),
+        visuals,
+        plural(visuals)
+    )];
+    if measures > 0 {
+        lines.push(format!(
+            "  measures: {}",
+            impact.dependent_measures.join(", ")
+        ));
+    }
+    for visual in &impact.visuals {
+        lines.push(format!(
+            "  visual \"{}\" on page \"{}\"",
+            visual.visual, visual.page
+        ));
+    }
+    lines
+}
+
+/// One line per edited cell with its dependent count per sheet; verbose output lists the cells.
+fn render_impact(report: &DiffReport, impact: &CellImpact, verbosity: Verbosity) -> Vec<String> {
+    let sheet_name = |id: StringId| report.resolve(id).unwrap_or("<unknown>");
+    let counts = impact.counts_by_sheet();
+    let per_sheet: Vec<String> = counts
+        .iter()
+        .map(|(sheet, count)| format!("{} on {}", count, sheet_name(*sheet)))
+        .collect();
+    let total = impact.dependents.len();
+    let mut lines = vec![format!(
+        "{}!{} affects {} formula cell{} ({})",
+        sheet_name(impact.sheet),
+        impact.addr,
+        total,
+        if total == 1 { "" } else { "s" },
+        per_sheet.join(", ")
+    )];
+
+    if verbosity == Verbosity::Verbose {
+        for (sheet, _) in counts {
+            let cells: Vec<String> = impact
+                .dependents
+                .iter()
+                .filter(|cell| cell.sheet == sheet)
+                .map(|cell| cell.addr.to_a1())
+                .collect();
+            lines.push(format!("  {}: {}", sheet_name(sheet), cells.join(", ")));
+        }
+    }
+
+    lines
+}
+
 fn partition_ops(
     report: &DiffReport,
 ) -> (
@@ -95,17 +204,21 @@ fn partition_ops(
     BTreeMap<String, Vec<&DiffOp>>,
     Vec<&DiffOp>,
     Vec<&DiffOp>,
+    Vec<&DiffOp>,
 ) {
     let mut workbook_ops: Vec<&DiffOp> = Vec::new();
     let mut sheet_ops: BTreeMap<String, Vec<&DiffOp>> = BTreeMap::new();
     let mut query_ops: Vec<&DiffOp> = Vec::new();
     let mut model_ops: Vec<&DiffOp> = Vec::new();
+    let mut report_ops: Vec<&DiffOp> = Vec::new();
 
     for op in &report.ops {
         if op.is_m_op() {
             query_ops.push(op);
         } else if op.is_model_op() {
             model_ops.push(op);
+        } else if op.is_report_op() {
+            report_ops.push(op);
         } else if let Some(sheet_id) = get_sheet_id(op) {
             let sheet_name = report.resolve(sheet_id).unwrap_or("<unknown>").to_string();
             sheet_ops.entry(sheet_name).or_default().push(op);
@@ -114,7 +227,7 @@ fn partition_ops(
         }
     }
 
-    (workbook_ops, sheet_ops, query_ops, model_ops)
+    (workbook_ops, sheet_ops, query_ops, model_ops, report_ops)
 }
 
 fn get_sheet_id(op: &DiffOp) -> Option<StringId> {
@@ -126,6 +239,7 @@ fn get_sheet_id(op: &DiffOp) -> Option<StringId> {
         DiffOp::RowRemoved { sheet, .. } => Some(*sheet),
         DiffOp::RowReplaced { sheet, .. } => Some(*sheet),
         DiffOp::DuplicateKeyCluster { sheet, .. } => Some(*sheet),
+        DiffOp::RecordChanged { sheet, .. } => Some(*sheet),
         DiffOp::ColumnAdded { sheet, .. } => Some(*sheet),
         DiffOp::ColumnRemoved { sheet, .. } => Some(*sheet),
         DiffOp::BlockMovedRows { sheet, .. } => Some(*sheet),
@@ -133,9 +247,45 @@ fn get_sheet_id(op: &DiffOp) -> Option<StringId> {
         DiffOp::BlockMovedRect { sheet, .. } => Some(*sheet),
         DiffOp::RectReplaced { sheet, .. } => Some(*sheet),
         DiffOp::CellEdited { sheet, .. } => Some(*sheet),
+        DiffOp::CellStyleChanged { sheet, .. } => Some(*sheet),
+        DiffOp::CommentAdded { sheet, .. } => Some(*sheet),
+        DiffOp::CommentRemoved { sheet, .. } => Some(*sheet),
+        DiffOp::CommentChanged { sheet, .. } => Some(*sheet),
+        DiffOp::DataValidationAdded { sheet, .. } => Some(*sheet),
+        DiffOp::DataValidationRemoved { sheet, .. } => Some(*sheet),
+        DiffOp::DataValidationChanged { sheet, .. } => Some(*sheet),
+        DiffOp::ConditionalFormatAdded { sheet, .. } => Some(*sheet),
+        DiffOp::ConditionalFormatRemoved { sheet, .. } => Some(*sheet),
+        DiffOp::ConditionalFormatChanged { sheet, .. } => Some(*sheet),
+        DiffOp::MergedRangeAdded { sheet, .. } => Some(*sheet),
+        DiffOp::MergedRangeRemoved { sheet, .. } => Some(*sheet),
+        DiffOp::RowVisibilityChanged { sheet, .. } => Some(*sheet),
+        DiffOp::ColumnVisibilityChanged { sheet, .. } => Some(*sheet),
+        DiffOp::RowOutlineChanged { sheet, .. } => Some(*sheet),
+        DiffOp::ColumnOutlineChanged { sheet, .. } => Some(*sheet),
+        DiffOp::RowHeightChanged { sheet, .. } => Some(*sheet),
+        DiffOp::ColumnWidthChanged { sheet, .. } => Some(*sheet),
+        DiffOp::FreezePaneChanged { sheet, .. } => Some(*sheet),
+        DiffOp::SpillRangeChanged { sheet, .. } => Some(*sheet),
+        DiffOp::SheetVisibilityChanged { sheet, .. } => Some(*sheet),
+        DiffOp::SheetTabColorChanged { sheet, .. } => Some(*sheet),
+        DiffOp::SheetProtectionChanged { sheet, .. } => Some(*sheet),
+        DiffOp::ExcelTableAdded { sheet, .. } => Some(*sheet),
+        DiffOp::ExcelTableRemoved { sheet, .. } => Some(*sheet),
+        DiffOp::ExcelTableRenamed { sheet, .. } => Some(*sheet),
+        DiffOp::ExcelTableResized { sheet, .. } => Some(*sheet),
+        DiffOp::ExcelTableColumnRenamed { sheet, .. } => Some(*sheet),
+        DiffOp::DrawingObjectAdded { sheet, .. } => Some(*sheet),
+        DiffOp::DrawingObjectRemoved { sheet, .. } => Some(*sheet),
+        DiffOp::DrawingObjectMoved { sheet, .. } => Some(*sheet),
+        DiffOp::DrawingObjectChanged { sheet, .. } => Some(*sheet),
         DiffOp::ChartAdded { sheet, .. } => Some(*sheet),
         DiffOp::ChartRemoved { sheet, .. } => Some(*sheet),
         DiffOp::ChartChanged { sheet, .. } => Some(*sheet),
+        DiffOp::PivotTableAdded { sheet, .. } => Some(*sheet),
+        DiffOp::PivotTableRemoved { sheet, .. } => Some(*sheet),
+        DiffOp::PivotTableSourceChanged { sheet, .. } => Some(*sheet),
+        DiffOp::PivotTableLayoutChanged { sheet, .. } => Some(*sheet),
         _ => None,
     }
 }
@@ -193,6 +343,40 @@ fn render_op(report: &DiffReport, op: &DiffOp, verbosity: Verbosity) -> Vec<Stri
                 )]
             }
         }
+        DiffOp::RecordChanged {
+            key,
+            old_row,
+            new_row,
+            fields,
+            ..
+        } => {
+            let rows = if old_row == new_row {
+                format!("row {}", new_row + 1)
+            } else {
+                format!("row {} → {}", old_row + 1, new_row + 1)
+            };
+            let changes: Vec<String> = fields
+                .iter()
+                .map(|field| {
+                    let name = match field.field {
+                        Some(id) => report.resolve(id).unwrap_or("<unknown>").to_string(),
+                        None => col_letter(field.col),
+                    };
+                    format!(
+                        "{} {} → {}",
+                        name,
+                        format_cell_value(&field.from, report),
+                        format_cell_value(&field.to, report)
+                    )
+                })
+                .collect();
+            vec![format!(
+                "Record [{}] ({}): {}",
+                format_key_values(key, report),
+                rows,
+                changes.join(", ")
+            )]
+        }
         DiffOp::ColumnAdded { col_idx, .. } => {
             vec![format!("Column {}: ADDED", col_letter(*col_idx))]
         }
@@ -285,7 +469,13 @@ fn render_op(report: &DiffReport, op: &DiffOp, verbosity: Verbosity) -> Vec<Stri
             let range = format_range(*start_row, *start_col, *row_count, *col_count);
             vec![format!("Rect replaced: {}", range)]
         }
-        DiffOp::CellEdited { addr, from, to, .. } => {
+        DiffOp::CellEdited {
+            addr,
+            from,
+            to,
+            formula_edits,
+            ..
+        } => {
             let old_str = format_cell_value(&from.value, report);
             let new_str = format_cell_value(&to.value, report);
             let mut result = vec![format!("Cell {}: {} → {}", addr, old_str, new_str)];
@@ -301,6 +491,241 @@ fn render_op(report: &DiffReport, op: &DiffOp, verbosity: Verbosity) -> Vec<Stri
                     }
                 }
             }
+            for edit in formula_edits {
+                result.push(format!("  {}", format_formula_edit(edit)));
+            }
+            result
+        }
+        DiffOp::CellStyleChanged { addr, from, to, .. } => {
+            let mut result = vec![format!("Cell {}: style changed", addr)];
+            for (facet, old_str, new_str) in style_facet_changes(report, from, to) {
+                result.push(format!("  {}: {} → {}", facet, old_str, new_str));
+            }
+            result
+        }
+        DiffOp::CommentAdded { addr, comment, .. } => {
+            vec![format!(
+                "Cell {}: comment added {}",
+                addr,
+                format_comment(comment, report)
+            )]
+        }
+        DiffOp::CommentRemoved { addr, comment, .. } => {
+            vec![format!(
+                "Cell {}: comment removed {}",
+                addr,
+                format_comment(comment, report)
+            )]
+        }
+        DiffOp::CommentChanged { addr, from, to, .. } => vec![
+            format!("Cell {}: comment changed", addr),
+            format!("  old: {}", format_comment(from, report)),
+            format!("  new: {}", format_comment(to, report)),
+        ],
+        DiffOp::DataValidationAdded { rule, .. } => {
+            vec![format!(
+                "Data validation added: {}",
+                format_validation(rule, report)
+            )]
+        }
+        DiffOp::DataValidationRemoved { rule, .. } => {
+            vec![format!(
+                "Data validation removed: {}",
+                format_validation(rule, report)
+            )]
+        }
+        DiffOp::DataValidationChanged { from, to, .. } => vec![
+            format!(
+                "Data validation changed: {}",
+                format_changed_ranges(&from.ranges, &to.ranges)
+            ),
+            format!("  old: {}", format_validation(from, report)),
+            format!("  new: {}", format_validation(to, report)),
+        ],
+        DiffOp::ConditionalFormatAdded { rule, .. } => {
+            vec![format!(
+                "Conditional format added: {}",
+                format_conditional_format(rule, report)
+            )]
+        }
+        DiffOp::ConditionalFormatRemoved { rule, .. } => {
+            vec![format!(
+                "Conditional format removed: {}",
+                format_conditional_format(rule, report)
+            )]
+        }
+        DiffOp::ConditionalFormatChanged { from, to, .. } => vec![
+            format!(
+                "Conditional format changed: {}",
+                format_changed_ranges(&from.ranges, &to.ranges)
+            ),
+            format!("  old: {}", format_conditional_format(from, report)),
+            format!("  new: {}", format_conditional_format(to, report)),
+        ],
+        DiffOp::MergedRangeAdded { range, .. } => {
+            vec![format!("Merged range added: {}", range)]
+        }
+        DiffOp::MergedRangeRemoved { range, .. } => {
+            vec![format!("Merged range removed: {}", range)]
+        }
+        DiffOp::RowVisibilityChanged {
+            row_idx, hidden, ..
+        } => vec![format!(
+            "Row {}: {}",
+            row_idx + 1,
+            if *hidden { "hidden" } else { "unhidden" }
+        )],
+        DiffOp::ColumnVisibilityChanged {
+            col_idx, hidden, ..
+        } => vec![format!(
+            "Column {}: {}",
+            col_letter(*col_idx),
+            if *hidden { "hidden" } else { "unhidden" }
+        )],
+        DiffOp::RowOutlineChanged {
+            row_idx, from, to, ..
+        } => vec![format!(
+            "Row {}: outline level {} → {}",
+            row_idx + 1,
+            from,
+            to
+        )],
+        DiffOp::ColumnOutlineChanged {
+            col_idx, from, to, ..
+        } => vec![format!(
+            "Column {}: outline level {} → {}",
+            col_letter(*col_idx),
+            from,
+            to
+        )],
+        DiffOp::RowHeightChanged {
+            row_idx, from, to, ..
+        } => vec![format!(
+            "Row {}: height {} → {}",
+            row_idx + 1,
+            format_row_height(*from),
+            format_row_height(*to)
+        )],
+        DiffOp::ColumnWidthChanged {
+            col_idx, from, to, ..
+        } => vec![format!(
+            "Column {}: width {} → {}",
+            col_letter(*col_idx),
+            format_col_width(*from),
+            format_col_width(*to)
+        )],
+        DiffOp::FreezePaneChanged { from, to, .. } => vec![format!(
+            "Freeze panes: {} → {}",
+            format_freeze_pane(*from),
+            format_freeze_pane(*to)
+        )],
+        DiffOp::SpillRangeChanged { addr, from, to, .. } => vec![format!(
+            "Array formula at {}: spill range {} → {}",
+            addr,
+            format_spill_range(*from),
+            format_spill_range(*to)
+        )],
+        DiffOp::SheetVisibilityChanged { from, to, .. } => vec![format!(
+            "Sheet visibility: {} → {}",
+            format_sheet_visibility(*from),
+            format_sheet_visibility(*to)
+        )],
+        DiffOp::SheetTabColorChanged { from, to, .. } => vec![format!(
+            "Tab color: {} → {}",
+            format_tab_color(*from, report),
+            format_tab_color(*to, report)
+        )],
+        DiffOp::SheetProtectionChanged { from, to, .. } => vec![format!(
+            "Sheet protection: {} → {}",
+            format_sheet_protection(from.as_ref(), report),
+            format_sheet_protection(to.as_ref(), report)
+        )],
+        DiffOp::ExcelTableAdded { name, range, .. } => vec![format!(
+            "Table added: {} ({})",
+            report.resolve(*name).unwrap_or("<unknown>"),
+            range
+        )],
+        DiffOp::ExcelTableRemoved { name, range, .. } => vec![format!(
+            "Table removed: {} ({})",
+            report.resolve(*name).unwrap_or("<unknown>"),
+            range
+        )],
+        DiffOp::ExcelTableRenamed { from, to, .. } => vec![format!(
+            "Table renamed: {} → {}",
+            report.resolve(*from).unwrap_or("<unknown>"),
+            report.resolve(*to).unwrap_or("<unknown>")
+        )],
+        DiffOp::ExcelTableResized { name, from, to, .. } => vec![format!(
+            "Table {} resized: {} → {}",
+            report.resolve(*name).unwrap_or("<unknown>"),
+            from,
+            to
+        )],
+        DiffOp::ExcelTableColumnRenamed {
+            table, from, to, ..
+        } => vec![format!(
+            "Table {} column renamed: {} → {}",
+            report.resolve(*table).unwrap_or("<unknown>"),
+            report.resolve(*from).unwrap_or("<unknown>"),
+            report.resolve(*to).unwrap_or("<unknown>")
+        )],
+        DiffOp::DrawingObjectAdded { object, .. } => vec![format!(
+            "{} added: \"{}\" at {}",
+            drawing_kind_label(object.kind),
+            escape_string(report.resolve(object.name).unwrap_or("<unknown>")),
+            format_drawing_anchor(object.anchor)
+        )],
+        DiffOp::DrawingObjectRemoved { object, .. } => vec![format!(
+            "{} removed: \"{}\" at {}",
+            drawing_kind_label(object.kind),
+            escape_string(report.resolve(object.name).unwrap_or("<unknown>")),
+            format_drawing_anchor(object.anchor)
+        )],
+        DiffOp::DrawingObjectMoved { name, from, to, .. } => {
+            let (from, to) = (format_drawing_anchor(*from), format_drawing_anchor(*to));
+            let within = if from == to { " (within cell)" } else { "" };
+            vec![format!(
+                "Drawing object \"{}\" moved: {} → {}{}",
+                escape_string(report.resolve(*name).unwrap_or("<unknown>")),
+                from,
+                to,
+                within
+            )]
+        }
+        DiffOp::DrawingObjectChanged { from, to, .. } => {
+            let mut result = vec![format!(
+                "{} \"{}\" changed",
+                drawing_kind_label(to.kind),
+                escape_string(report.resolve(to.name).unwrap_or("<unknown>"))
+            )];
+            if from.kind != to.kind {
+                result.push(format!(
+                    "  kind: {} → {}",
+                    drawing_kind_label(from.kind),
+                    drawing_kind_label(to.kind)
+                ));
+            }
+            let fields = [
+                ("name", Some(from.name), Some(to.name)),
+                ("text", from.text, to.text),
+                ("macro", from.macro_name, to.macro_name),
+            ];
+            for (label, old, new) in fields {
+                if old != new {
+                    result.push(format!(
+                        "  {}: {} → {}",
+                        label,
+                        format_optional_text(old, report),
+                        format_optional_text(new, report)
+                    ));
+                }
+            }
+            match (from.image_hash, to.image_hash) {
+                (old, new) if old == new => {}
+                (None, Some(_)) => result.push("  image: added".to_string()),
+                (Some(_), None) => result.push("  image: removed".to_string()),
+                _ => result.push("  image: replaced".to_string()),
+            }
             result
         }
         DiffOp::QueryAdded { name } => {
@@ -326,6 +751,8 @@ fn render_op(report: &DiffReport, op: &DiffOp, verbosity: Verbosity) -> Vec<Stri
             name,
             change_kind,
             semantic_detail,
+            downstream_queries,
+            loaded_tables,
             ..
         } => {
             let kind_str = match change_kind {
@@ -340,6 +767,22 @@ fn render_op(report: &DiffReport, op: &DiffOp, verbosity: Verbosity) -> Vec<Stri
                 kind_str
             )];
 
+            let names = |ids: &[StringId]| {
+                ids.iter()
+                    .map(|id| report.resolve(*id).unwrap_or("<unknown>"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            if !downstream_queries.is_empty() {
+                lines.push(format!(
+                    "  downstream queries: {}",
+                    names(downstream_queries)
+                ));
+            }
+            if !loaded_tables.is_empty() {
+                lines.push(format!("  loaded tables: {}", names(loaded_tables)));
+            }
+
             let Some(detail) = semantic_detail else {
                 return lines;
             };
@@ -437,11 +880,114 @@ fn render_op(report: &DiffReport, op: &DiffOp, verbosity: Verbosity) -> Vec<Stri
                 report.resolve(*name).unwrap_or("<unknown>")
             )]
         }
-        DiffOp::VbaModuleChanged { name } => {
+        DiffOp::VbaReferenceAdded { name, libid } => {
+            vec![format!(
+                "VBA reference \"{}\": ADDED ({})",
+                report.resolve(*name).unwrap_or("<unknown>"),
+                report.resolve(*libid).unwrap_or("<unknown>")
+            )]
+        }
+        DiffOp::VbaReferenceRemoved { name, libid } => {
+            vec![format!(
+                "VBA reference \"{}\": REMOVED ({})",
+                report.resolve(*name).unwrap_or("<unknown>"),
+                report.resolve(*libid).unwrap_or("<unknown>")
+            )]
+        }
+        DiffOp::VbaReferenceChanged { name, from, to } => {
+            vec![format!(
+                "VBA reference \"{}\": CHANGED: {} → {}",
+                report.resolve(*name).unwrap_or("<unknown>"),
+                report.resolve(*from).unwrap_or("<unknown>"),
+                report.resolve(*to).unwrap_or("<unknown>")
+            )]
+        }
+        DiffOp::VbaProjectChanged { property, from, to } => {
+            let value = |id: Option<StringId>| match id {
+                Some(id) => report.resolve(id).unwrap_or("<unknown>").to_string(),
+                None => "<none>".to_string(),
+            };
             vec![format!(
+                "VBA project: {} changed: {} → {}",
+                vba_project_property_name(*property),
+                value(*from),
+                value(*to)
+            )]
+        }
+        DiffOp::VbaModuleChanged {
+            name,
+            module_type,
+            attributes,
+            procedures,
+            hunks,
+        } => {
+            let mut lines = vec![format!(
                 "VBA module \"{}\": CHANGED",
                 report.resolve(*name).unwrap_or("<unknown>")
-            )]
+            )];
+            if let Some((from, to)) = module_type {
+                lines.push(format!(
+                    "  type: {} -> {}",
+                    vba_module_type_label(*from),
+                    vba_module_type_label(*to)
+                ));
+            }
+            for attribute in attributes {
+                let value = |id: Option<StringId>| match id {
+                    Some(id) => report.resolve(id).unwrap_or("<unknown>").to_string(),
+                    None => "<none>".to_string(),
+                };
+                lines.push(format!(
+                    "  attribute {}: {} -> {}",
+                    report.resolve(attribute.name).unwrap_or("<unknown>"),
+                    value(attribute.from),
+                    value(attribute.to)
+                ));
+            }
+            for procedure in procedures {
+                let marker = match procedure.change {
+                    VbaProcedureChangeKind::Added => "+",
+                    VbaProcedureChangeKind::Removed => "-",
+                    VbaProcedureChangeKind::Modified => "~",
+                };
+                lines.push(format!(
+                    "  {} {} {}",
+                    marker,
+                    vba_procedure_kind_label(procedure.kind),
+                    report.resolve(procedure.name).unwrap_or("<unknown>")
+                ));
+            }
+
+            let max_lines = if verbosity == Verbosity::Verbose {
+                usize::MAX
+            } else {
+                20
+            };
+            let mut shown = 0usize;
+            let total: usize = hunks.iter().map(|hunk| hunk.lines.len()).sum();
+            'hunks: for hunk in hunks {
+                lines.push(format!("  {}", format_hunk_header(report, hunk)));
+                for line in &hunk.lines {
+                    if shown == max_lines {
+                        break 'hunks;
+                    }
+                    shown += 1;
+                    let marker = match line.kind {
+                        TextLineKind::Context => " ",
+                        TextLineKind::Removed => "-",
+                        TextLineKind::Added => "+",
+                    };
+                    lines.push(format!(
+                        "  {}{}",
+                        marker,
+                        report.resolve(line.text).unwrap_or("<unknown>")
+                    ));
+                }
+            }
+            if total > shown {
+                lines.push(format!("  ... and {} more lines", total - shown));
+            }
+            lines
         }
         DiffOp::NamedRangeAdded { name } => {
             vec![format!(
@@ -479,10 +1025,190 @@ fn render_op(report: &DiffReport, op: &DiffOp, verbosity: Verbosity) -> Vec<Stri
             "Chart \"{}\": REMOVED",
             report.resolve(*name).unwrap_or("<unknown>")
         )],
-        DiffOp::ChartChanged { name, .. } => vec![format!(
-            "Chart \"{}\": CHANGED",
+        DiffOp::ChartChanged { name, changes, .. } => {
+            let mut lines = vec![format!(
+                "Chart \"{}\": CHANGED",
+                report.resolve(*name).unwrap_or("<unknown>")
+            )];
+            let value = |id: Option<StringId>| match id {
+                Some(id) => report.resolve(id).unwrap_or("<unknown>").to_string(),
+                None => "<none>".to_string(),
+            };
+            for change in changes {
+                let subject = chart_change_subject(report, change);
+                lines.push(match change.field {
+                    ChartField::Series | ChartField::Axis => match (change.from, change.to) {
+                        (None, to) => format!("  + {}: {}", subject, value(to)),
+                        (from, _) => format!("  - {}: {}", subject, value(from)),
+                    },
+                    _ => format!(
+                        "  {}: {} -> {}",
+                        subject,
+                        value(change.from),
+                        value(change.to)
+                    ),
+                });
+            }
+            lines
+        }
+        DiffOp::PivotTableAdded { name, .. } => vec![format!(
+            "Pivot table \"{}\": ADDED",
+            report.resolve(*name).unwrap_or("<unknown>")
+        )],
+        DiffOp::PivotTableRemoved { name, .. } => vec![format!(
+            "Pivot table \"{}\": REMOVED",
+            report.resolve(*name).unwrap_or("<unknown>")
+        )],
+        DiffOp::PivotTableSourceChanged {
+            name,
+            from,
+            to,
+            uncovered_rows,
+            ..
+        } => {
+            let mut lines = vec![format!(
+                "Pivot table \"{}\": SOURCE CHANGED",
+                report.resolve(*name).unwrap_or("<unknown>")
+            )];
+            let (from, to) = (
+                format_pivot_source(report, from),
+                format_pivot_source(report, to),
+            );
+            if from != to {
+                lines.push(format!("  source: {} -> {}", from, to));
+            }
+            match uncovered_rows {
+                0 => {}
+                1 => lines.push("  1 filled row below the source range is not included".into()),
+                n => lines.push(format!(
+                    "  {} filled rows below the source range are not included",
+                    n
+                )),
+            }
+            lines
+        }
+        DiffOp::PivotTableLayoutChanged { name, changes, .. } => {
+            let mut lines = vec![format!(
+                "Pivot table \"{}\": LAYOUT CHANGED",
+                report.resolve(*name).unwrap_or("<unknown>")
+            )];
+            let value = |id: Option<StringId>| match id {
+                Some(id) => report.resolve(id).unwrap_or("<unknown>").to_string(),
+                None => "<none>".to_string(),
+            };
+            for change in changes {
+                lines.push(format!(
+                    "  {}: {} -> {}",
+                    pivot_change_subject(report, change),
+                    value(change.from),
+                    value(change.to)
+                ));
+            }
+            lines
+        }
+        DiffOp::ExternalLinkAdded { index, target } => vec![format!(
+            "External link [{}] \"{}\": ADDED",
+            index,
+            report.resolve(*target).unwrap_or("<unknown>")
+        )],
+        DiffOp::ExternalLinkRemoved { index, target } => vec![format!(
+            "External link [{}] \"{}\": REMOVED",
+            index,
+            report.resolve(*target).unwrap_or("<unknown>")
+        )],
+        DiffOp::ExternalLinkChanged { from, to } => {
+            let resolve = |id: StringId| report.resolve(id).unwrap_or("<unknown>");
+            let mut lines = vec![format!("External link [{}]: CHANGED", to.index)];
+            if from.target != to.target {
+                lines.push(format!(
+                    "  target: {} -> {}",
+                    resolve(from.target),
+                    resolve(to.target)
+                ));
+            }
+            if from.kind != to.kind {
+                lines.push(format!(
+                    "  kind: {} -> {}",
+                    resolve(from.kind),
+                    resolve(to.kind)
+                ));
+            }
+            if from.sheet_names != to.sheet_names {
+                let sheets = |names: &[StringId]| {
+                    names
+                        .iter()
+                        .map(|id| resolve(*id))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                };
+                lines.push(format!(
+                    "  sheets: {} -> {}",
+                    sheets(&from.sheet_names),
+                    sheets(&to.sheet_names)
+                ));
+            }
+            lines
+        }
+        DiffOp::ConnectionAdded { name } => vec![format!(
+            "Connection \"{}\": ADDED",
+            report.resolve(*name).unwrap_or("<unknown>")
+        )],
+        DiffOp::ConnectionRemoved { name } => vec![format!(
+            "Connection \"{}\": REMOVED",
             report.resolve(*name).unwrap_or("<unknown>")
         )],
+        DiffOp::ConnectionChanged { name, changes } => {
+            let mut lines = vec![format!(
+                "Connection \"{}\": CHANGED",
+                report.resolve(*name).unwrap_or("<unknown>")
+            )];
+            let value = |id: Option<StringId>| match id {
+                Some(id) => report.resolve(id).unwrap_or("<unknown>").to_string(),
+                None => "<none>".to_string(),
+            };
+            for change in changes {
+                lines.push(format!(
+                    "  {}: {} -> {}",
+                    connection_field_name(change.field),
+                    value(change.from),
+                    value(change.to)
+                ));
+            }
+            lines
+        }
+        DiffOp::WorkbookSettingsChanged { changes } => {
+            let mut lines = vec!["Workbook settings: CHANGED".to_string()];
+            let value = |id: Option<StringId>| match id {
+                Some(id) => report.resolve(id).unwrap_or("<unknown>").to_string(),
+                None => "<none>".to_string(),
+            };
+            for change in changes {
+                lines.push(format!(
+                    "  {}: {} -> {}",
+                    workbook_setting_name(change.setting),
+                    value(change.from),
+                    value(change.to)
+                ));
+            }
+            lines
+        }
+        DiffOp::WorkbookProtectionChanged { from, to } => vec![format!(
+            "Workbook protection: {} -> {}",
+            format_workbook_protection(*from),
+            format_workbook_protection(*to)
+        )],
+        DiffOp::DocumentPropertyChanged { name, from, to } => {
+            let value = |id: Option<StringId>| match id {
+                Some(id) => format!("\"{}\"", report.resolve(id).unwrap_or("<unknown>")),
+                None => "<none>".to_string(),
+            };
+            vec![format!(
+                "Document property \"{}\": {} -> {}",
+                report.resolve(*name).unwrap_or("<unknown>"),
+                value(*from),
+                value(*to)
+            )]
+        }
         DiffOp::MeasureAdded { name } => vec![format!(
             "Measure \"{}\": ADDED",
             report.resolve(*name).unwrap_or("<unknown>")
@@ -492,12 +1218,118 @@ fn render_op(report: &DiffReport, op: &DiffOp, verbosity: Verbosity) -> Vec<Stri
             report.resolve(*name).unwrap_or("<unknown>")
         )],
         DiffOp::MeasureDefinitionChanged {
-            name, change_kind, ..
+            name,
+            change_kind,
+            edits,
+            ..
+        } => {
+            let mut result = vec![format!(
+                "Measure \"{}\": definition changed ({})",
+                report.resolve(*name).unwrap_or("<unknown>"),
+                expression_change_label(*change_kind)
+            )];
+            for edit in edits {
+                result.push(format!("  {}", format_dax_edit(edit)));
+            }
+            result
+        }
+        DiffOp::MeasurePropertyChanged {
+            name,
+            field,
+            old,
+            new,
+        } => {
+            let old_str = old.and_then(|id| report.resolve(id)).unwrap_or("<none>");
+            let new_str = new.and_then(|id| report.resolve(id)).unwrap_or("<none>");
+            vec![format!(
+                "Measure \"{}\": {} changed: {} -> {}",
+                report.resolve(*name).unwrap_or("<unknown>"),
+                measure_field_name(*field),
+                old_str,
+                new_str
+            )]
+        }
+        DiffOp::ModelObjectAdded {
+            object,
+            table,
+            name,
         } => vec![format!(
-            "Measure \"{}\": definition changed ({})",
-            report.resolve(*name).unwrap_or("<unknown>"),
-            expression_change_label(*change_kind)
+            "{}: ADDED",
+            model_object_label(report, *object, *table, *name)
         )],
+        DiffOp::ModelObjectRemoved {
+            object,
+            table,
+            name,
+        } => vec![format!(
+            "{}: REMOVED",
+            model_object_label(report, *object, *table, *name)
+        )],
+        DiffOp::ModelObjectChanged {
+            object,
+            table,
+            name,
+            changes,
+        } => {
+            let mut lines = vec![format!(
+                "{}: CHANGED",
+                model_object_label(report, *object, *table, *name)
+            )];
+            let value = |id: Option<StringId>| match id {
+                Some(id) => report.resolve(id).unwrap_or("<unknown>").to_string(),
+                None => "<none>".to_string(),
+            };
+            for change in changes {
+                let field = model_object_field_name(change.field);
+                let label = match change.item.and_then(|id| report.resolve(id)) {
+                    Some(item) => format!("{} ({})", field, item),
+                    None => field.to_string(),
+                };
+                lines.push(format!(
+                    "  {}: {} -> {}",
+                    label,
+                    value(change.from),
+                    value(change.to)
+                ));
+            }
+            lines
+        }
+        DiffOp::ReportObjectAdded { object, page, name } => {
+            vec![format!(
+                "{}: ADDED",
+                report_object_label(report, *object, *page, *name)
+            )]
+        }
+        DiffOp::ReportObjectRemoved { object, page, name } => {
+            vec![format!(
+                "{}: REMOVED",
+                report_object_label(report, *object, *page, *name)
+            )]
+        }
+        DiffOp::ReportObjectChanged {
+            object,
+            page,
+            name,
+            changes,
+        } => {
+            let mut lines = vec![format!(
+                "{}: CHANGED",
+                report_object_label(report, *object, *page, *name)
+            )];
+            let value = |id: Option<StringId>| match id {
+                Some(id) => report.resolve(id).unwrap_or("<unknown>").to_string(),
+                None => "<none>".to_string(),
+            };
+            for change in changes {
+                lines.push(format!(
+                    "  {}: {} -> {}",
+                    report_field_name(change.field),
+                    value(change.from),
+                    value(change.to)
+                ));
+            }
+            lines
+        }
         DiffOp::TableAdded { name } => vec![format!(
             "Table \"{}\": ADDED",
             report.resolve(*name).unwrap_or("<unknown>")
@@ -564,12 +1396,19 @@ fn render_op(report: &DiffReport, op: &DiffOp, verbosity: Verbosity) -> Vec<Stri
             table,
             name,
             change_kind,
+            edits,
             ..
-        } => vec![format!(
-            "Calculated column \"{}\": definition changed ({})",
-            format_column_ref(report, *table, *name),
-            expression_change_label(*change_kind)
-        )],
+        } => {
+            let mut result = vec![format!(
+                "Calculated column \"{}\": definition changed ({})",
+                format_column_ref(report, *table, *name),
+                expression_change_label(*change_kind)
+            )];
+            for edit in edits {
+                result.push(format!("  {}", format_dax_edit(edit)));
+            }
+            result
+        }
         DiffOp::RelationshipAdded {
             from_table,
             from_column,
@@ -710,6 +1549,293 @@ fn format_cell_value(value: &Option<CellValue>, report: &DiffReport) -> String {
     }
 }
 
+fn style_facet_changes(
+    report: &DiffReport,
+    from: &CellStyle,
+    to: &CellStyle,
+) -> Vec<(&'static str, String, String)> {
+    [
+        ("number format", from.number_format, to.number_format),
+        ("font", from.font, to.font),
+        ("fill", from.fill, to.fill),
+        ("border", from.border, to.border),
+        ("alignment", from.alignment, to.alignment),
+        ("protection", from.protection, to.protection),
+    ]
+    .into_iter()
+    .filter(|(_, old, new)| old != new)
+    .map(|(label, old, new)| {
+        (
+            label,
+            format_style_facet(old, report),
+            format_style_facet(new, report),
+        )
+    })
+    .collect()
+}
+
+fn format_style_facet(value: Option<StringId>, report: &DiffReport) -> String {
+    match value {
+        None => "<default>".to_string(),
+        Some(id) => format!(
+            "\"{}\"",
+            escape_string(report.resolve(id).unwrap_or("<unknown>"))
+        ),
+    }
+}
+
+fn drawing_kind_label(kind: DrawingObjectKind) -> &'static str {
+    match kind {
+        DrawingObjectKind::Shape => "Shape",
+        DrawingObjectKind::TextBox => "Text box",
+        DrawingObjectKind::Picture => "Picture",
+        DrawingObjectKind::Connector => "Connector",
+        DrawingObjectKind::Group => "Group",
+        DrawingObjectKind::FormControl => "Form control",
+    }
+}
+
+/// The anchor's cell span, e.g. `B2:E9`; offsets within the cells are not shown.
+fn format_drawing_anchor(anchor: DrawingAnchor) -> String {
+    let from = index_to_address(anchor.from.row, anchor.from.col);
+    match anchor.to {
+        Some(to) => format!("{}:{}", from, index_to_address(to.row, to.col)),
+        None => from,
+    }
+}
+
+fn format_optional_text(value: Option<StringId>, report: &DiffReport) -> String {
+    match value {
+        None => "<none>".to_string(),
+        Some(id) => format!(
+            "\"{}\"",
+            escape_string(report.resolve(id).unwrap_or("<unknown>"))
+        ),
+    }
+}
+
+fn format_comment(comment: &CellComment, report: &DiffReport) -> String {
+    let mut out = format!(
+        "\"{}\"",
+        escape_string(report.resolve(comment.text).unwrap_or("<unknown>"))
+    );
+    if let Some(author) = comment.author.and_then(|id| report.resolve(id)) {
+        out.push_str(&format!(" by {}", author));
+    }
+    if comment.resolved {
+        out.push_str(" (resolved)");
+    }
+    match comment.replies.len() {
+        0 => {}
+        1 => out.push_str(" +1 reply"),
+        n => out.push_str(&format!(" +{} replies", n)),
+    }
+    out
+}
+
+fn format_rule_ranges(ranges: &[CellRange]) -> String {
+    let parts: Vec<String> = ranges.iter().map(CellRange::to_a1).collect();
+    parts.join(" ")
+}
+
+fn format_changed_ranges(from: &[CellRange], to: &[CellRange]) -> String {
+    if from == to {
+        format_rule_ranges(to)
+    } else {
+        format!(
+            "ranges {} → {}",
+            format_rule_ranges(from),
+            format_rule_ranges(to)
+        )
+    }
+}
+
+fn format_rule_head(
+    ranges: &[CellRange],
+    kind: StringId,
+    operator: Option<StringId>,
+    report: &DiffReport,
+) -> String {
+    let mut out = format!(
+        "{} {}",
+        format_rule_ranges(ranges),
+        report.resolve(kind).unwrap_or("<unknown>")
+    );
+    if let Some(op) = operator.and_then(|id| report.resolve(id)) {
+        out.push_str(&format!(" {}", op));
+    }
+    out
+}
+
+fn format_rule_formulas(formulas: &[StringId], report: &DiffReport) -> String {
+    let parts: Vec<String> = formulas
+        .iter()
+        .map(|id| format!("={}", report.resolve(*id).unwrap_or("<unknown>")))
+        .collect();
+    parts.join(", ")
+}
+
+fn format_validation(rule: &DataValidationRule, report: &DiffReport) -> String {
+    let mut out = format_rule_head(&rule.ranges, rule.kind, rule.operator, report);
+    let formulas: Vec<StringId> = rule.formula1.into_iter().chain(rule.formula2).collect();
+    if !formulas.is_empty() {
+        out.push_str(&format!(": {}", format_rule_formulas(&formulas, report)));
+    }
+    if rule.allow_blank {
+        out.push_str(" (allow blank)");
+    }
+    out
+}
+
+fn format_conditional_format(rule: &ConditionalFormatRule, report: &DiffReport) -> String {
+    let mut out = format_rule_head(&rule.ranges, rule.kind, rule.operator, report);
+    if !rule.formulas.is_empty() {
+        out.push_str(&format!(
+            ": {}",
+            format_rule_formulas(&rule.formulas, report)
+        ));
+    }
+    if let Some(priority) = rule.priority {
+        out.push_str(&format!(" priority {}", priority));
+    }
+    if rule.stop_if_true {
+        out.push_str(" (stop if true)");
+    }
+    out
+}
+
+fn format_row_height(twips: Option<u32>) -> String {
+    match twips {
+        None => "default".to_string(),
+        Some(twips) => format!("{}pt", format_number(f64::from(twips) / 20.0)),
+    }
+}
+
+fn format_col_width(width: Option<u32>) -> String {
+    match width {
+        None => "default".to_string(),
+        Some(width) => format_number(f64::from(width) / 256.0),
+    }
+}
+
+fn format_freeze_pane(pane: Option<FreezePane>) -> String {
+    match pane {
+        None => "none".to_string(),
+        Some(pane) => format!("rows {}, columns {}", pane.rows, pane.cols),
+    }
+}
+
+fn format_formula_edit(edit: &FormulaEdit) -> String {
+    let from = edit.from.as_deref().unwrap_or("");
+    let to = edit.to.as_deref().unwrap_or("");
+    let change = match edit.kind {
+        FormulaEditKind::FunctionRenamed => format!("function {} → {}", from, to),
+        FormulaEditKind::ReferenceChanged => format!("reference {} → {}", from, to),
+        FormulaEditKind::OperatorChanged => format!("operator {} → {}", from, to),
+        FormulaEditKind::ValueChanged => format!("value {} → {}", from, to),
+        FormulaEditKind::Replaced => format!("{} → {}", from, to),
+        FormulaEditKind::Inserted => format!("added {}", to),
+        FormulaEditKind::Removed => format!("removed {}", from),
+    };
+    match (&edit.function, edit.argument) {
+        (Some(function), Some(argument)) => {
+            format!("{} argument {}: {}", function, argument, change)
+        }
+        (Some(function), None) => format!("{}: {}", function, change),
+        _ => change,
+    }
+}
+
+fn format_dax_edit(edit: &DaxEdit) -> String {
+    let from = edit.from.as_deref().unwrap_or("");
+    let to = edit.to.as_deref().unwrap_or("");
+    let change = match edit.kind {
+        DaxEditKind::FunctionRenamed => format!("function {} → {}", from, to),
+        DaxEditKind::ReferenceChanged => format!("reference {} → {}", from, to),
+        DaxEditKind::OperatorChanged => format!("operator {} → {}", from, to),
+        DaxEditKind::ValueChanged => format!("value {} → {}", from, to),
+        DaxEditKind::FilterAdded => format!("filter added {}", to),
+        DaxEditKind::FilterRemoved => format!("filter removed {}", from),
+        DaxEditKind::FilterChanged => format!("filter {} → {}", from, to),
+        DaxEditKind::Replaced => format!("{} → {}", from, to),
+        DaxEditKind::Inserted => format!("added {}", to),
+        DaxEditKind::Removed => format!("removed {}", from),
+    };
+    match (&edit.function, edit.argument) {
+        (Some(function), Some(argument)) => {
+            format!("{} argument {}: {}", function, argument, change)
+        }
+        (Some(function), None) => format!("{}: {}", function, change),
+        _ => change,
+    }
+}
+
+fn format_spill_range(range: Option<CellRange>) -> String {
+    match range {
+        None => "none".to_string(),
+        Some(range) => range.to_string(),
+    }
+}
+
+fn format_sheet_visibility(visibility: SheetVisibility) -> &'static str {
+    match visibility {
+        SheetVisibility::Visible => "visible",
+        SheetVisibility::Hidden => "hidden",
+        SheetVisibility::VeryHidden => "very hidden",
+    }
+}
+
+fn format_tab_color(color: Option<StringId>, report: &DiffReport) -> String {
+    match color {
+        None => "none".to_string(),
+        Some(id) => report.resolve(id).unwrap_or("<unknown>").to_string(),
+    }
+}
+
+fn format_sheet_protection(protection: Option<&SheetProtection>, report: &DiffReport) -> String {
+    let Some(protection) = protection else {
+        return "off".to_string();
+    };
+    let mut details = Vec::new();
+    if protection.has_password {
+        details.push("password".to_string());
+    }
+    if !protection.allowed.is_empty() {
+        let allowed: Vec<&str> = protection
+            .allowed
+            .iter()
+            .map(|id| report.resolve(*id).unwrap_or("<unknown>"))
+            .collect();
+        details.push(format!("allows {}", allowed.join(", ")));
+    }
+    if details.is_empty() {
+        "on".to_string()
+    } else {
+        format!("on ({})", details.join("; "))
+    }
+}
+
+fn format_workbook_protection(protection: Option<WorkbookProtection>) -> String {
+    let Some(protection) = protection else {
+        return "off".to_string();
+    };
+    let mut locks = Vec::new();
+    if protection.lock_structure {
+        locks.push("structure");
+    }
+    if protection.lock_windows {
+        locks.push("windows");
+    }
+    if protection.lock_revision {
+        locks.push("revisions");
+    }
+    let mut text = format!("locks {}", locks.join(", "));
+    if protection.has_password {
+        text.push_str(" (password)");
+    }
+    text
+}
+
 fn format_key_values(values: &[Option<CellValue>], report: &DiffReport) -> String {
     let parts: Vec<String> = values
         .iter()
@@ -761,12 +1887,18 @@ fn write_summary<W: Write>(w: &mut W, report: &DiffReport) -> Result<()> {
     if counts.cells > 0 {
         writeln!(w, "  Cell edits: {}", counts.cells)?;
     }
+    if counts.records > 0 {
+        writeln!(w, "  Record changes: {}", counts.records)?;
+    }
     if counts.queries > 0 {
         writeln!(w, "  Query changes: {}", counts.queries)?;
     }
     if counts.model > 0 {
         writeln!(w, "  Model changes: {}", counts.model)?;
     }
+    if counts.report > 0 {
+        writeln!(w, "  Report changes: {}", counts.report)?;
+    }
 
     if !report.complete {
         writeln!(w, "  Status: INCOMPLETE (some changes may be missing)")?;
@@ -783,8 +1915,10 @@ struct OpCounts {
     cols: usize,
     blocks: usize,
     cells: usize,
+    records: usize,
     queries: usize,
     model: usize,
+    report: usize,
 }
 
 fn count_ops(report: &DiffReport) -> OpCounts {
@@ -794,8 +1928,10 @@ fn count_ops(report: &DiffReport) -> OpCounts {
         cols: 0,
         blocks: 0,
         cells: 0,
+        records: 0,
         queries: 0,
         model: 0,
+        report: 0,
     };
 
     for op in &report.ops {
@@ -812,13 +1948,19 @@ fn count_ops(report: &DiffReport) -> OpCounts {
             | DiffOp::BlockMovedColumns { .. }
             | DiffOp::BlockMovedRect { .. }
             | DiffOp::RectReplaced { .. } => counts.blocks += 1,
-            DiffOp::CellEdited { .. } => counts.cells += 1,
+            DiffOp::CellEdited { .. }
+            | DiffOp::CellStyleChanged { .. }
+            | DiffOp::CommentAdded { .. }
+            | DiffOp::CommentRemoved { .. }
+            | DiffOp::CommentChanged { .. } => counts.cells += 1,
+            DiffOp::RecordChanged { .. } => counts.records += 1,
             DiffOp::QueryAdded { .. }
             | DiffOp::QueryRemoved { .. }
             | DiffOp::QueryRenamed { .. }
             | DiffOp::QueryDefinitionChanged { .. }
             | DiffOp::QueryMetadataChanged { .. } => counts.queries += 1,
             _ if op.is_model_op() => counts.model += 1,
+            _ if op.is_report_op() => counts.report += 1,
             _ => {}
         }
     }
@@ -826,6 +1968,173 @@ fn count_ops(report: &DiffReport) -> OpCounts {
     counts
 }
 
+fn vba_module_type_label(module_type: VbaModuleType) -> &'static str {
+    match module_type {
+        VbaModuleType::Standard => "standard",
+        VbaModuleType::Class => "class",
+        VbaModuleType::Form => "form",
+        VbaModuleType::Document => "document",
+    }
+}
+
+/// What a chart change is about, e.g. `series 2 values` or `value axis (left) max`. Series are
+/// numbered from 1.
+fn chart_change_subject(report: &DiffReport, change: &ChartChange) -> String {
+    let field = match change.field {
+        ChartField::Title | ChartField::AxisTitle => "title",
+        ChartField::Legend => "legend",
+        ChartField::Series | ChartField::Axis => "",
+        ChartField::SeriesName => "name",
+        ChartField::SeriesType => "type",
+        ChartField::SeriesCategories => "categories",
+        ChartField::SeriesValues => "values",
+        ChartField::AxisPosition => "position",
+        ChartField::AxisMin => "min",
+        ChartField::AxisMax => "max",
+        ChartField::AxisLogBase => "log base",
+        ChartField::AxisOrientation => "orientation",
+        ChartField::AxisDeleted => "deleted",
+    };
+    let owner = match (change.series, change.axis) {
+        (Some(series), _) => Some(format!("series {}", series + 1)),
+        (None, Some(axis)) => Some(report.resolve(axis).unwrap_or("<unknown>").to_string()),
+        (None, None) => None,
+    };
+    match owner {
+        Some(owner) if field.is_empty() => owner,
+        Some(owner) => format!("{owner} {field}"),
+        None => field.to_string(),
+    }
+}
+
+fn format_pivot_source(report: &DiffReport, source: &PivotSource) -> String {
+    let resolve = |id: StringId| report.resolve(id).unwrap_or("<unknown>");
+    match (
+        source.sheet,
+        source.range,
+        source.name,
+        source.connection_id,
+    ) {
+        (Some(sheet), Some(range), _, _) => format!("{}!{}", resolve(sheet), range),
+        (_, _, Some(name), _) => resolve(name).to_string(),
+        (_, _, None, Some(id)) => format!("connection {}", id),
+        _ => resolve(source.kind).to_string(),
+    }
+}
+
+fn pivot_change_subject(report: &DiffReport, change: &PivotLayoutChange) -> String {
+    let area = match change.area {
+        PivotArea::Rows => "rows",
+        PivotArea::Columns => "columns",
+        PivotArea::Pages => "filter",
+        PivotArea::Values => "values",
+        PivotArea::Filters => "item filter",
+        PivotArea::CalculatedFields => "calculated field",
+    };
+    match change.field.and_then(|field| report.resolve(field)) {
+        Some(field) => format!("{area} \"{field}\""),
+        None => area.to_string(),
+    }
+}
+
+/// `Visual "Sales by Region" on page "Overview"`, `Page "Overview"` or `Report`.
+fn report_object_label(
+    report: &DiffReport,
+    object: PbipEntityKind,
+    page: Option<StringId>,
+    name: StringId,
+) -> String {
+    let name = report.resolve(name).unwrap_or("<unknown>");
+    let label = match object {
+        PbipEntityKind::Report => return "Report".to_string(),
+        kind => format!("{} \"{}\"", kind.as_str(), name),
+    };
+    match page.and_then(|id| report.resolve(id)) {
+        Some(page) => format!("{} on page \"{}\"", label, page),
+        None => label,
+    }
+}
+
+fn report_field_name(field: ReportField) -> &'static str {
+    match field {
+        ReportField::DisplayName => "display name",
+        ReportField::Order => "tab position",
+        ReportField::Size => "size",
+        ReportField::Hidden => "hidden",
+        ReportField::Theme => "theme",
+        ReportField::VisualType => "visual type",
+        ReportField::Title => "title",
+        ReportField::Field => "field",
+        ReportField::Filter => "filter",
+        ReportField::Position => "position",
+        ReportField::Page => "page",
+    }
+}
+
+fn connection_field_name(field: ConnectionField) -> &'static str {
+    match field {
+        ConnectionField::Name => "name",
+        ConnectionField::Kind => "type",
+        ConnectionField::Connection => "connection",
+        ConnectionField::Command => "command",
+        ConnectionField::RefreshOnLoad => "refresh on open",
+        ConnectionField::BackgroundRefresh => "background refresh",
+        ConnectionField::RefreshInterval => "refresh interval (minutes)",
+        ConnectionField::SavePassword => "save password",
+    }
+}
+
+fn workbook_setting_name(setting: WorkbookSetting) -> &'static str {
+    match setting {
+        WorkbookSetting::Date1904 => "1904 date system",
+        WorkbookSetting::CodeName => "code name",
+        WorkbookSetting::CalcMode => "calculation mode",
+        WorkbookSetting::FullCalcOnLoad => "full calculation on load",
+        WorkbookSetting::CalcOnSave => "calculate before save",
+        WorkbookSetting::Iterate => "iterative calculation",
+        WorkbookSetting::IterateCount => "maximum iterations",
+        WorkbookSetting::IterateDelta => "maximum change",
+        WorkbookSetting::FullPrecision => "full precision",
+    }
+}
+
+fn vba_project_property_name(property: VbaProjectProperty) -> &'static str {
+    match property {
+        VbaProjectProperty::Name => "name",
+        VbaProjectProperty::Description => "description",
+        VbaProjectProperty::HelpFile => "help_file",
+        VbaProjectProperty::HelpContext => "help_context",
+        VbaProjectProperty::Constants => "constants",
+        VbaProjectProperty::LockedForViewing => "locked_for_viewing",
+        VbaProjectProperty::Password => "password",
+    }
+}
+
+fn vba_procedure_kind_label(kind: VbaProcedureKind) -> &'static str {
+    match kind {
+        VbaProcedureKind::Sub => "Sub",
+        VbaProcedureKind::Function => "Function",
+        VbaProcedureKind::PropertyGet => "Property Get",
+        VbaProcedureKind::PropertyLet => "Property Let",
+        VbaProcedureKind::PropertySet => "Property Set",
+    }
+}
+
+fn format_hunk_header(report: &DiffReport, hunk: &TextHunk) -> String {
+    let header = format!(
+        "@@ -{},{} +{},{} @@",
+        hunk.old_start, hunk.old_len, hunk.new_start, hunk.new_len
+    );
+    match hunk.procedure {
+        Some(procedure) => format!(
+            "{} {}",
+            header,
+            report.resolve(procedure).unwrap_or("<unknown>")
+        ),
+        None => header,
+    }
+}
+
 fn format_column_ref(report: &DiffReport, table: StringId, column: StringId) -> String {
     let table_name = report.resolve(table).unwrap_or("<unknown>");
     let column_name = report.resolve(column).unwrap_or("<unknown>");
@@ -849,6 +2158,61 @@ fn format_relationship_ref(
     )
 }
 
+fn model_object_label(
+    report: &DiffReport,
+    object: ModelObjectKind,
+    table: Option<StringId>,
+    name: StringId,
+) -> String {
+    let name = match table {
+        Some(table) => format_column_ref(report, table, name),
+        None => report.resolve(name).unwrap_or("<unknown>").to_string(),
+    };
+    let kind = match object {
+        ModelObjectKind::Role => "Role",
+        ModelObjectKind::Hierarchy => "Hierarchy",
+        ModelObjectKind::Partition => "Partition",
+        ModelObjectKind::CalculationGroup => "Calculation group",
+        ModelObjectKind::CalculationItem => "Calculation item",
+        ModelObjectKind::Perspective => "Perspective",
+        ModelObjectKind::Culture => "Culture",
+    };
+    format!("{} \"{}\"", kind, name)
+}
+
+fn model_object_field_name(field: ModelObjectField) -> &'static str {
+    match field {
+        ModelObjectField::Description => "description",
+        ModelObjectField::ModelPermission => "model permission",
+        ModelObjectField::RowFilter => "row filter",
+        ModelObjectField::ObjectPermission => "object permission",
+        ModelObjectField::Member => "member",
+        ModelObjectField::Hidden => "hidden",
+        ModelObjectField::DisplayFolder => "display folder",
+        ModelObjectField::Level => "level",
+        ModelObjectField::LevelOrder => "level order",
+        ModelObjectField::Mode => "mode",
+        ModelObjectField::SourceType => "source type",
+        ModelObjectField::Source => "source",
+        ModelObjectField::Precedence => "precedence",
+        ModelObjectField::Expression => "expression",
+        ModelObjectField::FormatStringExpression => "format string expression",
+        ModelObjectField::Ordinal => "ordinal",
+        ModelObjectField::Translation => "translation",
+    }
+}
+
+fn measure_field_name(field: MeasureProperty) -> &'static str {
+    match field {
+        MeasureProperty::FormatString => "format_string",
+        MeasureProperty::DisplayFolder => "display_folder",
+        MeasureProperty::Description => "description",
+        MeasureProperty::KpiTarget => "kpi_target",
+        MeasureProperty::KpiStatus => "kpi_status",
+        MeasureProperty::KpiTrend => "kpi_trend",
+    }
+}
+
 fn column_field_name(field: excel_diff::ModelColumnProperty) -> &'static str {
     match field {
         excel_diff::ModelColumnProperty::Hidden => "hidden",
@@ -188,7 +188,7 @@ fn parse_failure_missing_content_types_exit_2_and_prints_error_code() {
 }
 
 #[test]
-fn xlsb_is_unsupported_exit_2_and_has_convert_hint() {
+fn corrupt_xlsb_exit_2_with_invalid_binary_code() {
     let path = fixture_path("xlsb_stub.xlsb");
     let output = tabulensis_cmd()
         .args(["diff", &path, &path])
@@ -198,17 +198,17 @@ fn xlsb_is_unsupported_exit_2_and_has_convert_hint() {
     assert_eq!(
         output.status.code(),
         Some(2),
-        "xlsb should exit 2 (unsupported): stderr={}",
+        "corrupt xlsb should exit 2 (parse failure): stderr={}",
         String::from_utf8_lossy(&output.stderr)
     );
     let stderr = String::from_utf8_lossy(&output.stderr);
     assert!(
-        stderr.contains("EXDIFF_PKG_009"),
-        "stderr should contain error code for unsupported format: stderr={stderr}"
+        stderr.contains("EXDIFF_PKG_011"),
+        "stderr should contain error code for invalid binary records: stderr={stderr}"
     );
     assert!(
-        stderr.to_lowercase().contains("convert to .xlsx"),
-        "stderr should include a convert hint: stderr={stderr}"
+        stderr.contains("xl/workbook.bin"),
+        "stderr should name the failing part: stderr={stderr}"
     );
 }
 
@@ -465,6 +465,78 @@ fn database_flags_require_database_mode() {
     assert!(stderr.contains("--database"));
 }
 
+#[test]
+fn header_row_requires_header_keys() {
+    let output = tabulensis_cmd()
+        .args([
+            "diff",
+            "--database",
+            "--auto-keys",
+            "--header-row",
+            "2",
+            &fixture_path("db_equal_ordered_a.xlsx"),
+            &fixture_path("db_equal_ordered_b.xlsx"),
+        ])
+        .output()
+        .expect("failed to run tabulensis");
+
+    assert_eq!(
+        output.status.code(),
+        Some(2),
+        "header row without keys should exit 2"
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--header-row requires --keys"));
+}
+
+#[test]
+fn keys_file_conflicts_with_single_sheet_key_flags() {
+    let output = tabulensis_cmd()
+        .args([
+            "diff",
+            "--database",
+            "--keys-file",
+            "keys.json",
+            "--keys",
+            "A",
+            &fixture_path("db_equal_ordered_a.xlsx"),
+            &fixture_path("db_equal_ordered_b.xlsx"),
+        ])
+        .output()
+        .expect("failed to run tabulensis");
+
+    assert_eq!(
+        output.status.code(),
+        Some(2),
+        "keys file with --keys should exit 2"
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--keys-file cannot be combined"));
+}
+
+#[test]
+fn tables_mode_conflicts_with_database_flags() {
+    let output = tabulensis_cmd()
+        .args([
+            "diff",
+            "--tables",
+            "--database",
+            "--auto-keys",
+            &fixture_path("db_equal_ordered_a.xlsx"),
+            &fixture_path("db_equal_ordered_b.xlsx"),
+        ])
+        .output()
+        .expect("failed to run tabulensis");
+
+    assert_eq!(
+        output.status.code(),
+        Some(2),
+        "--tables with --database should exit 2"
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--tables"));
+}
+
 #[test]
 fn git_diff_produces_unified_style() {
     let output = tabulensis_cmd()
@@ -505,6 +577,57 @@ fn git_diff_conflicts_with_json_format() {
     );
 }
 
+#[test]
+fn impact_requires_text_or_json_output() {
+    let output = tabulensis_cmd()
+        .args([
+            "diff",
+            "--impact",
+            "--format",
+            "jsonl",
+            &fixture_path("equal_sheet_a.xlsx"),
+            &fixture_path("equal_sheet_b.xlsx"),
+        ])
+        .output()
+        .expect("failed to run tabulensis");
+
+    assert_eq!(
+        output.status.code(),
+        Some(2),
+        "--impact with jsonl format should exit 2"
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--impact"));
+}
+
+#[test]
+fn impact_with_json_output_writes_the_report() {
+    let output = tabulensis_cmd()
+        .args([
+            "diff",
+            "--impact",
+            "--format",
+            "json",
+            &fixture_path("equal_sheet_a.xlsx"),
+            &fixture_path("equal_sheet_b.xlsx"),
+        ])
+        .output()
+        .expect("failed to run tabulensis");
+
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "identical files should exit 0"
+    );
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("stdout should be JSON");
+    assert!(json["ops"].as_array().is_some_and(|ops| ops.is_empty()));
+    assert!(
+        json.get("formula_impact").is_none(),
+        "empty impact lists are omitted"
+    );
+}
+
 #[test]
 fn row_changes_detected() {
     let output = tabulensis_cmd()
@@ -893,9 +1016,28 @@ fn collect_string_ids(op: &excel_diff::DiffOp) -> Vec<excel_diff::StringId> {
             collect_snapshot(&mut ids, from);
             collect_snapshot(&mut ids, to);
         }
+        excel_diff::DiffOp::CellStyleChanged {
+            sheet, from, to, ..
+        } => {
+            ids.push(*sheet);
+            for style in [from, to] {
+                ids.extend(
+                    [
+                        style.number_format,
+                        style.font,
+                        style.fill,
+                        style.border,
+                        style.alignment,
+                        style.protection,
+                    ]
+                    .into_iter()
+                    .flatten(),
+                );
+            }
+        }
         excel_diff::DiffOp::VbaModuleAdded { name }
         | excel_diff::DiffOp::VbaModuleRemoved { name }
-        | excel_diff::DiffOp::VbaModuleChanged { name } => ids.push(*name),
+        | excel_diff::DiffOp::VbaModuleChanged { name, .. } => ids.push(*name),
         excel_diff::DiffOp::NamedRangeAdded { name }
         | excel_diff::DiffOp::NamedRangeRemoved { name } => ids.push(*name),
         excel_diff::DiffOp::NamedRangeChanged {
@@ -908,11 +1050,23 @@ fn collect_string_ids(op: &excel_diff::DiffOp) -> Vec<excel_diff::StringId> {
             ids.push(*new_ref);
         }
         excel_diff::DiffOp::ChartAdded { sheet, name }
-        | excel_diff::DiffOp::ChartRemoved { sheet, name }
-        | excel_diff::DiffOp::ChartChanged { sheet, name } => {
+        | excel_diff::DiffOp::ChartRemoved { sheet, name } => {
             ids.push(*sheet);
             ids.push(*name);
         }
+        excel_diff::DiffOp::ChartChanged {
+            sheet,
+            name,
+            changes,
+        } => {
+            ids.push(*sheet);
+            ids.push(*name);
+            for change in changes {
+                ids.extend(change.axis);
+                ids.extend(change.from);
+                ids.extend(change.to);
+            }
+        }
         excel_diff::DiffOp::QueryAdded { name }
         | excel_diff::DiffOp::QueryRemoved { name }
         | excel_diff::DiffOp::QueryDefinitionChanged { name, .. } => ids.push(*name),
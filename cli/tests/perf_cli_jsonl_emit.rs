@@ -50,6 +50,7 @@ fn perf_cli_jsonl_emit() {
                 from,
                 to,
                 formula_diff: FormulaDiffResult::Unknown,
+                formula_edits: Vec::new(),
             })
             .expect("emit should succeed");
         }
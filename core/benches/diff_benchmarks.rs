@@ -273,6 +273,7 @@ fn single_sheet_workbook(session: &mut DiffSession, name: &str, grid: Grid) -> W
             workbook_sheet_id: None,
             kind: SheetKind::Worksheet,
             grid,
+            layout: Default::default(),
         }],
         ..Default::default()
     }
@@ -70,6 +70,7 @@ fuzz_target!(|input: FuzzInput| {
             workbook_sheet_id: None,
             kind: SheetKind::Worksheet,
             grid: old_grid,
+            layout: Default::default(),
         }],
         ..Default::default()
     };
@@ -79,6 +80,7 @@ fuzz_target!(|input: FuzzInput| {
             workbook_sheet_id: None,
             kind: SheetKind::Worksheet,
             grid: new_grid,
+            layout: Default::default(),
         }],
         ..Default::default()
     };
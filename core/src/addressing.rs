@@ -80,6 +80,14 @@ pub fn address_to_index(a1: &str) -> Option<(u32, u32)> {
     Some((row - 1, col - 1))
 }
 
+/// Parse column letters (e.g. "AA") into a zero-based column index.
+pub(crate) fn col_letters_to_index(letters: &str) -> Option<u32> {
+    if letters.is_empty() || !letters.bytes().all(|b| b.is_ascii_alphabetic()) {
+        return None;
+    }
+    address_to_index(&format!("{letters}1")).map(|(_, col)| col)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
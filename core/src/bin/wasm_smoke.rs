@@ -16,6 +16,7 @@ fn make_workbook(session: &mut DiffSession, value: f64) -> Workbook {
             workbook_sheet_id: None,
             kind: SheetKind::Worksheet,
             grid,
+            layout: Default::default(),
         }],
         ..Default::default()
     }
@@ -0,0 +1,360 @@
+//! Chart part (`xl/charts/chart*.xml`) parsing.
+//!
+//! Charts are reached from a worksheet drawing through its relationships. The part is reduced to
+//! what users edit: the title, legend, each series' name/category/value references with the plot
+//! type holding it, and each axis' title, position and scaling. Caches and formatting are skipped.
+
+use crate::comments::push_text;
+use crate::grid_parser::{local_tag_name, xml_err, GridParseError};
+use crate::string_pool::{StringId, StringPool};
+use crate::styles::{attr, collect_attrs};
+use crate::workbook::{ChartAxis, ChartInfo, ChartSeries};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+const AXIS_TAGS: [&str; 4] = ["catAx", "valAx", "dateAx", "serAx"];
+
+#[derive(Default)]
+struct TitleText {
+    text: String,
+    formula: Option<String>,
+}
+
+impl TitleText {
+    fn finish(self) -> Option<String> {
+        match self.formula {
+            Some(formula) => Some(formula),
+            None => Some(self.text).filter(|text| !text.is_empty()),
+        }
+    }
+}
+
+#[derive(Default)]
+struct SeriesText {
+    index: Option<u32>,
+    chart_type: String,
+    name: Option<String>,
+    categories: Option<String>,
+    values: Option<String>,
+}
+
+#[derive(Default)]
+struct AxisText {
+    kind: String,
+    position: Option<String>,
+    title: Option<String>,
+    min: Option<String>,
+    max: Option<String>,
+    log_base: Option<String>,
+    orientation: Option<String>,
+    deleted: bool,
+}
+
+fn is_true(value: Option<&str>) -> bool {
+    // `c:delete` without `val` means true.
+    matches!(value.map(str::trim), None | Some("1") | Some("true"))
+}
+
+#[derive(Default)]
+struct ChartParser {
+    /// Local names of the open elements.
+    stack: Vec<String>,
+    /// Text of the innermost `f`, `t` or `v` element.
+    text: String,
+    chart_type: Option<String>,
+    data_range: Option<String>,
+    title: Option<String>,
+    legend: Option<String>,
+    plot_type: Option<String>,
+    current_title: Option<TitleText>,
+    current_series: Option<SeriesText>,
+    current_axis: Option<AxisText>,
+    series: Vec<SeriesText>,
+    axes: Vec<AxisText>,
+}
+
+impl ChartParser {
+    fn parent(&self) -> &str {
+        self.stack.last().map(String::as_str).unwrap_or_default()
+    }
+
+    /// The child of the innermost open `ser` element that contains the current element.
+    fn series_child(&self) -> Option<&str> {
+        let ser = self.stack.iter().rposition(|tag| tag == "ser")?;
+        self.stack.get(ser + 1).map(String::as_str)
+    }
+
+    fn open(
+        &mut self,
+        reader: &Reader<&[u8]>,
+        xml: &[u8],
+        e: &BytesStart,
+        tag: &str,
+    ) -> Result<(), GridParseError> {
+        let parent = self.parent().to_string();
+        let parent = parent.as_str();
+        let val = || -> Result<Option<String>, GridParseError> {
+            let attrs = collect_attrs(reader, xml, e)?;
+            Ok(attr(&attrs, "val").map(str::to_string))
+        };
+        match tag {
+            "title" => self.current_title = Some(TitleText::default()),
+            "p" => {
+                if let Some(title) = self.current_title.as_mut()
+                    && !title.text.is_empty()
+                {
+                    title.text.push('\n');
+                }
+            }
+            "legend" if parent == "chart" => self.legend = Some("r".to_string()),
+            "legendPos" if parent == "legend" => {
+                if let Some(pos) = val()? {
+                    self.legend = Some(pos);
+                }
+            }
+            "ser" => {
+                if let Some(plot_type) = self.plot_type.as_ref()
+                    && parent == plot_type
+                {
+                    self.current_series = Some(SeriesText {
+                        chart_type: plot_type.clone(),
+                        ..SeriesText::default()
+                    });
+                }
+            }
+            "idx" if parent == "ser" => {
+                if let Some(series) = self.current_series.as_mut() {
+                    series.index = val()?.and_then(|v| v.trim().parse().ok());
+                }
+            }
+            tag if parent == "plotArea" && AXIS_TAGS.contains(&tag) => {
+                self.current_axis = Some(AxisText {
+                    kind: tag.to_string(),
+                    ..AxisText::default()
+                });
+            }
+            tag if parent == "plotArea" && tag.ends_with("Chart") => {
+                self.plot_type = Some(tag.to_string());
+                self.chart_type.get_or_insert_with(|| tag.to_string());
+            }
+            _ => {
+                if self.current_title.is_some() {
+                    return Ok(());
+                }
+                let Some(axis) = self.current_axis.as_mut() else {
+                    return Ok(());
+                };
+                let on_axis = AXIS_TAGS.contains(&parent);
+                match (parent, tag) {
+                    (_, "axPos") if on_axis => axis.position = val()?,
+                    (_, "delete") if on_axis => axis.deleted = is_true(val()?.as_deref()),
+                    ("scaling", "min") => axis.min = val()?,
+                    ("scaling", "max") => axis.max = val()?,
+                    ("scaling", "logBase") => axis.log_base = val()?,
+                    ("scaling", "orientation") => axis.orientation = val()?,
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Close `tag`, which has already been popped off the stack.
+    fn close(&mut self, tag: &str) {
+        let text = std::mem::take(&mut self.text);
+        let parent = self.parent().to_string();
+        let parent = parent.as_str();
+        match tag {
+            "f" => {
+                let formula = text.trim().to_string();
+                if formula.is_empty() {
+                    return;
+                }
+                self.data_range.get_or_insert_with(|| formula.clone());
+                if let Some(title) = self.current_title.as_mut() {
+                    title.formula = Some(formula);
+                    return;
+                }
+                let child = self.series_child().map(str::to_string);
+                if let Some(series) = self.current_series.as_mut() {
+                    let slot = match child.as_deref() {
+                        Some("tx") => &mut series.name,
+                        Some("cat" | "xVal") => &mut series.categories,
+                        Some("val" | "yVal") => &mut series.values,
+                        _ => return,
+                    };
+                    *slot = Some(formula);
+                }
+            }
+            "t" => {
+                if let Some(title) = self.current_title.as_mut() {
+                    title.text.push_str(&text);
+                }
+            }
+            // A literal series name: `ser/tx/v`. Cached values sit deeper, under `strRef`.
+            "v" if parent == "tx" && self.stack.len() >= 2 => {
+                if self.stack[self.stack.len() - 2] == "ser"
+                    && let Some(series) = self.current_series.as_mut()
+                    && series.name.is_none()
+                {
+                    series.name = Some(text.trim().to_string());
+                }
+            }
+            "title" => {
+                let is_chart_title = parent == "chart";
+                let Some(finished) = self.current_title.take().and_then(TitleText::finish) else {
+                    return;
+                };
+                if let Some(axis) = self.current_axis.as_mut() {
+                    axis.title = Some(finished);
+                } else if is_chart_title {
+                    self.title = Some(finished);
+                }
+            }
+            "ser" => self.series.extend(self.current_series.take()),
+            tag if parent == "plotArea" && AXIS_TAGS.contains(&tag) => {
+                self.axes.extend(self.current_axis.take());
+            }
+            tag if parent == "plotArea" && tag.ends_with("Chart") => self.plot_type = None,
+            _ => {}
+        }
+    }
+
+    fn finish(self, pool: &mut StringPool) -> ChartInfo {
+        fn intern(pool: &mut StringPool, value: Option<String>) -> Option<StringId> {
+            value.map(|value| pool.intern(&value))
+        }
+
+        let series = self
+            .series
+            .into_iter()
+            .enumerate()
+            .map(|(position, series)| ChartSeries {
+                index: series.index.unwrap_or(position as u32),
+                chart_type: pool.intern(&series.chart_type),
+                name: intern(pool, series.name),
+                categories: intern(pool, series.categories),
+                values: intern(pool, series.values),
+            })
+            .collect();
+        let axes = self
+            .axes
+            .into_iter()
+            .map(|axis| ChartAxis {
+                kind: pool.intern(&axis.kind),
+                position: intern(pool, axis.position),
+                title: intern(pool, axis.title),
+                min: intern(pool, axis.min),
+                max: intern(pool, axis.max),
+                log_base: intern(pool, axis.log_base),
+                orientation: intern(pool, axis.orientation),
+                deleted: axis.deleted,
+            })
+            .collect();
+        ChartInfo {
+            name: pool.intern(""),
+            chart_type: pool.intern(self.chart_type.as_deref().unwrap_or("unknown")),
+            data_range: intern(pool, self.data_range),
+            title: intern(pool, self.title),
+            legend: intern(pool, self.legend),
+            series,
+            axes,
+        }
+    }
+}
+
+/// Parse one chart part. `name` is left empty for the caller to fill in.
+pub(crate) fn parse_chart_xml(
+    xml: &[u8],
+    pool: &mut StringPool,
+) -> Result<ChartInfo, GridParseError> {
+    let mut reader = Reader::from_reader(xml);
+    reader.config_mut().trim_text(false);
+    let mut buf = Vec::new();
+    let mut parser = ChartParser::default();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) => {
+                let tag = String::from_utf8_lossy(local_tag_name(e.name().as_ref())).into_owned();
+                parser.open(&reader, xml, &e, &tag)?;
+                parser.stack.push(tag);
+                parser.text.clear();
+            }
+            Ok(Event::Empty(e)) => {
+                let tag = String::from_utf8_lossy(local_tag_name(e.name().as_ref())).into_owned();
+                parser.open(&reader, xml, &e, &tag)?;
+                parser.text.clear();
+                parser.close(&tag);
+            }
+            Ok(Event::Text(e)) => {
+                if matches!(parser.parent(), "f" | "t" | "v") {
+                    push_text(&reader, xml, e.as_ref(), &mut parser.text)?;
+                }
+            }
+            Ok(Event::CData(e)) => parser.text.push_str(&String::from_utf8_lossy(e.as_ref())),
+            Ok(Event::End(_)) => {
+                if let Some(tag) = parser.stack.pop() {
+                    parser.close(&tag);
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(xml_err(&reader, xml, e)),
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(parser.finish(pool))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_title_series_axes_and_skips_caches() {
+        let xml = concat!(
+            r#"<c:chartSpace xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart" "#,
+            r#"xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main"><c:chart>"#,
+            r#"<c:title><c:tx><c:strRef><c:f>Data!$A$1</c:f><c:strCache><c:pt idx="0">"#,
+            r#"<c:v>Q1 &amp; Q2</c:v></c:pt></c:strCache></c:strRef></c:tx></c:title>"#,
+            r#"<c:plotArea><c:scatterChart><c:ser><c:idx val="3"/><c:tx><c:v>Actual</c:v></c:tx>"#,
+            r#"<c:xVal><c:numRef><c:f>Data!$A$2:$A$9</c:f><c:numCache><c:pt idx="0"><c:v>1</c:v>"#,
+            r#"</c:pt></c:numCache></c:numRef></c:xVal><c:yVal><c:numRef><c:f>Data!$B$2:$B$9"#,
+            r#"</c:f></c:numRef></c:yVal></c:ser><c:axId val="1"/><c:axId val="2"/>"#,
+            r#"</c:scatterChart><c:valAx><c:axId val="1"/><c:scaling><c:logBase val="10"/>"#,
+            r#"<c:orientation val="maxMin"/><c:min val="1"/></c:scaling><c:delete/>"#,
+            r#"<c:axPos val="b"/><c:title><c:tx><c:rich><a:p><a:r><a:t>Units </a:t></a:r>"#,
+            r#"<a:r><a:t>sold</a:t></a:r></a:p></c:rich></c:tx></c:title></c:valAx>"#,
+            r#"</c:plotArea></c:chart></c:chartSpace>"#,
+        );
+        let mut pool = StringPool::new();
+        let info = parse_chart_xml(xml.as_bytes(), &mut pool).expect("parse chart");
+        let text = |id: Option<StringId>| id.map(|id| pool.resolve(id));
+
+        assert_eq!(pool.resolve(info.chart_type), "scatterChart");
+        assert_eq!(text(info.title), Some("Data!$A$1"));
+        assert_eq!(text(info.data_range), Some("Data!$A$1"));
+        assert_eq!(info.legend, None);
+
+        assert_eq!(info.series.len(), 1);
+        let series = &info.series[0];
+        assert_eq!(series.index, 3);
+        assert_eq!(pool.resolve(series.chart_type), "scatterChart");
+        assert_eq!(text(series.name), Some("Actual"));
+        assert_eq!(text(series.categories), Some("Data!$A$2:$A$9"));
+        assert_eq!(text(series.values), Some("Data!$B$2:$B$9"));
+
+        assert_eq!(info.axes.len(), 1);
+        let axis = &info.axes[0];
+        assert_eq!(pool.resolve(axis.kind), "valAx");
+        assert_eq!(text(axis.position), Some("b"));
+        assert_eq!(text(axis.title), Some("Units sold"));
+        assert_eq!(text(axis.log_base), Some("10"));
+        assert_eq!(text(axis.orientation), Some("maxMin"));
+        assert_eq!(text(axis.min), Some("1"));
+        assert_eq!(axis.max, None);
+        assert!(axis.deleted);
+    }
+}
@@ -0,0 +1,305 @@
+//! Cell comment parsing.
+//!
+//! Legacy notes live in `xl/comments*.xml` and threaded comments in
+//! `xl/threadedComments/*.xml`, both attached to a worksheet through its relationships.
+//! Threaded comment authors are resolved through the workbook-level `xl/persons/person.xml`.
+
+use crate::addressing::address_to_index;
+use crate::grid_parser::{local_tag_name, xml_err, GridParseError};
+use crate::string_pool::StringPool;
+use crate::styles::{attr, collect_attrs};
+use crate::workbook::{CellComment, CommentKind, CommentReply};
+use quick_xml::escape::{resolve_xml_entity, unescape_with};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::{BTreeMap, HashMap};
+
+pub(crate) const LEGACY_COMMENTS_REL: &str = "/comments";
+pub(crate) const THREADED_COMMENTS_REL: &str = "/threadedComment";
+pub(crate) const PERSONS_REL: &str = "/person";
+
+fn parse_ref(raw: &str) -> Result<(u32, u32), GridParseError> {
+    // Threaded comments may anchor to a range; the top-left cell is the anchor.
+    let first = raw.split(':').next().unwrap_or(raw).trim();
+    address_to_index(first).ok_or_else(|| GridParseError::InvalidAddress(raw.to_string()))
+}
+
+pub(crate) fn push_text(
+    reader: &Reader<&[u8]>,
+    xml: &[u8],
+    raw: &[u8],
+    out: &mut String,
+) -> Result<(), GridParseError> {
+    let text = String::from_utf8_lossy(raw);
+    let text = unescape_with(&text, resolve_xml_entity)
+        .map_err(|e| xml_err(reader, xml, quick_xml::Error::from(e)))?;
+    out.push_str(&text);
+    Ok(())
+}
+
+/// Parse `xl/persons/person.xml` into a map of person id to display name.
+pub(crate) fn parse_persons(xml: &[u8]) -> Result<HashMap<String, String>, GridParseError> {
+    let mut reader = Reader::from_reader(xml);
+    reader.config_mut().trim_text(true);
+    let mut buf = Vec::new();
+    let mut persons = HashMap::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e))
+                if local_tag_name(e.name().as_ref()) == b"person" =>
+            {
+                let attrs = collect_attrs(&reader, xml, &e)?;
+                if let (Some(id), Some(name)) = (attr(&attrs, "id"), attr(&attrs, "displayName")) {
+                    persons.insert(id.to_string(), name.to_string());
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(xml_err(&reader, xml, e)),
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(persons)
+}
+
+/// Parse a legacy comments part into notes keyed by anchor cell.
+///
+/// Rich-text runs are flattened; phonetic runs (`rPh`) are skipped.
+pub(crate) fn parse_legacy_comments(
+    xml: &[u8],
+    pool: &mut StringPool,
+) -> Result<BTreeMap<(u32, u32), CellComment>, GridParseError> {
+    let mut reader = Reader::from_reader(xml);
+    reader.config_mut().trim_text(false);
+    let mut buf = Vec::new();
+
+    let mut authors: Vec<String> = Vec::new();
+    let mut comments = BTreeMap::new();
+    let mut in_author = false;
+    let mut author = String::new();
+    let mut current: Option<((u32, u32), Option<usize>)> = None;
+    let mut text = String::new();
+    let mut in_text = false;
+    let mut in_t = false;
+    let mut phonetic_depth = 0usize;
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) => match local_tag_name(e.name().as_ref()) {
+                b"author" => {
+                    in_author = true;
+                    author.clear();
+                }
+                b"comment" => {
+                    let attrs = collect_attrs(&reader, xml, &e)?;
+                    let Some(raw) = attr(&attrs, "ref") else {
+                        continue;
+                    };
+                    let addr = parse_ref(raw)?;
+                    let author_id = attr(&attrs, "authorId").and_then(|v| v.trim().parse().ok());
+                    current = Some((addr, author_id));
+                    text.clear();
+                }
+                b"text" if current.is_some() => in_text = true,
+                b"rPh" => phonetic_depth += 1,
+                b"t" if in_text && phonetic_depth == 0 => in_t = true,
+                _ => {}
+            },
+            Ok(Event::Empty(e)) if local_tag_name(e.name().as_ref()) == b"author" => {
+                authors.push(String::new());
+            }
+            Ok(Event::Text(e)) => {
+                if in_author {
+                    push_text(&reader, xml, e.as_ref(), &mut author)?;
+                } else if in_t {
+                    push_text(&reader, xml, e.as_ref(), &mut text)?;
+                }
+            }
+            Ok(Event::CData(e)) if in_t => text.push_str(&String::from_utf8_lossy(e.as_ref())),
+            Ok(Event::End(e)) => match local_tag_name(e.name().as_ref()) {
+                b"author" => {
+                    in_author = false;
+                    authors.push(std::mem::take(&mut author));
+                }
+                b"t" => in_t = false,
+                b"rPh" => phonetic_depth = phonetic_depth.saturating_sub(1),
+                b"text" => in_text = false,
+                b"comment" => {
+                    if let Some((addr, author_id)) = current.take() {
+                        let author = author_id
+                            .and_then(|idx| authors.get(idx))
+                            .filter(|name| !name.is_empty())
+                            .map(|name| pool.intern(name));
+                        comments.insert(
+                            addr,
+                            CellComment {
+                                kind: CommentKind::Note,
+                                author,
+                                text: pool.intern(&text),
+                                resolved: false,
+                                replies: Vec::new(),
+                            },
+                        );
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(xml_err(&reader, xml, e)),
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(comments)
+}
+
+/// Parse a threaded comments part into conversations keyed by anchor cell.
+///
+/// Top-level comments start a conversation; entries with a `parentId` become its replies in
+/// document order. Authors are resolved through `persons` (person id to display name).
+pub(crate) fn parse_threaded_comments(
+    xml: &[u8],
+    persons: &HashMap<String, String>,
+    pool: &mut StringPool,
+) -> Result<BTreeMap<(u32, u32), CellComment>, GridParseError> {
+    struct Entry {
+        addr: (u32, u32),
+        id: Option<String>,
+        parent: Option<String>,
+        person: Option<String>,
+        done: bool,
+        text: String,
+    }
+
+    let mut reader = Reader::from_reader(xml);
+    reader.config_mut().trim_text(false);
+    let mut buf = Vec::new();
+
+    let mut entries: Vec<Entry> = Vec::new();
+    let mut current: Option<Entry> = None;
+    let mut in_text = false;
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) => match local_tag_name(e.name().as_ref()) {
+                b"threadedComment" => {
+                    let attrs = collect_attrs(&reader, xml, &e)?;
+                    let Some(raw) = attr(&attrs, "ref") else {
+                        continue;
+                    };
+                    current = Some(Entry {
+                        addr: parse_ref(raw)?,
+                        id: attr(&attrs, "id").map(str::to_string),
+                        parent: attr(&attrs, "parentId").map(str::to_string),
+                        person: attr(&attrs, "personId").map(str::to_string),
+                        done: matches!(attr(&attrs, "done"), Some("1") | Some("true")),
+                        text: String::new(),
+                    });
+                }
+                b"text" if current.is_some() => in_text = true,
+                _ => {}
+            },
+            Ok(Event::Text(e)) if in_text => {
+                if let Some(entry) = current.as_mut() {
+                    push_text(&reader, xml, e.as_ref(), &mut entry.text)?;
+                }
+            }
+            Ok(Event::End(e)) => match local_tag_name(e.name().as_ref()) {
+                b"text" => in_text = false,
+                b"threadedComment" => entries.extend(current.take()),
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(xml_err(&reader, xml, e)),
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    let author_of = |person: &Option<String>, pool: &mut StringPool| {
+        person
+            .as_ref()
+            .and_then(|id| persons.get(id))
+            .map(|name| pool.intern(name))
+    };
+
+    let mut comments: BTreeMap<(u32, u32), CellComment> = BTreeMap::new();
+    let mut root_addr: HashMap<String, (u32, u32)> = HashMap::new();
+    let (roots, replies): (Vec<Entry>, Vec<Entry>) = entries
+        .into_iter()
+        .partition(|entry| entry.parent.is_none());
+    for entry in roots {
+        if let Some(id) = entry.id.clone() {
+            root_addr.insert(id, entry.addr);
+        }
+        let author = author_of(&entry.person, pool);
+        comments.insert(
+            entry.addr,
+            CellComment {
+                kind: CommentKind::Threaded,
+                author,
+                text: pool.intern(&entry.text),
+                resolved: entry.done,
+                replies: Vec::new(),
+            },
+        );
+    }
+    for entry in replies {
+        let addr = entry
+            .parent
+            .as_ref()
+            .and_then(|parent| root_addr.get(parent))
+            .copied()
+            .unwrap_or(entry.addr);
+        let author = author_of(&entry.person, pool);
+        if let Some(comment) = comments.get_mut(&addr) {
+            comment.replies.push(CommentReply {
+                author,
+                text: pool.intern(&entry.text),
+            });
+        }
+    }
+
+    Ok(comments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_notes_flatten_rich_text_and_resolve_authors() {
+        let xml = br#"<comments xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><authors><author>Ann</author><author>Bo &amp; Co</author></authors><commentList><comment ref="B2" authorId="1"><text><r><rPr><b/></rPr><t>Bo:</t></r><r><t xml:space="preserve"> check &quot;total&quot;</t></r></text></comment><comment ref="A1" authorId="0"><text><t>plain</t></text></comment></commentList></comments>"#;
+        let mut pool = StringPool::new();
+        let comments = parse_legacy_comments(xml, &mut pool).expect("parse notes");
+
+        assert_eq!(comments.len(), 2);
+        let note = &comments[&(1, 1)];
+        assert_eq!(note.kind, CommentKind::Note);
+        assert_eq!(pool.resolve(note.text), "Bo: check \"total\"");
+        assert_eq!(note.author.map(|id| pool.resolve(id)), Some("Bo & Co"));
+        assert_eq!(pool.resolve(comments[&(0, 0)].text), "plain");
+    }
+
+    #[test]
+    fn threaded_comments_group_replies_and_resolved_state() {
+        let persons = parse_persons(br#"<personList><person displayName="Ann" id="{P1}"/><person displayName="Bo" id="{P2}"/></personList>"#).expect("parse persons");
+        let xml = br#"<ThreadedComments><threadedComment ref="C3" personId="{P1}" id="{T1}" done="1"><text>Why?</text></threadedComment><threadedComment ref="C3" personId="{P2}" id="{T2}" parentId="{T1}"><text>Fixed</text></threadedComment></ThreadedComments>"#;
+        let mut pool = StringPool::new();
+        let comments = parse_threaded_comments(xml, &persons, &mut pool).expect("parse threads");
+
+        let thread = &comments[&(2, 2)];
+        assert_eq!(thread.kind, CommentKind::Threaded);
+        assert!(thread.resolved);
+        assert_eq!(thread.author.map(|id| pool.resolve(id)), Some("Ann"));
+        assert_eq!(thread.replies.len(), 1);
+        assert_eq!(pool.resolve(thread.replies[0].text), "Fixed");
+        assert_eq!(
+            thread.replies[0].author.map(|id| pool.resolve(id)),
+            Some("Bo")
+        );
+    }
+}
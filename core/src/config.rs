@@ -131,6 +131,9 @@ pub struct SemanticConfig {
     pub enable_m_semantic_diff: bool,
     pub enable_formula_semantic_diff: bool,
     pub enable_dax_semantic_diff: bool,
+    /// Emit `CellStyleChanged` ops for number format, font, fill, border, alignment and
+    /// protection changes. Turn off for pure data diffs.
+    pub enable_cell_style_diff: bool,
     /// Policy for handling formatting-only M changes when semantic diff is enabled.
     pub semantic_noise_policy: SemanticNoisePolicy,
     /// When true, emits CellEdited ops even when values are unchanged (diagnostic);
@@ -151,6 +154,7 @@ impl Default for SemanticConfig {
             enable_m_semantic_diff: true,
             enable_formula_semantic_diff: false,
             enable_dax_semantic_diff: false,
+            enable_cell_style_diff: true,
             semantic_noise_policy: SemanticNoisePolicy::ReportFormattingOnly,
             include_unchanged_cells: false,
             dense_row_replace_ratio: 0.90,
@@ -193,6 +197,74 @@ impl Default for HardeningConfig {
     }
 }
 
+/// Value-equivalence rules applied when deciding whether two cells hold the same value.
+///
+/// The defaults compare values exactly (numbers to ~15 significant digits, text by content).
+/// Formulas are always compared exactly.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ValueRules {
+    /// Numbers within this absolute distance of each other are equal.
+    pub numeric_abs_tolerance: f64,
+    /// Numbers within this fraction of the larger magnitude are equal.
+    pub numeric_rel_tolerance: f64,
+    /// Compare text without regard to case.
+    pub ignore_text_case: bool,
+    /// Ignore leading and trailing whitespace in text.
+    pub trim_text_whitespace: bool,
+    /// Treat blank cells and empty-string text as the same value.
+    pub blank_equals_empty_text: bool,
+}
+
+impl Default for ValueRules {
+    fn default() -> Self {
+        Self {
+            numeric_abs_tolerance: 0.0,
+            numeric_rel_tolerance: 0.0,
+            ignore_text_case: false,
+            trim_text_whitespace: false,
+            blank_equals_empty_text: false,
+        }
+    }
+}
+
+impl ValueRules {
+    /// True when these rules add nothing over exact comparison.
+    pub fn is_exact(&self) -> bool {
+        *self == ValueRules::default()
+    }
+}
+
+/// Replaces the default [`ValueRules`] for one sheet, some columns, or columns of one sheet.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ValueRuleOverride {
+    /// Sheet name (case-insensitive). `None` matches every sheet.
+    pub sheet: Option<String>,
+    /// Column letters (e.g. `["C", "AA"]`). Empty matches every column.
+    pub columns: Vec<String>,
+    pub rules: ValueRules,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ComparisonConfig {
+    /// Rules used wherever no override matches.
+    pub value_rules: ValueRules,
+    /// Per-sheet or per-column rules; when several match a cell, the last one wins.
+    pub value_rule_overrides: Vec<ValueRuleOverride>,
+}
+
+impl ComparisonConfig {
+    pub fn is_exact(&self) -> bool {
+        self.value_rules.is_exact()
+            && self
+                .value_rule_overrides
+                .iter()
+                .all(|rule| rule.rules.is_exact())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct DiffConfig {
@@ -206,6 +278,8 @@ pub struct DiffConfig {
     pub semantic: SemanticConfig,
     #[serde(flatten)]
     pub hardening: HardeningConfig,
+    #[serde(flatten)]
+    pub comparison: ComparisonConfig,
 }
 
 impl Default for DiffConfig {
@@ -216,6 +290,7 @@ impl Default for DiffConfig {
             moves: MoveConfig::default(),
             semantic: SemanticConfig::default(),
             hardening: HardeningConfig::default(),
+            comparison: ComparisonConfig::default(),
         }
     }
 }
@@ -335,6 +410,18 @@ impl DiffConfig {
             });
         }
 
+        validate_value_rules(&self.comparison.value_rules)?;
+        for rule in &self.comparison.value_rule_overrides {
+            validate_value_rules(&rule.rules)?;
+            for column in &rule.columns {
+                if crate::addressing::col_letters_to_index(column.trim()).is_none() {
+                    return Err(ConfigError::InvalidOverrideColumn {
+                        value: column.clone(),
+                    });
+                }
+            }
+        }
+
         Ok(())
     }
 }
@@ -351,6 +438,22 @@ pub enum ConfigError {
     InvalidDenseRowReplaceRatio { value: f64 },
     #[error("bailout_similarity_threshold must be in [0.0, 1.0] and finite (got {value})")]
     InvalidBailoutSimilarity { value: f64 },
+    #[error("{field} must be non-negative and finite (got {value})")]
+    InvalidTolerance { field: &'static str, value: f64 },
+    #[error("value rule override column must be column letters like \"C\" (got {value:?})")]
+    InvalidOverrideColumn { value: String },
+}
+
+fn validate_value_rules(rules: &ValueRules) -> Result<(), ConfigError> {
+    for (field, value) in [
+        ("numeric_abs_tolerance", rules.numeric_abs_tolerance),
+        ("numeric_rel_tolerance", rules.numeric_rel_tolerance),
+    ] {
+        if !value.is_finite() || value < 0.0 {
+            return Err(ConfigError::InvalidTolerance { field, value });
+        }
+    }
+    Ok(())
 }
 
 fn ensure_non_zero_u32(value: u32, field: &'static str) -> Result<(), ConfigError> {
@@ -464,6 +567,11 @@ impl DiffConfigBuilder {
         self
     }
 
+    pub fn enable_cell_style_diff(mut self, value: bool) -> Self {
+        self.inner.semantic.enable_cell_style_diff = value;
+        self
+    }
+
     pub fn semantic_noise_policy(mut self, value: SemanticNoisePolicy) -> Self {
         self.inner.semantic.semantic_noise_policy = value;
         self
@@ -489,6 +597,16 @@ impl DiffConfigBuilder {
         self
     }
 
+    pub fn value_rules(mut self, value: ValueRules) -> Self {
+        self.inner.comparison.value_rules = value;
+        self
+    }
+
+    pub fn value_rule_override(mut self, value: ValueRuleOverride) -> Self {
+        self.inner.comparison.value_rule_overrides.push(value);
+        self
+    }
+
     pub fn max_context_rows(mut self, value: u32) -> Self {
         self.inner.preflight.max_context_rows = value;
         self
@@ -627,6 +745,7 @@ mod tests {
         assert!(cfg.semantic.enable_m_semantic_diff);
         assert!(!cfg.semantic.enable_formula_semantic_diff);
         assert!(!cfg.semantic.enable_dax_semantic_diff);
+        assert!(cfg.semantic.enable_cell_style_diff);
         assert!(matches!(
             cfg.semantic.semantic_noise_policy,
             SemanticNoisePolicy::ReportFormattingOnly
@@ -761,6 +880,58 @@ mod tests {
         assert!(matches!(err, ConfigError::InvalidBailoutSimilarity { .. }));
     }
 
+    #[test]
+    fn builder_rejects_invalid_value_rules() {
+        let err = DiffConfig::builder()
+            .value_rules(ValueRules {
+                numeric_abs_tolerance: -1.0,
+                ..ValueRules::default()
+            })
+            .build()
+            .expect_err("builder should reject negative tolerance");
+        assert!(matches!(
+            err,
+            ConfigError::InvalidTolerance {
+                field: "numeric_abs_tolerance",
+                ..
+            }
+        ));
+
+        let err = DiffConfig::builder()
+            .value_rule_override(ValueRuleOverride {
+                sheet: None,
+                columns: vec!["B2".to_string()],
+                rules: ValueRules::default(),
+            })
+            .build()
+            .expect_err("builder should reject non-letter override columns");
+        assert!(matches!(err, ConfigError::InvalidOverrideColumn { .. }));
+    }
+
+    #[test]
+    fn value_rules_round_trip_through_flattened_config() {
+        let cfg = DiffConfig::builder()
+            .value_rules(ValueRules {
+                numeric_rel_tolerance: 1e-9,
+                ignore_text_case: true,
+                ..ValueRules::default()
+            })
+            .value_rule_override(ValueRuleOverride {
+                sheet: Some("Inputs".to_string()),
+                columns: vec!["C".to_string()],
+                rules: ValueRules::default(),
+            })
+            .build()
+            .expect("valid config should build");
+        assert!(!cfg.comparison.is_exact());
+        assert!(DiffConfig::default().comparison.is_exact());
+
+        let json = serde_json::to_value(&cfg).expect("serialize config");
+        assert_eq!(json["value_rules"]["ignore_text_case"], true);
+        let back: DiffConfig = serde_json::from_value(json).expect("deserialize config");
+        assert_eq!(back, cfg);
+    }
+
     #[test]
     fn preflight_config_builder_setters_work() {
         let cfg = DiffConfig::builder()
@@ -774,6 +945,7 @@ mod tests {
             .dense_row_replace_min_cols(16)
             .dense_rect_replace_min_rows(2)
             .semantic_noise_policy(SemanticNoisePolicy::SuppressFormattingOnly)
+            .enable_cell_style_diff(false)
             .build()
             .expect("valid config should build");
 
@@ -790,5 +962,6 @@ mod tests {
             cfg.semantic.semantic_noise_policy,
             SemanticNoisePolicy::SuppressFormattingOnly
         ));
+        assert!(!cfg.semantic.enable_cell_style_diff);
     }
 }
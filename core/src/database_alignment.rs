@@ -1,3 +1,4 @@
+use crate::database_keys::KeyNormalization;
 use crate::hashing::normalize_float_for_hash;
 use crate::string_pool::{StringId, StringPool};
 use crate::workbook::{CellValue, Grid};
@@ -6,11 +7,28 @@ use std::collections::{HashMap, HashSet};
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct KeyColumnSpec {
     pub columns: Vec<u32>,
+    /// Normalisations per column; missing entries mean none.
+    pub normalize: Vec<Vec<KeyNormalization>>,
 }
 
 impl KeyColumnSpec {
     pub fn new(columns: Vec<u32>) -> KeyColumnSpec {
-        KeyColumnSpec { columns }
+        KeyColumnSpec {
+            columns,
+            normalize: Vec::new(),
+        }
+    }
+
+    pub fn normalized(columns: Vec<u32>, normalize: Vec<Vec<KeyNormalization>>) -> KeyColumnSpec {
+        KeyColumnSpec { columns, normalize }
+    }
+
+    /// The same normalisations applied to different columns.
+    pub fn with_columns(&self, columns: Vec<u32>) -> KeyColumnSpec {
+        KeyColumnSpec {
+            columns,
+            normalize: self.normalize.clone(),
+        }
     }
 
     #[cfg(test)]
@@ -26,6 +44,8 @@ pub(crate) enum KeyValueRepr {
     Text(StringId),
     Bool(bool),
     Error(StringId),
+    /// Text (or number, with [`KeyNormalization::NumberAsText`]) after normalisation.
+    Normalized(String),
 }
 
 impl KeyValueRepr {
@@ -40,14 +60,26 @@ impl KeyValueRepr {
         }
     }
 
-    fn to_cell_value(&self) -> Option<CellValue> {
-        match self {
-            KeyValueRepr::None => None,
-            KeyValueRepr::Number(bits) => Some(CellValue::Number(f64::from_bits(*bits))),
-            KeyValueRepr::Text(id) => Some(CellValue::Text(*id)),
-            KeyValueRepr::Bool(b) => Some(CellValue::Bool(*b)),
-            KeyValueRepr::Error(id) => Some(CellValue::Error(*id)),
+    fn normalized(
+        value: Option<&CellValue>,
+        normalize: &[KeyNormalization],
+        pool: &StringPool,
+    ) -> KeyValueRepr {
+        let mut text = match value {
+            _ if normalize.is_empty() => return KeyValueRepr::from_cell_value(value),
+            Some(CellValue::Text(id)) => pool.resolve(*id).to_string(),
+            Some(CellValue::Number(n)) if normalize.contains(&KeyNormalization::NumberAsText) => {
+                n.to_string()
+            }
+            _ => return KeyValueRepr::from_cell_value(value),
+        };
+        if normalize.contains(&KeyNormalization::Trim) {
+            text = text.trim().to_string();
         }
+        if normalize.contains(&KeyNormalization::CaseFold) {
+            text = text.to_lowercase();
+        }
+        KeyValueRepr::Normalized(text)
     }
 }
 
@@ -60,13 +92,6 @@ impl KeyValue {
     fn new(components: Vec<KeyValueRepr>) -> KeyValue {
         KeyValue { components }
     }
-
-    pub(crate) fn as_cell_values(&self) -> Vec<Option<CellValue>> {
-        self.components
-            .iter()
-            .map(KeyValueRepr::to_cell_value)
-            .collect()
-    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -85,15 +110,20 @@ pub(crate) struct KeyedAlignment {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct DuplicateKeyCluster {
-    pub key: KeyValue,
     pub left_rows: Vec<u32>,
     pub right_rows: Vec<u32>,
 }
 
-pub(crate) fn diff_table_by_key(old: &Grid, new: &Grid, key_columns: &[u32]) -> KeyedAlignment {
-    let spec = KeyColumnSpec::new(key_columns.to_vec());
-    let (left_rows, left_lookup) = build_keyed_rows(old, &spec);
-    let (right_rows, right_lookup) = build_keyed_rows(new, &spec);
+/// Match rows of `old` and `new` by key; `old_keys` and `new_keys` list the same parts.
+pub(crate) fn diff_table_by_key(
+    old: &Grid,
+    new: &Grid,
+    old_keys: &KeyColumnSpec,
+    new_keys: &KeyColumnSpec,
+    pool: &StringPool,
+) -> KeyedAlignment {
+    let (left_rows, left_lookup) = build_keyed_rows(old, old_keys, pool);
+    let (right_rows, right_lookup) = build_keyed_rows(new, new_keys, pool);
 
     let mut matched_rows = Vec::new();
     let mut left_only_rows = Vec::new();
@@ -124,7 +154,6 @@ pub(crate) fn diff_table_by_key(old: &Grid, new: &Grid, key_columns: &[u32]) ->
 
         if left_dupe || right_dupe {
             duplicate_clusters.push(DuplicateKeyCluster {
-                key,
                 left_rows: left,
                 right_rows: right,
             });
@@ -150,12 +179,13 @@ pub(crate) fn diff_table_by_key(old: &Grid, new: &Grid, key_columns: &[u32]) ->
 fn build_keyed_rows(
     grid: &Grid,
     spec: &KeyColumnSpec,
+    pool: &StringPool,
 ) -> (Vec<KeyedRow>, HashMap<KeyValue, Vec<u32>>) {
     let mut rows = Vec::with_capacity(grid.nrows as usize);
     let mut lookup = HashMap::new();
 
     for row_idx in 0..grid.nrows {
-        let key = extract_key(grid, row_idx, spec);
+        let key = extract_key(grid, row_idx, spec, pool);
         lookup
             .entry(key.clone())
             .or_insert_with(Vec::new)
@@ -166,13 +196,18 @@ fn build_keyed_rows(
     (rows, lookup)
 }
 
-fn extract_key(grid: &Grid, row_idx: u32, spec: &KeyColumnSpec) -> KeyValue {
+fn extract_key(grid: &Grid, row_idx: u32, spec: &KeyColumnSpec, pool: &StringPool) -> KeyValue {
     let mut components = Vec::with_capacity(spec.columns.len());
 
-    for &col in &spec.columns {
+    for (idx, &col) in spec.columns.iter().enumerate() {
+        let normalize = spec
+            .normalize
+            .get(idx)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
         let value = grid
             .get(row_idx, col)
-            .map(|cell| KeyValueRepr::from_cell_value(cell.value.as_ref()))
+            .map(|cell| KeyValueRepr::normalized(cell.value.as_ref(), normalize, pool))
             .unwrap_or(KeyValueRepr::None);
         components.push(value);
     }
@@ -379,12 +414,17 @@ mod tests {
         grid
     }
 
+    fn align(old: &Grid, new: &Grid, key_columns: &[u32]) -> KeyedAlignment {
+        let spec = KeyColumnSpec::new(key_columns.to_vec());
+        diff_table_by_key(old, new, &spec, &spec, &StringPool::new())
+    }
+
     #[test]
     fn unique_keys_reorder_no_changes() {
         let grid_a = grid_from_rows(&[&[1, 10], &[2, 20], &[3, 30]]);
         let grid_b = grid_from_rows(&[&[3, 30], &[1, 10], &[2, 20]]);
 
-        let alignment = diff_table_by_key(&grid_a, &grid_b, &[0]);
+        let alignment = align(&grid_a, &grid_b, &[0]);
         assert_eq!(
             alignment.matched_rows,
             vec![(0, 1), (1, 2), (2, 0)],
@@ -400,7 +440,7 @@ mod tests {
         let grid_a = grid_from_rows(&[&[1, 10], &[2, 20]]);
         let grid_b = grid_from_rows(&[&[1, 10], &[2, 20], &[3, 30]]);
 
-        let alignment = diff_table_by_key(&grid_a, &grid_b, &[0]);
+        let alignment = align(&grid_a, &grid_b, &[0]);
         assert_eq!(alignment.matched_rows, vec![(0, 0), (1, 1)]);
         assert!(alignment.left_only_rows.is_empty());
         assert_eq!(alignment.right_only_rows, vec![2]);
@@ -412,7 +452,7 @@ mod tests {
         let grid_a = grid_from_rows(&[&[1, 10], &[1, 99]]);
         let grid_b = grid_from_rows(&[&[1, 10], &[1, 100]]);
 
-        let alignment = diff_table_by_key(&grid_a, &grid_b, &[0]);
+        let alignment = align(&grid_a, &grid_b, &[0]);
         assert_eq!(alignment.duplicate_clusters.len(), 1);
         let cluster = &alignment.duplicate_clusters[0];
         assert_eq!(cluster.left_rows, vec![0, 1]);
@@ -424,7 +464,7 @@ mod tests {
         let grid_a = grid_from_rows(&[&[1, 10, 100], &[1, 20, 200], &[2, 10, 300]]);
         let grid_b = grid_from_rows(&[&[1, 20, 200], &[2, 10, 300], &[1, 10, 100]]);
 
-        let alignment = diff_table_by_key(&grid_a, &grid_b, &[0, 1]);
+        let alignment = align(&grid_a, &grid_b, &[0, 1]);
 
         assert!(
             alignment.left_only_rows.is_empty(),
@@ -452,7 +492,7 @@ mod tests {
         let grid_a = grid_from_rows(&[&[1, 999, 10, 100], &[1, 888, 20, 200], &[2, 777, 10, 300]]);
         let grid_b = grid_from_rows(&[&[2, 777, 10, 300], &[1, 999, 10, 100], &[1, 888, 20, 200]]);
 
-        let alignment = diff_table_by_key(&grid_a, &grid_b, &[0, 2]);
+        let alignment = align(&grid_a, &grid_b, &[0, 2]);
 
         assert!(alignment.left_only_rows.is_empty());
         assert!(alignment.right_only_rows.is_empty());
@@ -484,7 +524,7 @@ mod tests {
             &[1, 10, 100, 1000],
         ]);
 
-        let alignment = diff_table_by_key(&grid_a, &grid_b, &[0, 1, 2]);
+        let alignment = align(&grid_a, &grid_b, &[0, 1, 2]);
 
         assert!(alignment.left_only_rows.is_empty());
         assert!(alignment.right_only_rows.is_empty());
@@ -501,6 +541,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn per_side_columns_and_normalisation_match_keys() {
+        let mut pool = StringPool::new();
+        let mut text = |s: &str| Some(CellValue::Text(pool.intern(s)));
+        let mut grid_a = Grid::new(3, 2);
+        grid_a.insert_cell(0, 0, text(" ab-1 "), None);
+        grid_a.insert_cell(1, 0, Some(CellValue::Number(42.0)), None);
+        grid_a.insert_cell(2, 0, text("x"), None);
+        let mut grid_b = Grid::new(3, 2);
+        grid_b.insert_cell(0, 1, text("42"), None);
+        grid_b.insert_cell(1, 1, text("AB-1"), None);
+        grid_b.insert_cell(2, 1, text("X "), None);
+
+        let normalize = vec![vec![
+            KeyNormalization::Trim,
+            KeyNormalization::CaseFold,
+            KeyNormalization::NumberAsText,
+        ]];
+        let old_keys = KeyColumnSpec::normalized(vec![0], normalize);
+        let new_keys = old_keys.with_columns(vec![1]);
+        let alignment = diff_table_by_key(&grid_a, &grid_b, &old_keys, &new_keys, &pool);
+        assert_eq!(alignment.matched_rows, vec![(0, 1), (1, 0), (2, 2)]);
+
+        let plain_old = KeyColumnSpec::new(vec![0]);
+        let plain_new = KeyColumnSpec::new(vec![1]);
+        let alignment = diff_table_by_key(&grid_a, &grid_b, &plain_old, &plain_new, &pool);
+        assert!(alignment.matched_rows.is_empty());
+    }
+
     #[test]
     fn is_key_column_single_column() {
         let spec = KeyColumnSpec::new(vec![0]);
@@ -0,0 +1,460 @@
+//! Key specifications for database mode.
+//!
+//! A [`DatabaseKeys`] names each key part either by column index or by header text, so a key
+//! keeps working when columns are inserted to its left. Header parts are resolved separately
+//! against each grid's header row before rows are matched. A [`SheetKeyMap`] assigns key specs to
+//! several sheets at once.
+
+use crate::addressing::col_letters_to_index;
+use crate::database_alignment::KeyColumnSpec;
+use crate::diff::DiffError;
+use crate::string_pool::StringPool;
+use crate::workbook::{CellValue, Grid};
+use std::fmt;
+
+/// Where a key part is read from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum KeyColumn {
+    /// 0-based column index.
+    Index(u32),
+    /// Header text in [`DatabaseKeys::header_row`], matched after trimming and ignoring case.
+    Header(String),
+}
+
+/// A normalisation applied to a key part before rows are matched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyNormalization {
+    /// Ignore leading and trailing whitespace.
+    Trim,
+    /// Compare text case-insensitively.
+    CaseFold,
+    /// Compare numbers by their text form, so `42` matches `"42"`.
+    NumberAsText,
+}
+
+/// One component of a (possibly composite) key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyPart {
+    pub column: KeyColumn,
+    pub normalize: Vec<KeyNormalization>,
+}
+
+impl KeyPart {
+    pub fn new(column: KeyColumn) -> KeyPart {
+        KeyPart {
+            column,
+            normalize: Vec::new(),
+        }
+    }
+
+    pub fn normalized(mut self, normalization: KeyNormalization) -> KeyPart {
+        if !self.normalize.contains(&normalization) {
+            self.normalize.push(normalization);
+        }
+        self
+    }
+}
+
+/// Key columns for database mode.
+///
+/// Parses from the CLI `--keys` syntax: comma-separated parts, each a column letter (`A`, `AA`)
+/// or a bracketed header (`[Customer ID]`), optionally wrapped in `trim(..)`, `lower(..)` and
+/// `text(..)`, e.g. `trim(lower([Region])),B`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct DatabaseKeys {
+    pub parts: Vec<KeyPart>,
+    /// 0-based row holding the headers named by [`KeyColumn::Header`] parts.
+    pub header_row: u32,
+}
+
+impl DatabaseKeys {
+    /// Keys at fixed 0-based column indices, without normalisation.
+    pub fn from_columns(columns: &[u32]) -> DatabaseKeys {
+        DatabaseKeys {
+            parts: columns
+                .iter()
+                .map(|&col| KeyPart::new(KeyColumn::Index(col)))
+                .collect(),
+            header_row: 0,
+        }
+    }
+
+    /// Keys named by header text in the first row, without normalisation.
+    pub fn from_headers<S: AsRef<str>>(headers: &[S]) -> DatabaseKeys {
+        DatabaseKeys {
+            parts: headers
+                .iter()
+                .map(|header| KeyPart::new(KeyColumn::Header(header.as_ref().to_string())))
+                .collect(),
+            header_row: 0,
+        }
+    }
+
+    pub fn with_header_row(mut self, header_row: u32) -> DatabaseKeys {
+        self.header_row = header_row;
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parts.is_empty()
+    }
+
+    /// Resolve every part to a column on each side.
+    pub(crate) fn resolve(
+        &self,
+        old: &Grid,
+        new: &Grid,
+        pool: &StringPool,
+    ) -> Result<ResolvedKeys, DiffError> {
+        let old_headers = header_texts(old, self.header_row, pool);
+        let new_headers = header_texts(new, self.header_row, pool);
+        let mut old_cols = Vec::with_capacity(self.parts.len());
+        let mut new_cols = Vec::with_capacity(self.parts.len());
+
+        for part in &self.parts {
+            match &part.column {
+                KeyColumn::Index(col) => {
+                    old_cols.push(*col);
+                    new_cols.push(*col);
+                }
+                KeyColumn::Header(name) => {
+                    old_cols.push(find_header(&old_headers, name, "old", self.header_row)?);
+                    new_cols.push(find_header(&new_headers, name, "new", self.header_row)?);
+                }
+            }
+        }
+
+        let normalize: Vec<Vec<KeyNormalization>> = self
+            .parts
+            .iter()
+            .map(|part| part.normalize.clone())
+            .collect();
+        Ok(ResolvedKeys {
+            old: KeyColumnSpec::normalized(old_cols, normalize.clone()),
+            new: KeyColumnSpec::normalized(new_cols, normalize),
+            header_row: self.header_row,
+        })
+    }
+}
+
+/// Key columns resolved against both grids; part `i` reads `old.columns[i]` on the old side and
+/// `new.columns[i]` on the new side.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ResolvedKeys {
+    pub old: KeyColumnSpec,
+    pub new: KeyColumnSpec,
+    /// Row whose text cells name the fields of [`DiffOp::RecordChanged`](crate::DiffOp).
+    pub header_row: u32,
+}
+
+impl ResolvedKeys {
+    /// The same columns on both sides, without normalisation, with headers in the first row.
+    pub(crate) fn positional(columns: &[u32]) -> ResolvedKeys {
+        ResolvedKeys {
+            old: KeyColumnSpec::new(columns.to_vec()),
+            new: KeyColumnSpec::new(columns.to_vec()),
+            header_row: 0,
+        }
+    }
+}
+
+/// `(column, header text)` for every text cell in `row`.
+fn header_texts(grid: &Grid, row: u32, pool: &StringPool) -> Vec<(u32, String)> {
+    (0..grid.ncols)
+        .filter_map(|col| match grid.get(row, col)?.value.as_ref()? {
+            CellValue::Text(id) => Some((col, pool.resolve(*id).to_string())),
+            _ => None,
+        })
+        .collect()
+}
+
+fn find_header(
+    headers: &[(u32, String)],
+    name: &str,
+    side: &str,
+    header_row: u32,
+) -> Result<u32, DiffError> {
+    let wanted = name.trim().to_lowercase();
+    headers
+        .iter()
+        .find(|(_, text)| text.trim().to_lowercase() == wanted)
+        .map(|(col, _)| *col)
+        .ok_or_else(|| DiffError::KeyColumnNotFound {
+            column: name.to_string(),
+            side: side.to_string(),
+            row: header_row + 1,
+            available: headers.iter().map(|(_, text)| text.clone()).collect(),
+        })
+}
+
+/// Key specs for several sheets, by sheet name or glob (`*` and `?`), for multi-sheet database
+/// mode.
+///
+/// Names match case-insensitively. An exact name takes precedence over globs; otherwise the first
+/// matching glob, in insertion order, wins.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SheetKeyMap {
+    entries: Vec<(String, DatabaseKeys)>,
+}
+
+impl SheetKeyMap {
+    pub fn new() -> SheetKeyMap {
+        SheetKeyMap::default()
+    }
+
+    /// Add (or replace) the keys for a sheet name or glob.
+    pub fn insert(&mut self, pattern: impl Into<String>, keys: DatabaseKeys) {
+        let pattern = pattern.into();
+        match self
+            .entries
+            .iter_mut()
+            .find(|(existing, _)| existing.eq_ignore_ascii_case(&pattern))
+        {
+            Some(entry) => entry.1 = keys,
+            None => self.entries.push((pattern, keys)),
+        }
+    }
+
+    pub fn with(mut self, pattern: impl Into<String>, keys: DatabaseKeys) -> SheetKeyMap {
+        self.insert(pattern, keys);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// `(pattern, keys)` pairs in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &DatabaseKeys)> {
+        self.entries
+            .iter()
+            .map(|(pattern, keys)| (pattern.as_str(), keys))
+    }
+
+    /// Keys for `sheet_name`, if any pattern matches it.
+    pub fn keys_for(&self, sheet_name: &str) -> Option<&DatabaseKeys> {
+        let name = sheet_name.to_lowercase();
+        self.entries
+            .iter()
+            .find(|(pattern, _)| !is_glob(pattern) && pattern.to_lowercase() == name)
+            .or_else(|| {
+                self.entries
+                    .iter()
+                    .find(|(pattern, _)| is_glob(pattern) && glob_match(pattern, &name))
+            })
+            .map(|(_, keys)| keys)
+    }
+}
+
+pub(crate) fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?'])
+}
+
+/// Case-insensitive match of `name` against a pattern where `*` is any run of characters and
+/// `?` is any single character.
+pub(crate) fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let name: Vec<char> = name.to_lowercase().chars().collect();
+    let (mut p, mut n) = (0usize, 0usize);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    n = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Error returned when a `--keys` style key expression cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyParseError {
+    pub input: String,
+    pub message: String,
+}
+
+impl fmt::Display for KeyParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid key '{}': {}", self.input, self.message)
+    }
+}
+
+impl std::error::Error for KeyParseError {}
+
+impl std::str::FromStr for DatabaseKeys {
+    type Err = KeyParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = |input: &str, message: &str| KeyParseError {
+            input: input.to_string(),
+            message: message.to_string(),
+        };
+
+        let mut parts: Vec<KeyPart> = Vec::new();
+        for token in split_top_level(s) {
+            let token = token.trim();
+            if token.is_empty() {
+                return Err(err(s, "empty key part"));
+            }
+            let part = parse_part(token).map_err(|message| err(token, &message))?;
+            if parts.contains(&part) {
+                return Err(err(token, "duplicate key part"));
+            }
+            parts.push(part);
+        }
+        if parts.is_empty() {
+            return Err(err(s, "no key columns specified"));
+        }
+        Ok(DatabaseKeys {
+            parts,
+            header_row: 0,
+        })
+    }
+}
+
+/// Split on commas outside brackets and parentheses.
+fn split_top_level(s: &str) -> Vec<&str> {
+    let mut out = Vec::new();
+    let (mut depth, mut in_header, mut start) = (0i32, false, 0usize);
+    for (idx, ch) in s.char_indices() {
+        match ch {
+            '[' => in_header = true,
+            ']' => in_header = false,
+            '(' if !in_header => depth += 1,
+            ')' if !in_header => depth -= 1,
+            ',' if !in_header && depth == 0 => {
+                out.push(&s[start..idx]);
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    out.push(&s[start..]);
+    out
+}
+
+fn parse_part(token: &str) -> Result<KeyPart, String> {
+    if let Some(open) = token.find('(')
+        && !token[..open].contains('[')
+    {
+        let Some(inner) = token[open + 1..].strip_suffix(')') else {
+            return Err("missing closing ')'".to_string());
+        };
+        let normalization = match token[..open].trim().to_ascii_lowercase().as_str() {
+            "trim" => KeyNormalization::Trim,
+            "lower" | "casefold" => KeyNormalization::CaseFold,
+            "text" => KeyNormalization::NumberAsText,
+            other => {
+                return Err(format!(
+                    "unknown function '{other}' (expected trim, lower or text)"
+                ));
+            }
+        };
+        return Ok(parse_part(inner.trim())?.normalized(normalization));
+    }
+
+    if let Some(header) = token.strip_prefix('[') {
+        let Some(header) = header.strip_suffix(']') else {
+            return Err("missing closing ']'".to_string());
+        };
+        if header.trim().is_empty() {
+            return Err("empty header name".to_string());
+        }
+        return Ok(KeyPart::new(KeyColumn::Header(header.to_string())));
+    }
+
+    col_letters_to_index(token)
+        .map(|col| KeyPart::new(KeyColumn::Index(col)))
+        .ok_or_else(|| {
+            "expected a column letter (e.g. A, AA) or a bracketed header (e.g. [Id])".to_string()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_letters_headers_and_normalisers() {
+        let keys: DatabaseKeys = "A, trim(lower([Customer, Inc])),text(AA)"
+            .parse()
+            .expect("parse keys");
+        assert_eq!(
+            keys.parts,
+            vec![
+                KeyPart::new(KeyColumn::Index(0)),
+                KeyPart::new(KeyColumn::Header("Customer, Inc".to_string()))
+                    .normalized(KeyNormalization::CaseFold)
+                    .normalized(KeyNormalization::Trim),
+                KeyPart::new(KeyColumn::Index(26)).normalized(KeyNormalization::NumberAsText),
+            ]
+        );
+
+        for bad in ["", "A,,B", "A1", "[Id", "upper(A)", "trim(A", "A,a", "[ ]"] {
+            assert!(
+                bad.parse::<DatabaseKeys>().is_err(),
+                "{bad:?} should not parse"
+            );
+        }
+    }
+
+    #[test]
+    fn sheet_key_map_prefers_exact_names_then_first_glob() {
+        let map = SheetKeyMap::new()
+            .with("Orders_*", DatabaseKeys::from_columns(&[0]))
+            .with("*", DatabaseKeys::from_columns(&[1]))
+            .with("orders_2024", DatabaseKeys::from_columns(&[2]));
+
+        let col = |name: &str| map.keys_for(name).map(|keys| keys.parts[0].column.clone());
+        assert_eq!(col("Orders_2024"), Some(KeyColumn::Index(2)));
+        assert_eq!(col("ORDERS_2023"), Some(KeyColumn::Index(0)));
+        assert_eq!(col("Customers"), Some(KeyColumn::Index(1)));
+
+        assert!(glob_match("q?_*.data", "Q1_sales.data"));
+        assert!(glob_match("*a*b", "xaxxab"));
+        assert!(!glob_match("q?_*", "q10_sales"));
+        assert!(!glob_match("Orders", "Orders2"));
+    }
+
+    #[test]
+    fn resolves_headers_per_side_and_reports_missing_columns() {
+        let mut pool = StringPool::new();
+        let mut grid = |headers: &[&str]| {
+            let mut grid = Grid::new(2, headers.len() as u32);
+            for (col, header) in headers.iter().enumerate() {
+                let id = pool.intern(header);
+                grid.insert_cell(1, col as u32, Some(CellValue::Text(id)), None);
+            }
+            grid
+        };
+        let old = grid(&["Id", "Name"]);
+        let new = grid(&["Added", " name ", "ID"]);
+
+        let keys = DatabaseKeys::from_headers(&["id", "NAME"]).with_header_row(1);
+        let resolved = keys.resolve(&old, &new, &pool).expect("resolve keys");
+        assert_eq!(resolved.old.columns, vec![0, 1]);
+        assert_eq!(resolved.new.columns, vec![2, 1]);
+
+        let missing = DatabaseKeys::from_headers(&["Added"]).with_header_row(1);
+        match missing.resolve(&old, &new, &pool) {
+            Err(DiffError::KeyColumnNotFound {
+                column, side, row, ..
+            }) => assert_eq!((column.as_str(), side.as_str(), row), ("Added", "old", 2)),
+            other => panic!("expected KeyColumnNotFound, got {other:?}"),
+        }
+    }
+}
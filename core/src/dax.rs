@@ -1,3 +1,9 @@
+//! DAX expression parser.
+//!
+//! Expressions parse into a [`DaxExpr`] tree that keeps identifiers as written. Comparisons go
+//! through [`DaxExpr::canonicalize`], which lowercases names the way DAX resolves them, so the
+//! semantic hash ignores whitespace, comments and letter case.
+
 use std::hash::{Hash, Hasher};
 
 use xxhash_rust::xxh64::Xxh64;
@@ -5,7 +11,7 @@ use xxhash_rust::xxh64::Xxh64;
 use crate::hashing::XXH64_SEED;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub(crate) struct DaxParseError {
+pub struct DaxParseError {
     message: String,
 }
 
@@ -26,7 +32,7 @@ impl std::fmt::Display for DaxParseError {
 impl std::error::Error for DaxParseError {}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum BinaryOp {
+pub enum DaxBinaryOp {
     Or,
     And,
     Eq,
@@ -43,42 +49,46 @@ enum BinaryOp {
     Concat,
 }
 
-impl BinaryOp {
+impl DaxBinaryOp {
     fn precedence(self) -> u8 {
         match self {
-            BinaryOp::Or => 1,
-            BinaryOp::And => 2,
-            BinaryOp::Eq
-            | BinaryOp::Ne
-            | BinaryOp::Lt
-            | BinaryOp::Le
-            | BinaryOp::Gt
-            | BinaryOp::Ge => 3,
-            BinaryOp::Concat => 4,
-            BinaryOp::Add | BinaryOp::Sub => 5,
-            BinaryOp::Mul | BinaryOp::Div => 6,
-            BinaryOp::Pow => 7,
+            DaxBinaryOp::Or => 1,
+            DaxBinaryOp::And => 2,
+            DaxBinaryOp::Eq
+            | DaxBinaryOp::Ne
+            | DaxBinaryOp::Lt
+            | DaxBinaryOp::Le
+            | DaxBinaryOp::Gt
+            | DaxBinaryOp::Ge => 3,
+            DaxBinaryOp::Concat => 4,
+            DaxBinaryOp::Add | DaxBinaryOp::Sub => 5,
+            DaxBinaryOp::Mul | DaxBinaryOp::Div => 6,
+            DaxBinaryOp::Pow => 7,
         }
     }
 
     fn right_assoc(self) -> bool {
-        matches!(self, BinaryOp::Pow)
+        matches!(self, DaxBinaryOp::Pow)
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum UnaryOp {
+pub enum DaxUnaryOp {
     Pos,
     Neg,
     Not,
 }
 
+/// Parsed DAX expression.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-enum Expr {
+pub enum DaxExpr {
+    /// Bit pattern of the `f64` literal, so the tree stays `Eq` and `Hash`.
     Number(u64),
     String(String),
     Boolean(bool),
+    /// A table name or a variable.
     Identifier(String),
+    /// `[Name]`: a measure, or a column of the table in row context.
     BracketRef(String),
     TableColumnRef {
         table: String,
@@ -86,20 +96,20 @@ enum Expr {
     },
     Call {
         name: String,
-        args: Vec<Expr>,
+        args: Vec<DaxExpr>,
     },
     Unary {
-        op: UnaryOp,
-        expr: Box<Expr>,
+        op: DaxUnaryOp,
+        expr: Box<DaxExpr>,
     },
     Binary {
-        op: BinaryOp,
-        left: Box<Expr>,
-        right: Box<Expr>,
+        op: DaxBinaryOp,
+        left: Box<DaxExpr>,
+        right: Box<DaxExpr>,
     },
     VarBlock {
-        vars: Vec<(String, Expr)>,
-        body: Box<Expr>,
+        vars: Vec<(String, DaxExpr)>,
+        body: Box<DaxExpr>,
     },
 }
 
@@ -109,7 +119,7 @@ enum TokenKind {
     BracketIdent(String),
     StringLiteral(String),
     Number(u64),
-    Operator(BinaryOp),
+    Operator(DaxBinaryOp),
     Plus,
     Minus,
     LParen,
@@ -237,19 +247,19 @@ impl Lexer {
             '*' => {
                 self.advance();
                 Ok(Token {
-                    kind: TokenKind::Operator(BinaryOp::Mul),
+                    kind: TokenKind::Operator(DaxBinaryOp::Mul),
                 })
             }
             '/' => {
                 self.advance();
                 Ok(Token {
-                    kind: TokenKind::Operator(BinaryOp::Div),
+                    kind: TokenKind::Operator(DaxBinaryOp::Div),
                 })
             }
             '^' => {
                 self.advance();
                 Ok(Token {
-                    kind: TokenKind::Operator(BinaryOp::Pow),
+                    kind: TokenKind::Operator(DaxBinaryOp::Pow),
                 })
             }
             '&' => {
@@ -257,11 +267,11 @@ impl Lexer {
                 if self.peek() == Some('&') {
                     self.advance();
                     Ok(Token {
-                        kind: TokenKind::Operator(BinaryOp::And),
+                        kind: TokenKind::Operator(DaxBinaryOp::And),
                     })
                 } else {
                     Ok(Token {
-                        kind: TokenKind::Operator(BinaryOp::Concat),
+                        kind: TokenKind::Operator(DaxBinaryOp::Concat),
                     })
                 }
             }
@@ -270,7 +280,7 @@ impl Lexer {
                 if self.peek() == Some('|') {
                     self.advance();
                     Ok(Token {
-                        kind: TokenKind::Operator(BinaryOp::Or),
+                        kind: TokenKind::Operator(DaxBinaryOp::Or),
                     })
                 } else {
                     Err(DaxParseError::new("unexpected '|'"))
@@ -279,7 +289,7 @@ impl Lexer {
             '=' => {
                 self.advance();
                 Ok(Token {
-                    kind: TokenKind::Operator(BinaryOp::Eq),
+                    kind: TokenKind::Operator(DaxBinaryOp::Eq),
                 })
             }
             '<' => {
@@ -288,17 +298,17 @@ impl Lexer {
                     Some('=') => {
                         self.advance();
                         Ok(Token {
-                            kind: TokenKind::Operator(BinaryOp::Le),
+                            kind: TokenKind::Operator(DaxBinaryOp::Le),
                         })
                     }
                     Some('>') => {
                         self.advance();
                         Ok(Token {
-                            kind: TokenKind::Operator(BinaryOp::Ne),
+                            kind: TokenKind::Operator(DaxBinaryOp::Ne),
                         })
                     }
                     _ => Ok(Token {
-                        kind: TokenKind::Operator(BinaryOp::Lt),
+                        kind: TokenKind::Operator(DaxBinaryOp::Lt),
                     }),
                 }
             }
@@ -307,11 +317,11 @@ impl Lexer {
                 if self.peek() == Some('=') {
                     self.advance();
                     Ok(Token {
-                        kind: TokenKind::Operator(BinaryOp::Ge),
+                        kind: TokenKind::Operator(DaxBinaryOp::Ge),
                     })
                 } else {
                     Ok(Token {
-                        kind: TokenKind::Operator(BinaryOp::Gt),
+                        kind: TokenKind::Operator(DaxBinaryOp::Gt),
                     })
                 }
             }
@@ -344,7 +354,7 @@ impl Lexer {
             }
         }
         Ok(Token {
-            kind: TokenKind::Ident(normalize_ident(&buf)),
+            kind: TokenKind::Ident(buf),
         })
     }
 
@@ -425,7 +435,7 @@ impl Lexer {
                     continue;
                 }
                 return Ok(Token {
-                    kind: TokenKind::BracketIdent(normalize_ident(&buf)),
+                    kind: TokenKind::BracketIdent(buf),
                 });
             }
             buf.push(ch);
@@ -445,7 +455,7 @@ impl Lexer {
                     continue;
                 }
                 return Ok(Token {
-                    kind: TokenKind::Ident(normalize_ident(&buf)),
+                    kind: TokenKind::Ident(buf),
                 });
             }
             buf.push(ch);
@@ -487,7 +497,7 @@ impl Parser {
         }
     }
 
-    fn parse(&mut self) -> Result<Expr, DaxParseError> {
+    fn parse(&mut self) -> Result<DaxExpr, DaxParseError> {
         let expr = self.parse_expr_bp(0)?;
         if !matches!(self.peek(), TokenKind::End) {
             return Err(DaxParseError::new("unexpected trailing tokens"));
@@ -495,13 +505,13 @@ impl Parser {
         Ok(expr)
     }
 
-    fn parse_expr_bp(&mut self, min_bp: u8) -> Result<Expr, DaxParseError> {
+    fn parse_expr_bp(&mut self, min_bp: u8) -> Result<DaxExpr, DaxParseError> {
         let mut lhs = self.parse_prefix()?;
         loop {
             let op = match self.peek() {
                 TokenKind::Operator(op) => *op,
-                TokenKind::Plus => BinaryOp::Add,
-                TokenKind::Minus => BinaryOp::Sub,
+                TokenKind::Plus => DaxBinaryOp::Add,
+                TokenKind::Minus => DaxBinaryOp::Sub,
                 _ => break,
             };
 
@@ -512,7 +522,7 @@ impl Parser {
             let next_min = if op.right_assoc() { prec } else { prec + 1 };
             self.next();
             let rhs = self.parse_expr_bp(next_min)?;
-            lhs = Expr::Binary {
+            lhs = DaxExpr::Binary {
                 op,
                 left: Box::new(lhs),
                 right: Box::new(rhs),
@@ -521,48 +531,52 @@ impl Parser {
         Ok(lhs)
     }
 
-    fn parse_prefix(&mut self) -> Result<Expr, DaxParseError> {
+    fn parse_prefix(&mut self) -> Result<DaxExpr, DaxParseError> {
         let token = self.next().clone();
         match token {
-            TokenKind::Ident(name) if name == "var" => self.parse_var_block(),
-            TokenKind::Ident(name) if name == "not" => {
+            TokenKind::Ident(name) if name.eq_ignore_ascii_case("var") => self.parse_var_block(),
+            TokenKind::Ident(name) if name.eq_ignore_ascii_case("not") => {
                 let expr = self.parse_expr_bp(8)?;
-                Ok(Expr::Unary {
-                    op: UnaryOp::Not,
+                Ok(DaxExpr::Unary {
+                    op: DaxUnaryOp::Not,
                     expr: Box::new(expr),
                 })
             }
-            TokenKind::Ident(name) if name == "true" => Ok(Expr::Boolean(true)),
-            TokenKind::Ident(name) if name == "false" => Ok(Expr::Boolean(false)),
+            TokenKind::Ident(name) if name.eq_ignore_ascii_case("true") => {
+                Ok(DaxExpr::Boolean(true))
+            }
+            TokenKind::Ident(name) if name.eq_ignore_ascii_case("false") => {
+                Ok(DaxExpr::Boolean(false))
+            }
             TokenKind::Ident(name) => {
                 if matches!(self.peek(), TokenKind::LParen) {
                     self.next();
                     let args = self.parse_call_args()?;
-                    Ok(Expr::Call { name, args })
+                    Ok(DaxExpr::Call { name, args })
                 } else if let TokenKind::BracketIdent(column) = self.peek().clone() {
                     self.next();
-                    Ok(Expr::TableColumnRef {
+                    Ok(DaxExpr::TableColumnRef {
                         table: name,
                         column,
                     })
                 } else {
-                    Ok(Expr::Identifier(name))
+                    Ok(DaxExpr::Identifier(name))
                 }
             }
-            TokenKind::BracketIdent(name) => Ok(Expr::BracketRef(name)),
-            TokenKind::StringLiteral(s) => Ok(Expr::String(s)),
-            TokenKind::Number(n) => Ok(Expr::Number(n)),
+            TokenKind::BracketIdent(name) => Ok(DaxExpr::BracketRef(name)),
+            TokenKind::StringLiteral(s) => Ok(DaxExpr::String(s)),
+            TokenKind::Number(n) => Ok(DaxExpr::Number(n)),
             TokenKind::Plus => {
                 let expr = self.parse_expr_bp(8)?;
-                Ok(Expr::Unary {
-                    op: UnaryOp::Pos,
+                Ok(DaxExpr::Unary {
+                    op: DaxUnaryOp::Pos,
                     expr: Box::new(expr),
                 })
             }
             TokenKind::Minus => {
                 let expr = self.parse_expr_bp(8)?;
-                Ok(Expr::Unary {
-                    op: UnaryOp::Neg,
+                Ok(DaxExpr::Unary {
+                    op: DaxUnaryOp::Neg,
                     expr: Box::new(expr),
                 })
             }
@@ -575,7 +589,7 @@ impl Parser {
         }
     }
 
-    fn parse_call_args(&mut self) -> Result<Vec<Expr>, DaxParseError> {
+    fn parse_call_args(&mut self) -> Result<Vec<DaxExpr>, DaxParseError> {
         let mut args = Vec::new();
         if matches!(self.peek(), TokenKind::RParen) {
             self.next();
@@ -600,7 +614,7 @@ impl Parser {
         Ok(args)
     }
 
-    fn parse_var_block(&mut self) -> Result<Expr, DaxParseError> {
+    fn parse_var_block(&mut self) -> Result<DaxExpr, DaxParseError> {
         let mut vars = Vec::new();
         loop {
             let name = match self.next() {
@@ -612,16 +626,16 @@ impl Parser {
                     )));
                 }
             };
-            self.consume(&TokenKind::Operator(BinaryOp::Eq))?;
+            self.consume(&TokenKind::Operator(DaxBinaryOp::Eq))?;
             let expr = self.parse_expr_bp(0)?;
             vars.push((name, expr));
 
             match self.peek() {
-                TokenKind::Ident(next) if next == "var" => {
+                TokenKind::Ident(next) if next.eq_ignore_ascii_case("var") => {
                     self.next();
                     continue;
                 }
-                TokenKind::Ident(next) if next == "return" => {
+                TokenKind::Ident(next) if next.eq_ignore_ascii_case("return") => {
                     self.next();
                     break;
                 }
@@ -631,7 +645,7 @@ impl Parser {
             }
         }
         let body = self.parse_expr_bp(0)?;
-        Ok(Expr::VarBlock {
+        Ok(DaxExpr::VarBlock {
             vars,
             body: Box::new(body),
         })
@@ -642,7 +656,185 @@ fn normalize_ident(s: &str) -> String {
     s.to_lowercase()
 }
 
-pub(crate) fn semantic_hash(expr: &str) -> Result<u64, DaxParseError> {
+impl DaxExpr {
+    /// The same tree with function, table, column, measure and variable names lowercased.
+    pub fn canonicalize(&self) -> DaxExpr {
+        match self {
+            DaxExpr::Number(_) | DaxExpr::String(_) | DaxExpr::Boolean(_) => self.clone(),
+            DaxExpr::Identifier(name) => DaxExpr::Identifier(normalize_ident(name)),
+            DaxExpr::BracketRef(name) => DaxExpr::BracketRef(normalize_ident(name)),
+            DaxExpr::TableColumnRef { table, column } => DaxExpr::TableColumnRef {
+                table: normalize_ident(table),
+                column: normalize_ident(column),
+            },
+            DaxExpr::Call { name, args } => DaxExpr::Call {
+                name: normalize_ident(name),
+                args: args.iter().map(DaxExpr::canonicalize).collect(),
+            },
+            DaxExpr::Unary { op, expr } => DaxExpr::Unary {
+                op: *op,
+                expr: Box::new(expr.canonicalize()),
+            },
+            DaxExpr::Binary { op, left, right } => DaxExpr::Binary {
+                op: *op,
+                left: Box::new(left.canonicalize()),
+                right: Box::new(right.canonicalize()),
+            },
+            DaxExpr::VarBlock { vars, body } => DaxExpr::VarBlock {
+                vars: vars
+                    .iter()
+                    .map(|(name, expr)| (normalize_ident(name), expr.canonicalize()))
+                    .collect(),
+                body: Box::new(body.canonicalize()),
+            },
+        }
+    }
+
+    /// Direct subexpressions in source order; for a `VAR` block, the variables then the body.
+    pub fn children(&self) -> Vec<&DaxExpr> {
+        match self {
+            DaxExpr::Call { args, .. } => args.iter().collect(),
+            DaxExpr::Unary { expr, .. } => vec![expr.as_ref()],
+            DaxExpr::Binary { left, right, .. } => vec![left.as_ref(), right.as_ref()],
+            DaxExpr::VarBlock { vars, body } => vars
+                .iter()
+                .map(|(_, expr)| expr)
+                .chain(std::iter::once(body.as_ref()))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Column and measure references as `(table, name)`, in source order. `[Name]` has no table.
+    pub fn references(&self) -> Vec<(Option<&str>, &str)> {
+        let mut out = Vec::new();
+        self.collect_references(&mut out);
+        out
+    }
+
+    fn collect_references<'a>(&'a self, out: &mut Vec<(Option<&'a str>, &'a str)>) {
+        match self {
+            DaxExpr::BracketRef(name) => out.push((None, name)),
+            DaxExpr::TableColumnRef { table, column } => out.push((Some(table), column)),
+            _ => {
+                for child in self.children() {
+                    child.collect_references(out);
+                }
+            }
+        }
+    }
+}
+
+impl DaxBinaryOp {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            DaxBinaryOp::Or => "||",
+            DaxBinaryOp::And => "&&",
+            DaxBinaryOp::Eq => "=",
+            DaxBinaryOp::Ne => "<>",
+            DaxBinaryOp::Lt => "<",
+            DaxBinaryOp::Le => "<=",
+            DaxBinaryOp::Gt => ">",
+            DaxBinaryOp::Ge => ">=",
+            DaxBinaryOp::Add => "+",
+            DaxBinaryOp::Sub => "-",
+            DaxBinaryOp::Mul => "*",
+            DaxBinaryOp::Div => "/",
+            DaxBinaryOp::Pow => "^",
+            DaxBinaryOp::Concat => "&",
+        }
+    }
+}
+
+impl DaxUnaryOp {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            DaxUnaryOp::Pos => "+",
+            DaxUnaryOp::Neg => "-",
+            DaxUnaryOp::Not => "NOT ",
+        }
+    }
+}
+
+/// Single-line DAX text. Operands are parenthesized only where precedence requires it.
+impl std::fmt::Display for DaxExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DaxExpr::Number(bits) => write!(f, "{}", f64::from_bits(*bits)),
+            DaxExpr::String(s) => write!(f, "\"{}\"", s.replace('"', "\"\"")),
+            DaxExpr::Boolean(b) => write!(f, "{}", if *b { "TRUE" } else { "FALSE" }),
+            DaxExpr::Identifier(name) => write_table_name(f, name),
+            DaxExpr::BracketRef(name) => write!(f, "[{}]", name.replace(']', "]]")),
+            DaxExpr::TableColumnRef { table, column } => {
+                write_table_name(f, table)?;
+                write!(f, "[{}]", column.replace(']', "]]"))
+            }
+            DaxExpr::Call { name, args } => {
+                write!(f, "{}(", name)?;
+                for (idx, arg) in args.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", arg)?;
+                }
+                write!(f, ")")
+            }
+            DaxExpr::Unary { op, expr } => {
+                write!(f, "{}", op.symbol())?;
+                if matches!(expr.as_ref(), DaxExpr::Binary { .. }) {
+                    write!(f, "({})", expr)
+                } else {
+                    write!(f, "{}", expr)
+                }
+            }
+            DaxExpr::Binary { op, left, right } => {
+                write_operand(f, left, op.precedence(), op.right_assoc())?;
+                write!(f, " {} ", op.symbol())?;
+                write_operand(f, right, op.precedence(), !op.right_assoc())
+            }
+            DaxExpr::VarBlock { vars, body } => {
+                for (name, expr) in vars {
+                    write!(f, "VAR {} = {} ", name, expr)?;
+                }
+                write!(f, "RETURN {}", body)
+            }
+        }
+    }
+}
+
+fn write_table_name(f: &mut std::fmt::Formatter<'_>, name: &str) -> std::fmt::Result {
+    if name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        write!(f, "{}", name)
+    } else {
+        write!(f, "'{}'", name.replace('\'', "''"))
+    }
+}
+
+/// Writes a binary operand, parenthesized when it binds looser than its parent, or equally
+/// loose on the side the parent does not associate to.
+fn write_operand(
+    f: &mut std::fmt::Formatter<'_>,
+    operand: &DaxExpr,
+    parent_precedence: u8,
+    paren_on_tie: bool,
+) -> std::fmt::Result {
+    let needs_parens = match operand {
+        DaxExpr::Binary { op, .. } => {
+            op.precedence() < parent_precedence
+                || (paren_on_tie && op.precedence() == parent_precedence)
+        }
+        DaxExpr::VarBlock { .. } => true,
+        _ => false,
+    };
+    if needs_parens {
+        write!(f, "({})", operand)
+    } else {
+        write!(f, "{}", operand)
+    }
+}
+
+/// Parse a DAX expression (a measure, calculated column or calculation item body).
+pub fn parse_dax(expr: &str) -> Result<DaxExpr, DaxParseError> {
     let mut lexer = Lexer::new(expr);
     let mut tokens = Vec::new();
     loop {
@@ -654,8 +846,11 @@ pub(crate) fn semantic_hash(expr: &str) -> Result<u64, DaxParseError> {
         }
     }
 
-    let mut parser = Parser::new(tokens);
-    let expr = parser.parse()?;
+    Parser::new(tokens).parse()
+}
+
+pub(crate) fn semantic_hash(expr: &str) -> Result<u64, DaxParseError> {
+    let expr = parse_dax(expr)?.canonicalize();
     let mut h = Xxh64::new(XXH64_SEED);
     expr.hash(&mut h);
     Ok(h.finish())
@@ -703,4 +898,27 @@ mod tests {
         let b = "SUM(Sales[Amount])";
         assert_eq!(hash(a).unwrap(), hash(b).unwrap());
     }
+
+    #[test]
+    fn parse_dax_keeps_name_case_and_displays_on_one_line() {
+        let expr =
+            parse_dax("CALCULATE ( [Total Sales],\n 'Sales Geo'[Region] = \"West\" )").unwrap();
+        assert_eq!(
+            expr.to_string(),
+            "CALCULATE([Total Sales], 'Sales Geo'[Region] = \"West\")"
+        );
+        assert_eq!(
+            parse_dax("(1 + 2) * -x").unwrap().to_string(),
+            "(1 + 2) * -x"
+        );
+    }
+
+    #[test]
+    fn references_lists_columns_and_measures_in_order() {
+        let expr = parse_dax("VAR t = [Base] RETURN DIVIDE(t, SUM(Sales[Amount]))").unwrap();
+        assert_eq!(
+            expr.references(),
+            vec![(None, "Base"), (Some("Sales"), "Amount")]
+        );
+    }
 }
@@ -0,0 +1,287 @@
+//! Measure dependency graph of a tabular model and the impact of edited measures.
+//!
+//! Every measure's DAX is parsed and its references resolved to other measures: `[Name]` matches
+//! a measure of that name in any table, `Table[Name]` only the one in `Table`. Names compare
+//! case-insensitively, as DAX does. Column references and measures that do not parse are left out
+//! of the graph. Report visuals are attached by the `Table.Name` query reference they bind.
+//! Edited measures are looked up by their resolved `Table/Name`, so the graph can be built with
+//! any string pool, independently of the one the diff interned into.
+
+use crate::dax::parse_dax;
+use crate::diff::{DiffOp, DiffReport};
+use crate::model::Model;
+use crate::report_layout::ReportLayout;
+use crate::string_pool::StringPool;
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::Serialize;
+use std::collections::VecDeque;
+
+/// A report visual, by page display name and visual title.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MeasureVisual {
+    pub page: String,
+    /// The visual's title, or its type and id when it has none.
+    pub visual: String,
+}
+
+/// Measures and visuals that read an edited measure, directly or through other measures.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MeasureImpact {
+    /// The edited measure, as `Table/Name`.
+    pub measure: String,
+    /// Downstream measures as `Table/Name`, in model order.
+    pub dependent_measures: Vec<String>,
+    /// Visuals bound to the edited measure or any of its dependents, in page order.
+    pub visuals: Vec<MeasureVisual>,
+}
+
+/// Reader graph over the measures of one tabular model.
+#[derive(Debug, Clone, Default)]
+pub struct MeasureGraph {
+    /// Measure names as `Table/Name`, in model order.
+    measures: Vec<String>,
+    /// Lowercased `Table/Name` to its position in `measures`.
+    index: FxHashMap<String, usize>,
+    /// Measures referencing each measure directly.
+    readers: Vec<Vec<usize>>,
+    /// Visuals bound to each measure.
+    visuals: Vec<Vec<MeasureVisual>>,
+}
+
+impl MeasureGraph {
+    /// Parse every measure in `model` and record which measures it reads.
+    pub fn build(model: &Model, pool: &StringPool) -> MeasureGraph {
+        let keys: Vec<(String, String)> = model
+            .measures
+            .iter()
+            .map(|measure| {
+                let (table, name) = split_measure_name(pool.resolve(measure.name));
+                (table.to_lowercase(), name.to_lowercase())
+            })
+            .collect();
+        let mut by_name: FxHashMap<&str, Vec<usize>> = FxHashMap::default();
+        for (idx, (_, name)) in keys.iter().enumerate() {
+            by_name.entry(name.as_str()).or_default().push(idx);
+        }
+
+        let mut readers = vec![Vec::new(); keys.len()];
+        for (reader, measure) in model.measures.iter().enumerate() {
+            let Ok(expr) = parse_dax(pool.resolve(measure.expression)) else {
+                continue;
+            };
+            let mut read = FxHashSet::default();
+            for (table, name) in expr.references() {
+                let Some(candidates) = by_name.get(name.to_lowercase().as_str()) else {
+                    continue;
+                };
+                for &target in candidates {
+                    let table_matches =
+                        table.is_none_or(|table| table.to_lowercase() == keys[target].0);
+                    if table_matches && target != reader && read.insert(target) {
+                        readers[target].push(reader);
+                    }
+                }
+            }
+        }
+
+        let measures: Vec<String> = model
+            .measures
+            .iter()
+            .map(|m| pool.resolve(m.name).to_string())
+            .collect();
+        MeasureGraph {
+            index: measures
+                .iter()
+                .enumerate()
+                .map(|(idx, name)| (name.to_lowercase(), idx))
+                .collect(),
+            visuals: vec![Vec::new(); measures.len()],
+            measures,
+            readers,
+        }
+    }
+
+    /// Attach the visuals of `layout` to the measures they bind.
+    pub(crate) fn bind_visuals(&mut self, layout: &ReportLayout) {
+        for (idx, name) in self.measures.iter().enumerate() {
+            let (table, name) = split_measure_name(name);
+            self.visuals[idx] = layout
+                .visuals_bound_to(&format!("{table}.{name}"))
+                .into_iter()
+                .map(|(page, visual)| MeasureVisual { page, visual })
+                .collect();
+        }
+    }
+
+    /// Every measure that reads the `Table/Name` measure `name`, directly or through other
+    /// measures. The measure itself is not listed, even when it sits on a circular reference.
+    pub fn dependents(&self, name: &str) -> Vec<&str> {
+        match self.index.get(&name.to_lowercase()) {
+            Some(&idx) => self
+                .dependent_indices(idx)
+                .into_iter()
+                .map(|dep| self.measures[dep].as_str())
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn dependent_indices(&self, start: usize) -> Vec<usize> {
+        let mut seen = FxHashSet::default();
+        seen.insert(start);
+        let mut queue = VecDeque::from([start]);
+        let mut found = Vec::new();
+        while let Some(node) = queue.pop_front() {
+            for &reader in &self.readers[node] {
+                if seen.insert(reader) {
+                    found.push(reader);
+                    queue.push_back(reader);
+                }
+            }
+        }
+        found.sort_unstable();
+        found
+    }
+
+    /// Dependents of each `MeasureDefinitionChanged` in `report`, whose names are resolved
+    /// through the report's string table. Build the graph from the new side of the diff; edited
+    /// measures nothing reads or shows are left out.
+    pub fn impact(&self, report: &DiffReport) -> Vec<MeasureImpact> {
+        report
+            .ops
+            .iter()
+            .filter_map(|op| match op {
+                DiffOp::MeasureDefinitionChanged { name, .. } => {
+                    let name = report.resolve(*name)?;
+                    let &idx = self.index.get(&name.to_lowercase())?;
+                    let dependents = self.dependent_indices(idx);
+                    let mut visuals: Vec<MeasureVisual> = Vec::new();
+                    for &node in std::iter::once(&idx).chain(&dependents) {
+                        for visual in &self.visuals[node] {
+                            if !visuals.contains(visual) {
+                                visuals.push(visual.clone());
+                            }
+                        }
+                    }
+                    if dependents.is_empty() && visuals.is_empty() {
+                        return None;
+                    }
+                    Some(MeasureImpact {
+                        measure: self.measures[idx].clone(),
+                        dependent_measures: dependents
+                            .into_iter()
+                            .map(|dep| self.measures[dep].clone())
+                            .collect(),
+                        visuals,
+                    })
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// `Table/Name` into its table and measure name; measures without a table have an empty one.
+fn split_measure_name(full: &str) -> (&str, &str) {
+    full.split_once('/').unwrap_or(("", full))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::ExpressionChangeKind;
+    use crate::model::Measure;
+    use crate::string_pool::StringId;
+
+    fn model(pool: &mut StringPool, measures: &[(&str, &str)]) -> Model {
+        Model {
+            measures: measures
+                .iter()
+                .map(|(name, expr)| Measure {
+                    name: pool.intern(name),
+                    expression: pool.intern(expr),
+                    format_string: None,
+                    display_folder: None,
+                    description: None,
+                    kpi: None,
+                })
+                .collect(),
+            ..Model::default()
+        }
+    }
+
+    fn changed(name: StringId) -> DiffOp {
+        DiffOp::MeasureDefinitionChanged {
+            name,
+            change_kind: ExpressionChangeKind::Semantic,
+            old_hash: 1,
+            new_hash: 2,
+            edits: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn dependents_are_transitive_and_case_insensitive() {
+        let mut pool = StringPool::new();
+        let model = model(
+            &mut pool,
+            &[
+                ("Sales/Revenue", "SUM(Sales[Amount])"),
+                ("Sales/Cost", "SUM(Sales[Cost])"),
+                ("Sales/Margin", "[revenue] - [Cost]"),
+                ("Sales/Margin %", "DIVIDE([Margin], SALES[Revenue])"),
+                ("Budget/Variance", "[Revenue] - SUM(Budget[Amount])"),
+                ("Budget/Other", "Budget[Revenue]"),
+            ],
+        );
+        let graph = MeasureGraph::build(&model, &pool);
+
+        assert_eq!(
+            graph.dependents("Sales/Revenue"),
+            vec!["Sales/Margin", "Sales/Margin %", "Budget/Variance"]
+        );
+        assert_eq!(
+            graph.dependents("sales/cost"),
+            vec!["Sales/Margin", "Sales/Margin %"]
+        );
+        assert!(graph.dependents("Budget/Variance").is_empty());
+    }
+
+    #[test]
+    fn circular_references_terminate() {
+        let mut pool = StringPool::new();
+        let model = model(&mut pool, &[("T/A", "[B] + 1"), ("T/B", "[A] * 2")]);
+        let graph = MeasureGraph::build(&model, &pool);
+        assert_eq!(graph.dependents("T/A"), vec!["T/B"]);
+    }
+
+    #[test]
+    fn impact_lists_dependents_and_skips_unread_measures() {
+        let mut pool = StringPool::new();
+        let model = model(
+            &mut pool,
+            &[
+                ("T/Base", "SUM(T[X])"),
+                ("T/Double", "[Base] * 2"),
+                ("T/Lone", "1"),
+            ],
+        );
+        let graph = MeasureGraph::build(&model, &pool);
+
+        // The diff interned into its own pool, so its ids differ from the graph's.
+        let mut diff_pool = StringPool::new();
+        diff_pool.intern("Unrelated");
+        let ops = vec![
+            changed(diff_pool.intern("T/Lone")),
+            changed(diff_pool.intern("T/Base")),
+        ];
+        let mut report = DiffReport::new(ops);
+        report.strings = diff_pool.into_strings();
+
+        let impacts = graph.impact(&report);
+        assert_eq!(impacts.len(), 1);
+        assert_eq!(impacts[0].measure, "T/Base");
+        assert_eq!(impacts[0].dependent_measures, vec!["T/Double"]);
+        assert!(impacts[0].visuals.is_empty());
+    }
+}
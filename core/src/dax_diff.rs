@@ -0,0 +1,463 @@
+//! Structural diff of DAX expressions.
+//!
+//! Both sides are parsed into [`DaxExpr`] trees and compared with the tree edit script shared with
+//! the M and formula diffs. Edits are located by their innermost function call and argument, and
+//! changes inside the filter arguments of `CALCULATE`/`CALCULATETABLE` are folded into one edit
+//! per filter.
+
+use std::hash::{Hash, Hasher};
+
+use crate::dax::{parse_dax, DaxExpr};
+use crate::diff::{DaxEdit, DaxEditKind};
+use crate::m_ast_diff::{simple_tree, tree_edit_script, SimpleTree, TreeEdit};
+
+/// Expressions larger than this (in AST nodes) are classified but not broken down into edits.
+const MAX_EDIT_TREE_NODES: usize = 240;
+/// Beyond this many edits the breakdown is replaced by a single whole-expression replacement.
+const MAX_DAX_EDITS: usize = 16;
+/// Functions whose second and later arguments are filters.
+const FILTER_FUNCTIONS: [&str; 2] = ["CALCULATE", "CALCULATETABLE"];
+
+/// Structural edits between two DAX expressions. Empty when either side does not parse, is too
+/// large to diff, or the two are equivalent.
+pub(crate) fn dax_edits(old: &str, new: &str) -> Vec<DaxEdit> {
+    let (Ok(old_expr), Ok(new_expr)) = (parse_dax(old), parse_dax(new)) else {
+        return Vec::new();
+    };
+    expr_edits(&old_expr, &new_expr)
+}
+
+fn expr_edits(old: &DaxExpr, new: &DaxExpr) -> Vec<DaxEdit> {
+    let old_tree = ExprTree::build(old);
+    let new_tree = ExprTree::build(new);
+    if old_tree.nodes.len().max(new_tree.nodes.len()) > MAX_EDIT_TREE_NODES {
+        return Vec::new();
+    }
+
+    let script = tree_edit_script(&old_tree.simple(), &new_tree.simple());
+    let mut edits = Vec::new();
+    // Roots of replaced subtrees; edits below them are already covered by the replacement.
+    let mut replaced_old: Vec<usize> = Vec::new();
+    let mut replaced_new: Vec<usize> = Vec::new();
+    let mut filters = FilterLog::default();
+    for step in script {
+        match step {
+            TreeEdit::Update { old: oi, new: ni } => {
+                if old_tree.within(oi, &replaced_old) || new_tree.within(ni, &replaced_new) {
+                    continue;
+                }
+                if let Some(new_root) = new_tree.filter_root(ni) {
+                    let old_root = old_tree
+                        .filter_root(oi)
+                        .or_else(|| new_tree.counterpart(new_root, &old_tree));
+                    edits.extend(filters.changed(&old_tree, &new_tree, old_root, Some(new_root)));
+                    continue;
+                }
+                let (kind, from, to) = match update_kind(old_tree.nodes[oi], new_tree.nodes[ni]) {
+                    Some(kind) => {
+                        let (from, to) = update_text(old_tree.nodes[oi], new_tree.nodes[ni]);
+                        (kind, from, to)
+                    }
+                    None => {
+                        replaced_old.push(oi);
+                        replaced_new.push(ni);
+                        (
+                            DaxEditKind::Replaced,
+                            old_tree.nodes[oi].to_string(),
+                            new_tree.nodes[ni].to_string(),
+                        )
+                    }
+                };
+                edits.push(new_tree.context(ni).edit(kind, Some(from), Some(to)));
+            }
+            TreeEdit::Insert { new: ni } => {
+                if new_tree.within(ni, &replaced_new) {
+                    continue;
+                }
+                let text = new_tree.nodes[ni].to_string();
+                match new_tree.filter_root(ni) {
+                    Some(root) if root == ni => {
+                        filters.new.push(root);
+                        let context = new_tree.context(ni);
+                        edits.push(context.edit(DaxEditKind::FilterAdded, None, Some(text)));
+                    }
+                    Some(root) => {
+                        let old_root = new_tree.counterpart(root, &old_tree);
+                        edits.extend(filters.changed(&old_tree, &new_tree, old_root, Some(root)));
+                    }
+                    None => {
+                        let context = new_tree.context(ni);
+                        edits.push(context.edit(DaxEditKind::Inserted, None, Some(text)));
+                    }
+                }
+            }
+            TreeEdit::Delete { old: oi } => {
+                if old_tree.within(oi, &replaced_old) {
+                    continue;
+                }
+                let text = old_tree.nodes[oi].to_string();
+                match old_tree.filter_root(oi) {
+                    Some(root) if root == oi => {
+                        filters.old.push(root);
+                        let context = old_tree.context(oi);
+                        edits.push(context.edit(DaxEditKind::FilterRemoved, Some(text), None));
+                    }
+                    Some(root) => {
+                        let new_root = old_tree.counterpart(root, &new_tree);
+                        edits.extend(filters.changed(&old_tree, &new_tree, Some(root), new_root));
+                    }
+                    None => {
+                        let context = old_tree.context(oi);
+                        edits.push(context.edit(DaxEditKind::Removed, Some(text), None));
+                    }
+                }
+            }
+        }
+    }
+
+    if edits.len() > MAX_DAX_EDITS {
+        return vec![DaxEdit {
+            kind: DaxEditKind::Replaced,
+            function: None,
+            argument: None,
+            from: Some(old.to_string()),
+            to: Some(new.to_string()),
+        }];
+    }
+    edits
+}
+
+/// Filter arguments already reported, as argument roots on each side.
+#[derive(Default)]
+struct FilterLog {
+    old: Vec<usize>,
+    new: Vec<usize>,
+}
+
+impl FilterLog {
+    /// One `FilterChanged` for the filter argument rooted at `old_root`/`new_root`, unless an
+    /// edit inside it was already reported. Both sides are filled whenever the argument exists
+    /// on both, whichever side the triggering edit was on.
+    fn changed(
+        &mut self,
+        old_tree: &ExprTree,
+        new_tree: &ExprTree,
+        old_root: Option<usize>,
+        new_root: Option<usize>,
+    ) -> Option<DaxEdit> {
+        if old_root.is_some_and(|r| self.old.contains(&r))
+            || new_root.is_some_and(|r| self.new.contains(&r))
+        {
+            return None;
+        }
+        self.old.extend(old_root);
+        self.new.extend(new_root);
+        let context = match new_root {
+            Some(root) => new_tree.context(root),
+            None => old_tree.context(old_root?),
+        };
+        Some(context.edit(
+            DaxEditKind::FilterChanged,
+            old_root.map(|r| old_tree.nodes[r].to_string()),
+            new_root.map(|r| new_tree.nodes[r].to_string()),
+        ))
+    }
+}
+
+/// Where an edited node sits: its innermost enclosing call and the 1-based argument leading to
+/// it.
+struct EditContext {
+    function: Option<String>,
+    argument: Option<u32>,
+}
+
+impl EditContext {
+    fn edit(&self, kind: DaxEditKind, from: Option<String>, to: Option<String>) -> DaxEdit {
+        DaxEdit {
+            kind,
+            function: self.function.clone(),
+            argument: self.argument,
+            from,
+            to,
+        }
+    }
+}
+
+/// A DAX tree flattened in preorder, with each node's parent and position among its siblings.
+struct ExprTree<'a> {
+    nodes: Vec<&'a DaxExpr>,
+    parents: Vec<Option<usize>>,
+    positions: Vec<u32>,
+    children: Vec<Vec<usize>>,
+}
+
+impl<'a> ExprTree<'a> {
+    fn build(root: &'a DaxExpr) -> Self {
+        let mut tree = ExprTree {
+            nodes: Vec::new(),
+            parents: Vec::new(),
+            positions: Vec::new(),
+            children: Vec::new(),
+        };
+        tree.push(root, None, 0);
+        tree
+    }
+
+    fn push(&mut self, expr: &'a DaxExpr, parent: Option<usize>, position: u32) -> usize {
+        let idx = self.nodes.len();
+        self.nodes.push(expr);
+        self.parents.push(parent);
+        self.positions.push(position);
+        self.children.push(Vec::new());
+        for (i, child) in expr.children().into_iter().enumerate() {
+            let child_idx = self.push(child, Some(idx), i as u32);
+            self.children[idx].push(child_idx);
+        }
+        idx
+    }
+
+    fn simple(&self) -> SimpleTree {
+        simple_tree(
+            self.nodes.iter().map(|expr| node_label(expr)).collect(),
+            self.children.clone(),
+        )
+    }
+
+    /// Whether `idx` is one of `roots` or below one of them.
+    fn within(&self, idx: usize, roots: &[usize]) -> bool {
+        let mut cur = Some(idx);
+        while let Some(node) = cur {
+            if roots.contains(&node) {
+                return true;
+            }
+            cur = self.parents[node];
+        }
+        false
+    }
+
+    /// The node of `other` reached by the same argument positions from the root, passing through
+    /// calls of the same names: where an argument of this tree sits on the other side.
+    fn counterpart(&self, idx: usize, other: &ExprTree) -> Option<usize> {
+        let mut path = Vec::new();
+        let mut child = idx;
+        while let Some(parent) = self.parents[child] {
+            path.push((parent, self.positions[child]));
+            child = parent;
+        }
+        let mut other_idx = 0;
+        for &(parent, position) in path.iter().rev() {
+            if let DaxExpr::Call { name, .. } = self.nodes[parent] {
+                match other.nodes[other_idx] {
+                    DaxExpr::Call {
+                        name: other_name, ..
+                    } if other_name.eq_ignore_ascii_case(name) => {}
+                    _ => return None,
+                }
+            }
+            other_idx = *other.children[other_idx].get(position as usize)?;
+        }
+        Some(other_idx)
+    }
+
+    /// Root of the nearest filter argument of `CALCULATE`/`CALCULATETABLE` enclosing `idx`, through
+    /// any calls nested inside the filter.
+    fn filter_root(&self, idx: usize) -> Option<usize> {
+        let mut child = idx;
+        while let Some(parent) = self.parents[child] {
+            if let DaxExpr::Call { name, .. } = self.nodes[parent]
+                && self.positions[child] >= 1
+                && FILTER_FUNCTIONS
+                    .iter()
+                    .any(|f| f.eq_ignore_ascii_case(name))
+            {
+                return Some(child);
+            }
+            child = parent;
+        }
+        None
+    }
+
+    fn context(&self, idx: usize) -> EditContext {
+        let mut child = idx;
+        while let Some(parent) = self.parents[child] {
+            if let DaxExpr::Call { name, .. } = self.nodes[parent] {
+                return EditContext {
+                    function: Some(name.to_ascii_uppercase()),
+                    argument: Some(self.positions[child] + 1),
+                };
+            }
+            child = parent;
+        }
+        EditContext {
+            function: None,
+            argument: None,
+        }
+    }
+}
+
+/// Node label for the tree diff: the node kind plus what distinguishes it from siblings of the
+/// same kind, ignoring its children. Names compare case-insensitively, as DAX resolves them.
+fn node_label(expr: &DaxExpr) -> u64 {
+    use crate::hashing::XXH64_SEED;
+    let mut h = xxhash_rust::xxh64::Xxh64::new(XXH64_SEED);
+    match expr {
+        DaxExpr::Number(bits) => (0u8, bits).hash(&mut h),
+        DaxExpr::String(s) => (1u8, s).hash(&mut h),
+        DaxExpr::Boolean(b) => (2u8, b).hash(&mut h),
+        DaxExpr::Identifier(name) => (3u8, name.to_lowercase()).hash(&mut h),
+        DaxExpr::BracketRef(name) => (4u8, name.to_lowercase()).hash(&mut h),
+        DaxExpr::TableColumnRef { table, column } => {
+            (5u8, table.to_lowercase(), column.to_lowercase()).hash(&mut h)
+        }
+        DaxExpr::Call { name, .. } => (6u8, name.to_lowercase()).hash(&mut h),
+        DaxExpr::Unary { op, .. } => (7u8, op).hash(&mut h),
+        DaxExpr::Binary { op, .. } => (8u8, op).hash(&mut h),
+        DaxExpr::VarBlock { vars, .. } => {
+            let names: Vec<String> = vars.iter().map(|(name, _)| name.to_lowercase()).collect();
+            (9u8, names).hash(&mut h)
+        }
+    }
+    h.finish()
+}
+
+/// How a node changed in place, or `None` when it became a different kind of expression.
+fn update_kind(old: &DaxExpr, new: &DaxExpr) -> Option<DaxEditKind> {
+    use DaxExpr as E;
+    match (old, new) {
+        (E::Call { .. }, E::Call { .. }) => Some(DaxEditKind::FunctionRenamed),
+        (
+            E::Identifier(_) | E::BracketRef(_) | E::TableColumnRef { .. },
+            E::Identifier(_) | E::BracketRef(_) | E::TableColumnRef { .. },
+        ) => Some(DaxEditKind::ReferenceChanged),
+        (E::Unary { .. }, E::Unary { .. }) | (E::Binary { .. }, E::Binary { .. }) => {
+            Some(DaxEditKind::OperatorChanged)
+        }
+        (
+            E::Number(_) | E::String(_) | E::Boolean(_),
+            E::Number(_) | E::String(_) | E::Boolean(_),
+        ) => Some(DaxEditKind::ValueChanged),
+        _ => None,
+    }
+}
+
+/// Text for an in-place change: the function name or operator for call and operator nodes,
+/// the node itself otherwise.
+fn update_text(old: &DaxExpr, new: &DaxExpr) -> (String, String) {
+    let text = |expr: &DaxExpr| match expr {
+        DaxExpr::Call { name, .. } => name.to_ascii_uppercase(),
+        DaxExpr::Binary { op, .. } => op.symbol().to_string(),
+        DaxExpr::Unary { op, .. } => op.symbol().trim().to_string(),
+        other => other.to_string(),
+    };
+    (text(old), text(new))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edits(old: &str, new: &str) -> Vec<(DaxEditKind, Option<String>, Option<String>)> {
+        dax_edits(old, new)
+            .into_iter()
+            .map(|e| (e.kind, e.from, e.to))
+            .collect()
+    }
+
+    #[test]
+    fn reports_changed_reference_with_call_context() {
+        let out = dax_edits("SUM(Sales[Amount])", "SUM(Sales[Net])");
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].kind, DaxEditKind::ReferenceChanged);
+        assert_eq!(out[0].function.as_deref(), Some("SUM"));
+        assert_eq!(out[0].argument, Some(1));
+        assert_eq!(out[0].from.as_deref(), Some("Sales[Amount]"));
+        assert_eq!(out[0].to.as_deref(), Some("Sales[Net]"));
+    }
+
+    #[test]
+    fn reports_renamed_function() {
+        assert_eq!(
+            edits("SUM(Sales[Amount])", "AVERAGE(Sales[Amount])"),
+            vec![(
+                DaxEditKind::FunctionRenamed,
+                Some("SUM".to_string()),
+                Some("AVERAGE".to_string())
+            )]
+        );
+    }
+
+    #[test]
+    fn folds_changes_inside_a_calculate_filter_into_one_edit() {
+        let out = dax_edits(
+            "CALCULATE([Total], Sales[Region] = \"West\", Sales[Year] = 2023)",
+            "CALCULATE([Total], Sales[Region] = \"East\", Sales[Year] = 2024)",
+        );
+        assert_eq!(out.len(), 2, "{out:?}");
+        assert!(out.iter().all(|e| e.kind == DaxEditKind::FilterChanged));
+        assert_eq!(out[0].argument, Some(2));
+        assert_eq!(out[0].from.as_deref(), Some("Sales[Region] = \"West\""));
+        assert_eq!(out[0].to.as_deref(), Some("Sales[Region] = \"East\""));
+        assert_eq!(out[1].argument, Some(3));
+    }
+
+    #[test]
+    fn condition_added_inside_a_filter_is_one_change_with_both_sides() {
+        let one = "CALCULATE([Total], AND(Sales[Region] = \"West\", Sales[Year] = 2024))";
+        let two =
+            "CALCULATE([Total], AND(Sales[Region] = \"West\", Sales[Year] = 2024, Sales[Q] = 1))";
+        let filter_one = "AND(Sales[Region] = \"West\", Sales[Year] = 2024)";
+        let filter_two = "AND(Sales[Region] = \"West\", Sales[Year] = 2024, Sales[Q] = 1)";
+        assert_eq!(
+            edits(one, two),
+            vec![(
+                DaxEditKind::FilterChanged,
+                Some(filter_one.to_string()),
+                Some(filter_two.to_string())
+            )]
+        );
+        assert_eq!(
+            edits(two, one),
+            vec![(
+                DaxEditKind::FilterChanged,
+                Some(filter_two.to_string()),
+                Some(filter_one.to_string())
+            )]
+        );
+    }
+
+    #[test]
+    fn reports_added_and_removed_filters() {
+        assert_eq!(
+            edits(
+                "CALCULATE([Total], Sales[Region] = \"West\")",
+                "CALCULATE([Total], Sales[Region] = \"West\", ALL(Dates))"
+            ),
+            vec![(
+                DaxEditKind::FilterAdded,
+                None,
+                Some("ALL(Dates)".to_string())
+            )]
+        );
+        assert_eq!(
+            edits(
+                "CALCULATE([Total], Sales[Region] = \"West\", ALL(Dates))",
+                "CALCULATE([Total], ALL(Dates))"
+            ),
+            vec![(
+                DaxEditKind::FilterRemoved,
+                Some("Sales[Region] = \"West\"".to_string()),
+                None
+            )]
+        );
+    }
+
+    #[test]
+    fn case_and_whitespace_changes_produce_no_edits() {
+        assert!(dax_edits("SUM(Sales[Amount])", "sum ( sales[amount] )").is_empty());
+    }
+
+    #[test]
+    fn unparseable_side_produces_no_edits() {
+        assert!(dax_edits("SUM(", "SUM(Sales[Amount])").is_empty());
+    }
+}
@@ -7,7 +7,12 @@
 
 use crate::error_codes;
 use crate::string_pool::StringId;
-use crate::workbook::{CellAddress, CellSnapshot, CellValue, ColSignature, RowSignature};
+use crate::vba::{VbaModuleType, VbaProcedureKind};
+use crate::workbook::{
+    CellAddress, CellComment, CellRange, CellSnapshot, CellStyle, CellValue, ColSignature,
+    ConditionalFormatRule, DataValidationRule, DrawingAnchor, DrawingObject, ExternalLink,
+    FreezePane, PivotSource, RowSignature, SheetProtection, SheetVisibility, WorkbookProtection,
+};
 use thiserror::Error;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -234,6 +239,44 @@ impl Default for FormulaDiffResult {
     }
 }
 
+/// One structural change between the old and new formula of an edited cell, from a tree diff
+/// of the parsed formulas. Expression text is rendered in A1 form without the leading `=`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct FormulaEdit {
+    pub kind: FormulaEditKind,
+    /// Innermost function call around the changed part, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+    /// 1-based argument of `function` holding the changed part.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub argument: Option<u32>,
+    /// Old text; `None` for insertions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    /// New text; `None` for deletions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FormulaEditKind {
+    /// A function call was renamed; `from` and `to` are the names.
+    FunctionRenamed,
+    /// A cell or range reference changed.
+    ReferenceChanged,
+    /// An operator changed; `from` and `to` are the operator symbols.
+    OperatorChanged,
+    /// A literal or defined name changed.
+    ValueChanged,
+    /// An expression was replaced by a different kind of expression.
+    Replaced,
+    /// An argument, operand or array item was added.
+    Inserted,
+    /// An argument, operand or array item was removed.
+    Removed,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum QueryMetadataField {
     /// Whether the query loads to a sheet.
@@ -265,6 +308,127 @@ pub enum RelationshipProperty {
     IsActive,
 }
 
+/// One structural change between the old and new DAX of a measure or calculated column, from a
+/// tree diff of the parsed expressions. Text is rendered on a single line.
+#[cfg(feature = "model-diff")]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DaxEdit {
+    pub kind: DaxEditKind,
+    /// Innermost function call around the changed part, uppercased.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+    /// 1-based argument of `function` holding the changed part.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub argument: Option<u32>,
+    /// Old text; `None` for insertions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    /// New text; `None` for deletions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+}
+
+#[cfg(feature = "model-diff")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DaxEditKind {
+    /// A function call was renamed; `from` and `to` are the names.
+    FunctionRenamed,
+    /// A column, measure, table or variable reference changed.
+    ReferenceChanged,
+    /// An operator changed; `from` and `to` are the operator symbols.
+    OperatorChanged,
+    /// A literal changed.
+    ValueChanged,
+    /// A filter argument was added to `CALCULATE` or `CALCULATETABLE`.
+    FilterAdded,
+    /// A filter argument was removed from `CALCULATE` or `CALCULATETABLE`.
+    FilterRemoved,
+    /// A filter argument of `CALCULATE` or `CALCULATETABLE` changed; `from` and `to` are the
+    /// whole argument.
+    FilterChanged,
+    /// An expression was replaced by a different kind of expression.
+    Replaced,
+    /// An argument or operand was added.
+    Inserted,
+    /// An argument or operand was removed.
+    Removed,
+}
+
+#[cfg(feature = "model-diff")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MeasureProperty {
+    FormatString,
+    DisplayFolder,
+    Description,
+    KpiTarget,
+    KpiStatus,
+    KpiTrend,
+}
+
+/// Tabular model object named by the `ModelObject*` ops.
+#[cfg(feature = "model-diff")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelObjectKind {
+    /// Security role; its row-level filters are reported as [`ModelObjectField::RowFilter`].
+    Role,
+    Hierarchy,
+    Partition,
+    /// A calculation group, named by its table.
+    CalculationGroup,
+    CalculationItem,
+    Perspective,
+    /// Translations for one culture, e.g. `fr-FR`.
+    Culture,
+}
+
+/// A model object property compared by [`DiffOp::ModelObjectChanged`].
+#[cfg(feature = "model-diff")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelObjectField {
+    Description,
+    /// Role permission on the model: `read`, `readRefresh`, `refresh` or `administrator`.
+    ModelPermission,
+    /// Row-level security DAX filter of a role on the table named by `item`.
+    RowFilter,
+    /// Object-level security on the table or column named by `item`.
+    ObjectPermission,
+    /// A role member, or an object included in a perspective.
+    Member,
+    Hidden,
+    DisplayFolder,
+    /// Column of the hierarchy level named by `item`.
+    Level,
+    /// Hierarchy level names in order, when only the order changed.
+    LevelOrder,
+    /// Partition storage mode, e.g. `import` or `directQuery`.
+    Mode,
+    /// Partition source type, e.g. `m` or `query`.
+    SourceType,
+    /// Partition M expression or query.
+    Source,
+    Precedence,
+    Expression,
+    FormatStringExpression,
+    Ordinal,
+    /// Translated text for the object property named by `item`.
+    Translation,
+}
+
+#[cfg(feature = "model-diff")]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ModelObjectChange {
+    pub field: ModelObjectField,
+    /// Part of the object the change applies to, e.g. the table of a row filter or a level.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item: Option<StringId>,
+    pub from: Option<StringId>,
+    pub to: Option<StringId>,
+}
+
 /// Errors produced by diffing APIs.
 #[derive(Debug, Error)]
 #[non_exhaustive]
@@ -291,6 +455,15 @@ pub enum DiffError {
 
     #[error("[EXDIFF_DIFF_004] internal error: {message}. Suggestion: report a bug with the input file if possible.")]
     InternalError { message: String },
+
+    /// A header-named database-mode key column is missing; `row` is the 1-based header row.
+    #[error("[EXDIFF_DIFF_005] key column '{column}' not found in row {row} of the {side} sheet. Headers: {}. Suggestion: check the header spelling and the header row.", available.join(", "))]
+    KeyColumnNotFound {
+        column: String,
+        side: String,
+        row: u32,
+        available: Vec<String>,
+    },
 }
 
 impl DiffError {
@@ -300,6 +473,7 @@ impl DiffError {
             DiffError::SinkError { .. } => error_codes::DIFF_SINK_ERROR,
             DiffError::SheetNotFound { .. } => error_codes::DIFF_SHEET_NOT_FOUND,
             DiffError::InternalError { .. } => error_codes::DIFF_INTERNAL_ERROR,
+            DiffError::KeyColumnNotFound { .. } => error_codes::DIFF_KEY_COLUMN_NOT_FOUND,
         }
     }
 }
@@ -320,6 +494,273 @@ pub struct DiffSummary {
     pub metrics: Option<crate::perf::DiffMetrics>,
 }
 
+/// One changed field of a keyed record; see [`DiffOp::RecordChanged`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct FieldChange {
+    /// Column in the new sheet; the old value may come from another column with the same header.
+    pub col: u32,
+    /// Header text of the column, when the header cell holds text.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<StringId>,
+    pub from: Option<CellValue>,
+    pub to: Option<CellValue>,
+}
+
+/// Role of a line within a [`TextHunk`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TextLineKind {
+    Context,
+    Removed,
+    Added,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TextLine {
+    pub kind: TextLineKind,
+    pub text: StringId,
+}
+
+/// A unified-diff style hunk. Starts are 1-based line numbers; `procedure` names the VBA
+/// procedure the hunk's first changed line belongs to.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TextHunk {
+    pub old_start: u32,
+    pub old_len: u32,
+    pub new_start: u32,
+    pub new_len: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub procedure: Option<StringId>,
+    pub lines: Vec<TextLine>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VbaProcedureChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct VbaProcedureChange {
+    pub name: StringId,
+    pub kind: VbaProcedureKind,
+    pub change: VbaProcedureChangeKind,
+}
+
+/// A module `Attribute` (e.g. `VB_PredeclaredId`) added, removed or given a new value.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct VbaAttributeChange {
+    pub name: StringId,
+    pub from: Option<StringId>,
+    pub to: Option<StringId>,
+}
+
+/// The part of a pivot table layout compared by [`DiffOp::PivotTableLayoutChanged`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum PivotArea {
+    /// Row fields in order, comma separated.
+    Rows,
+    /// Column fields in order, comma separated.
+    Columns,
+    /// A report filter (page) field and its selected item, `(All)` when none is selected.
+    Pages,
+    /// A value field as `caption: function`, with `showDataAs` in parentheses.
+    Values,
+    /// Item filters on a field, e.g. `manual: West, North` for hidden items.
+    Filters,
+    /// A calculated field's formula.
+    CalculatedFields,
+}
+
+/// One pivot layout difference. `field` names the field for per-field areas and is `None` for
+/// rows and columns; `from`/`to` are `None` when the entry is added or removed.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PivotLayoutChange {
+    pub area: PivotArea,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<StringId>,
+    pub from: Option<StringId>,
+    pub to: Option<StringId>,
+}
+
+/// A connection property compared by [`DiffOp::ConnectionChanged`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ConnectionField {
+    Name,
+    /// Connection type, e.g. `odbc` or `oledb`.
+    Kind,
+    /// Connection string, URL or source file, with secrets redacted.
+    Connection,
+    Command,
+    RefreshOnLoad,
+    BackgroundRefresh,
+    /// Minutes between automatic refreshes, `0` when off.
+    RefreshInterval,
+    SavePassword,
+}
+
+/// One connection property with its old and new value. Flags are `true`/`false`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ConnectionChange {
+    pub field: ConnectionField,
+    pub from: Option<StringId>,
+    pub to: Option<StringId>,
+}
+
+/// A workbook setting compared by [`DiffOp::WorkbookSettingsChanged`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum WorkbookSetting {
+    /// The 1904 date system.
+    Date1904,
+    CodeName,
+    /// `automatic`, `automatic_no_table` or `manual`.
+    CalcMode,
+    FullCalcOnLoad,
+    CalcOnSave,
+    /// Iterative calculation of circular references.
+    Iterate,
+    IterateCount,
+    IterateDelta,
+    /// `false` means "set precision as displayed".
+    FullPrecision,
+}
+
+/// One workbook setting with its old and new value. Flags are `true`/`false`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct WorkbookSettingChange {
+    pub setting: WorkbookSetting,
+    pub from: Option<StringId>,
+    pub to: Option<StringId>,
+}
+
+/// A chart property compared by [`DiffOp::ChartChanged`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ChartField {
+    Title,
+    /// Legend position; `None` means the chart has no legend.
+    Legend,
+    /// A whole series was added or removed; the value carries its value reference.
+    Series,
+    SeriesName,
+    /// Plot type of the series, e.g. `barChart` to `lineChart`.
+    SeriesType,
+    SeriesCategories,
+    SeriesValues,
+    /// A whole axis was added or removed.
+    Axis,
+    AxisTitle,
+    AxisPosition,
+    AxisMin,
+    AxisMax,
+    AxisLogBase,
+    AxisOrientation,
+    AxisDeleted,
+}
+
+/// One chart field with its old and new value. Series references in `from` are as written in
+/// the old workbook; they only count as changed when they differ after sheet renames.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ChartChange {
+    pub field: ChartField,
+    /// `c:idx` of the series, for series fields.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub series: Option<u32>,
+    /// Axis label for axis fields, e.g. `value axis (left)`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub axis: Option<StringId>,
+    pub from: Option<StringId>,
+    pub to: Option<StringId>,
+}
+
+/// Power BI report or model object, shared by the `ReportObject*` ops of PBIX/PBIT diffs and the
+/// entity diff of PBIP projects.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+pub enum PbipEntityKind {
+    /// The report as a whole: theme and report-level filters.
+    Report,
+    Page,
+    Visual,
+    Theme,
+    Bookmark,
+    Model,
+    Table,
+    Column,
+    Measure,
+    Relationship,
+    Other,
+}
+
+impl PbipEntityKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PbipEntityKind::Report => "Report",
+            PbipEntityKind::Page => "Page",
+            PbipEntityKind::Visual => "Visual",
+            PbipEntityKind::Theme => "Theme",
+            PbipEntityKind::Bookmark => "Bookmark",
+            PbipEntityKind::Model => "Model",
+            PbipEntityKind::Table => "Table",
+            PbipEntityKind::Column => "Column",
+            PbipEntityKind::Measure => "Measure",
+            PbipEntityKind::Relationship => "Relationship",
+            PbipEntityKind::Other => "Other",
+        }
+    }
+}
+
+/// A report property compared by [`DiffOp::ReportObjectChanged`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ReportField {
+    DisplayName,
+    /// 1-based position of a page in the tab order.
+    Order,
+    /// Page size as `width×height`.
+    Size,
+    Hidden,
+    /// Base theme of the report.
+    Theme,
+    /// Visual type, e.g. `clusteredBarChart`.
+    VisualType,
+    Title,
+    /// A field bound to a visual, as `role: queryRef`, e.g. `Y: Sum(Sales.Amount)`.
+    Field,
+    /// A filter on the report, a page or a visual, with its target and values.
+    Filter,
+    /// Visual position as `x, y, width×height`.
+    Position,
+    /// Page a bookmark opens.
+    Page,
+}
+
+/// One report property with its old and new value. A `None` side means the field or filter
+/// was added or removed.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ReportChange {
+    pub field: ReportField,
+    pub from: Option<StringId>,
+    pub to: Option<StringId>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum VbaProjectProperty {
+    /// Project name.
+    Name,
+    /// Project description.
+    Description,
+    HelpFile,
+    HelpContext,
+    /// Conditional compilation constants.
+    Constants,
+    /// Whether "Lock project for viewing" is set.
+    LockedForViewing,
+    /// Whether a project password is set.
+    Password,
+}
+
 /// A single diff operation representing one logical change between workbooks.
 ///
 /// Operations are emitted by the diff engine and collected into a [`DiffReport`].
@@ -357,6 +798,18 @@ pub enum DiffOp {
         left_rows: Vec<u32>,
         right_rows: Vec<u32>,
     },
+    /// A record matched by key in database mode whose non-key fields changed.
+    ///
+    /// Emitted after the record's `CellEdited` ops and summarising them: each field is one of
+    /// those edits, paired by column header. Consumers that count changes should count either
+    /// the record or its cell edits, not both.
+    RecordChanged {
+        sheet: SheetId,
+        key: Vec<Option<CellValue>>,
+        old_row: u32,
+        new_row: u32,
+        fields: Vec<FieldChange>,
+    },
     RowReplaced {
         sheet: SheetId,
         row_idx: u32,
@@ -423,6 +876,222 @@ pub enum DiffOp {
         to: CellSnapshot,
         #[serde(default)]
         formula_diff: FormulaDiffResult,
+        /// Structural formula changes, filled in when semantic formula diff classifies the edit
+        /// as a `SemanticChange`.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        formula_edits: Vec<FormulaEdit>,
+    },
+    /// Formatting change on a single cell (number format, font, fill, border, alignment or
+    /// protection). Emitted independently of `CellEdited`; gated by
+    /// `SemanticConfig::enable_cell_style_diff`.
+    CellStyleChanged {
+        sheet: SheetId,
+        addr: CellAddress,
+        from: CellStyle,
+        to: CellStyle,
+    },
+    /// A note or threaded comment appeared on a cell. `addr` is the new-grid position.
+    CommentAdded {
+        sheet: SheetId,
+        addr: CellAddress,
+        comment: CellComment,
+    },
+    /// A note or threaded comment disappeared, either deleted or dropped with its row or
+    /// column. `addr` is the old-grid position.
+    CommentRemoved {
+        sheet: SheetId,
+        addr: CellAddress,
+        comment: CellComment,
+    },
+    /// The comment anchored to a cell changed text, author, replies or resolved state.
+    /// Comments follow their cell through row/column alignment, so `addr` is the new-grid
+    /// position.
+    CommentChanged {
+        sheet: SheetId,
+        addr: CellAddress,
+        from: CellComment,
+        to: CellComment,
+    },
+    /// A data validation rule appeared. Its ranges are new-grid positions.
+    DataValidationAdded {
+        sheet: SheetId,
+        rule: DataValidationRule,
+    },
+    /// A data validation rule was deleted, or every cell it covered was removed. Its ranges are
+    /// old-grid positions.
+    DataValidationRemoved {
+        sheet: SheetId,
+        rule: DataValidationRule,
+    },
+    /// A data validation rule changed its ranges (after row/column alignment), type, operator,
+    /// formulas or blank handling. `from` ranges are old-grid positions.
+    DataValidationChanged {
+        sheet: SheetId,
+        from: DataValidationRule,
+        to: DataValidationRule,
+    },
+    /// A conditional formatting rule appeared. Its ranges are new-grid positions.
+    ConditionalFormatAdded {
+        sheet: SheetId,
+        rule: ConditionalFormatRule,
+    },
+    /// A conditional formatting rule was deleted, or every cell it covered was removed. Its
+    /// ranges are old-grid positions.
+    ConditionalFormatRemoved {
+        sheet: SheetId,
+        rule: ConditionalFormatRule,
+    },
+    /// A conditional formatting rule changed its ranges (after row/column alignment), type,
+    /// operator, formulas, priority or `stopIfTrue`. A rule keeping its type, formulas and
+    /// priority is paired even when its ranges moved; `from` ranges are old-grid positions.
+    ConditionalFormatChanged {
+        sheet: SheetId,
+        from: ConditionalFormatRule,
+        to: ConditionalFormatRule,
+    },
+    /// A merged range appeared. The range is in new-grid positions.
+    MergedRangeAdded {
+        sheet: SheetId,
+        range: CellRange,
+    },
+    /// A merged range was unmerged, or every cell it covered was removed. The range is in
+    /// old-grid positions.
+    MergedRangeRemoved {
+        sheet: SheetId,
+        range: CellRange,
+    },
+    /// A row was hidden or unhidden. `row_idx` is the new-grid index; `hidden` is the new state.
+    RowVisibilityChanged {
+        sheet: SheetId,
+        row_idx: u32,
+        hidden: bool,
+    },
+    /// A column was hidden or unhidden. `col_idx` is the new-grid index; `hidden` is the new
+    /// state.
+    ColumnVisibilityChanged {
+        sheet: SheetId,
+        col_idx: u32,
+        hidden: bool,
+    },
+    /// A row's outline (grouping) level changed.
+    RowOutlineChanged {
+        sheet: SheetId,
+        row_idx: u32,
+        from: u8,
+        to: u8,
+    },
+    /// A column's outline (grouping) level changed.
+    ColumnOutlineChanged {
+        sheet: SheetId,
+        col_idx: u32,
+        from: u8,
+        to: u8,
+    },
+    /// A row's custom height changed, in twips (1/20 pt). `None` is the sheet default.
+    RowHeightChanged {
+        sheet: SheetId,
+        row_idx: u32,
+        from: Option<u32>,
+        to: Option<u32>,
+    },
+    /// A column's custom width changed, in 1/256 of a character width. `None` is the sheet
+    /// default.
+    ColumnWidthChanged {
+        sheet: SheetId,
+        col_idx: u32,
+        from: Option<u32>,
+        to: Option<u32>,
+    },
+    /// Frozen panes were added, removed or moved. The old pane is translated through the row
+    /// and column alignment before comparing.
+    FreezePaneChanged {
+        sheet: SheetId,
+        from: Option<FreezePane>,
+        to: Option<FreezePane>,
+    },
+    /// An array formula's range appeared, disappeared or changed size; for dynamic arrays this
+    /// is the spill range. `from` is in old-grid positions and `to` in new-grid positions.
+    /// `addr` is the anchor cell holding the formula: the new anchor unless the array was
+    /// removed.
+    SpillRangeChanged {
+        sheet: SheetId,
+        addr: CellAddress,
+        from: Option<CellRange>,
+        to: Option<CellRange>,
+    },
+    /// A sheet was hidden, very-hidden or unhidden.
+    SheetVisibilityChanged {
+        sheet: SheetId,
+        from: SheetVisibility,
+        to: SheetVisibility,
+    },
+    /// A sheet's tab colour changed. Colours use the cell-format descriptor form.
+    SheetTabColorChanged {
+        sheet: SheetId,
+        from: Option<StringId>,
+        to: Option<StringId>,
+    },
+    /// Sheet protection was turned on or off, or allows other actions.
+    SheetProtectionChanged {
+        sheet: SheetId,
+        from: Option<SheetProtection>,
+        to: Option<SheetProtection>,
+    },
+    /// An Excel table (ListObject) was added. `range` includes header and totals rows.
+    ExcelTableAdded {
+        sheet: SheetId,
+        name: StringId,
+        range: CellRange,
+    },
+    ExcelTableRemoved {
+        sheet: SheetId,
+        name: StringId,
+        range: CellRange,
+    },
+    /// A table kept its id but changed its display name.
+    ExcelTableRenamed {
+        sheet: SheetId,
+        from: StringId,
+        to: StringId,
+    },
+    /// A table's range changed beyond what inserted or removed rows and columns explain.
+    ExcelTableResized {
+        sheet: SheetId,
+        name: StringId,
+        from: CellRange,
+        to: CellRange,
+    },
+    /// A table column kept its id but changed its header name.
+    ExcelTableColumnRenamed {
+        sheet: SheetId,
+        table: StringId,
+        from: StringId,
+        to: StringId,
+    },
+    /// A shape, picture, text box or form control appeared in a sheet drawing. Its anchor is a
+    /// new-grid position.
+    DrawingObjectAdded {
+        sheet: SheetId,
+        object: DrawingObject,
+    },
+    /// A drawing object was deleted. Its anchor is an old-grid position.
+    DrawingObjectRemoved {
+        sheet: SheetId,
+        object: DrawingObject,
+    },
+    /// A drawing object's anchor moved beyond what inserted or removed rows and columns explain.
+    DrawingObjectMoved {
+        sheet: SheetId,
+        name: StringId,
+        from: DrawingAnchor,
+        to: DrawingAnchor,
+    },
+    /// A drawing object kept its name or id but changed kind, name, text, assigned macro or
+    /// image content. Anchors are reported separately by `DrawingObjectMoved`.
+    DrawingObjectChanged {
+        sheet: SheetId,
+        from: DrawingObject,
+        to: DrawingObject,
     },
 
     VbaModuleAdded {
@@ -431,8 +1100,39 @@ pub enum DiffOp {
     VbaModuleRemoved {
         name: StringId,
     },
+    /// A module's code, attributes or type changed. `hunks` cover code lines only (attribute
+    /// lines are reported in `attributes`), numbered as in the VBA editor.
     VbaModuleChanged {
         name: StringId,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        module_type: Option<(VbaModuleType, VbaModuleType)>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        attributes: Vec<VbaAttributeChange>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        procedures: Vec<VbaProcedureChange>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        hunks: Vec<TextHunk>,
+    },
+    /// A type library or VBA project reference was added. `libid` is the reference target, e.g.
+    /// `*\G{guid}#1.0#0#C:\Windows\System32\scrrun.dll#Microsoft Scripting Runtime`.
+    VbaReferenceAdded {
+        name: StringId,
+        libid: StringId,
+    },
+    VbaReferenceRemoved {
+        name: StringId,
+        libid: StringId,
+    },
+    /// A reference's target changed, e.g. a new library version or path.
+    VbaReferenceChanged {
+        name: StringId,
+        from: StringId,
+        to: StringId,
+    },
+    VbaProjectChanged {
+        property: VbaProjectProperty,
+        from: Option<StringId>,
+        to: Option<StringId>,
     },
 
     NamedRangeAdded {
@@ -455,9 +1155,78 @@ pub enum DiffOp {
         sheet: StringId,
         name: StringId,
     },
+    /// Chart part changed. `changes` is empty when only formatting or other unmodelled parts of
+    /// the chart XML differ.
     ChartChanged {
         sheet: StringId,
         name: StringId,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        changes: Vec<ChartChange>,
+    },
+
+    PivotTableAdded {
+        sheet: StringId,
+        name: StringId,
+    },
+    PivotTableRemoved {
+        sheet: StringId,
+        name: StringId,
+    },
+    /// The pivot cache source changed, or the data below the source range grew past it.
+    /// `uncovered_rows` counts the filled rows directly below the new source range that the pivot
+    /// does not read; it is 0 for table, named and external sources.
+    PivotTableSourceChanged {
+        sheet: StringId,
+        name: StringId,
+        from: PivotSource,
+        to: PivotSource,
+        #[serde(default)]
+        uncovered_rows: u32,
+    },
+    /// Row, column, page or value fields, item filters or calculated fields changed.
+    PivotTableLayoutChanged {
+        sheet: StringId,
+        name: StringId,
+        changes: Vec<PivotLayoutChange>,
+    },
+
+    ExternalLinkAdded {
+        index: u32,
+        target: StringId,
+    },
+    ExternalLinkRemoved {
+        index: u32,
+        target: StringId,
+    },
+    /// A link was repointed to another file, or its cached sheet names changed.
+    ExternalLinkChanged {
+        from: ExternalLink,
+        to: ExternalLink,
+    },
+    ConnectionAdded {
+        name: StringId,
+    },
+    ConnectionRemoved {
+        name: StringId,
+    },
+    ConnectionChanged {
+        name: StringId,
+        changes: Vec<ConnectionChange>,
+    },
+    /// `<workbookPr>` or `<calcPr>` settings changed, e.g. calculation switched to manual.
+    WorkbookSettingsChanged {
+        changes: Vec<WorkbookSettingChange>,
+    },
+    /// Workbook protection was turned on or off, or locks something else.
+    WorkbookProtectionChanged {
+        from: Option<WorkbookProtection>,
+        to: Option<WorkbookProtection>,
+    },
+    /// A document property was added (`from` is `None`), removed (`to` is `None`) or edited.
+    DocumentPropertyChanged {
+        name: StringId,
+        from: Option<StringId>,
+        to: Option<StringId>,
     },
 
     QueryAdded {
@@ -477,6 +1246,13 @@ pub enum DiffOp {
         new_hash: u64,
         #[serde(skip_serializing_if = "Option::is_none")]
         semantic_detail: Option<QuerySemanticDetail>,
+        /// Queries reading this one, directly or through other queries, in the new package.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        downstream_queries: Vec<StringId>,
+        /// Tables loaded to a sheet or the data model by this query or a downstream one, named
+        /// after their queries.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        loaded_tables: Vec<StringId>,
     },
     QueryMetadataChanged {
         name: StringId,
@@ -531,6 +1307,9 @@ pub enum DiffOp {
         change_kind: ExpressionChangeKind,
         old_hash: u64,
         new_hash: u64,
+        /// Structural DAX changes, filled in when the change is classified as `Semantic`.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        edits: Vec<DaxEdit>,
     },
     #[cfg(feature = "model-diff")]
     RelationshipAdded {
@@ -573,6 +1352,66 @@ pub enum DiffOp {
         change_kind: ExpressionChangeKind,
         old_hash: u64,
         new_hash: u64,
+        /// Structural DAX changes, filled in when the change is classified as `Semantic`.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        edits: Vec<DaxEdit>,
+    },
+    #[cfg(feature = "model-diff")]
+    MeasurePropertyChanged {
+        name: StringId,
+        field: MeasureProperty,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        old: Option<StringId>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        new: Option<StringId>,
+    },
+    /// A role, hierarchy, partition, calculation group or item, perspective or culture was
+    /// added. `table` is set for objects that belong to a table.
+    #[cfg(feature = "model-diff")]
+    ModelObjectAdded {
+        object: ModelObjectKind,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        table: Option<StringId>,
+        name: StringId,
+    },
+    #[cfg(feature = "model-diff")]
+    ModelObjectRemoved {
+        object: ModelObjectKind,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        table: Option<StringId>,
+        name: StringId,
+    },
+    #[cfg(feature = "model-diff")]
+    ModelObjectChanged {
+        object: ModelObjectKind,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        table: Option<StringId>,
+        name: StringId,
+        changes: Vec<ModelObjectChange>,
+    },
+
+    /// A page, visual or bookmark was added to a Power BI report. `page` is set for visuals.
+    ReportObjectAdded {
+        object: PbipEntityKind,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        page: Option<StringId>,
+        name: StringId,
+    },
+    ReportObjectRemoved {
+        object: PbipEntityKind,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        page: Option<StringId>,
+        name: StringId,
+    },
+    /// A report, page, visual or bookmark changed. Only emitted when at least one listed property
+    /// differs; formatting and other unmodelled edits are not reported.
+    ReportObjectChanged {
+        object: PbipEntityKind,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        page: Option<StringId>,
+        name: StringId,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        changes: Vec<ReportChange>,
     },
 }
 
@@ -681,7 +1520,7 @@ impl DiffReport {
     pub fn grid_ops(&self) -> impl Iterator<Item = &DiffOp> {
         self.ops
             .iter()
-            .filter(|op| !op.is_m_op() && !op.is_model_op())
+            .filter(|op| !op.is_m_op() && !op.is_model_op() && !op.is_report_op())
     }
 
     pub fn m_ops(&self) -> impl Iterator<Item = &DiffOp> {
@@ -719,6 +1558,10 @@ impl DiffOp {
                     | DiffOp::MeasureAdded { .. }
                     | DiffOp::MeasureRemoved { .. }
                     | DiffOp::MeasureDefinitionChanged { .. }
+                    | DiffOp::MeasurePropertyChanged { .. }
+                    | DiffOp::ModelObjectAdded { .. }
+                    | DiffOp::ModelObjectRemoved { .. }
+                    | DiffOp::ModelObjectChanged { .. }
             )
         }
         #[cfg(not(feature = "model-diff"))]
@@ -727,6 +1570,16 @@ impl DiffOp {
         }
     }
 
+    /// Ops from a Power BI report layout: pages, visuals, filters and bookmarks.
+    pub fn is_report_op(&self) -> bool {
+        matches!(
+            self,
+            DiffOp::ReportObjectAdded { .. }
+                | DiffOp::ReportObjectRemoved { .. }
+                | DiffOp::ReportObjectChanged { .. }
+        )
+    }
+
     pub fn cell_edited(
         sheet: SheetId,
         addr: CellAddress,
@@ -742,6 +1595,7 @@ impl DiffOp {
             from,
             to,
             formula_diff,
+            formula_edits: Vec::new(),
         }
     }
 
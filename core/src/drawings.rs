@@ -0,0 +1,339 @@
+//! Drawing part (`xl/drawings/drawing*.xml`) parsing for objects other than charts.
+//!
+//! Each top-level anchor holds one object: a shape, text box, picture, connector or group. Forms
+//! controls (buttons, check boxes) are shapes marked with an `a14:compatExt` extension; only the
+//! `mc:Choice` branch of an `mc:AlternateContent` block is read. Chart frames are left to the chart
+//! reader.
+
+use crate::comments::push_text;
+use crate::grid_parser::{local_tag_name, xml_err, GridParseError};
+use crate::string_pool::StringPool;
+use crate::styles::{attr, collect_attrs};
+use crate::workbook::{DrawingAnchor, DrawingMarker, DrawingObject, DrawingObjectKind};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+const ANCHOR_TAGS: [&str; 3] = ["twoCellAnchor", "oneCellAnchor", "absoluteAnchor"];
+
+/// A parsed object and the relationship id of its picture, if any.
+pub(crate) struct ParsedDrawingObject {
+    pub object: DrawingObject,
+    pub image_rel: Option<String>,
+}
+
+#[derive(Default)]
+struct ObjectText {
+    /// `None` for chart frames, which are skipped.
+    kind: Option<DrawingObjectKind>,
+    id: u32,
+    name: Option<String>,
+    text: String,
+    macro_name: Option<String>,
+    image_rel: Option<String>,
+    /// Whether the object's own `cNvPr` has been read; nested group members have their own.
+    named: bool,
+}
+
+#[derive(Default)]
+struct DrawingParser {
+    stack: Vec<String>,
+    /// Depth of an `mc:Fallback` element whose content is being skipped.
+    skip_until: Option<usize>,
+    anchor: Option<DrawingAnchor>,
+    /// Stack depth of the open anchor element.
+    anchor_depth: usize,
+    object: Option<ObjectText>,
+    text: String,
+    objects: Vec<ParsedDrawingObject>,
+}
+
+impl DrawingParser {
+    fn parent(&self) -> &str {
+        self.stack.last().map(String::as_str).unwrap_or_default()
+    }
+
+    fn marker(&mut self) -> Option<&mut DrawingMarker> {
+        let anchor = self.anchor.as_mut()?;
+        match self.stack.get(self.anchor_depth + 1).map(String::as_str) {
+            Some("from") => Some(&mut anchor.from),
+            Some("to") => Some(anchor.to.get_or_insert_with(DrawingMarker::default)),
+            _ => None,
+        }
+    }
+
+    fn open(
+        &mut self,
+        reader: &Reader<&[u8]>,
+        xml: &[u8],
+        e: &BytesStart,
+        tag: &str,
+    ) -> Result<(), GridParseError> {
+        if ANCHOR_TAGS.contains(&tag) && self.anchor.is_none() {
+            self.anchor = Some(DrawingAnchor::default());
+            self.anchor_depth = self.stack.len();
+            return Ok(());
+        }
+        if self.anchor.is_none() {
+            return Ok(());
+        }
+
+        let at_anchor = self.stack.len() == self.anchor_depth + 1;
+        if at_anchor {
+            let kind = match tag {
+                "sp" => Some(DrawingObjectKind::Shape),
+                "pic" => Some(DrawingObjectKind::Picture),
+                "cxnSp" => Some(DrawingObjectKind::Connector),
+                "grpSp" => Some(DrawingObjectKind::Group),
+                "graphicFrame" => None,
+                "pos" => {
+                    let attrs = collect_attrs(reader, xml, e)?;
+                    if let Some(anchor) = self.anchor.as_mut() {
+                        anchor.from.col_offset = parse_i64(attr(&attrs, "x"));
+                        anchor.from.row_offset = parse_i64(attr(&attrs, "y"));
+                    }
+                    return Ok(());
+                }
+                _ => return Ok(()),
+            };
+            let attrs = collect_attrs(reader, xml, e)?;
+            self.object = Some(ObjectText {
+                kind,
+                macro_name: attr(&attrs, "macro")
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(str::to_string),
+                ..ObjectText::default()
+            });
+            return Ok(());
+        }
+
+        let parent = self.parent().to_string();
+        let Some(object) = self.object.as_mut() else {
+            return Ok(());
+        };
+        match tag {
+            "cNvPr" if !object.named => {
+                let attrs = collect_attrs(reader, xml, e)?;
+                object.named = true;
+                object.id = attr(&attrs, "id")
+                    .and_then(|id| id.trim().parse().ok())
+                    .unwrap_or(0);
+                object.name = attr(&attrs, "name").map(str::to_string);
+            }
+            "compatExt" => object.kind = object.kind.map(|_| DrawingObjectKind::FormControl),
+            "cNvSpPr" if parent == "nvSpPr" => {
+                let attrs = collect_attrs(reader, xml, e)?;
+                if matches!(attr(&attrs, "txBox"), Some("1" | "true"))
+                    && object.kind == Some(DrawingObjectKind::Shape)
+                {
+                    object.kind = Some(DrawingObjectKind::TextBox);
+                }
+            }
+            "blip" if object.image_rel.is_none() => {
+                let attrs = collect_attrs(reader, xml, e)?;
+                object.image_rel = attr(&attrs, "embed").map(str::to_string);
+            }
+            "p" if !object.text.is_empty() => object.text.push('\n'),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Close `tag`, which has already been popped off the stack.
+    fn close(&mut self, tag: &str, pool: &mut StringPool) {
+        let text = std::mem::take(&mut self.text);
+        if self.anchor.is_none() {
+            return;
+        }
+        if self.stack.len() == self.anchor_depth {
+            // The anchor itself.
+            let anchor = self.anchor.take().unwrap_or_default();
+            if let Some(object) = self.object.take()
+                && let Some(kind) = object.kind
+            {
+                let text = Some(object.text).filter(|text| !text.trim().is_empty());
+                self.objects.push(ParsedDrawingObject {
+                    object: DrawingObject {
+                        id: object.id,
+                        kind,
+                        name: pool.intern(object.name.as_deref().unwrap_or_default()),
+                        anchor,
+                        text: text.map(|text| pool.intern(&text)),
+                        macro_name: object.macro_name.map(|name| pool.intern(&name)),
+                        image_hash: None,
+                    },
+                    image_rel: object.image_rel,
+                });
+            }
+            return;
+        }
+        match tag {
+            "t" => {
+                if let Some(object) = self.object.as_mut() {
+                    object.text.push_str(&text);
+                }
+            }
+            "col" | "row" | "colOff" | "rowOff" => {
+                if let Some(marker) = self.marker() {
+                    let value = text.trim();
+                    match tag {
+                        "col" => marker.col = value.parse().unwrap_or(0),
+                        "row" => marker.row = value.parse().unwrap_or(0),
+                        "colOff" => marker.col_offset = parse_i64(Some(value)),
+                        _ => marker.row_offset = parse_i64(Some(value)),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn parse_i64(value: Option<&str>) -> i64 {
+    value.and_then(|v| v.trim().parse().ok()).unwrap_or(0)
+}
+
+/// Parse the non-chart objects of one drawing part, in document order.
+pub(crate) fn parse_drawing_objects(
+    xml: &[u8],
+    pool: &mut StringPool,
+) -> Result<Vec<ParsedDrawingObject>, GridParseError> {
+    let mut reader = Reader::from_reader(xml);
+    reader.config_mut().trim_text(false);
+    let mut buf = Vec::new();
+    let mut parser = DrawingParser::default();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) => {
+                let tag = String::from_utf8_lossy(local_tag_name(e.name().as_ref())).into_owned();
+                if parser.skip_until.is_none() {
+                    if tag == "Fallback" {
+                        parser.skip_until = Some(parser.stack.len());
+                    } else {
+                        parser.open(&reader, xml, &e, &tag)?;
+                    }
+                }
+                parser.stack.push(tag);
+                parser.text.clear();
+            }
+            Ok(Event::Empty(e)) => {
+                if parser.skip_until.is_none() {
+                    let tag =
+                        String::from_utf8_lossy(local_tag_name(e.name().as_ref())).into_owned();
+                    parser.open(&reader, xml, &e, &tag)?;
+                    parser.text.clear();
+                    parser.close(&tag, pool);
+                }
+            }
+            Ok(Event::Text(e)) => {
+                if parser.skip_until.is_none()
+                    && matches!(parser.parent(), "t" | "col" | "row" | "colOff" | "rowOff")
+                {
+                    push_text(&reader, xml, e.as_ref(), &mut parser.text)?;
+                }
+            }
+            Ok(Event::End(_)) => {
+                let Some(tag) = parser.stack.pop() else {
+                    continue;
+                };
+                match parser.skip_until {
+                    Some(depth) if depth == parser.stack.len() => parser.skip_until = None,
+                    Some(_) => {}
+                    None => parser.close(&tag, pool),
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(xml_err(&reader, xml, e)),
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(parser.objects)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_pictures_text_boxes_and_form_controls() {
+        let xml = concat!(
+            r#"<xdr:wsDr "#,
+            r#"xmlns:xdr="http://schemas.openxmlformats.org/drawingml/2006/spreadsheetDrawing" "#,
+            r#"xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" "#,
+            r#"xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" "#,
+            r#"xmlns:mc="http://schemas.openxmlformats.org/markup-compatibility/2006">"#,
+            r#"<xdr:twoCellAnchor editAs="oneCell"><xdr:from><xdr:col>1</xdr:col><xdr:colOff>10"#,
+            r#"</xdr:colOff><xdr:row>2</xdr:row><xdr:rowOff>0</xdr:rowOff></xdr:from><xdr:to>"#,
+            r#"<xdr:col>3</xdr:col><xdr:colOff>0</xdr:colOff><xdr:row>6</xdr:row><xdr:rowOff>5"#,
+            r#"</xdr:rowOff></xdr:to><xdr:pic><xdr:nvPicPr><xdr:cNvPr id="2" name="Logo"/>"#,
+            r#"<xdr:cNvPicPr/></xdr:nvPicPr><xdr:blipFill><a:blip r:embed="rId4"/></xdr:blipFill>"#,
+            r#"</xdr:pic><xdr:clientData/></xdr:twoCellAnchor><xdr:oneCellAnchor><xdr:from>"#,
+            r#"<xdr:col>0</xdr:col><xdr:colOff>0</xdr:colOff><xdr:row>9</xdr:row><xdr:rowOff>0"#,
+            r#"</xdr:rowOff></xdr:from><xdr:ext cx="100" cy="100"/><xdr:sp macro=""><xdr:nvSpPr>"#,
+            r#"<xdr:cNvPr id="3" name="TextBox 2"/><xdr:cNvSpPr txBox="1"/></xdr:nvSpPr>"#,
+            r#"<xdr:txBody><a:p><a:r><a:t>Fill in </a:t></a:r><a:r><a:t>column B</a:t></a:r>"#,
+            r#"</a:p><a:p><a:r><a:t>Then save &amp; close</a:t></a:r></a:p></xdr:txBody></xdr:sp>"#,
+            r#"<xdr:clientData/></xdr:oneCellAnchor><mc:AlternateContent>"#,
+            r#"<mc:Choice Requires="a14"><xdr:twoCellAnchor><xdr:from><xdr:col>5</xdr:col>"#,
+            r#"<xdr:colOff>0</xdr:colOff><xdr:row>0</xdr:row><xdr:rowOff>0</xdr:rowOff>"#,
+            r#"</xdr:from><xdr:to><xdr:col>6</xdr:col><xdr:colOff>0</xdr:colOff><xdr:row>1"#,
+            r#"</xdr:row><xdr:rowOff>0</xdr:rowOff></xdr:to>"#,
+            r#"<xdr:sp macro="[0]!Button1_Click" textlink=""><xdr:nvSpPr>"#,
+            r#"<xdr:cNvPr id="1025" name="Button 1"><a:extLst><a:ext uri="{63B3BB69}">"#,
+            r#"<a14:compatExt xmlns:a14="http://schemas.microsoft.com/office/drawing/2010/main" "#,
+            r#"spid="_x0000_s1025"/>"#,
+            r#"</a:ext></a:extLst></xdr:cNvPr><xdr:cNvSpPr/></xdr:nvSpPr><xdr:txBody><a:p><a:r>"#,
+            r#"<a:t>Run</a:t></a:r></a:p></xdr:txBody></xdr:sp><xdr:clientData/>"#,
+            r#"</xdr:twoCellAnchor></mc:Choice><mc:Fallback><xdr:twoCellAnchor><xdr:sp>"#,
+            r#"<xdr:nvSpPr><xdr:cNvPr id="9" name="Fallback"/></xdr:nvSpPr></xdr:sp>"#,
+            r#"</xdr:twoCellAnchor></mc:Fallback></mc:AlternateContent><xdr:twoCellAnchor>"#,
+            r#"<xdr:from><xdr:col>0</xdr:col><xdr:colOff>0</xdr:colOff><xdr:row>0</xdr:row>"#,
+            r#"<xdr:rowOff>0</xdr:rowOff></xdr:from><xdr:graphicFrame><xdr:nvGraphicFramePr>"#,
+            r#"<xdr:cNvPr id="4" name="Chart 1"/></xdr:nvGraphicFramePr></xdr:graphicFrame>"#,
+            r#"<xdr:clientData/></xdr:twoCellAnchor></xdr:wsDr>"#,
+        );
+        let mut pool = StringPool::new();
+        let objects = parse_drawing_objects(xml.as_bytes(), &mut pool).expect("parse drawing");
+
+        let summary: Vec<(u32, DrawingObjectKind, &str)> = objects
+            .iter()
+            .map(|o| (o.object.id, o.object.kind, pool.resolve(o.object.name)))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (2, DrawingObjectKind::Picture, "Logo"),
+                (3, DrawingObjectKind::TextBox, "TextBox 2"),
+                (1025, DrawingObjectKind::FormControl, "Button 1"),
+            ]
+        );
+
+        let picture = &objects[0];
+        assert_eq!(picture.image_rel.as_deref(), Some("rId4"));
+        let anchor = picture.object.anchor;
+        assert_eq!(
+            (anchor.from.row, anchor.from.col, anchor.from.col_offset),
+            (2, 1, 10)
+        );
+        let to = anchor.to.expect("two-cell anchor");
+        assert_eq!((to.row, to.col, to.row_offset), (6, 3, 5));
+
+        let text_box = &objects[1].object;
+        assert_eq!(
+            text_box.text.map(|id| pool.resolve(id)),
+            Some("Fill in column B\nThen save & close")
+        );
+        assert_eq!(text_box.macro_name, None);
+        assert_eq!(text_box.anchor.to, None);
+
+        let button = &objects[2].object;
+        assert_eq!(
+            button.macro_name.map(|id| pool.resolve(id)),
+            Some("[0]!Button1_Click")
+        );
+        assert_eq!(button.text.map(|id| pool.resolve(id)), Some("Run"));
+    }
+}
@@ -22,17 +22,13 @@ use super::grid_primitives::{
 };
 use super::move_mask::row_signature_at;
 
-pub(crate) fn row_signature_multiset_equal(a: &Grid, b: &Grid) -> bool {
-    if a.nrows != b.nrows {
+fn row_signature_multiset_equal(sigs_a: &[RowSignature], sigs_b: &[RowSignature]) -> bool {
+    if sigs_a.len() != sigs_b.len() {
         return false;
     }
 
-    let mut a_sigs: Vec<RowSignature> = (0..a.nrows)
-        .filter_map(|row| row_signature_at(a, row))
-        .collect();
-    let mut b_sigs: Vec<RowSignature> = (0..b.nrows)
-        .filter_map(|row| row_signature_at(b, row))
-        .collect();
+    let mut a_sigs = sigs_a.to_vec();
+    let mut b_sigs = sigs_b.to_vec();
 
     a_sigs.sort_unstable_by_key(|s| s.hash);
     b_sigs.sort_unstable_by_key(|s| s.hash);
@@ -40,7 +36,11 @@ pub(crate) fn row_signature_multiset_equal(a: &Grid, b: &Grid) -> bool {
     a_sigs == b_sigs
 }
 
-fn amr_strip_moves_policy(old: &Grid, new: &Grid, alignment: &mut RowAlignment) {
+fn amr_strip_moves_policy(
+    sigs_old: &[RowSignature],
+    sigs_new: &[RowSignature],
+    alignment: &mut RowAlignment,
+) {
     let mut deleted_from_moves = Vec::new();
     let mut inserted_from_moves = Vec::new();
     for mv in &alignment.moves {
@@ -48,10 +48,10 @@ fn amr_strip_moves_policy(old: &Grid, new: &Grid, alignment: &mut RowAlignment)
         inserted_from_moves.extend(mv.dst_start_row..mv.dst_start_row.saturating_add(mv.row_count));
     }
 
-    let multiset_equal = row_signature_multiset_equal(old, new);
+    let multiset_equal = row_signature_multiset_equal(sigs_old, sigs_new);
     if multiset_equal {
         for (a, b) in &alignment.matched {
-            if row_signature_at(old, *a) != row_signature_at(new, *b) {
+            if sigs_old.get(*a as usize) != sigs_new.get(*b as usize) {
                 deleted_from_moves.push(*a);
                 inserted_from_moves.push(*b);
             }
@@ -83,8 +83,8 @@ fn amr_should_fallback_no_matched_rows(alignment: &RowAlignment) -> bool {
 }
 
 fn amr_should_fallback_row_edits_with_structural(
-    old: &Grid,
-    new: &Grid,
+    sigs_old: &[RowSignature],
+    sigs_new: &[RowSignature],
     alignment: &RowAlignment,
     config: &DiffConfig,
 ) -> bool {
@@ -96,7 +96,7 @@ fn amr_should_fallback_row_edits_with_structural(
     let has_row_edits = alignment
         .matched
         .iter()
-        .any(|(a, b)| row_signature_at(old, *a) != row_signature_at(new, *b));
+        .any(|(a, b)| sigs_old.get(*a as usize) != sigs_new.get(*b as usize));
 
     has_row_edits && config.moves.max_move_iterations > 0
 }
@@ -113,13 +113,15 @@ fn amr_alignment_is_trivial_identity(old: &Grid, new: &Grid, alignment: &RowAlig
 fn amr_should_fallback_multiset_reorder(
     old: &Grid,
     new: &Grid,
+    sigs_old: &[RowSignature],
+    sigs_new: &[RowSignature],
     alignment: &RowAlignment,
     config: &DiffConfig,
 ) -> bool {
     let is_trivial = amr_alignment_is_trivial_identity(old, new, alignment);
     !is_trivial
         && alignment.moves.is_empty()
-        && row_signature_multiset_equal(old, new)
+        && row_signature_multiset_equal(sigs_old, sigs_new)
         && config.moves.max_move_iterations > 0
 }
 
@@ -239,7 +241,11 @@ pub(super) fn try_diff_with_amr<S: DiffSink>(
             &amr_result.row_signatures_b,
         );
     } else {
-        amr_strip_moves_policy(old, new, &mut alignment);
+        amr_strip_moves_policy(
+            &amr_result.row_signatures_a,
+            &amr_result.row_signatures_b,
+            &mut alignment,
+        );
     }
 
     if amr_should_fallback_no_matched_rows(&alignment) {
@@ -247,7 +253,12 @@ pub(super) fn try_diff_with_amr<S: DiffSink>(
         return Ok(true);
     }
 
-    if amr_should_fallback_row_edits_with_structural(old, new, &alignment, emit_ctx.config) {
+    if amr_should_fallback_row_edits_with_structural(
+        &amr_result.row_signatures_a,
+        &amr_result.row_signatures_b,
+        &alignment,
+        emit_ctx.config,
+    ) {
         run_positional_diff_from_views_with_metrics(emit_ctx, old, new, old_view, new_view)?;
         return Ok(true);
     }
@@ -276,7 +287,14 @@ pub(super) fn try_diff_with_amr<S: DiffSink>(
         }
     }
 
-    if amr_should_fallback_multiset_reorder(old, new, &alignment, emit_ctx.config) {
+    if amr_should_fallback_multiset_reorder(
+        old,
+        new,
+        &amr_result.row_signatures_a,
+        &amr_result.row_signatures_b,
+        &alignment,
+        emit_ctx.config,
+    ) {
         run_positional_diff_from_views_with_metrics(emit_ctx, old, new, old_view, new_view)?;
         return Ok(true);
     }
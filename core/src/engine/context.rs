@@ -5,6 +5,7 @@ use crate::formula_diff::FormulaParseCache;
 use crate::perf::{DiffMetrics, Phase};
 use crate::sink::DiffSink;
 use crate::string_pool::StringPool;
+use crate::value_compare::CellComparator;
 
 use super::hardening::HardeningController;
 use crate::diff::SheetId;
@@ -29,6 +30,7 @@ pub(super) struct EmitCtx<'a, 'p, S: DiffSink> {
     pub(super) sheet_id: SheetId,
     pub(super) pool: &'a StringPool,
     pub(super) config: &'a DiffConfig,
+    pub(super) compare: CellComparator<'a>,
     pub(super) cache: &'a mut FormulaParseCache,
     pub(super) sink: &'a mut S,
     pub(super) op_count: &'a mut usize,
@@ -54,6 +56,7 @@ impl<'a, 'p, S: DiffSink> EmitCtx<'a, 'p, S> {
             sheet_id,
             pool,
             config,
+            compare: CellComparator::for_sheet(pool, config, sheet_id),
             cache,
             sink,
             op_count,
@@ -1,5 +1,5 @@
 use crate::config::{DiffConfig, LimitBehavior};
-use crate::diff::{DiffError, DiffOp, DiffReport, DiffSummary};
+use crate::diff::{DiffError, DiffOp, DiffReport, DiffSummary, FieldChange};
 use crate::grid_view::GridView;
 use crate::hashing::hash_row_content_128;
 #[cfg(feature = "perf-metrics")]
@@ -7,6 +7,7 @@ use crate::perf::{DiffMetrics, Phase};
 use crate::progress::ProgressCallback;
 use crate::sink::{DiffSink, SinkFinishGuard, VecSink};
 use crate::string_pool::StringPool;
+use crate::value_compare::CellComparator;
 use crate::workbook::{CellAddress, CellContent, CellValue, Grid, GridStorage, RowSignature};
 use std::collections::{HashMap, HashSet};
 
@@ -18,6 +19,7 @@ use super::move_mask::SheetGridDiffer;
 use crate::diff::SheetId;
 
 use crate::database_alignment::diff_table_by_key;
+use crate::database_keys::ResolvedKeys;
 use crate::matching::hungarian;
 
 const GRID_MODE_SHEET_ID: &str = "<grid>";
@@ -343,7 +345,17 @@ fn diff_grids_core<'p, S: DiffSink>(
     if let Some(m) = metrics.as_mut() {
         m.start_phase(Phase::SignatureBuild);
     }
-    let preflight = preflight_decision_from_grids(old, new, config);
+    let compare = CellComparator::for_sheet(pool, config, sheet_id);
+    // Preflight shortcuts judge similarity from exact row signatures, so they are skipped
+    // when value rules could make differing signatures equal.
+    let preflight = if compare.is_exact() {
+        preflight_decision_from_grids(old, new, config)
+    } else {
+        PreflightLite {
+            decision: PreflightDecision::RunFullPipeline,
+            mismatched_rows: Vec::new(),
+        }
+    };
 
     if matches!(
         preflight.decision,
@@ -394,15 +406,17 @@ fn diff_grids_core<'p, S: DiffSink>(
         return Ok(());
     }
 
+    let build_view = |grid| {
+        let mut view = GridView::from_grid_with_config(grid, config);
+        view.apply_value_rules(&compare, config);
+        view
+    };
     #[cfg(feature = "parallel")]
-    let (old_view, new_view) = rayon::join(
-        || GridView::from_grid_with_config(old, config),
-        || GridView::from_grid_with_config(new, config),
-    );
+    let (old_view, new_view) = rayon::join(|| build_view(old), || build_view(new));
     #[cfg(not(feature = "parallel"))]
-    let old_view = GridView::from_grid_with_config(old, config);
+    let old_view = build_view(old);
     #[cfg(not(feature = "parallel"))]
-    let new_view = GridView::from_grid_with_config(new, config);
+    let new_view = build_view(new);
     #[cfg(feature = "perf-metrics")]
     if let Some(m) = metrics.as_mut() {
         let lookups = old.cell_count() as u64 + new.cell_count() as u64;
@@ -569,24 +583,77 @@ pub fn try_diff_grids_database_mode_streaming<S: DiffSink>(
     config: &DiffConfig,
     sink: &mut S,
     op_count: &mut usize,
+) -> Result<DiffSummary, DiffError> {
+    try_diff_grids_database_mode_keyed_streaming(
+        sheet_id,
+        old,
+        new,
+        &ResolvedKeys::positional(key_columns),
+        pool,
+        config,
+        sink,
+        op_count,
+    )
+}
+
+/// Like [`try_diff_grids_database_mode_streaming`], with key columns resolved per side.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn try_diff_grids_database_mode_keyed_streaming<S: DiffSink>(
+    sheet_id: SheetId,
+    old: &Grid,
+    new: &Grid,
+    keys: &ResolvedKeys,
+    pool: &mut StringPool,
+    config: &DiffConfig,
+    sink: &mut S,
+    op_count: &mut usize,
 ) -> Result<DiffSummary, DiffError> {
     let mut ctx = DiffContext::default();
     let mut hardening = super::hardening::HardeningController::new(config, None);
 
     sink.begin(pool)?;
     let mut finish_guard = SinkFinishGuard::new(sink);
-    if hardening.check_timeout(&mut ctx.warnings) {
-        finish_guard.finish_and_disarm()?;
-        return Ok(DiffSummary {
-            complete: false,
-            warnings: ctx.warnings,
-            op_count: *op_count,
-            #[cfg(feature = "perf-metrics")]
-            metrics: None,
-        });
+    if !hardening.check_timeout(&mut ctx.warnings) {
+        diff_grids_database_mode_keyed(
+            sheet_id,
+            old,
+            new,
+            keys,
+            pool,
+            config,
+            sink,
+            op_count,
+            &mut ctx,
+            &mut hardening,
+        )?;
     }
+    finish_guard.finish_and_disarm()?;
+    Ok(DiffSummary {
+        complete: ctx.warnings.is_empty(),
+        warnings: ctx.warnings,
+        op_count: *op_count,
+        #[cfg(feature = "perf-metrics")]
+        metrics: None,
+    })
+}
 
-    if key_columns.is_empty() {
+/// Diff one sheet by key into an already started `sink`, sharing the caller's context and
+/// hardening controller. Returns the `(old row, new row)` pairs matched by key, or `None` when
+/// the sheet fell back to a positional diff.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn diff_grids_database_mode_keyed<'p, S: DiffSink>(
+    sheet_id: SheetId,
+    old: &Grid,
+    new: &Grid,
+    keys: &ResolvedKeys,
+    pool: &mut StringPool,
+    config: &DiffConfig,
+    sink: &mut S,
+    op_count: &mut usize,
+    ctx: &mut DiffContext,
+    hardening: &mut super::hardening::HardeningController<'p>,
+) -> Result<Option<Vec<(u32, u32)>>, DiffError> {
+    if keys.old.columns.is_empty() {
         ctx.warnings.push(
             "database-mode: no key columns provided; falling back to spreadsheet mode".to_string(),
         );
@@ -598,25 +665,16 @@ pub fn try_diff_grids_database_mode_streaming<S: DiffSink>(
             pool,
             sink,
             op_count,
-            &mut ctx,
-            &mut hardening,
+            ctx,
+            hardening,
             #[cfg(feature = "perf-metrics")]
             None,
         )?;
-        finish_guard.finish_and_disarm()?;
-        let complete = ctx.warnings.is_empty();
-        return Ok(DiffSummary {
-            complete,
-            warnings: ctx.warnings,
-            op_count: *op_count,
-            #[cfg(feature = "perf-metrics")]
-            metrics: None,
-        });
+        return Ok(None);
     }
 
-    if key_columns
-        .iter()
-        .any(|&col| col >= old.ncols || col >= new.ncols)
+    if keys.old.columns.iter().any(|&col| col >= old.ncols)
+        || keys.new.columns.iter().any(|&col| col >= new.ncols)
     {
         ctx.warnings.push(
             "database-mode: invalid key columns; falling back to spreadsheet mode".to_string(),
@@ -629,23 +687,16 @@ pub fn try_diff_grids_database_mode_streaming<S: DiffSink>(
             pool,
             sink,
             op_count,
-            &mut ctx,
-            &mut hardening,
+            ctx,
+            hardening,
             #[cfg(feature = "perf-metrics")]
             None,
         )?;
-        finish_guard.finish_and_disarm()?;
-        let complete = ctx.warnings.is_empty();
-        return Ok(DiffSummary {
-            complete,
-            warnings: ctx.warnings,
-            op_count: *op_count,
-            #[cfg(feature = "perf-metrics")]
-            metrics: None,
-        });
+        return Ok(None);
     }
 
-    let Some(table_scope) = build_table_scope(old, new, key_columns) else {
+    let Some(table_scope) = build_table_scope(old, new, &keys.old.columns, &keys.new.columns)
+    else {
         ctx.warnings.push(
             "database-mode: no non-empty keys found; falling back to spreadsheet mode".to_string(),
         );
@@ -657,20 +708,12 @@ pub fn try_diff_grids_database_mode_streaming<S: DiffSink>(
             pool,
             sink,
             op_count,
-            &mut ctx,
-            &mut hardening,
+            ctx,
+            hardening,
             #[cfg(feature = "perf-metrics")]
             None,
         )?;
-        finish_guard.finish_and_disarm()?;
-        let complete = ctx.warnings.is_empty();
-        return Ok(DiffSummary {
-            complete,
-            warnings: ctx.warnings,
-            op_count: *op_count,
-            #[cfg(feature = "perf-metrics")]
-            metrics: None,
-        });
+        return Ok(None);
     };
 
     let table_rows = table_scope.rows_old.len().max(table_scope.rows_new.len()) as u32;
@@ -711,20 +754,12 @@ pub fn try_diff_grids_database_mode_streaming<S: DiffSink>(
                     sink,
                     op_count,
                     &mut ctx.warnings,
-                    &mut hardening,
+                    hardening,
                     #[cfg(feature = "perf-metrics")]
                     None,
                 );
                 run_positional_diff_with_metrics(&mut emit_ctx, old, new)?;
-                finish_guard.finish_and_disarm()?;
-                let complete = ctx.warnings.is_empty();
-                return Ok(DiffSummary {
-                    complete,
-                    warnings: ctx.warnings,
-                    op_count: *op_count,
-                    #[cfg(feature = "perf-metrics")]
-                    metrics: None,
-                });
+                return Ok(None);
             }
         }
     }
@@ -734,7 +769,10 @@ pub fn try_diff_grids_database_mode_streaming<S: DiffSink>(
     let (table_new, row_map_new) =
         build_table_grid(new, &table_scope.rows_new, &table_scope.cols_union);
 
-    let Some(table_key_cols) = map_key_columns(key_columns, &table_scope.cols_union) else {
+    let (Some(old_key_cols), Some(new_key_cols)) = (
+        map_key_columns(&keys.old.columns, &table_scope.cols_union),
+        map_key_columns(&keys.new.columns, &table_scope.cols_union),
+    ) else {
         ctx.warnings.push(
             "database-mode: invalid key columns; falling back to spreadsheet mode".to_string(),
         );
@@ -746,30 +784,31 @@ pub fn try_diff_grids_database_mode_streaming<S: DiffSink>(
             pool,
             sink,
             op_count,
-            &mut ctx,
-            &mut hardening,
+            ctx,
+            hardening,
             #[cfg(feature = "perf-metrics")]
             None,
         )?;
-        finish_guard.finish_and_disarm()?;
-        let complete = ctx.warnings.is_empty();
-        return Ok(DiffSummary {
-            complete,
-            warnings: ctx.warnings,
-            op_count: *op_count,
-            #[cfg(feature = "perf-metrics")]
-            metrics: None,
-        });
+        return Ok(None);
     };
 
-    let key_col_set: HashSet<u32> = table_key_cols.iter().copied().collect();
-    let max_cols = table_old.ncols.max(table_new.ncols);
-    let compare_cols: Vec<u32> = (0..max_cols)
-        .filter(|col| !key_col_set.contains(col))
-        .collect();
+    let columns = pair_table_columns(
+        (old, &table_old, &old_key_cols),
+        (new, &table_new, &new_key_cols),
+        keys.header_row,
+        &table_scope.cols_union,
+        pool,
+    );
 
-    let alignment = diff_table_by_key(&table_old, &table_new, &table_key_cols);
+    let alignment = diff_table_by_key(
+        &table_old,
+        &table_new,
+        &keys.old.with_columns(old_key_cols.clone()),
+        &keys.new.with_columns(new_key_cols.clone()),
+        pool,
+    );
 
+    let mut row_pairs = Vec::new();
     {
         let mut emit_ctx = EmitCtx::new(
             sheet_id,
@@ -779,7 +818,7 @@ pub fn try_diff_grids_database_mode_streaming<S: DiffSink>(
             sink,
             op_count,
             &mut ctx.warnings,
-            &mut hardening,
+            hardening,
             #[cfg(feature = "perf-metrics")]
             None,
         );
@@ -789,6 +828,13 @@ pub fn try_diff_grids_database_mode_streaming<S: DiffSink>(
             hardening.check_timeout(warnings) || hardening.should_abort()
         };
 
+        for &col in &columns.old_only {
+            emit_ctx.emit(DiffOp::column_removed(sheet_id, col, None))?;
+        }
+        for &col in &columns.new_only {
+            emit_ctx.emit(DiffOp::column_added(sheet_id, col, None))?;
+        }
+
         for row_idx in &alignment.left_only_rows {
             if should_abort(&mut emit_ctx) {
                 break;
@@ -817,25 +863,50 @@ pub fn try_diff_grids_database_mode_streaming<S: DiffSink>(
             let Some(row_b_orig) = row_map_new.get(*row_b as usize).copied() else {
                 continue;
             };
+            row_pairs.push((row_a_orig, row_b_orig));
             let row_shift = row_b_orig as i32 - row_a_orig as i32;
+            let mut fields = Vec::new();
 
-            for col in 0..max_cols {
-                if key_col_set.contains(&col) {
-                    continue;
-                }
-
-                let old_cell = table_old.get(*row_a, col);
-                let new_cell = table_new.get(*row_b, col);
+            for &(col_a, col_b) in &columns.pairs {
+                let col_a_orig = table_scope.cols_union[col_a as usize];
+                let col_b_orig = table_scope.cols_union[col_b as usize];
+                let old_cell = table_old.get(*row_a, col_a);
+                let new_cell = table_new.get(*row_b, col_b);
 
-                if cells_content_equal(old_cell, new_cell) {
+                if emit_ctx.compare.cells_equal(old_cell, new_cell, col_b_orig) {
                     continue;
                 }
 
-                let Some(col_orig) = table_scope.cols_union.get(col as usize).copied() else {
-                    continue;
-                };
-                let addr = CellAddress::from_indices(row_b_orig, col_orig);
-                emit_cell_edit(&mut emit_ctx, addr, old_cell, new_cell, row_shift, 0)?;
+                let addr = CellAddress::from_indices(row_b_orig, col_b_orig);
+                let col_shift = col_b_orig as i32 - col_a_orig as i32;
+                emit_cell_edit(
+                    &mut emit_ctx,
+                    addr,
+                    old_cell,
+                    new_cell,
+                    row_shift,
+                    col_shift,
+                )?;
+                fields.push(field_change(
+                    (old, col_a_orig),
+                    (new, col_b_orig),
+                    keys.header_row,
+                    old_cell,
+                    new_cell,
+                ));
+            }
+
+            if !fields.is_empty() {
+                emit_ctx.emit(DiffOp::RecordChanged {
+                    sheet: sheet_id,
+                    key: cluster_key_values(
+                        (&table_old, &[*row_a], &old_key_cols),
+                        (&table_new, &[*row_b], &new_key_cols),
+                    ),
+                    old_row: row_a_orig,
+                    new_row: row_b_orig,
+                    fields,
+                })?;
             }
         }
 
@@ -857,7 +928,10 @@ pub fn try_diff_grids_database_mode_streaming<S: DiffSink>(
 
             emit_ctx.emit(DiffOp::DuplicateKeyCluster {
                 sheet: sheet_id,
-                key: cluster.key.as_cell_values(),
+                key: cluster_key_values(
+                    (&table_old, &cluster.left_rows, &old_key_cols),
+                    (&table_new, &cluster.right_rows, &new_key_cols),
+                ),
                 left_rows: left_rows.clone(),
                 right_rows: right_rows.clone(),
             })?;
@@ -867,7 +941,9 @@ pub fn try_diff_grids_database_mode_streaming<S: DiffSink>(
                 &table_new,
                 &cluster.left_rows,
                 &cluster.right_rows,
-                &compare_cols,
+                &columns.pairs,
+                &emit_ctx.compare,
+                &table_scope.cols_union,
             );
 
             for (row_a, row_b) in cluster_match.matched {
@@ -880,23 +956,48 @@ pub fn try_diff_grids_database_mode_streaming<S: DiffSink>(
                 let Some(row_b_orig) = row_map_new.get(row_b as usize).copied() else {
                     continue;
                 };
+                row_pairs.push((row_a_orig, row_b_orig));
                 let row_shift = row_b_orig as i32 - row_a_orig as i32;
-
-                for col in 0..max_cols {
-                    if key_col_set.contains(&col) {
+                let mut fields = Vec::new();
+
+                for &(col_a, col_b) in &columns.pairs {
+                    let col_a_orig = table_scope.cols_union[col_a as usize];
+                    let col_b_orig = table_scope.cols_union[col_b as usize];
+                    let old_cell = table_old.get(row_a, col_a);
+                    let new_cell = table_new.get(row_b, col_b);
+                    if emit_ctx.compare.cells_equal(old_cell, new_cell, col_b_orig) {
                         continue;
                     }
+                    let addr = CellAddress::from_indices(row_b_orig, col_b_orig);
+                    let col_shift = col_b_orig as i32 - col_a_orig as i32;
+                    emit_cell_edit(
+                        &mut emit_ctx,
+                        addr,
+                        old_cell,
+                        new_cell,
+                        row_shift,
+                        col_shift,
+                    )?;
+                    fields.push(field_change(
+                        (old, col_a_orig),
+                        (new, col_b_orig),
+                        keys.header_row,
+                        old_cell,
+                        new_cell,
+                    ));
+                }
 
-                    let old_cell = table_old.get(row_a, col);
-                    let new_cell = table_new.get(row_b, col);
-                    if cells_content_equal(old_cell, new_cell) {
-                        continue;
-                    }
-                    let Some(col_orig) = table_scope.cols_union.get(col as usize).copied() else {
-                        continue;
-                    };
-                    let addr = CellAddress::from_indices(row_b_orig, col_orig);
-                    emit_cell_edit(&mut emit_ctx, addr, old_cell, new_cell, row_shift, 0)?;
+                if !fields.is_empty() {
+                    emit_ctx.emit(DiffOp::RecordChanged {
+                        sheet: sheet_id,
+                        key: cluster_key_values(
+                            (&table_old, &[row_a], &old_key_cols),
+                            (&table_new, &[row_b], &new_key_cols),
+                        ),
+                        old_row: row_a_orig,
+                        new_row: row_b_orig,
+                        fields,
+                    })?;
                 }
             }
 
@@ -937,6 +1038,7 @@ pub fn try_diff_grids_database_mode_streaming<S: DiffSink>(
     {
         let old_free = mask_grid_excluding_rect(
             old,
+            (new.nrows, new.ncols),
             table_scope.row_start,
             table_scope.row_end,
             table_scope.col_start,
@@ -944,6 +1046,7 @@ pub fn try_diff_grids_database_mode_streaming<S: DiffSink>(
         );
         let new_free = mask_grid_excluding_rect(
             new,
+            (old.nrows, old.ncols),
             table_scope.row_start,
             table_scope.row_end,
             table_scope.col_start,
@@ -959,7 +1062,7 @@ pub fn try_diff_grids_database_mode_streaming<S: DiffSink>(
                 sink,
                 op_count,
                 &mut ctx.warnings,
-                &mut hardening,
+                hardening,
                 #[cfg(feature = "perf-metrics")]
                 None,
             );
@@ -967,14 +1070,7 @@ pub fn try_diff_grids_database_mode_streaming<S: DiffSink>(
         }
     }
 
-    finish_guard.finish_and_disarm()?;
-    Ok(DiffSummary {
-        complete: ctx.warnings.is_empty(),
-        warnings: ctx.warnings,
-        op_count: *op_count,
-        #[cfg(feature = "perf-metrics")]
-        metrics: None,
-    })
+    Ok(Some(row_pairs))
 }
 
 #[derive(Debug, Clone)]
@@ -988,16 +1084,21 @@ struct TableScope {
     col_end: u32,
 }
 
-fn build_table_scope(old: &Grid, new: &Grid, key_columns: &[u32]) -> Option<TableScope> {
-    let rows_old = table_rows_for_grid(old, key_columns);
-    let rows_new = table_rows_for_grid(new, key_columns);
+fn build_table_scope(
+    old: &Grid,
+    new: &Grid,
+    old_key_columns: &[u32],
+    new_key_columns: &[u32],
+) -> Option<TableScope> {
+    let rows_old = table_rows_for_grid(old, old_key_columns);
+    let rows_new = table_rows_for_grid(new, new_key_columns);
     let rows_union = union_sorted(&rows_old, &rows_new);
     if rows_union.is_empty() {
         return None;
     }
 
-    let cols_old = table_cols_for_grid(old, &rows_old, key_columns);
-    let cols_new = table_cols_for_grid(new, &rows_new, key_columns);
+    let cols_old = table_cols_for_grid(old, &rows_old, old_key_columns);
+    let cols_new = table_cols_for_grid(new, &rows_new, new_key_columns);
     let cols_union = union_sorted(&cols_old, &cols_new);
     if cols_union.is_empty() {
         return None;
@@ -1099,6 +1200,128 @@ fn map_key_columns(key_columns: &[u32], cols_union: &[u32]) -> Option<Vec<u32>>
     Some(mapped)
 }
 
+/// The key cells of a duplicate cluster's first row (old side first), as they appear in the grid.
+fn cluster_key_values(
+    (old, left_rows, old_cols): (&Grid, &[u32], &[u32]),
+    (new, right_rows, new_cols): (&Grid, &[u32], &[u32]),
+) -> Vec<Option<CellValue>> {
+    let (grid, row, cols) = match (left_rows.first(), right_rows.first()) {
+        (Some(&row), _) => (old, row, old_cols),
+        (None, Some(&row)) => (new, row, new_cols),
+        (None, None) => return Vec::new(),
+    };
+    cols.iter()
+        .map(|&col| grid.get(row, col).and_then(|cell| cell.value))
+        .collect()
+}
+
+/// Non-key table columns paired across the two sides, as indices into the table grids, plus the
+/// sheet columns of unpaired columns that hold data.
+#[derive(Debug, Default)]
+struct ColumnPairing {
+    pairs: Vec<(u32, u32)>,
+    old_only: Vec<u32>,
+    new_only: Vec<u32>,
+}
+
+/// Pair non-key columns by header text in `header_row` (trimmed, ignoring case, unique on each
+/// side), then the rest by the offset between the first key column on each side.
+fn pair_table_columns(
+    (old, table_old, old_key_cols): (&Grid, &Grid, &[u32]),
+    (new, table_new, new_key_cols): (&Grid, &Grid, &[u32]),
+    header_row: u32,
+    cols_union: &[u32],
+    pool: &StringPool,
+) -> ColumnPairing {
+    let candidates = |key_cols: &[u32]| -> Vec<u32> {
+        (0..cols_union.len() as u32)
+            .filter(|col| !key_cols.contains(col))
+            .collect()
+    };
+    let headers = |grid: &Grid, cols: &[u32]| -> HashMap<String, Option<u32>> {
+        let mut headers: HashMap<String, Option<u32>> = HashMap::new();
+        for &col in cols {
+            if let Some(CellValue::Text(id)) = grid
+                .get(header_row, cols_union[col as usize])
+                .and_then(|cell| cell.value)
+            {
+                headers
+                    .entry(pool.resolve(id).trim().to_lowercase())
+                    .and_modify(|slot| *slot = None)
+                    .or_insert(Some(col));
+            }
+        }
+        headers
+    };
+    let old_cols = candidates(old_key_cols);
+    let new_cols = candidates(new_key_cols);
+    let new_headers = headers(new, &new_cols);
+
+    let mut matched: Vec<Option<u32>> = vec![None; old_cols.len()];
+    for (name, old_col) in headers(old, &old_cols) {
+        if let (Some(old_col), Some(&Some(new_col))) = (old_col, new_headers.get(&name))
+            && let Ok(slot) = old_cols.binary_search(&old_col)
+        {
+            matched[slot] = Some(new_col);
+        }
+    }
+    let mut paired_new: HashSet<u32> = matched.iter().flatten().copied().collect();
+
+    let key_orig = |key_cols: &[u32]| cols_union[key_cols[0] as usize] as i64;
+    let offset = key_orig(new_key_cols) - key_orig(old_key_cols);
+    for (slot, &old_col) in matched.iter_mut().zip(&old_cols) {
+        if slot.is_some() {
+            continue;
+        }
+        let target = cols_union[old_col as usize] as i64 + offset;
+        *slot = cols_union
+            .iter()
+            .position(|&col| col as i64 == target)
+            .map(|idx| idx as u32)
+            .filter(|col| new_cols.contains(col) && !paired_new.contains(col));
+        paired_new.extend(*slot);
+    }
+
+    let has_data =
+        |table: &Grid, col: u32| (0..table.nrows).any(|row| table.get(row, col).is_some());
+    let mut pairing = ColumnPairing::default();
+    for (old_col, new_col) in old_cols.iter().copied().zip(matched) {
+        match new_col {
+            Some(new_col) => pairing.pairs.push((old_col, new_col)),
+            None if has_data(table_old, old_col) => {
+                pairing.old_only.push(cols_union[old_col as usize]);
+            }
+            None => {}
+        }
+    }
+    pairing.new_only = new_cols
+        .into_iter()
+        .filter(|col| !paired_new.contains(col) && has_data(table_new, *col))
+        .map(|col| cols_union[col as usize])
+        .collect();
+    pairing
+}
+
+/// One changed field of a matched record, named by its header cell (new side first).
+fn field_change(
+    (old, old_col): (&Grid, u32),
+    (new, new_col): (&Grid, u32),
+    header_row: u32,
+    old_cell: Option<&CellContent>,
+    new_cell: Option<&CellContent>,
+) -> FieldChange {
+    let header = |grid: &Grid, col: u32| match grid.get(header_row, col)?.value {
+        Some(CellValue::Text(id)) => Some(id),
+        _ => None,
+    };
+    FieldChange {
+        col: new_col,
+        field: header(new, new_col).or_else(|| header(old, old_col)),
+        from: old_cell.and_then(|cell| cell.value),
+        to: new_cell.and_then(|cell| cell.value),
+    }
+}
+
 fn build_table_grid(grid: &Grid, rows: &[u32], cols: &[u32]) -> (Grid, Vec<u32>) {
     let mut table = Grid::new(rows.len() as u32, cols.len() as u32);
     for (row_idx, &row) in rows.iter().enumerate() {
@@ -1131,14 +1354,18 @@ fn has_cells_outside_rect(
     false
 }
 
+/// Copy the cells of `grid` outside the table rect, sized to cover `other` as well so that a
+/// positional diff of two masked grids reports cells rather than rows or columns the table
+/// already accounts for.
 fn mask_grid_excluding_rect(
     grid: &Grid,
+    (other_rows, other_cols): (u32, u32),
     row_start: u32,
     row_end: u32,
     col_start: u32,
     col_end: u32,
 ) -> Grid {
-    let mut out = Grid::new(grid.nrows, grid.ncols);
+    let mut out = Grid::new(grid.nrows.max(other_rows), grid.ncols.max(other_cols));
     for ((row, col), cell) in grid.iter_cells() {
         if row < row_start || row > row_end || col < col_start || col > col_end {
             out.insert_cell(row, col, cell.value.clone(), cell.formula);
@@ -1159,7 +1386,9 @@ fn match_duplicate_cluster(
     new: &Grid,
     left_rows: &[u32],
     right_rows: &[u32],
-    compare_cols: &[u32],
+    col_pairs: &[(u32, u32)],
+    compare: &CellComparator<'_>,
+    cols_union: &[u32],
 ) -> ClusterMatch {
     if left_rows.is_empty() && right_rows.is_empty() {
         return ClusterMatch::default();
@@ -1179,18 +1408,14 @@ fn match_duplicate_cluster(
         };
     }
 
-    let unmatched_cost = duplicate_unmatched_cost(compare_cols.len());
+    let unmatched_cost = duplicate_unmatched_cost(col_pairs.len());
 
     let mut costs: Vec<Vec<i64>> = Vec::with_capacity(left_rows.len());
     for &left_row in left_rows {
         let mut row_costs = Vec::with_capacity(right_rows.len());
         for &right_row in right_rows {
             row_costs.push(duplicate_pair_cost(
-                old,
-                new,
-                left_row,
-                right_row,
-                compare_cols,
+                old, new, left_row, right_row, col_pairs, compare, cols_union,
             ));
         }
         costs.push(row_costs);
@@ -1294,13 +1519,16 @@ fn duplicate_pair_cost(
     new: &Grid,
     left_row: u32,
     right_row: u32,
-    compare_cols: &[u32],
+    col_pairs: &[(u32, u32)],
+    compare: &CellComparator<'_>,
+    cols_union: &[u32],
 ) -> i64 {
     let mut cost = 0i64;
-    for &col in compare_cols {
-        let old_cell = old.get(left_row, col);
-        let new_cell = new.get(right_row, col);
-        if !cells_content_equal(old_cell, new_cell) {
+    for &(old_col, new_col) in col_pairs {
+        let old_cell = old.get(left_row, old_col);
+        let new_cell = new.get(right_row, new_col);
+        let col_orig = cols_union.get(new_col as usize).copied().unwrap_or(new_col);
+        if !compare.cells_equal(old_cell, new_cell, col_orig) {
             cost += 1;
         }
     }
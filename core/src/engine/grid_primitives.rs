@@ -4,7 +4,7 @@ use crate::column_alignment::{
 };
 use crate::config::DiffConfig;
 use crate::diff::{DiffError, DiffOp, FormulaDiffResult};
-use crate::formula_diff::{diff_cell_formulas_ids, FormulaParseCache};
+use crate::formula_diff::{diff_cell_formulas_ids, formula_edits_ids, FormulaParseCache};
 use crate::grid_view::GridView;
 #[cfg(feature = "perf-metrics")]
 use crate::perf::Phase;
@@ -12,6 +12,7 @@ use crate::rect_block_move::RectBlockMove;
 use crate::row_alignment::align_row_changes_from_views;
 use crate::sink::DiffSink;
 use crate::string_pool::StringPool;
+use crate::value_compare::CellComparator;
 use crate::workbook::{Cell, CellAddress, CellSnapshot, Grid};
 
 use super::context::EmitCtx;
@@ -70,13 +71,20 @@ pub(super) fn emit_cell_edit<S: DiffSink>(
     let formula_diff = compute_formula_diff(
         ctx.pool, ctx.cache, old_cell, new_cell, row_shift, col_shift, ctx.config,
     );
-    ctx.emit(DiffOp::cell_edited(
-        ctx.sheet_id,
+    let formula_edits = match (formula_diff, from.formula, to.formula) {
+        (FormulaDiffResult::SemanticChange, Some(old_f), Some(new_f)) => {
+            formula_edits_ids(ctx.pool, ctx.cache, old_f, new_f)
+        }
+        _ => Vec::new(),
+    };
+    ctx.emit(DiffOp::CellEdited {
+        sheet: ctx.sheet_id,
         addr,
         from,
         to,
         formula_diff,
-    ))
+        formula_edits,
+    })
 }
 
 fn dense_row_replace_threshold(config: &DiffConfig, total_cols: u32) -> Option<usize> {
@@ -234,7 +242,14 @@ pub(super) fn emit_moved_row_block_edits<S: DiffSink>(
             pairs.push((row_a, row_b));
         }
 
-        let plans = plan_row_pair_chunk(old_view, new_view, &pairs, overlap_cols, ctx.config);
+        let plans = plan_row_pair_chunk(
+            old_view,
+            new_view,
+            &pairs,
+            overlap_cols,
+            ctx.config,
+            &ctx.compare,
+        );
 
         for plan in plans {
             if plan.skipped {
@@ -262,12 +277,19 @@ pub(super) fn emit_moved_row_block_edits<S: DiffSink>(
 
 fn diff_row_pair_sparse_plan<'a>(
     config: &DiffConfig,
+    compare: &CellComparator<'_>,
     overlap_cols: u32,
     old_cells: &[(u32, &'a Cell)],
     new_cells: &[(u32, &'a Cell)],
 ) -> RowDiffResult<'a> {
     let Some(threshold) = dense_row_replace_threshold(config, overlap_cols) else {
-        return diff_row_pair_sparse_thresholdless(config, overlap_cols, old_cells, new_cells);
+        return diff_row_pair_sparse_thresholdless(
+            config,
+            compare,
+            overlap_cols,
+            old_cells,
+            new_cells,
+        );
     };
 
     let mut compared = 0u64;
@@ -307,7 +329,7 @@ fn diff_row_pair_sparse_plan<'a>(
 
         compared = compared.saturating_add(1);
 
-        if cells_content_equal(old_cell, new_cell) {
+        if compare.cells_equal(old_cell, new_cell, col) {
             if config.semantic.include_unchanged_cells {
                 pending.push(PendingCell {
                     col,
@@ -343,6 +365,7 @@ fn diff_row_pair_sparse_plan<'a>(
 
 fn diff_row_pair_sparse_thresholdless<'a>(
     config: &DiffConfig,
+    compare: &CellComparator<'_>,
     overlap_cols: u32,
     old_cells: &[(u32, &'a Cell)],
     new_cells: &[(u32, &'a Cell)],
@@ -383,7 +406,8 @@ fn diff_row_pair_sparse_thresholdless<'a>(
 
         compared = compared.saturating_add(1);
 
-        if config.semantic.include_unchanged_cells || !cells_content_equal(old_cell, new_cell) {
+        if config.semantic.include_unchanged_cells || !compare.cells_equal(old_cell, new_cell, col)
+        {
             pending.push(PendingCell {
                 col,
                 old_cell,
@@ -409,6 +433,7 @@ pub(super) fn diff_row_pair_sparse<'a, S: DiffSink>(
 ) -> Result<RowDiffResult<'a>, DiffError> {
     Ok(diff_row_pair_sparse_plan(
         ctx.config,
+        &ctx.compare,
         overlap_cols,
         old_cells,
         new_cells,
@@ -433,7 +458,7 @@ pub(super) fn diff_row_pair<'a, S: DiffSink>(
         let new_cell = new.get(row_b, col);
         compared = compared.saturating_add(1);
 
-        let changed = !cells_content_equal(old_cell, new_cell);
+        let changed = !ctx.compare.cells_equal(old_cell, new_cell, col);
         if changed {
             changed_cells = changed_cells.saturating_add(1);
             if let Some(limit) = threshold {
@@ -479,6 +504,7 @@ fn plan_row_pair_chunk<'a>(
     chunk: &[(u32, u32)],
     overlap_cols: u32,
     config: &DiffConfig,
+    compare: &CellComparator<'_>,
 ) -> Vec<RowPairPlan<'a>> {
     #[cfg(feature = "parallel")]
     {
@@ -486,7 +512,15 @@ fn plan_row_pair_chunk<'a>(
         chunk
             .par_iter()
             .map(|(row_a, row_b)| {
-                plan_one_row_pair(old_view, new_view, *row_a, *row_b, overlap_cols, config)
+                plan_one_row_pair(
+                    old_view,
+                    new_view,
+                    *row_a,
+                    *row_b,
+                    overlap_cols,
+                    config,
+                    compare,
+                )
             })
             .collect()
     }
@@ -495,7 +529,15 @@ fn plan_row_pair_chunk<'a>(
     chunk
         .iter()
         .map(|(row_a, row_b)| {
-            plan_one_row_pair(old_view, new_view, *row_a, *row_b, overlap_cols, config)
+            plan_one_row_pair(
+                old_view,
+                new_view,
+                *row_a,
+                *row_b,
+                overlap_cols,
+                config,
+                compare,
+            )
         })
         .collect()
 }
@@ -507,6 +549,7 @@ fn plan_one_row_pair<'a>(
     row_b: u32,
     overlap_cols: u32,
     config: &DiffConfig,
+    compare: &CellComparator<'_>,
 ) -> RowPairPlan<'a> {
     let Some(row_view_a) = old_view.rows.get(row_a as usize) else {
         return RowPairPlan {
@@ -546,7 +589,13 @@ fn plan_one_row_pair<'a>(
         }
     }
 
-    let r = diff_row_pair_sparse_plan(config, overlap_cols, &row_view_a.cells, &row_view_b.cells);
+    let r = diff_row_pair_sparse_plan(
+        config,
+        compare,
+        overlap_cols,
+        &row_view_a.cells,
+        &row_view_b.cells,
+    );
 
     RowPairPlan {
         row_a,
@@ -579,7 +628,14 @@ pub(super) fn emit_row_aligned_diffs<S: DiffSink>(
         let end = (idx + chunk_len).min(matched.len());
         let chunk = &matched[idx..end];
 
-        let plans = plan_row_pair_chunk(old_view, new_view, chunk, overlap_cols, ctx.config);
+        let plans = plan_row_pair_chunk(
+            old_view,
+            new_view,
+            chunk,
+            overlap_cols,
+            ctx.config,
+            &ctx.compare,
+        );
 
         for plan in plans {
             if plan.skipped {
@@ -663,7 +719,7 @@ pub(super) fn emit_column_aligned_diffs<S: DiffSink>(
             let old_cell = old.get(row, *col_a);
             let new_cell = new.get(row, *col_b);
 
-            if cells_content_equal(old_cell, new_cell) {
+            if ctx.compare.cells_equal(old_cell, new_cell, *col_b) {
                 continue;
             }
 
@@ -26,6 +26,8 @@ pub use grid_diff::{
     try_diff_grids, try_diff_grids_database_mode_streaming, try_diff_grids_streaming,
     try_diff_grids_streaming_with_progress,
 };
+pub(crate) use grid_diff::try_diff_grids_database_mode_keyed_streaming;
+pub(crate) use workbook_diff::try_diff_workbooks_database_mode_streaming;
 pub use sheet_diff::{
     diff_sheets, diff_sheets_streaming, diff_sheets_streaming_with_progress, try_diff_sheets,
     try_diff_sheets_streaming, try_diff_sheets_streaming_with_progress,
@@ -1030,7 +1030,7 @@ fn diff_aligned_with_masks<S: DiffSink>(
             let old_cell = old.get(*row_a, *col_a);
             let new_cell = new.get(*row_b, *col_b);
 
-            if cells_content_equal(old_cell, new_cell) {
+            if ctx.compare.cells_equal(old_cell, new_cell, *col_b) {
                 continue;
             }
 
@@ -1115,7 +1115,7 @@ fn positional_diff_with_masks<S: DiffSink>(
             let old_cell = old.get(row, col);
             let new_cell = new.get(row, col);
 
-            if cells_content_equal(old_cell, new_cell) {
+            if ctx.compare.cells_equal(old_cell, new_cell, col) {
                 continue;
             }
 
@@ -1224,7 +1224,7 @@ fn positional_diff_masked_equal_size<S: DiffSink>(
             let old_cell = old.get(row, col);
             let new_cell = new.get(row, col);
 
-            if cells_content_equal(old_cell, new_cell) {
+            if ctx.compare.cells_equal(old_cell, new_cell, col) {
                 continue;
             }
 
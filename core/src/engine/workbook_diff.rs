@@ -1,4 +1,5 @@
 use crate::config::DiffConfig;
+use crate::database_keys::SheetKeyMap;
 use crate::diff::{DiffError, DiffOp, DiffReport, DiffSummary};
 #[cfg(feature = "perf-metrics")]
 use crate::perf::{DiffMetrics, Phase};
@@ -12,7 +13,7 @@ use std::collections::{HashMap, HashSet};
 use std::mem::size_of;
 
 use super::context::{emit_op, DiffContext};
-use super::grid_diff::try_diff_grids_internal;
+use super::grid_diff::{diff_grids_database_mode_keyed, try_diff_grids_internal};
 use super::hardening::HardeningController;
 use crate::diff::SheetId;
 
@@ -189,7 +190,7 @@ pub fn try_diff_workbooks_streaming<S: DiffSink>(
     config: &DiffConfig,
     sink: &mut S,
 ) -> Result<DiffSummary, DiffError> {
-    try_diff_workbooks_streaming_impl(old, new, pool, config, sink, None)
+    try_diff_workbooks_streaming_impl(old, new, pool, config, sink, None, None, &mut Vec::new())
 }
 
 pub fn try_diff_workbooks_streaming_with_progress<S: DiffSink>(
@@ -200,9 +201,45 @@ pub fn try_diff_workbooks_streaming_with_progress<S: DiffSink>(
     sink: &mut S,
     progress: &dyn ProgressCallback,
 ) -> Result<DiffSummary, DiffError> {
-    try_diff_workbooks_streaming_impl(old, new, pool, config, sink, Some(progress))
+    try_diff_workbooks_streaming_impl(
+        old,
+        new,
+        pool,
+        config,
+        sink,
+        Some(progress),
+        None,
+        &mut Vec::new(),
+    )
 }
 
+/// Like [`try_diff_workbooks_streaming`], but worksheets matched by `sheet_keys` are diffed in
+/// database mode with their keys. Every other sheet gets the usual grid diff.
+///
+/// For each sheet actually aligned by key, `keyed_rows` receives its `(old row, new row)` pairs;
+/// sheets that fall back to a positional diff are left out.
+pub(crate) fn try_diff_workbooks_database_mode_streaming<S: DiffSink>(
+    old: &Workbook,
+    new: &Workbook,
+    pool: &mut StringPool,
+    config: &DiffConfig,
+    sink: &mut S,
+    sheet_keys: &SheetKeyMap,
+    keyed_rows: &mut Vec<(SheetId, Vec<(u32, u32)>)>,
+) -> Result<DiffSummary, DiffError> {
+    try_diff_workbooks_streaming_impl(
+        old,
+        new,
+        pool,
+        config,
+        sink,
+        None,
+        Some(sheet_keys),
+        keyed_rows,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
 fn try_diff_workbooks_streaming_impl<'p, S: DiffSink>(
     old: &Workbook,
     new: &Workbook,
@@ -210,6 +247,8 @@ fn try_diff_workbooks_streaming_impl<'p, S: DiffSink>(
     config: &DiffConfig,
     sink: &mut S,
     progress: Option<&'p dyn ProgressCallback>,
+    sheet_keys: Option<&SheetKeyMap>,
+    keyed_rows: &mut Vec<(SheetId, Vec<(u32, u32)>)>,
 ) -> Result<DiffSummary, DiffError> {
     let mut hardening = HardeningController::new(config, progress);
     #[cfg(feature = "perf-metrics")]
@@ -494,19 +533,49 @@ fn try_diff_workbooks_streaming_impl<'p, S: DiffSink>(
                 } else {
                     old_sheet.name
                 };
-                try_diff_grids_internal(
-                    sheet_id,
-                    &old_sheet.grid,
-                    &new_sheet.grid,
-                    config,
-                    pool,
-                    sink,
-                    &mut op_count,
-                    &mut ctx,
-                    &mut hardening,
-                    #[cfg(feature = "perf-metrics")]
-                    Some(&mut metrics),
-                )?;
+                let keys = sheet_keys
+                    .filter(|_| new_sheet.kind == SheetKind::Worksheet)
+                    .and_then(|map| {
+                        map.keys_for(pool.resolve(new_sheet.name))
+                            .or_else(|| map.keys_for(pool.resolve(old_sheet.name)))
+                    });
+                if let Some(keys) = keys {
+                    let keys = keys.resolve(&old_sheet.grid, &new_sheet.grid, pool)?;
+                    let first_warning = ctx.warnings.len();
+                    let row_pairs = diff_grids_database_mode_keyed(
+                        sheet_id,
+                        &old_sheet.grid,
+                        &new_sheet.grid,
+                        &keys,
+                        pool,
+                        config,
+                        sink,
+                        &mut op_count,
+                        &mut ctx,
+                        &mut hardening,
+                    )?;
+                    keyed_rows.extend(row_pairs.map(|pairs| (sheet_id, pairs)));
+                    let sheet_name = pool.resolve(sheet_id);
+                    for warning in &mut ctx.warnings[first_warning..] {
+                        if !warning.starts_with("Sheet '") {
+                            *warning = format!("Sheet '{sheet_name}': {warning}");
+                        }
+                    }
+                } else {
+                    try_diff_grids_internal(
+                        sheet_id,
+                        &old_sheet.grid,
+                        &new_sheet.grid,
+                        config,
+                        pool,
+                        sink,
+                        &mut op_count,
+                        &mut ctx,
+                        &mut hardening,
+                        #[cfg(feature = "perf-metrics")]
+                        Some(&mut metrics),
+                    )?;
+                }
                 if hardening.should_abort() {
                     break;
                 }
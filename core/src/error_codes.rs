@@ -8,6 +8,7 @@ pub const PKG_ZIP_TOTAL_TOO_LARGE: &str = "EXDIFF_PKG_007";
 pub const PKG_ZIP_READ: &str = "EXDIFF_PKG_008";
 pub const PKG_UNSUPPORTED_FORMAT: &str = "EXDIFF_PKG_009";
 pub const PKG_NO_DATAMASHUP_USE_TABULAR_MODEL: &str = "EXDIFF_PKG_010";
+pub const PKG_INVALID_BINARY: &str = "EXDIFF_PKG_011";
 
 pub const GRID_XML_ERROR: &str = "EXDIFF_GRID_001";
 pub const GRID_INVALID_ADDRESS: &str = "EXDIFF_GRID_002";
@@ -35,3 +36,4 @@ pub const DIFF_LIMITS_EXCEEDED: &str = "EXDIFF_DIFF_001";
 pub const DIFF_SINK_ERROR: &str = "EXDIFF_DIFF_002";
 pub const DIFF_SHEET_NOT_FOUND: &str = "EXDIFF_DIFF_003";
 pub const DIFF_INTERNAL_ERROR: &str = "EXDIFF_DIFF_004";
+pub const DIFF_KEY_COLUMN_NOT_FOUND: &str = "EXDIFF_DIFF_005";
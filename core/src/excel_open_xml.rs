@@ -3,29 +3,44 @@
 //! Provides functions for opening `.xlsx` files and parsing their contents into
 //! the internal representation used for diffing.
 
+use crate::charts;
+use crate::comments;
+use crate::connections;
 use crate::container::{ContainerError, OpcContainer};
 use crate::datamashup_framing::{
     decode_datamashup_base64, parse_data_mashup, read_datamashup_text, DataMashupError,
     RawDataMashup,
 };
+use crate::drawings;
 use crate::error_codes;
+use crate::excel_tables;
 use crate::grid_parser::{
     parse_defined_names, parse_relationship_targets_by_type_contains, parse_relationships,
     parse_relationships_all, parse_shared_strings, parse_sheet_xml,
     parse_sheet_xml_with_drawing_rids, parse_workbook_xml, resolve_sheet_target, GridParseError,
 };
+use crate::pivots::{self, PivotCache};
+use crate::sheet_layout;
+use crate::sheet_rules;
 use crate::string_pool::StringId;
 use crate::string_pool::StringPool;
-use crate::vba::VbaModule;
+use crate::styles;
 #[cfg(feature = "vba")]
 use crate::vba::VbaModuleType;
-use crate::workbook::{ChartInfo, ChartObject, Grid, Sheet, SheetKind, Workbook};
-use std::collections::{HashMap, HashSet};
+use crate::vba::{VbaModule, VbaProject};
+use crate::workbook::{
+    CellComment, ChartInfo, ChartObject, DataConnection, ExcelTable, ExternalLink, Grid,
+    PivotTable, Sheet, SheetCellStyles, SheetComments, SheetDrawings, SheetKind, SheetLayout,
+    SheetRules, Workbook, WorkbookSettings,
+};
+use crate::workbook_settings::{self, PropertyPart};
+use crate::xlsb;
+use std::collections::{BTreeMap, HashMap, HashSet};
 #[cfg(feature = "std-fs")]
 use std::path::Path;
 use std::time::Instant;
 use thiserror::Error;
-use xxhash_rust::xxh3::Xxh3;
+use xxhash_rust::xxh3::{xxh3_64, Xxh3};
 
 #[derive(Debug, Error)]
 #[non_exhaustive]
@@ -60,6 +75,13 @@ pub enum PackageError {
         message: String,
     },
 
+    #[error("[EXDIFF_PKG_011] invalid binary record in '{part}' at offset {offset}: {message}. Suggestion: re-save the file in Excel.")]
+    InvalidBinaryPart {
+        part: String,
+        offset: usize,
+        message: String,
+    },
+
     #[error("[EXDIFF_PKG_009] unsupported format: {message}. Suggestion: verify the workbook is a standard .xlsx saved by Excel.")]
     UnsupportedFormat { message: String },
 
@@ -95,6 +117,7 @@ impl PackageError {
             }
             PackageError::MissingPart { .. } => error_codes::PKG_MISSING_PART,
             PackageError::InvalidXml { .. } => error_codes::PKG_INVALID_XML,
+            PackageError::InvalidBinaryPart { .. } => error_codes::PKG_INVALID_BINARY,
             PackageError::UnsupportedFormat { .. } => error_codes::PKG_UNSUPPORTED_FORMAT,
             PackageError::ReadPartFailed { .. } => error_codes::PKG_ZIP_READ,
             PackageError::DataMashupPartError { source, .. } => source.code(),
@@ -142,6 +165,50 @@ struct OpenWorkbookProfile {
     chart_xml_bytes: u64,
 }
 
+/// Part names for the workbook-level parts, which differ between XML and binary (`.xlsb`)
+/// workbooks. Relationship parts stay XML in both layouts.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct WorkbookParts {
+    pub(crate) workbook: &'static str,
+    pub(crate) workbook_rels: &'static str,
+    pub(crate) shared_strings: &'static str,
+    pub(crate) styles: &'static str,
+}
+
+impl WorkbookParts {
+    pub(crate) fn for_layout(binary: bool) -> Self {
+        if binary {
+            Self {
+                workbook: "xl/workbook.bin",
+                workbook_rels: "xl/_rels/workbook.bin.rels",
+                shared_strings: "xl/sharedStrings.bin",
+                styles: "xl/styles.bin",
+            }
+        } else {
+            Self {
+                workbook: "xl/workbook.xml",
+                workbook_rels: "xl/_rels/workbook.xml.rels",
+                shared_strings: "xl/sharedStrings.xml",
+                styles: "xl/styles.xml",
+            }
+        }
+    }
+}
+
+/// True when the package stores its workbook as BIFF12 records (`.xlsb`) rather than XML.
+pub(crate) fn is_binary_workbook(container: &OpcContainer) -> bool {
+    let mut has_xml = false;
+    let mut has_bin = false;
+    for name in container.file_names() {
+        match name {
+            "xl/workbook.xml" => has_xml = true,
+            "xl/workbook.bin" => has_bin = true,
+            _ => {}
+        }
+    }
+    has_bin && !has_xml
+}
+
 fn open_profile_enabled() -> bool {
     match std::env::var("EXCEL_DIFF_PROFILE_OPEN") {
         Ok(value) => value == "1" || value.eq_ignore_ascii_case("true"),
@@ -165,6 +232,9 @@ pub(crate) fn open_workbook_from_container_with_grid_filter(
     let total_start = Instant::now();
     let mut profile = OpenWorkbookProfile::default();
 
+    let binary = is_binary_workbook(container);
+    let parts = WorkbookParts::for_layout(binary);
+
     let wants_any_grids = grid_targets_to_parse
         .as_ref()
         .map(|targets| !targets.is_empty())
@@ -172,7 +242,7 @@ pub(crate) fn open_workbook_from_container_with_grid_filter(
 
     let shared_strings_bytes = if wants_any_grids {
         let started = Instant::now();
-        let payload = container.read_file_optional_checked("xl/sharedStrings.xml")?;
+        let payload = container.read_file_optional_checked(parts.shared_strings)?;
         if profile_enabled {
             profile.shared_strings_read_ms = profile
                 .shared_strings_read_ms
@@ -191,8 +261,13 @@ pub(crate) fn open_workbook_from_container_with_grid_filter(
         match shared_strings_bytes {
             Some(bytes) => {
                 let started = Instant::now();
-                let parsed = parse_shared_strings(&bytes, pool)
-                    .map_err(|e| wrap_grid_parse_error(e, "xl/sharedStrings.xml"))?;
+                let parsed = if binary {
+                    xlsb::parse_shared_strings_bin(&bytes, pool)
+                        .map_err(|e| wrap_xlsb_error(e, parts.shared_strings))?
+                } else {
+                    parse_shared_strings(&bytes, pool)
+                        .map_err(|e| wrap_grid_parse_error(e, parts.shared_strings))?
+                };
                 if profile_enabled {
                     profile.shared_strings_parse_ms = profile
                         .shared_strings_parse_ms
@@ -206,26 +281,27 @@ pub(crate) fn open_workbook_from_container_with_grid_filter(
         Vec::new()
     };
 
+    // Cell formats are only read from XML packages; `styles.bin` is not decoded yet.
+    let cell_styles = if wants_any_grids && !binary {
+        match container.read_file_optional_checked(parts.styles)? {
+            Some(bytes) => styles::parse_styles_xml(&bytes, pool)
+                .map_err(|e| wrap_grid_parse_error(e, parts.styles))?,
+            None => Vec::new(),
+        }
+    } else {
+        Vec::new()
+    };
+
     let workbook_bytes = {
         let started = Instant::now();
         let payload = container
-            .read_file_checked("xl/workbook.xml")
+            .read_file_checked(parts.workbook)
             .map_err(|e| match e {
-                ContainerError::FileNotFound { .. } => {
-                    if container.file_names().any(|name| name == "xl/workbook.bin") {
-                        PackageError::UnsupportedFormat {
-                            message:
-                                "XLSB detected (xl/workbook.bin present); convert to .xlsx/.xlsm"
-                                    .to_string(),
-                        }
-                    } else {
-                        PackageError::MissingPart {
-                            path: "xl/workbook.xml".to_string(),
-                        }
-                    }
-                }
+                ContainerError::FileNotFound { .. } => PackageError::MissingPart {
+                    path: parts.workbook.to_string(),
+                },
                 other => PackageError::ReadPartFailed {
-                    part: "xl/workbook.xml".to_string(),
+                    part: parts.workbook.to_string(),
                     message: other.to_string(),
                 },
             })?;
@@ -240,22 +316,44 @@ pub(crate) fn open_workbook_from_container_with_grid_filter(
         payload
     };
 
-    let sheets = {
+    let binary_workbook = if binary {
         let started = Instant::now();
-        let parsed = parse_workbook_xml(&workbook_bytes)
-            .map_err(|e| wrap_grid_parse_error(e, "xl/workbook.xml"))?;
+        let parsed = xlsb::parse_workbook_bin(&workbook_bytes)
+            .map_err(|e| wrap_xlsb_error(e, parts.workbook))?;
         if profile_enabled {
             profile.workbook_xml_parse_ms = profile
                 .workbook_xml_parse_ms
                 .saturating_add(started.elapsed().as_millis() as u64);
         }
-        parsed
+        Some(parsed)
+    } else {
+        None
+    };
+
+    let sheets = match binary_workbook.as_ref() {
+        Some(parsed) => parsed.sheets.clone(),
+        None => {
+            let started = Instant::now();
+            let parsed = parse_workbook_xml(&workbook_bytes)
+                .map_err(|e| wrap_grid_parse_error(e, parts.workbook))?;
+            if profile_enabled {
+                profile.workbook_xml_parse_ms = profile
+                    .workbook_xml_parse_ms
+                    .saturating_add(started.elapsed().as_millis() as u64);
+            }
+            parsed
+        }
     };
 
     let named_ranges = {
         let started = Instant::now();
-        let parsed = parse_defined_names(&workbook_bytes, &sheets, pool)
-            .map_err(|e| wrap_grid_parse_error(e, "xl/workbook.xml"))?;
+        let parsed = match binary_workbook.as_ref() {
+            Some(parsed) => parsed
+                .defined_names(pool)
+                .map_err(|e| wrap_xlsb_error(e, parts.workbook))?,
+            None => parse_defined_names(&workbook_bytes, &sheets, pool)
+                .map_err(|e| wrap_grid_parse_error(e, parts.workbook))?,
+        };
         if profile_enabled {
             profile.defined_names_parse_ms = profile
                 .defined_names_parse_ms
@@ -266,7 +364,7 @@ pub(crate) fn open_workbook_from_container_with_grid_filter(
 
     let workbook_rels_bytes = {
         let started = Instant::now();
-        let payload = container.read_file_optional_checked("xl/_rels/workbook.xml.rels")?;
+        let payload = container.read_file_optional_checked(parts.workbook_rels)?;
         if profile_enabled {
             profile.workbook_rels_read_ms = profile
                 .workbook_rels_read_ms
@@ -279,11 +377,11 @@ pub(crate) fn open_workbook_from_container_with_grid_filter(
         }
         payload
     };
-    let relationships = match workbook_rels_bytes {
+    let relationships = match workbook_rels_bytes.as_ref() {
         Some(bytes) => {
             let started = Instant::now();
-            let parsed = parse_relationships(&bytes)
-                .map_err(|e| wrap_grid_parse_error(e, "xl/_rels/workbook.xml.rels"))?;
+            let parsed = parse_relationships(bytes)
+                .map_err(|e| wrap_grid_parse_error(e, parts.workbook_rels))?;
             if profile_enabled {
                 profile.workbook_rels_parse_ms = profile
                     .workbook_rels_parse_ms
@@ -294,12 +392,44 @@ pub(crate) fn open_workbook_from_container_with_grid_filter(
         None => HashMap::new(),
     };
 
+    let persons = match workbook_rels_bytes.as_ref().filter(|_| !binary) {
+        Some(bytes) => read_comment_persons(container, parts, bytes)?,
+        None => HashMap::new(),
+    };
+    let (external_links, data_connections) = match workbook_rels_bytes.as_ref().filter(|_| !binary)
+    {
+        Some(bytes) => (
+            read_external_links(container, parts, &workbook_bytes, bytes, pool)?,
+            read_connections(container, parts, bytes, pool)?,
+        ),
+        None => (Vec::new(), Vec::new()),
+    };
+    let mut settings = if binary {
+        WorkbookSettings::default()
+    } else {
+        workbook_settings::parse_workbook_settings(&workbook_bytes, pool)
+            .map_err(|e| wrap_grid_parse_error(e, parts.workbook))?
+    };
+    settings.properties = read_document_properties(container, pool)?;
+
     let mut charts: Vec<ChartObject> = Vec::new();
+    let mut sheet_cell_styles: Vec<SheetCellStyles> = Vec::new();
+    let mut sheet_comments: Vec<SheetComments> = Vec::new();
+    let mut sheet_rules: Vec<SheetRules> = Vec::new();
+    let mut tables: Vec<ExcelTable> = Vec::new();
+    let mut sheet_drawings: Vec<SheetDrawings> = Vec::new();
+    let mut image_hashes: HashMap<String, u64> = HashMap::new();
+    let mut pivot_tables: Vec<PivotTable> = Vec::new();
+    let mut pivot_caches: HashMap<String, Option<PivotCache>> = HashMap::new();
     let mut chart_parts: HashMap<String, ChartPartCacheEntry> = HashMap::new();
 
     let mut sheet_ir = Vec::with_capacity(sheets.len());
     for (idx, sheet) in sheets.iter().enumerate() {
-        let target = resolve_sheet_target(sheet, &relationships, idx);
+        let target = if binary {
+            xlsb::resolve_binary_sheet_target(sheet, &relationships, idx)
+        } else {
+            resolve_sheet_target(sheet, &relationships, idx)
+        };
         let parse_grid = grid_targets_to_parse
             .as_ref()
             .map(|targets| targets.contains(&target))
@@ -323,6 +453,7 @@ pub(crate) fn open_workbook_from_container_with_grid_filter(
             payload
         };
 
+        let mut layout = SheetLayout::default();
         let (grid, drawing_rids) = if parse_grid {
             let sheet_bytes = {
                 let started = Instant::now();
@@ -345,15 +476,56 @@ pub(crate) fn open_workbook_from_container_with_grid_filter(
                 payload
             };
 
+            if !binary {
+                let cells = if styles::sheet_uses_styles(&sheet_bytes) {
+                    styles::parse_sheet_cell_styles(&sheet_bytes)
+                        .map_err(|e| wrap_grid_parse_error(e, &target))?
+                } else {
+                    Default::default()
+                };
+                sheet_cell_styles.push(SheetCellStyles {
+                    sheet: sheet_name_id,
+                    workbook_sheet_id: sheet.sheet_id,
+                    cells,
+                });
+
+                let (data_validations, conditional_formats) =
+                    if sheet_rules::sheet_has_rules(&sheet_bytes) {
+                        sheet_rules::parse_sheet_rules(&sheet_bytes, pool)
+                            .map_err(|e| wrap_grid_parse_error(e, &target))?
+                    } else {
+                        Default::default()
+                    };
+                sheet_rules.push(SheetRules {
+                    sheet: sheet_name_id,
+                    workbook_sheet_id: sheet.sheet_id,
+                    data_validations,
+                    conditional_formats,
+                });
+
+                if sheet_layout::sheet_has_layout(&sheet_bytes) {
+                    layout = sheet_layout::parse_sheet_layout(&sheet_bytes, pool)
+                        .map_err(|e| wrap_grid_parse_error(e, &target))?;
+                }
+            }
+
             let started = Instant::now();
-            let parsed = if sheet_rels_bytes.is_some() {
+            let parsed = if let Some(binary_workbook) = binary_workbook.as_ref() {
+                let parsed =
+                    xlsb::parse_sheet_bin(&sheet_bytes, &shared_strings, binary_workbook, pool)
+                        .map_err(|e| wrap_xlsb_error(e, &target))?;
+                layout.array_ranges = parsed.array_ranges;
+                (parsed.grid, Some(parsed.drawing_rids))
+            } else if sheet_rels_bytes.is_some() {
                 let parsed = parse_sheet_xml_with_drawing_rids(&sheet_bytes, &shared_strings, pool)
                     .map_err(|e| wrap_grid_parse_error(e, &target))?;
+                layout.array_ranges = parsed.array_ranges;
                 (parsed.grid, Some(parsed.drawing_rids))
             } else {
-                let grid = parse_sheet_xml(&sheet_bytes, &shared_strings, pool)
+                let parsed = parse_sheet_xml(&sheet_bytes, &shared_strings, pool)
                     .map_err(|e| wrap_grid_parse_error(e, &target))?;
-                (grid, None)
+                layout.array_ranges = parsed.array_ranges;
+                (parsed.grid, None)
             };
             if profile_enabled {
                 profile.sheet_parse_ms = profile
@@ -365,13 +537,60 @@ pub(crate) fn open_workbook_from_container_with_grid_filter(
             (Grid::new(0, 0), None)
         };
 
+        layout.visibility = sheet.visibility;
         sheet_ir.push(Sheet {
             name: sheet_name_id,
             workbook_sheet_id: sheet.sheet_id,
             kind: SheetKind::Worksheet,
             grid,
+            layout,
         });
 
+        if parse_grid && !binary {
+            let comments = match sheet_rels_bytes.as_ref() {
+                Some(bytes) => read_sheet_comments(
+                    container,
+                    &target,
+                    bytes,
+                    &sheet_rels_path,
+                    &persons,
+                    pool,
+                )?,
+                None => BTreeMap::new(),
+            };
+            sheet_comments.push(SheetComments {
+                sheet: sheet_name_id,
+                workbook_sheet_id: sheet.sheet_id,
+                comments,
+            });
+
+            if let Some(bytes) = sheet_rels_bytes.as_ref() {
+                tables.extend(read_sheet_tables(
+                    container,
+                    &target,
+                    bytes,
+                    &sheet_rels_path,
+                    (sheet_name_id, sheet.sheet_id),
+                    pool,
+                )?);
+                pivot_tables.extend(read_sheet_pivot_tables(
+                    container,
+                    &target,
+                    bytes,
+                    &sheet_rels_path,
+                    (sheet_name_id, sheet.sheet_id),
+                    &mut pivot_caches,
+                    pool,
+                )?);
+            }
+
+            sheet_drawings.push(SheetDrawings {
+                sheet: sheet_name_id,
+                workbook_sheet_id: sheet.sheet_id,
+                objects: Vec::new(),
+            });
+        }
+
         let sheet_rels_bytes = match sheet_rels_bytes {
             Some(bytes) => bytes,
             None => continue,
@@ -446,7 +665,13 @@ pub(crate) fn open_workbook_from_container_with_grid_filter(
                 }
                 parsed
             };
-            if drawing_chart_refs.is_empty() {
+            let mut drawing_objects = if parse_grid && !binary {
+                drawings::parse_drawing_objects(&drawing_bytes, pool)
+                    .map_err(|e| wrap_grid_parse_error(e, &drawing_part_path))?
+            } else {
+                Vec::new()
+            };
+            if drawing_chart_refs.is_empty() && drawing_objects.is_empty() {
                 continue;
             }
 
@@ -467,7 +692,10 @@ pub(crate) fn open_workbook_from_container_with_grid_filter(
                 payload
             } {
                 Some(bytes) => bytes,
-                None => continue,
+                None => {
+                    push_drawing_objects(&mut sheet_drawings, drawing_objects);
+                    continue;
+                }
             };
             let drawing_rels = {
                 let started = Instant::now();
@@ -481,6 +709,30 @@ pub(crate) fn open_workbook_from_container_with_grid_filter(
                 parsed
             };
 
+            for parsed in &mut drawing_objects {
+                let Some(image_target) = parsed
+                    .image_rel
+                    .as_ref()
+                    .and_then(|rid| drawing_rels.get(rid))
+                else {
+                    continue;
+                };
+                let image_part_path = resolve_target_against_part(&drawing_part_path, image_target);
+                let hash = match image_hashes.get(&image_part_path) {
+                    Some(hash) => Some(*hash),
+                    None => {
+                        let hash = read_optional_part(container, &image_part_path)?
+                            .map(|bytes| xxh3_64(&bytes));
+                        if let Some(hash) = hash {
+                            image_hashes.insert(image_part_path, hash);
+                        }
+                        hash
+                    }
+                };
+                parsed.object.image_hash = hash;
+            }
+            push_drawing_objects(&mut sheet_drawings, drawing_objects);
+
             for chart_ref in drawing_chart_refs {
                 let Some(chart_target) = drawing_rels.get(&chart_ref.rel_id) else {
                     continue;
@@ -508,9 +760,9 @@ pub(crate) fn open_workbook_from_container_with_grid_filter(
                     Some(entry) => entry.clone(),
                     None => {
                         let xml_hash = hash_xml_part(&chart_bytes);
-                        let (chart_type, data_range) = {
+                        let info = {
                             let started = Instant::now();
-                            let parsed = parse_chart_part_metadata(&chart_bytes, pool)
+                            let parsed = charts::parse_chart_xml(&chart_bytes, pool)
                                 .map_err(|e| wrap_grid_parse_error(e, &chart_part_path))?;
                             if profile_enabled {
                                 profile.chart_parse_ms = profile
@@ -519,11 +771,7 @@ pub(crate) fn open_workbook_from_container_with_grid_filter(
                             }
                             parsed
                         };
-                        let entry = ChartPartCacheEntry {
-                            xml_hash,
-                            chart_type,
-                            data_range,
-                        };
+                        let entry = ChartPartCacheEntry { xml_hash, info };
                         chart_parts.insert(chart_part_path.clone(), entry.clone());
                         entry
                     }
@@ -539,8 +787,7 @@ pub(crate) fn open_workbook_from_container_with_grid_filter(
                     workbook_sheet_id: sheet.sheet_id,
                     info: ChartInfo {
                         name: pool.intern(&name),
-                        chart_type: entry.chart_type,
-                        data_range: entry.data_range,
+                        ..entry.info
                     },
                     xml_hash: entry.xml_hash,
                 });
@@ -586,14 +833,246 @@ pub(crate) fn open_workbook_from_container_with_grid_filter(
         sheets: sheet_ir,
         named_ranges,
         charts,
+        cell_styles,
+        sheet_cell_styles,
+        sheet_comments,
+        sheet_rules,
+        tables,
+        sheet_drawings,
+        pivot_tables,
+        external_links,
+        connections: data_connections,
+        settings,
     })
 }
 
+/// Appends parsed drawing objects to the sheet currently being read.
+fn push_drawing_objects(
+    sheet_drawings: &mut [SheetDrawings],
+    objects: Vec<drawings::ParsedDrawingObject>,
+) {
+    if let Some(current) = sheet_drawings.last_mut() {
+        current
+            .objects
+            .extend(objects.into_iter().map(|parsed| parsed.object));
+    }
+}
+
+/// Display names for threaded comment authors, from the workbook's persons part.
+fn read_comment_persons(
+    container: &mut OpcContainer,
+    parts: WorkbookParts,
+    workbook_rels: &[u8],
+) -> Result<HashMap<String, String>, PackageError> {
+    let targets = parse_relationship_targets_by_type_contains(workbook_rels, comments::PERSONS_REL)
+        .map_err(|e| wrap_grid_parse_error(e, parts.workbook_rels))?;
+    let Some(target) = targets.first() else {
+        return Ok(HashMap::new());
+    };
+    let path = resolve_target_against_part(parts.workbook, target);
+    match read_optional_part(container, &path)? {
+        Some(bytes) => comments::parse_persons(&bytes).map_err(|e| wrap_grid_parse_error(e, &path)),
+        None => Ok(HashMap::new()),
+    }
+}
+
+/// External links in `<externalReferences>` order, numbered the way formulas refer to them.
+fn read_external_links(
+    container: &mut OpcContainer,
+    parts: WorkbookParts,
+    workbook_xml: &[u8],
+    workbook_rels: &[u8],
+    pool: &mut StringPool,
+) -> Result<Vec<ExternalLink>, PackageError> {
+    let ids = connections::parse_external_reference_ids(workbook_xml)
+        .map_err(|e| wrap_grid_parse_error(e, parts.workbook))?;
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let rels = parse_relationships_all(workbook_rels)
+        .map_err(|e| wrap_grid_parse_error(e, parts.workbook_rels))?;
+
+    let mut links = Vec::new();
+    for (idx, id) in ids.iter().enumerate() {
+        let Some(target) = rels.get(id) else {
+            continue;
+        };
+        let path = resolve_target_against_part(parts.workbook, target);
+        let Some(bytes) = read_optional_part(container, &path)? else {
+            continue;
+        };
+        let link_rels_path = rels_part_path(&path);
+        let targets = match read_optional_part(container, &link_rels_path)? {
+            Some(rels) => parse_relationships_all(&rels)
+                .map_err(|e| wrap_grid_parse_error(e, &link_rels_path))?,
+            None => HashMap::new(),
+        };
+        let link = connections::parse_external_link_xml(&bytes, idx as u32 + 1, &targets, pool)
+            .map_err(|e| wrap_grid_parse_error(e, &path))?;
+        links.extend(link);
+    }
+    Ok(links)
+}
+
+fn read_connections(
+    container: &mut OpcContainer,
+    parts: WorkbookParts,
+    workbook_rels: &[u8],
+    pool: &mut StringPool,
+) -> Result<Vec<DataConnection>, PackageError> {
+    let targets =
+        parse_relationship_targets_by_type_contains(workbook_rels, connections::CONNECTIONS_REL)
+            .map_err(|e| wrap_grid_parse_error(e, parts.workbook_rels))?;
+    let Some(target) = targets.first() else {
+        return Ok(Vec::new());
+    };
+    let path = resolve_target_against_part(parts.workbook, target);
+    match read_optional_part(container, &path)? {
+        Some(bytes) => connections::parse_connections_xml(&bytes, pool)
+            .map_err(|e| wrap_grid_parse_error(e, &path)),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Document properties from the `docProps` parts, sorted by name.
+fn read_document_properties(
+    container: &mut OpcContainer,
+    pool: &mut StringPool,
+) -> Result<Vec<(StringId, StringId)>, PackageError> {
+    let mut properties = Vec::new();
+    for part in PropertyPart::ALL {
+        let path = part.path();
+        if let Some(bytes) = read_optional_part(container, path)? {
+            let parsed = workbook_settings::parse_document_properties(&bytes, part)
+                .map_err(|e| wrap_grid_parse_error(e, path))?;
+            properties.extend(parsed);
+        }
+    }
+    properties.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(properties
+        .into_iter()
+        .map(|(name, value)| (pool.intern(&name), pool.intern(&value)))
+        .collect())
+}
+
+/// Notes and threaded comments attached to a worksheet. When a cell carries both, the threaded
+/// conversation wins: Excel keeps a placeholder note alongside it for older readers.
+fn read_sheet_comments(
+    container: &mut OpcContainer,
+    sheet_part: &str,
+    sheet_rels: &[u8],
+    sheet_rels_path: &str,
+    persons: &HashMap<String, String>,
+    pool: &mut StringPool,
+) -> Result<BTreeMap<(u32, u32), CellComment>, PackageError> {
+    let mut merged = BTreeMap::new();
+
+    let legacy =
+        parse_relationship_targets_by_type_contains(sheet_rels, comments::LEGACY_COMMENTS_REL)
+            .map_err(|e| wrap_grid_parse_error(e, sheet_rels_path))?;
+    for target in legacy {
+        let path = resolve_target_against_part(sheet_part, &target);
+        if let Some(bytes) = read_optional_part(container, &path)? {
+            let notes = comments::parse_legacy_comments(&bytes, pool)
+                .map_err(|e| wrap_grid_parse_error(e, &path))?;
+            merged.extend(notes);
+        }
+    }
+
+    let threaded =
+        parse_relationship_targets_by_type_contains(sheet_rels, comments::THREADED_COMMENTS_REL)
+            .map_err(|e| wrap_grid_parse_error(e, sheet_rels_path))?;
+    for target in threaded {
+        let path = resolve_target_against_part(sheet_part, &target);
+        if let Some(bytes) = read_optional_part(container, &path)? {
+            let threads = comments::parse_threaded_comments(&bytes, persons, pool)
+                .map_err(|e| wrap_grid_parse_error(e, &path))?;
+            merged.extend(threads);
+        }
+    }
+
+    Ok(merged)
+}
+
+/// Read the tables attached to a worksheet, in relationship order.
+fn read_sheet_tables(
+    container: &mut OpcContainer,
+    sheet_part: &str,
+    sheet_rels: &[u8],
+    sheet_rels_path: &str,
+    (sheet, workbook_sheet_id): (StringId, Option<u32>),
+    pool: &mut StringPool,
+) -> Result<Vec<ExcelTable>, PackageError> {
+    let targets = parse_relationship_targets_by_type_contains(sheet_rels, excel_tables::TABLE_REL)
+        .map_err(|e| wrap_grid_parse_error(e, sheet_rels_path))?;
+    let mut tables = Vec::new();
+    for target in targets {
+        let path = resolve_target_against_part(sheet_part, &target);
+        if let Some(bytes) = read_optional_part(container, &path)? {
+            let table = excel_tables::parse_table_xml(&bytes, sheet, workbook_sheet_id, pool)
+                .map_err(|e| wrap_grid_parse_error(e, &path))?;
+            tables.extend(table);
+        }
+    }
+    Ok(tables)
+}
+
+/// Pivot tables attached to a worksheet. Cache definitions are parsed once per part and shared;
+/// a pivot whose cache part is missing is skipped.
+fn read_sheet_pivot_tables(
+    container: &mut OpcContainer,
+    sheet_part: &str,
+    sheet_rels: &[u8],
+    sheet_rels_path: &str,
+    sheet: (StringId, Option<u32>),
+    caches: &mut HashMap<String, Option<PivotCache>>,
+    pool: &mut StringPool,
+) -> Result<Vec<PivotTable>, PackageError> {
+    let targets = parse_relationship_targets_by_type_contains(sheet_rels, pivots::PIVOT_TABLE_REL)
+        .map_err(|e| wrap_grid_parse_error(e, sheet_rels_path))?;
+    let mut tables = Vec::new();
+    for target in targets {
+        let path = resolve_target_against_part(sheet_part, &target);
+        let Some(bytes) = read_optional_part(container, &path)? else {
+            continue;
+        };
+        let rels_path = rels_part_path(&path);
+        let Some(rels) = read_optional_part(container, &rels_path)? else {
+            continue;
+        };
+        let cache_targets =
+            parse_relationship_targets_by_type_contains(&rels, pivots::PIVOT_CACHE_REL)
+                .map_err(|e| wrap_grid_parse_error(e, &rels_path))?;
+        let Some(cache_target) = cache_targets.first() else {
+            continue;
+        };
+        let cache_path = resolve_target_against_part(&path, cache_target);
+        if !caches.contains_key(&cache_path) {
+            let cache = match read_optional_part(container, &cache_path)? {
+                Some(cache_bytes) => Some(
+                    pivots::parse_pivot_cache_xml(&cache_bytes, pool)
+                        .map_err(|e| wrap_grid_parse_error(e, &cache_path))?,
+                ),
+                None => None,
+            };
+            caches.insert(cache_path.clone(), cache);
+        }
+        let Some(Some(cache)) = caches.get(&cache_path) else {
+            continue;
+        };
+        let table = pivots::parse_pivot_table_xml(&bytes, cache, sheet, pool)
+            .map_err(|e| wrap_grid_parse_error(e, &path))?;
+        tables.extend(table);
+    }
+    Ok(tables)
+}
+
+/// Modules and project metadata from `xl/vbaProject.bin`, read from one parse of the project.
 #[cfg(feature = "vba")]
-pub(crate) fn open_vba_modules_from_container(
+pub(crate) fn open_vba_from_container(
     container: &mut OpcContainer,
     pool: &mut StringPool,
-) -> Result<Option<Vec<VbaModule>>, PackageError> {
+) -> Result<Option<(Vec<VbaModule>, VbaProject)>, PackageError> {
     let bytes = match container.read_file_optional_checked("xl/vbaProject.bin")? {
         Some(bytes) => bytes,
         None => return Ok(None),
@@ -626,22 +1105,32 @@ pub(crate) fn open_vba_modules_from_container(
         });
     }
 
-    Ok(Some(modules))
+    let dir_path = if cfg!(windows) {
+        "/VBA\\dir"
+    } else {
+        "/VBA/dir"
+    };
+    let dir = project
+        .decompress_stream_from(dir_path, 0)
+        .unwrap_or_default();
+    let project_stream = project.read_stream("/PROJECT").unwrap_or_default();
+    let metadata = crate::vba::parse_project_metadata(&dir, &project_stream);
+
+    Ok(Some((modules, metadata)))
 }
 
 #[cfg(not(feature = "vba"))]
-pub(crate) fn open_vba_modules_from_container(
+pub(crate) fn open_vba_from_container(
     _container: &mut OpcContainer,
     _pool: &mut StringPool,
-) -> Result<Option<Vec<VbaModule>>, PackageError> {
+) -> Result<Option<(Vec<VbaModule>, VbaProject)>, PackageError> {
     Ok(None)
 }
 
 #[derive(Debug, Clone)]
 struct ChartPartCacheEntry {
     xml_hash: u128,
-    chart_type: StringId,
-    data_range: Option<StringId>,
+    info: ChartInfo,
 }
 
 #[derive(Debug, Clone)]
@@ -765,50 +1254,6 @@ fn parse_drawing_chart_refs(drawing_xml: &[u8]) -> Result<Vec<DrawingChartRef>,
     Ok(refs)
 }
 
-fn parse_chart_part_metadata(
-    chart_xml: &[u8],
-    pool: &mut StringPool,
-) -> Result<(StringId, Option<StringId>), GridParseError> {
-    let mut reader = quick_xml::Reader::from_reader(chart_xml);
-    reader.config_mut().trim_text(true);
-    let mut buf = Vec::new();
-
-    let mut chart_type: Option<String> = None;
-    let mut data_range: Option<String> = None;
-
-    loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(quick_xml::events::Event::Start(e))
-                if local_name(e.name().as_ref()) == b"f" && data_range.is_none() =>
-            {
-                let text = reader
-                    .read_text(e.name())
-                    .map_err(|e| GridParseError::XmlError(e.to_string()))?
-                    .into_owned();
-                let trimmed = text.trim();
-                if !trimmed.is_empty() {
-                    data_range = Some(trimmed.to_string());
-                }
-            }
-            Ok(quick_xml::events::Event::Start(e)) | Ok(quick_xml::events::Event::Empty(e)) => {
-                let name = e.name();
-                let tag = local_name(name.as_ref());
-                if chart_type.is_none() && tag.ends_with(b"Chart") {
-                    chart_type = Some(String::from_utf8_lossy(tag).to_string());
-                }
-            }
-            Ok(quick_xml::events::Event::Eof) => break,
-            Err(e) => return Err(GridParseError::XmlError(e.to_string())),
-            _ => {}
-        }
-        buf.clear();
-    }
-
-    let chart_type_id = pool.intern(chart_type.as_deref().unwrap_or("unknown"));
-    let data_range_id = data_range.as_deref().map(|s| pool.intern(s));
-    Ok((chart_type_id, data_range_id))
-}
-
 fn fallback_chart_name_from_path(chart_part_path: &str) -> String {
     let file = chart_part_path
         .rsplit('/')
@@ -823,6 +1268,14 @@ fn hash_xml_part(xml: &[u8]) -> u128 {
     hasher.digest128()
 }
 
+pub(crate) fn wrap_xlsb_error(err: xlsb::XlsbError, part: &str) -> PackageError {
+    PackageError::InvalidBinaryPart {
+        part: part.to_string(),
+        offset: err.offset,
+        message: err.message,
+    }
+}
+
 pub(crate) fn wrap_grid_parse_error(err: GridParseError, part: &str) -> PackageError {
     match err {
         GridParseError::XmlErrorAt {
@@ -867,14 +1320,14 @@ pub fn open_workbook(
 
 #[cfg(feature = "std-fs")]
 #[allow(deprecated)]
-pub fn open_vba_modules(
+pub fn open_vba(
     path: impl AsRef<Path>,
     pool: &mut StringPool,
-) -> Result<Option<Vec<VbaModule>>, PackageError> {
+) -> Result<Option<(Vec<VbaModule>, VbaProject)>, PackageError> {
     let path_str = path.as_ref().display().to_string();
     let mut container = OpcContainer::open_from_path(path.as_ref())
         .map_err(|e| PackageError::from(e).with_path(&path_str))?;
-    open_vba_modules_from_container(&mut container, pool).map_err(|e| e.with_path(&path_str))
+    open_vba_from_container(&mut container, pool).map_err(|e| e.with_path(&path_str))
 }
 
 pub(crate) fn open_data_mashup_from_container(
@@ -0,0 +1,116 @@
+//! Excel table (ListObject) parsing.
+//!
+//! Each table lives in its own `xl/tables/table*.xml` part, attached to a worksheet through its
+//! relationships. Column names come from `<tableColumn name>`, which Excel keeps in sync with the
+//! header cells.
+
+use crate::grid_parser::{local_tag_name, xml_err, GridParseError};
+use crate::string_pool::{StringId, StringPool};
+use crate::styles::{attr, collect_attrs};
+use crate::workbook::{CellRange, ExcelTable, ExcelTableColumn};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+/// Also matches `tableSingleCells` parts, which have no `<table>` root and parse to `None`.
+pub(crate) const TABLE_REL: &str = "relationships/table";
+
+fn parse_u32(value: Option<&str>) -> Option<u32> {
+    value.and_then(|v| v.trim().parse().ok())
+}
+
+/// Parse one table part. Returns `None` when the part has no `<table>` with a range.
+pub(crate) fn parse_table_xml(
+    xml: &[u8],
+    sheet: StringId,
+    workbook_sheet_id: Option<u32>,
+    pool: &mut StringPool,
+) -> Result<Option<ExcelTable>, GridParseError> {
+    let mut reader = Reader::from_reader(xml);
+    reader.config_mut().trim_text(true);
+    let mut buf = Vec::new();
+
+    let mut table: Option<ExcelTable> = None;
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) => match local_tag_name(e.name().as_ref()) {
+                b"table" if table.is_none() => {
+                    let attrs = collect_attrs(&reader, xml, &e)?;
+                    if let Some(raw) = attr(&attrs, "ref") {
+                        let range = raw
+                            .trim()
+                            .parse::<CellRange>()
+                            .map_err(|_| GridParseError::InvalidAddress(raw.to_string()))?;
+                        let name = attr(&attrs, "displayName")
+                            .or_else(|| attr(&attrs, "name"))
+                            .unwrap_or_default();
+                        table = Some(ExcelTable {
+                            sheet,
+                            workbook_sheet_id,
+                            id: parse_u32(attr(&attrs, "id")).unwrap_or(0),
+                            name: pool.intern(name),
+                            range,
+                            header_row_count: parse_u32(attr(&attrs, "headerRowCount"))
+                                .unwrap_or(1),
+                            totals_row_count: parse_u32(attr(&attrs, "totalsRowCount"))
+                                .unwrap_or(0),
+                            columns: Vec::new(),
+                        });
+                    }
+                }
+                b"tableColumn" => {
+                    let attrs = collect_attrs(&reader, xml, &e)?;
+                    if let Some(table) = table.as_mut() {
+                        let id =
+                            parse_u32(attr(&attrs, "id")).unwrap_or(table.columns.len() as u32 + 1);
+                        let name = pool.intern(attr(&attrs, "name").unwrap_or_default());
+                        table.columns.push(ExcelTableColumn { id, name });
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(xml_err(&reader, xml, e)),
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_table_range_row_counts_and_columns() {
+        let xml = br#"<table xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" id="3" name="Table3" displayName="Orders" ref="B2:D10" totalsRowCount="1"><autoFilter ref="B2:D9"/><tableColumns count="3"><tableColumn id="1" name="OrderId"/><tableColumn id="2" name="Qty &amp; Unit"/><tableColumn id="4" name="Total" totalsRowFunction="sum"/></tableColumns><tableStyleInfo name="TableStyleMedium2" showRowStripes="1"/></table>"#;
+        let mut pool = StringPool::new();
+        let sheet = pool.intern("Sheet1");
+        let table = parse_table_xml(xml, sheet, Some(1), &mut pool)
+            .expect("parse table")
+            .expect("table present");
+
+        assert_eq!(table.id, 3);
+        assert_eq!(pool.resolve(table.name), "Orders");
+        assert_eq!(table.range.to_a1(), "B2:D10");
+        assert_eq!(table.header_row_count, 1);
+        assert_eq!(table.totals_row_count, 1);
+        assert_eq!(table.data_rows(), 2..9);
+        let columns: Vec<(u32, &str)> = table
+            .columns
+            .iter()
+            .map(|c| (c.id, pool.resolve(c.name)))
+            .collect();
+        assert_eq!(
+            columns,
+            vec![(1, "OrderId"), (2, "Qty & Unit"), (4, "Total")]
+        );
+
+        let single_cells = br#"<singleXmlCells xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"/>"#;
+        assert!(parse_table_xml(single_cells, sheet, None, &mut pool)
+            .expect("parse")
+            .is_none());
+    }
+}
@@ -1,4 +1,5 @@
 use std::fmt;
+use std::ops::Range;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum FormulaExpr {
@@ -113,15 +114,67 @@ impl fmt::Display for FormulaParseError {
 impl std::error::Error for FormulaParseError {}
 
 pub fn parse_formula(formula: &str) -> Result<FormulaExpr, FormulaParseError> {
-    let s = formula.trim();
-    let s = s.strip_prefix('=').unwrap_or(s);
+    parse_formula_with_refs(formula).map(|(expr, _)| expr)
+}
+
+/// A1 cell references with their byte ranges in the formula text they were parsed from.
+type RefSpans = Vec<(Range<usize>, CellReference)>;
+
+/// Parse `formula` and list where each A1 cell reference sits in it, as byte ranges of
+/// `formula` itself.
+fn parse_formula_with_refs(formula: &str) -> Result<(FormulaExpr, RefSpans), FormulaParseError> {
+    let s = formula.trim_start();
+    let s = s.strip_prefix('=').unwrap_or(s).trim_end();
+    let base = formula.len() - formula.trim_start().len()
+        + usize::from(formula.trim_start().starts_with('='));
     let mut p = Parser::new(s);
     let expr = p.parse_expr(0)?;
     p.skip_ws();
     if !p.eof() {
         return Err(p.err("trailing characters"));
     }
-    Ok(expr)
+    let refs = p
+        .refs
+        .into_iter()
+        .map(|(span, r)| (span.start + base..span.end + base, r))
+        .collect();
+    Ok((expr, refs))
+}
+
+/// A formula's text with the positions of its A1 cell references, so it can be rewritten for
+/// another cell without reformatting the rest: spacing, number spelling and name case are kept.
+#[derive(Debug, Clone)]
+pub(crate) struct FormulaTemplate {
+    text: String,
+    refs: RefSpans,
+}
+
+impl FormulaTemplate {
+    /// `None` when the formula does not parse.
+    pub(crate) fn parse(formula: &str) -> Option<FormulaTemplate> {
+        let (_, refs) = parse_formula_with_refs(formula).ok()?;
+        Some(FormulaTemplate {
+            text: formula.to_string(),
+            refs,
+        })
+    }
+
+    /// The formula with its references shifted the way [`FormulaExpr::shifted`] shifts them.
+    pub(crate) fn shifted(&self, row_shift: i32, col_shift: i32, mode: ShiftMode) -> String {
+        let mut out = String::with_capacity(self.text.len());
+        let mut copied = 0;
+        for (span, r) in &self.refs {
+            out.push_str(&self.text[copied..span.start]);
+            let shifted = CellReference {
+                sheet: None,
+                ..shift_cell_ref(r, row_shift, col_shift, mode)
+            };
+            out.push_str(&FormulaExpr::CellRef(shifted).to_string());
+            copied = span.end;
+        }
+        out.push_str(&self.text[copied..]);
+        out
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -143,6 +196,17 @@ impl FormulaExpr {
         shift_expr(self, row_shift, col_shift, mode)
     }
 
+    /// Rewrite same-sheet A1 references the way Excel does when rows or columns are inserted,
+    /// deleted or moved. `map_row`/`map_col` take and return zero-based indices; references to
+    /// other sheets and cells that no longer exist are left as they were.
+    pub(crate) fn remapped(
+        &self,
+        map_row: &dyn Fn(u32) -> Option<u32>,
+        map_col: &dyn Fn(u32) -> Option<u32>,
+    ) -> FormulaExpr {
+        remap_expr(self, map_row, map_col)
+    }
+
     fn canonicalize_in_place(&mut self) {
         match self {
             FormulaExpr::FunctionCall { name, args } => {
@@ -298,6 +362,74 @@ fn shift_expr(e: &FormulaExpr, row_shift: i32, col_shift: i32, mode: ShiftMode)
     }
 }
 
+fn remap_expr(
+    e: &FormulaExpr,
+    map_row: &dyn Fn(u32) -> Option<u32>,
+    map_col: &dyn Fn(u32) -> Option<u32>,
+) -> FormulaExpr {
+    match e {
+        FormulaExpr::CellRef(r) if r.sheet.is_none() => {
+            FormulaExpr::CellRef(remap_cell_ref(r, map_row, map_col))
+        }
+        FormulaExpr::RangeRef(r) if r.sheet.is_none() && r.start.sheet.is_none() => {
+            let mut rr = r.clone();
+            rr.start = remap_cell_ref(&rr.start, map_row, map_col);
+            rr.end = remap_cell_ref(&rr.end, map_row, map_col);
+            FormulaExpr::RangeRef(rr)
+        }
+        FormulaExpr::FunctionCall { name, args } => FormulaExpr::FunctionCall {
+            name: name.clone(),
+            args: args
+                .iter()
+                .map(|a| remap_expr(a, map_row, map_col))
+                .collect(),
+        },
+        FormulaExpr::UnaryOp { op, operand } => FormulaExpr::UnaryOp {
+            op: *op,
+            operand: Box::new(remap_expr(operand, map_row, map_col)),
+        },
+        FormulaExpr::BinaryOp { op, left, right } => FormulaExpr::BinaryOp {
+            op: *op,
+            left: Box::new(remap_expr(left, map_row, map_col)),
+            right: Box::new(remap_expr(right, map_row, map_col)),
+        },
+        FormulaExpr::Array(rows) => FormulaExpr::Array(
+            rows.iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|x| remap_expr(x, map_row, map_col))
+                        .collect()
+                })
+                .collect(),
+        ),
+        _ => e.clone(),
+    }
+}
+
+fn remap_cell_ref(
+    r: &CellReference,
+    map_row: &dyn Fn(u32) -> Option<u32>,
+    map_col: &dyn Fn(u32) -> Option<u32>,
+) -> CellReference {
+    // A1 references are one-based; zero marks a whole-row or whole-column part.
+    let remap = |n: u32, map: &dyn Fn(u32) -> Option<u32>| match n {
+        0 => 0,
+        n => map(n - 1).map_or(n, |m| m + 1),
+    };
+    let mut out = r.clone();
+    out.row = match r.row {
+        RowRef::Absolute(n) => RowRef::Absolute(remap(n, map_row)),
+        RowRef::Relative(n) => RowRef::Relative(remap(n, map_row)),
+        RowRef::Offset(n) => RowRef::Offset(n),
+    };
+    out.col = match r.col {
+        ColRef::Absolute(n) => ColRef::Absolute(remap(n, map_col)),
+        ColRef::Relative(n) => ColRef::Relative(remap(n, map_col)),
+        ColRef::Offset(n) => ColRef::Offset(n),
+    };
+    out
+}
+
 fn shift_cell_ref(
     r: &CellReference,
     row_shift: i32,
@@ -358,9 +490,221 @@ pub fn formulas_equivalent_modulo_shift(
     a_shifted == b_canon
 }
 
+/// Renders A1-style formula text (without the leading `=`) that parses back to the same
+/// expression. Parentheses are emitted only where precedence needs them, so the output can differ
+/// in spacing and grouping from the text that was parsed.
+impl fmt::Display for FormulaExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormulaExpr::Number(n) => write!(f, "{n}"),
+            FormulaExpr::Text(s) => write!(f, "\"{}\"", s.replace('"', "\"\"")),
+            FormulaExpr::Boolean(b) => f.write_str(if *b { "TRUE" } else { "FALSE" }),
+            FormulaExpr::Error(e) => f.write_str(e.as_str()),
+            FormulaExpr::CellRef(r) => {
+                write_sheet_prefix(f, r.sheet.as_deref())?;
+                write_cell_ref(f, r)
+            }
+            FormulaExpr::RangeRef(r) => {
+                write_sheet_prefix(f, r.sheet.as_deref().or(r.start.sheet.as_deref()))?;
+                write_cell_ref(f, &r.start)?;
+                f.write_str(":")?;
+                write_cell_ref(f, &r.end)
+            }
+            FormulaExpr::NamedRef(name) => f.write_str(name),
+            FormulaExpr::FunctionCall { name, args } => {
+                write!(f, "{name}(")?;
+                for (idx, arg) in args.iter().enumerate() {
+                    if idx > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{arg}")?;
+                }
+                f.write_str(")")
+            }
+            FormulaExpr::UnaryOp { op, operand } => {
+                let operand_prec = precedence(operand);
+                match op {
+                    UnaryOperator::Plus => f.write_str("+")?,
+                    UnaryOperator::Minus => f.write_str("-")?,
+                    UnaryOperator::Percent => {}
+                }
+                write_operand(f, operand, operand_prec < UNARY_PRECEDENCE)?;
+                if *op == UnaryOperator::Percent {
+                    f.write_str("%")?;
+                }
+                Ok(())
+            }
+            FormulaExpr::BinaryOp { op, left, right } => {
+                let prec = binary_precedence(*op);
+                // `^` is right-associative; every other operator groups to the left.
+                let right_assoc = *op == BinaryOperator::Pow;
+                let (left_prec, right_prec) = (precedence(left), precedence(right));
+                let left_parens = left_prec < prec || (right_assoc && left_prec == prec);
+                let right_parens = right_prec < prec || (!right_assoc && right_prec == prec);
+                write_operand(f, left, left_parens)?;
+                f.write_str(binary_symbol(*op))?;
+                write_operand(f, right, right_parens)
+            }
+            FormulaExpr::Array(rows) => {
+                f.write_str("{")?;
+                for (row_idx, row) in rows.iter().enumerate() {
+                    if row_idx > 0 {
+                        f.write_str(";")?;
+                    }
+                    for (idx, item) in row.iter().enumerate() {
+                        if idx > 0 {
+                            f.write_str(",")?;
+                        }
+                        write!(f, "{item}")?;
+                    }
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+impl ExcelError {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ExcelError::Null => "#NULL!",
+            ExcelError::Div0 => "#DIV/0!",
+            ExcelError::Value => "#VALUE!",
+            ExcelError::Ref => "#REF!",
+            ExcelError::Name => "#NAME?",
+            ExcelError::Num => "#NUM!",
+            ExcelError::NA => "#N/A",
+            ExcelError::Spill => "#SPILL!",
+            ExcelError::Calc => "#CALC!",
+            ExcelError::GettingData => "#GETTING_DATA",
+            ExcelError::Unknown(text) => text,
+        }
+    }
+}
+
+/// Binding strength of unary operators, above every binary operator (matches the parser).
+const UNARY_PRECEDENCE: u8 = 90;
+
+fn binary_precedence(op: BinaryOperator) -> u8 {
+    match op {
+        BinaryOperator::Eq
+        | BinaryOperator::Ne
+        | BinaryOperator::Lt
+        | BinaryOperator::Le
+        | BinaryOperator::Gt
+        | BinaryOperator::Ge => 30,
+        BinaryOperator::Concat => 40,
+        BinaryOperator::Add | BinaryOperator::Sub => 50,
+        BinaryOperator::Mul | BinaryOperator::Div => 60,
+        BinaryOperator::Pow => 70,
+    }
+}
+
+fn precedence(e: &FormulaExpr) -> u8 {
+    match e {
+        FormulaExpr::BinaryOp { op, .. } => binary_precedence(*op),
+        FormulaExpr::UnaryOp { .. } => UNARY_PRECEDENCE,
+        _ => u8::MAX,
+    }
+}
+
+pub(crate) fn binary_symbol(op: BinaryOperator) -> &'static str {
+    match op {
+        BinaryOperator::Add => "+",
+        BinaryOperator::Sub => "-",
+        BinaryOperator::Mul => "*",
+        BinaryOperator::Div => "/",
+        BinaryOperator::Pow => "^",
+        BinaryOperator::Concat => "&",
+        BinaryOperator::Eq => "=",
+        BinaryOperator::Ne => "<>",
+        BinaryOperator::Lt => "<",
+        BinaryOperator::Le => "<=",
+        BinaryOperator::Gt => ">",
+        BinaryOperator::Ge => ">=",
+    }
+}
+
+fn write_operand(f: &mut fmt::Formatter<'_>, e: &FormulaExpr, parens: bool) -> fmt::Result {
+    if parens {
+        write!(f, "({e})")
+    } else {
+        write!(f, "{e}")
+    }
+}
+
+fn write_sheet_prefix(f: &mut fmt::Formatter<'_>, sheet: Option<&str>) -> fmt::Result {
+    let Some(sheet) = sheet else {
+        return Ok(());
+    };
+    let bare = sheet.starts_with('[')
+        || (sheet.bytes().next().is_some_and(is_ident_start)
+            && sheet.bytes().all(is_ident_continue));
+    if bare {
+        write!(f, "{sheet}!")
+    } else {
+        write!(f, "'{}'!", sheet.replace('\'', "''"))
+    }
+}
+
+/// The sheet prefix of a whole-row or whole-column range, quoted like a cell reference's so the
+/// range renders back to text that parses.
+struct SheetPrefix<'a>(Option<&'a str>);
+
+impl fmt::Display for SheetPrefix<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_sheet_prefix(f, self.0)
+    }
+}
+
+/// A1 form for one-based references; R1C1 form when either part is an offset.
+fn write_cell_ref(f: &mut fmt::Formatter<'_>, r: &CellReference) -> fmt::Result {
+    if matches!(r.row, RowRef::Offset(_)) || matches!(r.col, ColRef::Offset(_)) {
+        match r.row {
+            RowRef::Offset(n) => write!(f, "R[{n}]")?,
+            RowRef::Absolute(n) => write!(f, "R{n}")?,
+            RowRef::Relative(_) => f.write_str("R")?,
+        }
+        match r.col {
+            ColRef::Offset(n) => write!(f, "C[{n}]")?,
+            ColRef::Absolute(n) => write!(f, "C{n}")?,
+            ColRef::Relative(_) => f.write_str("C")?,
+        }
+    } else {
+        let (col, col_abs) = match r.col {
+            ColRef::Absolute(n) => (n, true),
+            ColRef::Relative(n) => (n, false),
+            ColRef::Offset(_) => (0, false),
+        };
+        let (row, row_abs) = match r.row {
+            RowRef::Absolute(n) => (n, true),
+            RowRef::Relative(n) => (n, false),
+            RowRef::Offset(_) => (0, false),
+        };
+        // Shifting a reference off the top or left edge leaves a zero index.
+        if row == 0 || col == 0 {
+            return f.write_str("#REF!");
+        }
+        if col_abs {
+            f.write_str("$")?;
+        }
+        f.write_str(&col_u32_to_letters(col))?;
+        if row_abs {
+            f.write_str("$")?;
+        }
+        write!(f, "{row}")?;
+    }
+    if r.spill {
+        f.write_str("#")?;
+    }
+    Ok(())
+}
+
 struct Parser<'a> {
     s: &'a [u8],
     pos: usize,
+    /// Every A1 cell reference parsed so far, with its byte range in `s`.
+    refs: RefSpans,
 }
 
 impl<'a> Parser<'a> {
@@ -368,6 +712,7 @@ impl<'a> Parser<'a> {
         Self {
             s: input.as_bytes(),
             pos: 0,
+            refs: Vec::new(),
         }
     }
 
@@ -599,6 +944,9 @@ impl<'a> Parser<'a> {
         if matches!(self.peek(), Some(b'0'..=b'9')) && self.looks_like_row_range() {
             return self.parse_row_range(sheet);
         }
+        if let Some(end) = self.col_range_end() {
+            return self.parse_col_range(sheet, end);
+        }
 
         if matches!(self.peek(), Some(b'R' | b'r')) {
             let start = self.pos;
@@ -1024,7 +1372,7 @@ impl<'a> Parser<'a> {
             spill = true;
         }
 
-        Ok(Some(CellReference {
+        let reference = CellReference {
             sheet,
             row: if row_abs {
                 RowRef::Absolute(row_num)
@@ -1037,7 +1385,9 @@ impl<'a> Parser<'a> {
                 ColRef::Relative(col_num)
             },
             spill,
-        }))
+        };
+        self.refs.push((start..self.pos, reference.clone()));
+        Ok(Some(reference))
     }
 
     fn consume_if(&mut self, b: u8) -> bool {
@@ -1093,6 +1443,54 @@ impl<'a> Parser<'a> {
         i > j
     }
 
+    /// End of a whole-column range such as `B:B` or `$A:$C` starting at the cursor.
+    fn col_range_end(&self) -> Option<usize> {
+        let col = |mut i: usize| {
+            if self.s.get(i) == Some(&b'$') {
+                i += 1;
+            }
+            let start = i;
+            while i < self.s.len() && self.s[i].is_ascii_alphabetic() {
+                i += 1;
+            }
+            let letters = std::str::from_utf8(&self.s[start..i]).ok()?;
+            (i - start <= 3 && crate::addressing::col_letters_to_index(letters).is_some())
+                .then_some(i)
+        };
+        let skip_ws = |mut i: usize| {
+            while i < self.s.len() && matches!(self.s[i], b' ' | b'\t') {
+                i += 1;
+            }
+            i
+        };
+        let i = skip_ws(col(self.pos)?);
+        if self.s.get(i) != Some(&b':') {
+            return None;
+        }
+        let end = col(skip_ws(i + 1))?;
+        match self.s.get(end) {
+            Some(&b) if is_ident_continue(b) || matches!(b, b'(' | b'!' | b'[') => None,
+            _ => Some(end),
+        }
+    }
+
+    fn parse_col_range(
+        &mut self,
+        sheet: Option<String>,
+        end: usize,
+    ) -> Result<FormulaExpr, FormulaParseError> {
+        let text: String = std::str::from_utf8(&self.s[self.pos..end])
+            .map_err(|_| self.err("invalid utf-8 in column range"))?
+            .split_whitespace()
+            .collect();
+        self.pos = end;
+        Ok(FormulaExpr::NamedRef(format!(
+            "{}{}",
+            SheetPrefix(sheet.as_deref()),
+            text
+        )))
+    }
+
     fn parse_row_range(&mut self, sheet: Option<String>) -> Result<FormulaExpr, FormulaParseError> {
         let start_row = self.parse_u32()?;
         self.skip_ws();
@@ -1103,10 +1501,7 @@ impl<'a> Parser<'a> {
         let end_row = self.parse_u32()?;
         Ok(FormulaExpr::NamedRef(format!(
             "{}{}:{}",
-            match sheet {
-                Some(s) => format!("{}!", s),
-                None => "".to_string(),
-            },
+            SheetPrefix(sheet.as_deref()),
             start_row,
             end_row
         )))
@@ -1121,6 +1516,17 @@ fn is_ident_continue(b: u8) -> bool {
     is_ident_start(b) || matches!(b, b'0'..=b'9' | b'.')
 }
 
+fn col_u32_to_letters(mut col: u32) -> String {
+    let mut letters = Vec::new();
+    while col > 0 {
+        let rem = (col - 1) % 26;
+        letters.push(b'A' + rem as u8);
+        col = (col - 1) / 26;
+    }
+    letters.reverse();
+    String::from_utf8(letters).unwrap_or_default()
+}
+
 fn col_letters_to_u32(s: &str) -> Option<u32> {
     let mut col: u32 = 0;
     for b in s.bytes() {
@@ -0,0 +1,688 @@
+//! Cross-sheet formula dependency graph and the impact of edited cells.
+//!
+//! Every formula cell is parsed and its references resolved to single cells or rectangles on the
+//! sheets they name. Defined names are expanded through [`NamedRange::refers_to`], sheet-scoped
+//! names first. Whole rows and whole columns become rectangles spanning the sheet. References that
+//! cannot be placed statically (other workbooks, structured table references, `INDIRECT` targets)
+//! are left out of the graph.
+//!
+//! [`NamedRange::refers_to`]: crate::workbook::NamedRange::refers_to
+
+use crate::addressing::col_letters_to_index;
+use crate::diff::{DiffOp, SheetId};
+use crate::formula::{parse_formula, CellReference, ColRef, FormulaExpr, RowRef};
+use crate::string_pool::StringPool;
+use crate::workbook::{CellAddress, Workbook};
+use rustc_hash::FxHashMap;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+
+/// Longest chain of names referring to names that is followed.
+const MAX_NAME_DEPTH: usize = 16;
+
+/// `(sheet index, zero-based row, zero-based column)`.
+type Node = (usize, u32, u32);
+
+/// A formula cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub struct FormulaCell {
+    pub sheet: SheetId,
+    pub addr: CellAddress,
+}
+
+/// Formula cells that read an edited cell, directly or through other formulas.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CellImpact {
+    pub sheet: SheetId,
+    pub addr: CellAddress,
+    /// Downstream formula cells in sheet order, row-major within a sheet.
+    pub dependents: Vec<FormulaCell>,
+}
+
+impl CellImpact {
+    /// Number of dependents on each sheet, in sheet order.
+    pub fn counts_by_sheet(&self) -> Vec<(SheetId, usize)> {
+        let mut counts: Vec<(SheetId, usize)> = Vec::new();
+        for cell in &self.dependents {
+            match counts.last_mut() {
+                Some((sheet, count)) if *sheet == cell.sheet => *count += 1,
+                _ => counts.push((cell.sheet, 1)),
+            }
+        }
+        counts
+    }
+}
+
+/// Inclusive, zero-based rectangle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Rect {
+    top: u32,
+    left: u32,
+    bottom: u32,
+    right: u32,
+}
+
+impl Rect {
+    fn cell(row: u32, col: u32) -> Rect {
+        Rect {
+            top: row,
+            left: col,
+            bottom: row,
+            right: col,
+        }
+    }
+
+    fn contains(&self, row: u32, col: u32) -> bool {
+        (self.top..=self.bottom).contains(&row) && (self.left..=self.right).contains(&col)
+    }
+}
+
+/// The ranges read on one sheet, sorted by top row, with the largest bottom row of every subtree
+/// of an implicit binary tree over them. A lookup only descends into subtrees whose rows can
+/// cover the queried row, then checks each candidate's columns.
+#[derive(Debug, Clone, Default)]
+struct RangeIndex {
+    ranges: Vec<(Rect, Node)>,
+    /// Node 1 is the root and node `n` has children `2n` and `2n + 1`; leaves start at
+    /// `max_bottom.len() / 2`.
+    max_bottom: Vec<u32>,
+}
+
+impl RangeIndex {
+    fn new(mut ranges: Vec<(Rect, Node)>) -> RangeIndex {
+        ranges.sort_unstable_by_key(|(rect, _)| rect.top);
+        let leaves = ranges.len().next_power_of_two();
+        let mut max_bottom = vec![0; 2 * leaves];
+        for (slot, (rect, _)) in max_bottom[leaves..].iter_mut().zip(&ranges) {
+            *slot = rect.bottom;
+        }
+        for node in (1..leaves).rev() {
+            max_bottom[node] = max_bottom[2 * node].max(max_bottom[2 * node + 1]);
+        }
+        RangeIndex { ranges, max_bottom }
+    }
+
+    /// Readers of every range containing `(row, col)`.
+    fn readers(&self, row: u32, col: u32, out: &mut Vec<Node>) {
+        // Only ranges starting at or above `row` can contain it.
+        let end = self.ranges.partition_point(|(rect, _)| rect.top <= row);
+        let leaves = self.max_bottom.len() / 2;
+        let mut stack = vec![(1, 0, leaves)];
+        while let Some((node, lo, hi)) = stack.pop() {
+            if lo >= end || self.max_bottom[node] < row {
+                continue;
+            }
+            if hi - lo == 1 {
+                let (rect, reader) = self.ranges[lo];
+                if rect.contains(row, col) {
+                    out.push(reader);
+                }
+                continue;
+            }
+            let mid = (lo + hi) / 2;
+            stack.push((2 * node + 1, mid, hi));
+            stack.push((2 * node, lo, mid));
+        }
+    }
+}
+
+/// Precedent/dependent graph over the formula cells of one workbook.
+#[derive(Debug, Clone, Default)]
+pub struct DependencyGraph {
+    sheets: Vec<SheetId>,
+    /// Formula cells reading a single cell, keyed by that cell.
+    cell_readers: FxHashMap<Node, Vec<Node>>,
+    /// Formula cells reading a range, by the range's sheet.
+    range_readers: Vec<RangeIndex>,
+}
+
+impl DependencyGraph {
+    /// Parse every formula in `workbook` and record what it reads. Formulas that do not parse
+    /// are skipped.
+    pub fn build(workbook: &Workbook, pool: &StringPool) -> DependencyGraph {
+        let resolver = Resolver::new(workbook, pool);
+        let mut cell_readers: FxHashMap<Node, Vec<Node>> = FxHashMap::default();
+        let mut range_readers = vec![Vec::new(); workbook.sheets.len()];
+
+        let mut targets = Vec::new();
+        for (idx, sheet) in workbook.sheets.iter().enumerate() {
+            for ((row, col), cell) in sheet.grid.iter_cells() {
+                let Some(formula) = cell.formula else {
+                    continue;
+                };
+                let Ok(expr) = parse_formula(pool.resolve(formula)) else {
+                    continue;
+                };
+                let reader = (idx, row, col);
+                targets.clear();
+                resolver.collect(&expr, reader, 0, &mut targets);
+                for &(sheet, rect) in &targets {
+                    if rect.top == rect.bottom && rect.left == rect.right {
+                        cell_readers
+                            .entry((sheet, rect.top, rect.left))
+                            .or_default()
+                            .push(reader);
+                    } else {
+                        range_readers[sheet].push((rect, reader));
+                    }
+                }
+            }
+        }
+
+        DependencyGraph {
+            sheets: workbook.sheets.iter().map(|sheet| sheet.name).collect(),
+            cell_readers,
+            range_readers: range_readers.into_iter().map(RangeIndex::new).collect(),
+        }
+    }
+
+    /// Formula cells reading `node` directly.
+    fn readers(&self, node: Node, out: &mut Vec<Node>) {
+        let (sheet, row, col) = node;
+        out.extend(self.cell_readers.get(&node).into_iter().flatten().copied());
+        self.range_readers[sheet].readers(row, col, out);
+    }
+
+    fn node(&self, sheet: SheetId, addr: CellAddress) -> Option<Node> {
+        let idx = self.sheets.iter().position(|s| *s == sheet)?;
+        Some((idx, addr.row, addr.col))
+    }
+
+    fn formula_cells(&self, nodes: Vec<Node>) -> Vec<FormulaCell> {
+        nodes
+            .into_iter()
+            .map(|(sheet, row, col)| FormulaCell {
+                sheet: self.sheets[sheet],
+                addr: CellAddress::from_indices(row, col),
+            })
+            .collect()
+    }
+
+    /// Every formula cell that reads `addr` on `sheet`, directly or through other formulas.
+    /// The cell itself is not listed, even when it sits on a circular reference.
+    pub fn dependents(&self, sheet: SheetId, addr: CellAddress) -> Vec<FormulaCell> {
+        let Some(start) = self.node(sheet, addr) else {
+            return Vec::new();
+        };
+        let mut reach = Reach::discover(self, [start]);
+        self.formula_cells(reach.dependents(start))
+    }
+
+    /// Dependents of each `CellEdited` in `ops`. Build the graph from the new side of the diff;
+    /// edited cells no formula reads are left out.
+    ///
+    /// The cells downstream of any edit are looked up once and shared by every edit.
+    pub fn impact(&self, ops: &[DiffOp]) -> Vec<CellImpact> {
+        let edits: Vec<(SheetId, CellAddress, Node)> = ops
+            .iter()
+            .filter_map(|op| match op {
+                DiffOp::CellEdited { sheet, addr, .. } => {
+                    Some((*sheet, *addr, self.node(*sheet, *addr)?))
+                }
+                _ => None,
+            })
+            .collect();
+        let mut reach = Reach::discover(self, edits.iter().map(|&(_, _, node)| node));
+
+        edits
+            .into_iter()
+            .filter_map(|(sheet, addr, node)| {
+                let dependents = reach.dependents(node);
+                (!dependents.is_empty()).then(|| CellImpact {
+                    sheet,
+                    addr,
+                    dependents: self.formula_cells(dependents),
+                })
+            })
+            .collect()
+    }
+}
+
+/// The part of a [`DependencyGraph`] reachable from a set of start cells, with dense ids so
+/// that walking it from each start is cheap.
+struct Reach {
+    nodes: Vec<Node>,
+    ids: FxHashMap<Node, usize>,
+    /// Direct readers of each node, by id.
+    readers: Vec<Vec<usize>>,
+    /// Per-node stamp of the last walk that visited it.
+    visited: Vec<usize>,
+    walk: usize,
+}
+
+impl Reach {
+    /// One breadth-first pass from every start at once, resolving each cell's readers once.
+    fn discover(graph: &DependencyGraph, starts: impl IntoIterator<Item = Node>) -> Reach {
+        let mut reach = Reach {
+            nodes: Vec::new(),
+            ids: FxHashMap::default(),
+            readers: Vec::new(),
+            visited: Vec::new(),
+            walk: 0,
+        };
+        for start in starts {
+            reach.id(start);
+        }
+
+        let mut direct = Vec::new();
+        let mut next = 0;
+        while next < reach.nodes.len() {
+            direct.clear();
+            graph.readers(reach.nodes[next], &mut direct);
+            let ids: Vec<usize> = direct.iter().map(|&reader| reach.id(reader)).collect();
+            reach.readers[next] = ids;
+            next += 1;
+        }
+        reach
+    }
+
+    fn id(&mut self, node: Node) -> usize {
+        if let Some(&id) = self.ids.get(&node) {
+            return id;
+        }
+        let id = self.nodes.len();
+        self.nodes.push(node);
+        self.ids.insert(node, id);
+        self.readers.push(Vec::new());
+        self.visited.push(0);
+        id
+    }
+
+    /// Cells downstream of `start`, sorted, without `start` itself.
+    fn dependents(&mut self, start: Node) -> Vec<Node> {
+        let Some(&start) = self.ids.get(&start) else {
+            return Vec::new();
+        };
+        self.walk += 1;
+        self.visited[start] = self.walk;
+        let mut queue = VecDeque::from([start]);
+        let mut found = Vec::new();
+        while let Some(id) = queue.pop_front() {
+            for &reader in &self.readers[id] {
+                if self.visited[reader] != self.walk {
+                    self.visited[reader] = self.walk;
+                    found.push(self.nodes[reader]);
+                    queue.push_back(reader);
+                }
+            }
+        }
+        found.sort_unstable();
+        found
+    }
+}
+
+/// Resolves parsed references against a workbook's sheets and defined names.
+struct Resolver {
+    /// Sheet index by lowercased name.
+    sheets: HashMap<String, usize>,
+    /// Parsed `refers_to` by `(scope sheet, lowercased name)`; `None` scope is workbook-wide.
+    names: HashMap<(Option<usize>, String), FormulaExpr>,
+}
+
+impl Resolver {
+    fn new(workbook: &Workbook, pool: &StringPool) -> Resolver {
+        let sheets: HashMap<String, usize> = workbook
+            .sheets
+            .iter()
+            .enumerate()
+            .map(|(idx, sheet)| (pool.resolve(sheet.name).to_lowercase(), idx))
+            .collect();
+
+        let mut names = HashMap::new();
+        for range in &workbook.named_ranges {
+            let scope = match range.scope {
+                Some(scope) => match sheets.get(&pool.resolve(scope).to_lowercase()) {
+                    Some(&idx) => Some(idx),
+                    None => continue,
+                },
+                None => None,
+            };
+            // Sheet-scoped names are stored qualified, e.g. `'Q3 Inputs'!Rate`.
+            let name = pool.resolve(range.name);
+            let bare = name.rsplit_once('!').map_or(name, |(_, bare)| bare);
+            if let Ok(expr) = parse_formula(pool.resolve(range.refers_to)) {
+                names.insert((scope, bare.to_lowercase()), expr);
+            }
+        }
+
+        Resolver { sheets, names }
+    }
+
+    fn sheet(&self, name: Option<&str>, host: Node) -> Option<usize> {
+        match name {
+            Some(name) => self.sheets.get(&name.to_lowercase()).copied(),
+            None => Some(host.0),
+        }
+    }
+
+    fn cell(
+        &self,
+        r: &CellReference,
+        sheet: Option<&str>,
+        host: Node,
+    ) -> Option<(usize, u32, u32)> {
+        Some((
+            self.sheet(sheet, host)?,
+            resolve_row(r.row, host.1)?,
+            resolve_col(r.col, host.2)?,
+        ))
+    }
+
+    /// Append the `(sheet, rect)` pairs `expr` reads, as seen from the `host` cell.
+    fn collect(&self, expr: &FormulaExpr, host: Node, depth: usize, out: &mut Vec<(usize, Rect)>) {
+        match expr {
+            FormulaExpr::CellRef(r) => {
+                if let Some((sheet, row, col)) = self.cell(r, r.sheet.as_deref(), host) {
+                    out.push((sheet, Rect::cell(row, col)));
+                }
+            }
+            FormulaExpr::RangeRef(r) => {
+                let sheet = r.sheet.as_deref().or(r.start.sheet.as_deref());
+                if let (Some((sheet, r1, c1)), Some((_, r2, c2))) = (
+                    self.cell(&r.start, sheet, host),
+                    self.cell(&r.end, sheet, host),
+                ) {
+                    let rect = Rect {
+                        top: r1.min(r2),
+                        left: c1.min(c2),
+                        bottom: r1.max(r2),
+                        right: c1.max(c2),
+                    };
+                    out.push((sheet, rect));
+                }
+            }
+            FormulaExpr::NamedRef(name) => self.collect_name(name, host, depth, out),
+            FormulaExpr::FunctionCall { args, .. } => {
+                for arg in args {
+                    self.collect(arg, host, depth, out);
+                }
+            }
+            FormulaExpr::UnaryOp { operand, .. } => self.collect(operand, host, depth, out),
+            FormulaExpr::BinaryOp { left, right, .. } => {
+                self.collect(left, host, depth, out);
+                self.collect(right, host, depth, out);
+            }
+            FormulaExpr::Array(rows) => {
+                for item in rows.iter().flatten() {
+                    self.collect(item, host, depth, out);
+                }
+            }
+            FormulaExpr::Number(_)
+            | FormulaExpr::Text(_)
+            | FormulaExpr::Boolean(_)
+            | FormulaExpr::Error(_) => {}
+        }
+    }
+
+    /// A defined name, or a whole-row or whole-column range such as `Data!2:5` or `Data!$B:$C`,
+    /// which the parser also reports as names.
+    fn collect_name(&self, name: &str, host: Node, depth: usize, out: &mut Vec<(usize, Rect)>) {
+        let (sheet, bare) = match name.rsplit_once('!') {
+            Some((sheet, bare)) => {
+                let sheet = match sheet.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')) {
+                    Some(quoted) => quoted.replace("''", "'"),
+                    None => sheet.to_string(),
+                };
+                (self.sheet(Some(&sheet), host), bare)
+            }
+            None => (Some(host.0), name),
+        };
+        let Some(sheet) = sheet else {
+            return;
+        };
+
+        if let Some((first, last)) = bare.split_once(':') {
+            let (first, last) = (first.trim_start_matches('$'), last.trim_start_matches('$'));
+            if let (Ok(first), Ok(last)) = (first.parse::<u32>(), last.parse::<u32>()) {
+                if let (Some(first), Some(last)) = (first.checked_sub(1), last.checked_sub(1)) {
+                    let rect = Rect {
+                        top: first.min(last),
+                        left: 0,
+                        bottom: first.max(last),
+                        right: u32::MAX,
+                    };
+                    out.push((sheet, rect));
+                }
+                return;
+            }
+            if let (Some(first), Some(last)) =
+                (col_letters_to_index(first), col_letters_to_index(last))
+            {
+                let rect = Rect {
+                    top: 0,
+                    left: first.min(last),
+                    bottom: u32::MAX,
+                    right: first.max(last),
+                };
+                out.push((sheet, rect));
+                return;
+            }
+        }
+
+        if depth >= MAX_NAME_DEPTH {
+            return;
+        }
+        let key = bare.to_lowercase();
+        let expr = self
+            .names
+            .get(&(Some(sheet), key.clone()))
+            .or_else(|| self.names.get(&(None, key)));
+        if let Some(expr) = expr {
+            // Unqualified references inside a name point at the sheet the name is used from.
+            self.collect(expr, (sheet, host.1, host.2), depth + 1, out);
+        }
+    }
+}
+
+/// A1 rows are one-based; `RC` in R1C1 form parses as `Relative(0)`, the host row itself.
+fn resolve_row(row: RowRef, host: u32) -> Option<u32> {
+    match row {
+        RowRef::Absolute(n) => n.checked_sub(1),
+        RowRef::Relative(0) => Some(host),
+        RowRef::Relative(n) => Some(n - 1),
+        RowRef::Offset(offset) => host.checked_add_signed(offset),
+    }
+}
+
+fn resolve_col(col: ColRef, host: u32) -> Option<u32> {
+    match col {
+        ColRef::Absolute(n) => n.checked_sub(1),
+        ColRef::Relative(0) => Some(host),
+        ColRef::Relative(n) => Some(n - 1),
+        ColRef::Offset(offset) => host.checked_add_signed(offset),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workbook::{CellValue, Grid, NamedRange, Sheet, SheetKind, SheetLayout};
+
+    fn sheet(pool: &mut StringPool, name: &str, cells: &[(&str, &str)]) -> Sheet {
+        let mut grid = Grid::new(20, 10);
+        for (addr, formula) in cells {
+            let addr: CellAddress = addr.parse().expect("valid address");
+            let formula = formula.strip_prefix('=').map(|f| pool.intern(f));
+            grid.insert_cell(addr.row, addr.col, Some(CellValue::Number(1.0)), formula);
+        }
+        Sheet {
+            name: pool.intern(name),
+            workbook_sheet_id: None,
+            kind: SheetKind::Worksheet,
+            grid,
+            layout: SheetLayout::default(),
+        }
+    }
+
+    fn a1(cells: &[FormulaCell], pool: &StringPool) -> Vec<String> {
+        cells
+            .iter()
+            .map(|c| format!("{}!{}", pool.resolve(c.sheet), c.addr))
+            .collect()
+    }
+
+    #[test]
+    fn follows_ranges_names_and_chains_across_sheets() {
+        let mut pool = StringPool::new();
+        let inputs = sheet(&mut pool, "Inputs", &[("B4", "5"), ("B5", "6")]);
+        let calc = sheet(
+            &mut pool,
+            "Calc",
+            &[
+                ("A1", "=Rate*2"),
+                ("A2", "=SUM(Inputs!B1:B10)"),
+                ("A3", "=A1+1"),
+                ("A4", "=Inputs!B5"),
+                ("A5", "=A6"),
+                ("A6", "=A5+Local"),
+            ],
+        );
+        let summary = sheet(
+            &mut pool,
+            "Summary Q3",
+            &[
+                ("C2", "=Calc!A3"),
+                ("C3", "=5:5"),
+                ("C4", "='Summary Q3'!D:D"),
+            ],
+        );
+        let workbook = Workbook {
+            sheets: vec![inputs, calc, summary],
+            named_ranges: vec![
+                NamedRange {
+                    name: pool.intern("Rate"),
+                    refers_to: pool.intern("Inputs!$B$4"),
+                    scope: None,
+                },
+                NamedRange {
+                    name: pool.intern("Calc!Local"),
+                    refers_to: pool.intern("Inputs!$B$4"),
+                    scope: Some(pool.intern("Calc")),
+                },
+            ],
+            ..Workbook::default()
+        };
+
+        let graph = DependencyGraph::build(&workbook, &pool);
+        let inputs = pool.intern("Inputs");
+        let b4 = CellAddress::from_indices(3, 1);
+        assert_eq!(
+            a1(&graph.dependents(inputs, b4), &pool),
+            vec![
+                "Calc!A1",
+                "Calc!A2",
+                "Calc!A3",
+                "Calc!A5",
+                "Calc!A6",
+                "Summary Q3!C2"
+            ]
+        );
+
+        let impact = CellImpact {
+            sheet: inputs,
+            addr: b4,
+            dependents: graph.dependents(inputs, b4),
+        };
+        assert_eq!(
+            impact.counts_by_sheet(),
+            vec![(pool.intern("Calc"), 5), (pool.intern("Summary Q3"), 1)]
+        );
+
+        let summary_a5 = CellAddress::from_indices(4, 0);
+        assert_eq!(
+            a1(
+                &graph.dependents(pool.intern("Summary Q3"), summary_a5),
+                &pool
+            ),
+            vec!["Summary Q3!C3"]
+        );
+        let summary_d2 = CellAddress::from_indices(1, 3);
+        assert_eq!(
+            a1(
+                &graph.dependents(pool.intern("Summary Q3"), summary_d2),
+                &pool
+            ),
+            vec!["Summary Q3!C4"]
+        );
+        assert!(graph
+            .dependents(inputs, CellAddress::from_indices(19, 1))
+            .is_empty());
+    }
+
+    #[test]
+    fn whole_columns_read_every_row_of_their_columns() {
+        let mut pool = StringPool::new();
+        let inputs = sheet(&mut pool, "Assumptions", &[("B4", "5"), ("D9", "6")]);
+        let calc = sheet(
+            &mut pool,
+            "Calc",
+            &[
+                ("A1", "=SUM(Assumptions!B:B)"),
+                ("A2", "=SUM(Assumptions!$C:$D)"),
+                ("C1", "=COUNT(A:A)"),
+            ],
+        );
+        let workbook = Workbook {
+            sheets: vec![inputs, calc],
+            ..Workbook::default()
+        };
+
+        let graph = DependencyGraph::build(&workbook, &pool);
+        let assumptions = pool.intern("Assumptions");
+        assert_eq!(
+            a1(
+                &graph.dependents(assumptions, CellAddress::from_indices(3, 1)),
+                &pool
+            ),
+            vec!["Calc!A1", "Calc!C1"]
+        );
+        assert_eq!(
+            a1(
+                &graph.dependents(assumptions, CellAddress::from_indices(8, 3)),
+                &pool
+            ),
+            vec!["Calc!C1", "Calc!A2"]
+        );
+        assert!(graph
+            .dependents(assumptions, CellAddress::from_indices(3, 0))
+            .is_empty());
+    }
+
+    #[test]
+    fn range_index_matches_a_linear_scan() {
+        let rect = |top, left, bottom, right| Rect {
+            top,
+            left,
+            bottom,
+            right,
+        };
+        let ranges: Vec<(Rect, Node)> = [
+            rect(0, 0, 9, 0),
+            rect(2, 1, 2, 5),
+            rect(4, 0, 4, u32::MAX),
+            rect(1, 2, 6, 3),
+            rect(7, 0, 7, 1),
+            rect(0, 3, 0, 3),
+            rect(5, 1, 8, 1),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(idx, rect)| (rect, (0, idx as u32, 0)))
+        .collect();
+        let index = RangeIndex::new(ranges.clone());
+
+        for row in 0..11 {
+            for col in 0..7 {
+                let mut found = Vec::new();
+                index.readers(row, col, &mut found);
+                found.sort_unstable();
+                let expected: Vec<Node> = ranges
+                    .iter()
+                    .filter(|(rect, _)| rect.contains(row, col))
+                    .map(|&(_, reader)| reader)
+                    .collect();
+                assert_eq!(found, expected, "row {row}, col {col}");
+            }
+        }
+    }
+}
@@ -1,10 +1,20 @@
+use std::hash::{Hash, Hasher};
+
 use rustc_hash::FxHashMap;
 
 use crate::config::DiffConfig;
-use crate::diff::FormulaDiffResult;
-use crate::formula::{formulas_equivalent_modulo_shift, parse_formula, FormulaExpr};
+use crate::diff::{FormulaDiffResult, FormulaEdit, FormulaEditKind};
+use crate::formula::{
+    binary_symbol, formulas_equivalent_modulo_shift, parse_formula, FormulaExpr, UnaryOperator,
+};
+use crate::m_ast_diff::{simple_tree, tree_edit_script, SimpleTree, TreeEdit};
 use crate::string_pool::{StringId, StringPool};
 
+/// Formulas larger than this (in AST nodes) are classified but not broken down into edits.
+const MAX_EDIT_TREE_NODES: usize = 240;
+/// Beyond this many edits the breakdown is replaced by a single whole-formula replacement.
+const MAX_FORMULA_EDITS: usize = 16;
+
 #[derive(Debug, Default)]
 pub(crate) struct FormulaParseCache {
     parsed: FxHashMap<StringId, Option<FormulaExpr>>,
@@ -84,3 +94,249 @@ pub(crate) fn diff_cell_formulas_ids(
 
     FormulaDiffResult::SemanticChange
 }
+
+/// Structural edits between two formulas, for edits classified as `SemanticChange`. Empty when
+/// either side does not parse or is too large to diff.
+pub(crate) fn formula_edits_ids(
+    pool: &StringPool,
+    cache: &mut FormulaParseCache,
+    old: StringId,
+    new: StringId,
+) -> Vec<FormulaEdit> {
+    let Some(old_ast) = cache.parsed(pool, old).cloned() else {
+        return Vec::new();
+    };
+    let Some(new_ast) = cache.parsed(pool, new) else {
+        return Vec::new();
+    };
+    formula_edits(&old_ast, new_ast)
+}
+
+fn formula_edits(old: &FormulaExpr, new: &FormulaExpr) -> Vec<FormulaEdit> {
+    let old_tree = ExprTree::build(old);
+    let new_tree = ExprTree::build(new);
+    if old_tree.nodes.len().max(new_tree.nodes.len()) > MAX_EDIT_TREE_NODES {
+        return Vec::new();
+    }
+
+    let script = tree_edit_script(&old_tree.simple(), &new_tree.simple());
+    let mut edits = Vec::new();
+    // Roots of replaced subtrees; edits below them are already covered by the replacement.
+    let mut replaced_old: Vec<usize> = Vec::new();
+    let mut replaced_new: Vec<usize> = Vec::new();
+    for step in script {
+        match step {
+            TreeEdit::Update { old: oi, new: ni } => {
+                if old_tree.within(oi, &replaced_old) || new_tree.within(ni, &replaced_new) {
+                    continue;
+                }
+                let (kind, from, to) = match update_kind(old_tree.nodes[oi], new_tree.nodes[ni]) {
+                    Some(kind) => {
+                        let (from, to) = update_text(old_tree.nodes[oi], new_tree.nodes[ni]);
+                        (kind, from, to)
+                    }
+                    None => {
+                        replaced_old.push(oi);
+                        replaced_new.push(ni);
+                        (
+                            FormulaEditKind::Replaced,
+                            old_tree.nodes[oi].to_string(),
+                            new_tree.nodes[ni].to_string(),
+                        )
+                    }
+                };
+                let (function, argument) = new_tree.context(ni);
+                edits.push(FormulaEdit {
+                    kind,
+                    function,
+                    argument,
+                    from: Some(from),
+                    to: Some(to),
+                });
+            }
+            TreeEdit::Insert { new: ni } => {
+                if new_tree.within(ni, &replaced_new) {
+                    continue;
+                }
+                let (function, argument) = new_tree.context(ni);
+                edits.push(FormulaEdit {
+                    kind: FormulaEditKind::Inserted,
+                    function,
+                    argument,
+                    from: None,
+                    to: Some(new_tree.nodes[ni].to_string()),
+                });
+            }
+            TreeEdit::Delete { old: oi } => {
+                if old_tree.within(oi, &replaced_old) {
+                    continue;
+                }
+                let (function, argument) = old_tree.context(oi);
+                edits.push(FormulaEdit {
+                    kind: FormulaEditKind::Removed,
+                    function,
+                    argument,
+                    from: Some(old_tree.nodes[oi].to_string()),
+                    to: None,
+                });
+            }
+        }
+    }
+
+    if edits.len() > MAX_FORMULA_EDITS {
+        return vec![FormulaEdit {
+            kind: FormulaEditKind::Replaced,
+            function: None,
+            argument: None,
+            from: Some(old.to_string()),
+            to: Some(new.to_string()),
+        }];
+    }
+    edits
+}
+
+/// A formula AST flattened in preorder, with each node's parent and position among its
+/// siblings.
+struct ExprTree<'a> {
+    nodes: Vec<&'a FormulaExpr>,
+    parents: Vec<Option<usize>>,
+    positions: Vec<u32>,
+    children: Vec<Vec<usize>>,
+}
+
+impl<'a> ExprTree<'a> {
+    fn build(root: &'a FormulaExpr) -> Self {
+        let mut tree = ExprTree {
+            nodes: Vec::new(),
+            parents: Vec::new(),
+            positions: Vec::new(),
+            children: Vec::new(),
+        };
+        tree.push(root, None, 0);
+        tree
+    }
+
+    fn push(&mut self, expr: &'a FormulaExpr, parent: Option<usize>, position: u32) -> usize {
+        let idx = self.nodes.len();
+        self.nodes.push(expr);
+        self.parents.push(parent);
+        self.positions.push(position);
+        self.children.push(Vec::new());
+        for (i, child) in expr_children(expr).into_iter().enumerate() {
+            let child_idx = self.push(child, Some(idx), i as u32);
+            self.children[idx].push(child_idx);
+        }
+        idx
+    }
+
+    fn simple(&self) -> SimpleTree {
+        simple_tree(
+            self.nodes.iter().map(|expr| node_label(expr)).collect(),
+            self.children.clone(),
+        )
+    }
+
+    /// Whether `idx` is one of `roots` or below one of them.
+    fn within(&self, idx: usize, roots: &[usize]) -> bool {
+        let mut cur = Some(idx);
+        while let Some(node) = cur {
+            if roots.contains(&node) {
+                return true;
+            }
+            cur = self.parents[node];
+        }
+        false
+    }
+
+    /// Innermost enclosing function call and the 1-based argument that leads to `idx`.
+    fn context(&self, idx: usize) -> (Option<String>, Option<u32>) {
+        let mut child = idx;
+        while let Some(parent) = self.parents[child] {
+            if let FormulaExpr::FunctionCall { name, .. } = self.nodes[parent] {
+                return (
+                    Some(name.to_ascii_uppercase()),
+                    Some(self.positions[child] + 1),
+                );
+            }
+            child = parent;
+        }
+        (None, None)
+    }
+}
+
+fn expr_children(expr: &FormulaExpr) -> Vec<&FormulaExpr> {
+    match expr {
+        FormulaExpr::FunctionCall { args, .. } => args.iter().collect(),
+        FormulaExpr::UnaryOp { operand, .. } => vec![operand.as_ref()],
+        FormulaExpr::BinaryOp { left, right, .. } => vec![left.as_ref(), right.as_ref()],
+        FormulaExpr::Array(rows) => rows.iter().flatten().collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Node label for the tree diff: the node kind plus what distinguishes it from siblings of the
+/// same kind, ignoring its children.
+fn node_label(expr: &FormulaExpr) -> u64 {
+    use crate::hashing::XXH64_SEED;
+    let mut h = xxhash_rust::xxh64::Xxh64::new(XXH64_SEED);
+    match expr {
+        FormulaExpr::Number(n) => (0u8, n.to_bits()).hash(&mut h),
+        FormulaExpr::Text(s) => (1u8, s).hash(&mut h),
+        FormulaExpr::Boolean(b) => (2u8, b).hash(&mut h),
+        FormulaExpr::Error(e) => (3u8, e.as_str()).hash(&mut h),
+        FormulaExpr::CellRef(_) | FormulaExpr::RangeRef(_) => {
+            (4u8, expr.to_string().to_ascii_uppercase()).hash(&mut h)
+        }
+        FormulaExpr::NamedRef(name) => (5u8, name.to_ascii_uppercase()).hash(&mut h),
+        FormulaExpr::FunctionCall { name, .. } => (6u8, name.to_ascii_uppercase()).hash(&mut h),
+        FormulaExpr::UnaryOp { op, .. } => (7u8, op).hash(&mut h),
+        FormulaExpr::BinaryOp { op, .. } => (8u8, op).hash(&mut h),
+        FormulaExpr::Array(rows) => {
+            (9u8, rows.len(), rows.first().map_or(0, Vec::len)).hash(&mut h)
+        }
+    }
+    h.finish()
+}
+
+/// How a node changed in place, or `None` when it became a different kind of expression.
+fn update_kind(old: &FormulaExpr, new: &FormulaExpr) -> Option<FormulaEditKind> {
+    use FormulaExpr as E;
+    match (old, new) {
+        (E::FunctionCall { .. }, E::FunctionCall { .. }) => Some(FormulaEditKind::FunctionRenamed),
+        (E::CellRef(_) | E::RangeRef(_), E::CellRef(_) | E::RangeRef(_)) => {
+            Some(FormulaEditKind::ReferenceChanged)
+        }
+        (E::UnaryOp { .. }, E::UnaryOp { .. }) | (E::BinaryOp { .. }, E::BinaryOp { .. }) => {
+            Some(FormulaEditKind::OperatorChanged)
+        }
+        (
+            E::Number(_) | E::Text(_) | E::Boolean(_) | E::Error(_) | E::NamedRef(_),
+            E::Number(_) | E::Text(_) | E::Boolean(_) | E::Error(_) | E::NamedRef(_),
+        ) => Some(FormulaEditKind::ValueChanged),
+        _ => None,
+    }
+}
+
+/// Text for an in-place change: the function name or operator for call and operator nodes,
+/// the node itself otherwise.
+fn update_text(old: &FormulaExpr, new: &FormulaExpr) -> (String, String) {
+    let text = |expr: &FormulaExpr| match expr {
+        FormulaExpr::FunctionCall { name, .. } => name.to_ascii_uppercase(),
+        FormulaExpr::BinaryOp { .. } | FormulaExpr::UnaryOp { .. } => operator_symbol(expr),
+        other => other.to_string(),
+    };
+    (text(old), text(new))
+}
+
+fn operator_symbol(expr: &FormulaExpr) -> String {
+    let symbol = match expr {
+        FormulaExpr::UnaryOp { op, .. } => match op {
+            UnaryOperator::Plus => "+",
+            UnaryOperator::Minus => "-",
+            UnaryOperator::Percent => "%",
+        },
+        FormulaExpr::BinaryOp { op, .. } => binary_symbol(*op),
+        _ => "",
+    };
+    symbol.to_string()
+}
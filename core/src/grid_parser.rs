@@ -5,8 +5,11 @@
 
 use crate::addressing::address_to_index;
 use crate::error_codes;
+use crate::formula::{FormulaTemplate, ShiftMode};
 use crate::string_pool::{StringId, StringPool};
-use crate::workbook::{CellContent, CellValue, Grid, GridStorage, NamedRange};
+use crate::workbook::{
+    CellContent, CellRange, CellValue, Grid, GridStorage, NamedRange, SheetVisibility,
+};
 use quick_xml::events::{BytesStart, Event};
 use quick_xml::Reader;
 use std::collections::HashMap;
@@ -48,16 +51,21 @@ impl GridParseError {
     }
 }
 
+#[derive(Debug, Clone)]
 pub struct SheetDescriptor {
     pub name: String,
     pub rel_id: Option<String>,
     pub sheet_id: Option<u32>,
+    pub visibility: SheetVisibility,
 }
 
 #[derive(Debug)]
 pub struct ParsedSheetXml {
     pub grid: Grid,
     pub drawing_rids: Vec<String>,
+    /// Ranges filled by array formulas (legacy CSE arrays and dynamic-array spills), anchored
+    /// at their top-left cell, in document order.
+    pub array_ranges: Vec<CellRange>,
 }
 
 const STREAM_CELL_BUFFER_LIMIT: usize = 4096;
@@ -373,6 +381,7 @@ pub fn parse_workbook_xml(xml: &[u8]) -> Result<Vec<SheetDescriptor>, GridParseE
                 let mut name = None;
                 let mut rel_id = None;
                 let mut sheet_id = None;
+                let mut visibility = SheetVisibility::Visible;
                 for attr in e.attributes() {
                     let attr = attr.map_err(|e| xml_msg_err(&reader, xml, e.to_string()))?;
                     match attr.key.as_ref() {
@@ -396,6 +405,13 @@ pub fn parse_workbook_xml(xml: &[u8]) -> Result<Vec<SheetDescriptor>, GridParseE
                                     .into_owned(),
                             )
                         }
+                        b"state" => {
+                            visibility = match attr.value.as_ref() {
+                                b"hidden" => SheetVisibility::Hidden,
+                                b"veryHidden" => SheetVisibility::VeryHidden,
+                                _ => SheetVisibility::Visible,
+                            }
+                        }
                         _ => {}
                     }
                 }
@@ -404,6 +420,7 @@ pub fn parse_workbook_xml(xml: &[u8]) -> Result<Vec<SheetDescriptor>, GridParseE
                         name,
                         rel_id,
                         sheet_id,
+                        visibility,
                     });
                 }
             }
@@ -417,6 +434,18 @@ pub fn parse_workbook_xml(xml: &[u8]) -> Result<Vec<SheetDescriptor>, GridParseE
     Ok(sheets)
 }
 
+/// Quote a sheet name for use as a defined-name scope prefix (`'My Sheet'!Name`).
+pub(crate) fn quote_sheet_name(sheet: &str) -> String {
+    let needs_quotes = sheet
+        .chars()
+        .any(|c| matches!(c, ' ' | '\'' | '!' | ',' | ';' | '[' | ']' | '(' | ')'));
+    if !needs_quotes {
+        return sheet.to_string();
+    }
+    let escaped = sheet.replace('\'', "''");
+    format!("'{escaped}'")
+}
+
 pub fn parse_defined_names(
     workbook_xml: &[u8],
     sheets_in_order: &[SheetDescriptor],
@@ -426,17 +455,6 @@ pub fn parse_defined_names(
         name.rsplit(|&b| b == b':').next().unwrap_or(name)
     }
 
-    fn quote_sheet_name(sheet: &str) -> String {
-        let needs_quotes = sheet
-            .chars()
-            .any(|c| matches!(c, ' ' | '\'' | '!' | ',' | ';' | '[' | ']' | '(' | ')'));
-        if !needs_quotes {
-            return sheet.to_string();
-        }
-        let escaped = sheet.replace('\'', "''");
-        format!("'{escaped}'")
-    }
-
     let mut reader = Reader::from_reader(workbook_xml);
     reader.config_mut().trim_text(false);
     let mut buf = Vec::new();
@@ -789,14 +807,14 @@ pub fn parse_sheet_xml(
     xml: &[u8],
     shared_strings: &[StringId],
     pool: &mut StringPool,
-) -> Result<Grid, GridParseError> {
+) -> Result<ParsedSheetXml, GridParseError> {
     #[cfg(feature = "custom-xml")]
     {
-        Ok(parse_sheet_xml_internal_custom(xml, shared_strings, pool, false)?.grid)
+        parse_sheet_xml_internal_custom(xml, shared_strings, pool, false)
     }
     #[cfg(not(feature = "custom-xml"))]
     {
-        Ok(parse_sheet_xml_internal_quick_xml(xml, shared_strings, pool, false)?.grid)
+        parse_sheet_xml_internal_quick_xml(xml, shared_strings, pool, false)
     }
 }
 
@@ -820,6 +838,7 @@ fn parse_sheet_xml_internal_quick_xml(
     let mut max_row: Option<u32> = None;
     let mut max_col: Option<u32> = None;
     let mut drawing_rids = Vec::new();
+    let mut formulas = SheetFormulas::default();
     let mut stream_dense_recheck = 0usize;
 
     loop {
@@ -862,6 +881,7 @@ fn parse_sheet_xml_internal_quick_xml(
                     e,
                     shared_strings,
                     pool,
+                    &mut formulas,
                     &mut cell_buf,
                     &mut value_text_scratch,
                     &mut inline_string_scratch,
@@ -979,25 +999,34 @@ fn parse_sheet_xml_internal_quick_xml(
                 grid = rebuild_grid(grid, nrows, ncols, observed_bounds(max_row, max_col));
             }
         }
-        return Ok(ParsedSheetXml { grid, drawing_rids });
+        let array_ranges = formulas.expand(&mut grid, pool);
+        return Ok(ParsedSheetXml {
+            grid,
+            drawing_rids,
+            array_ranges,
+        });
     }
 
     if parsed_cells.is_empty() {
         return Ok(ParsedSheetXml {
             grid: Grid::new(0, 0),
             drawing_rids,
+            array_ranges: Vec::new(),
         });
     }
 
     let (nrows, ncols) = grid_bounds_from_hint(dimension_hint, max_row, max_col);
+    let mut grid = build_grid(
+        nrows,
+        ncols,
+        parsed_cells,
+        observed_bounds(max_row, max_col),
+    )?;
+    let array_ranges = formulas.expand(&mut grid, pool);
     Ok(ParsedSheetXml {
-        grid: build_grid(
-            nrows,
-            ncols,
-            parsed_cells,
-            observed_bounds(max_row, max_col),
-        )?,
+        grid,
         drawing_rids,
+        array_ranges,
     })
 }
 
@@ -1653,6 +1682,7 @@ fn parse_sheet_xml_internal_custom(
         tag_end: usize,
         shared_strings: &[StringId],
         pool: &mut StringPool,
+        formulas: &mut SheetFormulas,
         value_text_scratch: &mut Vec<u8>,
         inline_string_scratch: &mut String,
     ) -> Result<(ParsedCell, usize), GridParseError> {
@@ -1705,6 +1735,7 @@ fn parse_sheet_xml_internal_custom(
 
         let mut value: Option<CellValue> = None;
         let mut formula: Option<StringId> = None;
+        let mut group: Option<FormulaGroup> = None;
         let mut cursor = tag_end + 1;
 
         loop {
@@ -1810,6 +1841,14 @@ fn parse_sheet_xml_internal_custom(
                 continue;
             }
 
+            if !is_end_tag && local == b"f" && local == raw_name {
+                group = FormulaGroup::from_attrs(
+                    find_attr_value(xml, name_cursor, tag_end_offset, b"t"),
+                    find_attr_value(xml, name_cursor, tag_end_offset, b"si"),
+                    find_attr_value(xml, name_cursor, tag_end_offset, b"ref"),
+                );
+            }
+
             if !is_end_tag && !self_closing && local == b"f" && local == raw_name {
                 let content_start = tag_end_offset + 1;
                 let (raw_opt, next) =
@@ -1836,6 +1875,10 @@ fn parse_sheet_xml_internal_custom(
             cursor = tag_end_offset + 1;
         }
 
+        if let Some(group) = group {
+            formulas.record(row, col, group, formula);
+        }
+
         Ok((
             ParsedCell {
                 row,
@@ -1856,6 +1899,7 @@ fn parse_sheet_xml_internal_custom(
     let mut max_row: Option<u32> = None;
     let mut max_col: Option<u32> = None;
     let mut drawing_rids = Vec::new();
+    let mut formulas = SheetFormulas::default();
     let mut stream_dense_recheck = 0usize;
 
     let mut cursor = 0usize;
@@ -1991,6 +2035,7 @@ fn parse_sheet_xml_internal_custom(
                 tag_end_offset,
                 shared_strings,
                 pool,
+                &mut formulas,
                 &mut value_text_scratch,
                 &mut inline_string_scratch,
             )?;
@@ -2107,25 +2152,34 @@ fn parse_sheet_xml_internal_custom(
                 grid = rebuild_grid(grid, nrows, ncols, observed_bounds(max_row, max_col));
             }
         }
-        return Ok(ParsedSheetXml { grid, drawing_rids });
+        let array_ranges = formulas.expand(&mut grid, pool);
+        return Ok(ParsedSheetXml {
+            grid,
+            drawing_rids,
+            array_ranges,
+        });
     }
 
     if parsed_cells.is_empty() {
         return Ok(ParsedSheetXml {
             grid: Grid::new(0, 0),
             drawing_rids,
+            array_ranges: Vec::new(),
         });
     }
 
     let (nrows, ncols) = grid_bounds_from_hint(dimension_hint, max_row, max_col);
+    let mut grid = build_grid(
+        nrows,
+        ncols,
+        parsed_cells,
+        observed_bounds(max_row, max_col),
+    )?;
+    let array_ranges = formulas.expand(&mut grid, pool);
     Ok(ParsedSheetXml {
-        grid: build_grid(
-            nrows,
-            ncols,
-            parsed_cells,
-            observed_bounds(max_row, max_col),
-        )?,
+        grid,
         drawing_rids,
+        array_ranges,
     })
 }
 
@@ -2135,6 +2189,7 @@ fn parse_cell(
     start: BytesStart,
     shared_strings: &[StringId],
     pool: &mut StringPool,
+    formulas: &mut SheetFormulas,
     buf: &mut Vec<u8>,
     value_text_scratch: &mut Vec<u8>,
     inline_string_scratch: &mut String,
@@ -2178,6 +2233,7 @@ fn parse_cell(
 
     let mut value: Option<CellValue> = None;
     let mut formula: Option<StringId> = None;
+    let mut group: Option<FormulaGroup> = None;
 
     buf.clear();
     loop {
@@ -2186,7 +2242,11 @@ fn parse_cell(
                 let raw = read_element_text_bytes(reader, xml, b"v", buf, value_text_scratch)?;
                 value = convert_value_bytes(raw, cell_type, shared_strings, pool, reader, xml)?;
             }
+            Ok(Event::Empty(e)) if e.name().as_ref() == b"f" => {
+                group = formula_group_quick_xml(reader, xml, &e)?;
+            }
             Ok(Event::Start(e)) if e.name().as_ref() == b"f" => {
+                group = formula_group_quick_xml(reader, xml, &e)?;
                 let text = reader
                     .read_text(e.name())
                     .map_err(|e| xml_err(reader, xml, e))?;
@@ -2208,6 +2268,10 @@ fn parse_cell(
         buf.clear();
     }
 
+    if let Some(group) = group {
+        formulas.record(row, col, group, formula);
+    }
+
     Ok(ParsedCell {
         row,
         col,
@@ -2216,6 +2280,21 @@ fn parse_cell(
     })
 }
 
+fn formula_group_quick_xml(
+    reader: &Reader<&[u8]>,
+    xml: &[u8],
+    element: &BytesStart,
+) -> Result<Option<FormulaGroup>, GridParseError> {
+    let kind = get_attr_value(reader, xml, element, b"t")?;
+    let si = get_attr_value(reader, xml, element, b"si")?;
+    let range = get_attr_value(reader, xml, element, b"ref")?;
+    Ok(FormulaGroup::from_attrs(
+        kind.as_deref().map(str::as_bytes),
+        si.as_deref().map(str::as_bytes),
+        range.as_deref().map(str::as_bytes),
+    ))
+}
+
 fn read_inline_string(
     reader: &mut Reader<&[u8]>,
     xml: &[u8],
@@ -2785,7 +2864,23 @@ fn build_grid(
     Ok(grid)
 }
 
-fn local_tag_name(name: &[u8]) -> &[u8] {
+/// Build a grid from fully collected cells, using the same bounds and storage heuristics as the
+/// streaming worksheet parsers. Used by non-XML sheet readers.
+#[cfg_attr(not(feature = "excel-open-xml"), allow(dead_code))]
+pub(crate) fn build_grid_from_cells(
+    dimension_hint: Option<(u32, u32)>,
+    cells: Vec<ParsedCell>,
+) -> Result<Grid, GridParseError> {
+    if cells.is_empty() {
+        return Ok(Grid::new(0, 0));
+    }
+    let max_row = cells.iter().map(|cell| cell.row).max();
+    let max_col = cells.iter().map(|cell| cell.col).max();
+    let (nrows, ncols) = grid_bounds_from_hint(dimension_hint, max_row, max_col);
+    build_grid(nrows, ncols, cells, observed_bounds(max_row, max_col))
+}
+
+pub(crate) fn local_tag_name(name: &[u8]) -> &[u8] {
     name.rsplit(|&b| b == b':').next().unwrap_or(name)
 }
 
@@ -2828,11 +2923,15 @@ fn get_attr_value<'a>(
     Ok(None)
 }
 
-fn xml_err(reader: &Reader<&[u8]>, xml: &[u8], err: quick_xml::Error) -> GridParseError {
+pub(crate) fn xml_err(reader: &Reader<&[u8]>, xml: &[u8], err: quick_xml::Error) -> GridParseError {
     xml_error_with_position(err, xml, reader.buffer_position())
 }
 
-fn xml_msg_err(reader: &Reader<&[u8]>, xml: &[u8], message: impl Into<String>) -> GridParseError {
+pub(crate) fn xml_msg_err(
+    reader: &Reader<&[u8]>,
+    xml: &[u8],
+    message: impl Into<String>,
+) -> GridParseError {
     let (line, column) = compute_line_col(xml, reader.buffer_position());
     GridParseError::XmlErrorAt {
         line,
@@ -2866,11 +2965,85 @@ fn compute_line_col(data: &[u8], offset: usize) -> (usize, usize) {
     (line, column)
 }
 
-struct ParsedCell {
-    row: u32,
-    col: u32,
-    value: Option<CellValue>,
-    formula: Option<StringId>,
+pub(crate) struct ParsedCell {
+    pub(crate) row: u32,
+    pub(crate) col: u32,
+    pub(crate) value: Option<CellValue>,
+    pub(crate) formula: Option<StringId>,
+}
+
+/// How a cell's `<f>` element takes part in a formula group.
+enum FormulaGroup {
+    /// `t="shared"`: the master cell carries the text, the other cells only the `si` index.
+    Shared(u32),
+    /// `t="array"`: the anchor cell carries the text for the whole `ref` range.
+    Array(CellRange),
+}
+
+impl FormulaGroup {
+    fn from_attrs(kind: Option<&[u8]>, si: Option<&[u8]>, range: Option<&[u8]>) -> Option<Self> {
+        let text = |raw| std::str::from_utf8(raw).ok().map(str::trim);
+        match kind? {
+            b"shared" => text(si?)?.parse().ok().map(FormulaGroup::Shared),
+            b"array" => text(range?)?.parse().ok().map(FormulaGroup::Array),
+            _ => None,
+        }
+    }
+}
+
+/// Shared and array formula groups seen while parsing one worksheet.
+#[derive(Default)]
+struct SheetFormulas {
+    /// Master cell and formula text per shared index; the first master wins.
+    masters: HashMap<u32, (u32, u32, StringId)>,
+    /// Cells that refer to a shared formula without text of their own.
+    children: Vec<(u32, u32, u32)>,
+    array_ranges: Vec<CellRange>,
+}
+
+impl SheetFormulas {
+    fn record(&mut self, row: u32, col: u32, group: FormulaGroup, formula: Option<StringId>) {
+        match group {
+            FormulaGroup::Shared(si) => match formula {
+                Some(text) => {
+                    self.masters.entry(si).or_insert((row, col, text));
+                }
+                None => self.children.push((row, col, si)),
+            },
+            FormulaGroup::Array(range) => self.array_ranges.push(range),
+        }
+    }
+
+    /// Gives every shared-formula child the master formula shifted to its own position, so each
+    /// cell carries the formula Excel would show for it. Only the references are rewritten; the
+    /// rest of the master's text is kept as written, so a child reads the same as an unshared
+    /// copy of the formula. Children of a master whose text does not parse keep no formula.
+    /// Returns the array ranges.
+    fn expand(self, grid: &mut Grid, pool: &mut StringPool) -> Vec<CellRange> {
+        let mut parsed: HashMap<u32, Option<FormulaTemplate>> = HashMap::new();
+        for (row, col, si) in self.children {
+            let Some(&(master_row, master_col, text)) = self.masters.get(&si) else {
+                continue;
+            };
+            let master = parsed
+                .entry(si)
+                .or_insert_with(|| FormulaTemplate::parse(pool.resolve(text)));
+            let Some(master) = master else {
+                continue;
+            };
+            let shifted = master.shifted(
+                row as i32 - master_row as i32,
+                col as i32 - master_col as i32,
+                ShiftMode::RelativeOnly,
+            );
+            let formula = pool.intern(&shifted);
+            match grid.get_mut(row, col) {
+                Some(cell) => cell.formula = Some(formula),
+                None => grid.insert_cell(row, col, None, Some(formula)),
+            }
+        }
+        self.array_ranges
+    }
 }
 
 #[cfg(test)]
@@ -3175,6 +3348,43 @@ mod tests {
         assert_eq!(pool.resolve(text_id), "hello");
     }
 
+    #[test]
+    fn shared_formula_children_get_shifted_master_formula() {
+        let xml = br#"<worksheet>
+  <sheetData>
+    <row r="1">
+      <c r="A1"><v>1</v></c>
+      <c r="B1"><f t="shared" ref="B1:C3" si="0">A1*$A$1+Data!B$1</f><v>1</v></c>
+      <c r="C1"><f t="shared" si="0"/><v>2</v></c>
+      <c r="E1"><f t="array" ref="E1:E4" aca="false">SORT(A1:A4)</f><v>1</v></c>
+    </row>
+    <row r="3">
+      <c r="B3"><f t="shared" si="0"/></c>
+      <c r="C3"><f>A3</f><v>3</v></c>
+    </row>
+  </sheetData>
+</worksheet>"#;
+
+        let mut pool = StringPool::new();
+        let parsed =
+            parse_sheet_xml_with_drawing_rids(xml, &[], &mut pool).expect("sheet xml should parse");
+        let formula = |row, col| {
+            parsed
+                .grid
+                .get(row, col)
+                .and_then(|cell| cell.formula)
+                .map(|id| pool.resolve(id).to_string())
+        };
+
+        assert_eq!(formula(0, 1).as_deref(), Some("A1*$A$1+Data!B$1"));
+        assert_eq!(formula(0, 2).as_deref(), Some("B1*$A$1+Data!C$1"));
+        assert_eq!(formula(2, 1).as_deref(), Some("A3*$A$1+Data!B$1"));
+        assert_eq!(formula(2, 2).as_deref(), Some("A3"));
+        assert_eq!(formula(1, 4), None);
+        let ranges: Vec<String> = parsed.array_ranges.iter().map(|r| r.to_a1()).collect();
+        assert_eq!(ranges, vec!["E1:E4"]);
+    }
+
     #[cfg(feature = "custom-xml")]
     #[test]
     fn parse_sheet_xml_custom_matches_quick_xml_for_common_cell_types() {
@@ -3210,7 +3420,7 @@ mod tests {
         let xml = br#"<?xml version="1.0"?>
 <worksheet xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"
           xmlns:x="http://example.com">
-  <dimension ref="A1:H1"/>
+  <dimension ref="A1:H3"/>
   <x:drawing r:id="rId9"/>
   <sheetData>
     <row r="1">
@@ -3223,6 +3433,13 @@ mod tests {
       <c r="G1" t="inlineStr"><is><t><![CDATA[<raw>]]></t></is></c>
       <c r="H1"><v>A&amp;B</v></c>
     </row>
+    <row r="2">
+      <c r="A2"><f t="shared" ref="A2:A3" si="0">B2*2</f><v>0</v></c>
+      <c r="B2"><f t="array" ref="B2:B3">C2:C3</f><v>0</v></c>
+    </row>
+    <row r="3">
+      <c r="A3"><f t="shared" si="0" /><v>0</v></c>
+    </row>
   </sheetData>
   <drawing r:id="rId1"/>
 </worksheet>"#;
@@ -3240,6 +3457,8 @@ mod tests {
                 .expect("custom sheet parser should succeed");
 
         assert_eq!(custom.drawing_rids, quick.drawing_rids);
+        assert_eq!(custom.array_ranges, quick.array_ranges);
+        assert_eq!(quick.array_ranges.len(), 1);
         assert_eq!(custom.grid.nrows, quick.grid.nrows);
         assert_eq!(custom.grid.ncols, quick.grid.ncols);
 
@@ -7,6 +7,7 @@ use crate::config::DiffConfig;
 use crate::grid_metadata::classify_row_frequencies;
 use crate::hashing::{hash_cell_value, hash_row_content_128};
 use crate::memory_estimate::estimate_gridview_bytes;
+use crate::value_compare::CellComparator;
 use crate::workbook::{Cell, CellValue, ColSignature, Grid, GridStorage, RowSignature};
 use xxhash_rust::xxh3::Xxh3;
 
@@ -141,6 +142,38 @@ impl<'a> GridView<'a> {
         }
     }
 
+    /// Recomputes row and column signatures under the sheet's value rules so that cells the
+    /// comparator treats as equal also align as equal. No-op for exact comparison.
+    pub(crate) fn apply_value_rules(&mut self, compare: &CellComparator<'_>, config: &DiffConfig) {
+        if compare.is_exact() {
+            return;
+        }
+
+        let mut col_hashers: Vec<Xxh3> = (0..self.col_meta.len()).map(|_| Xxh3::new()).collect();
+        for (meta, row_view) in self.row_meta.iter_mut().zip(self.rows.iter()) {
+            let mut row_hasher = Xxh3::new();
+            for (col, cell) in &row_view.cells {
+                if compare.is_absent_equivalent(cell, *col) {
+                    continue;
+                }
+                compare.hash_cell(cell, *col, &mut row_hasher);
+                if let Some(col_hasher) = col_hashers.get_mut(*col as usize) {
+                    compare.hash_cell(cell, *col, col_hasher);
+                }
+            }
+            meta.signature = RowSignature {
+                hash: row_hasher.digest128(),
+            };
+        }
+        for meta in &mut self.col_meta {
+            meta.hash = ColSignature {
+                hash: col_hashers[meta.col_idx as usize].digest128(),
+            };
+        }
+
+        classify_row_frequencies(&mut self.row_meta, config);
+    }
+
     pub fn is_low_info_dominated(&self) -> bool {
         if self.row_meta.is_empty() {
             return false;
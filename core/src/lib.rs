@@ -79,22 +79,37 @@ mod addressing;
 pub(crate) mod alignment;
 mod alignment_types;
 mod capabilities;
+mod charts;
+#[cfg(feature = "excel-open-xml")]
+mod comments;
 pub(crate) mod column_alignment;
 mod config;
+#[cfg(feature = "excel-open-xml")]
+mod connections;
 mod container;
 mod database_alignment;
+mod database_keys;
 mod datamashup;
 mod datamashup_framing;
 mod datamashup_package;
 #[cfg(feature = "model-diff")]
 mod dax;
+#[cfg(feature = "model-diff")]
+mod dax_deps;
+#[cfg(feature = "model-diff")]
+mod dax_diff;
 mod diff;
 mod diffable;
+#[cfg(feature = "excel-open-xml")]
+mod drawings;
 mod engine;
 pub mod error_codes;
 #[cfg(feature = "excel-open-xml")]
 mod excel_open_xml;
+#[cfg(feature = "excel-open-xml")]
+mod excel_tables;
 mod formula;
+mod formula_deps;
 mod formula_diff;
 mod grid_metadata;
 mod grid_parser;
@@ -114,25 +129,43 @@ mod model;
 #[cfg(feature = "model-diff")]
 mod model_diff;
 mod object_diff;
+mod op_alignment;
 mod output;
 mod package;
 mod pbip;
+#[cfg(feature = "excel-open-xml")]
+mod pivots;
 #[cfg(feature = "perf-metrics")]
 #[doc(hidden)]
 pub mod perf;
 mod permission_bindings;
 mod policy;
 mod progress;
+mod query_deps;
 pub(crate) mod rect_block_move;
 pub(crate) mod region_mask;
+mod report_layout;
 pub(crate) mod row_alignment;
 mod session;
+#[cfg(feature = "excel-open-xml")]
+mod sheet_layout;
+#[cfg(feature = "excel-open-xml")]
+mod sheet_rules;
 mod sink;
 mod string_pool;
+#[cfg(feature = "excel-open-xml")]
+mod styles;
+mod table_mode;
 #[cfg(all(feature = "model-diff", feature = "excel-open-xml"))]
 mod tabular_schema;
+mod text_diff;
+mod value_compare;
 mod vba;
 mod workbook;
+#[cfg(feature = "excel-open-xml")]
+mod workbook_settings;
+#[cfg(feature = "excel-open-xml")]
+mod xlsb;
 
 #[cfg(all(feature = "perf-metrics", not(target_arch = "wasm32")))]
 #[global_allocator]
@@ -222,12 +255,14 @@ pub fn open_workbook(path: impl AsRef<std::path::Path>) -> Result<Workbook, Exce
 ///     workbook_sheet_id: None,
 ///     kind: SheetKind::Worksheet,
 ///     grid: Grid::new(1, 1),
+///     layout: Default::default(),
 /// };
 /// let new = Sheet {
 ///     name: sheet_id,
 ///     workbook_sheet_id: None,
 ///     kind: SheetKind::Worksheet,
 ///     grid: Grid::new(1, 1),
+///     layout: Default::default(),
 /// };
 /// let report =
 ///     excel_diff::advanced::diff_sheets_with_pool(&old, &new, &mut pool, &DiffConfig::default());
@@ -259,7 +294,10 @@ pub mod advanced {
 
 pub use addressing::{address_to_index, index_to_address, AddressParseError};
 pub use capabilities::{engine_features, EngineFeatures};
-pub use config::{DiffConfig, DiffConfigBuilder, LimitBehavior, SemanticNoisePolicy};
+pub use config::{
+    ComparisonConfig, DiffConfig, DiffConfigBuilder, LimitBehavior, SemanticNoisePolicy,
+    ValueRuleOverride, ValueRules,
+};
 pub use container::{
     ContainerError, ContainerLimits, OpcContainer, ZipContainer, ZipEntryFingerprint,
 };
@@ -279,14 +317,24 @@ pub use datamashup_package::{
     PackageParts, PackageXml, SectionDocument,
 };
 pub use diff::{
-    AstDiffMode, AstDiffSummary, AstMoveHint, ColumnTypeChange, DiffError, DiffOp, DiffReport,
-    DiffSummary, ExpressionChangeKind, ExtractedColumnTypeChanges, ExtractedRenamePairs,
-    ExtractedString, ExtractedStringList, FormulaDiffResult, QueryChangeKind, QueryMetadataField,
-    QuerySemanticDetail, RenamePair, SheetId, StepChange, StepDiff, StepParams, StepSnapshot,
-    StepType,
+    AstDiffMode, AstDiffSummary, AstMoveHint, ChartChange, ChartField, ColumnTypeChange,
+    ConnectionChange, ConnectionField, DiffError, DiffOp, DiffReport, DiffSummary,
+    ExpressionChangeKind, ExtractedColumnTypeChanges, ExtractedRenamePairs, ExtractedString,
+    ExtractedStringList, FieldChange, FormulaDiffResult, FormulaEdit, FormulaEditKind, PivotArea,
+    PivotLayoutChange, QueryChangeKind, QueryMetadataField, QuerySemanticDetail, RenamePair,
+    ReportChange, ReportField, SheetId, StepChange, StepDiff, StepParams, StepSnapshot, StepType,
+    TextHunk, TextLine, TextLineKind, VbaAttributeChange, VbaProcedureChange,
+    VbaProcedureChangeKind, VbaProjectProperty, WorkbookSetting, WorkbookSettingChange,
 };
 #[cfg(feature = "model-diff")]
-pub use diff::{ModelColumnProperty, RelationshipProperty};
+pub use dax::{parse_dax, DaxBinaryOp, DaxExpr, DaxParseError, DaxUnaryOp};
+#[cfg(feature = "model-diff")]
+pub use dax_deps::{MeasureGraph, MeasureImpact, MeasureVisual};
+#[cfg(feature = "model-diff")]
+pub use diff::{
+    DaxEdit, DaxEditKind, MeasureProperty, ModelColumnProperty, ModelObjectChange,
+    ModelObjectField, ModelObjectKind, RelationshipProperty,
+};
 pub use diffable::{DiffContext, Diffable};
 #[doc(hidden)]
 pub use engine::{
@@ -314,6 +362,7 @@ pub use formula::{
     formulas_equivalent_modulo_shift, parse_formula, BinaryOperator, CellReference, ColRef,
     ExcelError, FormulaExpr, FormulaParseError, RangeReference, RowRef, UnaryOperator,
 };
+pub use formula_deps::{CellImpact, DependencyGraph, FormulaCell};
 pub use grid_parser::{GridParseError, SheetDescriptor};
 pub use grid_view::{
     ColHash, ColMeta, FrequencyClass, GridView, HashStats, RowHash, RowMeta, RowView,
@@ -325,7 +374,11 @@ pub use m_ast::{
 pub use m_ast::{tokenize_for_testing, MAstAccessKind, MAstKind, MTokenDebug};
 pub use m_section::{parse_section_members, SectionMember, SectionParseError};
 #[cfg(feature = "model-diff")]
-pub use model::{Measure, Model, ModelColumn, ModelRelationship, ModelTable};
+pub use model::{
+    Measure, MeasureKpi, Model, ModelCalculationGroup, ModelCalculationItem, ModelColumn,
+    ModelCulture, ModelHierarchy, ModelLevel, ModelPartition, ModelPerspective, ModelRelationship,
+    ModelRole, ModelTable, ModelTablePermission,
+};
 #[cfg(feature = "model-diff")]
 pub use model_diff::{diff_models, ModelDiffResult};
 pub use permission_bindings::{
@@ -345,6 +398,9 @@ pub fn datamodel_schema_parse_counts(
     ))
 }
 pub use database_alignment::suggest_key_columns;
+pub use database_keys::{
+    DatabaseKeys, KeyColumn, KeyNormalization, KeyParseError, KeyPart, SheetKeyMap,
+};
 #[doc(hidden)]
 pub use output::json::diff_report_to_cell_diffs;
 #[cfg(all(feature = "excel-open-xml", feature = "std-fs"))]
@@ -361,13 +417,22 @@ pub use pbip::{
 };
 #[cfg(feature = "std-fs")]
 pub use pbip::{snapshot_project_from_fs as snapshot_pbip_project, PbipScanConfig, PbipScanError};
+#[cfg(all(feature = "model-diff", feature = "excel-open-xml"))]
+pub use pbip::diff_snapshot_models as diff_pbip_models;
 pub use policy::{should_use_large_mode, AUTO_STREAM_CELL_THRESHOLD};
 pub use progress::{NoProgress, ProgressCallback};
+pub use query_deps::{QueryGraph, QueryNode};
 pub use session::DiffSession;
 pub use sink::{CallbackSink, DiffSink, VecSink};
 pub use string_pool::{StringId, StringPool};
-pub use vba::{VbaModule, VbaModuleType};
+pub use vba::{VbaModule, VbaModuleType, VbaProcedureKind, VbaProject, VbaReference};
 pub use workbook::{
-    Cell, CellAddress, CellSnapshot, CellValue, ChartInfo, ChartObject, ColSignature, Grid,
-    NamedRange, RowSignature, Sheet, SheetKind, Workbook,
+    AxisLayout, CalcMode, CalcSettings, Cell, CellAddress, CellComment, CellRange, CellSnapshot,
+    CellStyle, CellValue, ChartAxis, ChartInfo, ChartObject, ChartSeries, ColSignature,
+    CommentKind, CommentReply, ConditionalFormatRule, DataConnection, DataValidationRule,
+    DrawingAnchor, DrawingMarker, DrawingObject, DrawingObjectKind, ExcelTable, ExcelTableColumn,
+    ExternalLink, FreezePane, Grid, NamedRange, PivotCalculatedField, PivotDataField, PivotFilter,
+    PivotPageField, PivotSource, PivotTable, RowSignature, Sheet, SheetCellStyles, SheetComments,
+    SheetDrawings, SheetKind, SheetLayout, SheetProtection, SheetRules, SheetVisibility, Workbook,
+    WorkbookProtection, WorkbookSettings,
 };
@@ -1,3 +1,4 @@
+use std::collections::BTreeSet;
 use std::hash::{Hash, Hasher};
 use std::iter::Peekable;
 use std::str::Chars;
@@ -473,6 +474,106 @@ pub fn canonicalize_m_ast(ast: &mut MModuleAst) {
     canonicalize_expr(&mut ast.root);
 }
 
+/// Names the expression reads from its enclosing scope: identifiers and called functions not
+/// bound by a `let`, record field, function parameter or `each`. Field names in `x[Field]`
+/// lookups are not identifiers and are left out.
+pub(crate) fn free_identifiers(ast: &MModuleAst) -> BTreeSet<String> {
+    let mut out = BTreeSet::new();
+    collect_free_identifiers(&ast.root, &mut Vec::new(), &mut out);
+    out
+}
+
+fn collect_free_identifiers<'a>(
+    expr: &'a MExpr,
+    bound: &mut Vec<&'a str>,
+    out: &mut BTreeSet<String>,
+) {
+    fn read(name: &str, bound: &[&str], out: &mut BTreeSet<String>) {
+        if !bound.contains(&name) {
+            out.insert(name.to_string());
+        }
+    }
+
+    match expr {
+        MExpr::Ident { name } => read(name, bound, out),
+        MExpr::FunctionCall { name, args } => {
+            read(name, bound, out);
+            for arg in args {
+                collect_free_identifiers(arg, bound, out);
+            }
+        }
+        MExpr::Let { bindings, body } => {
+            // Let bindings are in scope in each other's values, not only in later ones.
+            bound.extend(bindings.iter().map(|b| b.name.as_str()));
+            for binding in bindings {
+                collect_free_identifiers(&binding.value, bound, out);
+            }
+            collect_free_identifiers(body, bound, out);
+            bound.truncate(bound.len() - bindings.len());
+        }
+        MExpr::Record { fields } => {
+            bound.extend(fields.iter().map(|f| f.name.as_str()));
+            for field in fields {
+                collect_free_identifiers(&field.value, bound, out);
+            }
+            bound.truncate(bound.len() - fields.len());
+        }
+        MExpr::FunctionLiteral { params, body, .. } => {
+            bound.extend(params.iter().map(|p| p.name.as_str()));
+            collect_free_identifiers(body, bound, out);
+            bound.truncate(bound.len() - params.len());
+        }
+        MExpr::Each { body } => {
+            bound.push("_");
+            collect_free_identifiers(body, bound, out);
+            bound.pop();
+        }
+        MExpr::Access { base, kind, key } => {
+            collect_free_identifiers(base, bound, out);
+            if *kind == AccessKind::Item {
+                collect_free_identifiers(key, bound, out);
+            }
+        }
+        MExpr::Opaque(tokens) => {
+            let mut prev = None;
+            for token in tokens {
+                if let MToken::Identifier(name) = token
+                    && prev != Some(&MToken::Symbol('['))
+                {
+                    read(name, bound, out);
+                }
+                prev = Some(token);
+            }
+        }
+        MExpr::List { items } => {
+            for item in items {
+                collect_free_identifiers(item, bound, out);
+            }
+        }
+        MExpr::UnaryOp { expr, .. } | MExpr::TypeAscription { expr, .. } => {
+            collect_free_identifiers(expr, bound, out);
+        }
+        MExpr::BinaryOp { left, right, .. } => {
+            collect_free_identifiers(left, bound, out);
+            collect_free_identifiers(right, bound, out);
+        }
+        MExpr::TryOtherwise { expr, otherwise } => {
+            collect_free_identifiers(expr, bound, out);
+            collect_free_identifiers(otherwise, bound, out);
+        }
+        MExpr::If {
+            cond,
+            then_branch,
+            else_branch,
+        } => {
+            collect_free_identifiers(cond, bound, out);
+            collect_free_identifiers(then_branch, bound, out);
+            collect_free_identifiers(else_branch, bound, out);
+        }
+        MExpr::Primitive(_) => {}
+    }
+}
+
 pub fn ast_semantically_equal(a: &MModuleAst, b: &MModuleAst) -> bool {
     a == b
 }
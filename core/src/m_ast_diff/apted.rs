@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use super::SimpleTree;
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub(crate) struct EditCounts {
     pub(crate) cost: u32,
     pub(crate) inserted: u32,
@@ -95,6 +95,92 @@ fn tree_edit_counts_at(
     base
 }
 
+/// One step of an edit script. Inserts and deletes cover whole subtrees, matching the cost
+/// model of [`tree_edit_counts`]; indices are preorder positions in the respective tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TreeEdit {
+    Update { old: usize, new: usize },
+    Insert { new: usize },
+    Delete { old: usize },
+}
+
+/// The edits behind [`tree_edit_counts`], in preorder of the trees.
+pub(crate) fn tree_edit_script(old: &SimpleTree, new: &SimpleTree) -> Vec<TreeEdit> {
+    let mut script = Vec::new();
+    if old.labels.is_empty() || new.labels.is_empty() {
+        if !old.labels.is_empty() {
+            script.push(TreeEdit::Delete { old: 0 });
+        }
+        if !new.labels.is_empty() {
+            script.push(TreeEdit::Insert { new: 0 });
+        }
+        return script;
+    }
+
+    let mut memo: HashMap<(usize, usize), EditCounts> = HashMap::new();
+    tree_edit_script_at(old, new, 0, 0, &mut memo, &mut script);
+    script
+}
+
+fn tree_edit_script_at(
+    old: &SimpleTree,
+    new: &SimpleTree,
+    oi: usize,
+    ni: usize,
+    memo: &mut HashMap<(usize, usize), EditCounts>,
+    script: &mut Vec<TreeEdit>,
+) {
+    if old.labels[oi] != new.labels[ni] {
+        script.push(TreeEdit::Update { old: oi, new: ni });
+    }
+
+    let old_children = &old.children[oi];
+    let new_children = &new.children[ni];
+    let (m, n) = (old_children.len(), new_children.len());
+    let dp = children_table(old, new, old_children, new_children, memo);
+    let idx = |i: usize, j: usize| -> usize { i * (n + 1) + j };
+
+    // Walk the alignment table back from the end, preferring to pair children.
+    let mut steps = Vec::new();
+    let (mut i, mut j) = (m, n);
+    while i > 0 || j > 0 {
+        let here = dp[idx(i, j)];
+        if i > 0 && j > 0 {
+            let pair =
+                tree_edit_counts_at(old, new, old_children[i - 1], new_children[j - 1], memo);
+            if dp[idx(i - 1, j - 1)].add(pair) == here {
+                steps.push((
+                    Some(old_children[i - 1]),
+                    Some(new_children[j - 1]),
+                    pair.cost,
+                ));
+                i -= 1;
+                j -= 1;
+                continue;
+            }
+        }
+        if i > 0 && dp[idx(i - 1, j)].add(delete_cost(old, old_children[i - 1])) == here {
+            steps.push((Some(old_children[i - 1]), None, 0));
+            i -= 1;
+        } else {
+            steps.push((None, Some(new_children[j - 1]), 0));
+            j -= 1;
+        }
+    }
+
+    for step in steps.into_iter().rev() {
+        match step {
+            (Some(oc), Some(nc), cost) if cost > 0 => {
+                tree_edit_script_at(old, new, oc, nc, memo, script);
+            }
+            (Some(_), Some(_), _) => {}
+            (Some(oc), None, _) => script.push(TreeEdit::Delete { old: oc }),
+            (None, Some(nc), _) => script.push(TreeEdit::Insert { new: nc }),
+            (None, None, _) => {}
+        }
+    }
+}
+
 fn align_children(
     old: &SimpleTree,
     new: &SimpleTree,
@@ -102,6 +188,19 @@ fn align_children(
     new_children: &[usize],
     memo: &mut HashMap<(usize, usize), EditCounts>,
 ) -> EditCounts {
+    let table = children_table(old, new, old_children, new_children, memo);
+    table[table.len() - 1]
+}
+
+/// Sequence alignment of two child lists; entry `i * (n + 1) + j` is the cheapest way to turn
+/// the first `i` old children into the first `j` new ones.
+fn children_table(
+    old: &SimpleTree,
+    new: &SimpleTree,
+    old_children: &[usize],
+    new_children: &[usize],
+    memo: &mut HashMap<(usize, usize), EditCounts>,
+) -> Vec<EditCounts> {
     let m = old_children.len();
     let n = new_children.len();
     let mut dp = vec![EditCounts::zero(); (m + 1) * (n + 1)];
@@ -134,7 +233,7 @@ fn align_children(
         }
     }
 
-    dp[idx(m, n, n)]
+    dp
 }
 
 fn delete_cost(tree: &SimpleTree, idx: usize) -> EditCounts {
@@ -6,6 +6,8 @@ use crate::m_ast::{MExpr, MModuleAst};
 mod apted;
 mod gumtree;
 
+pub(crate) use apted::{tree_edit_script, TreeEdit};
+
 const SMALL_AST_NODE_LIMIT: usize = 240;
 const REDUCED_TED_MAX_NODES: usize = 320;
 const MOVE_SUBTREE_MIN_SIZE: u32 = 6;
@@ -136,6 +138,20 @@ pub(crate) struct SimpleTree {
     pub(crate) subtree_size: Vec<u32>,
 }
 
+/// A tree for the edit-distance routines from preorder labels and child lists, for callers
+/// diffing something other than an M module. Node 0 is the root.
+pub(crate) fn simple_tree(labels: Vec<u64>, children: Vec<Vec<usize>>) -> SimpleTree {
+    let mut out = SimpleTree {
+        subtree_size: vec![0; labels.len()],
+        labels,
+        children,
+    };
+    if !out.labels.is_empty() {
+        compute_simple_subtree_sizes(&mut out, 0);
+    }
+    out
+}
+
 fn simple_tree_from_flat(tree: &FlatTree) -> SimpleTree {
     let mut labels = Vec::with_capacity(tree.nodes.len());
     let mut children = Vec::with_capacity(tree.nodes.len());
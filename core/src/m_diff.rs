@@ -9,6 +9,7 @@ use crate::hashing::XXH64_SEED;
 use crate::m_ast::{canonicalize_m_ast, extract_steps, parse_m_expression, StepKind};
 use crate::m_section::SectionParseError;
 use crate::matching::hungarian;
+use crate::query_deps::QueryGraph;
 use crate::string_pool::{StringId, StringPool};
 
 #[deprecated(note = "use WorkbookPackage::diff instead")]
@@ -356,6 +357,8 @@ fn diff_queries_to_ops(
                 old_hash: old_h,
                 new_hash: new_h,
                 semantic_detail,
+                downstream_queries: Vec::new(),
+                loaded_tables: Vec::new(),
             });
         }
         emit_metadata_diffs(pool, &mut ops, to, old_q, new_q);
@@ -412,6 +415,8 @@ fn diff_queries_to_ops(
                         old_hash: old_h,
                         new_hash: new_h,
                         semantic_detail,
+                        downstream_queries: Vec::new(),
+                        loaded_tables: Vec::new(),
                     });
                 }
 
@@ -421,6 +426,13 @@ fn diff_queries_to_ops(
         }
     }
 
+    let definition_changed = ops
+        .iter()
+        .any(|op| matches!(op, DiffOp::QueryDefinitionChanged { .. }));
+    if definition_changed {
+        QueryGraph::build(new_queries).annotate(&mut ops, pool);
+    }
+
     ops
 }
 
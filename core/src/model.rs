@@ -1,23 +1,42 @@
 use crate::string_pool::StringId;
 
-/// Minimal tabular model IR for future DAX/model diffing.
+/// Tabular model IR used for model diffing.
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct Model {
     pub measures: Vec<Measure>,
     pub tables: Vec<ModelTable>,
     pub relationships: Vec<ModelRelationship>,
+    pub roles: Vec<ModelRole>,
+    pub perspectives: Vec<ModelPerspective>,
+    pub cultures: Vec<ModelCulture>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Measure {
     pub name: StringId,
     pub expression: StringId,
+    pub format_string: Option<StringId>,
+    pub display_folder: Option<StringId>,
+    pub description: Option<StringId>,
+    pub kpi: Option<MeasureKpi>,
+}
+
+/// KPI expressions attached to a measure.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MeasureKpi {
+    pub target_expression: Option<StringId>,
+    pub status_expression: Option<StringId>,
+    pub trend_expression: Option<StringId>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ModelTable {
     pub name: StringId,
     pub columns: Vec<ModelColumn>,
+    pub hierarchies: Vec<ModelHierarchy>,
+    pub partitions: Vec<ModelPartition>,
+    /// Set when the table is a calculation group.
+    pub calculation_group: Option<ModelCalculationGroup>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -42,3 +61,83 @@ pub struct ModelRelationship {
     pub is_active: Option<bool>,
     pub name: Option<StringId>,
 }
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelHierarchy {
+    pub name: StringId,
+    pub description: Option<StringId>,
+    pub display_folder: Option<StringId>,
+    pub is_hidden: Option<bool>,
+    /// Levels in ordinal order.
+    pub levels: Vec<ModelLevel>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelLevel {
+    pub name: StringId,
+    pub column: StringId,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelPartition {
+    pub name: StringId,
+    /// Storage mode, e.g. `import` or `directQuery`.
+    pub mode: Option<StringId>,
+    /// Source type, e.g. `m`, `query` or `calculated`.
+    pub source_type: Option<StringId>,
+    /// M expression, SQL query or DAX expression feeding the partition.
+    pub source: Option<StringId>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelCalculationGroup {
+    pub precedence: Option<i64>,
+    pub description: Option<StringId>,
+    pub items: Vec<ModelCalculationItem>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelCalculationItem {
+    pub name: StringId,
+    pub expression: StringId,
+    pub format_string_expression: Option<StringId>,
+    pub ordinal: Option<i64>,
+    pub description: Option<StringId>,
+}
+
+/// Security role with its row-level and object-level permissions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelRole {
+    pub name: StringId,
+    pub description: Option<StringId>,
+    /// `read`, `readRefresh`, `refresh` or `administrator`.
+    pub model_permission: Option<StringId>,
+    pub members: Vec<StringId>,
+    pub table_permissions: Vec<ModelTablePermission>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelTablePermission {
+    pub table: StringId,
+    /// Row-level security DAX filter.
+    pub filter_expression: Option<StringId>,
+    /// Object-level security on the table, e.g. `none`.
+    pub metadata_permission: Option<StringId>,
+    /// Object-level security on columns as `(column, permission)`.
+    pub column_permissions: Vec<(StringId, StringId)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelPerspective {
+    pub name: StringId,
+    /// Included objects, e.g. `table Sales`, `column Sales[Amount]`, `measure Sales[Total]`.
+    pub members: Vec<StringId>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelCulture {
+    pub name: StringId,
+    /// Translated captions, descriptions and display folders as `(object property, text)`,
+    /// e.g. `(column Sales[Amount] caption, Montant)`.
+    pub translations: Vec<(StringId, StringId)>,
+}
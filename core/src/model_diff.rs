@@ -3,9 +3,16 @@ use std::hash::{Hash, Hasher};
 
 use crate::config::{DiffConfig, SemanticNoisePolicy};
 use crate::dax;
-use crate::diff::{DiffOp, ExpressionChangeKind, ModelColumnProperty, RelationshipProperty};
+use crate::dax_diff;
+use crate::diff::{
+    DaxEdit, DiffOp, ExpressionChangeKind, MeasureProperty, ModelColumnProperty, ModelObjectChange,
+    ModelObjectField, ModelObjectKind, RelationshipProperty,
+};
 use crate::hashing::XXH64_SEED;
-use crate::model::{Measure, Model, ModelColumn, ModelRelationship, ModelTable};
+use crate::model::{
+    Measure, Model, ModelCalculationItem, ModelColumn, ModelCulture, ModelHierarchy,
+    ModelPartition, ModelPerspective, ModelRelationship, ModelRole, ModelTable,
+};
 use crate::string_pool::{StringId, StringPool};
 
 fn hash64<T: Hash>(value: &T) -> u64 {
@@ -60,7 +67,8 @@ impl OpEmitter {
     }
 }
 
-/// Diff two tabular models (tables/columns/relationships/measures).
+/// Diff two tabular models: tables, columns, hierarchies, partitions, calculation groups,
+/// relationships, measures, roles, perspectives and cultures.
 pub fn diff_models(
     old: &Model,
     new: &Model,
@@ -76,6 +84,42 @@ pub fn diff_models(
     if !emitter.truncated {
         diff_measures(old, new, pool, config, &mut emitter);
     }
+    if !emitter.truncated {
+        diff_named_objects(
+            &old.roles,
+            &new.roles,
+            ModelObjectKind::Role,
+            None,
+            |role| role.name,
+            |old, new, pool| role_changes(old, new, pool, config),
+            pool,
+            &mut emitter,
+        );
+    }
+    if !emitter.truncated {
+        diff_named_objects(
+            &old.perspectives,
+            &new.perspectives,
+            ModelObjectKind::Perspective,
+            None,
+            |perspective| perspective.name,
+            |old, new, _| perspective_changes(old, new),
+            pool,
+            &mut emitter,
+        );
+    }
+    if !emitter.truncated {
+        diff_named_objects(
+            &old.cultures,
+            &new.cultures,
+            ModelObjectKind::Culture,
+            None,
+            |culture| culture.name,
+            |old, new, _| culture_changes(old, new),
+            pool,
+            &mut emitter,
+        );
+    }
 
     let mut result = ModelDiffResult::new(emitter.ops);
     if emitter.truncated {
@@ -120,6 +164,7 @@ fn diff_tables(
             }
             (Some(old_table), Some(new_table)) => {
                 diff_columns(old_table, new_table, pool, config, emitter);
+                diff_table_objects(old_table, new_table, pool, config, emitter);
             }
             (None, None) => {}
         }
@@ -231,12 +276,19 @@ fn diff_column_properties(
     if let Some((kind, old_hash, new_hash)) =
         column_expression_change(old_col.expression, new_col.expression, pool, config)
     {
+        let edits = match (old_col.expression, new_col.expression) {
+            (Some(old_id), Some(new_id)) => {
+                definition_edits(kind, pool.resolve(old_id), pool.resolve(new_id))
+            }
+            _ => Vec::new(),
+        };
         emitter.push(DiffOp::CalculatedColumnDefinitionChanged {
             table: table_id,
             name: new_col.name,
             change_kind: kind,
             old_hash,
             new_hash,
+            edits,
         });
     }
 }
@@ -350,14 +402,585 @@ fn diff_measures(
                         change_kind: kind,
                         old_hash,
                         new_hash,
+                        edits: definition_edits(kind, old_expr, new_expr),
                     });
                 }
+                diff_measure_properties(old_measure, new_measure, pool, config, emitter);
             }
             (None, None) => {}
         }
     }
 }
 
+fn diff_measure_properties(
+    old: &Measure,
+    new: &Measure,
+    pool: &mut StringPool,
+    config: &DiffConfig,
+    emitter: &mut OpEmitter,
+) {
+    let old_kpi = old.kpi.clone().unwrap_or_default();
+    let new_kpi = new.kpi.clone().unwrap_or_default();
+    let fields = [
+        (
+            MeasureProperty::FormatString,
+            old.format_string,
+            new.format_string,
+        ),
+        (
+            MeasureProperty::DisplayFolder,
+            old.display_folder,
+            new.display_folder,
+        ),
+        (
+            MeasureProperty::Description,
+            old.description,
+            new.description,
+        ),
+        (
+            MeasureProperty::KpiTarget,
+            old_kpi.target_expression,
+            new_kpi.target_expression,
+        ),
+        (
+            MeasureProperty::KpiStatus,
+            old_kpi.status_expression,
+            new_kpi.status_expression,
+        ),
+        (
+            MeasureProperty::KpiTrend,
+            old_kpi.trend_expression,
+            new_kpi.trend_expression,
+        ),
+    ];
+
+    for (field, old_value, new_value) in fields {
+        let is_dax = matches!(
+            field,
+            MeasureProperty::KpiTarget | MeasureProperty::KpiStatus | MeasureProperty::KpiTrend
+        );
+        let changed = if is_dax {
+            dax_changed(old_value, new_value, pool, config)
+        } else {
+            old_value != new_value
+        };
+        if changed {
+            emitter.push(DiffOp::MeasurePropertyChanged {
+                name: new.name,
+                field,
+                old: old_value,
+                new: new_value,
+            });
+        }
+    }
+}
+
+fn diff_table_objects(
+    old_table: &ModelTable,
+    new_table: &ModelTable,
+    pool: &mut StringPool,
+    config: &DiffConfig,
+    emitter: &mut OpEmitter,
+) {
+    let table_id = Some(new_table.name);
+
+    diff_named_objects(
+        &old_table.hierarchies,
+        &new_table.hierarchies,
+        ModelObjectKind::Hierarchy,
+        table_id,
+        |hierarchy| hierarchy.name,
+        hierarchy_changes,
+        pool,
+        emitter,
+    );
+    diff_named_objects(
+        &old_table.partitions,
+        &new_table.partitions,
+        ModelObjectKind::Partition,
+        table_id,
+        |partition| partition.name,
+        |old, new, _| partition_changes(old, new),
+        pool,
+        emitter,
+    );
+
+    match (&old_table.calculation_group, &new_table.calculation_group) {
+        (None, Some(_)) => emitter.push(DiffOp::ModelObjectAdded {
+            object: ModelObjectKind::CalculationGroup,
+            table: None,
+            name: new_table.name,
+        }),
+        (Some(_), None) => emitter.push(DiffOp::ModelObjectRemoved {
+            object: ModelObjectKind::CalculationGroup,
+            table: None,
+            name: old_table.name,
+        }),
+        (Some(old_group), Some(new_group)) => {
+            let mut changes = Vec::new();
+            let precedence =
+                |p: Option<i64>, pool: &mut StringPool| p.map(|p| pool.intern(&p.to_string()));
+            let old_precedence = precedence(old_group.precedence, pool);
+            let new_precedence = precedence(new_group.precedence, pool);
+            push_change(
+                &mut changes,
+                ModelObjectField::Precedence,
+                None,
+                old_precedence,
+                new_precedence,
+            );
+            push_change(
+                &mut changes,
+                ModelObjectField::Description,
+                None,
+                old_group.description,
+                new_group.description,
+            );
+            if !changes.is_empty() {
+                emitter.push(DiffOp::ModelObjectChanged {
+                    object: ModelObjectKind::CalculationGroup,
+                    table: None,
+                    name: new_table.name,
+                    changes,
+                });
+            }
+            diff_named_objects(
+                &old_group.items,
+                &new_group.items,
+                ModelObjectKind::CalculationItem,
+                table_id,
+                |item| item.name,
+                |old, new, pool| calculation_item_changes(old, new, pool, config),
+                pool,
+                emitter,
+            );
+        }
+        (None, None) => {}
+    }
+}
+
+/// Matches objects by case-insensitive name and emits `ModelObjectAdded`/`Removed`, or
+/// `ModelObjectChanged` when `changes` reports a difference.
+#[allow(clippy::too_many_arguments)]
+fn diff_named_objects<T>(
+    old: &[T],
+    new: &[T],
+    object: ModelObjectKind,
+    table: Option<StringId>,
+    name_of: impl Fn(&T) -> StringId,
+    changes: impl Fn(&T, &T, &mut StringPool) -> Vec<ModelObjectChange>,
+    pool: &mut StringPool,
+    emitter: &mut OpEmitter,
+) {
+    let old_items = map_named(old, &name_of, pool);
+    let new_items = map_named(new, &name_of, pool);
+    let mut keys: BTreeSet<String> = BTreeSet::new();
+    keys.extend(old_items.keys().cloned());
+    keys.extend(new_items.keys().cloned());
+
+    for key in keys {
+        if emitter.truncated {
+            return;
+        }
+        match (old_items.get(&key), new_items.get(&key)) {
+            (None, Some(new_item)) => emitter.push(DiffOp::ModelObjectAdded {
+                object,
+                table,
+                name: name_of(new_item),
+            }),
+            (Some(old_item), None) => emitter.push(DiffOp::ModelObjectRemoved {
+                object,
+                table,
+                name: name_of(old_item),
+            }),
+            (Some(old_item), Some(new_item)) => {
+                let changes = changes(old_item, new_item, pool);
+                if !changes.is_empty() {
+                    emitter.push(DiffOp::ModelObjectChanged {
+                        object,
+                        table,
+                        name: name_of(new_item),
+                        changes,
+                    });
+                }
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+fn map_named<'a, T>(
+    items: &'a [T],
+    name_of: impl Fn(&T) -> StringId,
+    pool: &StringPool,
+) -> BTreeMap<String, &'a T> {
+    let mut out = BTreeMap::new();
+    for item in items {
+        let key = pool.resolve(name_of(item)).to_lowercase();
+        out.entry(key).or_insert(item);
+    }
+    out
+}
+
+fn push_change(
+    changes: &mut Vec<ModelObjectChange>,
+    field: ModelObjectField,
+    item: Option<StringId>,
+    from: Option<StringId>,
+    to: Option<StringId>,
+) {
+    if from != to {
+        changes.push(ModelObjectChange {
+            field,
+            item,
+            from,
+            to,
+        });
+    }
+}
+
+/// Reports members present on only one side, with `from` set for removed members.
+fn push_member_changes(changes: &mut Vec<ModelObjectChange>, old: &[StringId], new: &[StringId]) {
+    let old_set: BTreeSet<StringId> = old.iter().copied().collect();
+    let new_set: BTreeSet<StringId> = new.iter().copied().collect();
+    for member in old.iter().filter(|m| !new_set.contains(m)) {
+        push_change(changes, ModelObjectField::Member, None, Some(*member), None);
+    }
+    for member in new.iter().filter(|m| !old_set.contains(m)) {
+        push_change(changes, ModelObjectField::Member, None, None, Some(*member));
+    }
+}
+
+fn hierarchy_changes(
+    old: &ModelHierarchy,
+    new: &ModelHierarchy,
+    pool: &mut StringPool,
+) -> Vec<ModelObjectChange> {
+    let mut changes = Vec::new();
+    push_change(
+        &mut changes,
+        ModelObjectField::Description,
+        None,
+        old.description,
+        new.description,
+    );
+    push_change(
+        &mut changes,
+        ModelObjectField::DisplayFolder,
+        None,
+        old.display_folder,
+        new.display_folder,
+    );
+    let old_hidden = old.is_hidden.map(|v| intern_bool(pool, v));
+    let new_hidden = new.is_hidden.map(|v| intern_bool(pool, v));
+    push_change(
+        &mut changes,
+        ModelObjectField::Hidden,
+        None,
+        old_hidden,
+        new_hidden,
+    );
+
+    let key = |name: StringId, pool: &StringPool| pool.resolve(name).to_lowercase();
+    let old_levels: BTreeMap<String, StringId> = old
+        .levels
+        .iter()
+        .map(|level| (key(level.name, pool), level.column))
+        .collect();
+    let new_levels: BTreeMap<String, StringId> = new
+        .levels
+        .iter()
+        .map(|level| (key(level.name, pool), level.column))
+        .collect();
+    for level in &old.levels {
+        if !new_levels.contains_key(&key(level.name, pool)) {
+            push_change(
+                &mut changes,
+                ModelObjectField::Level,
+                Some(level.name),
+                Some(level.column),
+                None,
+            );
+        }
+    }
+    for level in &new.levels {
+        let old_column = old_levels.get(&key(level.name, pool)).copied();
+        push_change(
+            &mut changes,
+            ModelObjectField::Level,
+            Some(level.name),
+            old_column,
+            Some(level.column),
+        );
+    }
+
+    let old_order: Vec<&str> = old
+        .levels
+        .iter()
+        .filter(|level| new_levels.contains_key(&key(level.name, pool)))
+        .map(|level| pool.resolve(level.name))
+        .collect();
+    let new_order: Vec<&str> = new
+        .levels
+        .iter()
+        .filter(|level| old_levels.contains_key(&key(level.name, pool)))
+        .map(|level| pool.resolve(level.name))
+        .collect();
+    let reordered = old_order
+        .iter()
+        .map(|name| name.to_lowercase())
+        .ne(new_order.iter().map(|name| name.to_lowercase()));
+    if reordered {
+        let old_text = old_order.join(", ");
+        let new_text = new_order.join(", ");
+        let from = pool.intern(&old_text);
+        let to = pool.intern(&new_text);
+        push_change(
+            &mut changes,
+            ModelObjectField::LevelOrder,
+            None,
+            Some(from),
+            Some(to),
+        );
+    }
+    changes
+}
+
+fn partition_changes(old: &ModelPartition, new: &ModelPartition) -> Vec<ModelObjectChange> {
+    let mut changes = Vec::new();
+    push_change(
+        &mut changes,
+        ModelObjectField::Mode,
+        None,
+        old.mode,
+        new.mode,
+    );
+    push_change(
+        &mut changes,
+        ModelObjectField::SourceType,
+        None,
+        old.source_type,
+        new.source_type,
+    );
+    push_change(
+        &mut changes,
+        ModelObjectField::Source,
+        None,
+        old.source,
+        new.source,
+    );
+    changes
+}
+
+fn calculation_item_changes(
+    old: &ModelCalculationItem,
+    new: &ModelCalculationItem,
+    pool: &mut StringPool,
+    config: &DiffConfig,
+) -> Vec<ModelObjectChange> {
+    let mut changes = Vec::new();
+    if dax_changed(Some(old.expression), Some(new.expression), pool, config) {
+        changes.push(ModelObjectChange {
+            field: ModelObjectField::Expression,
+            item: None,
+            from: Some(old.expression),
+            to: Some(new.expression),
+        });
+    }
+    if dax_changed(
+        old.format_string_expression,
+        new.format_string_expression,
+        pool,
+        config,
+    ) {
+        changes.push(ModelObjectChange {
+            field: ModelObjectField::FormatStringExpression,
+            item: None,
+            from: old.format_string_expression,
+            to: new.format_string_expression,
+        });
+    }
+    let old_ordinal = old.ordinal.map(|o| pool.intern(&o.to_string()));
+    let new_ordinal = new.ordinal.map(|o| pool.intern(&o.to_string()));
+    push_change(
+        &mut changes,
+        ModelObjectField::Ordinal,
+        None,
+        old_ordinal,
+        new_ordinal,
+    );
+    push_change(
+        &mut changes,
+        ModelObjectField::Description,
+        None,
+        old.description,
+        new.description,
+    );
+    changes
+}
+
+fn role_changes(
+    old: &ModelRole,
+    new: &ModelRole,
+    pool: &mut StringPool,
+    config: &DiffConfig,
+) -> Vec<ModelObjectChange> {
+    let mut changes = Vec::new();
+    push_change(
+        &mut changes,
+        ModelObjectField::Description,
+        None,
+        old.description,
+        new.description,
+    );
+    push_change(
+        &mut changes,
+        ModelObjectField::ModelPermission,
+        None,
+        old.model_permission,
+        new.model_permission,
+    );
+    push_member_changes(&mut changes, &old.members, &new.members);
+
+    let old_tables: BTreeMap<String, _> = old
+        .table_permissions
+        .iter()
+        .map(|tp| (pool.resolve(tp.table).to_lowercase(), tp))
+        .collect();
+    let new_tables: BTreeMap<String, _> = new
+        .table_permissions
+        .iter()
+        .map(|tp| (pool.resolve(tp.table).to_lowercase(), tp))
+        .collect();
+    let mut keys: BTreeSet<String> = BTreeSet::new();
+    keys.extend(old_tables.keys().cloned());
+    keys.extend(new_tables.keys().cloned());
+
+    for key in keys {
+        let old_tp = old_tables.get(&key);
+        let new_tp = new_tables.get(&key);
+        let Some(table) = new_tp.or(old_tp).map(|tp| tp.table) else {
+            continue;
+        };
+
+        let old_filter = old_tp.and_then(|tp| tp.filter_expression);
+        let new_filter = new_tp.and_then(|tp| tp.filter_expression);
+        if dax_changed(old_filter, new_filter, pool, config) {
+            changes.push(ModelObjectChange {
+                field: ModelObjectField::RowFilter,
+                item: Some(table),
+                from: old_filter,
+                to: new_filter,
+            });
+        }
+
+        push_change(
+            &mut changes,
+            ModelObjectField::ObjectPermission,
+            Some(table),
+            old_tp.and_then(|tp| tp.metadata_permission),
+            new_tp.and_then(|tp| tp.metadata_permission),
+        );
+
+        let columns = |tp: Option<&&crate::model::ModelTablePermission>, pool: &StringPool| {
+            tp.map(|tp| {
+                tp.column_permissions
+                    .iter()
+                    .map(|(column, permission)| {
+                        (pool.resolve(*column).to_lowercase(), (*column, *permission))
+                    })
+                    .collect::<BTreeMap<_, _>>()
+            })
+            .unwrap_or_default()
+        };
+        let old_columns = columns(old_tp, pool);
+        let new_columns = columns(new_tp, pool);
+        let mut column_keys: BTreeSet<&String> = BTreeSet::new();
+        column_keys.extend(old_columns.keys());
+        column_keys.extend(new_columns.keys());
+        for column_key in column_keys {
+            let old_column = old_columns.get(column_key);
+            let new_column = new_columns.get(column_key);
+            let (old_permission, new_permission) =
+                (old_column.map(|c| c.1), new_column.map(|c| c.1));
+            if old_permission == new_permission {
+                continue;
+            }
+            let Some(column) = new_column.or(old_column).map(|c| c.0) else {
+                continue;
+            };
+            let label = format!("{}[{}]", pool.resolve(table), pool.resolve(column));
+            let item = pool.intern(&label);
+            push_change(
+                &mut changes,
+                ModelObjectField::ObjectPermission,
+                Some(item),
+                old_permission,
+                new_permission,
+            );
+        }
+    }
+    changes
+}
+
+fn perspective_changes(old: &ModelPerspective, new: &ModelPerspective) -> Vec<ModelObjectChange> {
+    let mut changes = Vec::new();
+    push_member_changes(&mut changes, &old.members, &new.members);
+    changes
+}
+
+fn culture_changes(old: &ModelCulture, new: &ModelCulture) -> Vec<ModelObjectChange> {
+    let old_map: BTreeMap<StringId, StringId> = old.translations.iter().copied().collect();
+    let new_map: BTreeMap<StringId, StringId> = new.translations.iter().copied().collect();
+    let mut changes = Vec::new();
+    for (key, text) in &old.translations {
+        if !new_map.contains_key(key) {
+            push_change(
+                &mut changes,
+                ModelObjectField::Translation,
+                Some(*key),
+                Some(*text),
+                None,
+            );
+        }
+    }
+    for (key, text) in &new.translations {
+        push_change(
+            &mut changes,
+            ModelObjectField::Translation,
+            Some(*key),
+            old_map.get(key).copied(),
+            Some(*text),
+        );
+    }
+    changes
+}
+
+/// Compares two optional DAX expressions, honouring the formatting-only noise policy.
+fn dax_changed(
+    old: Option<StringId>,
+    new: Option<StringId>,
+    pool: &StringPool,
+    config: &DiffConfig,
+) -> bool {
+    match (old, new) {
+        (Some(old), Some(new)) => {
+            expression_change(pool.resolve(old), pool.resolve(new), config).is_some()
+        }
+        (old, new) => old != new,
+    }
+}
+
+/// Structural edits for a definition change. Only semantic changes are broken down, which
+/// implies the DAX semantic diff is enabled and both sides parse.
+fn definition_edits(kind: ExpressionChangeKind, old_expr: &str, new_expr: &str) -> Vec<DaxEdit> {
+    if kind == ExpressionChangeKind::Semantic {
+        dax_diff::dax_edits(old_expr, new_expr)
+    } else {
+        Vec::new()
+    }
+}
+
 fn column_expression_change(
     old_expr: Option<StringId>,
     new_expr: Option<StringId>,
@@ -494,6 +1117,10 @@ mod tests {
             measures: vec![Measure {
                 name,
                 expression: expr_id,
+                format_string: None,
+                display_folder: None,
+                description: None,
+                kpi: None,
             }],
             ..Default::default()
         }
@@ -515,6 +1142,9 @@ mod tests {
         let table = ModelTable {
             name: table_name,
             columns: vec![column],
+            hierarchies: Vec::new(),
+            partitions: Vec::new(),
+            calculation_group: None,
         };
         Model {
             tables: vec![table],
@@ -522,6 +1152,127 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "excel-open-xml")]
+    fn parse_model(pool: &mut StringPool, json: &str) -> Model {
+        let raw = crate::tabular_schema::parse_data_model_schema(json.as_bytes())
+            .expect("schema should parse");
+        crate::tabular_schema::build_model(&raw, pool)
+    }
+
+    #[cfg(feature = "excel-open-xml")]
+    fn full_model_json(region: &str, source: &str, format: &str, levels: &str) -> String {
+        format!(
+            r#"{{"model": {{
+                "tables": [
+                    {{
+                        "name": "Sales",
+                        "measures": [
+                            {{ "name": "Total", "expression": "1", "formatString": "{format}" }}
+                        ],
+                        "hierarchies": [{{ "name": "Dates", "levels": {levels} }}],
+                        "partitions": [
+                            {{
+                                "name": "Sales",
+                                "source": {{ "type": "m", "expression": "{source}" }}
+                            }}
+                        ]
+                    }},
+                    {{
+                        "name": "Time",
+                        "calculationGroup": {{
+                            "calculationItems": [
+                                {{ "name": "YTD", "expression": "SELECTEDMEASURE()" }}
+                            ]
+                        }}
+                    }}
+                ],
+                "roles": [{{
+                    "name": "Regional",
+                    "tablePermissions": [
+                        {{ "name": "Sales", "filterExpression": "[Region] = \"{region}\"" }}
+                    ]
+                }}],
+                "perspectives": [{{ "name": "Finance", "tables": [{{ "name": "Sales" }}] }}]
+            }}}}"#
+        )
+    }
+
+    #[cfg(feature = "excel-open-xml")]
+    #[test]
+    fn role_filter_partition_source_measure_and_level_changes_are_reported() {
+        let levels_old = r#"[{ "name": "Year", "ordinal": 0, "column": "Year" },
+                             { "name": "Month", "ordinal": 1, "column": "Month" }]"#;
+        let levels_new = r#"[{ "name": "Month", "ordinal": 0, "column": "Month" },
+                             { "name": "Year", "ordinal": 1, "column": "Year" }]"#;
+        let mut pool = StringPool::new();
+        let old = parse_model(
+            &mut pool,
+            &full_model_json("East", "let a = 1 in a", "0.00", levels_old),
+        );
+        let new = parse_model(
+            &mut pool,
+            &full_model_json("West", "let a = 2 in a", "#,0", levels_new),
+        );
+
+        let result = diff_models(&old, &new, &mut pool, &DiffConfig::default());
+        let object_changes = |kind: ModelObjectKind| {
+            result
+                .ops
+                .iter()
+                .find_map(|op| match op {
+                    DiffOp::ModelObjectChanged {
+                        object, changes, ..
+                    } if *object == kind => Some(changes.clone()),
+                    _ => None,
+                })
+                .unwrap_or_default()
+        };
+
+        let role = object_changes(ModelObjectKind::Role);
+        assert_eq!(role.len(), 1);
+        assert_eq!(role[0].field, ModelObjectField::RowFilter);
+        assert_eq!(role[0].item.map(|id| pool.resolve(id)), Some("Sales"));
+        assert_eq!(
+            role[0].to.map(|id| pool.resolve(id)),
+            Some("[Region] = \"West\"")
+        );
+
+        let partition = object_changes(ModelObjectKind::Partition);
+        assert_eq!(partition.len(), 1);
+        assert_eq!(partition[0].field, ModelObjectField::Source);
+
+        let hierarchy = object_changes(ModelObjectKind::Hierarchy);
+        assert_eq!(hierarchy.len(), 1);
+        assert_eq!(hierarchy[0].field, ModelObjectField::LevelOrder);
+        assert_eq!(
+            hierarchy[0].to.map(|id| pool.resolve(id)),
+            Some("Month, Year")
+        );
+
+        assert!(result.ops.iter().any(|op| matches!(
+            op,
+            DiffOp::MeasurePropertyChanged {
+                field: MeasureProperty::FormatString,
+                ..
+            }
+        )));
+        assert!(object_changes(ModelObjectKind::CalculationItem).is_empty());
+        assert!(object_changes(ModelObjectKind::Perspective).is_empty());
+    }
+
+    #[cfg(feature = "excel-open-xml")]
+    #[test]
+    fn identical_full_models_produce_no_ops() {
+        let levels = r#"[{ "name": "Year", "column": "Year" }]"#;
+        let json = full_model_json("East", "let a = 1 in a", "0.00", levels);
+        let mut pool = StringPool::new();
+        let old = parse_model(&mut pool, &json);
+        let new = parse_model(&mut pool, &json);
+
+        let result = diff_models(&old, &new, &mut pool, &DiffConfig::default());
+        assert!(result.ops.is_empty(), "unexpected ops: {:?}", result.ops);
+    }
+
     #[test]
     fn dax_formatting_only_suppressed_when_configured() {
         let mut pool = StringPool::new();
@@ -1,7 +1,19 @@
-use crate::diff::DiffOp;
-use crate::string_pool::StringPool;
-use crate::vba::VbaModule;
-use crate::workbook::{ChartObject, NamedRange, Workbook};
+use crate::diff::{
+    ChartChange, ChartField, ConnectionChange, ConnectionField, DiffOp, PivotArea,
+    PivotLayoutChange, SheetId, TextHunk, TextLine, VbaAttributeChange, VbaProcedureChange,
+    VbaProcedureChangeKind, VbaProjectProperty, WorkbookSetting, WorkbookSettingChange,
+};
+use crate::formula::{parse_formula, FormulaExpr};
+use crate::op_alignment::GridOpAlignment;
+use crate::string_pool::{StringId, StringPool};
+use crate::text_diff::{self, LineEdit};
+use crate::vba::{find_procedures, split_module_source, VbaModule, VbaProcedure, VbaProject};
+use crate::workbook::{
+    AxisLayout, CalcMode, CellAddress, CellRange, CellStyle, CellValue, ChartAxis, ChartObject,
+    ChartSeries, ConditionalFormatRule, DataConnection, DataValidationRule, DrawingAnchor,
+    DrawingMarker, DrawingObject, ExcelTable, ExternalLink, FreezePane, NamedRange, PivotSource,
+    PivotTable, Sheet, SheetCellStyles, SheetComments, SheetDrawings, SheetRules, Workbook,
+};
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 pub(crate) fn diff_named_ranges(old: &Workbook, new: &Workbook, pool: &StringPool) -> Vec<DiffOp> {
@@ -48,7 +60,8 @@ pub(crate) fn diff_named_ranges(old: &Workbook, new: &Workbook, pool: &StringPoo
     ops
 }
 
-pub(crate) fn diff_charts(old: &Workbook, new: &Workbook, pool: &StringPool) -> Vec<DiffOp> {
+/// Interns axis labels and flag values; streaming callers must run it before `sink.begin`.
+pub(crate) fn diff_charts(old: &Workbook, new: &Workbook, pool: &mut StringPool) -> Vec<DiffOp> {
     #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
     struct ChartIdKey {
         sheet_id: u32,
@@ -92,6 +105,7 @@ pub(crate) fn diff_charts(old: &Workbook, new: &Workbook, pool: &StringPool) ->
         ambiguous
     }
 
+    let renames = sheet_renames(old, new, pool);
     let ambiguous_old = ambiguous_sheet_ids(&old.charts, pool);
     let ambiguous_new = ambiguous_sheet_ids(&new.charts, pool);
 
@@ -171,12 +185,7 @@ pub(crate) fn diff_charts(old: &Workbook, new: &Workbook, pool: &StringPool) ->
                 name: old_chart.info.name,
             }),
             (Some(old_chart), Some(new_chart)) => {
-                if old_chart.xml_hash != new_chart.xml_hash {
-                    ops.push(DiffOp::ChartChanged {
-                        sheet: new_chart.sheet,
-                        name: new_chart.info.name,
-                    });
-                }
+                ops.extend(diff_chart(old_chart, new_chart, &renames, pool));
             }
             (None, None) => {}
         }
@@ -205,10 +214,404 @@ pub(crate) fn diff_charts(old: &Workbook, new: &Workbook, pool: &StringPool) ->
                 name: old_chart.info.name,
             }),
             (Some(old_chart), Some(new_chart)) => {
-                if old_chart.xml_hash != new_chart.xml_hash {
-                    ops.push(DiffOp::ChartChanged {
-                        sheet: new_chart.sheet,
-                        name: new_chart.info.name,
+                ops.extend(diff_chart(old_chart, new_chart, &renames, pool));
+            }
+            (None, None) => {}
+        }
+    }
+
+    ops
+}
+
+/// Old sheet name (lowercase) to new sheet name, for sheets paired by workbook sheet id whose
+/// name changed.
+fn sheet_renames(old: &Workbook, new: &Workbook, pool: &StringPool) -> HashMap<String, String> {
+    let mut renames = HashMap::new();
+    for old_sheet in &old.sheets {
+        let Some(sheet_id) = old_sheet.workbook_sheet_id else {
+            continue;
+        };
+        let Some(new_sheet) = new
+            .sheets
+            .iter()
+            .find(|sheet| sheet.workbook_sheet_id == Some(sheet_id))
+        else {
+            continue;
+        };
+        let (old_name, new_name) = (pool.resolve(old_sheet.name), pool.resolve(new_sheet.name));
+        if old_name != new_name {
+            renames.insert(old_name.to_lowercase(), new_name.to_string());
+        }
+    }
+    renames
+}
+
+/// Canonical form of a chart reference for comparison: every sheet prefix is written as a
+/// lowercase quoted name, after mapping it through `renames`. String literals are left alone.
+fn canonical_chart_ref(formula: &str, renames: &HashMap<String, String>) -> String {
+    let chars: Vec<char> = formula.chars().collect();
+    let mut out = String::with_capacity(formula.len());
+    let mut idx = 0;
+    while idx < chars.len() {
+        let c = chars[idx];
+        if c == '"' {
+            let start = idx;
+            idx += 1;
+            while idx < chars.len() {
+                if chars[idx] == '"' && chars.get(idx + 1) == Some(&'"') {
+                    idx += 2;
+                } else if chars[idx] == '"' {
+                    idx += 1;
+                    break;
+                } else {
+                    idx += 1;
+                }
+            }
+            out.extend(&chars[start..idx]);
+            continue;
+        }
+
+        let start = idx;
+        let mut name = String::new();
+        if c == '\'' {
+            idx += 1;
+            while idx < chars.len() {
+                if chars[idx] == '\'' && chars.get(idx + 1) == Some(&'\'') {
+                    name.push('\'');
+                    idx += 2;
+                } else if chars[idx] == '\'' {
+                    idx += 1;
+                    break;
+                } else {
+                    name.push(chars[idx]);
+                    idx += 1;
+                }
+            }
+        } else if c.is_alphanumeric() || c == '_' || c == '.' {
+            while idx < chars.len()
+                && (chars[idx].is_alphanumeric() || chars[idx] == '_' || chars[idx] == '.')
+            {
+                name.push(chars[idx]);
+                idx += 1;
+            }
+        } else {
+            out.push(c);
+            idx += 1;
+            continue;
+        }
+
+        if chars.get(idx) == Some(&'!') {
+            let lower = name.to_lowercase();
+            let sheet = renames
+                .get(&lower)
+                .map(|renamed| renamed.to_lowercase())
+                .unwrap_or(lower);
+            out.push('\'');
+            out.push_str(&sheet.replace('\'', "''"));
+            out.push_str("'!");
+            idx += 1;
+        } else {
+            out.extend(&chars[start..idx]);
+        }
+    }
+    out
+}
+
+fn axis_label(axis: &ChartAxis, pool: &StringPool) -> String {
+    let kind = match pool.resolve(axis.kind) {
+        "catAx" => "category axis",
+        "valAx" => "value axis",
+        "dateAx" => "date axis",
+        "serAx" => "series axis",
+        other => other,
+    };
+    let position = axis.position.map(|id| match pool.resolve(id) {
+        "b" => "bottom",
+        "l" => "left",
+        "r" => "right",
+        "t" => "top",
+        other => other,
+    });
+    match position {
+        Some(position) => format!("{kind} ({position})"),
+        None => kind.to_string(),
+    }
+}
+
+/// Pair axes by kind and position, then leftover axes of the same kind in document order.
+fn pair_axes<'a>(
+    old: &'a [ChartAxis],
+    new: &'a [ChartAxis],
+) -> Vec<(Option<&'a ChartAxis>, Option<&'a ChartAxis>)> {
+    let mut used: HashSet<usize> = HashSet::new();
+    let mut matched: Vec<Option<usize>> = old
+        .iter()
+        .map(|axis| {
+            let idx = (0..new.len()).find(|&idx| {
+                !used.contains(&idx)
+                    && new[idx].kind == axis.kind
+                    && new[idx].position == axis.position
+            })?;
+            used.insert(idx);
+            Some(idx)
+        })
+        .collect();
+    for (slot, axis) in matched.iter_mut().zip(old) {
+        if slot.is_none() {
+            *slot = (0..new.len()).find(|&idx| !used.contains(&idx) && new[idx].kind == axis.kind);
+            used.extend(*slot);
+        }
+    }
+
+    let mut pairs: Vec<(Option<&ChartAxis>, Option<&ChartAxis>)> = old
+        .iter()
+        .zip(matched)
+        .map(|(axis, idx)| (Some(axis), idx.map(|idx| &new[idx])))
+        .collect();
+    pairs.extend(
+        (0..new.len())
+            .filter(|idx| !used.contains(idx))
+            .map(|idx| (None, Some(&new[idx]))),
+    );
+    pairs
+}
+
+/// Compare two paired charts. Series, title and axis title references only count as changed
+/// when they differ after sheet renames, and a chart whose part differs only through renamed
+/// sheet references is not reported.
+fn diff_chart(
+    old: &ChartObject,
+    new: &ChartObject,
+    renames: &HashMap<String, String>,
+    pool: &mut StringPool,
+) -> Option<DiffOp> {
+    if old.xml_hash == new.xml_hash {
+        return None;
+    }
+
+    let mut renamed_refs = false;
+    let mut same_ref = |pool: &StringPool, from: Option<StringId>, to: Option<StringId>| {
+        let (Some(from), Some(to)) = (from, to) else {
+            return from == to;
+        };
+        if from == to {
+            return true;
+        }
+        let no_renames = HashMap::new();
+        let same = canonical_chart_ref(pool.resolve(from), renames)
+            == canonical_chart_ref(pool.resolve(to), &no_renames);
+        renamed_refs |= same;
+        same
+    };
+
+    let (old_info, new_info) = (&old.info, &new.info);
+    let mut changes = Vec::new();
+    let chart_change = |field, from, to| ChartChange {
+        field,
+        series: None,
+        axis: None,
+        from,
+        to,
+    };
+    if !same_ref(pool, old_info.title, new_info.title) {
+        changes.push(chart_change(
+            ChartField::Title,
+            old_info.title,
+            new_info.title,
+        ));
+    }
+    if old_info.legend != new_info.legend {
+        changes.push(chart_change(
+            ChartField::Legend,
+            old_info.legend,
+            new_info.legend,
+        ));
+    }
+
+    let old_series: BTreeMap<u32, &ChartSeries> =
+        old_info.series.iter().map(|s| (s.index, s)).collect();
+    let new_series: BTreeMap<u32, &ChartSeries> =
+        new_info.series.iter().map(|s| (s.index, s)).collect();
+    let indexes: BTreeSet<u32> = old_series
+        .keys()
+        .chain(new_series.keys())
+        .copied()
+        .collect();
+    for index in indexes {
+        let series_change = |field, from, to| ChartChange {
+            field,
+            series: Some(index),
+            axis: None,
+            from,
+            to,
+        };
+        match (old_series.get(&index), new_series.get(&index)) {
+            (Some(old_s), Some(new_s)) => {
+                if !same_ref(pool, old_s.name, new_s.name) {
+                    changes.push(series_change(
+                        ChartField::SeriesName,
+                        old_s.name,
+                        new_s.name,
+                    ));
+                }
+                if old_s.chart_type != new_s.chart_type {
+                    changes.push(series_change(
+                        ChartField::SeriesType,
+                        Some(old_s.chart_type),
+                        Some(new_s.chart_type),
+                    ));
+                }
+                if !same_ref(pool, old_s.categories, new_s.categories) {
+                    changes.push(series_change(
+                        ChartField::SeriesCategories,
+                        old_s.categories,
+                        new_s.categories,
+                    ));
+                }
+                if !same_ref(pool, old_s.values, new_s.values) {
+                    changes.push(series_change(
+                        ChartField::SeriesValues,
+                        old_s.values,
+                        new_s.values,
+                    ));
+                }
+            }
+            (Some(old_s), None) => {
+                changes.push(series_change(ChartField::Series, old_s.values, None));
+            }
+            (None, Some(new_s)) => {
+                changes.push(series_change(ChartField::Series, None, new_s.values));
+            }
+            (None, None) => {}
+        }
+    }
+
+    for (old_axis, new_axis) in pair_axes(&old_info.axes, &new_info.axes) {
+        let Some(label_axis) = new_axis.or(old_axis) else {
+            continue;
+        };
+        let axis = Some(pool.intern(&axis_label(label_axis, pool)));
+        let axis_change = |field, from, to| ChartChange {
+            field,
+            series: None,
+            axis,
+            from,
+            to,
+        };
+        let (Some(old_axis), Some(new_axis)) = (old_axis, new_axis) else {
+            let (from, to) = (old_axis.map(|a| a.kind), new_axis.map(|a| a.kind));
+            changes.push(axis_change(ChartField::Axis, from, to));
+            continue;
+        };
+        if !same_ref(pool, old_axis.title, new_axis.title) {
+            changes.push(axis_change(
+                ChartField::AxisTitle,
+                old_axis.title,
+                new_axis.title,
+            ));
+        }
+        let fields = [
+            (
+                ChartField::AxisPosition,
+                old_axis.position,
+                new_axis.position,
+            ),
+            (ChartField::AxisMin, old_axis.min, new_axis.min),
+            (ChartField::AxisMax, old_axis.max, new_axis.max),
+            (
+                ChartField::AxisLogBase,
+                old_axis.log_base,
+                new_axis.log_base,
+            ),
+            (
+                ChartField::AxisOrientation,
+                old_axis.orientation,
+                new_axis.orientation,
+            ),
+        ];
+        for (field, from, to) in fields {
+            if from != to {
+                changes.push(axis_change(field, from, to));
+            }
+        }
+        if old_axis.deleted != new_axis.deleted {
+            let mut flag = |value: bool| Some(pool.intern(if value { "true" } else { "false" }));
+            let (from, to) = (flag(old_axis.deleted), flag(new_axis.deleted));
+            changes.push(axis_change(ChartField::AxisDeleted, from, to));
+        }
+    }
+
+    if changes.is_empty() && renamed_refs {
+        return None;
+    }
+    Some(DiffOp::ChartChanged {
+        sheet: new.sheet,
+        name: new.info.name,
+        changes,
+    })
+}
+
+/// Pairs pivot tables by sheet (through renames) and name. Interns the layout descriptors it
+/// compares; streaming callers must run it before `sink.begin`.
+pub(crate) fn diff_pivot_tables(
+    old: &Workbook,
+    new: &Workbook,
+    pool: &mut StringPool,
+) -> Vec<DiffOp> {
+    let renames = sheet_renames(old, new, pool);
+    let key = |pivot: &PivotTable, renames: Option<&HashMap<String, String>>| {
+        let sheet = pool.resolve(pivot.sheet).to_lowercase();
+        let sheet = renames
+            .and_then(|renames| renames.get(&sheet))
+            .map(|name| name.to_lowercase())
+            .unwrap_or(sheet);
+        (sheet, pool.resolve(pivot.name).to_lowercase())
+    };
+
+    let mut old_map: BTreeMap<(String, String), &PivotTable> = BTreeMap::new();
+    for pivot in &old.pivot_tables {
+        old_map.insert(key(pivot, Some(&renames)), pivot);
+    }
+    let mut new_map: BTreeMap<(String, String), &PivotTable> = BTreeMap::new();
+    for pivot in &new.pivot_tables {
+        new_map.insert(key(pivot, None), pivot);
+    }
+
+    let mut keys: BTreeSet<(String, String)> = BTreeSet::new();
+    keys.extend(old_map.keys().cloned());
+    keys.extend(new_map.keys().cloned());
+
+    let mut ops = Vec::new();
+    for k in keys {
+        match (old_map.get(&k), new_map.get(&k)) {
+            (None, Some(new_pivot)) => ops.push(DiffOp::PivotTableAdded {
+                sheet: new_pivot.sheet,
+                name: new_pivot.name,
+            }),
+            (Some(old_pivot), None) => ops.push(DiffOp::PivotTableRemoved {
+                sheet: old_pivot.sheet,
+                name: old_pivot.name,
+            }),
+            (Some(old_pivot), Some(new_pivot)) => {
+                let old_uncovered = uncovered_source_rows(old, &old_pivot.source, pool);
+                let new_uncovered = uncovered_source_rows(new, &new_pivot.source, pool);
+                if !same_pivot_source(&old_pivot.source, &new_pivot.source, &renames, pool)
+                    || new_uncovered > old_uncovered
+                {
+                    ops.push(DiffOp::PivotTableSourceChanged {
+                        sheet: new_pivot.sheet,
+                        name: new_pivot.name,
+                        from: old_pivot.source.clone(),
+                        to: new_pivot.source.clone(),
+                        uncovered_rows: new_uncovered,
+                    });
+                }
+                let changes = diff_pivot_layout(old_pivot, new_pivot, pool);
+                if !changes.is_empty() {
+                    ops.push(DiffOp::PivotTableLayoutChanged {
+                        sheet: new_pivot.sheet,
+                        name: new_pivot.name,
+                        changes,
                     });
                 }
             }
@@ -219,10 +622,476 @@ pub(crate) fn diff_charts(old: &Workbook, new: &Workbook, pool: &StringPool) ->
     ops
 }
 
+fn same_pivot_source(
+    old: &PivotSource,
+    new: &PivotSource,
+    renames: &HashMap<String, String>,
+    pool: &StringPool,
+) -> bool {
+    let lower = |id: Option<StringId>| id.map(|id| pool.resolve(id).to_lowercase());
+    let old_sheet = lower(old.sheet).map(|sheet| {
+        renames
+            .get(&sheet)
+            .map(|name| name.to_lowercase())
+            .unwrap_or(sheet)
+    });
+    old.kind == new.kind
+        && old.range == new.range
+        && old.connection_id == new.connection_id
+        && lower(old.name) == lower(new.name)
+        && old_sheet == lower(new.sheet)
+}
+
+/// Filled rows directly below a worksheet source range, within its columns, that the pivot does
+/// not read. Stops at the first empty row.
+fn uncovered_source_rows(workbook: &Workbook, source: &PivotSource, pool: &StringPool) -> u32 {
+    let (Some(sheet_name), Some(range)) = (source.sheet, source.range) else {
+        return 0;
+    };
+    let sheet_name = pool.resolve(sheet_name).to_lowercase();
+    let Some(sheet) = workbook
+        .sheets
+        .iter()
+        .find(|sheet| pool.resolve(sheet.name).to_lowercase() == sheet_name)
+    else {
+        return 0;
+    };
+
+    let filled = |row: u32| {
+        (range.start.col..=range.end.col).any(|col| {
+            sheet.grid.get(row, col).is_some_and(|cell| {
+                cell.formula.is_some() || !matches!(cell.value, None | Some(CellValue::Blank))
+            })
+        })
+    };
+    let mut row = range.end.row + 1;
+    while row < sheet.grid.nrows && filled(row) {
+        row += 1;
+    }
+    row - (range.end.row + 1)
+}
+
+fn diff_pivot_layout(
+    old: &PivotTable,
+    new: &PivotTable,
+    pool: &mut StringPool,
+) -> Vec<PivotLayoutChange> {
+    fn joined(fields: &[StringId], pool: &StringPool) -> Option<String> {
+        let names: Vec<&str> = fields.iter().map(|field| pool.resolve(*field)).collect();
+        (!names.is_empty()).then(|| names.join(", "))
+    }
+
+    /// Per-field descriptors keyed by lowercase field name, with an occurrence suffix for
+    /// fields that appear more than once (the same field summed and counted).
+    fn keyed(
+        entries: Vec<(StringId, String)>,
+        pool: &StringPool,
+    ) -> BTreeMap<(String, usize), (StringId, String)> {
+        let mut map = BTreeMap::new();
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        for (field, descriptor) in entries {
+            let name = pool.resolve(field).to_lowercase();
+            let occurrence = seen.entry(name.clone()).or_default();
+            map.insert((name, *occurrence), (field, descriptor));
+            *occurrence += 1;
+        }
+        map
+    }
+
+    fn values(pivot: &PivotTable, pool: &StringPool) -> Vec<(StringId, String)> {
+        pivot
+            .data_fields
+            .iter()
+            .map(|data| {
+                let mut descriptor = format!(
+                    "{}: {}",
+                    pool.resolve(data.name),
+                    pool.resolve(data.function)
+                );
+                if let Some(show_as) = data.show_data_as {
+                    descriptor.push_str(&format!(" ({})", pool.resolve(show_as)));
+                }
+                (data.field, descriptor)
+            })
+            .collect()
+    }
+
+    fn filters(pivot: &PivotTable, pool: &StringPool) -> Vec<(StringId, String)> {
+        let mut by_field: Vec<(StringId, String)> = Vec::new();
+        for filter in &pivot.filters {
+            let values: Vec<&str> = filter.values.iter().map(|v| pool.resolve(*v)).collect();
+            let descriptor = format!("{}: {}", pool.resolve(filter.kind), values.join(", "));
+            match by_field
+                .iter_mut()
+                .find(|(field, _)| *field == filter.field)
+            {
+                Some((_, existing)) => {
+                    existing.push_str("; ");
+                    existing.push_str(&descriptor);
+                }
+                None => by_field.push((filter.field, descriptor)),
+            }
+        }
+        by_field
+    }
+
+    let mut changes = Vec::new();
+    let intern =
+        |pool: &mut StringPool, value: Option<String>| value.map(|value| pool.intern(&value));
+
+    for (area, old_fields, new_fields) in [
+        (PivotArea::Rows, &old.row_fields, &new.row_fields),
+        (PivotArea::Columns, &old.column_fields, &new.column_fields),
+    ] {
+        let (from, to) = (joined(old_fields, pool), joined(new_fields, pool));
+        if from != to {
+            changes.push(PivotLayoutChange {
+                area,
+                field: None,
+                from: intern(pool, from),
+                to: intern(pool, to),
+            });
+        }
+    }
+
+    let page = |pivot: &PivotTable, pool: &StringPool| -> Vec<(StringId, String)> {
+        pivot
+            .page_fields
+            .iter()
+            .map(|page| {
+                let item = page.item.map_or("(All)", |item| pool.resolve(item));
+                (page.field, item.to_string())
+            })
+            .collect()
+    };
+    let calculated = |pivot: &PivotTable, pool: &StringPool| -> Vec<(StringId, String)> {
+        pivot
+            .calculated_fields
+            .iter()
+            .map(|calc| (calc.name, pool.resolve(calc.formula).to_string()))
+            .collect()
+    };
+
+    let areas = [
+        (PivotArea::Pages, page(old, pool), page(new, pool)),
+        (PivotArea::Values, values(old, pool), values(new, pool)),
+        (PivotArea::Filters, filters(old, pool), filters(new, pool)),
+        (
+            PivotArea::CalculatedFields,
+            calculated(old, pool),
+            calculated(new, pool),
+        ),
+    ];
+    for (area, old_entries, new_entries) in areas {
+        let old_map = keyed(old_entries, pool);
+        let new_map = keyed(new_entries, pool);
+        let mut keys: BTreeSet<&(String, usize)> = BTreeSet::new();
+        keys.extend(old_map.keys());
+        keys.extend(new_map.keys());
+        let mut area_changes = Vec::new();
+        for k in keys {
+            let (from, to) = (old_map.get(k), new_map.get(k));
+            if from.map(|(_, d)| d) == to.map(|(_, d)| d) {
+                continue;
+            }
+            let field = to.or(from).map(|(field, _)| *field);
+            area_changes.push((
+                field,
+                from.map(|(_, d)| d.clone()),
+                to.map(|(_, d)| d.clone()),
+            ));
+        }
+        for (field, from, to) in area_changes {
+            changes.push(PivotLayoutChange {
+                area,
+                field,
+                from: intern(pool, from),
+                to: intern(pool, to),
+            });
+        }
+    }
+
+    changes
+}
+
+/// Pairs links by target (case-insensitive), then repointed links by their link number, so
+/// renumbering after a removed link is not reported as a change.
+pub(crate) fn diff_external_links(
+    old: &Workbook,
+    new: &Workbook,
+    pool: &StringPool,
+) -> Vec<DiffOp> {
+    let target = |link: &ExternalLink| pool.resolve(link.target).to_lowercase();
+
+    let mut unpaired_old: Vec<Option<&ExternalLink>> =
+        old.external_links.iter().map(Some).collect();
+    let mut pairs: Vec<(&ExternalLink, &ExternalLink)> = Vec::new();
+    let mut unpaired_new: Vec<&ExternalLink> = Vec::new();
+    for new_link in &new.external_links {
+        let new_target = target(new_link);
+        let slot = unpaired_old
+            .iter_mut()
+            .find(|slot| slot.is_some_and(|old_link| target(old_link) == new_target));
+        match slot.and_then(|slot| slot.take()) {
+            Some(old_link) => pairs.push((old_link, new_link)),
+            None => unpaired_new.push(new_link),
+        }
+    }
+
+    let mut ops = Vec::new();
+    for new_link in unpaired_new {
+        let slot = unpaired_old
+            .iter_mut()
+            .find(|slot| slot.is_some_and(|old_link| old_link.index == new_link.index));
+        match slot.and_then(|slot| slot.take()) {
+            Some(old_link) => pairs.push((old_link, new_link)),
+            None => ops.push(DiffOp::ExternalLinkAdded {
+                index: new_link.index,
+                target: new_link.target,
+            }),
+        }
+    }
+    for old_link in unpaired_old.into_iter().flatten() {
+        ops.push(DiffOp::ExternalLinkRemoved {
+            index: old_link.index,
+            target: old_link.target,
+        });
+    }
+
+    pairs.sort_by_key(|(_, new_link)| new_link.index);
+    let changed = pairs.into_iter().filter(|(old_link, new_link)| {
+        old_link.kind != new_link.kind
+            || old_link.target != new_link.target
+            || old_link.sheet_names != new_link.sheet_names
+    });
+    let mut changed: Vec<DiffOp> = changed
+        .map(|(old_link, new_link)| DiffOp::ExternalLinkChanged {
+            from: old_link.clone(),
+            to: new_link.clone(),
+        })
+        .collect();
+    changed.append(&mut ops);
+    changed
+}
+
+/// Pairs connections by name (case-insensitive), then renamed ones by id. Interns flag and
+/// interval values; streaming callers must run it before `sink.begin`.
+pub(crate) fn diff_connections(
+    old: &Workbook,
+    new: &Workbook,
+    pool: &mut StringPool,
+) -> Vec<DiffOp> {
+    let name = |conn: &DataConnection, pool: &StringPool| pool.resolve(conn.name).to_lowercase();
+
+    let mut unpaired_old: Vec<Option<&DataConnection>> = old.connections.iter().map(Some).collect();
+    let mut pairs: Vec<(&DataConnection, &DataConnection)> = Vec::new();
+    let mut unpaired_new: Vec<&DataConnection> = Vec::new();
+    for new_conn in &new.connections {
+        let new_name = name(new_conn, pool);
+        let slot = unpaired_old
+            .iter_mut()
+            .find(|slot| slot.is_some_and(|old_conn| name(old_conn, pool) == new_name));
+        match slot.and_then(|slot| slot.take()) {
+            Some(old_conn) => pairs.push((old_conn, new_conn)),
+            None => unpaired_new.push(new_conn),
+        }
+    }
+
+    let mut ops = Vec::new();
+    let mut added = Vec::new();
+    for new_conn in unpaired_new {
+        let slot = unpaired_old
+            .iter_mut()
+            .find(|slot| slot.is_some_and(|old_conn| old_conn.id == new_conn.id));
+        match slot.and_then(|slot| slot.take()) {
+            Some(old_conn) => pairs.push((old_conn, new_conn)),
+            None => added.push(DiffOp::ConnectionAdded {
+                name: new_conn.name,
+            }),
+        }
+    }
+
+    for (old_conn, new_conn) in pairs {
+        let mut changes = Vec::new();
+        let mut push = |field, from: Option<StringId>, to: Option<StringId>| {
+            if from != to {
+                changes.push(ConnectionChange { field, from, to });
+            }
+        };
+        push(
+            ConnectionField::Name,
+            Some(old_conn.name),
+            Some(new_conn.name),
+        );
+        push(
+            ConnectionField::Kind,
+            Some(old_conn.kind),
+            Some(new_conn.kind),
+        );
+        push(
+            ConnectionField::Connection,
+            old_conn.connection,
+            new_conn.connection,
+        );
+        push(ConnectionField::Command, old_conn.command, new_conn.command);
+        let flags = [
+            (
+                ConnectionField::RefreshOnLoad,
+                old_conn.refresh_on_load,
+                new_conn.refresh_on_load,
+            ),
+            (
+                ConnectionField::BackgroundRefresh,
+                old_conn.background_refresh,
+                new_conn.background_refresh,
+            ),
+            (
+                ConnectionField::SavePassword,
+                old_conn.save_password,
+                new_conn.save_password,
+            ),
+        ];
+        for (field, from, to) in flags {
+            if from != to {
+                let (from, to) = (pool.intern(&from.to_string()), pool.intern(&to.to_string()));
+                push(field, Some(from), Some(to));
+            }
+        }
+        if old_conn.refresh_interval != new_conn.refresh_interval {
+            let from = pool.intern(&old_conn.refresh_interval.to_string());
+            let to = pool.intern(&new_conn.refresh_interval.to_string());
+            push(ConnectionField::RefreshInterval, Some(from), Some(to));
+        }
+        if !changes.is_empty() {
+            ops.push(DiffOp::ConnectionChanged {
+                name: new_conn.name,
+                changes,
+            });
+        }
+    }
+    ops.extend(added);
+    for old_conn in unpaired_old.into_iter().flatten() {
+        ops.push(DiffOp::ConnectionRemoved {
+            name: old_conn.name,
+        });
+    }
+    ops
+}
+
+fn calc_mode_name(mode: CalcMode) -> &'static str {
+    match mode {
+        CalcMode::Automatic => "automatic",
+        CalcMode::AutomaticNoTable => "automatic_no_table",
+        CalcMode::Manual => "manual",
+    }
+}
+
+/// Workbook settings, protection and document properties. Interns setting values; streaming
+/// callers must run it before `sink.begin`.
+pub(crate) fn diff_workbook_settings(
+    old: &Workbook,
+    new: &Workbook,
+    pool: &mut StringPool,
+) -> Vec<DiffOp> {
+    let (old, new) = (&old.settings, &new.settings);
+    let mut ops = Vec::new();
+    let (from, to) = (&old.calculation, &new.calculation);
+
+    let mut changes = Vec::new();
+    if old.code_name != new.code_name {
+        changes.push(WorkbookSettingChange {
+            setting: WorkbookSetting::CodeName,
+            from: old.code_name,
+            to: new.code_name,
+        });
+    }
+    if from.iterate_delta != to.iterate_delta {
+        changes.push(WorkbookSettingChange {
+            setting: WorkbookSetting::IterateDelta,
+            from: from.iterate_delta,
+            to: to.iterate_delta,
+        });
+    }
+    let values = [
+        (
+            WorkbookSetting::Date1904,
+            old.date1904.to_string(),
+            new.date1904.to_string(),
+        ),
+        (
+            WorkbookSetting::CalcMode,
+            calc_mode_name(from.mode).to_string(),
+            calc_mode_name(to.mode).to_string(),
+        ),
+        (
+            WorkbookSetting::FullCalcOnLoad,
+            from.full_calc_on_load.to_string(),
+            to.full_calc_on_load.to_string(),
+        ),
+        (
+            WorkbookSetting::CalcOnSave,
+            from.calc_on_save.to_string(),
+            to.calc_on_save.to_string(),
+        ),
+        (
+            WorkbookSetting::Iterate,
+            from.iterate.to_string(),
+            to.iterate.to_string(),
+        ),
+        (
+            WorkbookSetting::IterateCount,
+            from.iterate_count.to_string(),
+            to.iterate_count.to_string(),
+        ),
+        (
+            WorkbookSetting::FullPrecision,
+            from.full_precision.to_string(),
+            to.full_precision.to_string(),
+        ),
+    ];
+    for (setting, from, to) in values {
+        if from != to {
+            changes.push(WorkbookSettingChange {
+                setting,
+                from: Some(pool.intern(&from)),
+                to: Some(pool.intern(&to)),
+            });
+        }
+    }
+    if !changes.is_empty() {
+        ops.push(DiffOp::WorkbookSettingsChanged { changes });
+    }
+
+    if old.protection != new.protection {
+        ops.push(DiffOp::WorkbookProtectionChanged {
+            from: old.protection,
+            to: new.protection,
+        });
+    }
+
+    let old_props: HashMap<StringId, StringId> = old.properties.iter().copied().collect();
+    let new_props: HashMap<StringId, StringId> = new.properties.iter().copied().collect();
+    let mut names: Vec<StringId> = old_props.keys().chain(new_props.keys()).copied().collect();
+    names.sort_by(|a, b| pool.resolve(*a).cmp(pool.resolve(*b)));
+    names.dedup();
+    for name in names {
+        let (from, to) = (old_props.get(&name).copied(), new_props.get(&name).copied());
+        if from != to {
+            ops.push(DiffOp::DocumentPropertyChanged { name, from, to });
+        }
+    }
+    ops
+}
+
+/// Unchanged lines shown around each change in a VBA hunk.
+const VBA_HUNK_CONTEXT: usize = 3;
+
+/// Interns hunk text, procedure names and attributes; streaming callers must run it before
+/// `sink.begin`.
 pub(crate) fn diff_vba_modules(
     old: Option<&[VbaModule]>,
     new: Option<&[VbaModule]>,
-    pool: &StringPool,
+    pool: &mut StringPool,
 ) -> Vec<DiffOp> {
     fn key(module: &VbaModule, pool: &StringPool) -> String {
         pool.resolve(module.name).to_lowercase()
@@ -259,10 +1128,10 @@ pub(crate) fn diff_vba_modules(
                 name: old_module.name,
             }),
             (Some(old_module), Some(new_module)) => {
-                if normalize(&old_module.code) != normalize(&new_module.code) {
-                    ops.push(DiffOp::VbaModuleChanged {
-                        name: new_module.name,
-                    });
+                if normalize(&old_module.code) != normalize(&new_module.code)
+                    || old_module.module_type != new_module.module_type
+                {
+                    ops.push(diff_vba_module(old_module, new_module, pool));
                 }
             }
             (None, None) => {}
@@ -271,3 +1140,1055 @@ pub(crate) fn diff_vba_modules(
 
     ops
 }
+
+/// Attribute, procedure and line-level changes between two versions of one module.
+fn diff_vba_module(old: &VbaModule, new: &VbaModule, pool: &mut StringPool) -> DiffOp {
+    let (old_attributes, old_lines) = split_module_source(&old.code);
+    let (new_attributes, new_lines) = split_module_source(&new.code);
+
+    let mut attributes = Vec::new();
+    for (name, new_value) in &new_attributes {
+        let old_value = old_attributes
+            .iter()
+            .find(|(old_name, _)| old_name.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value);
+        if old_value != Some(new_value) {
+            attributes.push(VbaAttributeChange {
+                name: pool.intern(name),
+                from: old_value.map(|value| pool.intern(value)),
+                to: Some(pool.intern(new_value)),
+            });
+        }
+    }
+    for (name, old_value) in &old_attributes {
+        if !new_attributes
+            .iter()
+            .any(|(new_name, _)| new_name.eq_ignore_ascii_case(name))
+        {
+            attributes.push(VbaAttributeChange {
+                name: pool.intern(name),
+                from: Some(pool.intern(old_value)),
+                to: None,
+            });
+        }
+    }
+
+    let old_procs = find_procedures(&old_lines);
+    let new_procs = find_procedures(&new_lines);
+    let same = |a: &VbaProcedure, b: &VbaProcedure| {
+        a.kind == b.kind && a.name.eq_ignore_ascii_case(&b.name)
+    };
+    let mut procedures = Vec::new();
+    for proc in &new_procs {
+        let change = match old_procs.iter().find(|old_proc| same(old_proc, proc)) {
+            None => VbaProcedureChangeKind::Added,
+            Some(old_proc)
+                if old_lines[old_proc.lines.clone()] != new_lines[proc.lines.clone()] =>
+            {
+                VbaProcedureChangeKind::Modified
+            }
+            Some(_) => continue,
+        };
+        procedures.push(VbaProcedureChange {
+            name: pool.intern(&proc.name),
+            kind: proc.kind,
+            change,
+        });
+    }
+    for proc in &old_procs {
+        if !new_procs.iter().any(|new_proc| same(proc, new_proc)) {
+            procedures.push(VbaProcedureChange {
+                name: pool.intern(&proc.name),
+                kind: proc.kind,
+                change: VbaProcedureChangeKind::Removed,
+            });
+        }
+    }
+
+    let old_refs: Vec<&str> = old_lines.iter().map(String::as_str).collect();
+    let new_refs: Vec<&str> = new_lines.iter().map(String::as_str).collect();
+    let edits = text_diff::diff_lines(&old_refs, &new_refs);
+    let containing = |procs: &[VbaProcedure], line: usize| {
+        procs
+            .iter()
+            .find(|proc| proc.lines.contains(&line))
+            .map(|proc| proc.name.clone())
+    };
+    let hunks = text_diff::group_hunks(&edits, VBA_HUNK_CONTEXT)
+        .into_iter()
+        .map(|hunk| {
+            let procedure = hunk.edits.iter().find_map(|edit| match *edit {
+                LineEdit::Equal(..) => None,
+                LineEdit::Delete(line) => Some(containing(&old_procs, line)),
+                LineEdit::Insert(line) => Some(containing(&new_procs, line)),
+            });
+            TextHunk {
+                old_start: hunk.old_start,
+                old_len: hunk.old_len,
+                new_start: hunk.new_start,
+                new_len: hunk.new_len,
+                procedure: procedure.flatten().map(|name| pool.intern(&name)),
+                lines: hunk
+                    .edits
+                    .iter()
+                    .map(|edit| TextLine {
+                        kind: edit.kind(),
+                        text: pool.intern(match *edit {
+                            LineEdit::Equal(_, line) | LineEdit::Insert(line) => &new_lines[line],
+                            LineEdit::Delete(line) => &old_lines[line],
+                        }),
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+
+    DiffOp::VbaModuleChanged {
+        name: new.name,
+        module_type: (old.module_type != new.module_type)
+            .then_some((old.module_type, new.module_type)),
+        attributes,
+        procedures,
+        hunks,
+    }
+}
+
+/// Reference and project property changes. A missing project compares as an empty one, so
+/// references in a newly added project are reported. Interns names and values; streaming callers
+/// must run it before `sink.begin`.
+pub(crate) fn diff_vba_project(
+    old: Option<&VbaProject>,
+    new: Option<&VbaProject>,
+    pool: &mut StringPool,
+) -> Vec<DiffOp> {
+    if old.is_none() && new.is_none() {
+        return Vec::new();
+    }
+    let empty = VbaProject::default();
+    let (old, new) = (old.unwrap_or(&empty), new.unwrap_or(&empty));
+    let mut ops = Vec::new();
+
+    let key = |name: &str, libid: &str| {
+        if name.is_empty() {
+            libid.to_lowercase()
+        } else {
+            name.to_lowercase()
+        }
+    };
+    let old_refs: BTreeMap<String, _> = old
+        .references
+        .iter()
+        .map(|reference| (key(&reference.name, &reference.libid), reference))
+        .collect();
+    let new_refs: BTreeMap<String, _> = new
+        .references
+        .iter()
+        .map(|reference| (key(&reference.name, &reference.libid), reference))
+        .collect();
+    let keys: BTreeSet<&String> = old_refs.keys().chain(new_refs.keys()).collect();
+    for k in keys {
+        match (old_refs.get(k), new_refs.get(k)) {
+            (None, Some(added)) => ops.push(DiffOp::VbaReferenceAdded {
+                name: pool.intern(&added.name),
+                libid: pool.intern(&added.libid),
+            }),
+            (Some(removed), None) => ops.push(DiffOp::VbaReferenceRemoved {
+                name: pool.intern(&removed.name),
+                libid: pool.intern(&removed.libid),
+            }),
+            (Some(before), Some(after)) if !before.libid.eq_ignore_ascii_case(&after.libid) => ops
+                .push(DiffOp::VbaReferenceChanged {
+                    name: pool.intern(&after.name),
+                    from: pool.intern(&before.libid),
+                    to: pool.intern(&after.libid),
+                }),
+            _ => {}
+        }
+    }
+
+    let text = |value: &str| (!value.is_empty()).then(|| value.to_string());
+    let number = |value: u32| (value != 0).then(|| value.to_string());
+    let flag = |value: bool| Some(value.to_string());
+    let properties = [
+        (VbaProjectProperty::Name, text(&old.name), text(&new.name)),
+        (
+            VbaProjectProperty::Description,
+            text(&old.description),
+            text(&new.description),
+        ),
+        (
+            VbaProjectProperty::HelpFile,
+            text(&old.help_file),
+            text(&new.help_file),
+        ),
+        (
+            VbaProjectProperty::HelpContext,
+            number(old.help_context),
+            number(new.help_context),
+        ),
+        (
+            VbaProjectProperty::Constants,
+            text(&old.constants),
+            text(&new.constants),
+        ),
+        (
+            VbaProjectProperty::LockedForViewing,
+            flag(old.locked_for_viewing),
+            flag(new.locked_for_viewing),
+        ),
+        (
+            VbaProjectProperty::Password,
+            flag(old.has_password),
+            flag(new.has_password),
+        ),
+    ];
+    for (property, from, to) in properties {
+        if from != to {
+            ops.push(DiffOp::VbaProjectChanged {
+                property,
+                from: from.map(|value| pool.intern(&value)),
+                to: to.map(|value| pool.intern(&value)),
+            });
+        }
+    }
+
+    ops
+}
+
+/// Pair per-sheet object tables by sheet name, then by workbook sheet id so that a renamed sheet
+/// is still compared. Sheets present on only one side are left out.
+fn pair_sheets<'a, T>(
+    old: &'a [T],
+    new: &'a [T],
+    pool: &StringPool,
+    identity: impl Fn(&T) -> (StringId, Option<u32>),
+) -> Vec<(&'a T, &'a T)> {
+    pair_sheet_indices(old, new, pool, identity)
+        .into_iter()
+        .map(|(old_idx, new_idx)| (&old[old_idx], &new[new_idx]))
+        .collect()
+}
+
+/// [`pair_sheets`] as `(old, new)` index pairs.
+fn pair_sheet_indices<T>(
+    old: &[T],
+    new: &[T],
+    pool: &StringPool,
+    identity: impl Fn(&T) -> (StringId, Option<u32>),
+) -> Vec<(usize, usize)> {
+    let mut old_by_name: HashMap<String, usize> = HashMap::new();
+    let mut old_by_id: HashMap<u32, usize> = HashMap::new();
+    for (idx, item) in old.iter().enumerate() {
+        let (sheet, id) = identity(item);
+        old_by_name.insert(pool.resolve(sheet).to_lowercase(), idx);
+        if let Some(id) = id {
+            old_by_id.insert(id, idx);
+        }
+    }
+
+    let mut pairs: Vec<(usize, usize)> = Vec::new();
+    let mut used: HashSet<usize> = HashSet::new();
+    let mut unmatched: Vec<usize> = Vec::new();
+    for (new_idx, item) in new.iter().enumerate() {
+        let name = pool.resolve(identity(item).0).to_lowercase();
+        match old_by_name.get(&name) {
+            Some(&idx) if used.insert(idx) => pairs.push((idx, new_idx)),
+            _ => unmatched.push(new_idx),
+        }
+    }
+    for new_idx in unmatched {
+        let Some(id) = identity(&new[new_idx]).1 else {
+            continue;
+        };
+        if let Some(&idx) = old_by_id.get(&id)
+            && used.insert(idx)
+        {
+            pairs.push((idx, new_idx));
+        }
+    }
+    pairs
+}
+
+/// Style comparison for sheets present in both workbooks.
+///
+/// Only sheets whose cells were scanned on both sides participate. Old cells are carried to
+/// their new address through `alignment`; cells on removed or added rows and columns are left to
+/// the grid ops.
+pub(crate) fn diff_cell_styles(
+    old: &Workbook,
+    new: &Workbook,
+    pool: &StringPool,
+    alignment: &GridOpAlignment,
+) -> Vec<DiffOp> {
+    fn resolve(workbook: &Workbook, idx: u32) -> CellStyle {
+        workbook
+            .cell_styles
+            .get(idx as usize)
+            .or_else(|| workbook.cell_styles.first())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    let pairs = pair_sheets(
+        &old.sheet_cell_styles,
+        &new.sheet_cell_styles,
+        pool,
+        |styles: &SheetCellStyles| (styles.sheet, styles.workbook_sheet_id),
+    );
+
+    let mut ops = Vec::new();
+    for (old_sheet, new_sheet) in pairs {
+        let grid_sheet = grid_sheet_id(alignment, old_sheet.sheet, new_sheet.sheet);
+
+        // New address -> old style index for every styled old cell that survived.
+        let carried: BTreeMap<(u32, u32), u32> = old_sheet
+            .cells
+            .iter()
+            .filter_map(|(&(row, col), &idx)| {
+                Some((alignment.map_cell(grid_sheet, row, col)?, idx))
+            })
+            .collect();
+        let mut addrs: BTreeSet<(u32, u32)> = carried.keys().copied().collect();
+        addrs.extend(
+            new_sheet
+                .cells
+                .keys()
+                .copied()
+                .filter(|&(row, col)| !alignment.is_added_cell(grid_sheet, row, col)),
+        );
+
+        for (row, col) in addrs {
+            let old_idx = carried.get(&(row, col)).copied().unwrap_or(0);
+            let new_idx = new_sheet.cells.get(&(row, col)).copied().unwrap_or(0);
+            let from = resolve(old, old_idx);
+            let to = resolve(new, new_idx);
+            if from != to {
+                ops.push(DiffOp::CellStyleChanged {
+                    sheet: new_sheet.sheet,
+                    addr: CellAddress::from_indices(row, col),
+                    from,
+                    to,
+                });
+            }
+        }
+    }
+
+    ops
+}
+
+/// Comment comparison for sheets present in both workbooks.
+///
+/// Old comments are carried to their new position through `alignment`, so a comment on a row
+/// that was inserted around, moved, or kept in place is compared with whatever sits at its new
+/// anchor. Comments on removed rows or columns are reported as removed at their old address.
+pub(crate) fn diff_cell_comments(
+    old: &Workbook,
+    new: &Workbook,
+    pool: &StringPool,
+    alignment: &GridOpAlignment,
+) -> Vec<DiffOp> {
+    let pairs = pair_sheets(
+        &old.sheet_comments,
+        &new.sheet_comments,
+        pool,
+        |comments: &SheetComments| (comments.sheet, comments.workbook_sheet_id),
+    );
+
+    let mut ops = Vec::new();
+    for (old_sheet, new_sheet) in pairs {
+        let grid_sheet = grid_sheet_id(alignment, old_sheet.sheet, new_sheet.sheet);
+
+        // New anchor -> old anchor for every old comment whose cell survived.
+        let mut removed: Vec<(u32, u32)> = Vec::new();
+        let mut carried: BTreeMap<(u32, u32), (u32, u32)> = BTreeMap::new();
+        for &(row, col) in old_sheet.comments.keys() {
+            match alignment.map_cell(grid_sheet, row, col) {
+                Some(target) if !carried.contains_key(&target) => {
+                    carried.insert(target, (row, col));
+                }
+                _ => removed.push((row, col)),
+            }
+        }
+
+        for (&(row, col), new_comment) in &new_sheet.comments {
+            let addr = CellAddress::from_indices(row, col);
+            match carried
+                .remove(&(row, col))
+                .map(|old| &old_sheet.comments[&old])
+            {
+                Some(old_comment) if old_comment == new_comment => {}
+                Some(old_comment) => ops.push(DiffOp::CommentChanged {
+                    sheet: new_sheet.sheet,
+                    addr,
+                    from: old_comment.clone(),
+                    to: new_comment.clone(),
+                }),
+                None => ops.push(DiffOp::CommentAdded {
+                    sheet: new_sheet.sheet,
+                    addr,
+                    comment: new_comment.clone(),
+                }),
+            }
+        }
+
+        // Whatever was not claimed by a new comment was deleted.
+        removed.extend(carried.into_values());
+        removed.sort_unstable();
+        for (row, col) in removed {
+            ops.push(DiffOp::CommentRemoved {
+                sheet: new_sheet.sheet,
+                addr: CellAddress::from_indices(row, col),
+                comment: old_sheet.comments[&(row, col)].clone(),
+            });
+        }
+    }
+
+    ops
+}
+
+/// Grid ops name a renamed sheet by its new name and a same-named pair by its old one.
+fn grid_sheet_id(alignment: &GridOpAlignment, old: SheetId, new: SheetId) -> SheetId {
+    if alignment.has_sheet(new) {
+        new
+    } else {
+        old
+    }
+}
+
+enum RuleMatch<'a, T> {
+    Added(&'a T),
+    Removed(&'a T),
+    Changed(&'a T, &'a T),
+}
+
+fn sorted_ranges(ranges: &[CellRange]) -> Vec<CellRange> {
+    let mut ranges = ranges.to_vec();
+    ranges.sort_by_key(|r| (r.start.row, r.start.col, r.end.row, r.end.col));
+    ranges.dedup();
+    ranges
+}
+
+/// Pair old rules with new ones, comparing old ranges once translated into new-grid coordinates.
+///
+/// Each pass pairs whatever the earlier ones left: an equivalent rule over the same ranges, then
+/// a rule with the same identity over any ranges, so a rule whose range was extended is reported
+/// as changed, then a rule of the same type over the same ranges, then any rule over the same
+/// ranges. Old rules whose ranges were entirely removed are reported as removed.
+fn pair_rules<'a, T>(
+    old: &'a [T],
+    new: &'a [T],
+    ranges: impl Fn(&T) -> &[CellRange],
+    translate: impl Fn(CellRange) -> Option<CellRange>,
+    same_kind: impl Fn(&T, &T) -> bool,
+    same_identity: impl Fn(&T, &T) -> bool,
+    equivalent: impl Fn(&T, &T) -> bool,
+) -> Vec<RuleMatch<'a, T>> {
+    let translated: Vec<Vec<CellRange>> = old
+        .iter()
+        .map(|rule| {
+            let mapped: Vec<CellRange> =
+                ranges(rule).iter().filter_map(|r| translate(*r)).collect();
+            sorted_ranges(&mapped)
+        })
+        .collect();
+    let mut used: Vec<bool> = translated.iter().map(Vec::is_empty).collect();
+    let keys: Vec<Vec<CellRange>> = new.iter().map(|rule| sorted_ranges(ranges(rule))).collect();
+
+    let passes: [&dyn Fn(usize, usize) -> bool; 4] = [
+        &|o, n| translated[o] == keys[n] && equivalent(&old[o], &new[n]),
+        &|o, n| same_identity(&old[o], &new[n]),
+        &|o, n| translated[o] == keys[n] && same_kind(&old[o], &new[n]),
+        &|o, n| translated[o] == keys[n],
+    ];
+    let mut picks: Vec<Option<usize>> = vec![None; new.len()];
+    for pass in passes {
+        for (n, pick) in picks.iter_mut().enumerate() {
+            if pick.is_some() {
+                continue;
+            }
+            if let Some(o) = (0..old.len()).find(|&o| !used[o] && pass(o, n)) {
+                used[o] = true;
+                *pick = Some(o);
+            }
+        }
+    }
+
+    let mut matches = Vec::new();
+    for (n, rule) in new.iter().enumerate() {
+        match picks[n] {
+            Some(o) if translated[o] == keys[n] && equivalent(&old[o], rule) => {}
+            Some(o) => matches.push(RuleMatch::Changed(&old[o], rule)),
+            None => matches.push(RuleMatch::Added(rule)),
+        }
+    }
+
+    for (idx, rule) in old.iter().enumerate() {
+        if !used[idx] || translated[idx].is_empty() {
+            matches.push(RuleMatch::Removed(rule));
+        }
+    }
+    matches
+}
+
+/// Rule formulas compare equal when the old formula, with its same-sheet references moved
+/// through the grid alignment, matches the new one. Unparseable formulas compare as text.
+fn rule_formulas_match(
+    pool: &StringPool,
+    remap: &dyn Fn(&FormulaExpr) -> FormulaExpr,
+    old: Option<StringId>,
+    new: Option<StringId>,
+) -> bool {
+    let (Some(old), Some(new)) = (old, new) else {
+        return old.is_none() && new.is_none();
+    };
+    match (
+        parse_formula(pool.resolve(old)),
+        parse_formula(pool.resolve(new)),
+    ) {
+        (Ok(old_expr), Ok(new_expr)) => remap(&old_expr).canonicalize() == new_expr.canonicalize(),
+        _ => old == new,
+    }
+}
+
+/// Data validation and conditional formatting comparison for sheets present in both workbooks.
+///
+/// Old rules are translated into new-grid coordinates before comparing: their ranges follow
+/// inserted, removed and moved rows and columns, and so do same-sheet references in their
+/// formulas. Inserting a row above a validated column therefore reports nothing.
+pub(crate) fn diff_sheet_rules(
+    old: &Workbook,
+    new: &Workbook,
+    pool: &StringPool,
+    alignment: &GridOpAlignment,
+) -> Vec<DiffOp> {
+    let pairs = pair_sheets(
+        &old.sheet_rules,
+        &new.sheet_rules,
+        pool,
+        |rules: &SheetRules| (rules.sheet, rules.workbook_sheet_id),
+    );
+
+    let mut ops = Vec::new();
+    for (old_sheet, new_sheet) in pairs {
+        let grid_sheet = grid_sheet_id(alignment, old_sheet.sheet, new_sheet.sheet);
+        let sheet = new_sheet.sheet;
+        let map_row = |row: u32| alignment.map_row(grid_sheet, row);
+        let map_col = |col: u32| alignment.map_col(grid_sheet, col);
+        let remap = |expr: &FormulaExpr| expr.remapped(&map_row, &map_col);
+        let translate = |range: CellRange| alignment.map_range(grid_sheet, range);
+        let formulas_match = |old: Option<StringId>, new: Option<StringId>| {
+            rule_formulas_match(pool, &remap, old, new)
+        };
+        let formulas_equal = |old: &[StringId], new: &[StringId]| {
+            old.len() == new.len()
+                && old
+                    .iter()
+                    .zip(new)
+                    .all(|(x, y)| formulas_match(Some(*x), Some(*y)))
+        };
+
+        let validations = pair_rules(
+            &old_sheet.data_validations,
+            &new_sheet.data_validations,
+            |rule: &DataValidationRule| &rule.ranges,
+            translate,
+            |a, b| a.kind == b.kind,
+            |a, b| {
+                a.kind == b.kind
+                    && formulas_match(a.formula1, b.formula1)
+                    && formulas_match(a.formula2, b.formula2)
+            },
+            |a, b| {
+                a.kind == b.kind
+                    && a.operator == b.operator
+                    && a.allow_blank == b.allow_blank
+                    && formulas_match(a.formula1, b.formula1)
+                    && formulas_match(a.formula2, b.formula2)
+            },
+        );
+        for change in validations {
+            ops.push(match change {
+                RuleMatch::Added(rule) => DiffOp::DataValidationAdded {
+                    sheet,
+                    rule: rule.clone(),
+                },
+                RuleMatch::Removed(rule) => DiffOp::DataValidationRemoved {
+                    sheet,
+                    rule: rule.clone(),
+                },
+                RuleMatch::Changed(from, to) => DiffOp::DataValidationChanged {
+                    sheet,
+                    from: from.clone(),
+                    to: to.clone(),
+                },
+            });
+        }
+
+        let formats = pair_rules(
+            &old_sheet.conditional_formats,
+            &new_sheet.conditional_formats,
+            |rule: &ConditionalFormatRule| &rule.ranges,
+            translate,
+            |a, b| a.kind == b.kind,
+            |a, b| {
+                a.kind == b.kind
+                    && a.priority == b.priority
+                    && formulas_equal(&a.formulas, &b.formulas)
+            },
+            |a, b| {
+                a.kind == b.kind
+                    && a.operator == b.operator
+                    && a.priority == b.priority
+                    && a.stop_if_true == b.stop_if_true
+                    && formulas_equal(&a.formulas, &b.formulas)
+            },
+        );
+        for change in formats {
+            ops.push(match change {
+                RuleMatch::Added(rule) => DiffOp::ConditionalFormatAdded {
+                    sheet,
+                    rule: rule.clone(),
+                },
+                RuleMatch::Removed(rule) => DiffOp::ConditionalFormatRemoved {
+                    sheet,
+                    rule: rule.clone(),
+                },
+                RuleMatch::Changed(from, to) => DiffOp::ConditionalFormatChanged {
+                    sheet,
+                    from: from.clone(),
+                    to: to.clone(),
+                },
+            });
+        }
+    }
+
+    ops
+}
+
+/// Layout changes of one axis: `(new index, old layout, new layout)` for every index whose
+/// layout differs once old indices are moved through `map`. Rows or columns that were removed
+/// are skipped; added ones compare against the default layout.
+fn diff_axis_layout(
+    old: &BTreeMap<u32, AxisLayout>,
+    new: &BTreeMap<u32, AxisLayout>,
+    map: impl Fn(u32) -> Option<u32>,
+) -> Vec<(u32, AxisLayout, AxisLayout)> {
+    let moved: BTreeMap<u32, AxisLayout> = old
+        .iter()
+        .filter_map(|(&idx, &layout)| Some((map(idx)?, layout)))
+        .collect();
+    let keys: BTreeSet<u32> = moved.keys().chain(new.keys()).copied().collect();
+    keys.into_iter()
+        .filter_map(|idx| {
+            let from = moved.get(&idx).copied().unwrap_or_default();
+            let to = new.get(&idx).copied().unwrap_or_default();
+            (from != to).then_some((idx, from, to))
+        })
+        .collect()
+}
+
+/// Number of leading rows or columns still frozen after the old ones moved through `map`.
+fn translate_frozen(count: u32, map: impl Fn(u32) -> Option<u32>) -> u32 {
+    (0..count).rev().find_map(map).map_or(0, |idx| idx + 1)
+}
+
+/// Sheet layout comparison for sheets present in both workbooks.
+///
+/// Row and column properties, merges, and frozen panes are compared after moving the old
+/// positions through the grid alignment, so an inserted row does not shift every hidden row.
+pub(crate) fn diff_sheet_layout(
+    old: &Workbook,
+    new: &Workbook,
+    pool: &StringPool,
+    alignment: &GridOpAlignment,
+) -> Vec<DiffOp> {
+    let pairs = pair_sheets(&old.sheets, &new.sheets, pool, |sheet: &Sheet| {
+        (sheet.name, sheet.workbook_sheet_id)
+    });
+
+    let mut ops = Vec::new();
+    for (old_sheet, new_sheet) in pairs {
+        let (from, to) = (&old_sheet.layout, &new_sheet.layout);
+        let grid_sheet = grid_sheet_id(alignment, old_sheet.name, new_sheet.name);
+        let sheet = new_sheet.name;
+        let map_row = |row: u32| alignment.map_row(grid_sheet, row);
+        let map_col = |col: u32| alignment.map_col(grid_sheet, col);
+
+        if from.visibility != to.visibility {
+            ops.push(DiffOp::SheetVisibilityChanged {
+                sheet,
+                from: from.visibility,
+                to: to.visibility,
+            });
+        }
+        if from.tab_color != to.tab_color {
+            ops.push(DiffOp::SheetTabColorChanged {
+                sheet,
+                from: from.tab_color,
+                to: to.tab_color,
+            });
+        }
+        if from.protection != to.protection {
+            ops.push(DiffOp::SheetProtectionChanged {
+                sheet,
+                from: from.protection.clone(),
+                to: to.protection.clone(),
+            });
+        }
+        let moved_freeze = from
+            .freeze
+            .map(|pane| FreezePane {
+                rows: translate_frozen(pane.rows, map_row),
+                cols: translate_frozen(pane.cols, map_col),
+            })
+            .filter(|pane| pane.rows > 0 || pane.cols > 0);
+        if moved_freeze != to.freeze {
+            ops.push(DiffOp::FreezePaneChanged {
+                sheet,
+                from: from.freeze,
+                to: to.freeze,
+            });
+        }
+
+        let new_merges: HashSet<CellRange> = to.merges.iter().copied().collect();
+        let mut kept: HashSet<CellRange> = HashSet::new();
+        for range in &from.merges {
+            match alignment.map_range(grid_sheet, *range) {
+                Some(moved) if new_merges.contains(&moved) => {
+                    kept.insert(moved);
+                }
+                _ => ops.push(DiffOp::MergedRangeRemoved {
+                    sheet,
+                    range: *range,
+                }),
+            }
+        }
+        for range in &to.merges {
+            if !kept.contains(range) {
+                ops.push(DiffOp::MergedRangeAdded {
+                    sheet,
+                    range: *range,
+                });
+            }
+        }
+
+        // Array formulas pair by anchor cell; a resized spill keeps its anchor.
+        let new_arrays: HashMap<CellAddress, CellRange> = to
+            .array_ranges
+            .iter()
+            .map(|range| (range.start, *range))
+            .collect();
+        let mut paired: HashSet<CellAddress> = HashSet::new();
+        for range in &from.array_ranges {
+            let moved = alignment
+                .map_cell(grid_sheet, range.start.row, range.start.col)
+                .and_then(|(row, col)| new_arrays.get(&CellAddress::from_indices(row, col)));
+            match moved {
+                Some(now) => {
+                    paired.insert(now.start);
+                    if alignment.map_range(grid_sheet, *range) != Some(*now) {
+                        ops.push(DiffOp::SpillRangeChanged {
+                            sheet,
+                            addr: now.start,
+                            from: Some(*range),
+                            to: Some(*now),
+                        });
+                    }
+                }
+                None => ops.push(DiffOp::SpillRangeChanged {
+                    sheet,
+                    addr: range.start,
+                    from: Some(*range),
+                    to: None,
+                }),
+            }
+        }
+        for range in &to.array_ranges {
+            if !paired.contains(&range.start) {
+                ops.push(DiffOp::SpillRangeChanged {
+                    sheet,
+                    addr: range.start,
+                    from: None,
+                    to: Some(*range),
+                });
+            }
+        }
+
+        for (row_idx, was, now) in diff_axis_layout(&from.rows, &to.rows, map_row) {
+            if was.hidden != now.hidden {
+                ops.push(DiffOp::RowVisibilityChanged {
+                    sheet,
+                    row_idx,
+                    hidden: now.hidden,
+                });
+            }
+            if was.outline_level != now.outline_level {
+                ops.push(DiffOp::RowOutlineChanged {
+                    sheet,
+                    row_idx,
+                    from: was.outline_level,
+                    to: now.outline_level,
+                });
+            }
+            if was.size != now.size {
+                ops.push(DiffOp::RowHeightChanged {
+                    sheet,
+                    row_idx,
+                    from: was.size,
+                    to: now.size,
+                });
+            }
+        }
+        for (col_idx, was, now) in diff_axis_layout(&from.cols, &to.cols, map_col) {
+            if was.hidden != now.hidden {
+                ops.push(DiffOp::ColumnVisibilityChanged {
+                    sheet,
+                    col_idx,
+                    hidden: now.hidden,
+                });
+            }
+            if was.outline_level != now.outline_level {
+                ops.push(DiffOp::ColumnOutlineChanged {
+                    sheet,
+                    col_idx,
+                    from: was.outline_level,
+                    to: now.outline_level,
+                });
+            }
+            if was.size != now.size {
+                ops.push(DiffOp::ColumnWidthChanged {
+                    sheet,
+                    col_idx,
+                    from: was.size,
+                    to: now.size,
+                });
+            }
+        }
+    }
+
+    ops
+}
+
+/// Pair tables across the workbooks by display name, then by table id for renames.
+pub(crate) fn pair_tables<'a>(
+    old: &'a [ExcelTable],
+    new: &'a [ExcelTable],
+    pool: &StringPool,
+) -> Vec<(&'a ExcelTable, &'a ExcelTable)> {
+    pair_sheets(old, new, pool, table_identity)
+}
+
+fn table_identity(table: &ExcelTable) -> (StringId, Option<u32>) {
+    (table.name, Some(table.id))
+}
+
+/// Table adds, removals, renames, resizes and column renames.
+///
+/// An old table's range is translated through `alignment` before comparing, so rows inserted
+/// inside a table grow it without reporting a resize.
+pub(crate) fn diff_excel_tables(
+    old: &Workbook,
+    new: &Workbook,
+    pool: &StringPool,
+    alignment: &GridOpAlignment,
+) -> Vec<DiffOp> {
+    let mut paired_old = vec![false; old.tables.len()];
+    let mut paired_new: Vec<Option<usize>> = vec![None; new.tables.len()];
+    for (old_idx, new_idx) in pair_sheet_indices(&old.tables, &new.tables, pool, table_identity) {
+        paired_old[old_idx] = true;
+        paired_new[new_idx] = Some(old_idx);
+    }
+
+    let mut ops = Vec::new();
+    for (table, paired) in old.tables.iter().zip(&paired_old) {
+        if !paired {
+            ops.push(DiffOp::ExcelTableRemoved {
+                sheet: table.sheet,
+                name: table.name,
+                range: table.range,
+            });
+        }
+    }
+
+    for (table, prior) in new.tables.iter().zip(&paired_new) {
+        let Some(prior) = prior.map(|idx| &old.tables[idx]) else {
+            ops.push(DiffOp::ExcelTableAdded {
+                sheet: table.sheet,
+                name: table.name,
+                range: table.range,
+            });
+            continue;
+        };
+        let sheet = table.sheet;
+        if prior.name != table.name {
+            ops.push(DiffOp::ExcelTableRenamed {
+                sheet,
+                from: prior.name,
+                to: table.name,
+            });
+        }
+        let grid_sheet = grid_sheet_id(alignment, prior.sheet, table.sheet);
+        if alignment.map_range(grid_sheet, prior.range) != Some(table.range) {
+            ops.push(DiffOp::ExcelTableResized {
+                sheet,
+                name: table.name,
+                from: prior.range,
+                to: table.range,
+            });
+        }
+        for column in &table.columns {
+            if let Some(before) = prior.columns.iter().find(|c| c.id == column.id)
+                && before.name != column.name
+            {
+                ops.push(DiffOp::ExcelTableColumnRenamed {
+                    sheet,
+                    table: table.name,
+                    from: before.name,
+                    to: column.name,
+                });
+            }
+        }
+    }
+
+    ops
+}
+
+/// Pair drawing objects by name (case-insensitive), then by drawing id so a renamed shape is
+/// still compared.
+fn pair_drawing_objects<'a>(
+    old: &'a [DrawingObject],
+    new: &'a [DrawingObject],
+    pool: &StringPool,
+) -> Vec<(Option<&'a DrawingObject>, Option<&'a DrawingObject>)> {
+    let mut old_by_name: HashMap<String, Vec<usize>> = HashMap::new();
+    for (idx, object) in old.iter().enumerate() {
+        old_by_name
+            .entry(pool.resolve(object.name).to_lowercase())
+            .or_default()
+            .push(idx);
+    }
+
+    let mut used: HashSet<usize> = HashSet::new();
+    let mut matched: Vec<Option<usize>> = vec![None; new.len()];
+    for (slot, object) in matched.iter_mut().zip(new) {
+        let name = pool.resolve(object.name).to_lowercase();
+        if let Some(candidates) = old_by_name.get(&name)
+            && let Some(&idx) = candidates.iter().find(|idx| !used.contains(idx))
+        {
+            used.insert(idx);
+            *slot = Some(idx);
+        }
+    }
+    for (slot, object) in matched.iter_mut().zip(new) {
+        if slot.is_some() {
+            continue;
+        }
+        if let Some(idx) =
+            (0..old.len()).find(|idx| !used.contains(idx) && old[*idx].id == object.id)
+        {
+            used.insert(idx);
+            *slot = Some(idx);
+        }
+    }
+
+    let mut pairs: Vec<(Option<&DrawingObject>, Option<&DrawingObject>)> = old
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| !used.contains(idx))
+        .map(|(_, object)| (Some(object), None))
+        .collect();
+    pairs.extend(
+        matched
+            .into_iter()
+            .zip(new)
+            .map(|(idx, object)| (idx.map(|idx| &old[idx]), Some(object))),
+    );
+    pairs
+}
+
+/// Where an old anchor ends up after row and column alignment, or `None` if its top-left cell
+/// was removed. Offsets within the cells are kept.
+fn map_drawing_anchor(
+    alignment: &GridOpAlignment,
+    sheet: SheetId,
+    anchor: DrawingAnchor,
+) -> Option<DrawingAnchor> {
+    let with_cell =
+        |marker: DrawingMarker, (row, col): (u32, u32)| DrawingMarker { row, col, ..marker };
+    let Some(to) = anchor.to else {
+        let from = alignment.map_cell(sheet, anchor.from.row, anchor.from.col)?;
+        return Some(DrawingAnchor {
+            from: with_cell(anchor.from, from),
+            to: None,
+        });
+    };
+    let range = CellRange::new(
+        CellAddress::from_indices(anchor.from.row, anchor.from.col),
+        CellAddress::from_indices(to.row.max(anchor.from.row), to.col.max(anchor.from.col)),
+    );
+    let mapped = alignment.map_range(sheet, range)?;
+    Some(DrawingAnchor {
+        from: with_cell(anchor.from, (mapped.start.row, mapped.start.col)),
+        to: Some(with_cell(to, (mapped.end.row, mapped.end.col))),
+    })
+}
+
+/// Shape, picture, text box and form control adds, removals, moves and content changes.
+///
+/// Old anchors are translated through `alignment` before comparing, so inserting rows above a
+/// picture does not report it as moved.
+pub(crate) fn diff_drawing_objects(
+    old: &Workbook,
+    new: &Workbook,
+    pool: &StringPool,
+    alignment: &GridOpAlignment,
+) -> Vec<DiffOp> {
+    let pairs = pair_sheets(
+        &old.sheet_drawings,
+        &new.sheet_drawings,
+        pool,
+        |drawings: &SheetDrawings| (drawings.sheet, drawings.workbook_sheet_id),
+    );
+
+    let mut ops = Vec::new();
+    for (old_sheet, new_sheet) in pairs {
+        let grid_sheet = grid_sheet_id(alignment, old_sheet.sheet, new_sheet.sheet);
+        let sheet = new_sheet.sheet;
+        for pair in pair_drawing_objects(&old_sheet.objects, &new_sheet.objects, pool) {
+            match pair {
+                (Some(object), None) => ops.push(DiffOp::DrawingObjectRemoved {
+                    sheet,
+                    object: object.clone(),
+                }),
+                (None, Some(object)) => ops.push(DiffOp::DrawingObjectAdded {
+                    sheet,
+                    object: object.clone(),
+                }),
+                (Some(before), Some(after)) => {
+                    if map_drawing_anchor(alignment, grid_sheet, before.anchor)
+                        != Some(after.anchor)
+                    {
+                        ops.push(DiffOp::DrawingObjectMoved {
+                            sheet,
+                            name: after.name,
+                            from: before.anchor,
+                            to: after.anchor,
+                        });
+                    }
+                    let content =
+                        |o: &DrawingObject| (o.kind, o.name, o.text, o.macro_name, o.image_hash);
+                    if content(before) != content(after) {
+                        ops.push(DiffOp::DrawingObjectChanged {
+                            sheet,
+                            from: before.clone(),
+                            to: after.clone(),
+                        });
+                    }
+                }
+                (None, None) => {}
+            }
+        }
+    }
+
+    ops
+}
@@ -0,0 +1,415 @@
+//! Old-to-new cell correspondence reconstructed from emitted grid ops.
+//!
+//! Objects anchored to cells (comments, for example) must follow the rows and columns they sit
+//! on. Rather than exposing the engine's internal alignment, this module replays the structural
+//! ops it emitted: `RowRemoved`/`ColumnRemoved` indices are old positions, `RowAdded`/
+//! `ColumnAdded` indices are new positions, and block moves map a source range onto a
+//! destination range. Every other old index maps, in order, onto the remaining new indices.
+
+use crate::diff::{DiffOp, SheetId};
+use crate::sink::DiffSink;
+use crate::string_pool::StringPool;
+use crate::workbook::{CellAddress, CellRange};
+use std::collections::{BTreeSet, HashMap};
+
+#[derive(Debug, Default)]
+struct AxisOps {
+    /// Old indices that are removed or moved away, sorted once recording finishes.
+    vacated: Vec<u32>,
+    /// New indices that are added or moved into, sorted once recording finishes.
+    filled: Vec<u32>,
+    /// `filled[i] - i` for each filled index: how many unfilled new indices precede it.
+    filled_gaps: Vec<u32>,
+    /// `(src_start, count, dst_start)`, sorted by source once recording finishes.
+    moves: Vec<(u32, u32, u32)>,
+    removed: BTreeSet<u32>,
+    added: BTreeSet<u32>,
+    /// Old-to-new pairs matched by key, which take precedence over positions.
+    paired: HashMap<u32, u32>,
+}
+
+impl AxisOps {
+    fn remove(&mut self, idx: u32) {
+        self.vacated.push(idx);
+        self.removed.insert(idx);
+    }
+
+    fn add(&mut self, idx: u32) {
+        self.filled.push(idx);
+        self.added.insert(idx);
+    }
+
+    fn record_move(&mut self, src: u32, count: u32, dst: u32) {
+        self.vacated.extend(src..src.saturating_add(count));
+        self.filled.extend(dst..dst.saturating_add(count));
+        self.moves.push((src, count, dst));
+    }
+
+    /// Sort the recorded indices so [`AxisOps::map`] can binary-search them.
+    fn finish(&mut self) {
+        self.vacated.sort_unstable();
+        self.vacated.dedup();
+        self.filled.sort_unstable();
+        self.filled.dedup();
+        self.filled_gaps = self
+            .filled
+            .iter()
+            .enumerate()
+            .map(|(i, &idx)| idx - i as u32)
+            .collect();
+        self.moves.sort_unstable();
+    }
+
+    fn map(&self, idx: u32) -> Option<u32> {
+        if self.removed.contains(&idx) {
+            return None;
+        }
+        if let Some(&paired) = self.paired.get(&idx) {
+            return Some(paired);
+        }
+        let after = self.moves.partition_point(|&(src, _, _)| src <= idx);
+        if let Some(&(src, count, dst)) = after.checked_sub(1).map(|i| &self.moves[i])
+            && idx - src < count
+        {
+            return Some(dst + (idx - src));
+        }
+
+        // The surviving old index's position among survivors, then the new index with that
+        // many unfilled indices before it.
+        let rank = idx - self.vacated.partition_point(|&v| v < idx) as u32;
+        Some(rank + self.filled_gaps.partition_point(|&gap| gap <= rank) as u32)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RectMove {
+    src_row: u32,
+    src_col: u32,
+    rows: u32,
+    cols: u32,
+    dst_row: u32,
+    dst_col: u32,
+}
+
+#[derive(Debug, Default)]
+struct SheetAxes {
+    rows: AxisOps,
+    cols: AxisOps,
+    rects: Vec<RectMove>,
+}
+
+/// Per-sheet row and column correspondence between the old and new grids.
+#[derive(Debug, Default)]
+pub(crate) struct GridOpAlignment {
+    sheets: HashMap<SheetId, SheetAxes>,
+}
+
+impl GridOpAlignment {
+    pub(crate) fn from_ops<'a>(ops: impl IntoIterator<Item = &'a DiffOp>) -> Self {
+        let mut alignment = Self::default();
+        for op in ops {
+            alignment.record(op);
+        }
+        alignment.finish();
+        alignment
+    }
+
+    fn record(&mut self, op: &DiffOp) {
+        match op {
+            DiffOp::RowAdded { sheet, row_idx, .. } => self.sheet(*sheet).rows.add(*row_idx),
+            DiffOp::RowRemoved { sheet, row_idx, .. } => self.sheet(*sheet).rows.remove(*row_idx),
+            DiffOp::ColumnAdded { sheet, col_idx, .. } => self.sheet(*sheet).cols.add(*col_idx),
+            DiffOp::ColumnRemoved { sheet, col_idx, .. } => {
+                self.sheet(*sheet).cols.remove(*col_idx)
+            }
+            DiffOp::BlockMovedRows {
+                sheet,
+                src_start_row,
+                row_count,
+                dst_start_row,
+                ..
+            } => self
+                .sheet(*sheet)
+                .rows
+                .record_move(*src_start_row, *row_count, *dst_start_row),
+            DiffOp::BlockMovedColumns {
+                sheet,
+                src_start_col,
+                col_count,
+                dst_start_col,
+                ..
+            } => self
+                .sheet(*sheet)
+                .cols
+                .record_move(*src_start_col, *col_count, *dst_start_col),
+            DiffOp::BlockMovedRect {
+                sheet,
+                src_start_row,
+                src_row_count,
+                src_start_col,
+                src_col_count,
+                dst_start_row,
+                dst_start_col,
+                ..
+            } => self.sheet(*sheet).rects.push(RectMove {
+                src_row: *src_start_row,
+                src_col: *src_start_col,
+                rows: *src_row_count,
+                cols: *src_col_count,
+                dst_row: *dst_start_row,
+                dst_col: *dst_start_col,
+            }),
+            _ => {}
+        }
+    }
+
+    fn finish(&mut self) {
+        for axes in self.sheets.values_mut() {
+            axes.rows.finish();
+            axes.cols.finish();
+        }
+    }
+
+    fn sheet(&mut self, sheet: SheetId) -> &mut SheetAxes {
+        self.sheets.entry(sheet).or_default()
+    }
+
+    /// Record rows that database mode matched by key; they map onto their partner rather than
+    /// by position.
+    pub(crate) fn record_row_pairs(&mut self, sheet: SheetId, pairs: &[(u32, u32)]) {
+        self.sheet(sheet).rows.paired.extend(pairs.iter().copied());
+    }
+
+    /// Whether any structural op was recorded for `sheet`.
+    pub(crate) fn has_sheet(&self, sheet: SheetId) -> bool {
+        self.sheets.contains_key(&sheet)
+    }
+
+    /// Where the old cell `(row, col)` of `sheet` ended up, or `None` if its row or column was
+    /// removed. Sheets without structural ops map every cell onto itself.
+    pub(crate) fn map_cell(&self, sheet: SheetId, row: u32, col: u32) -> Option<(u32, u32)> {
+        let Some(axes) = self.sheets.get(&sheet) else {
+            return Some((row, col));
+        };
+        if let Some(rect) = axes.rects.iter().find(|rect| {
+            row >= rect.src_row
+                && row - rect.src_row < rect.rows
+                && col >= rect.src_col
+                && col - rect.src_col < rect.cols
+        }) {
+            return Some((
+                rect.dst_row + (row - rect.src_row),
+                rect.dst_col + (col - rect.src_col),
+            ));
+        }
+        Some((axes.rows.map(row)?, axes.cols.map(col)?))
+    }
+
+    /// Whether the new cell `(row, col)` of `sheet` sits on an added row or column, so no old
+    /// cell ended up there.
+    pub(crate) fn is_added_cell(&self, sheet: SheetId, row: u32, col: u32) -> bool {
+        self.sheets
+            .get(&sheet)
+            .is_some_and(|axes| axes.rows.added.contains(&row) || axes.cols.added.contains(&col))
+    }
+
+    /// Where old row `row` of `sheet` ended up, ignoring rectangular block moves.
+    pub(crate) fn map_row(&self, sheet: SheetId, row: u32) -> Option<u32> {
+        match self.sheets.get(&sheet) {
+            Some(axes) => axes.rows.map(row),
+            None => Some(row),
+        }
+    }
+
+    /// Where old column `col` of `sheet` ended up, ignoring rectangular block moves.
+    pub(crate) fn map_col(&self, sheet: SheetId, col: u32) -> Option<u32> {
+        match self.sheets.get(&sheet) {
+            Some(axes) => axes.cols.map(col),
+            None => Some(col),
+        }
+    }
+
+    /// Translate an old range the way Excel adjusts a rule's `sqref`: rows and columns inserted
+    /// inside the range widen it, removed ones shrink it, and `None` means nothing survived.
+    pub(crate) fn map_range(&self, sheet: SheetId, range: CellRange) -> Option<CellRange> {
+        let Some(axes) = self.sheets.get(&sheet) else {
+            return Some(range);
+        };
+        let (start_row, end_row) = map_span(&axes.rows, range.start.row, range.end.row, MAX_ROW)?;
+        let (start_col, end_col) = map_span(&axes.cols, range.start.col, range.end.col, MAX_COL)?;
+        Some(CellRange::new(
+            CellAddress::from_indices(start_row, start_col),
+            CellAddress::from_indices(end_row, end_col),
+        ))
+    }
+}
+
+const MAX_ROW: u32 = 1_048_575;
+const MAX_COL: u32 = 16_383;
+
+/// Map the first and last surviving indices of `start..=end`, clamped to the sheet bounds.
+fn map_span(axis: &AxisOps, start: u32, end: u32, max: u32) -> Option<(u32, u32)> {
+    let (start, end) = (start.min(end), start.max(end));
+    let first = (start..=end).find_map(|idx| axis.map(idx))?;
+    let last = (start..=end).rev().find_map(|idx| axis.map(idx))?;
+    Some((first.min(last).min(max), first.max(last).min(max)))
+}
+
+/// Forwards ops to `inner` while recording the structural ones into a [`GridOpAlignment`].
+pub(crate) struct AlignmentRecordingSink<'a, S: DiffSink> {
+    inner: &'a mut S,
+    alignment: GridOpAlignment,
+}
+
+impl<'a, S: DiffSink> AlignmentRecordingSink<'a, S> {
+    pub(crate) fn new(inner: &'a mut S) -> Self {
+        Self {
+            inner,
+            alignment: GridOpAlignment::default(),
+        }
+    }
+
+    pub(crate) fn into_alignment(mut self) -> GridOpAlignment {
+        self.alignment.finish();
+        self.alignment
+    }
+}
+
+impl<S: DiffSink> DiffSink for AlignmentRecordingSink<'_, S> {
+    fn begin(&mut self, pool: &StringPool) -> Result<(), crate::diff::DiffError> {
+        self.inner.begin(pool)
+    }
+
+    fn emit(&mut self, op: DiffOp) -> Result<(), crate::diff::DiffError> {
+        self.alignment.record(&op);
+        self.inner.emit(op)
+    }
+
+    fn finish(&mut self) -> Result<(), crate::diff::DiffError> {
+        self.inner.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_shift_around_inserts_removals_and_moves() {
+        let mut pool = StringPool::new();
+        let sheet = pool.intern("Sheet1");
+        let ops = [
+            DiffOp::RowRemoved {
+                sheet,
+                row_idx: 1,
+                row_signature: None,
+            },
+            DiffOp::RowAdded {
+                sheet,
+                row_idx: 0,
+                row_signature: None,
+            },
+            DiffOp::BlockMovedRows {
+                sheet,
+                src_start_row: 5,
+                row_count: 2,
+                dst_start_row: 2,
+                block_hash: None,
+            },
+        ];
+        let alignment = GridOpAlignment::from_ops(&ops);
+
+        // Old rows 0,2,3,4,7 survive in place; new rows 0 (added) and 2,3 (moved) are taken.
+        assert_eq!(alignment.map_cell(sheet, 0, 0), Some((1, 0)));
+        assert_eq!(alignment.map_cell(sheet, 1, 0), None);
+        assert_eq!(alignment.map_cell(sheet, 2, 3), Some((4, 3)));
+        assert_eq!(alignment.map_cell(sheet, 4, 0), Some((6, 0)));
+        assert_eq!(alignment.map_cell(sheet, 5, 0), Some((2, 0)));
+        assert_eq!(alignment.map_cell(sheet, 6, 1), Some((3, 1)));
+        assert_eq!(alignment.map_cell(sheet, 7, 0), Some((7, 0)));
+
+        let other = pool.intern("Other");
+        assert_eq!(alignment.map_cell(other, 9, 9), Some((9, 9)));
+    }
+
+    #[test]
+    fn positional_mapping_matches_a_linear_replay() {
+        let mut pool = StringPool::new();
+        let sheet = pool.intern("Sheet1");
+        let mut seed = 0x2545_f491_u32;
+        let mut next = |modulo: u32| {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            seed % modulo
+        };
+        let ops: Vec<DiffOp> = (0..400)
+            .map(|_| match next(2) {
+                0 => DiffOp::RowRemoved {
+                    sheet,
+                    row_idx: next(600),
+                    row_signature: None,
+                },
+                _ => DiffOp::RowAdded {
+                    sheet,
+                    row_idx: next(600),
+                    row_signature: None,
+                },
+            })
+            .collect();
+        let alignment = GridOpAlignment::from_ops(&ops);
+
+        let removed: BTreeSet<u32> = ops
+            .iter()
+            .filter_map(|op| match op {
+                DiffOp::RowRemoved { row_idx, .. } => Some(*row_idx),
+                _ => None,
+            })
+            .collect();
+        let added: BTreeSet<u32> = ops
+            .iter()
+            .filter_map(|op| match op {
+                DiffOp::RowAdded { row_idx, .. } => Some(*row_idx),
+                _ => None,
+            })
+            .collect();
+        let mut free_new = (0..).filter(|idx| !added.contains(idx));
+        for old in 0..600 {
+            let expected = if removed.contains(&old) {
+                None
+            } else {
+                free_new.next()
+            };
+            assert_eq!(alignment.map_row(sheet, old), expected, "old row {old}");
+        }
+    }
+
+    #[test]
+    fn ranges_widen_over_inserts_and_shrink_over_removals() {
+        let mut pool = StringPool::new();
+        let sheet = pool.intern("Sheet1");
+        let ops = [
+            DiffOp::RowAdded {
+                sheet,
+                row_idx: 3,
+                row_signature: None,
+            },
+            DiffOp::ColumnRemoved {
+                sheet,
+                col_idx: 0,
+                col_signature: None,
+            },
+        ];
+        let alignment = GridOpAlignment::from_ops(&ops);
+        let map = |raw: &str| {
+            alignment
+                .map_range(sheet, raw.parse().expect("range"))
+                .map(|range| range.to_a1())
+        };
+
+        assert_eq!(map("B2:C8").as_deref(), Some("A2:B9"));
+        assert_eq!(map("B1:B2").as_deref(), Some("A1:A2"));
+        assert_eq!(map("A1:B3").as_deref(), Some("A1:A3"));
+        assert_eq!(map("A5"), None);
+    }
+}
@@ -6,7 +6,7 @@ use crate::diff::DiffReport;
 #[cfg(all(feature = "excel-open-xml", feature = "std-fs"))]
 use crate::diff::DiffSummary;
 #[cfg(all(feature = "excel-open-xml", feature = "std-fs"))]
-use crate::excel_open_xml::{open_data_mashup, open_vba_modules, open_workbook, PackageError};
+use crate::excel_open_xml::{open_data_mashup, open_vba, open_workbook, PackageError};
 #[allow(unused_imports)]
 use crate::session::DiffSession;
 #[cfg(all(feature = "excel-open-xml", feature = "std-fs"))]
@@ -60,8 +60,8 @@ pub fn diff_workbooks(
         .map(|raw| build_data_mashup(&raw))
         .transpose()?;
 
-    let vba_a = open_vba_modules(path_a, session.strings_mut())?;
-    let vba_b = open_vba_modules(path_b, session.strings_mut())?;
+    let (vba_a, project_a) = open_vba(path_a, session.strings_mut())?.unzip();
+    let (vba_b, project_b) = open_vba(path_b, session.strings_mut())?.unzip();
 
     let mut sink = VecSink::new();
     let grid_result = crate::engine::try_diff_workbooks_streaming(
@@ -90,12 +90,17 @@ pub fn diff_workbooks(
     object_ops.extend(crate::object_diff::diff_charts(
         &wb_a,
         &wb_b,
-        session.strings(),
+        session.strings_mut(),
     ));
     object_ops.extend(crate::object_diff::diff_vba_modules(
         vba_a.as_deref(),
         vba_b.as_deref(),
-        session.strings(),
+        session.strings_mut(),
+    ));
+    object_ops.extend(crate::object_diff::diff_vba_project(
+        project_a.as_ref(),
+        project_b.as_ref(),
+        session.strings_mut(),
     ));
     ops.extend(object_ops);
 
@@ -164,6 +169,10 @@ fn contains_non_finite_numbers(report: &DiffReport) -> bool {
         DiffOp::DuplicateKeyCluster { key, .. } => key
             .iter()
             .any(|value| matches!(value, Some(CellValue::Number(n)) if !n.is_finite())),
+        DiffOp::RecordChanged { key, fields, .. } => key
+            .iter()
+            .chain(fields.iter().flat_map(|field| [&field.from, &field.to]))
+            .any(|value| matches!(value, Some(CellValue::Number(n)) if !n.is_finite())),
         _ => false,
     })
 }
@@ -1,15 +1,28 @@
 use crate::diff::{
-    AstDiffMode, AstDiffSummary, AstMoveHint, ColumnTypeChange, DiffOp, ExtractedColumnTypeChanges,
-    ExtractedRenamePairs, ExtractedString, ExtractedStringList, FormulaDiffResult, QueryChangeKind,
-    QueryMetadataField, QuerySemanticDetail, RenamePair, StepChange, StepDiff, StepParams,
-    StepSnapshot, StepType,
+    AstDiffMode, AstDiffSummary, AstMoveHint, ChartChange, ChartField, ColumnTypeChange,
+    ConnectionChange, ConnectionField, DiffOp, ExtractedColumnTypeChanges, ExtractedRenamePairs,
+    ExtractedString, ExtractedStringList, FieldChange, FormulaDiffResult, FormulaEdit,
+    FormulaEditKind, PbipEntityKind, PivotArea, PivotLayoutChange, QueryChangeKind,
+    QueryMetadataField, QuerySemanticDetail, RenamePair, ReportChange, ReportField, StepChange,
+    StepDiff, StepParams, StepSnapshot, StepType, TextHunk, TextLineKind, VbaAttributeChange,
+    VbaProcedureChange, VbaProcedureChangeKind, VbaProjectProperty, WorkbookSetting,
+    WorkbookSettingChange,
 };
 use crate::string_pool::StringId;
-use crate::workbook::{CellAddress, CellSnapshot, CellValue, ColSignature, RowSignature};
+use crate::vba::{VbaModuleType, VbaProcedureKind};
+use crate::workbook::{
+    CellAddress, CellComment, CellRange, CellSnapshot, CellStyle, CellValue, ColSignature,
+    CommentKind, ConditionalFormatRule, DataValidationRule, DrawingAnchor, DrawingMarker,
+    DrawingObject, DrawingObjectKind, ExternalLink, FreezePane, PivotSource, RowSignature,
+    SheetProtection, SheetVisibility, WorkbookProtection,
+};
 use std::io::{self, Write};
 
 #[cfg(feature = "model-diff")]
-use crate::diff::{ExpressionChangeKind, ModelColumnProperty, RelationshipProperty};
+use crate::diff::{
+    DaxEdit, DaxEditKind, ExpressionChangeKind, MeasureProperty, ModelColumnProperty,
+    ModelObjectChange, ModelObjectField, ModelObjectKind, RelationshipProperty,
+};
 
 const HEX_LOWER: &[u8; 16] = b"0123456789abcdef";
 
@@ -111,6 +124,30 @@ pub fn write_diff_op(w: &mut impl Write, op: &DiffOp) -> io::Result<()> {
             write_json_key(w, "right_rows")?;
             write_u32_array(w, right_rows)?;
         }
+        DiffOp::RecordChanged {
+            sheet,
+            key,
+            old_row,
+            new_row,
+            fields,
+        } => {
+            write_json_string_lit(w, "RecordChanged")?;
+            w.write_all(b",")?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
+            w.write_all(b",")?;
+            write_json_key(w, "key")?;
+            write_cell_value_opt_array(w, key)?;
+            w.write_all(b",")?;
+            write_json_key(w, "old_row")?;
+            write_u32(w, *old_row)?;
+            w.write_all(b",")?;
+            write_json_key(w, "new_row")?;
+            write_u32(w, *new_row)?;
+            w.write_all(b",")?;
+            write_json_key(w, "fields")?;
+            write_field_change_array(w, fields)?;
+        }
         DiffOp::RowReplaced { sheet, row_idx } => {
             write_json_string_lit(w, "RowReplaced")?;
             w.write_all(b",")?;
@@ -276,6 +313,7 @@ pub fn write_diff_op(w: &mut impl Write, op: &DiffOp) -> io::Result<()> {
             from,
             to,
             formula_diff,
+            formula_edits,
         } => {
             write_json_string_lit(w, "CellEdited")?;
             w.write_all(b",")?;
@@ -293,746 +331,2424 @@ pub fn write_diff_op(w: &mut impl Write, op: &DiffOp) -> io::Result<()> {
             w.write_all(b",")?;
             write_json_key(w, "formula_diff")?;
             write_formula_diff_result(w, *formula_diff)?;
+            if !formula_edits.is_empty() {
+                w.write_all(b",")?;
+                write_json_key(w, "formula_edits")?;
+                write_formula_edit_array(w, formula_edits)?;
+            }
         }
-        DiffOp::VbaModuleAdded { name } => {
-            write_json_string_lit(w, "VbaModuleAdded")?;
+        DiffOp::CellStyleChanged {
+            sheet,
+            addr,
+            from,
+            to,
+        } => {
+            write_json_string_lit(w, "CellStyleChanged")?;
             w.write_all(b",")?;
-            write_json_key(w, "name")?;
-            write_string_id(w, *name)?;
-        }
-        DiffOp::VbaModuleRemoved { name } => {
-            write_json_string_lit(w, "VbaModuleRemoved")?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
             w.write_all(b",")?;
-            write_json_key(w, "name")?;
-            write_string_id(w, *name)?;
-        }
-        DiffOp::VbaModuleChanged { name } => {
-            write_json_string_lit(w, "VbaModuleChanged")?;
+            write_json_key(w, "addr")?;
+            write_cell_address(w, *addr)?;
             w.write_all(b",")?;
-            write_json_key(w, "name")?;
-            write_string_id(w, *name)?;
+            write_json_key(w, "from")?;
+            write_cell_style(w, from)?;
+            w.write_all(b",")?;
+            write_json_key(w, "to")?;
+            write_cell_style(w, to)?;
         }
-        DiffOp::NamedRangeAdded { name } => {
-            write_json_string_lit(w, "NamedRangeAdded")?;
+        DiffOp::CommentAdded {
+            sheet,
+            addr,
+            comment,
+        } => {
+            write_json_string_lit(w, "CommentAdded")?;
             w.write_all(b",")?;
-            write_json_key(w, "name")?;
-            write_string_id(w, *name)?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
+            w.write_all(b",")?;
+            write_json_key(w, "addr")?;
+            write_cell_address(w, *addr)?;
+            w.write_all(b",")?;
+            write_json_key(w, "comment")?;
+            write_cell_comment(w, comment)?;
         }
-        DiffOp::NamedRangeRemoved { name } => {
-            write_json_string_lit(w, "NamedRangeRemoved")?;
+        DiffOp::CommentRemoved {
+            sheet,
+            addr,
+            comment,
+        } => {
+            write_json_string_lit(w, "CommentRemoved")?;
             w.write_all(b",")?;
-            write_json_key(w, "name")?;
-            write_string_id(w, *name)?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
+            w.write_all(b",")?;
+            write_json_key(w, "addr")?;
+            write_cell_address(w, *addr)?;
+            w.write_all(b",")?;
+            write_json_key(w, "comment")?;
+            write_cell_comment(w, comment)?;
         }
-        DiffOp::NamedRangeChanged {
-            name,
-            old_ref,
-            new_ref,
+        DiffOp::CommentChanged {
+            sheet,
+            addr,
+            from,
+            to,
         } => {
-            write_json_string_lit(w, "NamedRangeChanged")?;
+            write_json_string_lit(w, "CommentChanged")?;
             w.write_all(b",")?;
-            write_json_key(w, "name")?;
-            write_string_id(w, *name)?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
             w.write_all(b",")?;
-            write_json_key(w, "old_ref")?;
-            write_string_id(w, *old_ref)?;
+            write_json_key(w, "addr")?;
+            write_cell_address(w, *addr)?;
             w.write_all(b",")?;
-            write_json_key(w, "new_ref")?;
-            write_string_id(w, *new_ref)?;
+            write_json_key(w, "from")?;
+            write_cell_comment(w, from)?;
+            w.write_all(b",")?;
+            write_json_key(w, "to")?;
+            write_cell_comment(w, to)?;
         }
-        DiffOp::ChartAdded { sheet, name } => {
-            write_json_string_lit(w, "ChartAdded")?;
+        DiffOp::DataValidationAdded { sheet, rule } => {
+            write_json_string_lit(w, "DataValidationAdded")?;
             w.write_all(b",")?;
             write_json_key(w, "sheet")?;
             write_string_id(w, *sheet)?;
             w.write_all(b",")?;
-            write_json_key(w, "name")?;
-            write_string_id(w, *name)?;
+            write_json_key(w, "rule")?;
+            write_data_validation_rule(w, rule)?;
         }
-        DiffOp::ChartRemoved { sheet, name } => {
-            write_json_string_lit(w, "ChartRemoved")?;
+        DiffOp::DataValidationRemoved { sheet, rule } => {
+            write_json_string_lit(w, "DataValidationRemoved")?;
             w.write_all(b",")?;
             write_json_key(w, "sheet")?;
             write_string_id(w, *sheet)?;
             w.write_all(b",")?;
-            write_json_key(w, "name")?;
-            write_string_id(w, *name)?;
+            write_json_key(w, "rule")?;
+            write_data_validation_rule(w, rule)?;
         }
-        DiffOp::ChartChanged { sheet, name } => {
-            write_json_string_lit(w, "ChartChanged")?;
+        DiffOp::DataValidationChanged { sheet, from, to } => {
+            write_json_string_lit(w, "DataValidationChanged")?;
             w.write_all(b",")?;
             write_json_key(w, "sheet")?;
             write_string_id(w, *sheet)?;
             w.write_all(b",")?;
-            write_json_key(w, "name")?;
-            write_string_id(w, *name)?;
+            write_json_key(w, "from")?;
+            write_data_validation_rule(w, from)?;
+            w.write_all(b",")?;
+            write_json_key(w, "to")?;
+            write_data_validation_rule(w, to)?;
         }
-        DiffOp::QueryAdded { name } => {
-            write_json_string_lit(w, "QueryAdded")?;
+        DiffOp::ConditionalFormatAdded { sheet, rule } => {
+            write_json_string_lit(w, "ConditionalFormatAdded")?;
             w.write_all(b",")?;
-            write_json_key(w, "name")?;
-            write_string_id(w, *name)?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
+            w.write_all(b",")?;
+            write_json_key(w, "rule")?;
+            write_conditional_format_rule(w, rule)?;
         }
-        DiffOp::QueryRemoved { name } => {
-            write_json_string_lit(w, "QueryRemoved")?;
+        DiffOp::ConditionalFormatRemoved { sheet, rule } => {
+            write_json_string_lit(w, "ConditionalFormatRemoved")?;
             w.write_all(b",")?;
-            write_json_key(w, "name")?;
-            write_string_id(w, *name)?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
+            w.write_all(b",")?;
+            write_json_key(w, "rule")?;
+            write_conditional_format_rule(w, rule)?;
         }
-        DiffOp::QueryRenamed { from, to } => {
-            write_json_string_lit(w, "QueryRenamed")?;
+        DiffOp::ConditionalFormatChanged { sheet, from, to } => {
+            write_json_string_lit(w, "ConditionalFormatChanged")?;
+            w.write_all(b",")?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
             w.write_all(b",")?;
             write_json_key(w, "from")?;
-            write_string_id(w, *from)?;
+            write_conditional_format_rule(w, from)?;
             w.write_all(b",")?;
             write_json_key(w, "to")?;
-            write_string_id(w, *to)?;
+            write_conditional_format_rule(w, to)?;
         }
-        DiffOp::QueryDefinitionChanged {
-            name,
-            change_kind,
-            old_hash,
-            new_hash,
-            semantic_detail,
-        } => {
-            write_json_string_lit(w, "QueryDefinitionChanged")?;
+        DiffOp::MergedRangeAdded { sheet, range } => {
+            write_json_string_lit(w, "MergedRangeAdded")?;
             w.write_all(b",")?;
-            write_json_key(w, "name")?;
-            write_string_id(w, *name)?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
             w.write_all(b",")?;
-            write_json_key(w, "change_kind")?;
-            write_query_change_kind(w, *change_kind)?;
+            write_json_key(w, "range")?;
+            write_json_string(w, &range.to_a1())?;
+        }
+        DiffOp::MergedRangeRemoved { sheet, range } => {
+            write_json_string_lit(w, "MergedRangeRemoved")?;
             w.write_all(b",")?;
-            write_json_key(w, "old_hash")?;
-            write_u64(w, *old_hash)?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
             w.write_all(b",")?;
-            write_json_key(w, "new_hash")?;
-            write_u64(w, *new_hash)?;
-            if let Some(detail) = semantic_detail {
-                w.write_all(b",")?;
-                write_json_key(w, "semantic_detail")?;
-                write_query_semantic_detail(w, detail)?;
-            }
+            write_json_key(w, "range")?;
+            write_json_string(w, &range.to_a1())?;
         }
-        DiffOp::QueryMetadataChanged {
-            name,
-            field,
-            old,
-            new,
+        DiffOp::RowVisibilityChanged {
+            sheet,
+            row_idx,
+            hidden,
         } => {
-            write_json_string_lit(w, "QueryMetadataChanged")?;
+            write_json_string_lit(w, "RowVisibilityChanged")?;
             w.write_all(b",")?;
-            write_json_key(w, "name")?;
-            write_string_id(w, *name)?;
-            w.write_all(b",")?;
-            write_json_key(w, "field")?;
-            write_query_metadata_field(w, *field)?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
             w.write_all(b",")?;
-            write_json_key(w, "old")?;
-            write_option_string_id(w, *old)?;
+            write_json_key(w, "row_idx")?;
+            write_u32(w, *row_idx)?;
             w.write_all(b",")?;
-            write_json_key(w, "new")?;
-            write_option_string_id(w, *new)?;
+            write_json_key(w, "hidden")?;
+            write_bool(w, *hidden)?;
         }
-        #[cfg(feature = "model-diff")]
-        DiffOp::TableAdded { name } => {
-            write_json_string_lit(w, "TableAdded")?;
+        DiffOp::ColumnVisibilityChanged {
+            sheet,
+            col_idx,
+            hidden,
+        } => {
+            write_json_string_lit(w, "ColumnVisibilityChanged")?;
             w.write_all(b",")?;
-            write_json_key(w, "name")?;
-            write_string_id(w, *name)?;
-        }
-        #[cfg(feature = "model-diff")]
-        DiffOp::TableRemoved { name } => {
-            write_json_string_lit(w, "TableRemoved")?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
             w.write_all(b",")?;
-            write_json_key(w, "name")?;
-            write_string_id(w, *name)?;
+            write_json_key(w, "col_idx")?;
+            write_u32(w, *col_idx)?;
+            w.write_all(b",")?;
+            write_json_key(w, "hidden")?;
+            write_bool(w, *hidden)?;
         }
-        #[cfg(feature = "model-diff")]
-        DiffOp::ModelColumnAdded {
-            table,
-            name,
-            data_type,
+        DiffOp::RowOutlineChanged {
+            sheet,
+            row_idx,
+            from,
+            to,
         } => {
-            write_json_string_lit(w, "ModelColumnAdded")?;
+            write_json_string_lit(w, "RowOutlineChanged")?;
             w.write_all(b",")?;
-            write_json_key(w, "table")?;
-            write_string_id(w, *table)?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
             w.write_all(b",")?;
-            write_json_key(w, "name")?;
-            write_string_id(w, *name)?;
-            if let Some(dt) = data_type {
-                w.write_all(b",")?;
-                write_json_key(w, "data_type")?;
-                write_string_id(w, *dt)?;
-            }
+            write_json_key(w, "row_idx")?;
+            write_u32(w, *row_idx)?;
+            w.write_all(b",")?;
+            write_json_key(w, "from")?;
+            write_u32(w, u32::from(*from))?;
+            w.write_all(b",")?;
+            write_json_key(w, "to")?;
+            write_u32(w, u32::from(*to))?;
         }
-        #[cfg(feature = "model-diff")]
-        DiffOp::ModelColumnRemoved { table, name } => {
-            write_json_string_lit(w, "ModelColumnRemoved")?;
+        DiffOp::ColumnOutlineChanged {
+            sheet,
+            col_idx,
+            from,
+            to,
+        } => {
+            write_json_string_lit(w, "ColumnOutlineChanged")?;
             w.write_all(b",")?;
-            write_json_key(w, "table")?;
-            write_string_id(w, *table)?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
             w.write_all(b",")?;
-            write_json_key(w, "name")?;
-            write_string_id(w, *name)?;
+            write_json_key(w, "col_idx")?;
+            write_u32(w, *col_idx)?;
+            w.write_all(b",")?;
+            write_json_key(w, "from")?;
+            write_u32(w, u32::from(*from))?;
+            w.write_all(b",")?;
+            write_json_key(w, "to")?;
+            write_u32(w, u32::from(*to))?;
         }
-        #[cfg(feature = "model-diff")]
-        DiffOp::ModelColumnTypeChanged {
-            table,
-            name,
-            old_type,
-            new_type,
+        DiffOp::RowHeightChanged {
+            sheet,
+            row_idx,
+            from,
+            to,
         } => {
-            write_json_string_lit(w, "ModelColumnTypeChanged")?;
+            write_json_string_lit(w, "RowHeightChanged")?;
             w.write_all(b",")?;
-            write_json_key(w, "table")?;
-            write_string_id(w, *table)?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
             w.write_all(b",")?;
-            write_json_key(w, "name")?;
-            write_string_id(w, *name)?;
-            if let Some(ty) = old_type {
-                w.write_all(b",")?;
-                write_json_key(w, "old_type")?;
-                write_string_id(w, *ty)?;
-            }
-            if let Some(ty) = new_type {
-                w.write_all(b",")?;
-                write_json_key(w, "new_type")?;
-                write_string_id(w, *ty)?;
-            }
+            write_json_key(w, "row_idx")?;
+            write_u32(w, *row_idx)?;
+            w.write_all(b",")?;
+            write_json_key(w, "from")?;
+            write_option_u32(w, *from)?;
+            w.write_all(b",")?;
+            write_json_key(w, "to")?;
+            write_option_u32(w, *to)?;
         }
-        #[cfg(feature = "model-diff")]
-        DiffOp::ModelColumnPropertyChanged {
-            table,
-            name,
-            field,
-            old,
-            new,
+        DiffOp::ColumnWidthChanged {
+            sheet,
+            col_idx,
+            from,
+            to,
         } => {
-            write_json_string_lit(w, "ModelColumnPropertyChanged")?;
+            write_json_string_lit(w, "ColumnWidthChanged")?;
             w.write_all(b",")?;
-            write_json_key(w, "table")?;
-            write_string_id(w, *table)?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
+            w.write_all(b",")?;
+            write_json_key(w, "col_idx")?;
+            write_u32(w, *col_idx)?;
+            w.write_all(b",")?;
+            write_json_key(w, "from")?;
+            write_option_u32(w, *from)?;
+            w.write_all(b",")?;
+            write_json_key(w, "to")?;
+            write_option_u32(w, *to)?;
+        }
+        DiffOp::FreezePaneChanged { sheet, from, to } => {
+            write_json_string_lit(w, "FreezePaneChanged")?;
+            w.write_all(b",")?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
+            w.write_all(b",")?;
+            write_json_key(w, "from")?;
+            write_option_freeze_pane(w, *from)?;
+            w.write_all(b",")?;
+            write_json_key(w, "to")?;
+            write_option_freeze_pane(w, *to)?;
+        }
+        DiffOp::SpillRangeChanged {
+            sheet,
+            addr,
+            from,
+            to,
+        } => {
+            write_json_string_lit(w, "SpillRangeChanged")?;
+            w.write_all(b",")?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
+            w.write_all(b",")?;
+            write_json_key(w, "addr")?;
+            write_cell_address(w, *addr)?;
+            w.write_all(b",")?;
+            write_json_key(w, "from")?;
+            write_option_cell_range(w, *from)?;
+            w.write_all(b",")?;
+            write_json_key(w, "to")?;
+            write_option_cell_range(w, *to)?;
+        }
+        DiffOp::SheetVisibilityChanged { sheet, from, to } => {
+            write_json_string_lit(w, "SheetVisibilityChanged")?;
+            w.write_all(b",")?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
+            w.write_all(b",")?;
+            write_json_key(w, "from")?;
+            write_sheet_visibility(w, *from)?;
+            w.write_all(b",")?;
+            write_json_key(w, "to")?;
+            write_sheet_visibility(w, *to)?;
+        }
+        DiffOp::SheetTabColorChanged { sheet, from, to } => {
+            write_json_string_lit(w, "SheetTabColorChanged")?;
+            w.write_all(b",")?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
+            w.write_all(b",")?;
+            write_json_key(w, "from")?;
+            write_option_string_id(w, *from)?;
+            w.write_all(b",")?;
+            write_json_key(w, "to")?;
+            write_option_string_id(w, *to)?;
+        }
+        DiffOp::SheetProtectionChanged { sheet, from, to } => {
+            write_json_string_lit(w, "SheetProtectionChanged")?;
+            w.write_all(b",")?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
+            w.write_all(b",")?;
+            write_json_key(w, "from")?;
+            write_option_sheet_protection(w, from.as_ref())?;
+            w.write_all(b",")?;
+            write_json_key(w, "to")?;
+            write_option_sheet_protection(w, to.as_ref())?;
+        }
+        DiffOp::ExcelTableAdded { sheet, name, range } => {
+            write_json_string_lit(w, "ExcelTableAdded")?;
+            w.write_all(b",")?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
             w.write_all(b",")?;
             write_json_key(w, "name")?;
             write_string_id(w, *name)?;
             w.write_all(b",")?;
-            write_json_key(w, "field")?;
-            write_model_column_property(w, *field)?;
-            if let Some(val) = old {
-                w.write_all(b",")?;
-                write_json_key(w, "old")?;
-                write_string_id(w, *val)?;
-            }
-            if let Some(val) = new {
-                w.write_all(b",")?;
-                write_json_key(w, "new")?;
-                write_string_id(w, *val)?;
-            }
+            write_json_key(w, "range")?;
+            write_json_string(w, &range.to_a1())?;
         }
-        #[cfg(feature = "model-diff")]
-        DiffOp::CalculatedColumnDefinitionChanged {
-            table,
-            name,
-            change_kind,
-            old_hash,
-            new_hash,
-        } => {
-            write_json_string_lit(w, "CalculatedColumnDefinitionChanged")?;
+        DiffOp::ExcelTableRemoved { sheet, name, range } => {
+            write_json_string_lit(w, "ExcelTableRemoved")?;
             w.write_all(b",")?;
-            write_json_key(w, "table")?;
-            write_string_id(w, *table)?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
             w.write_all(b",")?;
             write_json_key(w, "name")?;
             write_string_id(w, *name)?;
             w.write_all(b",")?;
-            write_json_key(w, "change_kind")?;
-            write_expression_change_kind(w, *change_kind)?;
+            write_json_key(w, "range")?;
+            write_json_string(w, &range.to_a1())?;
+        }
+        DiffOp::ExcelTableRenamed { sheet, from, to } => {
+            write_json_string_lit(w, "ExcelTableRenamed")?;
             w.write_all(b",")?;
-            write_json_key(w, "old_hash")?;
-            write_u64(w, *old_hash)?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
             w.write_all(b",")?;
-            write_json_key(w, "new_hash")?;
-            write_u64(w, *new_hash)?;
+            write_json_key(w, "from")?;
+            write_string_id(w, *from)?;
+            w.write_all(b",")?;
+            write_json_key(w, "to")?;
+            write_string_id(w, *to)?;
         }
-        #[cfg(feature = "model-diff")]
-        DiffOp::RelationshipAdded {
-            from_table,
-            from_column,
-            to_table,
-            to_column,
+        DiffOp::ExcelTableResized {
+            sheet,
+            name,
+            from,
+            to,
         } => {
-            write_json_string_lit(w, "RelationshipAdded")?;
+            write_json_string_lit(w, "ExcelTableResized")?;
             w.write_all(b",")?;
-            write_json_key(w, "from_table")?;
-            write_string_id(w, *from_table)?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
             w.write_all(b",")?;
-            write_json_key(w, "from_column")?;
-            write_string_id(w, *from_column)?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
             w.write_all(b",")?;
-            write_json_key(w, "to_table")?;
-            write_string_id(w, *to_table)?;
+            write_json_key(w, "from")?;
+            write_json_string(w, &from.to_a1())?;
             w.write_all(b",")?;
-            write_json_key(w, "to_column")?;
-            write_string_id(w, *to_column)?;
+            write_json_key(w, "to")?;
+            write_json_string(w, &to.to_a1())?;
         }
-        #[cfg(feature = "model-diff")]
-        DiffOp::RelationshipRemoved {
-            from_table,
-            from_column,
-            to_table,
-            to_column,
+        DiffOp::ExcelTableColumnRenamed {
+            sheet,
+            table,
+            from,
+            to,
         } => {
-            write_json_string_lit(w, "RelationshipRemoved")?;
+            write_json_string_lit(w, "ExcelTableColumnRenamed")?;
             w.write_all(b",")?;
-            write_json_key(w, "from_table")?;
-            write_string_id(w, *from_table)?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
             w.write_all(b",")?;
-            write_json_key(w, "from_column")?;
-            write_string_id(w, *from_column)?;
+            write_json_key(w, "table")?;
+            write_string_id(w, *table)?;
             w.write_all(b",")?;
-            write_json_key(w, "to_table")?;
-            write_string_id(w, *to_table)?;
+            write_json_key(w, "from")?;
+            write_string_id(w, *from)?;
             w.write_all(b",")?;
-            write_json_key(w, "to_column")?;
-            write_string_id(w, *to_column)?;
+            write_json_key(w, "to")?;
+            write_string_id(w, *to)?;
         }
-        #[cfg(feature = "model-diff")]
-        DiffOp::RelationshipPropertyChanged {
-            from_table,
-            from_column,
-            to_table,
-            to_column,
-            field,
-            old,
-            new,
+        DiffOp::DrawingObjectAdded { sheet, object } => {
+            write_json_string_lit(w, "DrawingObjectAdded")?;
+            w.write_all(b",")?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
+            w.write_all(b",")?;
+            write_json_key(w, "object")?;
+            write_drawing_object(w, object)?;
+        }
+        DiffOp::DrawingObjectRemoved { sheet, object } => {
+            write_json_string_lit(w, "DrawingObjectRemoved")?;
+            w.write_all(b",")?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
+            w.write_all(b",")?;
+            write_json_key(w, "object")?;
+            write_drawing_object(w, object)?;
+        }
+        DiffOp::DrawingObjectMoved {
+            sheet,
+            name,
+            from,
+            to,
         } => {
-            write_json_string_lit(w, "RelationshipPropertyChanged")?;
+            write_json_string_lit(w, "DrawingObjectMoved")?;
             w.write_all(b",")?;
-            write_json_key(w, "from_table")?;
-            write_string_id(w, *from_table)?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
             w.write_all(b",")?;
-            write_json_key(w, "from_column")?;
-            write_string_id(w, *from_column)?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
             w.write_all(b",")?;
-            write_json_key(w, "to_table")?;
-            write_string_id(w, *to_table)?;
+            write_json_key(w, "from")?;
+            write_drawing_anchor(w, *from)?;
             w.write_all(b",")?;
-            write_json_key(w, "to_column")?;
-            write_string_id(w, *to_column)?;
+            write_json_key(w, "to")?;
+            write_drawing_anchor(w, *to)?;
+        }
+        DiffOp::DrawingObjectChanged { sheet, from, to } => {
+            write_json_string_lit(w, "DrawingObjectChanged")?;
             w.write_all(b",")?;
-            write_json_key(w, "field")?;
-            write_relationship_property(w, *field)?;
-            if let Some(val) = old {
-                w.write_all(b",")?;
-                write_json_key(w, "old")?;
-                write_string_id(w, *val)?;
-            }
-            if let Some(val) = new {
-                w.write_all(b",")?;
-                write_json_key(w, "new")?;
-                write_string_id(w, *val)?;
-            }
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
+            w.write_all(b",")?;
+            write_json_key(w, "from")?;
+            write_drawing_object(w, from)?;
+            w.write_all(b",")?;
+            write_json_key(w, "to")?;
+            write_drawing_object(w, to)?;
         }
-        #[cfg(feature = "model-diff")]
-        DiffOp::MeasureAdded { name } => {
-            write_json_string_lit(w, "MeasureAdded")?;
+        DiffOp::VbaModuleAdded { name } => {
+            write_json_string_lit(w, "VbaModuleAdded")?;
             w.write_all(b",")?;
             write_json_key(w, "name")?;
             write_string_id(w, *name)?;
         }
-        #[cfg(feature = "model-diff")]
-        DiffOp::MeasureRemoved { name } => {
-            write_json_string_lit(w, "MeasureRemoved")?;
+        DiffOp::VbaModuleRemoved { name } => {
+            write_json_string_lit(w, "VbaModuleRemoved")?;
             w.write_all(b",")?;
             write_json_key(w, "name")?;
             write_string_id(w, *name)?;
         }
-        #[cfg(feature = "model-diff")]
-        DiffOp::MeasureDefinitionChanged {
+        DiffOp::VbaModuleChanged {
             name,
-            change_kind,
-            old_hash,
-            new_hash,
+            module_type,
+            attributes,
+            procedures,
+            hunks,
         } => {
-            write_json_string_lit(w, "MeasureDefinitionChanged")?;
+            write_json_string_lit(w, "VbaModuleChanged")?;
+            w.write_all(b",")?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
+            if let Some((from, to)) = module_type {
+                w.write_all(b",")?;
+                write_json_key(w, "module_type")?;
+                w.write_all(b"[")?;
+                write_vba_module_type(w, *from)?;
+                w.write_all(b",")?;
+                write_vba_module_type(w, *to)?;
+                w.write_all(b"]")?;
+            }
+            if !attributes.is_empty() {
+                w.write_all(b",")?;
+                write_json_key(w, "attributes")?;
+                write_vba_attribute_change_array(w, attributes)?;
+            }
+            if !procedures.is_empty() {
+                w.write_all(b",")?;
+                write_json_key(w, "procedures")?;
+                write_vba_procedure_change_array(w, procedures)?;
+            }
+            if !hunks.is_empty() {
+                w.write_all(b",")?;
+                write_json_key(w, "hunks")?;
+                write_text_hunk_array(w, hunks)?;
+            }
+        }
+        DiffOp::VbaReferenceAdded { name, libid } => {
+            write_json_string_lit(w, "VbaReferenceAdded")?;
+            w.write_all(b",")?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
+            w.write_all(b",")?;
+            write_json_key(w, "libid")?;
+            write_string_id(w, *libid)?;
+        }
+        DiffOp::VbaReferenceRemoved { name, libid } => {
+            write_json_string_lit(w, "VbaReferenceRemoved")?;
+            w.write_all(b",")?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
+            w.write_all(b",")?;
+            write_json_key(w, "libid")?;
+            write_string_id(w, *libid)?;
+        }
+        DiffOp::VbaReferenceChanged { name, from, to } => {
+            write_json_string_lit(w, "VbaReferenceChanged")?;
+            w.write_all(b",")?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
+            w.write_all(b",")?;
+            write_json_key(w, "from")?;
+            write_string_id(w, *from)?;
+            w.write_all(b",")?;
+            write_json_key(w, "to")?;
+            write_string_id(w, *to)?;
+        }
+        DiffOp::VbaProjectChanged { property, from, to } => {
+            write_json_string_lit(w, "VbaProjectChanged")?;
+            w.write_all(b",")?;
+            write_json_key(w, "property")?;
+            write_vba_project_property(w, *property)?;
+            w.write_all(b",")?;
+            write_json_key(w, "from")?;
+            write_option_string_id(w, *from)?;
+            w.write_all(b",")?;
+            write_json_key(w, "to")?;
+            write_option_string_id(w, *to)?;
+        }
+        DiffOp::NamedRangeAdded { name } => {
+            write_json_string_lit(w, "NamedRangeAdded")?;
+            w.write_all(b",")?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
+        }
+        DiffOp::NamedRangeRemoved { name } => {
+            write_json_string_lit(w, "NamedRangeRemoved")?;
+            w.write_all(b",")?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
+        }
+        DiffOp::NamedRangeChanged {
+            name,
+            old_ref,
+            new_ref,
+        } => {
+            write_json_string_lit(w, "NamedRangeChanged")?;
+            w.write_all(b",")?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
+            w.write_all(b",")?;
+            write_json_key(w, "old_ref")?;
+            write_string_id(w, *old_ref)?;
+            w.write_all(b",")?;
+            write_json_key(w, "new_ref")?;
+            write_string_id(w, *new_ref)?;
+        }
+        DiffOp::ChartAdded { sheet, name } => {
+            write_json_string_lit(w, "ChartAdded")?;
+            w.write_all(b",")?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
+            w.write_all(b",")?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
+        }
+        DiffOp::ChartRemoved { sheet, name } => {
+            write_json_string_lit(w, "ChartRemoved")?;
+            w.write_all(b",")?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
+            w.write_all(b",")?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
+        }
+        DiffOp::ChartChanged {
+            sheet,
+            name,
+            changes,
+        } => {
+            write_json_string_lit(w, "ChartChanged")?;
+            w.write_all(b",")?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
+            w.write_all(b",")?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
+            if !changes.is_empty() {
+                w.write_all(b",")?;
+                write_json_key(w, "changes")?;
+                write_chart_change_array(w, changes)?;
+            }
+        }
+        DiffOp::PivotTableAdded { sheet, name } => {
+            write_json_string_lit(w, "PivotTableAdded")?;
+            w.write_all(b",")?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
+            w.write_all(b",")?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
+        }
+        DiffOp::PivotTableRemoved { sheet, name } => {
+            write_json_string_lit(w, "PivotTableRemoved")?;
+            w.write_all(b",")?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
+            w.write_all(b",")?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
+        }
+        DiffOp::PivotTableSourceChanged {
+            sheet,
+            name,
+            from,
+            to,
+            uncovered_rows,
+        } => {
+            write_json_string_lit(w, "PivotTableSourceChanged")?;
+            w.write_all(b",")?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
+            w.write_all(b",")?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
+            w.write_all(b",")?;
+            write_json_key(w, "from")?;
+            write_pivot_source(w, from)?;
+            w.write_all(b",")?;
+            write_json_key(w, "to")?;
+            write_pivot_source(w, to)?;
+            w.write_all(b",")?;
+            write_json_key(w, "uncovered_rows")?;
+            write_u32(w, *uncovered_rows)?;
+        }
+        DiffOp::PivotTableLayoutChanged {
+            sheet,
+            name,
+            changes,
+        } => {
+            write_json_string_lit(w, "PivotTableLayoutChanged")?;
+            w.write_all(b",")?;
+            write_json_key(w, "sheet")?;
+            write_string_id(w, *sheet)?;
+            w.write_all(b",")?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
+            w.write_all(b",")?;
+            write_json_key(w, "changes")?;
+            write_pivot_layout_change_array(w, changes)?;
+        }
+        DiffOp::ExternalLinkAdded { index, target } => {
+            write_json_string_lit(w, "ExternalLinkAdded")?;
+            w.write_all(b",")?;
+            write_json_key(w, "index")?;
+            write_u32(w, *index)?;
+            w.write_all(b",")?;
+            write_json_key(w, "target")?;
+            write_string_id(w, *target)?;
+        }
+        DiffOp::ExternalLinkRemoved { index, target } => {
+            write_json_string_lit(w, "ExternalLinkRemoved")?;
+            w.write_all(b",")?;
+            write_json_key(w, "index")?;
+            write_u32(w, *index)?;
+            w.write_all(b",")?;
+            write_json_key(w, "target")?;
+            write_string_id(w, *target)?;
+        }
+        DiffOp::ExternalLinkChanged { from, to } => {
+            write_json_string_lit(w, "ExternalLinkChanged")?;
+            w.write_all(b",")?;
+            write_json_key(w, "from")?;
+            write_external_link(w, from)?;
+            w.write_all(b",")?;
+            write_json_key(w, "to")?;
+            write_external_link(w, to)?;
+        }
+        DiffOp::ConnectionAdded { name } => {
+            write_json_string_lit(w, "ConnectionAdded")?;
+            w.write_all(b",")?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
+        }
+        DiffOp::ConnectionRemoved { name } => {
+            write_json_string_lit(w, "ConnectionRemoved")?;
+            w.write_all(b",")?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
+        }
+        DiffOp::ConnectionChanged { name, changes } => {
+            write_json_string_lit(w, "ConnectionChanged")?;
+            w.write_all(b",")?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
+            w.write_all(b",")?;
+            write_json_key(w, "changes")?;
+            write_connection_change_array(w, changes)?;
+        }
+        DiffOp::WorkbookSettingsChanged { changes } => {
+            write_json_string_lit(w, "WorkbookSettingsChanged")?;
+            w.write_all(b",")?;
+            write_json_key(w, "changes")?;
+            write_workbook_setting_change_array(w, changes)?;
+        }
+        DiffOp::WorkbookProtectionChanged { from, to } => {
+            write_json_string_lit(w, "WorkbookProtectionChanged")?;
+            w.write_all(b",")?;
+            write_json_key(w, "from")?;
+            write_option_workbook_protection(w, *from)?;
+            w.write_all(b",")?;
+            write_json_key(w, "to")?;
+            write_option_workbook_protection(w, *to)?;
+        }
+        DiffOp::DocumentPropertyChanged { name, from, to } => {
+            write_json_string_lit(w, "DocumentPropertyChanged")?;
+            w.write_all(b",")?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
+            w.write_all(b",")?;
+            write_json_key(w, "from")?;
+            write_option_string_id(w, *from)?;
+            w.write_all(b",")?;
+            write_json_key(w, "to")?;
+            write_option_string_id(w, *to)?;
+        }
+        DiffOp::QueryAdded { name } => {
+            write_json_string_lit(w, "QueryAdded")?;
+            w.write_all(b",")?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
+        }
+        DiffOp::QueryRemoved { name } => {
+            write_json_string_lit(w, "QueryRemoved")?;
+            w.write_all(b",")?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
+        }
+        DiffOp::QueryRenamed { from, to } => {
+            write_json_string_lit(w, "QueryRenamed")?;
+            w.write_all(b",")?;
+            write_json_key(w, "from")?;
+            write_string_id(w, *from)?;
+            w.write_all(b",")?;
+            write_json_key(w, "to")?;
+            write_string_id(w, *to)?;
+        }
+        DiffOp::QueryDefinitionChanged {
+            name,
+            change_kind,
+            old_hash,
+            new_hash,
+            semantic_detail,
+            downstream_queries,
+            loaded_tables,
+        } => {
+            write_json_string_lit(w, "QueryDefinitionChanged")?;
             w.write_all(b",")?;
             write_json_key(w, "name")?;
             write_string_id(w, *name)?;
             w.write_all(b",")?;
             write_json_key(w, "change_kind")?;
-            write_expression_change_kind(w, *change_kind)?;
+            write_query_change_kind(w, *change_kind)?;
             w.write_all(b",")?;
             write_json_key(w, "old_hash")?;
             write_u64(w, *old_hash)?;
             w.write_all(b",")?;
             write_json_key(w, "new_hash")?;
             write_u64(w, *new_hash)?;
+            if let Some(detail) = semantic_detail {
+                w.write_all(b",")?;
+                write_json_key(w, "semantic_detail")?;
+                write_query_semantic_detail(w, detail)?;
+            }
+            if !downstream_queries.is_empty() {
+                w.write_all(b",")?;
+                write_json_key(w, "downstream_queries")?;
+                write_string_id_array(w, downstream_queries)?;
+            }
+            if !loaded_tables.is_empty() {
+                w.write_all(b",")?;
+                write_json_key(w, "loaded_tables")?;
+                write_string_id_array(w, loaded_tables)?;
+            }
+        }
+        DiffOp::QueryMetadataChanged {
+            name,
+            field,
+            old,
+            new,
+        } => {
+            write_json_string_lit(w, "QueryMetadataChanged")?;
+            w.write_all(b",")?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
+            w.write_all(b",")?;
+            write_json_key(w, "field")?;
+            write_query_metadata_field(w, *field)?;
+            w.write_all(b",")?;
+            write_json_key(w, "old")?;
+            write_option_string_id(w, *old)?;
+            w.write_all(b",")?;
+            write_json_key(w, "new")?;
+            write_option_string_id(w, *new)?;
+        }
+        #[cfg(feature = "model-diff")]
+        DiffOp::TableAdded { name } => {
+            write_json_string_lit(w, "TableAdded")?;
+            w.write_all(b",")?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
+        }
+        #[cfg(feature = "model-diff")]
+        DiffOp::TableRemoved { name } => {
+            write_json_string_lit(w, "TableRemoved")?;
+            w.write_all(b",")?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
+        }
+        #[cfg(feature = "model-diff")]
+        DiffOp::ModelColumnAdded {
+            table,
+            name,
+            data_type,
+        } => {
+            write_json_string_lit(w, "ModelColumnAdded")?;
+            w.write_all(b",")?;
+            write_json_key(w, "table")?;
+            write_string_id(w, *table)?;
+            w.write_all(b",")?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
+            if let Some(dt) = data_type {
+                w.write_all(b",")?;
+                write_json_key(w, "data_type")?;
+                write_string_id(w, *dt)?;
+            }
+        }
+        #[cfg(feature = "model-diff")]
+        DiffOp::ModelColumnRemoved { table, name } => {
+            write_json_string_lit(w, "ModelColumnRemoved")?;
+            w.write_all(b",")?;
+            write_json_key(w, "table")?;
+            write_string_id(w, *table)?;
+            w.write_all(b",")?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
+        }
+        #[cfg(feature = "model-diff")]
+        DiffOp::ModelColumnTypeChanged {
+            table,
+            name,
+            old_type,
+            new_type,
+        } => {
+            write_json_string_lit(w, "ModelColumnTypeChanged")?;
+            w.write_all(b",")?;
+            write_json_key(w, "table")?;
+            write_string_id(w, *table)?;
+            w.write_all(b",")?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
+            if let Some(ty) = old_type {
+                w.write_all(b",")?;
+                write_json_key(w, "old_type")?;
+                write_string_id(w, *ty)?;
+            }
+            if let Some(ty) = new_type {
+                w.write_all(b",")?;
+                write_json_key(w, "new_type")?;
+                write_string_id(w, *ty)?;
+            }
+        }
+        #[cfg(feature = "model-diff")]
+        DiffOp::ModelColumnPropertyChanged {
+            table,
+            name,
+            field,
+            old,
+            new,
+        } => {
+            write_json_string_lit(w, "ModelColumnPropertyChanged")?;
+            w.write_all(b",")?;
+            write_json_key(w, "table")?;
+            write_string_id(w, *table)?;
+            w.write_all(b",")?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
+            w.write_all(b",")?;
+            write_json_key(w, "field")?;
+            write_model_column_property(w, *field)?;
+            if let Some(val) = old {
+                w.write_all(b",")?;
+                write_json_key(w, "old")?;
+                write_string_id(w, *val)?;
+            }
+            if let Some(val) = new {
+                w.write_all(b",")?;
+                write_json_key(w, "new")?;
+                write_string_id(w, *val)?;
+            }
+        }
+        #[cfg(feature = "model-diff")]
+        DiffOp::CalculatedColumnDefinitionChanged {
+            table,
+            name,
+            change_kind,
+            old_hash,
+            new_hash,
+            edits,
+        } => {
+            write_json_string_lit(w, "CalculatedColumnDefinitionChanged")?;
+            w.write_all(b",")?;
+            write_json_key(w, "table")?;
+            write_string_id(w, *table)?;
+            w.write_all(b",")?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
+            w.write_all(b",")?;
+            write_json_key(w, "change_kind")?;
+            write_expression_change_kind(w, *change_kind)?;
+            w.write_all(b",")?;
+            write_json_key(w, "old_hash")?;
+            write_u64(w, *old_hash)?;
+            w.write_all(b",")?;
+            write_json_key(w, "new_hash")?;
+            write_u64(w, *new_hash)?;
+            if !edits.is_empty() {
+                w.write_all(b",")?;
+                write_json_key(w, "edits")?;
+                write_dax_edit_array(w, edits)?;
+            }
+        }
+        #[cfg(feature = "model-diff")]
+        DiffOp::RelationshipAdded {
+            from_table,
+            from_column,
+            to_table,
+            to_column,
+        } => {
+            write_json_string_lit(w, "RelationshipAdded")?;
+            w.write_all(b",")?;
+            write_json_key(w, "from_table")?;
+            write_string_id(w, *from_table)?;
+            w.write_all(b",")?;
+            write_json_key(w, "from_column")?;
+            write_string_id(w, *from_column)?;
+            w.write_all(b",")?;
+            write_json_key(w, "to_table")?;
+            write_string_id(w, *to_table)?;
+            w.write_all(b",")?;
+            write_json_key(w, "to_column")?;
+            write_string_id(w, *to_column)?;
+        }
+        #[cfg(feature = "model-diff")]
+        DiffOp::RelationshipRemoved {
+            from_table,
+            from_column,
+            to_table,
+            to_column,
+        } => {
+            write_json_string_lit(w, "RelationshipRemoved")?;
+            w.write_all(b",")?;
+            write_json_key(w, "from_table")?;
+            write_string_id(w, *from_table)?;
+            w.write_all(b",")?;
+            write_json_key(w, "from_column")?;
+            write_string_id(w, *from_column)?;
+            w.write_all(b",")?;
+            write_json_key(w, "to_table")?;
+            write_string_id(w, *to_table)?;
+            w.write_all(b",")?;
+            write_json_key(w, "to_column")?;
+            write_string_id(w, *to_column)?;
+        }
+        #[cfg(feature = "model-diff")]
+        DiffOp::RelationshipPropertyChanged {
+            from_table,
+            from_column,
+            to_table,
+            to_column,
+            field,
+            old,
+            new,
+        } => {
+            write_json_string_lit(w, "RelationshipPropertyChanged")?;
+            w.write_all(b",")?;
+            write_json_key(w, "from_table")?;
+            write_string_id(w, *from_table)?;
+            w.write_all(b",")?;
+            write_json_key(w, "from_column")?;
+            write_string_id(w, *from_column)?;
+            w.write_all(b",")?;
+            write_json_key(w, "to_table")?;
+            write_string_id(w, *to_table)?;
+            w.write_all(b",")?;
+            write_json_key(w, "to_column")?;
+            write_string_id(w, *to_column)?;
+            w.write_all(b",")?;
+            write_json_key(w, "field")?;
+            write_relationship_property(w, *field)?;
+            if let Some(val) = old {
+                w.write_all(b",")?;
+                write_json_key(w, "old")?;
+                write_string_id(w, *val)?;
+            }
+            if let Some(val) = new {
+                w.write_all(b",")?;
+                write_json_key(w, "new")?;
+                write_string_id(w, *val)?;
+            }
+        }
+        #[cfg(feature = "model-diff")]
+        DiffOp::MeasureAdded { name } => {
+            write_json_string_lit(w, "MeasureAdded")?;
+            w.write_all(b",")?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
+        }
+        #[cfg(feature = "model-diff")]
+        DiffOp::MeasureRemoved { name } => {
+            write_json_string_lit(w, "MeasureRemoved")?;
+            w.write_all(b",")?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
+        }
+        #[cfg(feature = "model-diff")]
+        DiffOp::MeasureDefinitionChanged {
+            name,
+            change_kind,
+            old_hash,
+            new_hash,
+            edits,
+        } => {
+            write_json_string_lit(w, "MeasureDefinitionChanged")?;
+            w.write_all(b",")?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
+            w.write_all(b",")?;
+            write_json_key(w, "change_kind")?;
+            write_expression_change_kind(w, *change_kind)?;
+            w.write_all(b",")?;
+            write_json_key(w, "old_hash")?;
+            write_u64(w, *old_hash)?;
+            w.write_all(b",")?;
+            write_json_key(w, "new_hash")?;
+            write_u64(w, *new_hash)?;
+            if !edits.is_empty() {
+                w.write_all(b",")?;
+                write_json_key(w, "edits")?;
+                write_dax_edit_array(w, edits)?;
+            }
+        }
+        #[cfg(feature = "model-diff")]
+        DiffOp::MeasurePropertyChanged {
+            name,
+            field,
+            old,
+            new,
+        } => {
+            write_json_string_lit(w, "MeasurePropertyChanged")?;
+            w.write_all(b",")?;
+            write_json_key(w, "name")?;
+            write_string_id(w, *name)?;
+            w.write_all(b",")?;
+            write_json_key(w, "field")?;
+            write_measure_property(w, *field)?;
+            if let Some(val) = old {
+                w.write_all(b",")?;
+                write_json_key(w, "old")?;
+                write_string_id(w, *val)?;
+            }
+            if let Some(val) = new {
+                w.write_all(b",")?;
+                write_json_key(w, "new")?;
+                write_string_id(w, *val)?;
+            }
+        }
+        #[cfg(feature = "model-diff")]
+        DiffOp::ModelObjectAdded {
+            object,
+            table,
+            name,
+        } => {
+            write_json_string_lit(w, "ModelObjectAdded")?;
+            write_model_object(w, *object, *table, *name)?;
+        }
+        #[cfg(feature = "model-diff")]
+        DiffOp::ModelObjectRemoved {
+            object,
+            table,
+            name,
+        } => {
+            write_json_string_lit(w, "ModelObjectRemoved")?;
+            write_model_object(w, *object, *table, *name)?;
+        }
+        #[cfg(feature = "model-diff")]
+        DiffOp::ModelObjectChanged {
+            object,
+            table,
+            name,
+            changes,
+        } => {
+            write_json_string_lit(w, "ModelObjectChanged")?;
+            write_model_object(w, *object, *table, *name)?;
+            w.write_all(b",")?;
+            write_json_key(w, "changes")?;
+            write_model_object_change_array(w, changes)?;
+        }
+        DiffOp::ReportObjectAdded { object, page, name } => {
+            write_json_string_lit(w, "ReportObjectAdded")?;
+            write_report_object(w, *object, *page, *name)?;
+        }
+        DiffOp::ReportObjectRemoved { object, page, name } => {
+            write_json_string_lit(w, "ReportObjectRemoved")?;
+            write_report_object(w, *object, *page, *name)?;
+        }
+        DiffOp::ReportObjectChanged {
+            object,
+            page,
+            name,
+            changes,
+        } => {
+            write_json_string_lit(w, "ReportObjectChanged")?;
+            write_report_object(w, *object, *page, *name)?;
+            if !changes.is_empty() {
+                w.write_all(b",")?;
+                write_json_key(w, "changes")?;
+                write_report_change_array(w, changes)?;
+            }
+        }
+    }
+    w.write_all(b"}")?;
+    Ok(())
+}
+
+pub fn write_json_string(w: &mut impl Write, s: &str) -> io::Result<()> {
+    w.write_all(b"\"")?;
+
+    let bytes = s.as_bytes();
+    let mut start = 0usize;
+    for (i, &b) in bytes.iter().enumerate() {
+        let esc: Option<&'static [u8]> = match b {
+            b'\"' => Some(br#"\""#),
+            b'\\' => Some(br#"\\"#),
+            b'\n' => Some(br#"\n"#),
+            b'\r' => Some(br#"\r"#),
+            b'\t' => Some(br#"\t"#),
+            0x08 => Some(br#"\b"#),
+            0x0c => Some(br#"\f"#),
+            b if b < 0x20 => None,
+            _ => continue,
+        };
+
+        w.write_all(&bytes[start..i])?;
+        match esc {
+            Some(seq) => w.write_all(seq)?,
+            None => {
+                let mut buf = [0u8; 6];
+                buf[0] = b'\\';
+                buf[1] = b'u';
+                buf[2] = b'0';
+                buf[3] = b'0';
+                buf[4] = HEX_LOWER[(b >> 4) as usize];
+                buf[5] = HEX_LOWER[(b & 0x0f) as usize];
+                w.write_all(&buf)?;
+            }
+        }
+        start = i + 1;
+    }
+    w.write_all(&bytes[start..])?;
+    w.write_all(b"\"")?;
+    Ok(())
+}
+
+fn write_json_string_lit(w: &mut impl Write, s: &'static str) -> io::Result<()> {
+    w.write_all(b"\"")?;
+    w.write_all(s.as_bytes())?;
+    w.write_all(b"\"")?;
+    Ok(())
+}
+
+fn write_json_key(w: &mut impl Write, key: &'static str) -> io::Result<()> {
+    w.write_all(b"\"")?;
+    w.write_all(key.as_bytes())?;
+    w.write_all(b"\":")?;
+    Ok(())
+}
+
+fn write_json_string_array(w: &mut impl Write, strings: &[String]) -> io::Result<()> {
+    w.write_all(b"[")?;
+    for (i, s) in strings.iter().enumerate() {
+        if i != 0 {
+            w.write_all(b",")?;
+        }
+        write_json_string(w, s)?;
+    }
+    w.write_all(b"]")?;
+    Ok(())
+}
+
+fn write_u32(w: &mut impl Write, value: u32) -> io::Result<()> {
+    write_u64(w, value as u64)
+}
+
+fn write_u64(w: &mut impl Write, mut value: u64) -> io::Result<()> {
+    if value == 0 {
+        return w.write_all(b"0");
+    }
+
+    let mut buf = [0u8; 20];
+    let mut i = buf.len();
+    while value != 0 {
+        i -= 1;
+        buf[i] = b'0' + (value % 10) as u8;
+        value /= 10;
+    }
+    w.write_all(&buf[i..])
+}
+
+fn write_i64(w: &mut impl Write, value: i64) -> io::Result<()> {
+    if value < 0 {
+        w.write_all(b"-")?;
+    }
+    write_u64(w, value.unsigned_abs())
+}
+
+fn write_option_u64(w: &mut impl Write, value: Option<u64>) -> io::Result<()> {
+    match value {
+        Some(v) => write_u64(w, v),
+        None => w.write_all(b"null"),
+    }
+}
+
+fn write_option_u32(w: &mut impl Write, value: Option<u32>) -> io::Result<()> {
+    match value {
+        Some(v) => write_u32(w, v),
+        None => w.write_all(b"null"),
+    }
+}
+
+fn write_bool(w: &mut impl Write, value: bool) -> io::Result<()> {
+    if value {
+        w.write_all(b"true")
+    } else {
+        w.write_all(b"false")
+    }
+}
+
+fn write_f64(w: &mut impl Write, value: f64) -> io::Result<()> {
+    if !value.is_finite() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "non-finite numbers are not supported in JSON output",
+        ));
+    }
+
+    let mut buf = ryu::Buffer::new();
+    let s = buf.format_finite(value);
+    w.write_all(s.as_bytes())
+}
+
+fn write_string_id(w: &mut impl Write, value: StringId) -> io::Result<()> {
+    write_u32(w, value.0)
+}
+
+fn write_option_string_id(w: &mut impl Write, value: Option<StringId>) -> io::Result<()> {
+    match value {
+        Some(v) => write_string_id(w, v),
+        None => w.write_all(b"null"),
+    }
+}
+
+fn write_u32_array(w: &mut impl Write, values: &[u32]) -> io::Result<()> {
+    w.write_all(b"[")?;
+    for (i, v) in values.iter().enumerate() {
+        if i != 0 {
+            w.write_all(b",")?;
+        }
+        write_u32(w, *v)?;
+    }
+    w.write_all(b"]")?;
+    Ok(())
+}
+
+fn write_string_id_array(w: &mut impl Write, values: &[StringId]) -> io::Result<()> {
+    w.write_all(b"[")?;
+    for (i, v) in values.iter().enumerate() {
+        if i != 0 {
+            w.write_all(b",")?;
+        }
+        write_string_id(w, *v)?;
+    }
+    w.write_all(b"]")?;
+    Ok(())
+}
+
+fn write_cell_address(w: &mut impl Write, addr: CellAddress) -> io::Result<()> {
+    w.write_all(b"\"")?;
+
+    // Same behavior as `addressing::index_to_address`, but without allocating.
+    let mut col_index = addr.col;
+    let mut letters = [0u8; 16];
+    let mut letters_len: usize = 0;
+    loop {
+        let rem = (col_index % 26) as u8;
+        letters[letters_len] = b'A' + rem;
+        letters_len += 1;
+        if col_index < 26 {
+            break;
+        }
+        col_index = col_index / 26 - 1;
+    }
+
+    let mut buf = [0u8; 32];
+    let mut pos = 0usize;
+    for i in (0..letters_len).rev() {
+        buf[pos] = letters[i];
+        pos += 1;
+    }
+
+    let mut row_num = addr.row.saturating_add(1);
+    let mut digits = [0u8; 10];
+    let mut digits_len: usize = 0;
+    while row_num != 0 {
+        digits[digits_len] = b'0' + (row_num % 10) as u8;
+        digits_len += 1;
+        row_num /= 10;
+    }
+    for i in (0..digits_len).rev() {
+        buf[pos] = digits[i];
+        pos += 1;
+    }
+
+    w.write_all(&buf[..pos])?;
+    w.write_all(b"\"")?;
+    Ok(())
+}
+
+fn write_cell_value(w: &mut impl Write, value: &CellValue) -> io::Result<()> {
+    match value {
+        CellValue::Blank => write_json_string_lit(w, "Blank"),
+        CellValue::Number(n) => {
+            w.write_all(b"{")?;
+            write_json_key(w, "Number")?;
+            write_f64(w, *n)?;
+            w.write_all(b"}")?;
+            Ok(())
+        }
+        CellValue::Text(id) => {
+            w.write_all(b"{")?;
+            write_json_key(w, "Text")?;
+            write_string_id(w, *id)?;
+            w.write_all(b"}")?;
+            Ok(())
+        }
+        CellValue::Bool(b) => {
+            w.write_all(b"{")?;
+            write_json_key(w, "Bool")?;
+            write_bool(w, *b)?;
+            w.write_all(b"}")?;
+            Ok(())
+        }
+        CellValue::Error(id) => {
+            w.write_all(b"{")?;
+            write_json_key(w, "Error")?;
+            write_string_id(w, *id)?;
+            w.write_all(b"}")?;
+            Ok(())
+        }
+    }
+}
+
+fn write_option_cell_value(w: &mut impl Write, value: Option<&CellValue>) -> io::Result<()> {
+    match value {
+        Some(v) => write_cell_value(w, v),
+        None => w.write_all(b"null"),
+    }
+}
+
+fn write_cell_value_opt_array(w: &mut impl Write, values: &[Option<CellValue>]) -> io::Result<()> {
+    w.write_all(b"[")?;
+    for (i, v) in values.iter().enumerate() {
+        if i != 0 {
+            w.write_all(b",")?;
+        }
+        match v {
+            Some(cv) => write_cell_value(w, cv)?,
+            None => w.write_all(b"null")?,
+        }
+    }
+    w.write_all(b"]")?;
+    Ok(())
+}
+
+fn write_field_change_array(w: &mut impl Write, fields: &[FieldChange]) -> io::Result<()> {
+    w.write_all(b"[")?;
+    for (i, field) in fields.iter().enumerate() {
+        if i != 0 {
+            w.write_all(b",")?;
+        }
+        w.write_all(b"{")?;
+        write_json_key(w, "col")?;
+        write_u32(w, field.col)?;
+        if let Some(name) = field.field {
+            w.write_all(b",")?;
+            write_json_key(w, "field")?;
+            write_string_id(w, name)?;
+        }
+        w.write_all(b",")?;
+        write_json_key(w, "from")?;
+        write_option_cell_value(w, field.from.as_ref())?;
+        w.write_all(b",")?;
+        write_json_key(w, "to")?;
+        write_option_cell_value(w, field.to.as_ref())?;
+        w.write_all(b"}")?;
+    }
+    w.write_all(b"]")?;
+    Ok(())
+}
+
+fn write_cell_snapshot(w: &mut impl Write, snap: &CellSnapshot) -> io::Result<()> {
+    w.write_all(b"{")?;
+    write_json_key(w, "addr")?;
+    write_cell_address(w, snap.addr)?;
+    w.write_all(b",")?;
+    write_json_key(w, "value")?;
+    write_option_cell_value(w, snap.value.as_ref())?;
+    w.write_all(b",")?;
+    write_json_key(w, "formula")?;
+    write_option_string_id(w, snap.formula)?;
+    w.write_all(b"}")?;
+    Ok(())
+}
+
+fn write_cell_style(w: &mut impl Write, style: &CellStyle) -> io::Result<()> {
+    w.write_all(b"{")?;
+    write_json_key(w, "number_format")?;
+    write_option_string_id(w, style.number_format)?;
+    w.write_all(b",")?;
+    write_json_key(w, "font")?;
+    write_option_string_id(w, style.font)?;
+    w.write_all(b",")?;
+    write_json_key(w, "fill")?;
+    write_option_string_id(w, style.fill)?;
+    w.write_all(b",")?;
+    write_json_key(w, "border")?;
+    write_option_string_id(w, style.border)?;
+    w.write_all(b",")?;
+    write_json_key(w, "alignment")?;
+    write_option_string_id(w, style.alignment)?;
+    w.write_all(b",")?;
+    write_json_key(w, "protection")?;
+    write_option_string_id(w, style.protection)?;
+    w.write_all(b"}")?;
+    Ok(())
+}
+
+fn write_cell_comment(w: &mut impl Write, comment: &CellComment) -> io::Result<()> {
+    w.write_all(b"{")?;
+    write_json_key(w, "kind")?;
+    write_json_string_lit(
+        w,
+        match comment.kind {
+            CommentKind::Note => "note",
+            CommentKind::Threaded => "threaded",
+        },
+    )?;
+    w.write_all(b",")?;
+    write_json_key(w, "author")?;
+    write_option_string_id(w, comment.author)?;
+    w.write_all(b",")?;
+    write_json_key(w, "text")?;
+    write_string_id(w, comment.text)?;
+    w.write_all(b",")?;
+    write_json_key(w, "resolved")?;
+    write_bool(w, comment.resolved)?;
+    if !comment.replies.is_empty() {
+        w.write_all(b",")?;
+        write_json_key(w, "replies")?;
+        w.write_all(b"[")?;
+        for (idx, reply) in comment.replies.iter().enumerate() {
+            if idx > 0 {
+                w.write_all(b",")?;
+            }
+            w.write_all(b"{")?;
+            write_json_key(w, "author")?;
+            write_option_string_id(w, reply.author)?;
+            w.write_all(b",")?;
+            write_json_key(w, "text")?;
+            write_string_id(w, reply.text)?;
+            w.write_all(b"}")?;
         }
+        w.write_all(b"]")?;
     }
     w.write_all(b"}")?;
     Ok(())
 }
 
-pub fn write_json_string(w: &mut impl Write, s: &str) -> io::Result<()> {
-    w.write_all(b"\"")?;
+fn write_drawing_object(w: &mut impl Write, object: &DrawingObject) -> io::Result<()> {
+    w.write_all(b"{")?;
+    write_json_key(w, "id")?;
+    write_u32(w, object.id)?;
+    w.write_all(b",")?;
+    write_json_key(w, "kind")?;
+    write_json_string_lit(
+        w,
+        match object.kind {
+            DrawingObjectKind::Shape => "shape",
+            DrawingObjectKind::TextBox => "text_box",
+            DrawingObjectKind::Picture => "picture",
+            DrawingObjectKind::Connector => "connector",
+            DrawingObjectKind::Group => "group",
+            DrawingObjectKind::FormControl => "form_control",
+        },
+    )?;
+    w.write_all(b",")?;
+    write_json_key(w, "name")?;
+    write_string_id(w, object.name)?;
+    w.write_all(b",")?;
+    write_json_key(w, "anchor")?;
+    write_drawing_anchor(w, object.anchor)?;
+    w.write_all(b",")?;
+    write_json_key(w, "text")?;
+    write_option_string_id(w, object.text)?;
+    w.write_all(b",")?;
+    write_json_key(w, "macro")?;
+    write_option_string_id(w, object.macro_name)?;
+    w.write_all(b",")?;
+    write_json_key(w, "image_hash")?;
+    write_option_u64(w, object.image_hash)?;
+    w.write_all(b"}")?;
+    Ok(())
+}
 
-    let bytes = s.as_bytes();
-    let mut start = 0usize;
-    for (i, &b) in bytes.iter().enumerate() {
-        let esc: Option<&'static [u8]> = match b {
-            b'\"' => Some(br#"\""#),
-            b'\\' => Some(br#"\\"#),
-            b'\n' => Some(br#"\n"#),
-            b'\r' => Some(br#"\r"#),
-            b'\t' => Some(br#"\t"#),
-            0x08 => Some(br#"\b"#),
-            0x0c => Some(br#"\f"#),
-            b if b < 0x20 => None,
-            _ => continue,
-        };
+fn write_drawing_anchor(w: &mut impl Write, anchor: DrawingAnchor) -> io::Result<()> {
+    w.write_all(b"{")?;
+    write_json_key(w, "from")?;
+    write_drawing_marker(w, anchor.from)?;
+    w.write_all(b",")?;
+    write_json_key(w, "to")?;
+    match anchor.to {
+        Some(marker) => write_drawing_marker(w, marker)?,
+        None => w.write_all(b"null")?,
+    }
+    w.write_all(b"}")?;
+    Ok(())
+}
 
-        w.write_all(&bytes[start..i])?;
-        match esc {
-            Some(seq) => w.write_all(seq)?,
-            None => {
-                let mut buf = [0u8; 6];
-                buf[0] = b'\\';
-                buf[1] = b'u';
-                buf[2] = b'0';
-                buf[3] = b'0';
-                buf[4] = HEX_LOWER[(b >> 4) as usize];
-                buf[5] = HEX_LOWER[(b & 0x0f) as usize];
-                w.write_all(&buf)?;
-            }
+fn write_drawing_marker(w: &mut impl Write, marker: DrawingMarker) -> io::Result<()> {
+    w.write_all(b"{")?;
+    write_json_key(w, "row")?;
+    write_u32(w, marker.row)?;
+    w.write_all(b",")?;
+    write_json_key(w, "col")?;
+    write_u32(w, marker.col)?;
+    w.write_all(b",")?;
+    write_json_key(w, "row_offset")?;
+    write_i64(w, marker.row_offset)?;
+    w.write_all(b",")?;
+    write_json_key(w, "col_offset")?;
+    write_i64(w, marker.col_offset)?;
+    w.write_all(b"}")?;
+    Ok(())
+}
+
+fn write_cell_ranges(w: &mut impl Write, ranges: &[CellRange]) -> io::Result<()> {
+    w.write_all(b"[")?;
+    for (i, range) in ranges.iter().enumerate() {
+        if i != 0 {
+            w.write_all(b",")?;
         }
-        start = i + 1;
+        write_json_string(w, &range.to_a1())?;
     }
-    w.write_all(&bytes[start..])?;
-    w.write_all(b"\"")?;
+    w.write_all(b"]")?;
     Ok(())
 }
 
-fn write_json_string_lit(w: &mut impl Write, s: &'static str) -> io::Result<()> {
+fn write_data_validation_rule(w: &mut impl Write, rule: &DataValidationRule) -> io::Result<()> {
+    w.write_all(b"{")?;
+    write_json_key(w, "ranges")?;
+    write_cell_ranges(w, &rule.ranges)?;
+    w.write_all(b",")?;
+    write_json_key(w, "kind")?;
+    write_string_id(w, rule.kind)?;
+    w.write_all(b",")?;
+    write_json_key(w, "operator")?;
+    write_option_string_id(w, rule.operator)?;
+    w.write_all(b",")?;
+    write_json_key(w, "formula1")?;
+    write_option_string_id(w, rule.formula1)?;
+    w.write_all(b",")?;
+    write_json_key(w, "formula2")?;
+    write_option_string_id(w, rule.formula2)?;
+    w.write_all(b",")?;
+    write_json_key(w, "allow_blank")?;
+    write_bool(w, rule.allow_blank)?;
+    w.write_all(b"}")?;
+    Ok(())
+}
+
+fn write_conditional_format_rule(
+    w: &mut impl Write,
+    rule: &ConditionalFormatRule,
+) -> io::Result<()> {
+    w.write_all(b"{")?;
+    write_json_key(w, "ranges")?;
+    write_cell_ranges(w, &rule.ranges)?;
+    w.write_all(b",")?;
+    write_json_key(w, "kind")?;
+    write_string_id(w, rule.kind)?;
+    w.write_all(b",")?;
+    write_json_key(w, "operator")?;
+    write_option_string_id(w, rule.operator)?;
+    if !rule.formulas.is_empty() {
+        w.write_all(b",")?;
+        write_json_key(w, "formulas")?;
+        write_string_id_array(w, &rule.formulas)?;
+    }
+    w.write_all(b",")?;
+    write_json_key(w, "priority")?;
+    write_option_u32(w, rule.priority)?;
+    w.write_all(b",")?;
+    write_json_key(w, "stop_if_true")?;
+    write_bool(w, rule.stop_if_true)?;
+    w.write_all(b"}")?;
+    Ok(())
+}
+
+fn write_option_freeze_pane(w: &mut impl Write, pane: Option<FreezePane>) -> io::Result<()> {
+    let Some(pane) = pane else {
+        return w.write_all(b"null");
+    };
+    w.write_all(b"{")?;
+    write_json_key(w, "rows")?;
+    write_u32(w, pane.rows)?;
+    w.write_all(b",")?;
+    write_json_key(w, "cols")?;
+    write_u32(w, pane.cols)?;
+    w.write_all(b"}")?;
+    Ok(())
+}
+
+fn write_option_cell_range(w: &mut impl Write, range: Option<CellRange>) -> io::Result<()> {
+    match range {
+        Some(range) => write_json_string(w, &range.to_a1()),
+        None => w.write_all(b"null"),
+    }
+}
+
+fn write_sheet_visibility(w: &mut impl Write, visibility: SheetVisibility) -> io::Result<()> {
+    write_json_string_lit(
+        w,
+        match visibility {
+            SheetVisibility::Visible => "visible",
+            SheetVisibility::Hidden => "hidden",
+            SheetVisibility::VeryHidden => "very_hidden",
+        },
+    )
+}
+
+fn write_u128_hex_32(w: &mut impl Write, value: u128) -> io::Result<()> {
+    let mut buf = [0u8; 32];
+    for i in 0..32 {
+        let shift = 4 * (31 - i);
+        let nibble = ((value >> shift) & 0x0f) as usize;
+        buf[i] = HEX_LOWER[nibble];
+    }
+    w.write_all(&buf)
+}
+
+fn write_row_signature(w: &mut impl Write, sig: &RowSignature) -> io::Result<()> {
+    w.write_all(b"{")?;
+    write_json_key(w, "hash")?;
     w.write_all(b"\"")?;
-    w.write_all(s.as_bytes())?;
+    write_u128_hex_32(w, sig.hash)?;
     w.write_all(b"\"")?;
+    w.write_all(b"}")?;
     Ok(())
 }
 
-fn write_json_key(w: &mut impl Write, key: &'static str) -> io::Result<()> {
+fn write_col_signature(w: &mut impl Write, sig: &ColSignature) -> io::Result<()> {
+    w.write_all(b"{")?;
+    write_json_key(w, "hash")?;
     w.write_all(b"\"")?;
-    w.write_all(key.as_bytes())?;
-    w.write_all(b"\":")?;
+    write_u128_hex_32(w, sig.hash)?;
+    w.write_all(b"\"")?;
+    w.write_all(b"}")?;
     Ok(())
 }
 
-fn write_json_string_array(w: &mut impl Write, strings: &[String]) -> io::Result<()> {
+fn write_formula_diff_result(w: &mut impl Write, value: FormulaDiffResult) -> io::Result<()> {
+    let s = match value {
+        FormulaDiffResult::Unknown => "unknown",
+        FormulaDiffResult::Unchanged => "unchanged",
+        FormulaDiffResult::Added => "added",
+        FormulaDiffResult::Removed => "removed",
+        FormulaDiffResult::FormattingOnly => "formatting_only",
+        FormulaDiffResult::Filled => "filled",
+        FormulaDiffResult::SemanticChange => "semantic_change",
+        FormulaDiffResult::TextChange => "text_change",
+    };
+    write_json_string_lit(w, s)
+}
+
+fn write_formula_edit_array(w: &mut impl Write, edits: &[FormulaEdit]) -> io::Result<()> {
     w.write_all(b"[")?;
-    for (i, s) in strings.iter().enumerate() {
+    for (i, edit) in edits.iter().enumerate() {
         if i != 0 {
             w.write_all(b",")?;
         }
-        write_json_string(w, s)?;
+        w.write_all(b"{")?;
+        write_json_key(w, "kind")?;
+        write_json_string_lit(
+            w,
+            match edit.kind {
+                FormulaEditKind::FunctionRenamed => "function_renamed",
+                FormulaEditKind::ReferenceChanged => "reference_changed",
+                FormulaEditKind::OperatorChanged => "operator_changed",
+                FormulaEditKind::ValueChanged => "value_changed",
+                FormulaEditKind::Replaced => "replaced",
+                FormulaEditKind::Inserted => "inserted",
+                FormulaEditKind::Removed => "removed",
+            },
+        )?;
+        if let Some(function) = &edit.function {
+            w.write_all(b",")?;
+            write_json_key(w, "function")?;
+            write_json_string(w, function)?;
+        }
+        if let Some(argument) = edit.argument {
+            w.write_all(b",")?;
+            write_json_key(w, "argument")?;
+            write_u32(w, argument)?;
+        }
+        if let Some(from) = &edit.from {
+            w.write_all(b",")?;
+            write_json_key(w, "from")?;
+            write_json_string(w, from)?;
+        }
+        if let Some(to) = &edit.to {
+            w.write_all(b",")?;
+            write_json_key(w, "to")?;
+            write_json_string(w, to)?;
+        }
+        w.write_all(b"}")?;
     }
     w.write_all(b"]")?;
     Ok(())
 }
 
-fn write_u32(w: &mut impl Write, value: u32) -> io::Result<()> {
-    write_u64(w, value as u64)
+fn write_query_change_kind(w: &mut impl Write, value: QueryChangeKind) -> io::Result<()> {
+    let s = match value {
+        QueryChangeKind::Semantic => "semantic",
+        QueryChangeKind::FormattingOnly => "formatting_only",
+        QueryChangeKind::Renamed => "renamed",
+    };
+    write_json_string_lit(w, s)
 }
 
-fn write_u64(w: &mut impl Write, mut value: u64) -> io::Result<()> {
-    if value == 0 {
-        return w.write_all(b"0");
-    }
+#[cfg(feature = "model-diff")]
+fn write_expression_change_kind(w: &mut impl Write, value: ExpressionChangeKind) -> io::Result<()> {
+    let s = match value {
+        ExpressionChangeKind::Semantic => "semantic",
+        ExpressionChangeKind::FormattingOnly => "formatting_only",
+        ExpressionChangeKind::Unknown => "unknown",
+    };
+    write_json_string_lit(w, s)
+}
 
-    let mut buf = [0u8; 20];
-    let mut i = buf.len();
-    while value != 0 {
-        i -= 1;
-        buf[i] = b'0' + (value % 10) as u8;
-        value /= 10;
+#[cfg(feature = "model-diff")]
+fn write_dax_edit_array(w: &mut impl Write, edits: &[DaxEdit]) -> io::Result<()> {
+    w.write_all(b"[")?;
+    for (i, edit) in edits.iter().enumerate() {
+        if i != 0 {
+            w.write_all(b",")?;
+        }
+        w.write_all(b"{")?;
+        write_json_key(w, "kind")?;
+        write_json_string_lit(
+            w,
+            match edit.kind {
+                DaxEditKind::FunctionRenamed => "function_renamed",
+                DaxEditKind::ReferenceChanged => "reference_changed",
+                DaxEditKind::OperatorChanged => "operator_changed",
+                DaxEditKind::ValueChanged => "value_changed",
+                DaxEditKind::FilterAdded => "filter_added",
+                DaxEditKind::FilterRemoved => "filter_removed",
+                DaxEditKind::FilterChanged => "filter_changed",
+                DaxEditKind::Replaced => "replaced",
+                DaxEditKind::Inserted => "inserted",
+                DaxEditKind::Removed => "removed",
+            },
+        )?;
+        if let Some(function) = &edit.function {
+            w.write_all(b",")?;
+            write_json_key(w, "function")?;
+            write_json_string(w, function)?;
+        }
+        if let Some(argument) = edit.argument {
+            w.write_all(b",")?;
+            write_json_key(w, "argument")?;
+            write_u32(w, argument)?;
+        }
+        if let Some(from) = &edit.from {
+            w.write_all(b",")?;
+            write_json_key(w, "from")?;
+            write_json_string(w, from)?;
+        }
+        if let Some(to) = &edit.to {
+            w.write_all(b",")?;
+            write_json_key(w, "to")?;
+            write_json_string(w, to)?;
+        }
+        w.write_all(b"}")?;
     }
-    w.write_all(&buf[i..])
+    w.write_all(b"]")?;
+    Ok(())
 }
 
-fn write_option_u64(w: &mut impl Write, value: Option<u64>) -> io::Result<()> {
-    match value {
-        Some(v) => write_u64(w, v),
-        None => w.write_all(b"null"),
-    }
+fn write_vba_project_property(w: &mut impl Write, value: VbaProjectProperty) -> io::Result<()> {
+    let s = match value {
+        VbaProjectProperty::Name => "Name",
+        VbaProjectProperty::Description => "Description",
+        VbaProjectProperty::HelpFile => "HelpFile",
+        VbaProjectProperty::HelpContext => "HelpContext",
+        VbaProjectProperty::Constants => "Constants",
+        VbaProjectProperty::LockedForViewing => "LockedForViewing",
+        VbaProjectProperty::Password => "Password",
+    };
+    write_json_string_lit(w, s)
 }
 
-fn write_option_u32(w: &mut impl Write, value: Option<u32>) -> io::Result<()> {
-    match value {
-        Some(v) => write_u32(w, v),
-        None => w.write_all(b"null"),
+fn write_query_metadata_field(w: &mut impl Write, value: QueryMetadataField) -> io::Result<()> {
+    let s = match value {
+        QueryMetadataField::LoadToSheet => "LoadToSheet",
+        QueryMetadataField::LoadToModel => "LoadToModel",
+        QueryMetadataField::GroupPath => "GroupPath",
+        QueryMetadataField::ConnectionOnly => "ConnectionOnly",
+    };
+    write_json_string_lit(w, s)
+}
+
+#[cfg(feature = "model-diff")]
+fn write_model_column_property(w: &mut impl Write, value: ModelColumnProperty) -> io::Result<()> {
+    let s = match value {
+        ModelColumnProperty::Hidden => "hidden",
+        ModelColumnProperty::FormatString => "format_string",
+        ModelColumnProperty::SortBy => "sort_by",
+        ModelColumnProperty::SummarizeBy => "summarize_by",
+    };
+    write_json_string_lit(w, s)
+}
+
+#[cfg(feature = "model-diff")]
+fn write_relationship_property(w: &mut impl Write, value: RelationshipProperty) -> io::Result<()> {
+    let s = match value {
+        RelationshipProperty::CrossFilteringBehavior => "cross_filtering_behavior",
+        RelationshipProperty::Cardinality => "cardinality",
+        RelationshipProperty::IsActive => "is_active",
+    };
+    write_json_string_lit(w, s)
+}
+
+#[cfg(feature = "model-diff")]
+fn write_measure_property(w: &mut impl Write, value: MeasureProperty) -> io::Result<()> {
+    let s = match value {
+        MeasureProperty::FormatString => "format_string",
+        MeasureProperty::DisplayFolder => "display_folder",
+        MeasureProperty::Description => "description",
+        MeasureProperty::KpiTarget => "kpi_target",
+        MeasureProperty::KpiStatus => "kpi_status",
+        MeasureProperty::KpiTrend => "kpi_trend",
+    };
+    write_json_string_lit(w, s)
+}
+
+/// Writes the `object`, optional `table` and `name` members shared by the model object ops.
+#[cfg(feature = "model-diff")]
+fn write_model_object(
+    w: &mut impl Write,
+    object: ModelObjectKind,
+    table: Option<StringId>,
+    name: StringId,
+) -> io::Result<()> {
+    w.write_all(b",")?;
+    write_json_key(w, "object")?;
+    write_json_string_lit(
+        w,
+        match object {
+            ModelObjectKind::Role => "role",
+            ModelObjectKind::Hierarchy => "hierarchy",
+            ModelObjectKind::Partition => "partition",
+            ModelObjectKind::CalculationGroup => "calculation_group",
+            ModelObjectKind::CalculationItem => "calculation_item",
+            ModelObjectKind::Perspective => "perspective",
+            ModelObjectKind::Culture => "culture",
+        },
+    )?;
+    if let Some(table) = table {
+        w.write_all(b",")?;
+        write_json_key(w, "table")?;
+        write_string_id(w, table)?;
     }
+    w.write_all(b",")?;
+    write_json_key(w, "name")?;
+    write_string_id(w, name)
 }
 
-fn write_bool(w: &mut impl Write, value: bool) -> io::Result<()> {
-    if value {
-        w.write_all(b"true")
-    } else {
-        w.write_all(b"false")
-    }
+#[cfg(feature = "model-diff")]
+fn write_model_object_field(w: &mut impl Write, field: ModelObjectField) -> io::Result<()> {
+    let s = match field {
+        ModelObjectField::Description => "description",
+        ModelObjectField::ModelPermission => "model_permission",
+        ModelObjectField::RowFilter => "row_filter",
+        ModelObjectField::ObjectPermission => "object_permission",
+        ModelObjectField::Member => "member",
+        ModelObjectField::Hidden => "hidden",
+        ModelObjectField::DisplayFolder => "display_folder",
+        ModelObjectField::Level => "level",
+        ModelObjectField::LevelOrder => "level_order",
+        ModelObjectField::Mode => "mode",
+        ModelObjectField::SourceType => "source_type",
+        ModelObjectField::Source => "source",
+        ModelObjectField::Precedence => "precedence",
+        ModelObjectField::Expression => "expression",
+        ModelObjectField::FormatStringExpression => "format_string_expression",
+        ModelObjectField::Ordinal => "ordinal",
+        ModelObjectField::Translation => "translation",
+    };
+    write_json_string_lit(w, s)
 }
 
-fn write_f64(w: &mut impl Write, value: f64) -> io::Result<()> {
-    if !value.is_finite() {
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidInput,
-            "non-finite numbers are not supported in JSON output",
-        ));
+#[cfg(feature = "model-diff")]
+fn write_model_object_change_array(
+    w: &mut impl Write,
+    changes: &[ModelObjectChange],
+) -> io::Result<()> {
+    w.write_all(b"[")?;
+    for (i, change) in changes.iter().enumerate() {
+        if i != 0 {
+            w.write_all(b",")?;
+        }
+        w.write_all(b"{")?;
+        write_json_key(w, "field")?;
+        write_model_object_field(w, change.field)?;
+        if let Some(item) = change.item {
+            w.write_all(b",")?;
+            write_json_key(w, "item")?;
+            write_string_id(w, item)?;
+        }
+        w.write_all(b",")?;
+        write_json_key(w, "from")?;
+        write_option_string_id(w, change.from)?;
+        w.write_all(b",")?;
+        write_json_key(w, "to")?;
+        write_option_string_id(w, change.to)?;
+        w.write_all(b"}")?;
     }
-
-    let mut buf = ryu::Buffer::new();
-    let s = buf.format_finite(value);
-    w.write_all(s.as_bytes())
+    w.write_all(b"]")?;
+    Ok(())
 }
 
-fn write_string_id(w: &mut impl Write, value: StringId) -> io::Result<()> {
-    write_u32(w, value.0)
+fn write_vba_module_type(w: &mut impl Write, value: VbaModuleType) -> io::Result<()> {
+    let s = match value {
+        VbaModuleType::Standard => "standard",
+        VbaModuleType::Class => "class",
+        VbaModuleType::Form => "form",
+        VbaModuleType::Document => "document",
+    };
+    write_json_string_lit(w, s)
 }
 
-fn write_option_string_id(w: &mut impl Write, value: Option<StringId>) -> io::Result<()> {
-    match value {
-        Some(v) => write_string_id(w, v),
-        None => w.write_all(b"null"),
-    }
+fn write_chart_field(w: &mut impl Write, value: ChartField) -> io::Result<()> {
+    let s = match value {
+        ChartField::Title => "Title",
+        ChartField::Legend => "Legend",
+        ChartField::Series => "Series",
+        ChartField::SeriesName => "SeriesName",
+        ChartField::SeriesType => "SeriesType",
+        ChartField::SeriesCategories => "SeriesCategories",
+        ChartField::SeriesValues => "SeriesValues",
+        ChartField::Axis => "Axis",
+        ChartField::AxisTitle => "AxisTitle",
+        ChartField::AxisPosition => "AxisPosition",
+        ChartField::AxisMin => "AxisMin",
+        ChartField::AxisMax => "AxisMax",
+        ChartField::AxisLogBase => "AxisLogBase",
+        ChartField::AxisOrientation => "AxisOrientation",
+        ChartField::AxisDeleted => "AxisDeleted",
+    };
+    write_json_string_lit(w, s)
 }
 
-fn write_u32_array(w: &mut impl Write, values: &[u32]) -> io::Result<()> {
+fn write_chart_change_array(w: &mut impl Write, changes: &[ChartChange]) -> io::Result<()> {
     w.write_all(b"[")?;
-    for (i, v) in values.iter().enumerate() {
+    for (i, change) in changes.iter().enumerate() {
         if i != 0 {
             w.write_all(b",")?;
         }
-        write_u32(w, *v)?;
+        w.write_all(b"{")?;
+        write_json_key(w, "field")?;
+        write_chart_field(w, change.field)?;
+        if let Some(series) = change.series {
+            w.write_all(b",")?;
+            write_json_key(w, "series")?;
+            write_u32(w, series)?;
+        }
+        if let Some(axis) = change.axis {
+            w.write_all(b",")?;
+            write_json_key(w, "axis")?;
+            write_string_id(w, axis)?;
+        }
+        w.write_all(b",")?;
+        write_json_key(w, "from")?;
+        write_option_string_id(w, change.from)?;
+        w.write_all(b",")?;
+        write_json_key(w, "to")?;
+        write_option_string_id(w, change.to)?;
+        w.write_all(b"}")?;
     }
     w.write_all(b"]")?;
     Ok(())
 }
 
-fn write_string_id_array(w: &mut impl Write, values: &[StringId]) -> io::Result<()> {
+fn write_pivot_source(w: &mut impl Write, source: &PivotSource) -> io::Result<()> {
+    w.write_all(b"{")?;
+    write_json_key(w, "kind")?;
+    write_string_id(w, source.kind)?;
+    w.write_all(b",")?;
+    write_json_key(w, "sheet")?;
+    write_option_string_id(w, source.sheet)?;
+    w.write_all(b",")?;
+    write_json_key(w, "range")?;
+    match source.range {
+        Some(range) => write_json_string(w, &range.to_a1())?,
+        None => w.write_all(b"null")?,
+    }
+    w.write_all(b",")?;
+    write_json_key(w, "name")?;
+    write_option_string_id(w, source.name)?;
+    w.write_all(b",")?;
+    write_json_key(w, "connection_id")?;
+    write_option_u32(w, source.connection_id)?;
+    w.write_all(b"}")?;
+    Ok(())
+}
+
+fn write_pivot_area(w: &mut impl Write, area: PivotArea) -> io::Result<()> {
+    let s = match area {
+        PivotArea::Rows => "Rows",
+        PivotArea::Columns => "Columns",
+        PivotArea::Pages => "Pages",
+        PivotArea::Values => "Values",
+        PivotArea::Filters => "Filters",
+        PivotArea::CalculatedFields => "CalculatedFields",
+    };
+    write_json_string_lit(w, s)
+}
+
+fn write_pivot_layout_change_array(
+    w: &mut impl Write,
+    changes: &[PivotLayoutChange],
+) -> io::Result<()> {
     w.write_all(b"[")?;
-    for (i, v) in values.iter().enumerate() {
+    for (i, change) in changes.iter().enumerate() {
         if i != 0 {
             w.write_all(b",")?;
         }
-        write_string_id(w, *v)?;
+        w.write_all(b"{")?;
+        write_json_key(w, "area")?;
+        write_pivot_area(w, change.area)?;
+        if let Some(field) = change.field {
+            w.write_all(b",")?;
+            write_json_key(w, "field")?;
+            write_string_id(w, field)?;
+        }
+        w.write_all(b",")?;
+        write_json_key(w, "from")?;
+        write_option_string_id(w, change.from)?;
+        w.write_all(b",")?;
+        write_json_key(w, "to")?;
+        write_option_string_id(w, change.to)?;
+        w.write_all(b"}")?;
     }
     w.write_all(b"]")?;
     Ok(())
 }
 
-fn write_cell_address(w: &mut impl Write, addr: CellAddress) -> io::Result<()> {
-    w.write_all(b"\"")?;
-
-    // Same behavior as `addressing::index_to_address`, but without allocating.
-    let mut col_index = addr.col;
-    let mut letters = [0u8; 16];
-    let mut letters_len: usize = 0;
-    loop {
-        let rem = (col_index % 26) as u8;
-        letters[letters_len] = b'A' + rem;
-        letters_len += 1;
-        if col_index < 26 {
-            break;
-        }
-        col_index = col_index / 26 - 1;
-    }
+fn write_external_link(w: &mut impl Write, link: &ExternalLink) -> io::Result<()> {
+    w.write_all(b"{")?;
+    write_json_key(w, "index")?;
+    write_u32(w, link.index)?;
+    w.write_all(b",")?;
+    write_json_key(w, "kind")?;
+    write_string_id(w, link.kind)?;
+    w.write_all(b",")?;
+    write_json_key(w, "target")?;
+    write_string_id(w, link.target)?;
+    w.write_all(b",")?;
+    write_json_key(w, "sheet_names")?;
+    write_string_id_array(w, &link.sheet_names)?;
+    w.write_all(b"}")?;
+    Ok(())
+}
 
-    let mut buf = [0u8; 32];
-    let mut pos = 0usize;
-    for i in (0..letters_len).rev() {
-        buf[pos] = letters[i];
-        pos += 1;
-    }
+fn write_connection_field(w: &mut impl Write, field: ConnectionField) -> io::Result<()> {
+    let s = match field {
+        ConnectionField::Name => "Name",
+        ConnectionField::Kind => "Kind",
+        ConnectionField::Connection => "Connection",
+        ConnectionField::Command => "Command",
+        ConnectionField::RefreshOnLoad => "RefreshOnLoad",
+        ConnectionField::BackgroundRefresh => "BackgroundRefresh",
+        ConnectionField::RefreshInterval => "RefreshInterval",
+        ConnectionField::SavePassword => "SavePassword",
+    };
+    write_json_string_lit(w, s)
+}
 
-    let mut row_num = addr.row.saturating_add(1);
-    let mut digits = [0u8; 10];
-    let mut digits_len: usize = 0;
-    while row_num != 0 {
-        digits[digits_len] = b'0' + (row_num % 10) as u8;
-        digits_len += 1;
-        row_num /= 10;
-    }
-    for i in (0..digits_len).rev() {
-        buf[pos] = digits[i];
-        pos += 1;
+fn write_connection_change_array(
+    w: &mut impl Write,
+    changes: &[ConnectionChange],
+) -> io::Result<()> {
+    w.write_all(b"[")?;
+    for (i, change) in changes.iter().enumerate() {
+        if i != 0 {
+            w.write_all(b",")?;
+        }
+        w.write_all(b"{")?;
+        write_json_key(w, "field")?;
+        write_connection_field(w, change.field)?;
+        w.write_all(b",")?;
+        write_json_key(w, "from")?;
+        write_option_string_id(w, change.from)?;
+        w.write_all(b",")?;
+        write_json_key(w, "to")?;
+        write_option_string_id(w, change.to)?;
+        w.write_all(b"}")?;
     }
-
-    w.write_all(&buf[..pos])?;
-    w.write_all(b"\"")?;
+    w.write_all(b"]")?;
     Ok(())
 }
 
-fn write_cell_value(w: &mut impl Write, value: &CellValue) -> io::Result<()> {
-    match value {
-        CellValue::Blank => write_json_string_lit(w, "Blank"),
-        CellValue::Number(n) => {
-            w.write_all(b"{")?;
-            write_json_key(w, "Number")?;
-            write_f64(w, *n)?;
-            w.write_all(b"}")?;
-            Ok(())
-        }
-        CellValue::Text(id) => {
-            w.write_all(b"{")?;
-            write_json_key(w, "Text")?;
-            write_string_id(w, *id)?;
-            w.write_all(b"}")?;
-            Ok(())
-        }
-        CellValue::Bool(b) => {
-            w.write_all(b"{")?;
-            write_json_key(w, "Bool")?;
-            write_bool(w, *b)?;
-            w.write_all(b"}")?;
-            Ok(())
-        }
-        CellValue::Error(id) => {
-            w.write_all(b"{")?;
-            write_json_key(w, "Error")?;
-            write_string_id(w, *id)?;
-            w.write_all(b"}")?;
-            Ok(())
-        }
+/// Writes the `object`, optional `page` and `name` members shared by the report ops.
+fn write_report_object(
+    w: &mut impl Write,
+    object: PbipEntityKind,
+    page: Option<StringId>,
+    name: StringId,
+) -> io::Result<()> {
+    w.write_all(b",")?;
+    write_json_key(w, "object")?;
+    write_json_string_lit(w, object.as_str())?;
+    if let Some(page) = page {
+        w.write_all(b",")?;
+        write_json_key(w, "page")?;
+        write_string_id(w, page)?;
     }
+    w.write_all(b",")?;
+    write_json_key(w, "name")?;
+    write_string_id(w, name)
 }
 
-fn write_option_cell_value(w: &mut impl Write, value: Option<&CellValue>) -> io::Result<()> {
-    match value {
-        Some(v) => write_cell_value(w, v),
-        None => w.write_all(b"null"),
-    }
+fn write_report_field(w: &mut impl Write, field: ReportField) -> io::Result<()> {
+    let s = match field {
+        ReportField::DisplayName => "DisplayName",
+        ReportField::Order => "Order",
+        ReportField::Size => "Size",
+        ReportField::Hidden => "Hidden",
+        ReportField::Theme => "Theme",
+        ReportField::VisualType => "VisualType",
+        ReportField::Title => "Title",
+        ReportField::Field => "Field",
+        ReportField::Filter => "Filter",
+        ReportField::Position => "Position",
+        ReportField::Page => "Page",
+    };
+    write_json_string_lit(w, s)
 }
 
-fn write_cell_value_opt_array(w: &mut impl Write, values: &[Option<CellValue>]) -> io::Result<()> {
+fn write_report_change_array(w: &mut impl Write, changes: &[ReportChange]) -> io::Result<()> {
     w.write_all(b"[")?;
-    for (i, v) in values.iter().enumerate() {
+    for (i, change) in changes.iter().enumerate() {
         if i != 0 {
             w.write_all(b",")?;
         }
-        match v {
-            Some(cv) => write_cell_value(w, cv)?,
-            None => w.write_all(b"null")?,
-        }
+        w.write_all(b"{")?;
+        write_json_key(w, "field")?;
+        write_report_field(w, change.field)?;
+        w.write_all(b",")?;
+        write_json_key(w, "from")?;
+        write_option_string_id(w, change.from)?;
+        w.write_all(b",")?;
+        write_json_key(w, "to")?;
+        write_option_string_id(w, change.to)?;
+        w.write_all(b"}")?;
     }
     w.write_all(b"]")?;
     Ok(())
 }
 
-fn write_cell_snapshot(w: &mut impl Write, snap: &CellSnapshot) -> io::Result<()> {
-    w.write_all(b"{")?;
-    write_json_key(w, "addr")?;
-    write_cell_address(w, snap.addr)?;
-    w.write_all(b",")?;
-    write_json_key(w, "value")?;
-    write_option_cell_value(w, snap.value.as_ref())?;
-    w.write_all(b",")?;
-    write_json_key(w, "formula")?;
-    write_option_string_id(w, snap.formula)?;
-    w.write_all(b"}")?;
-    Ok(())
+fn write_workbook_setting(w: &mut impl Write, setting: WorkbookSetting) -> io::Result<()> {
+    let s = match setting {
+        WorkbookSetting::Date1904 => "Date1904",
+        WorkbookSetting::CodeName => "CodeName",
+        WorkbookSetting::CalcMode => "CalcMode",
+        WorkbookSetting::FullCalcOnLoad => "FullCalcOnLoad",
+        WorkbookSetting::CalcOnSave => "CalcOnSave",
+        WorkbookSetting::Iterate => "Iterate",
+        WorkbookSetting::IterateCount => "IterateCount",
+        WorkbookSetting::IterateDelta => "IterateDelta",
+        WorkbookSetting::FullPrecision => "FullPrecision",
+    };
+    write_json_string_lit(w, s)
 }
 
-fn write_u128_hex_32(w: &mut impl Write, value: u128) -> io::Result<()> {
-    let mut buf = [0u8; 32];
-    for i in 0..32 {
-        let shift = 4 * (31 - i);
-        let nibble = ((value >> shift) & 0x0f) as usize;
-        buf[i] = HEX_LOWER[nibble];
+fn write_workbook_setting_change_array(
+    w: &mut impl Write,
+    changes: &[WorkbookSettingChange],
+) -> io::Result<()> {
+    w.write_all(b"[")?;
+    for (i, change) in changes.iter().enumerate() {
+        if i != 0 {
+            w.write_all(b",")?;
+        }
+        w.write_all(b"{")?;
+        write_json_key(w, "setting")?;
+        write_workbook_setting(w, change.setting)?;
+        w.write_all(b",")?;
+        write_json_key(w, "from")?;
+        write_option_string_id(w, change.from)?;
+        w.write_all(b",")?;
+        write_json_key(w, "to")?;
+        write_option_string_id(w, change.to)?;
+        w.write_all(b"}")?;
     }
-    w.write_all(&buf)
-}
-
-fn write_row_signature(w: &mut impl Write, sig: &RowSignature) -> io::Result<()> {
-    w.write_all(b"{")?;
-    write_json_key(w, "hash")?;
-    w.write_all(b"\"")?;
-    write_u128_hex_32(w, sig.hash)?;
-    w.write_all(b"\"")?;
-    w.write_all(b"}")?;
+    w.write_all(b"]")?;
     Ok(())
 }
 
-fn write_col_signature(w: &mut impl Write, sig: &ColSignature) -> io::Result<()> {
+fn write_option_workbook_protection(
+    w: &mut impl Write,
+    protection: Option<WorkbookProtection>,
+) -> io::Result<()> {
+    let Some(protection) = protection else {
+        return w.write_all(b"null");
+    };
     w.write_all(b"{")?;
-    write_json_key(w, "hash")?;
-    w.write_all(b"\"")?;
-    write_u128_hex_32(w, sig.hash)?;
-    w.write_all(b"\"")?;
+    write_json_key(w, "lock_structure")?;
+    write_bool(w, protection.lock_structure)?;
+    w.write_all(b",")?;
+    write_json_key(w, "lock_windows")?;
+    write_bool(w, protection.lock_windows)?;
+    w.write_all(b",")?;
+    write_json_key(w, "lock_revision")?;
+    write_bool(w, protection.lock_revision)?;
+    w.write_all(b",")?;
+    write_json_key(w, "has_password")?;
+    write_bool(w, protection.has_password)?;
     w.write_all(b"}")?;
     Ok(())
 }
 
-fn write_formula_diff_result(w: &mut impl Write, value: FormulaDiffResult) -> io::Result<()> {
-    let s = match value {
-        FormulaDiffResult::Unknown => "unknown",
-        FormulaDiffResult::Unchanged => "unchanged",
-        FormulaDiffResult::Added => "added",
-        FormulaDiffResult::Removed => "removed",
-        FormulaDiffResult::FormattingOnly => "formatting_only",
-        FormulaDiffResult::Filled => "filled",
-        FormulaDiffResult::SemanticChange => "semantic_change",
-        FormulaDiffResult::TextChange => "text_change",
-    };
-    write_json_string_lit(w, s)
-}
-
-fn write_query_change_kind(w: &mut impl Write, value: QueryChangeKind) -> io::Result<()> {
-    let s = match value {
-        QueryChangeKind::Semantic => "semantic",
-        QueryChangeKind::FormattingOnly => "formatting_only",
-        QueryChangeKind::Renamed => "renamed",
-    };
-    write_json_string_lit(w, s)
-}
-
-#[cfg(feature = "model-diff")]
-fn write_expression_change_kind(w: &mut impl Write, value: ExpressionChangeKind) -> io::Result<()> {
-    let s = match value {
-        ExpressionChangeKind::Semantic => "semantic",
-        ExpressionChangeKind::FormattingOnly => "formatting_only",
-        ExpressionChangeKind::Unknown => "unknown",
+fn write_option_sheet_protection(
+    w: &mut impl Write,
+    protection: Option<&SheetProtection>,
+) -> io::Result<()> {
+    let Some(protection) = protection else {
+        return w.write_all(b"null");
     };
-    write_json_string_lit(w, s)
+    w.write_all(b"{")?;
+    write_json_key(w, "has_password")?;
+    write_bool(w, protection.has_password)?;
+    w.write_all(b",")?;
+    write_json_key(w, "allowed")?;
+    write_string_id_array(w, &protection.allowed)?;
+    w.write_all(b"}")?;
+    Ok(())
 }
 
-fn write_query_metadata_field(w: &mut impl Write, value: QueryMetadataField) -> io::Result<()> {
-    let s = match value {
-        QueryMetadataField::LoadToSheet => "LoadToSheet",
-        QueryMetadataField::LoadToModel => "LoadToModel",
-        QueryMetadataField::GroupPath => "GroupPath",
-        QueryMetadataField::ConnectionOnly => "ConnectionOnly",
-    };
-    write_json_string_lit(w, s)
+fn write_vba_attribute_change_array(
+    w: &mut impl Write,
+    changes: &[VbaAttributeChange],
+) -> io::Result<()> {
+    w.write_all(b"[")?;
+    for (i, change) in changes.iter().enumerate() {
+        if i != 0 {
+            w.write_all(b",")?;
+        }
+        w.write_all(b"{")?;
+        write_json_key(w, "name")?;
+        write_string_id(w, change.name)?;
+        w.write_all(b",")?;
+        write_json_key(w, "from")?;
+        write_option_string_id(w, change.from)?;
+        w.write_all(b",")?;
+        write_json_key(w, "to")?;
+        write_option_string_id(w, change.to)?;
+        w.write_all(b"}")?;
+    }
+    w.write_all(b"]")?;
+    Ok(())
 }
 
-#[cfg(feature = "model-diff")]
-fn write_model_column_property(w: &mut impl Write, value: ModelColumnProperty) -> io::Result<()> {
-    let s = match value {
-        ModelColumnProperty::Hidden => "hidden",
-        ModelColumnProperty::FormatString => "format_string",
-        ModelColumnProperty::SortBy => "sort_by",
-        ModelColumnProperty::SummarizeBy => "summarize_by",
-    };
-    write_json_string_lit(w, s)
+fn write_vba_procedure_change_array(
+    w: &mut impl Write,
+    changes: &[VbaProcedureChange],
+) -> io::Result<()> {
+    w.write_all(b"[")?;
+    for (i, change) in changes.iter().enumerate() {
+        if i != 0 {
+            w.write_all(b",")?;
+        }
+        w.write_all(b"{")?;
+        write_json_key(w, "name")?;
+        write_string_id(w, change.name)?;
+        w.write_all(b",")?;
+        write_json_key(w, "kind")?;
+        write_json_string_lit(
+            w,
+            match change.kind {
+                VbaProcedureKind::Sub => "sub",
+                VbaProcedureKind::Function => "function",
+                VbaProcedureKind::PropertyGet => "property_get",
+                VbaProcedureKind::PropertyLet => "property_let",
+                VbaProcedureKind::PropertySet => "property_set",
+            },
+        )?;
+        w.write_all(b",")?;
+        write_json_key(w, "change")?;
+        write_json_string_lit(
+            w,
+            match change.change {
+                VbaProcedureChangeKind::Added => "added",
+                VbaProcedureChangeKind::Removed => "removed",
+                VbaProcedureChangeKind::Modified => "modified",
+            },
+        )?;
+        w.write_all(b"}")?;
+    }
+    w.write_all(b"]")?;
+    Ok(())
 }
 
-#[cfg(feature = "model-diff")]
-fn write_relationship_property(w: &mut impl Write, value: RelationshipProperty) -> io::Result<()> {
-    let s = match value {
-        RelationshipProperty::CrossFilteringBehavior => "cross_filtering_behavior",
-        RelationshipProperty::Cardinality => "cardinality",
-        RelationshipProperty::IsActive => "is_active",
-    };
-    write_json_string_lit(w, s)
+fn write_text_hunk_array(w: &mut impl Write, hunks: &[TextHunk]) -> io::Result<()> {
+    w.write_all(b"[")?;
+    for (i, hunk) in hunks.iter().enumerate() {
+        if i != 0 {
+            w.write_all(b",")?;
+        }
+        w.write_all(b"{")?;
+        write_json_key(w, "old_start")?;
+        write_u32(w, hunk.old_start)?;
+        w.write_all(b",")?;
+        write_json_key(w, "old_len")?;
+        write_u32(w, hunk.old_len)?;
+        w.write_all(b",")?;
+        write_json_key(w, "new_start")?;
+        write_u32(w, hunk.new_start)?;
+        w.write_all(b",")?;
+        write_json_key(w, "new_len")?;
+        write_u32(w, hunk.new_len)?;
+        if let Some(procedure) = hunk.procedure {
+            w.write_all(b",")?;
+            write_json_key(w, "procedure")?;
+            write_string_id(w, procedure)?;
+        }
+        w.write_all(b",")?;
+        write_json_key(w, "lines")?;
+        w.write_all(b"[")?;
+        for (j, line) in hunk.lines.iter().enumerate() {
+            if j != 0 {
+                w.write_all(b",")?;
+            }
+            w.write_all(b"{")?;
+            write_json_key(w, "kind")?;
+            write_json_string_lit(
+                w,
+                match line.kind {
+                    TextLineKind::Context => "context",
+                    TextLineKind::Removed => "removed",
+                    TextLineKind::Added => "added",
+                },
+            )?;
+            w.write_all(b",")?;
+            write_json_key(w, "text")?;
+            write_string_id(w, line.text)?;
+            w.write_all(b"}")?;
+        }
+        w.write_all(b"]")?;
+        w.write_all(b"}")?;
+    }
+    w.write_all(b"]")?;
+    Ok(())
 }
 
 fn write_step_type(w: &mut impl Write, value: StepType) -> io::Result<()> {
@@ -1498,9 +3214,9 @@ fn write_ast_move_hint_array(w: &mut impl Write, hints: &[AstMoveHint]) -> io::R
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::diff::{DiffReport, SheetId};
+    use crate::diff::{DiffReport, SheetId, TextLine};
     use crate::string_pool::StringPool;
-    use crate::workbook::CellSnapshot;
+    use crate::workbook::{CellSnapshot, CommentReply};
 
     fn sid(v: u32) -> StringId {
         StringId(v)
@@ -1524,6 +3240,37 @@ mod tests {
 
     fn sample_ops() -> Vec<DiffOp> {
         let addr = CellAddress::from_indices(0, 0);
+        let picture = DrawingObject {
+            id: 2,
+            kind: DrawingObjectKind::Picture,
+            name: sid(37),
+            anchor: DrawingAnchor {
+                from: DrawingMarker {
+                    row: 1,
+                    col: 1,
+                    row_offset: 0,
+                    col_offset: 9525,
+                },
+                to: Some(DrawingMarker {
+                    row: 8,
+                    col: 4,
+                    row_offset: 0,
+                    col_offset: 0,
+                }),
+            },
+            text: None,
+            macro_name: None,
+            image_hash: Some(0x1234_5678_9abc_def0),
+        };
+        let button = DrawingObject {
+            id: 3,
+            kind: DrawingObjectKind::FormControl,
+            name: sid(38),
+            text: Some(sid(39)),
+            macro_name: Some(sid(40)),
+            image_hash: None,
+            ..picture.clone()
+        };
         vec![
             DiffOp::SheetAdded { sheet: sheet(1) },
             DiffOp::SheetRemoved { sheet: sheet(2) },
@@ -1555,6 +3302,26 @@ mod tests {
                 left_rows: vec![1, 2, 3],
                 right_rows: vec![4, 5],
             },
+            DiffOp::RecordChanged {
+                sheet: sheet(9),
+                key: vec![Some(CellValue::Number(1001.0))],
+                old_row: 3,
+                new_row: 4,
+                fields: vec![
+                    FieldChange {
+                        col: 2,
+                        field: Some(sid(11)),
+                        from: Some(CellValue::Number(10.0)),
+                        to: Some(CellValue::Number(12.0)),
+                    },
+                    FieldChange {
+                        col: 5,
+                        field: None,
+                        from: None,
+                        to: Some(CellValue::Text(sid(12))),
+                    },
+                ],
+            },
             DiffOp::RowReplaced {
                 sheet: sheet(1),
                 row_idx: 123,
@@ -1608,10 +3375,347 @@ mod tests {
                 from: snapshot(addr, Some(CellValue::Number(1.5)), Some(sid(99))),
                 to: snapshot(addr, Some(CellValue::Bool(true)), None),
                 formula_diff: FormulaDiffResult::SemanticChange,
+                formula_edits: vec![
+                    FormulaEdit {
+                        kind: FormulaEditKind::ReferenceChanged,
+                        function: Some("SUM".to_string()),
+                        argument: Some(2),
+                        from: Some("B2".to_string()),
+                        to: Some("B3".to_string()),
+                    },
+                    FormulaEdit {
+                        kind: FormulaEditKind::Inserted,
+                        function: None,
+                        argument: None,
+                        from: None,
+                        to: Some("\"a\\b\"".to_string()),
+                    },
+                ],
+            },
+            DiffOp::CellStyleChanged {
+                sheet: sheet(1),
+                addr,
+                from: CellStyle::default(),
+                to: CellStyle {
+                    number_format: Some(sid(5)),
+                    fill: Some(sid(6)),
+                    ..CellStyle::default()
+                },
+            },
+            DiffOp::CommentAdded {
+                sheet: sheet(1),
+                addr,
+                comment: CellComment {
+                    kind: CommentKind::Note,
+                    author: None,
+                    text: sid(7),
+                    resolved: false,
+                    replies: Vec::new(),
+                },
+            },
+            DiffOp::CommentRemoved {
+                sheet: sheet(1),
+                addr,
+                comment: CellComment {
+                    kind: CommentKind::Note,
+                    author: Some(sid(8)),
+                    text: sid(7),
+                    resolved: false,
+                    replies: Vec::new(),
+                },
+            },
+            DiffOp::CommentChanged {
+                sheet: sheet(1),
+                addr,
+                from: CellComment {
+                    kind: CommentKind::Threaded,
+                    author: Some(sid(8)),
+                    text: sid(7),
+                    resolved: false,
+                    replies: Vec::new(),
+                },
+                to: CellComment {
+                    kind: CommentKind::Threaded,
+                    author: Some(sid(8)),
+                    text: sid(7),
+                    resolved: true,
+                    replies: vec![CommentReply {
+                        author: None,
+                        text: sid(9),
+                    }],
+                },
+            },
+            DiffOp::DataValidationAdded {
+                sheet: sheet(1),
+                rule: DataValidationRule {
+                    ranges: vec![CellRange::new(addr, CellAddress::from_indices(9, 1))],
+                    kind: sid(20),
+                    operator: None,
+                    formula1: Some(sid(21)),
+                    formula2: None,
+                    allow_blank: true,
+                },
+            },
+            DiffOp::DataValidationRemoved {
+                sheet: sheet(1),
+                rule: DataValidationRule {
+                    ranges: vec![CellRange::new(addr, addr)],
+                    kind: sid(22),
+                    operator: Some(sid(23)),
+                    formula1: Some(sid(24)),
+                    formula2: Some(sid(25)),
+                    allow_blank: false,
+                },
+            },
+            DiffOp::DataValidationChanged {
+                sheet: sheet(1),
+                from: DataValidationRule {
+                    ranges: vec![CellRange::new(addr, addr)],
+                    kind: sid(20),
+                    operator: None,
+                    formula1: Some(sid(21)),
+                    formula2: None,
+                    allow_blank: false,
+                },
+                to: DataValidationRule {
+                    ranges: vec![CellRange::new(addr, addr)],
+                    kind: sid(20),
+                    operator: None,
+                    formula1: Some(sid(26)),
+                    formula2: None,
+                    allow_blank: false,
+                },
+            },
+            DiffOp::ConditionalFormatAdded {
+                sheet: sheet(1),
+                rule: ConditionalFormatRule {
+                    ranges: vec![
+                        CellRange::new(addr, CellAddress::from_indices(4, 2)),
+                        CellRange::new(addr, addr),
+                    ],
+                    kind: sid(27),
+                    operator: Some(sid(23)),
+                    formulas: vec![sid(28)],
+                    priority: Some(1),
+                    stop_if_true: true,
+                },
+            },
+            DiffOp::ConditionalFormatRemoved {
+                sheet: sheet(1),
+                rule: ConditionalFormatRule {
+                    ranges: vec![CellRange::new(addr, addr)],
+                    kind: sid(29),
+                    operator: None,
+                    formulas: Vec::new(),
+                    priority: None,
+                    stop_if_true: false,
+                },
+            },
+            DiffOp::ConditionalFormatChanged {
+                sheet: sheet(1),
+                from: ConditionalFormatRule {
+                    ranges: vec![CellRange::new(addr, addr)],
+                    kind: sid(27),
+                    operator: None,
+                    formulas: vec![sid(28)],
+                    priority: Some(2),
+                    stop_if_true: false,
+                },
+                to: ConditionalFormatRule {
+                    ranges: vec![CellRange::new(addr, addr)],
+                    kind: sid(27),
+                    operator: None,
+                    formulas: vec![sid(28), sid(30)],
+                    priority: Some(1),
+                    stop_if_true: false,
+                },
+            },
+            DiffOp::MergedRangeAdded {
+                sheet: sheet(1),
+                range: CellRange::new(addr, CellAddress::from_indices(0, 2)),
+            },
+            DiffOp::MergedRangeRemoved {
+                sheet: sheet(1),
+                range: CellRange::new(addr, CellAddress::from_indices(1, 0)),
+            },
+            DiffOp::RowVisibilityChanged {
+                sheet: sheet(1),
+                row_idx: 4,
+                hidden: true,
+            },
+            DiffOp::ColumnVisibilityChanged {
+                sheet: sheet(1),
+                col_idx: 2,
+                hidden: false,
+            },
+            DiffOp::RowOutlineChanged {
+                sheet: sheet(1),
+                row_idx: 5,
+                from: 0,
+                to: 2,
+            },
+            DiffOp::ColumnOutlineChanged {
+                sheet: sheet(1),
+                col_idx: 3,
+                from: 1,
+                to: 0,
+            },
+            DiffOp::RowHeightChanged {
+                sheet: sheet(1),
+                row_idx: 6,
+                from: None,
+                to: Some(600),
+            },
+            DiffOp::ColumnWidthChanged {
+                sheet: sheet(1),
+                col_idx: 1,
+                from: Some(2340),
+                to: None,
+            },
+            DiffOp::FreezePaneChanged {
+                sheet: sheet(1),
+                from: None,
+                to: Some(FreezePane { rows: 1, cols: 0 }),
+            },
+            DiffOp::SpillRangeChanged {
+                sheet: sheet(1),
+                addr,
+                from: Some(CellRange::new(addr, CellAddress::from_indices(4, 2))),
+                to: None,
+            },
+            DiffOp::SheetVisibilityChanged {
+                sheet: sheet(1),
+                from: SheetVisibility::Visible,
+                to: SheetVisibility::VeryHidden,
+            },
+            DiffOp::SheetTabColorChanged {
+                sheet: sheet(1),
+                from: Some(sid(31)),
+                to: None,
+            },
+            DiffOp::SheetProtectionChanged {
+                sheet: sheet(1),
+                from: None,
+                to: Some(SheetProtection {
+                    has_password: true,
+                    allowed: vec![sid(40)],
+                }),
+            },
+            DiffOp::ExcelTableAdded {
+                sheet: sheet(1),
+                name: sid(32),
+                range: CellRange::new(addr, CellAddress::from_indices(3, 2)),
+            },
+            DiffOp::ExcelTableRemoved {
+                sheet: sheet(1),
+                name: sid(33),
+                range: CellRange::new(addr, CellAddress::from_indices(1, 1)),
+            },
+            DiffOp::ExcelTableRenamed {
+                sheet: sheet(1),
+                from: sid(32),
+                to: sid(34),
+            },
+            DiffOp::ExcelTableResized {
+                sheet: sheet(1),
+                name: sid(34),
+                from: CellRange::new(addr, CellAddress::from_indices(3, 2)),
+                to: CellRange::new(addr, CellAddress::from_indices(5, 3)),
+            },
+            DiffOp::ExcelTableColumnRenamed {
+                sheet: sheet(1),
+                table: sid(34),
+                from: sid(35),
+                to: sid(36),
+            },
+            DiffOp::DrawingObjectAdded {
+                sheet: sheet(1),
+                object: picture.clone(),
+            },
+            DiffOp::DrawingObjectRemoved {
+                sheet: sheet(1),
+                object: button.clone(),
+            },
+            DiffOp::DrawingObjectMoved {
+                sheet: sheet(1),
+                name: sid(37),
+                from: picture.anchor,
+                to: DrawingAnchor {
+                    from: DrawingMarker {
+                        row: 4,
+                        col: 1,
+                        row_offset: -12700,
+                        col_offset: 0,
+                    },
+                    to: None,
+                },
+            },
+            DiffOp::DrawingObjectChanged {
+                sheet: sheet(1),
+                from: button.clone(),
+                to: DrawingObject {
+                    macro_name: None,
+                    ..button
+                },
             },
             DiffOp::VbaModuleAdded { name: sid(1) },
             DiffOp::VbaModuleRemoved { name: sid(2) },
-            DiffOp::VbaModuleChanged { name: sid(3) },
+            DiffOp::VbaModuleChanged {
+                name: sid(3),
+                module_type: None,
+                attributes: Vec::new(),
+                procedures: Vec::new(),
+                hunks: Vec::new(),
+            },
+            DiffOp::VbaModuleChanged {
+                name: sid(3),
+                module_type: Some((VbaModuleType::Standard, VbaModuleType::Document)),
+                attributes: vec![VbaAttributeChange {
+                    name: sid(4),
+                    from: Some(sid(5)),
+                    to: None,
+                }],
+                procedures: vec![VbaProcedureChange {
+                    name: sid(6),
+                    kind: VbaProcedureKind::PropertyLet,
+                    change: VbaProcedureChangeKind::Modified,
+                }],
+                hunks: vec![TextHunk {
+                    old_start: 4,
+                    old_len: 2,
+                    new_start: 4,
+                    new_len: 1,
+                    procedure: Some(sid(6)),
+                    lines: vec![
+                        TextLine {
+                            kind: TextLineKind::Context,
+                            text: sid(7),
+                        },
+                        TextLine {
+                            kind: TextLineKind::Removed,
+                            text: sid(8),
+                        },
+                    ],
+                }],
+            },
+            DiffOp::VbaReferenceAdded {
+                name: sid(4),
+                libid: sid(5),
+            },
+            DiffOp::VbaReferenceRemoved {
+                name: sid(4),
+                libid: sid(5),
+            },
+            DiffOp::VbaReferenceChanged {
+                name: sid(4),
+                from: sid(5),
+                to: sid(6),
+            },
+            DiffOp::VbaProjectChanged {
+                property: VbaProjectProperty::LockedForViewing,
+                from: Some(sid(7)),
+                to: None,
+            },
             DiffOp::NamedRangeAdded { name: sid(11) },
             DiffOp::NamedRangeRemoved { name: sid(12) },
             DiffOp::NamedRangeChanged {
@@ -1630,6 +3734,166 @@ mod tests {
             DiffOp::ChartChanged {
                 sheet: sid(20),
                 name: sid(21),
+                changes: Vec::new(),
+            },
+            DiffOp::ChartChanged {
+                sheet: sid(20),
+                name: sid(21),
+                changes: vec![
+                    ChartChange {
+                        field: ChartField::SeriesValues,
+                        series: Some(1),
+                        axis: None,
+                        from: Some(sid(15)),
+                        to: Some(sid(16)),
+                    },
+                    ChartChange {
+                        field: ChartField::AxisMax,
+                        series: None,
+                        axis: Some(sid(17)),
+                        from: None,
+                        to: Some(sid(18)),
+                    },
+                ],
+            },
+            DiffOp::PivotTableAdded {
+                sheet: sid(22),
+                name: sid(23),
+            },
+            DiffOp::PivotTableRemoved {
+                sheet: sid(22),
+                name: sid(23),
+            },
+            DiffOp::PivotTableSourceChanged {
+                sheet: sid(22),
+                name: sid(23),
+                from: PivotSource {
+                    kind: sid(24),
+                    sheet: Some(sid(25)),
+                    range: Some(CellRange::new(
+                        CellAddress::from_indices(0, 0),
+                        CellAddress::from_indices(9, 3),
+                    )),
+                    name: None,
+                    connection_id: None,
+                },
+                to: PivotSource {
+                    kind: sid(26),
+                    sheet: None,
+                    range: None,
+                    name: None,
+                    connection_id: Some(2),
+                },
+                uncovered_rows: 4,
+            },
+            DiffOp::PivotTableLayoutChanged {
+                sheet: sid(22),
+                name: sid(23),
+                changes: vec![
+                    PivotLayoutChange {
+                        area: PivotArea::Rows,
+                        field: None,
+                        from: Some(sid(27)),
+                        to: None,
+                    },
+                    PivotLayoutChange {
+                        area: PivotArea::Values,
+                        field: Some(sid(28)),
+                        from: Some(sid(29)),
+                        to: Some(sid(30)),
+                    },
+                ],
+            },
+            DiffOp::ExternalLinkAdded {
+                index: 2,
+                target: sid(31),
+            },
+            DiffOp::ExternalLinkRemoved {
+                index: 1,
+                target: sid(32),
+            },
+            DiffOp::ExternalLinkChanged {
+                from: ExternalLink {
+                    index: 1,
+                    kind: sid(33),
+                    target: sid(32),
+                    sheet_names: vec![sid(34)],
+                },
+                to: ExternalLink {
+                    index: 1,
+                    kind: sid(33),
+                    target: sid(31),
+                    sheet_names: Vec::new(),
+                },
+            },
+            DiffOp::ConnectionAdded { name: sid(35) },
+            DiffOp::ConnectionRemoved { name: sid(36) },
+            DiffOp::ConnectionChanged {
+                name: sid(35),
+                changes: vec![
+                    ConnectionChange {
+                        field: ConnectionField::Connection,
+                        from: Some(sid(37)),
+                        to: Some(sid(38)),
+                    },
+                    ConnectionChange {
+                        field: ConnectionField::Command,
+                        from: None,
+                        to: Some(sid(39)),
+                    },
+                ],
+            },
+            DiffOp::ReportObjectAdded {
+                object: PbipEntityKind::Page,
+                page: None,
+                name: sid(43),
+            },
+            DiffOp::ReportObjectRemoved {
+                object: PbipEntityKind::Visual,
+                page: Some(sid(43)),
+                name: sid(44),
+            },
+            DiffOp::ReportObjectChanged {
+                object: PbipEntityKind::Visual,
+                page: Some(sid(43)),
+                name: sid(44),
+                changes: vec![
+                    ReportChange {
+                        field: ReportField::Field,
+                        from: Some(sid(45)),
+                        to: Some(sid(46)),
+                    },
+                    ReportChange {
+                        field: ReportField::Filter,
+                        from: None,
+                        to: Some(sid(47)),
+                    },
+                ],
+            },
+            DiffOp::ReportObjectChanged {
+                object: PbipEntityKind::Bookmark,
+                page: None,
+                name: sid(48),
+                changes: Vec::new(),
+            },
+            DiffOp::WorkbookSettingsChanged {
+                changes: vec![WorkbookSettingChange {
+                    setting: WorkbookSetting::CalcMode,
+                    from: Some(sid(41)),
+                    to: Some(sid(42)),
+                }],
+            },
+            DiffOp::WorkbookProtectionChanged {
+                from: Some(WorkbookProtection {
+                    lock_structure: true,
+                    ..WorkbookProtection::default()
+                }),
+                to: None,
+            },
+            DiffOp::DocumentPropertyChanged {
+                name: sid(43),
+                from: None,
+                to: Some(sid(44)),
             },
             DiffOp::QueryAdded { name: sid(22) },
             DiffOp::QueryRemoved { name: sid(23) },
@@ -1710,6 +3974,8 @@ mod tests {
                         }],
                     }),
                 }),
+                downstream_queries: vec![sid(60), sid(61)],
+                loaded_tables: vec![sid(62)],
             },
             DiffOp::QueryMetadataChanged {
                 name: sid(27),
@@ -1754,4 +4020,77 @@ mod tests {
             assert_eq!(serde_val, custom_val);
         }
     }
+
+    #[cfg(feature = "model-diff")]
+    #[test]
+    fn model_object_op_writer_matches_serde_json() {
+        let ops = [
+            DiffOp::MeasureDefinitionChanged {
+                name: sid(11),
+                change_kind: ExpressionChangeKind::Semantic,
+                old_hash: 1,
+                new_hash: 2,
+                edits: vec![
+                    DaxEdit {
+                        kind: DaxEditKind::FilterAdded,
+                        function: Some("CALCULATE".to_string()),
+                        argument: Some(3),
+                        from: None,
+                        to: Some("Sales[Region] = \"West\"".to_string()),
+                    },
+                    DaxEdit {
+                        kind: DaxEditKind::ReferenceChanged,
+                        function: None,
+                        argument: None,
+                        from: Some("[Cost]".to_string()),
+                        to: Some("[Net Cost]".to_string()),
+                    },
+                ],
+            },
+            DiffOp::MeasurePropertyChanged {
+                name: sid(1),
+                field: MeasureProperty::FormatString,
+                old: Some(sid(2)),
+                new: None,
+            },
+            DiffOp::ModelObjectAdded {
+                object: ModelObjectKind::Hierarchy,
+                table: Some(sid(3)),
+                name: sid(4),
+            },
+            DiffOp::ModelObjectRemoved {
+                object: ModelObjectKind::Perspective,
+                table: None,
+                name: sid(5),
+            },
+            DiffOp::ModelObjectChanged {
+                object: ModelObjectKind::Role,
+                table: None,
+                name: sid(6),
+                changes: vec![
+                    ModelObjectChange {
+                        field: ModelObjectField::RowFilter,
+                        item: Some(sid(7)),
+                        from: Some(sid(8)),
+                        to: Some(sid(9)),
+                    },
+                    ModelObjectChange {
+                        field: ModelObjectField::Member,
+                        item: None,
+                        from: None,
+                        to: Some(sid(10)),
+                    },
+                ],
+            },
+        ];
+        for op in ops {
+            let serde = serde_json::to_vec(&op).unwrap();
+            let mut custom = Vec::new();
+            write_diff_op(&mut custom, &op).unwrap();
+
+            let serde_val: serde_json::Value = serde_json::from_slice(&serde).unwrap();
+            let custom_val: serde_json::Value = serde_json::from_slice(&custom).unwrap();
+            assert_eq!(serde_val, custom_val);
+        }
+    }
 }
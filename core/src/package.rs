@@ -1,15 +1,18 @@
 use crate::config::DiffConfig;
 use crate::container::ZipContainer;
+use crate::database_keys::{is_glob, DatabaseKeys, SheetKeyMap};
 use crate::datamashup::DataMashup;
-use crate::diff::{DiffError, DiffReport, DiffSummary, SheetId};
+use crate::diff::{DiffError, DiffOp, DiffReport, DiffSummary, SheetId};
 use crate::diffable::{DiffContext, Diffable};
+use crate::formula_deps::{CellImpact, DependencyGraph};
+use crate::op_alignment::{AlignmentRecordingSink, GridOpAlignment};
 #[cfg(feature = "perf-metrics")]
 use crate::perf::DiffMetrics;
 use crate::permission_bindings::{permission_bindings_warning, PermissionBindingsStatus};
 use crate::progress::ProgressCallback;
 use crate::sink::{DiffSink, NoFinishSink, SinkFinishGuard, VecSink};
 use crate::string_pool::StringPool;
-use crate::vba::VbaModule;
+use crate::vba::{VbaModule, VbaProject};
 use crate::workbook::{Sheet, Workbook};
 #[cfg(feature = "excel-open-xml")]
 use std::collections::{HashMap, HashSet};
@@ -35,6 +38,8 @@ pub struct WorkbookPackage {
     pub data_mashup: Option<DataMashup>,
     /// Extracted VBA modules, if present and the `vba` feature is enabled.
     pub vba_modules: Option<Vec<VbaModule>>,
+    /// VBA project properties, references and protection, alongside `vba_modules`.
+    pub vba_project: Option<VbaProject>,
     #[cfg(feature = "perf-metrics")]
     /// Parse time for this package (ms), captured when opening from bytes.
     pub parse_time_ms: u64,
@@ -46,6 +51,7 @@ impl From<Workbook> for WorkbookPackage {
             workbook,
             data_mashup: None,
             vba_modules: None,
+            vba_project: None,
             #[cfg(feature = "perf-metrics")]
             parse_time_ms: 0,
         }
@@ -91,50 +97,53 @@ struct SheetTargetMeta {
 }
 
 #[cfg(feature = "excel-open-xml")]
-fn read_workbook_xml_checked(
+fn read_workbook_part_checked(
     container: &mut crate::container::OpcContainer,
+    part: &str,
 ) -> Result<Vec<u8>, crate::excel_open_xml::PackageError> {
-    container
-        .read_file_checked("xl/workbook.xml")
-        .map_err(|e| match e {
-            crate::ContainerError::FileNotFound { .. } => {
-                if container.file_names().any(|name| name == "xl/workbook.bin") {
-                    crate::excel_open_xml::PackageError::UnsupportedFormat {
-                        message: "XLSB detected (xl/workbook.bin present); convert to .xlsx/.xlsm"
-                            .to_string(),
-                    }
-                } else {
-                    crate::excel_open_xml::PackageError::MissingPart {
-                        path: "xl/workbook.xml".to_string(),
-                    }
-                }
+    container.read_file_checked(part).map_err(|e| match e {
+        crate::ContainerError::FileNotFound { .. } => {
+            crate::excel_open_xml::PackageError::MissingPart {
+                path: part.to_string(),
             }
-            other => crate::excel_open_xml::PackageError::ReadPartFailed {
-                part: "xl/workbook.xml".to_string(),
-                message: other.to_string(),
-            },
-        })
+        }
+        other => crate::excel_open_xml::PackageError::ReadPartFailed {
+            part: part.to_string(),
+            message: other.to_string(),
+        },
+    })
 }
 
 #[cfg(feature = "excel-open-xml")]
 fn workbook_sheet_targets(
     container: &mut crate::container::OpcContainer,
 ) -> Result<Vec<SheetTargetMeta>, crate::excel_open_xml::PackageError> {
-    let workbook_bytes = read_workbook_xml_checked(container)?;
-    let sheets = crate::grid_parser::parse_workbook_xml(&workbook_bytes)
-        .map_err(|e| crate::excel_open_xml::wrap_grid_parse_error(e, "xl/workbook.xml"))?;
+    let binary = crate::excel_open_xml::is_binary_workbook(container);
+    let parts = crate::excel_open_xml::WorkbookParts::for_layout(binary);
+    let workbook_bytes = read_workbook_part_checked(container, parts.workbook)?;
+    let sheets = if binary {
+        crate::xlsb::parse_workbook_bin(&workbook_bytes)
+            .map_err(|e| crate::excel_open_xml::wrap_xlsb_error(e, parts.workbook))?
+            .sheets
+    } else {
+        crate::grid_parser::parse_workbook_xml(&workbook_bytes)
+            .map_err(|e| crate::excel_open_xml::wrap_grid_parse_error(e, parts.workbook))?
+    };
 
-    let workbook_rels_bytes = container.read_file_optional_checked("xl/_rels/workbook.xml.rels")?;
+    let workbook_rels_bytes = container.read_file_optional_checked(parts.workbook_rels)?;
     let relationships = match workbook_rels_bytes {
-        Some(bytes) => crate::grid_parser::parse_relationships(&bytes).map_err(|e| {
-            crate::excel_open_xml::wrap_grid_parse_error(e, "xl/_rels/workbook.xml.rels")
-        })?,
+        Some(bytes) => crate::grid_parser::parse_relationships(&bytes)
+            .map_err(|e| crate::excel_open_xml::wrap_grid_parse_error(e, parts.workbook_rels))?,
         None => HashMap::new(),
     };
 
     let mut metas = Vec::with_capacity(sheets.len());
     for (idx, sheet) in sheets.iter().enumerate() {
-        let target = crate::grid_parser::resolve_sheet_target(sheet, &relationships, idx);
+        let target = if binary {
+            crate::xlsb::resolve_binary_sheet_target(sheet, &relationships, idx)
+        } else {
+            crate::grid_parser::resolve_sheet_target(sheet, &relationships, idx)
+        };
         metas.push(SheetTargetMeta {
             sheet_id: sheet.sheet_id,
             name_lower: sheet.name.to_lowercase(),
@@ -181,6 +190,39 @@ fn sheet_uses_shared_strings(xml: &[u8]) -> bool {
     xml.windows(5).any(|w| w == br#"t="s""# || w == b"t='s'")
 }
 
+#[cfg(feature = "excel-open-xml")]
+fn sheet_part_uses_shared_strings(target: &str, bytes: &[u8]) -> bool {
+    if target.ends_with(".bin") {
+        crate::xlsb::sheet_uses_shared_strings(bytes)
+    } else {
+        sheet_uses_shared_strings(bytes)
+    }
+}
+
+#[cfg(feature = "excel-open-xml")]
+fn sheet_part_uses_styles(target: &str, bytes: &[u8]) -> bool {
+    // `styles.bin` is not decoded, so binary sheets never carry style indices.
+    !target.ends_with(".bin") && crate::styles::sheet_uses_styles(bytes)
+}
+
+#[cfg(feature = "excel-open-xml")]
+fn styles_fingerprint(
+    container: &mut crate::container::OpcContainer,
+) -> Result<Option<crate::container::ZipEntryFingerprint>, crate::excel_open_xml::PackageError> {
+    let binary = crate::excel_open_xml::is_binary_workbook(container);
+    let part = crate::excel_open_xml::WorkbookParts::for_layout(binary).styles;
+    Ok(container.file_fingerprint_optional_checked(part)?)
+}
+
+#[cfg(feature = "excel-open-xml")]
+fn shared_strings_fingerprint(
+    container: &mut crate::container::OpcContainer,
+) -> Result<Option<crate::container::ZipEntryFingerprint>, crate::excel_open_xml::PackageError> {
+    let binary = crate::excel_open_xml::is_binary_workbook(container);
+    let part = crate::excel_open_xml::WorkbookParts::for_layout(binary).shared_strings;
+    Ok(container.file_fingerprint_optional_checked(part)?)
+}
+
 #[cfg(feature = "excel-open-xml")]
 fn compute_sheet_grid_parse_targets(
     old_container: &mut crate::container::OpcContainer,
@@ -213,9 +255,10 @@ fn compute_sheet_grid_parse_targets(
         }
     }
 
-    let shared_old = old_container.file_fingerprint_optional_checked("xl/sharedStrings.xml")?;
-    let shared_new = new_container.file_fingerprint_optional_checked("xl/sharedStrings.xml")?;
+    let shared_old = shared_strings_fingerprint(old_container)?;
+    let shared_new = shared_strings_fingerprint(new_container)?;
     let shared_same = shared_old == shared_new;
+    let styles_same = styles_fingerprint(old_container)? == styles_fingerprint(new_container)?;
 
     let mut old_consumed = vec![false; old_metas.len()];
     let mut new_consumed = vec![false; new_metas.len()];
@@ -260,9 +303,11 @@ fn compute_sheet_grid_parse_targets(
             continue;
         }
 
-        if !shared_same {
+        if !shared_same || !styles_same {
             let bytes = old_container.read_file_checked(old_target)?;
-            if sheet_uses_shared_strings(&bytes) {
+            if (!shared_same && sheet_part_uses_shared_strings(old_target, &bytes))
+                || (!styles_same && sheet_part_uses_styles(old_target, &bytes))
+            {
                 old_parse.insert(old_target.clone());
                 new_parse.insert(new_target.clone());
             }
@@ -311,9 +356,11 @@ fn compute_sheet_grid_parse_targets(
             continue;
         }
 
-        if !shared_same {
+        if !shared_same || !styles_same {
             let bytes = old_container.read_file_checked(old_target)?;
-            if sheet_uses_shared_strings(&bytes) {
+            if (!shared_same && sheet_part_uses_shared_strings(old_target, &bytes))
+                || (!styles_same && sheet_part_uses_styles(old_target, &bytes))
+            {
                 old_parse.insert(old_target.clone());
                 new_parse.insert(new_target.clone());
             }
@@ -343,7 +390,8 @@ fn open_workbook_package_from_container_with_grid_filter(
         None => None,
     };
 
-    let vba_modules = crate::excel_open_xml::open_vba_modules_from_container(container, pool)?;
+    let (vba_modules, vba_project) =
+        crate::excel_open_xml::open_vba_from_container(container, pool)?.unzip();
 
     #[cfg(feature = "perf-metrics")]
     let parse_time_ms = total_start.elapsed().as_millis() as u64;
@@ -352,6 +400,7 @@ fn open_workbook_package_from_container_with_grid_filter(
         workbook,
         data_mashup,
         vba_modules,
+        vba_project,
         #[cfg(feature = "perf-metrics")]
         parse_time_ms,
     })
@@ -398,10 +447,11 @@ impl WorkbookPackage {
             let data_mashup_ms = data_mashup_start.elapsed().as_millis() as u64;
 
             let vba_start = Instant::now();
-            let vba_modules = crate::excel_open_xml::open_vba_modules_from_container(
+            let (vba_modules, vba_project) = crate::excel_open_xml::open_vba_from_container(
                 &mut container,
                 &mut session.strings,
-            )?;
+            )?
+            .unzip();
             let vba_ms = vba_start.elapsed().as_millis() as u64;
 
             #[cfg(feature = "perf-metrics")]
@@ -428,6 +478,7 @@ impl WorkbookPackage {
                 workbook,
                 data_mashup,
                 vba_modules,
+                vba_project,
                 #[cfg(feature = "perf-metrics")]
                 parse_time_ms,
             })
@@ -462,10 +513,11 @@ impl WorkbookPackage {
             let data_mashup_ms = data_mashup_start.elapsed().as_millis() as u64;
 
             let vba_start = Instant::now();
-            let vba_modules = crate::excel_open_xml::open_vba_modules_from_container(
+            let (vba_modules, vba_project) = crate::excel_open_xml::open_vba_from_container(
                 &mut container,
                 &mut session.strings,
-            )?;
+            )?
+            .unzip();
             let vba_ms = vba_start.elapsed().as_millis() as u64;
 
             #[cfg(feature = "perf-metrics")]
@@ -492,6 +544,7 @@ impl WorkbookPackage {
                 workbook,
                 data_mashup,
                 vba_modules,
+                vba_project,
                 #[cfg(feature = "perf-metrics")]
                 parse_time_ms,
             })
@@ -637,6 +690,110 @@ impl WorkbookPackage {
         })
     }
 
+    /// Formula cells downstream of each `CellEdited` in `ops`, traced through this package's
+    /// formulas and defined names. Call it on the new side of the diff that produced `ops`, with
+    /// the string pool that diff used (the default session's for [`WorkbookPackage::diff`]).
+    pub fn formula_impact(&self, ops: &[DiffOp], pool: &StringPool) -> Vec<CellImpact> {
+        DependencyGraph::build(&self.workbook, pool).impact(ops)
+    }
+
+    /// Workbook-level object diffs: named ranges, charts, pivot tables, external links, data
+    /// connections, workbook settings, VBA modules and the VBA project. Chart, pivot, connection,
+    /// settings and VBA diffs intern labels, values, hunk text and reference names, so streaming
+    /// callers run this before the grid stage.
+    fn diff_workbook_objects(&self, other: &Self, pool: &mut StringPool) -> Vec<DiffOp> {
+        let mut ops = crate::object_diff::diff_named_ranges(&self.workbook, &other.workbook, pool);
+        ops.extend(crate::object_diff::diff_charts(
+            &self.workbook,
+            &other.workbook,
+            pool,
+        ));
+        ops.extend(crate::object_diff::diff_pivot_tables(
+            &self.workbook,
+            &other.workbook,
+            pool,
+        ));
+        ops.extend(crate::object_diff::diff_external_links(
+            &self.workbook,
+            &other.workbook,
+            pool,
+        ));
+        ops.extend(crate::object_diff::diff_connections(
+            &self.workbook,
+            &other.workbook,
+            pool,
+        ));
+        ops.extend(crate::object_diff::diff_workbook_settings(
+            &self.workbook,
+            &other.workbook,
+            pool,
+        ));
+        ops.extend(crate::object_diff::diff_vba_modules(
+            self.vba_modules.as_deref(),
+            other.vba_modules.as_deref(),
+            pool,
+        ));
+        ops.extend(crate::object_diff::diff_vba_project(
+            self.vba_project.as_ref(),
+            other.vba_project.as_ref(),
+            pool,
+        ));
+        ops
+    }
+
+    /// Ops emitted between the grid and M stages of a positional or multi-sheet database diff.
+    /// Single-sheet database mode skips cell formatting, comments, sheet rules and layout, since
+    /// it only compares the one sheet.
+    ///
+    /// `alignment` is replayed from the grid ops so comments, rule ranges, row/column layout,
+    /// table ranges and drawing anchors follow their rows and columns.
+    /// Nothing here interns strings, so streaming callers may run it after the grid stage; the
+    /// workbook-level objects from [`Self::diff_workbook_objects`] follow these ops.
+    fn diff_objects(
+        &self,
+        other: &Self,
+        pool: &mut StringPool,
+        config: &DiffConfig,
+        alignment: &GridOpAlignment,
+    ) -> Vec<DiffOp> {
+        let mut ops = if config.semantic.enable_cell_style_diff {
+            crate::object_diff::diff_cell_styles(&self.workbook, &other.workbook, pool, alignment)
+        } else {
+            Vec::new()
+        };
+        ops.extend(crate::object_diff::diff_cell_comments(
+            &self.workbook,
+            &other.workbook,
+            pool,
+            alignment,
+        ));
+        ops.extend(crate::object_diff::diff_sheet_rules(
+            &self.workbook,
+            &other.workbook,
+            pool,
+            alignment,
+        ));
+        ops.extend(crate::object_diff::diff_sheet_layout(
+            &self.workbook,
+            &other.workbook,
+            pool,
+            alignment,
+        ));
+        ops.extend(crate::object_diff::diff_excel_tables(
+            &self.workbook,
+            &other.workbook,
+            pool,
+            alignment,
+        ));
+        ops.extend(crate::object_diff::diff_drawing_objects(
+            &self.workbook,
+            &other.workbook,
+            pool,
+            alignment,
+        ));
+        ops
+    }
+
     pub fn diff_with_pool(
         &self,
         other: &Self,
@@ -648,19 +805,10 @@ impl WorkbookPackage {
             self.workbook.diff(&other.workbook, &mut ctx)
         };
 
-        let mut object_ops =
-            crate::object_diff::diff_named_ranges(&self.workbook, &other.workbook, pool);
-        object_ops.extend(crate::object_diff::diff_charts(
-            &self.workbook,
-            &other.workbook,
-            pool,
-        ));
-        object_ops.extend(crate::object_diff::diff_vba_modules(
-            self.vba_modules.as_deref(),
-            other.vba_modules.as_deref(),
-            pool,
-        ));
+        let alignment = GridOpAlignment::from_ops(&report.ops);
+        let object_ops = self.diff_objects(other, pool, config, &alignment);
         report.ops.extend(object_ops);
+        report.ops.extend(self.diff_workbook_objects(other, pool));
 
         let m_ops = crate::m_diff::diff_m_ops_for_packages(
             &self.data_mashup,
@@ -692,19 +840,10 @@ impl WorkbookPackage {
             progress,
         );
 
-        let mut object_ops =
-            crate::object_diff::diff_named_ranges(&self.workbook, &other.workbook, pool);
-        object_ops.extend(crate::object_diff::diff_charts(
-            &self.workbook,
-            &other.workbook,
-            pool,
-        ));
-        object_ops.extend(crate::object_diff::diff_vba_modules(
-            self.vba_modules.as_deref(),
-            other.vba_modules.as_deref(),
-            pool,
-        ));
+        let alignment = GridOpAlignment::from_ops(&report.ops);
+        let object_ops = self.diff_objects(other, pool, config, &alignment);
         report.ops.extend(object_ops);
+        report.ops.extend(self.diff_workbook_objects(other, pool));
 
         let m_ops = crate::m_diff::diff_m_ops_for_packages(
             &self.data_mashup,
@@ -789,19 +928,7 @@ impl WorkbookPackage {
         config: &DiffConfig,
         sink: &mut S,
     ) -> Result<DiffSummary, DiffError> {
-        let mut object_ops =
-            crate::object_diff::diff_named_ranges(&self.workbook, &other.workbook, pool);
-        object_ops.extend(crate::object_diff::diff_charts(
-            &self.workbook,
-            &other.workbook,
-            pool,
-        ));
-        object_ops.extend(crate::object_diff::diff_vba_modules(
-            self.vba_modules.as_deref(),
-            other.vba_modules.as_deref(),
-            pool,
-        ));
-
+        let workbook_object_ops = self.diff_workbook_objects(other, pool);
         let m_ops = crate::m_diff::diff_m_ops_for_packages(
             &self.data_mashup,
             &other.data_mashup,
@@ -809,15 +936,17 @@ impl WorkbookPackage {
             config,
         );
 
-        let grid_result = {
+        let (grid_result, alignment) = {
             let mut no_finish = NoFinishSink::new(sink);
-            crate::engine::try_diff_workbooks_streaming(
+            let mut recording = AlignmentRecordingSink::new(&mut no_finish);
+            let result = crate::engine::try_diff_workbooks_streaming(
                 &self.workbook,
                 &other.workbook,
                 pool,
                 config,
-                &mut no_finish,
-            )
+                &mut recording,
+            );
+            (result, recording.into_alignment())
         };
 
         let mut summary = match grid_result {
@@ -828,7 +957,9 @@ impl WorkbookPackage {
             }
         };
 
-        for op in object_ops {
+        let object_ops = self.diff_objects(other, pool, config, &alignment);
+
+        for op in object_ops.into_iter().chain(workbook_object_ops) {
             if let Err(e) = sink.emit(op) {
                 let _ = sink.finish();
                 return Err(e);
@@ -865,19 +996,7 @@ impl WorkbookPackage {
         sink: &mut S,
         progress: &dyn ProgressCallback,
     ) -> Result<DiffSummary, DiffError> {
-        let mut object_ops =
-            crate::object_diff::diff_named_ranges(&self.workbook, &other.workbook, pool);
-        object_ops.extend(crate::object_diff::diff_charts(
-            &self.workbook,
-            &other.workbook,
-            pool,
-        ));
-        object_ops.extend(crate::object_diff::diff_vba_modules(
-            self.vba_modules.as_deref(),
-            other.vba_modules.as_deref(),
-            pool,
-        ));
-
+        let workbook_object_ops = self.diff_workbook_objects(other, pool);
         let m_ops = crate::m_diff::diff_m_ops_for_packages(
             &self.data_mashup,
             &other.data_mashup,
@@ -885,16 +1004,18 @@ impl WorkbookPackage {
             config,
         );
 
-        let grid_result = {
+        let (grid_result, alignment) = {
             let mut no_finish = NoFinishSink::new(sink);
-            crate::engine::try_diff_workbooks_streaming_with_progress(
+            let mut recording = AlignmentRecordingSink::new(&mut no_finish);
+            let result = crate::engine::try_diff_workbooks_streaming_with_progress(
                 &self.workbook,
                 &other.workbook,
                 pool,
                 config,
-                &mut no_finish,
+                &mut recording,
                 progress,
-            )
+            );
+            (result, recording.into_alignment())
         };
 
         let mut summary = match grid_result {
@@ -905,7 +1026,9 @@ impl WorkbookPackage {
             }
         };
 
-        for op in object_ops {
+        let object_ops = self.diff_objects(other, pool, config, &alignment);
+
+        for op in object_ops.into_iter().chain(workbook_object_ops) {
             if let Err(e) = sink.emit(op) {
                 let _ = sink.finish();
                 return Err(e);
@@ -937,7 +1060,8 @@ impl WorkbookPackage {
     /// Diff a single sheet using key-based row alignment ("database mode").
     ///
     /// `sheet_name` must exist in both workbooks (matching is case-insensitive). `key_columns`
-    /// are 0-based column indices (A=0, B=1, ...).
+    /// are 0-based column indices (A=0, B=1, ...); see
+    /// [`WorkbookPackage::diff_database_mode_with_keys`] for header-named and normalised keys.
     ///
     /// # Examples
     ///
@@ -961,56 +1085,103 @@ impl WorkbookPackage {
         sheet_name: &str,
         key_columns: &[u32],
         config: &DiffConfig,
+    ) -> Result<DiffReport, DiffError> {
+        self.diff_database_mode_with_keys(
+            other,
+            sheet_name,
+            &DatabaseKeys::from_columns(key_columns),
+            config,
+        )
+    }
+
+    /// Like [`WorkbookPackage::diff_database_mode`], but uses a caller-provided string pool.
+    pub fn diff_database_mode_with_pool(
+        &self,
+        other: &Self,
+        sheet_name: &str,
+        key_columns: &[u32],
+        pool: &mut StringPool,
+        config: &DiffConfig,
+    ) -> Result<DiffReport, DiffError> {
+        self.diff_database_mode_with_keys_and_pool(
+            other,
+            sheet_name,
+            &DatabaseKeys::from_columns(key_columns),
+            pool,
+            config,
+        )
+    }
+
+    /// Database mode with a [`DatabaseKeys`] spec.
+    ///
+    /// Header-named key parts are looked up in `keys.header_row` of each sheet separately, so a
+    /// key still matches after columns are inserted to its left. A header missing on either side
+    /// fails with [`DiffError::KeyColumnNotFound`]. Non-key columns are compared by position.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use excel_diff::{DatabaseKeys, DiffConfig, WorkbookPackage};
+    /// use std::fs::File;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let old_pkg = WorkbookPackage::open(File::open("old.xlsx")?)?;
+    /// let new_pkg = WorkbookPackage::open(File::open("new.xlsx")?)?;
+    ///
+    /// let keys: DatabaseKeys = "[Customer ID],trim(lower([Region]))".parse()?;
+    /// let report =
+    ///     old_pkg.diff_database_mode_with_keys(&new_pkg, "Data", &keys, &DiffConfig::default())?;
+    /// println!("complete={} ops={}", report.complete, report.ops.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn diff_database_mode_with_keys(
+        &self,
+        other: &Self,
+        sheet_name: &str,
+        keys: &DatabaseKeys,
+        config: &DiffConfig,
     ) -> Result<DiffReport, DiffError> {
         crate::with_default_session(|session| {
-            self.diff_database_mode_with_pool(
+            self.diff_database_mode_with_keys_and_pool(
                 other,
                 sheet_name,
-                key_columns,
+                keys,
                 &mut session.strings,
                 config,
             )
         })
     }
 
-    /// Like [`WorkbookPackage::diff_database_mode`], but uses a caller-provided string pool.
-    pub fn diff_database_mode_with_pool(
+    /// Like [`WorkbookPackage::diff_database_mode_with_keys`], but uses a caller-provided string
+    /// pool.
+    pub fn diff_database_mode_with_keys_and_pool(
         &self,
         other: &Self,
         sheet_name: &str,
-        key_columns: &[u32],
+        keys: &DatabaseKeys,
         pool: &mut StringPool,
         config: &DiffConfig,
     ) -> Result<DiffReport, DiffError> {
         let (old_sheet, new_sheet, sheet_id) =
             find_sheets_case_insensitive(&self.workbook, &other.workbook, sheet_name, pool)?;
+        let keys = keys.resolve(&old_sheet.grid, &new_sheet.grid, pool)?;
 
         let mut sink = VecSink::new();
         let mut op_count = 0usize;
 
-        let summary = crate::engine::try_diff_grids_database_mode_streaming(
+        let summary = crate::engine::try_diff_grids_database_mode_keyed_streaming(
             sheet_id,
             &old_sheet.grid,
             &new_sheet.grid,
-            key_columns,
+            &keys,
             pool,
             config,
             &mut sink,
             &mut op_count,
         )?;
 
-        let mut object_ops =
-            crate::object_diff::diff_named_ranges(&self.workbook, &other.workbook, pool);
-        object_ops.extend(crate::object_diff::diff_charts(
-            &self.workbook,
-            &other.workbook,
-            pool,
-        ));
-        object_ops.extend(crate::object_diff::diff_vba_modules(
-            self.vba_modules.as_deref(),
-            other.vba_modules.as_deref(),
-            pool,
-        ));
+        let object_ops = self.diff_workbook_objects(other, pool);
 
         let m_ops = crate::m_diff::diff_m_ops_for_packages(
             &self.data_mashup,
@@ -1039,12 +1210,50 @@ impl WorkbookPackage {
         key_columns: &[u32],
         config: &DiffConfig,
         sink: &mut S,
+    ) -> Result<DiffSummary, DiffError> {
+        self.diff_database_mode_streaming_with_keys(
+            other,
+            sheet_name,
+            &DatabaseKeys::from_columns(key_columns),
+            config,
+            sink,
+        )
+    }
+
+    /// Like [`WorkbookPackage::diff_database_mode_streaming`], but uses a caller-provided string pool.
+    pub fn diff_database_mode_streaming_with_pool<S: DiffSink>(
+        &self,
+        other: &Self,
+        sheet_name: &str,
+        key_columns: &[u32],
+        pool: &mut StringPool,
+        config: &DiffConfig,
+        sink: &mut S,
+    ) -> Result<DiffSummary, DiffError> {
+        self.diff_database_mode_streaming_with_keys_and_pool(
+            other,
+            sheet_name,
+            &DatabaseKeys::from_columns(key_columns),
+            pool,
+            config,
+            sink,
+        )
+    }
+
+    /// Streaming form of [`WorkbookPackage::diff_database_mode_with_keys`].
+    pub fn diff_database_mode_streaming_with_keys<S: DiffSink>(
+        &self,
+        other: &Self,
+        sheet_name: &str,
+        keys: &DatabaseKeys,
+        config: &DiffConfig,
+        sink: &mut S,
     ) -> Result<DiffSummary, DiffError> {
         crate::with_default_session(|session| {
-            self.diff_database_mode_streaming_with_pool(
+            self.diff_database_mode_streaming_with_keys_and_pool(
                 other,
                 sheet_name,
-                key_columns,
+                keys,
                 &mut session.strings,
                 config,
                 sink,
@@ -1052,28 +1261,18 @@ impl WorkbookPackage {
         })
     }
 
-    /// Like [`WorkbookPackage::diff_database_mode_streaming`], but uses a caller-provided string pool.
-    pub fn diff_database_mode_streaming_with_pool<S: DiffSink>(
+    /// Like [`WorkbookPackage::diff_database_mode_streaming_with_keys`], but uses a
+    /// caller-provided string pool.
+    pub fn diff_database_mode_streaming_with_keys_and_pool<S: DiffSink>(
         &self,
         other: &Self,
         sheet_name: &str,
-        key_columns: &[u32],
+        keys: &DatabaseKeys,
         pool: &mut StringPool,
         config: &DiffConfig,
         sink: &mut S,
     ) -> Result<DiffSummary, DiffError> {
-        let mut object_ops =
-            crate::object_diff::diff_named_ranges(&self.workbook, &other.workbook, pool);
-        object_ops.extend(crate::object_diff::diff_charts(
-            &self.workbook,
-            &other.workbook,
-            pool,
-        ));
-        object_ops.extend(crate::object_diff::diff_vba_modules(
-            self.vba_modules.as_deref(),
-            other.vba_modules.as_deref(),
-            pool,
-        ));
+        let object_ops = self.diff_workbook_objects(other, pool);
 
         let m_ops = crate::m_diff::diff_m_ops_for_packages(
             &self.data_mashup,
@@ -1084,14 +1283,15 @@ impl WorkbookPackage {
 
         let (old_sheet, new_sheet, sheet_id) =
             find_sheets_case_insensitive(&self.workbook, &other.workbook, sheet_name, pool)?;
+        let keys = keys.resolve(&old_sheet.grid, &new_sheet.grid, pool)?;
 
         let grid_result = {
             let mut no_finish = NoFinishSink::new(sink);
-            crate::engine::try_diff_grids_database_mode_streaming(
+            crate::engine::try_diff_grids_database_mode_keyed_streaming(
                 sheet_id,
                 &old_sheet.grid,
                 &new_sheet.grid,
-                key_columns,
+                &keys,
                 pool,
                 config,
                 &mut no_finish,
@@ -1132,12 +1332,287 @@ impl WorkbookPackage {
         );
         Ok(summary)
     }
+
+    /// Database mode across a whole workbook, with keys per sheet.
+    ///
+    /// Worksheets whose name matches a pattern in `sheet_keys` are diffed by key; every other
+    /// sheet falls back to the usual grid diff. Sheet additions, removals and renames are
+    /// reported as in [`WorkbookPackage::diff`], and so are cell formatting, comments, rules,
+    /// layout, tables and drawings; on keyed sheets these follow the rows matched by key.
+    /// Workbook-level objects and Power Query changes are included as in single-sheet database
+    /// mode.
+    ///
+    /// An exact sheet name that exists in neither workbook fails with
+    /// [`DiffError::SheetNotFound`]; a glob that matches nothing is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use excel_diff::{DatabaseKeys, DiffConfig, SheetKeyMap, WorkbookPackage};
+    /// use std::fs::File;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let old_pkg = WorkbookPackage::open(File::open("old.xlsx")?)?;
+    /// let new_pkg = WorkbookPackage::open(File::open("new.xlsx")?)?;
+    ///
+    /// let sheet_keys = SheetKeyMap::new()
+    ///     .with("Customers", "[Customer ID]".parse::<DatabaseKeys>()?)
+    ///     .with("Orders_*", "[Order ID],[Line]".parse::<DatabaseKeys>()?);
+    /// let report =
+    ///     old_pkg.diff_database_mode_sheets(&new_pkg, &sheet_keys, &DiffConfig::default())?;
+    /// println!("complete={} ops={}", report.complete, report.ops.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn diff_database_mode_sheets(
+        &self,
+        other: &Self,
+        sheet_keys: &SheetKeyMap,
+        config: &DiffConfig,
+    ) -> Result<DiffReport, DiffError> {
+        crate::with_default_session(|session| {
+            self.diff_database_mode_sheets_with_pool(
+                other,
+                sheet_keys,
+                &mut session.strings,
+                config,
+            )
+        })
+    }
+
+    /// Like [`WorkbookPackage::diff_database_mode_sheets`], but uses a caller-provided string
+    /// pool.
+    pub fn diff_database_mode_sheets_with_pool(
+        &self,
+        other: &Self,
+        sheet_keys: &SheetKeyMap,
+        pool: &mut StringPool,
+        config: &DiffConfig,
+    ) -> Result<DiffReport, DiffError> {
+        let mut sink = VecSink::new();
+        // The streaming path already appends the permission-bindings warnings to the summary.
+        let summary = self.diff_database_mode_sheets_streaming_with_pool(
+            other, sheet_keys, pool, config, &mut sink,
+        )?;
+        let strings = pool.strings().to_vec();
+        Ok(DiffReport::from_ops_and_summary(
+            sink.into_ops(),
+            summary,
+            strings,
+        ))
+    }
+
+    /// Streaming form of [`WorkbookPackage::diff_database_mode_sheets`].
+    ///
+    /// Streaming output follows the contract in `docs/streaming_contract.md`.
+    pub fn diff_database_mode_sheets_streaming<S: DiffSink>(
+        &self,
+        other: &Self,
+        sheet_keys: &SheetKeyMap,
+        config: &DiffConfig,
+        sink: &mut S,
+    ) -> Result<DiffSummary, DiffError> {
+        crate::with_default_session(|session| {
+            self.diff_database_mode_sheets_streaming_with_pool(
+                other,
+                sheet_keys,
+                &mut session.strings,
+                config,
+                sink,
+            )
+        })
+    }
+
+    /// Like [`WorkbookPackage::diff_database_mode_sheets_streaming`], but uses a caller-provided
+    /// string pool.
+    pub fn diff_database_mode_sheets_streaming_with_pool<S: DiffSink>(
+        &self,
+        other: &Self,
+        sheet_keys: &SheetKeyMap,
+        pool: &mut StringPool,
+        config: &DiffConfig,
+        sink: &mut S,
+    ) -> Result<DiffSummary, DiffError> {
+        for (pattern, _) in sheet_keys.iter() {
+            if !is_glob(pattern) {
+                find_sheet_in_either(&self.workbook, &other.workbook, pattern, pool)?;
+            }
+        }
+
+        let object_ops = self.diff_workbook_objects(other, pool);
+
+        let m_ops = crate::m_diff::diff_m_ops_for_packages(
+            &self.data_mashup,
+            &other.data_mashup,
+            pool,
+            config,
+        );
+
+        let mut keyed_rows = Vec::new();
+        let (grid_result, mut alignment) = {
+            let mut no_finish = NoFinishSink::new(sink);
+            let mut recording = AlignmentRecordingSink::new(&mut no_finish);
+            let result = crate::engine::try_diff_workbooks_database_mode_streaming(
+                &self.workbook,
+                &other.workbook,
+                pool,
+                config,
+                &mut recording,
+                sheet_keys,
+                &mut keyed_rows,
+            );
+            (result, recording.into_alignment())
+        };
+
+        let mut summary = match grid_result {
+            Ok(summary) => summary,
+            Err(e) => {
+                let _ = sink.finish();
+                return Err(e);
+            }
+        };
+
+        for (sheet, pairs) in &keyed_rows {
+            alignment.record_row_pairs(*sheet, pairs);
+        }
+        let sheet_object_ops = self.diff_objects(other, pool, config, &alignment);
+
+        for op in sheet_object_ops.into_iter().chain(object_ops).chain(m_ops) {
+            if let Err(e) = sink.emit(op) {
+                let _ = sink.finish();
+                return Err(e);
+            }
+            summary.op_count = summary.op_count.saturating_add(1);
+        }
+
+        sink.finish()?;
+
+        #[cfg(feature = "perf-metrics")]
+        apply_parse_metrics(self, other, &mut summary.metrics);
+
+        append_permission_bindings_warnings_summary(
+            &mut summary,
+            &self.data_mashup,
+            &other.data_mashup,
+        );
+        Ok(summary)
+    }
+
+    /// Diff the contents of every Excel table present in both workbooks by key ("table mode").
+    ///
+    /// Columns are matched by header name, so reordered columns compare correctly, and key
+    /// columns are suggested per table by [`crate::suggest_key_columns`]. Cells outside tables
+    /// are not compared. Table adds, removals, renames and resizes are reported alongside the
+    /// workbook-level objects and Power Query changes.
+    pub fn diff_tables_mode(
+        &self,
+        other: &Self,
+        config: &DiffConfig,
+    ) -> Result<DiffReport, DiffError> {
+        crate::with_default_session(|session| {
+            self.diff_tables_mode_with_pool(other, &mut session.strings, config)
+        })
+    }
+
+    /// Like [`WorkbookPackage::diff_tables_mode`], but uses a caller-provided string pool.
+    pub fn diff_tables_mode_with_pool(
+        &self,
+        other: &Self,
+        pool: &mut StringPool,
+        config: &DiffConfig,
+    ) -> Result<DiffReport, DiffError> {
+        let mut sink = VecSink::new();
+        // The streaming path already appends the permission-bindings warnings to the summary.
+        let summary = self.diff_tables_mode_streaming_with_pool(other, pool, config, &mut sink)?;
+        let strings = pool.strings().to_vec();
+        Ok(DiffReport::from_ops_and_summary(
+            sink.into_ops(),
+            summary,
+            strings,
+        ))
+    }
+
+    /// Streaming table mode diff. Emits ops into `sink` and returns a [`DiffSummary`].
+    ///
+    /// Streaming output follows the contract in `docs/streaming_contract.md`.
+    pub fn diff_tables_mode_streaming<S: DiffSink>(
+        &self,
+        other: &Self,
+        config: &DiffConfig,
+        sink: &mut S,
+    ) -> Result<DiffSummary, DiffError> {
+        crate::with_default_session(|session| {
+            self.diff_tables_mode_streaming_with_pool(other, &mut session.strings, config, sink)
+        })
+    }
+
+    /// Like [`WorkbookPackage::diff_tables_mode_streaming`], but uses a caller-provided string pool.
+    pub fn diff_tables_mode_streaming_with_pool<S: DiffSink>(
+        &self,
+        other: &Self,
+        pool: &mut StringPool,
+        config: &DiffConfig,
+        sink: &mut S,
+    ) -> Result<DiffSummary, DiffError> {
+        let mut object_ops = crate::object_diff::diff_excel_tables(
+            &self.workbook,
+            &other.workbook,
+            pool,
+            &GridOpAlignment::default(),
+        );
+        object_ops.extend(self.diff_workbook_objects(other, pool));
+
+        let m_ops = crate::m_diff::diff_m_ops_for_packages(
+            &self.data_mashup,
+            &other.data_mashup,
+            pool,
+            config,
+        );
+
+        let table_result = {
+            let mut no_finish = NoFinishSink::new(sink);
+            crate::table_mode::diff_tables_streaming(
+                &self.workbook,
+                &other.workbook,
+                pool,
+                config,
+                &mut no_finish,
+            )
+        };
+
+        let mut summary = match table_result {
+            Ok(summary) => summary,
+            Err(e) => {
+                let _ = sink.finish();
+                return Err(e);
+            }
+        };
+
+        for op in object_ops.into_iter().chain(m_ops) {
+            if let Err(e) = sink.emit(op) {
+                let _ = sink.finish();
+                return Err(e);
+            }
+            summary.op_count = summary.op_count.saturating_add(1);
+        }
+
+        sink.finish()?;
+
+        append_permission_bindings_warnings_summary(
+            &mut summary,
+            &self.data_mashup,
+            &other.data_mashup,
+        );
+        Ok(summary)
+    }
 }
 
 /// A parsed PBIX/PBIT package (Power BI) containing Power Query data.
 #[derive(Debug, Clone)]
 pub struct PbixPackage {
     pub(crate) data_mashup: Option<DataMashup>,
+    /// `Report/Layout`, when the package has one.
+    pub(crate) report_layout: Option<crate::report_layout::ReportLayout>,
     #[cfg(all(feature = "model-diff", feature = "excel-open-xml"))]
     pub(crate) model_schema: Option<crate::tabular_schema::RawTabularModel>,
 }
@@ -1157,6 +1632,10 @@ impl PbixPackage {
         } else {
             None
         };
+        let report_layout = container
+            .read_file_optional_checked("Report/Layout")?
+            .map(|bytes| crate::report_layout::parse_report_layout(&bytes))
+            .transpose()?;
 
         #[cfg(all(feature = "model-diff", feature = "excel-open-xml"))]
         let mut model_schema = None;
@@ -1170,6 +1649,7 @@ impl PbixPackage {
                             Some(crate::tabular_schema::parse_data_model_schema(&bytes)?);
                         return Ok(Self {
                             data_mashup,
+                            report_layout,
                             model_schema,
                         });
                     }
@@ -1185,6 +1665,7 @@ impl PbixPackage {
 
         Ok(Self {
             data_mashup,
+            report_layout,
             #[cfg(all(feature = "model-diff", feature = "excel-open-xml"))]
             model_schema,
         })
@@ -1205,6 +1686,10 @@ impl PbixPackage {
         } else {
             None
         };
+        let report_layout = container
+            .read_file_optional_checked("Report/Layout")?
+            .map(|bytes| crate::report_layout::parse_report_layout(&bytes))
+            .transpose()?;
 
         #[cfg(all(feature = "model-diff", feature = "excel-open-xml"))]
         let mut model_schema = None;
@@ -1218,6 +1703,7 @@ impl PbixPackage {
                             Some(crate::tabular_schema::parse_data_model_schema(&bytes)?);
                         return Ok(Self {
                             data_mashup,
+                            report_layout,
                             model_schema,
                         });
                     }
@@ -1233,6 +1719,7 @@ impl PbixPackage {
 
         Ok(Self {
             data_mashup,
+            report_layout,
             #[cfg(all(feature = "model-diff", feature = "excel-open-xml"))]
             model_schema,
         })
@@ -1242,6 +1729,23 @@ impl PbixPackage {
         self.data_mashup.as_ref()
     }
 
+    /// Measures and report visuals downstream of each `MeasureDefinitionChanged` in `report`,
+    /// traced through this package's DAX measures and report layout. Call it on the new side of
+    /// the diff that produced `report`; edited measures are matched by their resolved names.
+    #[cfg(all(feature = "model-diff", feature = "excel-open-xml"))]
+    pub fn measure_impact(&self, report: &DiffReport) -> Vec<crate::dax_deps::MeasureImpact> {
+        let Some(raw) = self.model_schema.as_ref() else {
+            return Vec::new();
+        };
+        let mut pool = StringPool::new();
+        let model = crate::tabular_schema::build_model(raw, &mut pool);
+        let mut graph = crate::dax_deps::MeasureGraph::build(&model, &pool);
+        if let Some(layout) = &self.report_layout {
+            graph.bind_visuals(layout);
+        }
+        graph.impact(report)
+    }
+
     pub fn diff(&self, other: &Self, config: &DiffConfig) -> DiffReport {
         crate::with_default_session(|session| {
             let mut report = DiffReport::new(Vec::new());
@@ -1282,6 +1786,10 @@ impl PbixPackage {
                 }
             }
 
+            report
+                .ops
+                .extend(self.report_layout_ops(other, &mut session.strings));
+
             report.strings = session.strings.strings().to_vec();
             append_permission_bindings_warnings(&mut report, &self.data_mashup, &other.data_mashup);
             report
@@ -1338,6 +1846,8 @@ impl PbixPackage {
             }
         };
 
+        let report_ops = self.report_layout_ops(other, pool);
+
         sink.begin(pool)?;
         let mut finish_guard = SinkFinishGuard::new(sink);
 
@@ -1364,6 +1874,11 @@ impl PbixPackage {
             }
         }
 
+        for op in report_ops {
+            sink.emit(op)?;
+            op_count = op_count.saturating_add(1);
+        }
+
         finish_guard.finish_and_disarm()?;
 
         let mut summary = DiffSummary {
@@ -1393,6 +1908,18 @@ impl PbixPackage {
         progress.on_progress("m_diff", 1.0);
         out
     }
+
+    /// Report layout ops; a side without `Report/Layout` counts as an empty report.
+    fn report_layout_ops(&self, other: &Self, pool: &mut StringPool) -> Vec<DiffOp> {
+        match (&self.report_layout, &other.report_layout) {
+            (None, None) => Vec::new(),
+            (old, new) => crate::report_layout::diff_report_layouts(
+                old.as_ref().unwrap_or(&Default::default()),
+                new.as_ref().unwrap_or(&Default::default()),
+                pool,
+            ),
+        }
+    }
 }
 
 #[cfg(feature = "perf-metrics")]
@@ -1446,28 +1973,55 @@ fn find_sheets_case_insensitive<'a>(
             let sheet_id = old.name;
             Ok((old, new, sheet_id))
         }
-        _ => {
-            let mut available: Vec<String> = old_wb
-                .sheets
-                .iter()
-                .map(|s| pool.resolve(s.name).to_string())
-                .collect();
-            for s in &new_wb.sheets {
-                let name = pool.resolve(s.name).to_string();
-                if !available
-                    .iter()
-                    .any(|n| n.to_lowercase() == name.to_lowercase())
-                {
-                    available.push(name);
-                }
-            }
-            available.sort();
-            Err(DiffError::SheetNotFound {
-                requested: sheet_name.to_string(),
-                available,
-            })
+        _ => Err(sheet_not_found(old_wb, new_wb, sheet_name, pool)),
+    }
+}
+
+/// Like [`find_sheets_case_insensitive`], but only requires the sheet on one side.
+fn find_sheet_in_either(
+    old_wb: &Workbook,
+    new_wb: &Workbook,
+    sheet_name: &str,
+    pool: &StringPool,
+) -> Result<(), DiffError> {
+    let sheet_name_lower = sheet_name.to_lowercase();
+    let found = old_wb
+        .sheets
+        .iter()
+        .chain(&new_wb.sheets)
+        .any(|s| pool.resolve(s.name).to_lowercase() == sheet_name_lower);
+    if found {
+        Ok(())
+    } else {
+        Err(sheet_not_found(old_wb, new_wb, sheet_name, pool))
+    }
+}
+
+fn sheet_not_found(
+    old_wb: &Workbook,
+    new_wb: &Workbook,
+    sheet_name: &str,
+    pool: &StringPool,
+) -> DiffError {
+    let mut available: Vec<String> = old_wb
+        .sheets
+        .iter()
+        .map(|s| pool.resolve(s.name).to_string())
+        .collect();
+    for s in &new_wb.sheets {
+        let name = pool.resolve(s.name).to_string();
+        if !available
+            .iter()
+            .any(|n| n.to_lowercase() == name.to_lowercase())
+        {
+            available.push(name);
         }
     }
+    available.sort();
+    DiffError::SheetNotFound {
+        requested: sheet_name.to_string(),
+        available,
+    }
 }
 
 fn append_permission_bindings_warnings(
@@ -1687,7 +2241,9 @@ mod tests {
             measures: vec![RawMeasure {
                 full_name: "Table/Measure1".to_string(),
                 expression: "1".to_string(),
+                ..Default::default()
             }],
+            ..Default::default()
         };
         let raw_b = RawTabularModel {
             tables: Vec::new(),
@@ -1695,15 +2251,19 @@ mod tests {
             measures: vec![RawMeasure {
                 full_name: "Table/Measure1".to_string(),
                 expression: "2".to_string(),
+                ..Default::default()
             }],
+            ..Default::default()
         };
 
         let pkg_a = PbixPackage {
             data_mashup: Some(dm_a),
+            report_layout: None,
             model_schema: Some(raw_a),
         };
         let pkg_b = PbixPackage {
             data_mashup: Some(dm_b),
+            report_layout: None,
             model_schema: Some(raw_b),
         };
 
@@ -1728,4 +2288,60 @@ mod tests {
             }
         }
     }
+
+    #[cfg(all(feature = "model-diff", feature = "excel-open-xml"))]
+    #[test]
+    fn pbix_diff_emits_report_layout_ops_after_query_ops() {
+        let layout = |title: &str| {
+            let literal =
+                serde_json::json!({ "expr": { "Literal": { "Value": format!("'{title}'") } } });
+            let config = serde_json::json!({
+                "name": "v1",
+                "singleVisual": {
+                    "visualType": "card",
+                    "vcObjects": { "title": [{ "properties": { "text": literal } }] },
+                },
+            })
+            .to_string();
+            let json = serde_json::json!({
+                "sections": [{
+                    "name": "ReportSection1",
+                    "displayName": "Overview",
+                    "visualContainers": [{ "config": config }],
+                }],
+            });
+            crate::report_layout::parse_report_layout(json.to_string().as_bytes())
+                .expect("layout should parse")
+        };
+
+        let pkg_a = PbixPackage {
+            data_mashup: Some(make_dm("section Section1;\nshared Foo = 1;")),
+            report_layout: Some(layout("Sales")),
+            model_schema: None,
+        };
+        let pkg_b = PbixPackage {
+            data_mashup: Some(make_dm("section Section1;\nshared Bar = 1;")),
+            report_layout: Some(layout("Revenue")),
+            model_schema: None,
+        };
+
+        let report = pkg_a.diff(&pkg_b, &DiffConfig::default());
+        let first_report = report
+            .ops
+            .iter()
+            .position(DiffOp::is_report_op)
+            .expect("expected report ops");
+        assert!(
+            report.ops[..first_report].iter().any(DiffOp::is_m_op),
+            "query ops should precede report ops"
+        );
+        assert!(matches!(
+            &report.ops[first_report],
+            DiffOp::ReportObjectChanged {
+                object: crate::diff::PbipEntityKind::Visual,
+                changes,
+                ..
+            } if changes.len() == 1
+        ));
+    }
 }
@@ -5,20 +5,7 @@ use xxhash_rust::xxh3::xxh3_64;
 
 use super::types::{PbipChangeKind, PbipDocDiff, PbipDocRecord, PbipDocSnapshot, PbipDocType, PbipProjectSnapshot};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-pub enum PbipEntityKind {
-    Report,
-    Page,
-    Visual,
-    Theme,
-    Bookmark,
-    Model,
-    Table,
-    Column,
-    Measure,
-    Relationship,
-    Other,
-}
+pub use crate::diff::PbipEntityKind;
 
 #[derive(Debug, Clone)]
 pub struct PbipEntityDiff {
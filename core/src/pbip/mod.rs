@@ -8,6 +8,8 @@
 
 mod diff;
 mod normalize;
+#[cfg(all(feature = "model-diff", feature = "excel-open-xml"))]
+mod tmdl;
 mod types;
 
 #[cfg(feature = "std-fs")]
@@ -17,6 +19,8 @@ pub use diff::{diff_snapshots, PbipDiffReport, PbipEntityDiff, PbipEntityKind};
 pub use normalize::{normalize_doc_text, NormalizationApplied, NormalizationError};
 #[cfg(feature = "std-fs")]
 pub use scan::{snapshot_project_from_fs, PbipScanConfig, PbipScanError};
+#[cfg(all(feature = "model-diff", feature = "excel-open-xml"))]
+pub use tmdl::diff_snapshot_models;
 pub use types::{
     PbipChangeKind, PbipDocDiff, PbipDocRecord, PbipDocSnapshot, PbipDocType,
     PbipNormalizationProfile, PbipProjectSnapshot,
@@ -99,3 +99,174 @@ fn diff_classifies_added_removed_modified_unchanged() {
     );
 }
 
+
+#[cfg(all(feature = "model-diff", feature = "excel-open-xml"))]
+const SALES_TMDL: &str = r#"table Sales
+    lineageTag: 1d3c
+
+    /// Sum of all sales
+    measure 'Total Sales' = SUM(Sales[Amount])
+        formatString: #,0
+        displayFolder: KPIs
+
+    measure Margin =
+            VAR cost = SUM(Sales[Cost])
+            RETURN
+                [Total Sales] - cost
+        kpi
+            targetExpression = 100
+
+    column Amount
+        dataType: decimal
+        isHidden
+        summarizeBy: sum
+        sourceColumn: Amount
+
+    column Double = [Amount] * 2
+        dataType: decimal
+
+    hierarchy Geography
+        level Country
+            column: Country
+        level City
+            column: City
+
+    partition Sales = m
+        mode: import
+        source =
+                let
+                    Source = Sql.Database("srv", "db")
+                in
+                    Source
+
+relationship 5e1f
+    fromColumn: Sales.CustomerKey
+    toColumn: 'Customer Dim'.'Customer Key'
+    isActive: false
+
+role 'West Region'
+    modelPermission: read
+
+    member 'ana@example.com'
+
+    tablePermission Sales = [Region] = "West"
+"#;
+
+#[cfg(all(feature = "model-diff", feature = "excel-open-xml"))]
+fn tmdl_snapshot(text: &str) -> PbipProjectSnapshot {
+    let text = text.replace("    ", "\t");
+    PbipProjectSnapshot {
+        docs: vec![PbipDocRecord {
+            path: "Sales.SemanticModel/definition/tables/Sales.tmdl".to_string(),
+            doc_type: PbipDocType::Tmdl,
+            snapshot: PbipDocSnapshot {
+                doc_type: PbipDocType::Tmdl,
+                hash: super::diff::hash_text(&text),
+                normalized_text: text,
+                error: None,
+                normalization_applied: None,
+            },
+        }],
+        profile: PbipNormalizationProfile::Balanced,
+        profile_summary: "x".to_string(),
+    }
+}
+
+#[cfg(all(feature = "model-diff", feature = "excel-open-xml"))]
+#[test]
+fn tmdl_parser_builds_tables_measures_relationships_and_roles() {
+    let text = SALES_TMDL.replace("    ", "\t");
+    let raw = super::tmdl::parse_tmdl_documents([text.as_str()]);
+
+    let table = &raw.tables[0];
+    assert_eq!(table.name, "Sales");
+    assert_eq!(table.columns.len(), 2);
+    assert_eq!(table.columns[0].is_hidden, Some(true));
+    assert_eq!(table.columns[1].expression.as_deref(), Some("[Amount] * 2"));
+    assert_eq!(
+        table.hierarchies[0].levels,
+        vec![
+            ("Country".to_string(), "Country".to_string()),
+            ("City".to_string(), "City".to_string()),
+        ]
+    );
+    let partition = &table.partitions[0];
+    assert_eq!(partition.source_type.as_deref(), Some("m"));
+    assert_eq!(partition.mode.as_deref(), Some("import"));
+    assert_eq!(
+        partition.source.as_deref(),
+        Some("let\n\tSource = Sql.Database(\"srv\", \"db\")\nin\n\tSource")
+    );
+
+    let margin = &raw.measures[0];
+    assert_eq!(margin.full_name, "Sales/Margin");
+    assert_eq!(
+        margin.expression,
+        "VAR cost = SUM(Sales[Cost])\nRETURN\n\t[Total Sales] - cost"
+    );
+    assert_eq!(
+        margin.kpi.as_ref().and_then(|k| k.target_expression.as_deref()),
+        Some("100")
+    );
+    let total = &raw.measures[1];
+    assert_eq!(total.full_name, "Sales/Total Sales");
+    assert_eq!(total.expression, "SUM(Sales[Amount])");
+    assert_eq!(total.format_string.as_deref(), Some("#,0"));
+    assert_eq!(total.description.as_deref(), Some("Sum of all sales"));
+
+    let rel = &raw.relationships[0];
+    assert_eq!((rel.from_table.as_str(), rel.from_column.as_str()), ("Sales", "CustomerKey"));
+    assert_eq!((rel.to_table.as_str(), rel.to_column.as_str()), ("Customer Dim", "Customer Key"));
+    assert_eq!(rel.is_active, Some(false));
+
+    let role = &raw.roles[0];
+    assert_eq!(role.name, "West Region");
+    assert_eq!(role.members, vec!["ana@example.com".to_string()]);
+    assert_eq!(
+        role.table_permissions[0].filter_expression.as_deref(),
+        Some("[Region] = \"West\"")
+    );
+}
+
+#[cfg(all(feature = "model-diff", feature = "excel-open-xml"))]
+#[test]
+fn pbip_model_diff_detects_formatting_only_measure_and_rls_changes() {
+    use crate::{DiffConfig, DiffOp, ExpressionChangeKind, ModelObjectKind};
+
+    let old = tmdl_snapshot(SALES_TMDL);
+    let new = tmdl_snapshot(
+        &SALES_TMDL
+            .replace("SUM(Sales[Amount])\n", "SUM ( Sales[Amount] )\n")
+            .replace("\"West\"", "\"East\""),
+    );
+
+    let mut config = DiffConfig::default();
+    config.semantic.enable_dax_semantic_diff = true;
+    let report = diff_snapshot_models(&old, &new, &config).expect("tmdl docs present");
+
+    assert_eq!(report.ops.len(), 2, "ops: {:?}", report.ops);
+    assert!(report.ops.iter().any(|op| matches!(
+        op,
+        DiffOp::MeasureDefinitionChanged {
+            change_kind: ExpressionChangeKind::FormattingOnly,
+            ..
+        }
+    )));
+    assert!(report.ops.iter().any(|op| matches!(
+        op,
+        DiffOp::ModelObjectChanged {
+            object: ModelObjectKind::Role,
+            ..
+        }
+    )));
+
+    let unchanged = diff_snapshot_models(&old, &old, &config).expect("tmdl docs present");
+    assert!(unchanged.ops.is_empty());
+}
+
+#[cfg(all(feature = "model-diff", feature = "excel-open-xml"))]
+#[test]
+fn pbip_model_diff_skips_projects_without_tmdl() {
+    let empty = PbipProjectSnapshot::default();
+    assert!(diff_snapshot_models(&empty, &empty, &crate::DiffConfig::default()).is_none());
+}
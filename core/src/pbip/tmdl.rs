@@ -0,0 +1,546 @@
+//! TMDL (Tabular Model Definition Language) parser for PBIP semantic models.
+//!
+//! The `definition/` folder of a PBIP semantic model is parsed into the same raw tabular model
+//! the PBIX `DataModelSchema` reader produces, so PBIP projects are diffed by `model_diff`
+//! (including DAX formatting-only detection) instead of by text hashing alone.
+//!
+//! The parser is deliberately forgiving: unknown objects and properties are ignored, and a
+//! malformed line never aborts the document.
+
+use crate::config::DiffConfig;
+use crate::diff::DiffReport;
+use crate::tabular_schema::{
+    build_model, normalize, RawCalculationGroup, RawCalculationItem, RawColumn, RawCulture,
+    RawHierarchy, RawKpi, RawMeasure, RawPartition, RawPerspective, RawRelationship, RawRole,
+    RawTable, RawTablePermission, RawTabularModel,
+};
+
+use super::types::{PbipDocType, PbipProjectSnapshot};
+
+/// One TMDL line with its nested children.
+///
+/// `measure 'Total' = SUM(Sales[Amount])` has keyword `measure`, name `Total` and the DAX as
+/// value; `formatString: 0.00` has keyword `formatString` and value `0.00`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct TmdlNode {
+    keyword: String,
+    name: String,
+    value: Option<String>,
+    description: Option<String>,
+    children: Vec<TmdlNode>,
+}
+
+impl TmdlNode {
+    fn children_named<'a>(&'a self, keyword: &'a str) -> impl Iterator<Item = &'a TmdlNode> {
+        self.children
+            .iter()
+            .filter(move |child| child.keyword == keyword)
+    }
+
+    fn property(&self, keyword: &str) -> Option<String> {
+        self.children_named(keyword)
+            .find_map(|child| child.value.clone())
+            .filter(|value| !value.is_empty())
+    }
+
+    /// Boolean properties may be written bare (`isHidden`) or with a value (`isActive: false`).
+    fn flag(&self, keyword: &str) -> Option<bool> {
+        let child = self.children_named(keyword).next()?;
+        match child.value.as_deref() {
+            None | Some("") => Some(true),
+            Some(value) => value.trim().parse().ok(),
+        }
+    }
+
+    fn expression(&self) -> Option<String> {
+        self.value.clone().filter(|value| !value.trim().is_empty())
+    }
+}
+
+/// Diff the tabular models described by the TMDL documents of two PBIP snapshots.
+///
+/// Returns `None` when neither snapshot contains TMDL documents (report-only projects).
+pub fn diff_snapshot_models(
+    old: &PbipProjectSnapshot,
+    new: &PbipProjectSnapshot,
+    config: &DiffConfig,
+) -> Option<DiffReport> {
+    let old_docs = tmdl_documents(old);
+    let new_docs = tmdl_documents(new);
+    if old_docs.is_empty() && new_docs.is_empty() {
+        return None;
+    }
+
+    let old_raw = parse_tmdl_documents(old_docs);
+    let new_raw = parse_tmdl_documents(new_docs);
+
+    Some(crate::with_default_session(|session| {
+        let old_model = build_model(&old_raw, &mut session.strings);
+        let new_model = build_model(&new_raw, &mut session.strings);
+        let result =
+            crate::model_diff::diff_models(&old_model, &new_model, &mut session.strings, config);
+
+        let mut report = DiffReport::new(result.ops);
+        if !result.complete {
+            report.complete = false;
+            report.warnings.extend(result.warnings);
+        }
+        report.strings = session.strings.strings().to_vec();
+        report
+    }))
+}
+
+fn tmdl_documents(snapshot: &PbipProjectSnapshot) -> Vec<&str> {
+    let mut docs: Vec<_> = snapshot
+        .docs
+        .iter()
+        .filter(|doc| doc.doc_type == PbipDocType::Tmdl && doc.snapshot.error.is_none())
+        .collect();
+    docs.sort_by(|a, b| a.path.cmp(&b.path));
+    docs.iter()
+        .map(|doc| doc.snapshot.normalized_text.as_str())
+        .collect()
+}
+
+/// Parse a set of TMDL documents (one per file) into a single normalized raw model.
+pub(crate) fn parse_tmdl_documents<'a>(docs: impl IntoIterator<Item = &'a str>) -> RawTabularModel {
+    let mut out = RawTabularModel::default();
+    for text in docs {
+        for node in parse_document(text) {
+            collect_object(&node, &mut out);
+        }
+    }
+    normalize(&mut out);
+    out
+}
+
+fn collect_object(node: &TmdlNode, out: &mut RawTabularModel) {
+    match node.keyword.as_str() {
+        "database" | "model" => {
+            for child in &node.children {
+                collect_object(child, out);
+            }
+        }
+        "table" => collect_table(node, out),
+        "relationship" => {
+            if let Some(relationship) = parse_relationship(node) {
+                out.relationships.push(relationship);
+            }
+        }
+        "role" => out.roles.push(parse_role(node)),
+        "perspective" => out.perspectives.push(parse_perspective(node)),
+        "cultureInfo" => out.cultures.push(parse_culture(node)),
+        _ => {}
+    }
+}
+
+fn collect_table(node: &TmdlNode, out: &mut RawTabularModel) {
+    let mut table = RawTable {
+        name: node.name.clone(),
+        ..Default::default()
+    };
+
+    for child in &node.children {
+        match child.keyword.as_str() {
+            "column" => table.columns.push(RawColumn {
+                name: child.name.clone(),
+                data_type: child.property("dataType"),
+                is_hidden: child.flag("isHidden"),
+                format_string: child.property("formatString"),
+                sort_by: child.property("sortByColumn"),
+                summarize_by: child.property("summarizeBy"),
+                expression: child.expression(),
+            }),
+            "measure" => out.measures.push(RawMeasure {
+                full_name: format!("{}/{}", table.name, child.name),
+                expression: child.expression().unwrap_or_default(),
+                format_string: child.property("formatString"),
+                display_folder: child.property("displayFolder"),
+                description: child.description.clone(),
+                kpi: child.children_named("kpi").next().map(|kpi| RawKpi {
+                    target_expression: kpi.property("targetExpression"),
+                    status_expression: kpi.property("statusExpression"),
+                    trend_expression: kpi.property("trendExpression"),
+                }),
+            }),
+            "hierarchy" => table.hierarchies.push(parse_hierarchy(child)),
+            "partition" => table.partitions.push(parse_partition(child)),
+            "calculationGroup" => table.calculation_group = Some(parse_calculation_group(child)),
+            _ => {}
+        }
+    }
+
+    if !table.name.is_empty() {
+        out.tables.push(table);
+    }
+}
+
+fn parse_hierarchy(node: &TmdlNode) -> RawHierarchy {
+    let mut levels: Vec<(i64, String, String)> = node
+        .children_named("level")
+        .enumerate()
+        .map(|(idx, level)| {
+            let ordinal = level
+                .property("ordinal")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(idx as i64);
+            let column = level.property("column").unwrap_or_default();
+            (ordinal, level.name.clone(), column)
+        })
+        .collect();
+    levels.sort_by_key(|(ordinal, _, _)| *ordinal);
+
+    RawHierarchy {
+        name: node.name.clone(),
+        description: node.description.clone(),
+        display_folder: node.property("displayFolder"),
+        is_hidden: node.flag("isHidden"),
+        levels: levels
+            .into_iter()
+            .map(|(_, name, column)| (name, column))
+            .collect(),
+    }
+}
+
+fn parse_partition(node: &TmdlNode) -> RawPartition {
+    let source = node.children_named("source").next();
+    RawPartition {
+        name: node.name.clone(),
+        mode: node.property("mode"),
+        source_type: node.expression(),
+        source: source.and_then(|s| {
+            s.expression()
+                .or_else(|| s.property("expression"))
+                .or_else(|| s.property("query"))
+        }),
+    }
+}
+
+fn parse_calculation_group(node: &TmdlNode) -> RawCalculationGroup {
+    let items = node
+        .children_named("calculationItem")
+        .map(|item| RawCalculationItem {
+            name: item.name.clone(),
+            expression: item.expression().unwrap_or_default(),
+            format_string_expression: item.property("formatStringDefinition"),
+            ordinal: item.property("ordinal").and_then(|v| v.parse().ok()),
+            description: item.description.clone(),
+        })
+        .collect();
+
+    RawCalculationGroup {
+        precedence: node.property("precedence").and_then(|v| v.parse().ok()),
+        description: node.description.clone(),
+        items,
+    }
+}
+
+fn parse_relationship(node: &TmdlNode) -> Option<RawRelationship> {
+    let (from_table, from_column) = split_column_ref(&node.property("fromColumn")?)?;
+    let (to_table, to_column) = split_column_ref(&node.property("toColumn")?)?;
+
+    let from_cardinality = node.property("fromCardinality");
+    let to_cardinality = node.property("toCardinality");
+    let cardinality = (from_cardinality.is_some() || to_cardinality.is_some()).then(|| {
+        format!(
+            "{}-{}",
+            from_cardinality.as_deref().unwrap_or("many"),
+            to_cardinality.as_deref().unwrap_or("one")
+        )
+    });
+
+    Some(RawRelationship {
+        from_table,
+        from_column,
+        to_table,
+        to_column,
+        cross_filtering_behavior: node.property("crossFilteringBehavior"),
+        cardinality,
+        is_active: node.flag("isActive"),
+        name: Some(node.name.clone()).filter(|name| !name.is_empty()),
+    })
+}
+
+fn parse_role(node: &TmdlNode) -> RawRole {
+    let table_permissions = node
+        .children_named("tablePermission")
+        .map(|permission| RawTablePermission {
+            table: permission.name.clone(),
+            filter_expression: permission.expression(),
+            metadata_permission: permission.property("metadataPermission"),
+            column_permissions: permission
+                .children_named("columnPermission")
+                .filter_map(|column| {
+                    let value = column
+                        .expression()
+                        .or_else(|| column.property("metadataPermission"))?;
+                    Some((column.name.clone(), value))
+                })
+                .collect(),
+        })
+        .collect();
+
+    RawRole {
+        name: node.name.clone(),
+        description: node.description.clone(),
+        model_permission: node.property("modelPermission"),
+        members: node
+            .children_named("member")
+            .map(|member| member.name.clone())
+            .collect(),
+        table_permissions,
+    }
+}
+
+fn parse_perspective(node: &TmdlNode) -> RawPerspective {
+    let mut members = Vec::new();
+    for table in node.children_named("perspectiveTable") {
+        members.push(format!("table {}", table.name));
+        for (keyword, kind) in [
+            ("perspectiveColumn", "column"),
+            ("perspectiveMeasure", "measure"),
+            ("perspectiveHierarchy", "hierarchy"),
+        ] {
+            for member in table.children_named(keyword) {
+                members.push(format!("{} {}[{}]", kind, table.name, member.name));
+            }
+        }
+    }
+    RawPerspective {
+        name: node.name.clone(),
+        members,
+    }
+}
+
+fn parse_culture(node: &TmdlNode) -> RawCulture {
+    let mut translations = Vec::new();
+    for model in node
+        .children_named("translations")
+        .flat_map(|t| t.children_named("model"))
+    {
+        push_translations(model, "model", &mut translations);
+        for table in model.children_named("table") {
+            push_translations(table, &format!("table {}", table.name), &mut translations);
+            for kind in ["column", "measure", "hierarchy"] {
+                for member in table.children_named(kind) {
+                    let object = format!("{} {}[{}]", kind, table.name, member.name);
+                    push_translations(member, &object, &mut translations);
+                }
+            }
+        }
+    }
+    RawCulture {
+        name: node.name.clone(),
+        translations,
+    }
+}
+
+fn push_translations(node: &TmdlNode, object: &str, out: &mut Vec<(String, String)>) {
+    for (keyword, property) in [
+        ("caption", "caption"),
+        ("description", "description"),
+        ("displayFolder", "display folder"),
+    ] {
+        if let Some(text) = node.property(keyword) {
+            out.push((format!("{} {}", object, property), text));
+        }
+    }
+}
+
+/// Split `Sales.CustomerKey` or `'Sales Data'.'Customer Key'` into table and column.
+fn split_column_ref(text: &str) -> Option<(String, String)> {
+    let text = text.trim();
+    let (table, rest) = if text.starts_with('\'') {
+        parse_name(text)
+    } else {
+        let dot = text.find('.')?;
+        (text[..dot].to_string(), &text[dot..])
+    };
+    let (column, _) = parse_name(rest.strip_prefix('.')?);
+    (!table.is_empty() && !column.is_empty()).then_some((table, column))
+}
+
+fn parse_document(text: &str) -> Vec<TmdlNode> {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut roots: Vec<TmdlNode> = Vec::new();
+    let mut stack: Vec<(usize, TmdlNode)> = Vec::new();
+    let mut description: Vec<String> = Vec::new();
+
+    let mut idx = 0;
+    while idx < lines.len() {
+        let (level, text) = indent_level(lines[idx]);
+        idx += 1;
+        if text.is_empty() {
+            continue;
+        }
+        if let Some(doc) = text.strip_prefix("///") {
+            description.push(doc.trim().to_string());
+            continue;
+        }
+        let Some((mut node, is_expression)) = parse_line(text) else {
+            description.clear();
+            continue;
+        };
+
+        if is_expression {
+            if node.value.as_deref() == Some("```") {
+                let (expression, next) = take_fenced_expression(&lines, idx);
+                node.value = Some(expression);
+                idx = next;
+            } else {
+                // Multi-line expressions sit deeper than the object's own properties.
+                let min_level = if node.name.is_empty() {
+                    level
+                } else {
+                    level + 1
+                };
+                let (expression, next) = take_expression(&lines, idx, min_level);
+                if !expression.is_empty() {
+                    node.value = Some(match node.value.take().filter(|v| !v.is_empty()) {
+                        Some(head) => format!("{head}\n{expression}"),
+                        None => expression,
+                    });
+                    idx = next;
+                }
+            }
+        }
+        if !description.is_empty() {
+            node.description = Some(description.join("\n"));
+            description.clear();
+        }
+
+        while stack.last().is_some_and(|(top, _)| *top >= level) {
+            if let Some((_, done)) = stack.pop() {
+                attach(&mut stack, &mut roots, done);
+            }
+        }
+        stack.push((level, node));
+    }
+
+    while let Some((_, done)) = stack.pop() {
+        attach(&mut stack, &mut roots, done);
+    }
+    roots
+}
+
+fn attach(stack: &mut [(usize, TmdlNode)], roots: &mut Vec<TmdlNode>, node: TmdlNode) {
+    match stack.last_mut() {
+        Some((_, parent)) => parent.children.push(node),
+        None => roots.push(node),
+    }
+}
+
+/// Indentation level of a line (one tab or four spaces per level) and its trimmed text.
+fn indent_level(line: &str) -> (usize, &str) {
+    let trimmed = line.trim_start();
+    let indent = &line[..line.len() - trimmed.len()];
+    let tabs = indent.chars().filter(|c| *c == '\t').count();
+    let spaces = indent.chars().filter(|c| *c == ' ').count();
+    (tabs + spaces / 4, trimmed.trim_end())
+}
+
+/// Parse `keyword`, `keyword: value`, `keyword = expr` or `keyword name [= expr]`.
+///
+/// The flag is `true` when the value is an expression (`=`) that may continue on later lines.
+fn parse_line(text: &str) -> Option<(TmdlNode, bool)> {
+    let keyword_len = text
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(text.len());
+    if keyword_len == 0 {
+        return None;
+    }
+    let keyword = text[..keyword_len].to_string();
+    let rest = text[keyword_len..].trim_start();
+
+    let mut node = TmdlNode {
+        keyword,
+        ..Default::default()
+    };
+    if let Some(value) = rest.strip_prefix(':') {
+        node.value = Some(value.trim().to_string());
+        return Some((node, false));
+    }
+    if let Some(value) = rest.strip_prefix('=') {
+        node.value = Some(value.trim().to_string());
+        return Some((node, true));
+    }
+    if rest.is_empty() {
+        return Some((node, false));
+    }
+
+    let (name, rest) = parse_name(rest);
+    node.name = name;
+    match rest.trim_start().strip_prefix('=') {
+        Some(value) => {
+            node.value = Some(value.trim().to_string());
+            Some((node, true))
+        }
+        None => Some((node, false)),
+    }
+}
+
+/// Parse a possibly single-quoted object name, returning it with the unconsumed remainder.
+fn parse_name(text: &str) -> (String, &str) {
+    let Some(quoted) = text.strip_prefix('\'') else {
+        let end = text
+            .find(|c: char| c.is_whitespace() || c == '=' || c == ':')
+            .unwrap_or(text.len());
+        return (text[..end].to_string(), &text[end..]);
+    };
+
+    let mut name = String::new();
+    let mut chars = quoted.char_indices().peekable();
+    while let Some((idx, c)) = chars.next() {
+        if c != '\'' {
+            name.push(c);
+            continue;
+        }
+        if chars.peek().is_some_and(|(_, next)| *next == '\'') {
+            chars.next();
+            name.push('\'');
+            continue;
+        }
+        return (name, &quoted[idx + 1..]);
+    }
+    (name, "")
+}
+
+fn take_expression(lines: &[&str], start: usize, min_level: usize) -> (String, usize) {
+    let mut end = start;
+    let mut last_content = start;
+    while end < lines.len() {
+        let (level, text) = indent_level(lines[end]);
+        if !text.is_empty() {
+            if level <= min_level {
+                break;
+            }
+            last_content = end + 1;
+        }
+        end += 1;
+    }
+    (dedent(&lines[start..last_content]), last_content)
+}
+
+fn take_fenced_expression(lines: &[&str], start: usize) -> (String, usize) {
+    let mut end = start;
+    while end < lines.len() && lines[end].trim() != "```" {
+        end += 1;
+    }
+    (dedent(&lines[start..end]), (end + 1).min(lines.len()))
+}
+
+fn dedent(lines: &[&str]) -> String {
+    let common = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|line| line.get(common..).unwrap_or("").trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
@@ -0,0 +1,445 @@
+//! Pivot table and pivot cache definition parsing.
+//!
+//! A worksheet's relationships point at its `xl/pivotTables/pivotTable*.xml` parts; each of those
+//! points at the `xl/pivotCache/pivotCacheDefinition*.xml` it reads. The cache supplies the source
+//! and the field names, and its shared items name the hidden and selected items that the table
+//! refers to by index. Cache records are not read.
+
+use crate::grid_parser::{local_tag_name, xml_err, GridParseError};
+use crate::string_pool::{StringId, StringPool};
+use crate::styles::{attr, collect_attrs};
+use crate::workbook::{
+    PivotCalculatedField, PivotDataField, PivotFilter, PivotPageField, PivotSource, PivotTable,
+};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+pub(crate) const PIVOT_TABLE_REL: &str = "relationships/pivotTable";
+pub(crate) const PIVOT_CACHE_REL: &str = "relationships/pivotCacheDefinition";
+
+/// Field index of the values pseudo-field in `rowFields`/`colFields`.
+const VALUES_FIELD: i64 = -2;
+
+/// A parsed pivot cache definition, shared by every pivot table built on it.
+#[derive(Debug, Clone)]
+pub(crate) struct PivotCache {
+    pub source: PivotSource,
+    pub fields: Vec<PivotCacheField>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PivotCacheField {
+    pub name: String,
+    /// Formula of a calculated field.
+    pub formula: Option<String>,
+    /// Display text of each shared item, in index order.
+    pub items: Vec<String>,
+}
+
+fn parse_u32(value: Option<&str>) -> Option<u32> {
+    value.and_then(|v| v.trim().parse().ok())
+}
+
+fn parse_index(value: Option<&str>) -> Option<i64> {
+    value.and_then(|v| v.trim().parse().ok())
+}
+
+pub(crate) fn parse_pivot_cache_xml(
+    xml: &[u8],
+    pool: &mut StringPool,
+) -> Result<PivotCache, GridParseError> {
+    let mut reader = Reader::from_reader(xml);
+    reader.config_mut().trim_text(true);
+    let mut buf = Vec::new();
+
+    let mut source = PivotSource {
+        kind: pool.intern("worksheet"),
+        sheet: None,
+        range: None,
+        name: None,
+        connection_id: None,
+    };
+    let mut fields: Vec<PivotCacheField> = Vec::new();
+    // Inside `sharedItems` or `groupItems`. Grouped fields (dates, numeric ranges) index their
+    // pivot items into the group items, so those replace the shared items.
+    let mut in_items = false;
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) if local_tag_name(e.name().as_ref()) == b"sharedItems" => {
+                in_items = true;
+            }
+            Ok(Event::Start(e)) if local_tag_name(e.name().as_ref()) == b"groupItems" => {
+                in_items = true;
+                if let Some(field) = fields.last_mut() {
+                    field.items.clear();
+                }
+            }
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) => match local_tag_name(e.name().as_ref()) {
+                b"cacheSource" => {
+                    let attrs = collect_attrs(&reader, xml, &e)?;
+                    if let Some(kind) = attr(&attrs, "type") {
+                        source.kind = pool.intern(kind);
+                    }
+                    source.connection_id = parse_u32(attr(&attrs, "connectionId"));
+                }
+                b"worksheetSource" => {
+                    let attrs = collect_attrs(&reader, xml, &e)?;
+                    source.sheet = attr(&attrs, "sheet").map(|s| pool.intern(s));
+                    source.range = attr(&attrs, "ref").and_then(|r| r.trim().parse().ok());
+                    source.name = attr(&attrs, "name").map(|s| pool.intern(s));
+                }
+                b"cacheField" => {
+                    let attrs = collect_attrs(&reader, xml, &e)?;
+                    fields.push(PivotCacheField {
+                        name: attr(&attrs, "name").unwrap_or_default().to_string(),
+                        formula: attr(&attrs, "formula").map(str::to_string),
+                        items: Vec::new(),
+                    });
+                }
+                b"s" | b"n" | b"d" | b"b" | b"e" | b"m" if in_items => {
+                    let attrs = collect_attrs(&reader, xml, &e)?;
+                    let text = attr(&attrs, "v").unwrap_or("(blank)").to_string();
+                    if let Some(field) = fields.last_mut() {
+                        field.items.push(text);
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::End(e))
+                if matches!(
+                    local_tag_name(e.name().as_ref()),
+                    b"sharedItems" | b"groupItems"
+                ) =>
+            {
+                in_items = false;
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(xml_err(&reader, xml, e)),
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(PivotCache { source, fields })
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Other,
+    PivotFields,
+    RowFields,
+    ColFields,
+}
+
+#[derive(Default)]
+struct PivotFieldItems {
+    /// Shared item index of each `<item>`, `None` for subtotal items.
+    items: Vec<Option<usize>>,
+    hidden: Vec<usize>,
+}
+
+/// Parse one pivot table part against its cache. Returns `None` when the part has no
+/// `<pivotTableDefinition>`.
+pub(crate) fn parse_pivot_table_xml(
+    xml: &[u8],
+    cache: &PivotCache,
+    (sheet, workbook_sheet_id): (StringId, Option<u32>),
+    pool: &mut StringPool,
+) -> Result<Option<PivotTable>, GridParseError> {
+    let mut reader = Reader::from_reader(xml);
+    reader.config_mut().trim_text(true);
+    let mut buf = Vec::new();
+
+    let field_name = |idx: i64| -> String {
+        if idx == VALUES_FIELD {
+            return "Values".to_string();
+        }
+        usize::try_from(idx)
+            .ok()
+            .and_then(|idx| cache.fields.get(idx))
+            .map(|field| field.name.clone())
+            .unwrap_or_else(|| format!("field {idx}"))
+    };
+    let item_name = |field: usize, item: usize| -> String {
+        cache
+            .fields
+            .get(field)
+            .and_then(|f| f.items.get(item))
+            .cloned()
+            .unwrap_or_else(|| format!("item {item}"))
+    };
+
+    let mut table: Option<PivotTable> = None;
+    let mut pivot_fields: Vec<PivotFieldItems> = Vec::new();
+    let mut section = Section::Other;
+    // Filter field index and arguments of the open `<filter>`.
+    let mut open_filter: Option<(i64, String, Vec<String>)> = None;
+    let mut page_fields: Vec<(i64, Option<usize>)> = Vec::new();
+    let mut filters: Vec<(i64, String, Vec<String>)> = Vec::new();
+
+    loop {
+        let (e, is_start) = match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) => (e, true),
+            Ok(Event::Empty(e)) => (e, false),
+            Ok(Event::End(e)) => {
+                match local_tag_name(e.name().as_ref()) {
+                    b"rowFields" | b"colFields" | b"pivotFields" => section = Section::Other,
+                    b"filter" => filters.extend(open_filter.take()),
+                    _ => {}
+                }
+                buf.clear();
+                continue;
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(xml_err(&reader, xml, e)),
+            _ => {
+                buf.clear();
+                continue;
+            }
+        };
+        let attrs = collect_attrs(&reader, xml, &e)?;
+        match local_tag_name(e.name().as_ref()) {
+            b"pivotTableDefinition" => {
+                table = Some(PivotTable {
+                    sheet,
+                    workbook_sheet_id,
+                    name: pool.intern(attr(&attrs, "name").unwrap_or_default()),
+                    location: None,
+                    source: cache.source.clone(),
+                    row_fields: Vec::new(),
+                    column_fields: Vec::new(),
+                    page_fields: Vec::new(),
+                    data_fields: Vec::new(),
+                    filters: Vec::new(),
+                    calculated_fields: Vec::new(),
+                });
+            }
+            b"location" => {
+                if let Some(table) = table.as_mut() {
+                    table.location = attr(&attrs, "ref").and_then(|r| r.trim().parse().ok());
+                }
+            }
+            b"pivotFields" if is_start => section = Section::PivotFields,
+            b"rowFields" if is_start => section = Section::RowFields,
+            b"colFields" if is_start => section = Section::ColFields,
+            b"pivotField" => pivot_fields.push(PivotFieldItems::default()),
+            b"item" if section == Section::PivotFields => {
+                if let Some(field) = pivot_fields.last_mut() {
+                    let x = parse_u32(attr(&attrs, "x")).map(|x| x as usize);
+                    let x = x.filter(|_| attr(&attrs, "t").is_none());
+                    if let Some(x) = x
+                        && matches!(attr(&attrs, "h"), Some("1" | "true"))
+                    {
+                        field.hidden.push(x);
+                    }
+                    field.items.push(x);
+                }
+            }
+            b"field" if matches!(section, Section::RowFields | Section::ColFields) => {
+                if let (Some(table), Some(idx)) = (table.as_mut(), parse_index(attr(&attrs, "x"))) {
+                    let name = pool.intern(&field_name(idx));
+                    if section == Section::RowFields {
+                        table.row_fields.push(name);
+                    } else {
+                        table.column_fields.push(name);
+                    }
+                }
+            }
+            b"pageField" => {
+                if let Some(fld) = parse_index(attr(&attrs, "fld")) {
+                    page_fields.push((fld, parse_u32(attr(&attrs, "item")).map(|i| i as usize)));
+                }
+            }
+            b"dataField" => {
+                if let Some(table) = table.as_mut() {
+                    let field = field_name(parse_index(attr(&attrs, "fld")).unwrap_or(-1));
+                    let name = attr(&attrs, "name").map(str::to_string);
+                    let function = attr(&attrs, "subtotal").unwrap_or("sum");
+                    table.data_fields.push(PivotDataField {
+                        name: pool.intern(name.as_deref().unwrap_or(&field)),
+                        field: pool.intern(&field),
+                        function: pool.intern(function),
+                        show_data_as: attr(&attrs, "showDataAs").map(|s| pool.intern(s)),
+                    });
+                }
+            }
+            b"filter" => {
+                let fld = parse_index(attr(&attrs, "fld")).unwrap_or(-1);
+                let kind = attr(&attrs, "type").unwrap_or("unknown").to_string();
+                let values: Vec<String> = ["stringValue1", "stringValue2"]
+                    .iter()
+                    .filter_map(|key| attr(&attrs, key).map(str::to_string))
+                    .collect();
+                let filter = (fld, kind, values);
+                if is_start {
+                    open_filter = Some(filter);
+                } else {
+                    filters.push(filter);
+                }
+            }
+            // Value and top-N filters keep their arguments in the nested auto filter.
+            b"customFilter" | b"top10" => {
+                if let Some((_, _, values)) = open_filter.as_mut()
+                    && values.is_empty()
+                    && let Some(val) = attr(&attrs, "val")
+                {
+                    values.push(val.to_string());
+                }
+            }
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    let Some(mut table) = table else {
+        return Ok(None);
+    };
+
+    for (fld, item) in page_fields {
+        let field = pool.intern(&field_name(fld));
+        let item = usize::try_from(fld).ok().and_then(|f| {
+            let shared = pivot_fields.get(f)?.items.get(item?).copied().flatten()?;
+            Some(pool.intern(&item_name(f, shared)))
+        });
+        table.page_fields.push(PivotPageField { field, item });
+    }
+
+    for (idx, field) in pivot_fields.iter().enumerate() {
+        if field.hidden.is_empty() {
+            continue;
+        }
+        let values = field
+            .hidden
+            .iter()
+            .map(|&item| pool.intern(&item_name(idx, item)))
+            .collect();
+        table.filters.push(PivotFilter {
+            field: pool.intern(&field_name(idx as i64)),
+            kind: pool.intern("manual"),
+            values,
+        });
+    }
+    for (fld, kind, values) in filters {
+        table.filters.push(PivotFilter {
+            field: pool.intern(&field_name(fld)),
+            kind: pool.intern(&kind),
+            values: values.iter().map(|v| pool.intern(v)).collect(),
+        });
+    }
+
+    for field in &cache.fields {
+        if let Some(formula) = field.formula.as_deref() {
+            table.calculated_fields.push(PivotCalculatedField {
+                name: pool.intern(&field.name),
+                formula: pool.intern(formula),
+            });
+        }
+    }
+
+    Ok(Some(table))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_pivot_layout_against_its_cache() {
+        let cache_xml = concat!(
+            r#"<pivotCacheDefinition "#,
+            r#"xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">"#,
+            r#"<cacheSource type="worksheet"><worksheetSource ref="A1:C50" sheet="Data"/>"#,
+            r#"</cacheSource><cacheFields count="4"><cacheField name="Region" numFmtId="0">"#,
+            r#"<sharedItems count="3"><s v="East"/><s v="West"/><s v="North"/></sharedItems>"#,
+            r#"</cacheField><cacheField name="Year"><sharedItems containsNumber="1">"#,
+            r#"<n v="2023"/><n v="2024"/></sharedItems></cacheField><cacheField name="Sales">"#,
+            r#"<sharedItems containsNumber="1"/></cacheField>"#,
+            r#"<cacheField name="Margin" formula="Sales*0.1" databaseField="0"/>"#,
+            r#"</cacheFields></pivotCacheDefinition>"#,
+        );
+        let table_xml = concat!(
+            r#"<pivotTableDefinition "#,
+            r#"xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" "#,
+            r#"name="Sales by Region" cacheId="1" dataCaption="Values">"#,
+            r#"<location ref="A3:C8" firstHeaderRow="1" firstDataRow="1" firstDataCol="1"/>"#,
+            r#"<pivotFields count="4"><pivotField axis="axisRow" showAll="0"><items count="4">"#,
+            r#"<item x="0"/><item h="1" x="1"/><item x="2"/><item t="default"/></items>"#,
+            r#"</pivotField><pivotField axis="axisPage" showAll="0"><items count="3">"#,
+            r#"<item x="0"/><item x="1"/><item t="default"/></items></pivotField>"#,
+            r#"<pivotField dataField="1" showAll="0"/>"#,
+            r#"<pivotField dataField="1" dragToRow="0"/></pivotFields>"#,
+            r#"<rowFields count="1"><field x="0"/></rowFields><colFields count="1">"#,
+            r#"<field x="-2"/></colFields><pageFields count="1">"#,
+            r#"<pageField fld="1" item="1" hier="-1"/></pageFields><dataFields count="2">"#,
+            r#"<dataField name="Sum of Sales" fld="2" baseField="0" baseItem="0"/>"#,
+            r#"<dataField name="Share" fld="3" subtotal="average" showDataAs="percentOfTotal"/>"#,
+            r#"</dataFields><filters count="1"><filter fld="0" type="captionBeginsWith" "#,
+            r#"evalOrder="-1" id="2" stringValue1="E"><autoFilter ref="A1">"#,
+            r#"<filterColumn colId="0"><customFilters><customFilter val="E*"/></customFilters>"#,
+            r#"</filterColumn></autoFilter></filter></filters>"#,
+            r#"</pivotTableDefinition>"#,
+        );
+
+        let mut pool = StringPool::new();
+        let cache = parse_pivot_cache_xml(cache_xml.as_bytes(), &mut pool).expect("parse cache");
+        let sheet = pool.intern("Report");
+        let table =
+            parse_pivot_table_xml(table_xml.as_bytes(), &cache, (sheet, Some(2)), &mut pool)
+                .expect("parse pivot")
+                .expect("pivot present");
+
+        let names = |ids: &[StringId]| -> Vec<String> {
+            ids.iter().map(|id| pool.resolve(*id).to_string()).collect()
+        };
+        assert_eq!(pool.resolve(table.name), "Sales by Region");
+        assert_eq!(table.location.map(|r| r.to_a1()).as_deref(), Some("A3:C8"));
+        assert_eq!(pool.resolve(table.source.kind), "worksheet");
+        assert_eq!(table.source.sheet.map(|s| pool.resolve(s)), Some("Data"));
+        assert_eq!(
+            table.source.range.map(|r| r.to_a1()).as_deref(),
+            Some("A1:C50")
+        );
+        assert_eq!(names(&table.row_fields), vec!["Region"]);
+        assert_eq!(names(&table.column_fields), vec!["Values"]);
+
+        let page = &table.page_fields[0];
+        assert_eq!(pool.resolve(page.field), "Year");
+        assert_eq!(page.item.map(|i| pool.resolve(i)), Some("2024"));
+
+        let share = &table.data_fields[1];
+        assert_eq!(
+            (
+                pool.resolve(share.name),
+                pool.resolve(share.field),
+                pool.resolve(share.function),
+                share.show_data_as.map(|s| pool.resolve(s)),
+            ),
+            ("Share", "Margin", "average", Some("percentOfTotal"))
+        );
+        assert_eq!(pool.resolve(table.data_fields[0].function), "sum");
+
+        let filters: Vec<(&str, &str, Vec<String>)> = table
+            .filters
+            .iter()
+            .map(|f| {
+                (
+                    pool.resolve(f.field),
+                    pool.resolve(f.kind),
+                    names(&f.values),
+                )
+            })
+            .collect();
+        assert_eq!(
+            filters,
+            vec![
+                ("Region", "manual", vec!["West".to_string()]),
+                ("Region", "captionBeginsWith", vec!["E".to_string()]),
+            ]
+        );
+
+        let calculated = &table.calculated_fields[0];
+        assert_eq!(pool.resolve(calculated.name), "Margin");
+        assert_eq!(pool.resolve(calculated.formula), "Sales*0.1");
+    }
+}
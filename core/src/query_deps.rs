@@ -0,0 +1,329 @@
+//! Dependency graph between the Power Query queries of one package and the impact of edited
+//! queries.
+//!
+//! Every query's M is parsed and the names it reads from the section scope are matched against
+//! the other members of its section, so `Source = Sales` and `#"Sales (2)"(x)` both count as
+//! references. Queries whose M does not parse read nothing, but can still be read by others.
+//! Embedded queries resolve within their own embedded section.
+
+use crate::datamashup::{build_embedded_queries, build_queries, DataMashup, Query};
+use crate::diff::DiffOp;
+use crate::m_ast::{free_identifiers, parse_m_expression};
+use crate::m_section::SectionParseError;
+use crate::string_pool::StringPool;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+
+/// A query and the queries it reads directly.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct QueryNode {
+    /// `Section/Member`, as in [`Query::name`].
+    pub name: String,
+    /// The section member name, which is also the name of the table the query loads.
+    pub member: String,
+    pub load_to_sheet: bool,
+    pub load_to_model: bool,
+    /// Queries read directly, by full name, in graph order.
+    pub references: Vec<String>,
+}
+
+impl QueryNode {
+    /// Whether the query loads a table to a sheet or the data model.
+    pub fn is_loaded(&self) -> bool {
+        self.load_to_sheet || self.load_to_model
+    }
+}
+
+/// Reference graph over the queries of one package, in query order.
+#[derive(Debug, Clone, Default)]
+pub struct QueryGraph {
+    nodes: Vec<QueryNode>,
+    index: HashMap<String, usize>,
+    /// Queries reading each query directly.
+    readers: Vec<Vec<usize>>,
+}
+
+impl QueryGraph {
+    /// Parse every query and record which queries it reads.
+    pub fn build(queries: &[Query]) -> QueryGraph {
+        let mut members: HashMap<(&str, &str), usize> = HashMap::new();
+        for (idx, query) in queries.iter().enumerate() {
+            members.insert(
+                (
+                    query.metadata.section_name.as_str(),
+                    query.section_member.as_str(),
+                ),
+                idx,
+            );
+        }
+
+        let mut references = vec![Vec::new(); queries.len()];
+        let mut readers = vec![Vec::new(); queries.len()];
+        for (reader, query) in queries.iter().enumerate() {
+            let Ok(ast) = parse_m_expression(&query.expression_m) else {
+                continue;
+            };
+            let section = query.metadata.section_name.as_str();
+            let mut read: Vec<usize> = free_identifiers(&ast)
+                .iter()
+                .filter_map(|name| members.get(&(section, name.as_str())).copied())
+                .filter(|&target| target != reader)
+                .collect();
+            read.sort_unstable();
+            for &target in &read {
+                readers[target].push(reader);
+            }
+            references[reader] = read;
+        }
+
+        let nodes: Vec<QueryNode> = queries
+            .iter()
+            .zip(&references)
+            .map(|(query, read)| QueryNode {
+                name: query.name.clone(),
+                member: query.section_member.clone(),
+                load_to_sheet: query.metadata.load_to_sheet,
+                load_to_model: query.metadata.load_to_model,
+                references: read.iter().map(|&idx| queries[idx].name.clone()).collect(),
+            })
+            .collect();
+        QueryGraph {
+            index: nodes
+                .iter()
+                .enumerate()
+                .map(|(idx, node)| (node.name.clone(), idx))
+                .collect(),
+            nodes,
+            readers,
+        }
+    }
+
+    /// Graph over the top-level and embedded queries of `dm`.
+    pub fn from_data_mashup(dm: &DataMashup) -> Result<QueryGraph, SectionParseError> {
+        let mut queries = build_queries(dm)?;
+        queries.extend(build_embedded_queries(dm));
+        Ok(QueryGraph::build(&queries))
+    }
+
+    /// Every query, in package order.
+    pub fn queries(&self) -> &[QueryNode] {
+        &self.nodes
+    }
+
+    pub fn query(&self, name: &str) -> Option<&QueryNode> {
+        self.index.get(name).map(|&idx| &self.nodes[idx])
+    }
+
+    /// Every query that reads `name`, directly or through other queries, in package order. The
+    /// query itself is not listed, even when it sits on a cycle.
+    pub fn dependents(&self, name: &str) -> Vec<&QueryNode> {
+        match self.index.get(name) {
+            Some(&idx) => self
+                .dependent_indices(idx)
+                .into_iter()
+                .map(|dep| &self.nodes[dep])
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Queries among `name` and its dependents that load a table, in package order.
+    pub fn loaded_tables(&self, name: &str) -> Vec<&QueryNode> {
+        let Some(&idx) = self.index.get(name) else {
+            return Vec::new();
+        };
+        let mut nodes = self.dependent_indices(idx);
+        nodes.push(idx);
+        nodes.sort_unstable();
+        nodes
+            .into_iter()
+            .map(|node| &self.nodes[node])
+            .filter(|node| node.is_loaded())
+            .collect()
+    }
+
+    fn dependent_indices(&self, start: usize) -> Vec<usize> {
+        let mut seen = vec![false; self.nodes.len()];
+        seen[start] = true;
+        let mut queue = VecDeque::from([start]);
+        let mut found = Vec::new();
+        while let Some(node) = queue.pop_front() {
+            for &reader in &self.readers[node] {
+                if !seen[reader] {
+                    seen[reader] = true;
+                    found.push(reader);
+                    queue.push_back(reader);
+                }
+            }
+        }
+        found.sort_unstable();
+        found
+    }
+
+    /// Fill in the downstream queries and loaded tables of each `QueryDefinitionChanged` in
+    /// `ops`. Build the graph from the new side of the diff.
+    pub(crate) fn annotate(&self, ops: &mut [DiffOp], pool: &mut StringPool) {
+        for op in ops {
+            let DiffOp::QueryDefinitionChanged {
+                name,
+                downstream_queries,
+                loaded_tables,
+                ..
+            } = op
+            else {
+                continue;
+            };
+            let query = pool.resolve(*name).to_string();
+            *downstream_queries = self
+                .dependents(&query)
+                .iter()
+                .map(|node| pool.intern(&node.name))
+                .collect();
+            *loaded_tables = self
+                .loaded_tables(&query)
+                .iter()
+                .map(|node| pool.intern(&node.member))
+                .collect();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datamashup::QueryMetadata;
+
+    fn query(section: &str, member: &str, expr: &str, load_to_sheet: bool) -> Query {
+        let name = format!("{section}/{member}");
+        Query {
+            name: name.clone(),
+            section_member: member.to_string(),
+            expression_m: expr.to_string(),
+            metadata: QueryMetadata {
+                item_path: name,
+                section_name: section.to_string(),
+                formula_name: member.to_string(),
+                load_to_sheet,
+                load_to_model: false,
+                is_connection_only: !load_to_sheet,
+                group_path: None,
+            },
+        }
+    }
+
+    fn names(nodes: &[&QueryNode]) -> Vec<String> {
+        nodes.iter().map(|node| node.name.clone()).collect()
+    }
+
+    fn sample() -> QueryGraph {
+        QueryGraph::build(&[
+            query(
+                "Section1",
+                "Raw",
+                "let Source = Csv.Document(\"a\") in Source",
+                false,
+            ),
+            query(
+                "Section1",
+                "Clean",
+                "let Source = Raw, Rows = Table.SelectRows(Source, each [Raw] <> null) in Rows",
+                false,
+            ),
+            query(
+                "Section1",
+                "Report",
+                "Table.Combine({Clean, #\"Fn Pad\"(Raw)})",
+                true,
+            ),
+            query("Section1", "Fn Pad", "(t) => t", false),
+            query("Section1", "Shadow", "let Raw = 1 in Raw", true),
+        ])
+    }
+
+    #[test]
+    fn references_resolve_quoted_names_and_function_calls() {
+        let graph = sample();
+        assert_eq!(
+            graph.query("Section1/Clean").unwrap().references,
+            vec!["Section1/Raw"]
+        );
+        assert_eq!(
+            graph.query("Section1/Report").unwrap().references,
+            vec!["Section1/Raw", "Section1/Clean", "Section1/Fn Pad"]
+        );
+        assert!(graph
+            .query("Section1/Shadow")
+            .unwrap()
+            .references
+            .is_empty());
+    }
+
+    #[test]
+    fn dependents_and_loaded_tables_are_transitive() {
+        let graph = sample();
+        assert_eq!(
+            names(&graph.dependents("Section1/Raw")),
+            vec!["Section1/Clean", "Section1/Report"]
+        );
+        assert_eq!(
+            names(&graph.loaded_tables("Section1/Raw")),
+            vec!["Section1/Report"]
+        );
+        assert_eq!(
+            names(&graph.loaded_tables("Section1/Report")),
+            vec!["Section1/Report"]
+        );
+        assert!(graph.dependents("Section1/Missing").is_empty());
+    }
+
+    #[test]
+    fn references_stay_within_their_section() {
+        let graph = QueryGraph::build(&[
+            query("Section1", "Raw", "1", false),
+            query("Embedded/x/Section1", "Use", "Raw", false),
+        ]);
+        assert!(graph
+            .query("Embedded/x/Section1/Use")
+            .unwrap()
+            .references
+            .is_empty());
+    }
+
+    #[test]
+    fn annotate_fills_query_definition_changes() {
+        let graph = sample();
+        let mut pool = StringPool::new();
+        let mut ops = vec![DiffOp::QueryDefinitionChanged {
+            name: pool.intern("Section1/Clean"),
+            change_kind: crate::diff::QueryChangeKind::Semantic,
+            old_hash: 1,
+            new_hash: 2,
+            semantic_detail: None,
+            downstream_queries: Vec::new(),
+            loaded_tables: Vec::new(),
+        }];
+        graph.annotate(&mut ops, &mut pool);
+        let DiffOp::QueryDefinitionChanged {
+            downstream_queries,
+            loaded_tables,
+            ..
+        } = &ops[0]
+        else {
+            panic!("expected QueryDefinitionChanged");
+        };
+        assert_eq!(
+            downstream_queries
+                .iter()
+                .map(|id| pool.resolve(*id))
+                .collect::<Vec<_>>(),
+            vec!["Section1/Report"]
+        );
+        assert_eq!(
+            loaded_tables
+                .iter()
+                .map(|id| pool.resolve(*id))
+                .collect::<Vec<_>>(),
+            vec!["Report"]
+        );
+    }
+}
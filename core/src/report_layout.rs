@@ -0,0 +1,962 @@
+//! Legacy PBIX report layout (`Report/Layout`): pages, visuals, filters and bookmarks.
+//!
+//! The part is one JSON document, usually UTF-16LE. Most of the interesting content sits in
+//! `config` and `filters` members that hold JSON encoded as strings, so those are decoded a second
+//! time. Objects whose hash is unchanged are skipped; the others are reported only when a modelled
+//! property differs, so re-saving a report does not surface as a change.
+
+use std::hash::{Hash, Hasher};
+
+use rustc_hash::FxHashMap;
+use serde_json::Value;
+
+use crate::diff::{DiffOp, PbipEntityKind, ReportChange, ReportField};
+#[cfg(feature = "excel-open-xml")]
+use crate::excel_open_xml::PackageError;
+use crate::hashing::XXH64_SEED;
+use crate::string_pool::StringPool;
+
+/// Literal values listed in a filter's text before it is cut short.
+const MAX_FILTER_VALUES: usize = 8;
+
+/// Report config members Power BI Desktop rewrites on save without an edit, such as the page
+/// that was open.
+const VOLATILE_CONFIG_KEYS: &[&str] = &["activeSectionIndex", "linguisticSchemaSyncVersion"];
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ReportLayout {
+    /// Report-level filters.
+    filters: Vec<ReportFilter>,
+    /// Base theme name.
+    theme: Option<String>,
+    /// Hash of the report config without bookmarks and volatile members.
+    hash: u64,
+    pages: Vec<ReportPage>,
+    bookmarks: Vec<ReportBookmark>,
+}
+
+#[derive(Debug, Clone)]
+struct ReportPage {
+    /// Internal section name, stable across renames.
+    name: String,
+    display_name: String,
+    ordinal: i64,
+    /// `width×height`.
+    size: String,
+    hidden: bool,
+    filters: Vec<ReportFilter>,
+    visuals: Vec<ReportVisual>,
+}
+
+#[derive(Debug, Clone)]
+struct ReportVisual {
+    /// Visual id from its config, stable across edits.
+    name: String,
+    visual_type: String,
+    title: Option<String>,
+    /// `x, y, width×height`, rounded to whole pixels.
+    position: String,
+    /// Bound fields as `(role, queryRef)`, e.g. `("Y", "Sum(Sales.Amount)")`.
+    fields: Vec<(String, String)>,
+    filters: Vec<ReportFilter>,
+    hash: u64,
+}
+
+#[derive(Debug, Clone)]
+struct ReportBookmark {
+    name: String,
+    display_name: String,
+    /// Section name of the page the bookmark opens.
+    page: Option<String>,
+    hash: u64,
+}
+
+#[derive(Debug, Clone)]
+struct ReportFilter {
+    /// Filter name, or its target when unnamed.
+    key: String,
+    /// Target and literal values, e.g. `Geo.Region: 'East', 'West'`.
+    text: String,
+    hash: u64,
+}
+
+#[cfg(feature = "excel-open-xml")]
+pub(crate) fn parse_report_layout(bytes: &[u8]) -> Result<ReportLayout, PackageError> {
+    let text = decode_layout(bytes)?;
+    let root: Value = serde_json::from_str(text.trim_start_matches('\u{FEFF}')).map_err(|e| {
+        PackageError::UnsupportedFormat {
+            message: format!("Report/Layout JSON parse error: {}", e),
+        }
+    })?;
+    Ok(layout_from_value(&root))
+}
+
+impl ReportLayout {
+    /// `(page, visual)` labels of the visuals with a field whose `queryRef` is `query_ref`,
+    /// ignoring case, in page order.
+    #[cfg(feature = "model-diff")]
+    pub(crate) fn visuals_bound_to(&self, query_ref: &str) -> Vec<(String, String)> {
+        let mut out = Vec::new();
+        for page in &self.pages {
+            for visual in &page.visuals {
+                let bound = visual
+                    .fields
+                    .iter()
+                    .any(|(_, field)| field.eq_ignore_ascii_case(query_ref));
+                if bound {
+                    out.push((page.display_name.clone(), visual_label(visual)));
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Power BI Desktop writes the layout as UTF-16LE, usually without a BOM; older tools used UTF-8.
+#[cfg(feature = "excel-open-xml")]
+fn decode_layout(bytes: &[u8]) -> Result<String, PackageError> {
+    let utf16 = match bytes {
+        [0xFF, 0xFE, rest @ ..] => Some(rest),
+        [b, 0, ..] if *b != 0 => Some(bytes),
+        _ => None,
+    };
+    let Some(body) = utf16 else {
+        return String::from_utf8(bytes.to_vec()).map_err(|e| PackageError::UnsupportedFormat {
+            message: format!("Report/Layout is not UTF-8 or UTF-16: {}", e),
+        });
+    };
+    let units = body
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]));
+    char::decode_utf16(units)
+        .collect::<Result<String, _>>()
+        .map_err(|e| PackageError::UnsupportedFormat {
+            message: format!("Report/Layout is not valid UTF-16: {}", e),
+        })
+}
+
+fn layout_from_value(root: &Value) -> ReportLayout {
+    let config = embedded(root, "config");
+    let mut bookmarks = Vec::new();
+    if let Some(Value::Array(items)) = config.get("bookmarks") {
+        collect_bookmarks(items, &mut bookmarks);
+    }
+    let mut config_rest = config.clone();
+    if let Value::Object(map) = &mut config_rest {
+        map.remove("bookmarks");
+        for key in VOLATILE_CONFIG_KEYS {
+            map.remove(*key);
+        }
+    }
+    let filters = embedded(root, "filters");
+
+    let mut pages: Vec<ReportPage> = root
+        .get("sections")
+        .and_then(Value::as_array)
+        .map(|sections| sections.iter().map(page_from_value).collect())
+        .unwrap_or_default();
+    pages.sort_by_key(|page| page.ordinal);
+
+    ReportLayout {
+        filters: filters_from_value(&filters),
+        theme: config
+            .pointer("/themeCollection/baseTheme/name")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        hash: hash_values(&[&config_rest, &filters]),
+        pages,
+        bookmarks,
+    }
+}
+
+fn page_from_value(section: &Value) -> ReportPage {
+    let config = embedded(section, "config");
+    let filters = embedded(section, "filters");
+    let name = str_member(section, "name").unwrap_or_default();
+    let size = format!(
+        "{}×{}",
+        number(section, "width").round(),
+        number(section, "height").round()
+    );
+    let visuals = section
+        .get("visualContainers")
+        .and_then(Value::as_array)
+        .map(|containers| containers.iter().map(visual_from_value).collect())
+        .unwrap_or_default();
+    ReportPage {
+        display_name: str_member(section, "displayName").unwrap_or_else(|| name.clone()),
+        name,
+        ordinal: section.get("ordinal").and_then(Value::as_i64).unwrap_or(0),
+        hidden: config.get("visibility").and_then(Value::as_i64) == Some(1),
+        filters: filters_from_value(&filters),
+        size,
+        visuals,
+    }
+}
+
+fn visual_from_value(container: &Value) -> ReportVisual {
+    let config = embedded(container, "config");
+    let filters = embedded(container, "filters");
+    let single = config.get("singleVisual");
+    let visual_type = single
+        .and_then(|v| str_member(v, "visualType"))
+        .or_else(|| config.get("singleVisualGroup").map(|_| "group".to_string()))
+        .unwrap_or_else(|| "visual".to_string());
+    let title = single
+        .and_then(|v| v.pointer("/vcObjects/title/0/properties/text/expr/Literal/Value"))
+        .and_then(Value::as_str)
+        .map(unquote_literal)
+        .or_else(|| {
+            config
+                .pointer("/singleVisualGroup/displayName")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        });
+
+    let mut fields = Vec::new();
+    if let Some(Value::Object(projections)) = single.and_then(|v| v.get("projections")) {
+        for (role, items) in projections {
+            for item in items.as_array().into_iter().flatten() {
+                if let Some(query_ref) = str_member(item, "queryRef") {
+                    fields.push((role.clone(), query_ref));
+                }
+            }
+        }
+    }
+
+    let position = format!(
+        "{}, {}, {}×{}",
+        number(container, "x").round(),
+        number(container, "y").round(),
+        number(container, "width").round(),
+        number(container, "height").round()
+    );
+    ReportVisual {
+        name: str_member(&config, "name").unwrap_or_default(),
+        visual_type,
+        title,
+        fields,
+        filters: filters_from_value(&filters),
+        hash: hash_values(&[&config, &filters, &Value::String(position.clone())]),
+        position,
+    }
+}
+
+/// Bookmark groups hold their bookmarks in `children`; the groups themselves are not reported.
+fn collect_bookmarks(items: &[Value], out: &mut Vec<ReportBookmark>) {
+    for item in items {
+        if let Some(Value::Array(children)) = item.get("children") {
+            collect_bookmarks(children, out);
+            continue;
+        }
+        let name = str_member(item, "name").unwrap_or_default();
+        out.push(ReportBookmark {
+            display_name: str_member(item, "displayName").unwrap_or_else(|| name.clone()),
+            name,
+            page: item
+                .pointer("/explorationState/activeSection")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            hash: hash_values(&[item]),
+        });
+    }
+}
+
+fn filters_from_value(filters: &Value) -> Vec<ReportFilter> {
+    let Some(items) = filters.as_array() else {
+        return Vec::new();
+    };
+    items
+        .iter()
+        .map(|filter| {
+            let target = filter
+                .get("expression")
+                .and_then(|e| filter_target(filter, e));
+            let mut values = Vec::new();
+            if let Some(condition) = filter.get("filter") {
+                collect_literals(condition, &mut values);
+            }
+            let label = target
+                .clone()
+                .or_else(|| str_member(filter, "name"))
+                .unwrap_or_else(|| "filter".to_string());
+            let text = if values.is_empty() {
+                label
+            } else {
+                let mut shown = values
+                    .iter()
+                    .take(MAX_FILTER_VALUES)
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                if values.len() > MAX_FILTER_VALUES {
+                    shown.push_str(", …");
+                }
+                format!("{}: {}", label, shown)
+            };
+            ReportFilter {
+                key: str_member(filter, "name")
+                    .or(target)
+                    .unwrap_or_else(|| text.clone()),
+                text,
+                hash: hash_values(&[filter]),
+            }
+        })
+        .collect()
+}
+
+/// `Entity.Property` for the column, measure or hierarchy level a filter applies to.
+fn filter_target(filter: &Value, expr: &Value) -> Option<String> {
+    for kind in ["Column", "Measure"] {
+        if let Some(inner) = expr.get(kind) {
+            let property = str_member(inner, "Property")?;
+            let source = inner.pointer("/Expression/SourceRef")?;
+            let entity = str_member(source, "Entity").or_else(|| {
+                let alias = str_member(source, "Source")?;
+                filter
+                    .pointer("/filter/From")
+                    .and_then(Value::as_array)?
+                    .iter()
+                    .find(|from| str_member(from, "Name").as_deref() == Some(alias.as_str()))
+                    .and_then(|from| str_member(from, "Entity"))
+            })?;
+            return Some(format!("{}.{}", entity, property));
+        }
+    }
+    match expr {
+        Value::Object(map) => map.values().find_map(|v| filter_target(filter, v)),
+        _ => None,
+    }
+}
+
+fn collect_literals(value: &Value, out: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            if let Some(literal) = map
+                .get("Literal")
+                .and_then(|l| l.get("Value"))
+                .and_then(Value::as_str)
+            {
+                out.push(literal.to_string());
+                return;
+            }
+            for v in map.values() {
+                collect_literals(v, out);
+            }
+        }
+        Value::Array(items) => {
+            for v in items {
+                collect_literals(v, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// A member that may hold JSON directly or as an encoded string. Undecodable strings are kept
+/// as plain strings so they still count towards hashes.
+fn embedded(value: &Value, key: &str) -> Value {
+    match value.get(key) {
+        Some(Value::String(s)) => {
+            serde_json::from_str(s).unwrap_or_else(|_| Value::String(s.clone()))
+        }
+        Some(other) => other.clone(),
+        None => Value::Null,
+    }
+}
+
+fn str_member(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+fn number(value: &Value, key: &str) -> f64 {
+    value.get(key).and_then(Value::as_f64).unwrap_or(0.0)
+}
+
+/// `'Sales by Region'` to `Sales by Region`, undoing doubled quotes.
+fn unquote_literal(raw: &str) -> String {
+    raw.strip_prefix('\'')
+        .and_then(|s| s.strip_suffix('\''))
+        .map(|s| s.replace("''", "'"))
+        .unwrap_or_else(|| raw.to_string())
+}
+
+/// Hash of JSON values with object keys visited in sorted order.
+fn hash_values(values: &[&Value]) -> u64 {
+    fn walk(value: &Value, h: &mut impl Hasher) {
+        match value {
+            Value::Null => 0u8.hash(h),
+            Value::Bool(b) => (1u8, b).hash(h),
+            Value::Number(n) => (2u8, n.to_string()).hash(h),
+            Value::String(s) => (3u8, s).hash(h),
+            Value::Array(items) => {
+                (4u8, items.len()).hash(h);
+                for item in items {
+                    walk(item, h);
+                }
+            }
+            Value::Object(map) => {
+                let mut keys: Vec<&String> = map.keys().collect();
+                keys.sort();
+                (5u8, keys.len()).hash(h);
+                for key in keys {
+                    key.hash(h);
+                    walk(&map[key], h);
+                }
+            }
+        }
+    }
+    let mut h = xxhash_rust::xxh64::Xxh64::new(XXH64_SEED);
+    for value in values {
+        walk(value, &mut h);
+    }
+    h.finish()
+}
+
+/// Pairs pages by section name, visuals by page and visual id, and bookmarks by name. Visuals of
+/// an added or removed page are covered by the page op.
+pub(crate) fn diff_report_layouts(
+    old: &ReportLayout,
+    new: &ReportLayout,
+    pool: &mut StringPool,
+) -> Vec<DiffOp> {
+    let mut ops = Vec::new();
+
+    if old.hash != new.hash {
+        let mut changes = Vec::new();
+        push_change(
+            &mut changes,
+            pool,
+            ReportField::Theme,
+            old.theme.as_deref(),
+            new.theme.as_deref(),
+        );
+        diff_filters(&old.filters, &new.filters, pool, &mut changes);
+        if !changes.is_empty() {
+            ops.push(DiffOp::ReportObjectChanged {
+                object: PbipEntityKind::Report,
+                page: None,
+                name: pool.intern("Report"),
+                changes,
+            });
+        }
+    }
+
+    let old_pages: FxHashMap<&str, &ReportPage> =
+        old.pages.iter().map(|p| (p.name.as_str(), p)).collect();
+    let new_names: FxHashMap<&str, ()> = new.pages.iter().map(|p| (p.name.as_str(), ())).collect();
+    let common_rank = |pages: &[ReportPage], other: &FxHashMap<&str, ()>, name: &str| {
+        pages
+            .iter()
+            .filter(|p| other.contains_key(p.name.as_str()))
+            .position(|p| p.name == name)
+    };
+    let old_names: FxHashMap<&str, ()> = old.pages.iter().map(|p| (p.name.as_str(), ())).collect();
+
+    for new_page in &new.pages {
+        let page_name = pool.intern(&new_page.display_name);
+        let Some(old_page) = old_pages.get(new_page.name.as_str()) else {
+            ops.push(DiffOp::ReportObjectAdded {
+                object: PbipEntityKind::Page,
+                page: None,
+                name: page_name,
+            });
+            continue;
+        };
+
+        let mut changes = Vec::new();
+        push_change(
+            &mut changes,
+            pool,
+            ReportField::DisplayName,
+            Some(&old_page.display_name),
+            Some(&new_page.display_name),
+        );
+        // Compare tab order among pages on both sides, so adding a page does not move the rest.
+        if common_rank(&old.pages, &new_names, &old_page.name)
+            != common_rank(&new.pages, &old_names, &new_page.name)
+        {
+            push_change(
+                &mut changes,
+                pool,
+                ReportField::Order,
+                Some(&(old_page.ordinal + 1).to_string()),
+                Some(&(new_page.ordinal + 1).to_string()),
+            );
+        }
+        push_change(
+            &mut changes,
+            pool,
+            ReportField::Size,
+            Some(&old_page.size),
+            Some(&new_page.size),
+        );
+        push_change(
+            &mut changes,
+            pool,
+            ReportField::Hidden,
+            Some(&old_page.hidden.to_string()),
+            Some(&new_page.hidden.to_string()),
+        );
+        diff_filters(&old_page.filters, &new_page.filters, pool, &mut changes);
+        if !changes.is_empty() {
+            ops.push(DiffOp::ReportObjectChanged {
+                object: PbipEntityKind::Page,
+                page: None,
+                name: page_name,
+                changes,
+            });
+        }
+        diff_visuals(old_page, new_page, page_name, pool, &mut ops);
+    }
+
+    for old_page in &old.pages {
+        if new_names.contains_key(old_page.name.as_str()) {
+            continue;
+        }
+        let page_name = pool.intern(&old_page.display_name);
+        ops.push(DiffOp::ReportObjectRemoved {
+            object: PbipEntityKind::Page,
+            page: None,
+            name: page_name,
+        });
+    }
+
+    diff_bookmarks(old, new, pool, &mut ops);
+    ops
+}
+
+fn diff_visuals(
+    old_page: &ReportPage,
+    new_page: &ReportPage,
+    page_name: crate::string_pool::StringId,
+    pool: &mut StringPool,
+    ops: &mut Vec<DiffOp>,
+) {
+    let old_visuals: FxHashMap<&str, &ReportVisual> = old_page
+        .visuals
+        .iter()
+        .map(|v| (v.name.as_str(), v))
+        .collect();
+    for new_visual in &new_page.visuals {
+        let name = pool.intern(&visual_label(new_visual));
+        let Some(old_visual) = old_visuals.get(new_visual.name.as_str()) else {
+            ops.push(DiffOp::ReportObjectAdded {
+                object: PbipEntityKind::Visual,
+                page: Some(page_name),
+                name,
+            });
+            continue;
+        };
+        if old_visual.hash == new_visual.hash {
+            continue;
+        }
+        let mut changes = Vec::new();
+        push_change(
+            &mut changes,
+            pool,
+            ReportField::VisualType,
+            Some(&old_visual.visual_type),
+            Some(&new_visual.visual_type),
+        );
+        push_change(
+            &mut changes,
+            pool,
+            ReportField::Title,
+            old_visual.title.as_deref(),
+            new_visual.title.as_deref(),
+        );
+        diff_fields(&old_visual.fields, &new_visual.fields, pool, &mut changes);
+        diff_filters(&old_visual.filters, &new_visual.filters, pool, &mut changes);
+        push_change(
+            &mut changes,
+            pool,
+            ReportField::Position,
+            Some(&old_visual.position),
+            Some(&new_visual.position),
+        );
+        if !changes.is_empty() {
+            ops.push(DiffOp::ReportObjectChanged {
+                object: PbipEntityKind::Visual,
+                page: Some(page_name),
+                name,
+                changes,
+            });
+        }
+    }
+
+    for old_visual in &old_page.visuals {
+        if new_page.visuals.iter().any(|v| v.name == old_visual.name) {
+            continue;
+        }
+        ops.push(DiffOp::ReportObjectRemoved {
+            object: PbipEntityKind::Visual,
+            page: Some(page_name),
+            name: pool.intern(&visual_label(old_visual)),
+        });
+    }
+}
+
+/// Per role, a removed field and an added field are reported as one replacement, so swapping a
+/// measure reads as `Y: Sum(Sales.Amount)` to `Y: Sum(Sales.Margin)`.
+fn diff_fields(
+    old: &[(String, String)],
+    new: &[(String, String)],
+    pool: &mut StringPool,
+    changes: &mut Vec<ReportChange>,
+) {
+    let mut roles: Vec<&str> = Vec::new();
+    for (role, _) in old.iter().chain(new) {
+        if !roles.contains(&role.as_str()) {
+            roles.push(role);
+        }
+    }
+    for role in roles {
+        let in_role = |fields: &'_ [(String, String)], other: &[(String, String)]| {
+            fields
+                .iter()
+                .filter(|(r, f)| r == role && !other.iter().any(|(or, of)| or == r && of == f))
+                .map(|(_, f)| format!("{}: {}", role, f))
+                .collect::<Vec<_>>()
+        };
+        let removed = in_role(old, new);
+        let added = in_role(new, old);
+        for i in 0..removed.len().max(added.len()) {
+            let from = removed.get(i).map(String::as_str);
+            let to = added.get(i).map(String::as_str);
+            push_change(changes, pool, ReportField::Field, from, to);
+        }
+    }
+}
+
+fn diff_filters(
+    old: &[ReportFilter],
+    new: &[ReportFilter],
+    pool: &mut StringPool,
+    changes: &mut Vec<ReportChange>,
+) {
+    for new_filter in new {
+        match old.iter().find(|f| f.key == new_filter.key) {
+            None => push_change(
+                changes,
+                pool,
+                ReportField::Filter,
+                None,
+                Some(&new_filter.text),
+            ),
+            Some(old_filter) if old_filter.hash != new_filter.hash => {
+                let (from, to) = (pool.intern(&old_filter.text), pool.intern(&new_filter.text));
+                changes.push(ReportChange {
+                    field: ReportField::Filter,
+                    from: Some(from),
+                    to: Some(to),
+                });
+            }
+            Some(_) => {}
+        }
+    }
+    for old_filter in old {
+        if !new.iter().any(|f| f.key == old_filter.key) {
+            push_change(
+                changes,
+                pool,
+                ReportField::Filter,
+                Some(&old_filter.text),
+                None,
+            );
+        }
+    }
+}
+
+fn diff_bookmarks(
+    old: &ReportLayout,
+    new: &ReportLayout,
+    pool: &mut StringPool,
+    ops: &mut Vec<DiffOp>,
+) {
+    let page_label = |layout: &ReportLayout, section: Option<&str>| {
+        let section = section?;
+        Some(
+            layout
+                .pages
+                .iter()
+                .find(|p| p.name == section)
+                .map_or(section, |p| p.display_name.as_str())
+                .to_string(),
+        )
+    };
+    for new_bookmark in &new.bookmarks {
+        let name = pool.intern(&new_bookmark.display_name);
+        let Some(old_bookmark) = old.bookmarks.iter().find(|b| b.name == new_bookmark.name) else {
+            ops.push(DiffOp::ReportObjectAdded {
+                object: PbipEntityKind::Bookmark,
+                page: None,
+                name,
+            });
+            continue;
+        };
+        if old_bookmark.hash == new_bookmark.hash {
+            continue;
+        }
+        let mut changes = Vec::new();
+        push_change(
+            &mut changes,
+            pool,
+            ReportField::DisplayName,
+            Some(&old_bookmark.display_name),
+            Some(&new_bookmark.display_name),
+        );
+        push_change(
+            &mut changes,
+            pool,
+            ReportField::Page,
+            page_label(old, old_bookmark.page.as_deref()).as_deref(),
+            page_label(new, new_bookmark.page.as_deref()).as_deref(),
+        );
+        if !changes.is_empty() {
+            ops.push(DiffOp::ReportObjectChanged {
+                object: PbipEntityKind::Bookmark,
+                page: None,
+                name,
+                changes,
+            });
+        }
+    }
+    for old_bookmark in &old.bookmarks {
+        if !new.bookmarks.iter().any(|b| b.name == old_bookmark.name) {
+            ops.push(DiffOp::ReportObjectRemoved {
+                object: PbipEntityKind::Bookmark,
+                page: None,
+                name: pool.intern(&old_bookmark.display_name),
+            });
+        }
+    }
+}
+
+/// The visual's title, or its type and id when it has none.
+fn visual_label(visual: &ReportVisual) -> String {
+    match &visual.title {
+        Some(title) => title.clone(),
+        None => format!("{} {}", visual.visual_type, visual.name),
+    }
+}
+
+fn push_change(
+    changes: &mut Vec<ReportChange>,
+    pool: &mut StringPool,
+    field: ReportField,
+    from: Option<&str>,
+    to: Option<&str>,
+) {
+    if from != to {
+        changes.push(ReportChange {
+            field,
+            from: from.map(|s| pool.intern(s)),
+            to: to.map(|s| pool.intern(s)),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn visual(id: &str, title: &str, measure: &str) -> Value {
+        let config = json!({
+            "name": id,
+            "singleVisual": {
+                "visualType": "clusteredBarChart",
+                "projections": {
+                    "Category": [{"queryRef": "Geo.Region"}],
+                    "Y": [{"queryRef": measure}],
+                },
+                "vcObjects": {
+                    "title": [{"properties": {"text": {"expr": {"Literal": {"Value": title}}}}}],
+                },
+            },
+        });
+        json!({
+            "x": 10.0, "y": 20.0, "width": 300.4, "height": 200.0,
+            "config": config.to_string(),
+            "filters": "[]",
+        })
+    }
+
+    fn region_filter(values: &[&str]) -> Value {
+        let values: Vec<Value> = values
+            .iter()
+            .map(|v| json!([{"Literal": {"Value": format!("'{v}'")}}]))
+            .collect();
+        json!({
+            "name": "Filter1",
+            "expression": {"Column": {
+                "Expression": {"SourceRef": {"Entity": "Geo"}},
+                "Property": "Region",
+            }},
+            "filter": {"Where": [{"Condition": {"In": {"Values": values}}}]},
+        })
+    }
+
+    fn layout(measure: &str, regions: &[&str], bookmark_page: &str) -> ReportLayout {
+        layout_from_value(&layout_value(measure, regions, bookmark_page))
+    }
+
+    fn layout_value(measure: &str, regions: &[&str], bookmark_page: &str) -> Value {
+        let config = json!({
+            "themeCollection": {"baseTheme": {"name": "CY24SU06"}},
+            "bookmarks": [{
+                "displayName": "Group",
+                "name": "g1",
+                "children": [{
+                    "displayName": "East only",
+                    "name": "b1",
+                    "explorationState": {"activeSection": bookmark_page},
+                }],
+            }],
+        });
+        json!({
+            "config": config.to_string(),
+            "filters": "[]",
+            "sections": [
+                {
+                    "name": "s1",
+                    "displayName": "Overview",
+                    "ordinal": 0,
+                    "width": 1280, "height": 720,
+                    "config": "{}",
+                    "filters": json!([region_filter(regions)]).to_string(),
+                    "visualContainers": [visual("v1", "'Sales by Region'", measure)],
+                },
+                {
+                    "name": "s2",
+                    "displayName": "Details",
+                    "ordinal": 1,
+                    "width": 1280, "height": 720,
+                    "config": "{}",
+                    "visualContainers": [],
+                },
+            ],
+        })
+    }
+
+    fn describe(ops: &[DiffOp], pool: &StringPool) -> Vec<String> {
+        let opt = |id: Option<crate::string_pool::StringId>| {
+            id.map(|id| pool.resolve(id).to_string())
+                .unwrap_or_default()
+        };
+        ops.iter()
+            .map(|op| match op {
+                DiffOp::ReportObjectChanged {
+                    object,
+                    page,
+                    name,
+                    changes,
+                } => {
+                    let changes: Vec<String> = changes
+                        .iter()
+                        .map(|c| format!("{:?} {} -> {}", c.field, opt(c.from), opt(c.to)))
+                        .collect();
+                    format!(
+                        "{:?} {} / {}: {}",
+                        object,
+                        opt(*page),
+                        pool.resolve(*name),
+                        changes.join("; ")
+                    )
+                }
+                other => format!("{:?}", other),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parses_pages_visuals_filters_and_nested_bookmarks() {
+        let layout = layout("Sum(Sales.Amount)", &["East", "West"], "s1");
+        assert_eq!(layout.theme.as_deref(), Some("CY24SU06"));
+        assert_eq!(layout.pages.len(), 2);
+        let page = &layout.pages[0];
+        assert_eq!(page.display_name, "Overview");
+        assert_eq!(page.size, "1280×720");
+        assert_eq!(page.filters[0].text, "Geo.Region: 'East', 'West'");
+        let visual = &page.visuals[0];
+        assert_eq!(visual.title.as_deref(), Some("Sales by Region"));
+        assert_eq!(visual.position, "10, 20, 300×200");
+        assert_eq!(
+            visual.fields,
+            vec![
+                ("Category".to_string(), "Geo.Region".to_string()),
+                ("Y".to_string(), "Sum(Sales.Amount)".to_string()),
+            ]
+        );
+        assert_eq!(layout.bookmarks.len(), 1);
+        assert_eq!(layout.bookmarks[0].display_name, "East only");
+    }
+
+    #[test]
+    fn changed_measure_filter_and_bookmark_target_are_reported() {
+        let old = layout("Sum(Sales.Amount)", &["East", "West"], "s1");
+        let new = layout("Sum(Sales.Margin)", &["East"], "s2");
+        let mut pool = StringPool::new();
+        let ops = diff_report_layouts(&old, &new, &mut pool);
+        assert_eq!(
+            describe(&ops, &pool),
+            vec![
+                "Page  / Overview: Filter Geo.Region: 'East', 'West' -> Geo.Region: 'East'",
+                "Visual Overview / Sales by Region: \
+                 Field Y: Sum(Sales.Amount) -> Y: Sum(Sales.Margin)",
+                "Bookmark  / East only: Page Overview -> Details",
+            ]
+        );
+    }
+
+    #[test]
+    fn identical_layouts_produce_no_ops() {
+        let old = layout("Sum(Sales.Amount)", &["East"], "s1");
+        let new = layout("Sum(Sales.Amount)", &["East"], "s1");
+        let mut pool = StringPool::new();
+        assert!(diff_report_layouts(&old, &new, &mut pool).is_empty());
+    }
+
+    #[test]
+    fn resaves_and_unmodelled_edits_produce_no_ops() {
+        let old = layout_value("Sum(Sales.Amount)", &["East"], "s1");
+        let mut new = old.clone();
+        let mut config: Value = serde_json::from_str(new["config"].as_str().unwrap()).unwrap();
+        config["activeSectionIndex"] = json!(1);
+        new["config"] = Value::String(config.to_string());
+        let old = layout_from_value(&old);
+        let resaved = layout_from_value(&new);
+        assert_eq!(old.hash, resaved.hash);
+
+        let container = &mut new["sections"][0]["visualContainers"][0];
+        let mut visual: Value =
+            serde_json::from_str(container["config"].as_str().unwrap()).unwrap();
+        visual["singleVisual"]["objects"] = json!({"dataPoint": [{"properties": {}}]});
+        container["config"] = Value::String(visual.to_string());
+        let mut pool = StringPool::new();
+        assert!(diff_report_layouts(&old, &layout_from_value(&new), &mut pool).is_empty());
+    }
+
+    #[cfg(feature = "model-diff")]
+    #[test]
+    fn visuals_bound_to_matches_query_ref_ignoring_case() {
+        let layout = layout("Sales.Total Sales", &["East"], "s1");
+        assert_eq!(
+            layout.visuals_bound_to("sales.total sales"),
+            vec![("Overview".to_string(), "Sales by Region".to_string())]
+        );
+        assert!(layout.visuals_bound_to("Sales.Margin").is_empty());
+    }
+
+    #[cfg(feature = "excel-open-xml")]
+    #[test]
+    fn decodes_utf16_layout() {
+        let text = r#"{"sections":[{"name":"s1","displayName":"Übersicht"}]}"#;
+        let bytes: Vec<u8> = text.encode_utf16().flat_map(u16::to_le_bytes).collect();
+        let layout = parse_report_layout(&bytes).expect("utf-16 layout should parse");
+        assert_eq!(layout.pages[0].display_name, "Übersicht");
+        let layout = parse_report_layout(b"{}").expect("utf-8 layout should parse");
+        assert!(layout.pages.is_empty());
+    }
+}
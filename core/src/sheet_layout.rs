@@ -0,0 +1,246 @@
+//! Worksheet layout parsing: merged cells, row and column properties, frozen panes, the tab
+//! colour and sheet protection. Sheet visibility comes from `workbook.xml` and is filled in by
+//! the caller.
+
+use crate::grid_parser::{local_tag_name, xml_err, GridParseError};
+use crate::sheet_rules::parse_sqref;
+use crate::string_pool::StringPool;
+use crate::styles::{attr, collect_attrs, color_descriptor};
+use crate::workbook::{AxisLayout, FreezePane, SheetLayout, SheetProtection};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+/// Columns are 1-based in `<col min max>`; ranges past this are clamped.
+const MAX_COLS: u32 = 16_384;
+
+/// Conservative detector so sheets with default layout skip the extra scan.
+pub(crate) fn sheet_has_layout(xml: &[u8]) -> bool {
+    const NEEDLES: [&[u8]; 8] = [
+        b"mergeCell",
+        b"hidden=",
+        b"outlineLevel=",
+        b"customHeight=",
+        b"customWidth=",
+        b"pane",
+        b"tabColor",
+        b"sheetProtection",
+    ];
+    NEEDLES
+        .iter()
+        .any(|needle| xml.windows(needle.len()).any(|w| w == *needle))
+}
+
+fn parse_bool(value: Option<&str>) -> bool {
+    matches!(value, Some("1") | Some("true"))
+}
+
+fn parse_number(value: Option<&str>) -> Option<f64> {
+    value
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .filter(|v| v.is_finite() && *v >= 0.0)
+}
+
+/// `<sheetProtection>` flags with their defaults. A set flag means the action is blocked.
+const PROTECTION_FLAGS: [(&str, bool); 15] = [
+    ("objects", false),
+    ("scenarios", false),
+    ("formatCells", true),
+    ("formatColumns", true),
+    ("formatRows", true),
+    ("insertColumns", true),
+    ("insertRows", true),
+    ("insertHyperlinks", true),
+    ("deleteColumns", true),
+    ("deleteRows", true),
+    ("selectLockedCells", false),
+    ("sort", true),
+    ("autoFilter", true),
+    ("pivotTables", true),
+    ("selectUnlockedCells", false),
+];
+
+/// `None` unless the `sheet` flag turns protection on.
+fn sheet_protection(attrs: &[(String, String)], pool: &mut StringPool) -> Option<SheetProtection> {
+    if !parse_bool(attr(attrs, "sheet")) {
+        return None;
+    }
+    let allowed = PROTECTION_FLAGS
+        .iter()
+        .filter(|(name, default)| {
+            attr(attrs, name).map_or(!default, |v| !parse_bool(Some(v.trim())))
+        })
+        .map(|(name, _)| pool.intern(name))
+        .collect();
+    Some(SheetProtection {
+        has_password: attr(attrs, "password").is_some() || attr(attrs, "hashValue").is_some(),
+        allowed,
+    })
+}
+
+/// Row or column properties; `size` is only kept when the custom flag is set, scaled by `unit`.
+fn axis_layout(
+    attrs: &[(String, String)],
+    size_attr: &str,
+    custom_attr: &str,
+    unit: f64,
+) -> AxisLayout {
+    AxisLayout {
+        hidden: parse_bool(attr(attrs, "hidden")),
+        outline_level: attr(attrs, "outlineLevel")
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(0),
+        size: if parse_bool(attr(attrs, custom_attr)) {
+            parse_number(attr(attrs, size_attr)).map(|v| (v * unit).round() as u32)
+        } else {
+            None
+        },
+    }
+}
+
+/// Parse the layout of one worksheet. `visibility` is left at its default.
+pub(crate) fn parse_sheet_layout(
+    xml: &[u8],
+    pool: &mut StringPool,
+) -> Result<SheetLayout, GridParseError> {
+    let mut reader = Reader::from_reader(xml);
+    reader.config_mut().trim_text(true);
+    let mut buf = Vec::new();
+
+    let mut layout = SheetLayout::default();
+    let mut in_sheet_pr = false;
+    let mut sheet_views = 0usize;
+    let mut next_row = 0u32;
+
+    loop {
+        let event = reader.read_event_into(&mut buf);
+        match event {
+            Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
+                let is_start = matches!(event, Ok(Event::Start(_)));
+                match local_tag_name(e.name().as_ref()) {
+                    b"sheetPr" => in_sheet_pr = is_start,
+                    b"tabColor" if in_sheet_pr => {
+                        let attrs = collect_attrs(&reader, xml, e)?;
+                        layout.tab_color = Some(pool.intern(&color_descriptor(&attrs)));
+                    }
+                    b"sheetView" => sheet_views += 1,
+                    b"pane" if sheet_views == 1 => {
+                        let attrs = collect_attrs(&reader, xml, e)?;
+                        if matches!(attr(&attrs, "state"), Some("frozen") | Some("frozenSplit")) {
+                            let split = |key| parse_number(attr(&attrs, key)).unwrap_or(0.0) as u32;
+                            let pane = FreezePane {
+                                rows: split("ySplit"),
+                                cols: split("xSplit"),
+                            };
+                            if pane.rows > 0 || pane.cols > 0 {
+                                layout.freeze = Some(pane);
+                            }
+                        }
+                    }
+                    b"col" => {
+                        let attrs = collect_attrs(&reader, xml, e)?;
+                        let bound =
+                            |key| attr(&attrs, key).and_then(|v| v.trim().parse::<u32>().ok());
+                        let col = axis_layout(&attrs, "width", "customWidth", 256.0);
+                        if let (Some(min), Some(max)) = (bound("min"), bound("max"))
+                            && col != AxisLayout::default()
+                        {
+                            for idx in min.max(1)..=max.min(MAX_COLS) {
+                                layout.cols.insert(idx - 1, col);
+                            }
+                        }
+                    }
+                    b"row" => {
+                        let attrs = collect_attrs(&reader, xml, e)?;
+                        let idx = attr(&attrs, "r")
+                            .and_then(|v| v.trim().parse::<u32>().ok())
+                            .map(|r| r.saturating_sub(1))
+                            .unwrap_or(next_row);
+                        next_row = idx.saturating_add(1);
+                        let row = axis_layout(&attrs, "ht", "customHeight", 20.0);
+                        if row != AxisLayout::default() {
+                            layout.rows.insert(idx, row);
+                        }
+                    }
+                    b"sheetProtection" => {
+                        let attrs = collect_attrs(&reader, xml, e)?;
+                        layout.protection = sheet_protection(&attrs, pool);
+                    }
+                    b"mergeCell" => {
+                        let attrs = collect_attrs(&reader, xml, e)?;
+                        if let Some(raw) = attr(&attrs, "ref") {
+                            layout.merges.extend(parse_sqref(raw)?);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Ok(Event::End(ref e)) if local_tag_name(e.name().as_ref()) == b"sheetPr" => {
+                in_sheet_pr = false;
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(xml_err(&reader, xml, e)),
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(layout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workbook::CellRange;
+
+    #[test]
+    fn parses_merges_axes_panes_and_tab_color() {
+        let xml = br#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetPr><tabColor rgb="ffff0000"/></sheetPr><sheetViews><sheetView workbookViewId="0"><pane xSplit="1" ySplit="2" topLeftCell="B3" activePane="bottomRight" state="frozen"/></sheetView><sheetView workbookViewId="1"><pane ySplit="9" state="frozen"/></sheetView></sheetViews><cols><col min="2" max="3" width="20.5" customWidth="1"/><col min="5" max="5" width="9" hidden="1"/><col min="6" max="16384" width="9.140625"/></cols><sheetData><row r="1"><c r="A1"><v>1</v></c></row><row r="4" hidden="1" outlineLevel="1"/><row ht="30" customHeight="1"/></sheetData><mergeCells count="1"><mergeCell ref="A1:C1"/></mergeCells></worksheet>"#;
+        let mut pool = StringPool::new();
+        let layout = parse_sheet_layout(xml, &mut pool).expect("parse layout");
+
+        assert_eq!(
+            layout.tab_color.map(|id| pool.resolve(id)),
+            Some("rgb:FFFF0000")
+        );
+        assert_eq!(layout.freeze, Some(FreezePane { rows: 2, cols: 1 }));
+        let merges: Vec<String> = layout.merges.iter().map(CellRange::to_a1).collect();
+        assert_eq!(merges, vec!["A1:C1"]);
+
+        assert_eq!(layout.cols.len(), 3);
+        assert_eq!(layout.cols[&1].size, Some(5248));
+        assert_eq!(layout.cols[&2].size, Some(5248));
+        assert!(layout.cols[&4].hidden && layout.cols[&4].size.is_none());
+
+        assert_eq!(layout.rows.len(), 2);
+        assert_eq!(
+            layout.rows[&3],
+            AxisLayout {
+                hidden: true,
+                outline_level: 1,
+                size: None,
+            }
+        );
+        assert_eq!(layout.rows[&4].size, Some(600));
+        assert_eq!(layout.protection, None);
+    }
+
+    #[test]
+    fn sheet_protection_lists_allowed_actions() {
+        let xml = br#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData/><sheetProtection algorithmName="SHA-512" hashValue="abc=" saltValue="def=" spinCount="100000" sheet="1" objects="1" scenarios="1" formatColumns="0" selectLockedCells="1"/></worksheet>"#;
+        let mut pool = StringPool::new();
+        let layout = parse_sheet_layout(xml, &mut pool).expect("parse layout");
+        let protection = layout.protection.expect("sheet is protected");
+
+        assert!(protection.has_password);
+        let allowed: Vec<&str> = protection
+            .allowed
+            .iter()
+            .map(|id| pool.resolve(*id))
+            .collect();
+        assert_eq!(allowed, vec!["formatColumns", "selectUnlockedCells"]);
+
+        let off = br#"<worksheet><sheetProtection sheet="0"/></worksheet>"#;
+        let layout = parse_sheet_layout(off, &mut pool).expect("parse layout");
+        assert_eq!(layout.protection, None);
+    }
+}
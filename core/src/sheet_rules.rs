@@ -0,0 +1,281 @@
+//! Data validation and conditional formatting rule parsing.
+//!
+//! Both live in the worksheet XML after `<sheetData>`. Rules that reference other sheets are
+//! written by Excel into the `x14` extension list, with the range in an `<xm:sqref>` child and
+//! formulas wrapped in `<xm:f>`; both spellings are read into the same IR.
+
+use crate::comments::push_text;
+use crate::grid_parser::{local_tag_name, xml_err, GridParseError};
+use crate::string_pool::StringPool;
+use crate::styles::{attr, collect_attrs};
+use crate::workbook::{CellRange, ConditionalFormatRule, DataValidationRule};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+/// Conservative detector so sheets without rules skip the extra scan.
+pub(crate) fn sheet_has_rules(xml: &[u8]) -> bool {
+    const NEEDLES: [&[u8]; 2] = [b"dataValidation", b"conditionalFormatting"];
+    NEEDLES
+        .iter()
+        .any(|needle| xml.windows(needle.len()).any(|w| w == *needle))
+}
+
+pub(crate) fn parse_sqref(raw: &str) -> Result<Vec<CellRange>, GridParseError> {
+    raw.split_whitespace()
+        .map(|part| {
+            part.parse::<CellRange>()
+                .map_err(|_| GridParseError::InvalidAddress(part.to_string()))
+        })
+        .collect()
+}
+
+fn parse_bool(value: Option<&str>) -> bool {
+    matches!(value, Some("1") | Some("true"))
+}
+
+#[derive(Default)]
+struct PendingValidation {
+    ranges: Vec<CellRange>,
+    kind: String,
+    operator: Option<String>,
+    formula1: Option<String>,
+    formula2: Option<String>,
+    allow_blank: bool,
+}
+
+#[derive(Default)]
+struct PendingFormat {
+    kind: String,
+    operator: Option<String>,
+    formulas: Vec<String>,
+    priority: Option<u32>,
+    stop_if_true: bool,
+}
+
+/// Which text-bearing element the reader is inside.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TextTarget {
+    None,
+    Formula1,
+    Formula2,
+    CfFormula,
+    Sqref,
+}
+
+/// Parse the data validations and conditional formats of one worksheet, in document order.
+pub(crate) fn parse_sheet_rules(
+    xml: &[u8],
+    pool: &mut StringPool,
+) -> Result<(Vec<DataValidationRule>, Vec<ConditionalFormatRule>), GridParseError> {
+    let mut reader = Reader::from_reader(xml);
+    reader.config_mut().trim_text(false);
+    let mut buf = Vec::new();
+
+    let mut validations = Vec::new();
+    let mut formats = Vec::new();
+
+    let mut validation: Option<PendingValidation> = None;
+    let mut block_ranges: Option<Vec<CellRange>> = None;
+    let mut block_rules: Vec<PendingFormat> = Vec::new();
+    let mut rule: Option<PendingFormat> = None;
+    let mut target = TextTarget::None;
+    let mut text = String::new();
+
+    loop {
+        let event = reader.read_event_into(&mut buf);
+        match event {
+            Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
+                let is_empty = matches!(event, Ok(Event::Empty(_)));
+                match local_tag_name(e.name().as_ref()) {
+                    b"dataValidation" => {
+                        let attrs = collect_attrs(&reader, xml, e)?;
+                        let pending = PendingValidation {
+                            ranges: match attr(&attrs, "sqref") {
+                                Some(raw) => parse_sqref(raw)?,
+                                None => Vec::new(),
+                            },
+                            kind: attr(&attrs, "type").unwrap_or("none").to_string(),
+                            operator: attr(&attrs, "operator").map(str::to_string),
+                            allow_blank: parse_bool(attr(&attrs, "allowBlank")),
+                            ..PendingValidation::default()
+                        };
+                        if is_empty {
+                            validations.push(finish_validation(pending, pool));
+                        } else {
+                            validation = Some(pending);
+                        }
+                    }
+                    b"conditionalFormatting" if !is_empty => {
+                        let attrs = collect_attrs(&reader, xml, e)?;
+                        block_ranges = Some(match attr(&attrs, "sqref") {
+                            Some(raw) => parse_sqref(raw)?,
+                            None => Vec::new(),
+                        });
+                        block_rules.clear();
+                    }
+                    b"cfRule" if block_ranges.is_some() => {
+                        let attrs = collect_attrs(&reader, xml, e)?;
+                        let pending = PendingFormat {
+                            kind: attr(&attrs, "type").unwrap_or_default().to_string(),
+                            operator: attr(&attrs, "operator").map(str::to_string),
+                            priority: attr(&attrs, "priority").and_then(|v| v.trim().parse().ok()),
+                            stop_if_true: parse_bool(attr(&attrs, "stopIfTrue")),
+                            formulas: Vec::new(),
+                        };
+                        if is_empty {
+                            block_rules.push(pending);
+                        } else {
+                            rule = Some(pending);
+                        }
+                    }
+                    b"formula1" if validation.is_some() => {
+                        target = TextTarget::Formula1;
+                        text.clear();
+                    }
+                    b"formula2" if validation.is_some() => {
+                        target = TextTarget::Formula2;
+                        text.clear();
+                    }
+                    // `<xm:f>` inside an x14 `formula1`/`formula2` keeps the outer target.
+                    b"f" if target != TextTarget::None => {}
+                    b"formula" | b"f" if rule.is_some() => {
+                        target = TextTarget::CfFormula;
+                        text.clear();
+                    }
+                    b"sqref" if validation.is_some() || block_ranges.is_some() => {
+                        target = TextTarget::Sqref;
+                        text.clear();
+                    }
+                    _ => {}
+                }
+            }
+            Ok(Event::Text(e)) if target != TextTarget::None => {
+                push_text(&reader, xml, e.as_ref(), &mut text)?;
+            }
+            Ok(Event::CData(e)) if target != TextTarget::None => {
+                text.push_str(&String::from_utf8_lossy(e.as_ref()));
+            }
+            Ok(Event::End(e)) => match local_tag_name(e.name().as_ref()) {
+                b"formula1" if target == TextTarget::Formula1 => {
+                    if let Some(pending) = validation.as_mut() {
+                        pending.formula1 = Some(std::mem::take(&mut text));
+                    }
+                    target = TextTarget::None;
+                }
+                b"formula2" if target == TextTarget::Formula2 => {
+                    if let Some(pending) = validation.as_mut() {
+                        pending.formula2 = Some(std::mem::take(&mut text));
+                    }
+                    target = TextTarget::None;
+                }
+                b"formula" | b"f" if target == TextTarget::CfFormula => {
+                    if let Some(pending) = rule.as_mut() {
+                        pending.formulas.push(std::mem::take(&mut text));
+                    }
+                    target = TextTarget::None;
+                }
+                b"sqref" if target == TextTarget::Sqref => {
+                    let ranges = parse_sqref(&text)?;
+                    if let Some(pending) = validation.as_mut() {
+                        pending.ranges = ranges;
+                    } else if let Some(block) = block_ranges.as_mut() {
+                        *block = ranges;
+                    }
+                    text.clear();
+                    target = TextTarget::None;
+                }
+                b"dataValidation" => {
+                    if let Some(pending) = validation.take() {
+                        validations.push(finish_validation(pending, pool));
+                    }
+                }
+                b"cfRule" => block_rules.extend(rule.take()),
+                b"conditionalFormatting" => {
+                    // x14 blocks carry their ranges in a trailing `<xm:sqref>`, so rules are only
+                    // finished once the whole block has been read.
+                    if let Some(ranges) = block_ranges.take() {
+                        for pending in block_rules.drain(..) {
+                            formats.push(finish_format(pending, &ranges, pool));
+                        }
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(xml_err(&reader, xml, e)),
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok((validations, formats))
+}
+
+fn finish_validation(pending: PendingValidation, pool: &mut StringPool) -> DataValidationRule {
+    DataValidationRule {
+        ranges: pending.ranges,
+        kind: pool.intern(&pending.kind),
+        operator: pending.operator.map(|op| pool.intern(&op)),
+        formula1: pending.formula1.map(|f| pool.intern(f.trim())),
+        formula2: pending.formula2.map(|f| pool.intern(f.trim())),
+        allow_blank: pending.allow_blank,
+    }
+}
+
+fn finish_format(
+    pending: PendingFormat,
+    ranges: &[CellRange],
+    pool: &mut StringPool,
+) -> ConditionalFormatRule {
+    ConditionalFormatRule {
+        ranges: ranges.to_vec(),
+        kind: pool.intern(&pending.kind),
+        operator: pending.operator.map(|op| pool.intern(&op)),
+        formulas: pending
+            .formulas
+            .iter()
+            .map(|f| pool.intern(f.trim()))
+            .collect(),
+        priority: pending.priority,
+        stop_if_true: pending.stop_if_true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_validations_and_formats_including_x14_extensions() {
+        let xml = br#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:x14="http://schemas.microsoft.com/office/spreadsheetml/2009/9/main" xmlns:xm="http://schemas.microsoft.com/office/excel/2006/main"><sheetData/><conditionalFormatting sqref="C2:C10 E2"><cfRule type="cellIs" dxfId="0" priority="2" operator="greaterThan" stopIfTrue="1"><formula>100</formula></cfRule><cfRule type="expression" priority="1"><formula>$A2="x"</formula></cfRule></conditionalFormatting><dataValidations count="1"><dataValidation type="list" allowBlank="1" sqref="B2:B10"><formula1>"Yes,No"</formula1></dataValidation></dataValidations><extLst><ext><x14:dataValidations><x14:dataValidation type="list"><x14:formula1><xm:f>Lists!$A$1:$A$3</xm:f></x14:formula1><xm:sqref>D2:D5</xm:sqref></x14:dataValidation></x14:dataValidations><x14:conditionalFormattings><x14:conditionalFormatting><x14:cfRule type="expression" priority="3"><xm:f>Lists!$B$1&gt;0</xm:f></x14:cfRule><xm:sqref>F1:F4</xm:sqref></x14:conditionalFormatting></x14:conditionalFormattings></ext></extLst></worksheet>"#;
+        let mut pool = StringPool::new();
+        let (validations, formats) = parse_sheet_rules(xml, &mut pool).expect("parse rules");
+
+        assert_eq!(validations.len(), 2);
+        assert_eq!(pool.resolve(validations[0].kind), "list");
+        assert!(validations[0].allow_blank);
+        assert_eq!(validations[0].ranges[0].to_a1(), "B2:B10");
+        assert_eq!(
+            validations[0].formula1.map(|id| pool.resolve(id)),
+            Some("\"Yes,No\"")
+        );
+        assert_eq!(validations[1].ranges[0].to_a1(), "D2:D5");
+        assert_eq!(
+            validations[1].formula1.map(|id| pool.resolve(id)),
+            Some("Lists!$A$1:$A$3")
+        );
+
+        assert_eq!(formats.len(), 3);
+        let ranges: Vec<String> = formats[0].ranges.iter().map(CellRange::to_a1).collect();
+        assert_eq!(ranges, vec!["C2:C10", "E2"]);
+        assert_eq!(formats[0].priority, Some(2));
+        assert!(formats[0].stop_if_true);
+        assert_eq!(
+            formats[0].operator.map(|id| pool.resolve(id)),
+            Some("greaterThan")
+        );
+        assert_eq!(pool.resolve(formats[1].formulas[0]), "$A2=\"x\"");
+        assert_eq!(formats[2].ranges[0].to_a1(), "F1:F4");
+        assert_eq!(pool.resolve(formats[2].formulas[0]), "Lists!$B$1>0");
+    }
+}
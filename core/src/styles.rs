@@ -0,0 +1,566 @@
+//! Cell format parsing for `xl/styles.xml`.
+//!
+//! Resolves the `cellXfs` table into [`CellStyle`] values whose facets are canonical
+//! descriptor strings, and scans worksheet XML for the `s` style index on each cell.
+
+use crate::addressing::address_to_index;
+use crate::grid_parser::{local_tag_name, xml_err, xml_msg_err, GridParseError};
+use crate::string_pool::{StringId, StringPool};
+use crate::workbook::CellStyle;
+use quick_xml::escape::resolve_xml_entity;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::collections::{BTreeMap, HashMap};
+
+/// Format code for one of Excel's built-in number formats.
+fn builtin_number_format(id: u32) -> Option<&'static str> {
+    Some(match id {
+        0 => "General",
+        1 => "0",
+        2 => "0.00",
+        3 => "#,##0",
+        4 => "#,##0.00",
+        9 => "0%",
+        10 => "0.00%",
+        11 => "0.00E+00",
+        12 => "# ?/?",
+        13 => "# ??/??",
+        14 => "mm-dd-yy",
+        15 => "d-mmm-yy",
+        16 => "d-mmm",
+        17 => "mmm-yy",
+        18 => "h:mm AM/PM",
+        19 => "h:mm:ss AM/PM",
+        20 => "h:mm",
+        21 => "h:mm:ss",
+        22 => "m/d/yy h:mm",
+        37 => "#,##0 ;(#,##0)",
+        38 => "#,##0 ;[Red](#,##0)",
+        39 => "#,##0.00;(#,##0.00)",
+        40 => "#,##0.00;[Red](#,##0.00)",
+        45 => "mm:ss",
+        46 => "[h]:mm:ss",
+        47 => "mmss.0",
+        48 => "##0.0E+0",
+        49 => "@",
+        _ => return None,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    None,
+    NumFmts,
+    Fonts,
+    Fills,
+    Borders,
+    CellXfs,
+    Other,
+}
+
+#[derive(Debug, Default)]
+struct RawXf {
+    num_fmt_id: u32,
+    font_id: Option<usize>,
+    fill_id: Option<usize>,
+    border_id: Option<usize>,
+    alignment: Option<String>,
+    protection: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct StylesState {
+    num_fmts: HashMap<u32, String>,
+    fonts: Vec<String>,
+    fills: Vec<String>,
+    borders: Vec<String>,
+    xfs: Vec<RawXf>,
+
+    parts: Vec<String>,
+    /// Name of the fill/border child (`patternFill`, `left`, `stop`, ...) currently open.
+    child: Option<String>,
+    xf: Option<RawXf>,
+}
+
+pub(crate) fn collect_attrs(
+    reader: &Reader<&[u8]>,
+    xml: &[u8],
+    e: &BytesStart,
+) -> Result<Vec<(String, String)>, GridParseError> {
+    let mut attrs = Vec::new();
+    for attr in e.attributes() {
+        let attr = attr.map_err(|err| xml_msg_err(reader, xml, err.to_string()))?;
+        let key = String::from_utf8_lossy(local_tag_name(attr.key.as_ref())).into_owned();
+        // `unescape_value` does not fall back to the predefined entities in this
+        // quick-xml version, and format codes routinely contain `&quot;`.
+        let value = attr
+            .unescape_value_with(resolve_xml_entity)
+            .map_err(|err| xml_err(reader, xml, err))?
+            .into_owned();
+        attrs.push((key, value));
+    }
+    Ok(attrs)
+}
+
+pub(crate) fn attr<'a>(attrs: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    attrs
+        .iter()
+        .find(|(k, _)| k == key)
+        .map(|(_, v)| v.as_str())
+}
+
+fn normalize_bool(value: &str) -> &str {
+    match value {
+        "true" => "1",
+        "false" => "0",
+        other => other,
+    }
+}
+
+/// Canonical text for a `CT_Color` element: `rgb:AARRGGBB`, `theme:N`, `indexed:N` or `auto`,
+/// followed by `;tint=...` when a tint is present.
+pub(crate) fn color_descriptor(attrs: &[(String, String)]) -> String {
+    let mut out = if let Some(rgb) = attr(attrs, "rgb") {
+        format!("rgb:{}", rgb.to_ascii_uppercase())
+    } else if let Some(theme) = attr(attrs, "theme") {
+        format!("theme:{theme}")
+    } else if let Some(indexed) = attr(attrs, "indexed") {
+        format!("indexed:{indexed}")
+    } else if attr(attrs, "auto").map(normalize_bool) == Some("1") {
+        "auto".to_string()
+    } else {
+        "none".to_string()
+    };
+    if let Some(tint) = attr(attrs, "tint") {
+        out.push_str(";tint=");
+        out.push_str(tint);
+    }
+    out
+}
+
+/// Sorted `key=value` list of the element's attributes, or `None` when it has none.
+fn sorted_attr_descriptor(attrs: &[(String, String)]) -> Option<String> {
+    let mut pairs: Vec<String> = attrs
+        .iter()
+        .map(|(k, v)| format!("{k}={}", normalize_bool(v)))
+        .collect();
+    if pairs.is_empty() {
+        return None;
+    }
+    pairs.sort();
+    Some(pairs.join(";"))
+}
+
+fn parse_index(value: Option<&str>) -> Option<usize> {
+    value.and_then(|v| v.trim().parse::<usize>().ok())
+}
+
+impl StylesState {
+    fn start(&mut self, section: Section, name: &str, attrs: &[(String, String)]) {
+        match section {
+            Section::NumFmts if name == "numFmt" => {
+                if let (Some(id), Some(code)) = (
+                    attr(attrs, "numFmtId").and_then(|v| v.trim().parse::<u32>().ok()),
+                    attr(attrs, "formatCode"),
+                ) {
+                    self.num_fmts.insert(id, code.to_string());
+                }
+            }
+            Section::Fonts => match name {
+                "font" => self.parts.clear(),
+                "b" | "i" | "strike" | "outline" | "shadow" | "condense" | "extend" => {
+                    let on = attr(attrs, "val").map(normalize_bool).unwrap_or("1");
+                    if on == "1" {
+                        self.parts.push(name.to_string());
+                    }
+                }
+                "u" => {
+                    let val = attr(attrs, "val").unwrap_or("single");
+                    if val != "none" {
+                        self.parts.push(format!("u={val}"));
+                    }
+                }
+                "sz" | "name" | "vertAlign" => {
+                    if let Some(val) = attr(attrs, "val") {
+                        self.parts.push(format!("{name}={val}"));
+                    }
+                }
+                "color" => self
+                    .parts
+                    .push(format!("color={}", color_descriptor(attrs))),
+                _ => {}
+            },
+            Section::Fills => match name {
+                "fill" => self.parts.clear(),
+                "patternFill" => {
+                    let pattern = attr(attrs, "patternType").unwrap_or("none");
+                    self.parts.push(format!("pattern={pattern}"));
+                }
+                "gradientFill" => {
+                    let mut desc = String::from("gradient");
+                    if let Some(rest) = sorted_attr_descriptor(attrs) {
+                        desc.push(';');
+                        desc.push_str(&rest);
+                    }
+                    self.parts.push(desc);
+                }
+                "stop" => {
+                    self.child = attr(attrs, "position").map(|p| format!("stop@{p}"));
+                }
+                "fgColor" | "bgColor" => {
+                    let key = if name == "fgColor" { "fg" } else { "bg" };
+                    self.parts
+                        .push(format!("{key}={}", color_descriptor(attrs)));
+                }
+                "color" => {
+                    if let Some(stop) = self.child.as_deref() {
+                        let entry = format!("{stop}={}", color_descriptor(attrs));
+                        self.parts.push(entry);
+                    }
+                }
+                _ => {}
+            },
+            Section::Borders => match name {
+                "border" => {
+                    self.parts.clear();
+                    for flag in ["diagonalUp", "diagonalDown", "outline"] {
+                        if attr(attrs, flag).map(normalize_bool) == Some("1") {
+                            self.parts.push(flag.to_string());
+                        }
+                    }
+                }
+                "left" | "right" | "top" | "bottom" | "diagonal" | "start" | "end" | "vertical"
+                | "horizontal" => {
+                    self.child = None;
+                    if let Some(style) = attr(attrs, "style").filter(|s| *s != "none") {
+                        self.parts.push(format!("{name}={style}"));
+                        self.child = Some(name.to_string());
+                    }
+                }
+                "color" => {
+                    if self.child.is_some()
+                        && let Some(last) = self.parts.last_mut()
+                    {
+                        last.push(':');
+                        last.push_str(&color_descriptor(attrs));
+                    }
+                }
+                _ => {}
+            },
+            Section::CellXfs => match name {
+                "xf" => {
+                    self.xf = Some(RawXf {
+                        num_fmt_id: attr(attrs, "numFmtId")
+                            .and_then(|v| v.trim().parse::<u32>().ok())
+                            .unwrap_or(0),
+                        font_id: parse_index(attr(attrs, "fontId")),
+                        fill_id: parse_index(attr(attrs, "fillId")),
+                        border_id: parse_index(attr(attrs, "borderId")),
+                        alignment: None,
+                        protection: None,
+                    });
+                }
+                "alignment" => {
+                    if let Some(xf) = self.xf.as_mut() {
+                        xf.alignment = sorted_attr_descriptor(attrs);
+                    }
+                }
+                "protection" => {
+                    if let Some(xf) = self.xf.as_mut() {
+                        xf.protection = sorted_attr_descriptor(attrs);
+                    }
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, section: Section, name: &str) {
+        match (section, name) {
+            (Section::Fonts, "font") => {
+                let desc = self.take_parts();
+                self.fonts.push(desc);
+            }
+            (Section::Fills, "fill") => {
+                let desc = self.take_parts();
+                self.fills.push(desc);
+            }
+            (Section::Fills, "stop") => self.child = None,
+            (Section::Borders, "border") => {
+                let desc = self.take_parts();
+                self.borders.push(desc);
+            }
+            (Section::CellXfs, "xf") => {
+                if let Some(xf) = self.xf.take() {
+                    self.xfs.push(xf);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn take_parts(&mut self) -> String {
+        self.child = None;
+        if self.parts.is_empty() {
+            return "none".to_string();
+        }
+        std::mem::take(&mut self.parts).join(";")
+    }
+
+    fn resolve(self, pool: &mut StringPool) -> Vec<CellStyle> {
+        fn intern_at(
+            list: &[String],
+            idx: Option<usize>,
+            pool: &mut StringPool,
+        ) -> Option<StringId> {
+            idx.and_then(|i| list.get(i)).map(|s| pool.intern(s))
+        }
+
+        let mut out = Vec::with_capacity(self.xfs.len());
+        for xf in &self.xfs {
+            let number_format = if xf.num_fmt_id == 0 && !self.num_fmts.contains_key(&0) {
+                None
+            } else {
+                let code = match self.num_fmts.get(&xf.num_fmt_id) {
+                    Some(code) => code.clone(),
+                    None => builtin_number_format(xf.num_fmt_id)
+                        .map(str::to_string)
+                        .unwrap_or_else(|| format!("builtin:{}", xf.num_fmt_id)),
+                };
+                Some(pool.intern(&code))
+            };
+            out.push(CellStyle {
+                number_format,
+                font: intern_at(&self.fonts, xf.font_id, pool),
+                fill: intern_at(&self.fills, xf.fill_id, pool),
+                border: intern_at(&self.borders, xf.border_id, pool),
+                alignment: xf.alignment.as_deref().map(|s| pool.intern(s)),
+                protection: xf.protection.as_deref().map(|s| pool.intern(s)),
+            });
+        }
+        out
+    }
+}
+
+/// Parse `xl/styles.xml` into the resolved `cellXfs` table.
+///
+/// The returned vector is indexed by the `s` attribute of worksheet `<c>` elements.
+pub(crate) fn parse_styles_xml(
+    xml: &[u8],
+    pool: &mut StringPool,
+) -> Result<Vec<CellStyle>, GridParseError> {
+    let mut reader = Reader::from_reader(xml);
+    reader.config_mut().trim_text(true);
+    let mut buf = Vec::new();
+    let mut state = StylesState::default();
+    let mut section = Section::None;
+    let mut depth = 0usize;
+    let mut section_depth = 0usize;
+
+    loop {
+        let event = reader.read_event_into(&mut buf);
+        match event {
+            Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
+                let is_empty = matches!(event, Ok(Event::Empty(_)));
+                let name = String::from_utf8_lossy(local_tag_name(e.name().as_ref())).into_owned();
+                depth += 1;
+                if depth == 2 {
+                    section = match name.as_str() {
+                        "numFmts" => Section::NumFmts,
+                        "fonts" => Section::Fonts,
+                        "fills" => Section::Fills,
+                        "borders" => Section::Borders,
+                        "cellXfs" => Section::CellXfs,
+                        _ => Section::Other,
+                    };
+                    section_depth = depth;
+                } else if depth > 2 {
+                    let attrs = collect_attrs(&reader, xml, e)?;
+                    state.start(section, &name, &attrs);
+                }
+                if is_empty {
+                    if depth > section_depth {
+                        state.end(section, &name);
+                    }
+                    if depth == section_depth {
+                        section = Section::None;
+                    }
+                    depth -= 1;
+                }
+            }
+            Ok(Event::End(ref e)) => {
+                let name = String::from_utf8_lossy(local_tag_name(e.name().as_ref())).into_owned();
+                if depth > section_depth && section_depth > 0 {
+                    state.end(section, &name);
+                }
+                if depth == section_depth {
+                    section = Section::None;
+                    section_depth = 0;
+                }
+                depth = depth.saturating_sub(1);
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(xml_err(&reader, xml, e)),
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(state.resolve(pool))
+}
+
+/// Conservative detector for the `s` attribute on worksheet cells.
+pub(crate) fn sheet_uses_styles(xml: &[u8]) -> bool {
+    xml.windows(4).any(|w| w == br#" s=""# || w == b" s='")
+}
+
+/// Scan worksheet XML for cells with a non-zero `s` style index.
+///
+/// This covers styled-but-empty cells, which the grid parser drops.
+pub(crate) fn parse_sheet_cell_styles(
+    xml: &[u8],
+) -> Result<BTreeMap<(u32, u32), u32>, GridParseError> {
+    let mut reader = Reader::from_reader(xml);
+    reader.config_mut().trim_text(true);
+    let mut buf = Vec::new();
+    let mut cells = BTreeMap::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e))
+                if local_tag_name(e.name().as_ref()) == b"c" =>
+            {
+                let mut addr = None;
+                let mut style = 0u32;
+                for attr in e.attributes() {
+                    let attr = attr.map_err(|err| xml_msg_err(&reader, xml, err.to_string()))?;
+                    match attr.key.as_ref() {
+                        b"r" => {
+                            let raw = attr
+                                .unescape_value()
+                                .map_err(|err| xml_err(&reader, xml, err))?;
+                            addr =
+                                Some(address_to_index(raw.as_ref()).ok_or_else(|| {
+                                    GridParseError::InvalidAddress(raw.into_owned())
+                                })?);
+                        }
+                        b"s" => {
+                            style = std::str::from_utf8(attr.value.as_ref())
+                                .ok()
+                                .and_then(|v| v.trim().parse::<u32>().ok())
+                                .unwrap_or(0);
+                        }
+                        _ => {}
+                    }
+                }
+                if let Some(addr) = addr
+                    && style != 0
+                {
+                    cells.insert(addr, style);
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(xml_err(&reader, xml, e)),
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(cells)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STYLES: &[u8] = br#"<?xml version="1.0" encoding="UTF-8"?>
+<styleSheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">
+  <numFmts count="1"><numFmt numFmtId="164" formatCode="&quot;$&quot;#,##0.00"/></numFmts>
+  <fonts count="2">
+    <font><sz val="11"/><color theme="1"/><name val="Calibri"/></font>
+    <font><b/><sz val="11"/><color rgb="ffff0000"/><name val="Calibri"/></font>
+  </fonts>
+  <fills count="3">
+    <fill><patternFill patternType="none"/></fill>
+    <fill><patternFill patternType="gray125"/></fill>
+    <fill><patternFill patternType="solid"><fgColor rgb="FFFFFF00"/><bgColor indexed="64"/></patternFill></fill>
+  </fills>
+  <borders count="2">
+    <border><left/><right/><top/><bottom/><diagonal/></border>
+    <border><left style="thin"><color auto="1"/></left><right/><top/><bottom style="double"/><diagonal/></border>
+  </borders>
+  <cellStyleXfs count="1"><xf numFmtId="9" fontId="1" fillId="2" borderId="1"/></cellStyleXfs>
+  <cellXfs count="4">
+    <xf numFmtId="0" fontId="0" fillId="0" borderId="0" xfId="0"/>
+    <xf numFmtId="10" fontId="0" fillId="2" borderId="0" xfId="0" applyNumberFormat="1" applyFill="1"/>
+    <xf numFmtId="164" fontId="1" fillId="0" borderId="1" xfId="0">
+      <alignment horizontal="center" wrapText="true"/>
+      <protection locked="0"/>
+    </xf>
+    <xf numFmtId="200" fontId="0" fillId="0" borderId="0" xfId="0"/>
+  </cellXfs>
+  <dxfs count="1"><dxf><font><i/></font></dxf></dxfs>
+</styleSheet>"#;
+
+    #[test]
+    fn resolves_cell_xfs_into_descriptors() {
+        let mut pool = StringPool::new();
+        let styles = parse_styles_xml(STYLES, &mut pool).expect("styles parse");
+        assert_eq!(
+            styles.len(),
+            4,
+            "cellStyleXfs and dxfs are not cell formats"
+        );
+
+        let text = |id: Option<StringId>| id.map(|id| pool.resolve(id).to_string());
+        assert_eq!(text(styles[0].number_format), None);
+        assert_eq!(
+            text(styles[0].font).as_deref(),
+            Some("sz=11;color=theme:1;name=Calibri")
+        );
+        assert_eq!(text(styles[0].fill).as_deref(), Some("pattern=none"));
+        assert_eq!(text(styles[0].border).as_deref(), Some("none"));
+
+        assert_eq!(text(styles[1].number_format).as_deref(), Some("0.00%"));
+        assert_eq!(
+            text(styles[1].fill).as_deref(),
+            Some("pattern=solid;fg=rgb:FFFFFF00;bg=indexed:64")
+        );
+
+        assert_eq!(
+            text(styles[2].number_format).as_deref(),
+            Some("\"$\"#,##0.00")
+        );
+        assert_eq!(
+            text(styles[2].font).as_deref(),
+            Some("b;sz=11;color=rgb:FFFF0000;name=Calibri")
+        );
+        assert_eq!(
+            text(styles[2].border).as_deref(),
+            Some("left=thin:auto;bottom=double")
+        );
+        assert_eq!(
+            text(styles[2].alignment).as_deref(),
+            Some("horizontal=center;wrapText=1")
+        );
+        assert_eq!(text(styles[2].protection).as_deref(), Some("locked=0"));
+
+        assert_eq!(
+            text(styles[3].number_format).as_deref(),
+            Some("builtin:200")
+        );
+    }
+
+    #[test]
+    fn sheet_scan_collects_non_default_style_indices() {
+        let xml = br#"<worksheet><sheetData>
+            <row r="1"><c r="A1" s="1"><v>1</v></c><c r="B1"><v>2</v></c><c r="C1" s="0"/></row>
+            <row r="2"><c r="B2" s="3"/></row>
+        </sheetData></worksheet>"#;
+        let cells = parse_sheet_cell_styles(xml).expect("scan");
+        assert_eq!(cells.len(), 2);
+        assert_eq!(cells.get(&(0, 0)), Some(&1));
+        assert_eq!(cells.get(&(1, 1)), Some(&3));
+    }
+}
@@ -0,0 +1,345 @@
+//! Table mode: every Excel table present in both workbooks is diffed by key, with columns
+//! matched by header name instead of position.
+//!
+//! A table's header and data rows (not its totals row) are cut into a sub-grid whose columns are
+//! the headers shared by both versions, in old-table order. Key columns come from
+//! [`suggest_key_columns`] on the old sub-grid and the database-mode engine compares the two
+//! sub-grids; its ops are then mapped back onto sheet coordinates.
+
+use crate::config::DiffConfig;
+use crate::database_alignment::suggest_key_columns;
+use crate::diff::{DiffError, DiffOp, DiffSummary, FieldChange};
+use crate::engine::try_diff_grids_database_mode_streaming;
+use crate::object_diff::pair_tables;
+use crate::sink::DiffSink;
+use crate::string_pool::StringPool;
+use crate::workbook::{CellAddress, ExcelTable, ExcelTableColumn, Grid, Workbook};
+use std::collections::HashSet;
+
+/// Sheet coordinates of a table sub-grid's rows and columns on each side.
+struct TableCoords {
+    old_row_start: u32,
+    new_row_start: u32,
+    old_cols: Vec<u32>,
+    new_cols: Vec<u32>,
+}
+
+impl TableCoords {
+    fn old_col(&self, col: u32) -> u32 {
+        self.old_cols.get(col as usize).copied().unwrap_or(col)
+    }
+
+    fn new_col(&self, col: u32) -> u32 {
+        self.new_cols.get(col as usize).copied().unwrap_or(col)
+    }
+
+    fn new_addr(&self, addr: CellAddress) -> CellAddress {
+        CellAddress::from_indices(self.new_row_start + addr.row, self.new_col(addr.col))
+    }
+
+    /// Rewrite sub-grid indices: removed/source positions are old, the rest are new.
+    fn translate(&self, op: DiffOp) -> DiffOp {
+        let (old_row, new_row) = (self.old_row_start, self.new_row_start);
+        match op {
+            DiffOp::RowAdded {
+                sheet,
+                row_idx,
+                row_signature,
+            } => DiffOp::RowAdded {
+                sheet,
+                row_idx: new_row + row_idx,
+                row_signature,
+            },
+            DiffOp::RowRemoved {
+                sheet,
+                row_idx,
+                row_signature,
+            } => DiffOp::RowRemoved {
+                sheet,
+                row_idx: old_row + row_idx,
+                row_signature,
+            },
+            DiffOp::RowReplaced { sheet, row_idx } => DiffOp::RowReplaced {
+                sheet,
+                row_idx: new_row + row_idx,
+            },
+            DiffOp::DuplicateKeyCluster {
+                sheet,
+                key,
+                left_rows,
+                right_rows,
+            } => DiffOp::DuplicateKeyCluster {
+                sheet,
+                key,
+                left_rows: left_rows.into_iter().map(|row| old_row + row).collect(),
+                right_rows: right_rows.into_iter().map(|row| new_row + row).collect(),
+            },
+            DiffOp::RecordChanged {
+                sheet,
+                key,
+                old_row: record_old_row,
+                new_row: record_new_row,
+                fields,
+            } => DiffOp::RecordChanged {
+                sheet,
+                key,
+                old_row: old_row + record_old_row,
+                new_row: new_row + record_new_row,
+                fields: fields
+                    .into_iter()
+                    .map(|field| FieldChange {
+                        col: self.new_col(field.col),
+                        ..field
+                    })
+                    .collect(),
+            },
+            DiffOp::ColumnAdded {
+                sheet,
+                col_idx,
+                col_signature,
+            } => DiffOp::ColumnAdded {
+                sheet,
+                col_idx: self.new_col(col_idx),
+                col_signature,
+            },
+            DiffOp::ColumnRemoved {
+                sheet,
+                col_idx,
+                col_signature,
+            } => DiffOp::ColumnRemoved {
+                sheet,
+                col_idx: self.old_col(col_idx),
+                col_signature,
+            },
+            DiffOp::BlockMovedRows {
+                sheet,
+                src_start_row,
+                row_count,
+                dst_start_row,
+                block_hash,
+            } => DiffOp::BlockMovedRows {
+                sheet,
+                src_start_row: old_row + src_start_row,
+                row_count,
+                dst_start_row: new_row + dst_start_row,
+                block_hash,
+            },
+            DiffOp::BlockMovedColumns {
+                sheet,
+                src_start_col,
+                col_count,
+                dst_start_col,
+                block_hash,
+            } => DiffOp::BlockMovedColumns {
+                sheet,
+                src_start_col: self.old_col(src_start_col),
+                col_count,
+                dst_start_col: self.new_col(dst_start_col),
+                block_hash,
+            },
+            DiffOp::BlockMovedRect {
+                sheet,
+                src_start_row,
+                src_row_count,
+                src_start_col,
+                src_col_count,
+                dst_start_row,
+                dst_start_col,
+                block_hash,
+            } => DiffOp::BlockMovedRect {
+                sheet,
+                src_start_row: old_row + src_start_row,
+                src_row_count,
+                src_start_col: self.old_col(src_start_col),
+                src_col_count,
+                dst_start_row: new_row + dst_start_row,
+                dst_start_col: self.new_col(dst_start_col),
+                block_hash,
+            },
+            DiffOp::RectReplaced {
+                sheet,
+                start_row,
+                row_count,
+                start_col,
+                col_count,
+            } => DiffOp::RectReplaced {
+                sheet,
+                start_row: new_row + start_row,
+                row_count,
+                start_col: self.new_col(start_col),
+                col_count,
+            },
+            DiffOp::CellEdited {
+                sheet,
+                addr,
+                mut from,
+                mut to,
+                formula_diff,
+                formula_edits,
+            } => {
+                let addr = self.new_addr(addr);
+                from.addr = addr;
+                to.addr = addr;
+                DiffOp::CellEdited {
+                    sheet,
+                    addr,
+                    from,
+                    to,
+                    formula_diff,
+                    formula_edits,
+                }
+            }
+            other => other,
+        }
+    }
+}
+
+/// Forwards translated ops to `inner`, calling its `begin` at most once and never `finish`.
+struct TableSink<'a, S: DiffSink> {
+    inner: &'a mut S,
+    begun: &'a mut bool,
+    coords: &'a TableCoords,
+}
+
+impl<S: DiffSink> DiffSink for TableSink<'_, S> {
+    fn begin(&mut self, pool: &StringPool) -> Result<(), DiffError> {
+        if *self.begun {
+            return Ok(());
+        }
+        *self.begun = true;
+        self.inner.begin(pool)
+    }
+
+    fn emit(&mut self, op: DiffOp) -> Result<(), DiffError> {
+        self.inner.emit(self.coords.translate(op))
+    }
+
+    fn finish(&mut self) -> Result<(), DiffError> {
+        Ok(())
+    }
+}
+
+/// Pair columns by header name (case-insensitive), then by column id for renamed headers.
+/// Returns `(old position, new position)` in old-table order.
+fn pair_columns(
+    old: &[ExcelTableColumn],
+    new: &[ExcelTableColumn],
+    pool: &StringPool,
+) -> Vec<(u32, u32)> {
+    let name = |column: &ExcelTableColumn| pool.resolve(column.name).to_lowercase();
+    let mut used: HashSet<usize> = HashSet::new();
+    let mut matched: Vec<Option<usize>> = old
+        .iter()
+        .map(|column| {
+            let key = name(column);
+            let idx = (0..new.len()).find(|&idx| !used.contains(&idx) && name(&new[idx]) == key)?;
+            used.insert(idx);
+            Some(idx)
+        })
+        .collect();
+    for (slot, column) in matched.iter_mut().zip(old) {
+        if slot.is_none() {
+            *slot = (0..new.len()).find(|&idx| !used.contains(&idx) && new[idx].id == column.id);
+            used.extend(*slot);
+        }
+    }
+    matched
+        .into_iter()
+        .enumerate()
+        .filter_map(|(old_idx, new_idx)| Some((old_idx as u32, new_idx? as u32)))
+        .collect()
+}
+
+/// Copy a table's header and data rows, restricted to `cols` (sheet columns), into a new grid.
+fn table_grid(workbook: &Workbook, table: &ExcelTable, cols: &[u32]) -> Grid {
+    let rows = table.range.start.row..table.data_rows().end;
+    let mut grid = Grid::new(rows.len() as u32, cols.len() as u32);
+    let Some(sheet) = workbook
+        .sheets
+        .iter()
+        .find(|sheet| sheet.name == table.sheet)
+    else {
+        return grid;
+    };
+    for (row, sheet_row) in rows.enumerate() {
+        for (col, &sheet_col) in cols.iter().enumerate() {
+            if let Some(cell) = sheet.grid.get(sheet_row, sheet_col) {
+                grid.insert_cell(row as u32, col as u32, cell.value, cell.formula);
+            }
+        }
+    }
+    grid
+}
+
+/// Diff the contents of every table present in both workbooks into `sink`.
+///
+/// `sink.begin` is called exactly once; `finish` is left to the caller. Nothing here interns
+/// strings, so callers may compute other ops beforehand.
+pub(crate) fn diff_tables_streaming<S: DiffSink>(
+    old: &Workbook,
+    new: &Workbook,
+    pool: &mut StringPool,
+    config: &DiffConfig,
+    sink: &mut S,
+) -> Result<DiffSummary, DiffError> {
+    let mut begun = false;
+    let mut op_count = 0usize;
+    let mut complete = true;
+    let mut warnings = Vec::new();
+
+    for (old_table, new_table) in pair_tables(&old.tables, &new.tables, pool) {
+        let columns = pair_columns(&old_table.columns, &new_table.columns, pool);
+        let coords = TableCoords {
+            old_row_start: old_table.range.start.row,
+            new_row_start: new_table.range.start.row,
+            old_cols: columns
+                .iter()
+                .map(|(idx, _)| old_table.range.start.col + idx)
+                .collect(),
+            new_cols: columns
+                .iter()
+                .map(|(_, idx)| new_table.range.start.col + idx)
+                .collect(),
+        };
+        let old_grid = table_grid(old, old_table, &coords.old_cols);
+        let new_grid = table_grid(new, new_table, &coords.new_cols);
+
+        let key_columns = suggest_key_columns(&old_grid, pool);
+        if key_columns.is_empty() {
+            warnings.push(format!(
+                "table '{}': no key columns detected; rows compared by position",
+                pool.resolve(new_table.name)
+            ));
+        }
+
+        let mut table_sink = TableSink {
+            inner: sink,
+            begun: &mut begun,
+            coords: &coords,
+        };
+        let summary = try_diff_grids_database_mode_streaming(
+            new_table.sheet,
+            &old_grid,
+            &new_grid,
+            &key_columns,
+            pool,
+            config,
+            &mut table_sink,
+            &mut op_count,
+        )?;
+        complete &= summary.complete;
+        warnings.extend(summary.warnings);
+    }
+
+    if !begun {
+        sink.begin(pool)?;
+    }
+
+    Ok(DiffSummary {
+        complete: complete && warnings.is_empty(),
+        warnings,
+        op_count,
+        #[cfg(feature = "perf-metrics")]
+        metrics: None,
+    })
+}
@@ -3,20 +3,30 @@ use serde::Deserialize;
 use serde_json::Value;
 
 use crate::excel_open_xml::PackageError;
-use crate::model::{Measure, Model, ModelColumn, ModelRelationship, ModelTable};
-use crate::string_pool::StringPool;
+use crate::model::{
+    Measure, MeasureKpi, Model, ModelCalculationGroup, ModelCalculationItem, ModelColumn,
+    ModelCulture, ModelHierarchy, ModelLevel, ModelPartition, ModelPerspective, ModelRelationship,
+    ModelRole, ModelTable, ModelTablePermission,
+};
+use crate::string_pool::{StringId, StringPool};
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub(crate) struct RawTabularModel {
     pub tables: Vec<RawTable>,
     pub relationships: Vec<RawRelationship>,
     pub measures: Vec<RawMeasure>,
+    pub roles: Vec<RawRole>,
+    pub perspectives: Vec<RawPerspective>,
+    pub cultures: Vec<RawCulture>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub(crate) struct RawTable {
     pub name: String,
     pub columns: Vec<RawColumn>,
+    pub hierarchies: Vec<RawHierarchy>,
+    pub partitions: Vec<RawPartition>,
+    pub calculation_group: Option<RawCalculationGroup>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -42,10 +52,86 @@ pub(crate) struct RawRelationship {
     pub name: Option<String>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub(crate) struct RawMeasure {
     pub full_name: String,
     pub expression: String,
+    pub format_string: Option<String>,
+    pub display_folder: Option<String>,
+    pub description: Option<String>,
+    pub kpi: Option<RawKpi>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct RawKpi {
+    pub target_expression: Option<String>,
+    pub status_expression: Option<String>,
+    pub trend_expression: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RawHierarchy {
+    pub name: String,
+    pub description: Option<String>,
+    pub display_folder: Option<String>,
+    pub is_hidden: Option<bool>,
+    /// `(level, column)` in ordinal order.
+    pub levels: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RawPartition {
+    pub name: String,
+    pub mode: Option<String>,
+    pub source_type: Option<String>,
+    pub source: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct RawCalculationGroup {
+    pub precedence: Option<i64>,
+    pub description: Option<String>,
+    pub items: Vec<RawCalculationItem>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RawCalculationItem {
+    pub name: String,
+    pub expression: String,
+    pub format_string_expression: Option<String>,
+    pub ordinal: Option<i64>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RawRole {
+    pub name: String,
+    pub description: Option<String>,
+    pub model_permission: Option<String>,
+    pub members: Vec<String>,
+    pub table_permissions: Vec<RawTablePermission>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RawTablePermission {
+    pub table: String,
+    pub filter_expression: Option<String>,
+    pub metadata_permission: Option<String>,
+    /// `(column, metadataPermission)`.
+    pub column_permissions: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RawPerspective {
+    pub name: String,
+    pub members: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RawCulture {
+    pub name: String,
+    /// `(object property, translated text)`, e.g. `("table Sales caption", "Ventes")`.
+    pub translations: Vec<(String, String)>,
 }
 
 fn strip_bom(s: &str) -> &str {
@@ -110,7 +196,7 @@ fn parse_data_model_schema_custom(text: &str) -> Result<RawTabularModel, Package
         if !table_name.is_empty() {
             let mut raw_table = RawTable {
                 name: table_name.clone(),
-                columns: Vec::new(),
+                ..Default::default()
             };
 
             if let Some(columns) = t.columns {
@@ -121,6 +207,12 @@ fn parse_data_model_schema_custom(text: &str) -> Result<RawTabularModel, Package
                 }
             }
 
+            collect_table_objects(
+                &mut raw_table,
+                t.hierarchies.as_deref(),
+                t.partitions.as_deref(),
+                t.calculation_group.as_ref(),
+            );
             out.tables.push(raw_table);
         }
 
@@ -141,6 +233,12 @@ fn parse_data_model_schema_custom(text: &str) -> Result<RawTabularModel, Package
         }
     }
 
+    collect_model_objects(
+        model.roles.as_deref(),
+        model.perspectives.as_deref(),
+        model.cultures.as_deref(),
+        &mut out,
+    );
     normalize(&mut out);
     Ok(out)
 }
@@ -164,6 +262,10 @@ fn parse_measure_schema(v: SchemaMeasure, table_name: &str) -> Option<RawMeasure
     Some(RawMeasure {
         full_name,
         expression: expr,
+        format_string: opt_nonempty_string(v.format_string),
+        display_folder: opt_nonempty_string(v.display_folder),
+        description: v.description.as_ref().and_then(expression_text),
+        kpi: v.kpi.as_ref().map(parse_kpi_obj),
     })
 }
 
@@ -212,6 +314,9 @@ struct SchemaRoot {
 struct SchemaModel {
     tables: Option<Vec<SchemaTable>>,
     relationships: Option<Vec<SchemaRelationship>>,
+    roles: Option<Vec<Value>>,
+    perspectives: Option<Vec<Value>>,
+    cultures: Option<Vec<Value>>,
 }
 
 #[cfg(feature = "custom-json-schema")]
@@ -221,6 +326,9 @@ struct SchemaTable {
     name: Option<String>,
     columns: Option<Vec<SchemaColumn>>,
     measures: Option<Vec<SchemaMeasure>>,
+    hierarchies: Option<Vec<Value>>,
+    partitions: Option<Vec<Value>>,
+    calculation_group: Option<Value>,
 }
 
 #[cfg(feature = "custom-json-schema")]
@@ -256,6 +364,10 @@ struct SchemaRelationship {
 struct SchemaMeasure {
     name: Option<String>,
     expression: Option<String>,
+    format_string: Option<String>,
+    display_folder: Option<String>,
+    description: Option<Value>,
+    kpi: Option<Value>,
 }
 
 fn try_collect_from_model_tables(v: &Value, out: &mut RawTabularModel) -> bool {
@@ -272,7 +384,7 @@ fn try_collect_from_model_tables(v: &Value, out: &mut RawTabularModel) -> bool {
         let table_name = t.get("name").and_then(|x| x.as_str()).unwrap_or("");
         let mut raw_table = RawTable {
             name: table_name.to_string(),
-            columns: Vec::new(),
+            ..Default::default()
         };
 
         if let Some(columns) = t.get("columns").and_then(|c| c.as_array()) {
@@ -283,6 +395,13 @@ fn try_collect_from_model_tables(v: &Value, out: &mut RawTabularModel) -> bool {
             }
         }
 
+        collect_table_objects(
+            &mut raw_table,
+            array_field(t, "hierarchies"),
+            array_field(t, "partitions"),
+            t.get("calculationGroup"),
+        );
+
         if !raw_table.name.is_empty() {
             out.tables.push(raw_table);
         }
@@ -303,6 +422,13 @@ fn try_collect_from_model_tables(v: &Value, out: &mut RawTabularModel) -> bool {
             }
         }
     }
+
+    collect_model_objects(
+        array_field(model, "roles"),
+        array_field(model, "perspectives"),
+        array_field(model, "cultures"),
+        out,
+    );
     true
 }
 
@@ -319,9 +445,223 @@ fn parse_measure_obj(v: &Value, table_name: &str) -> Option<RawMeasure> {
     Some(RawMeasure {
         full_name,
         expression: expr.to_string(),
+        format_string: opt_string_field(v, "formatString"),
+        display_folder: opt_string_field(v, "displayFolder"),
+        description: opt_expression_field(v, "description"),
+        kpi: v.get("kpi").map(parse_kpi_obj),
+    })
+}
+
+fn parse_kpi_obj(v: &Value) -> RawKpi {
+    RawKpi {
+        target_expression: opt_expression_field(v, "targetExpression"),
+        status_expression: opt_expression_field(v, "statusExpression"),
+        trend_expression: opt_expression_field(v, "trendExpression"),
+    }
+}
+
+fn collect_table_objects(
+    table: &mut RawTable,
+    hierarchies: Option<&[Value]>,
+    partitions: Option<&[Value]>,
+    calculation_group: Option<&Value>,
+) {
+    for h in hierarchies.unwrap_or_default() {
+        let Some(name) = h.get("name").and_then(|x| x.as_str()) else {
+            continue;
+        };
+        let mut levels: Vec<(i64, String, String)> = array_field(h, "levels")
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|level| {
+                let name = level.get("name").and_then(|x| x.as_str())?;
+                let column = level.get("column").and_then(|x| x.as_str()).unwrap_or("");
+                let ordinal = level.get("ordinal").and_then(|x| x.as_i64()).unwrap_or(0);
+                Some((ordinal, name.to_string(), column.to_string()))
+            })
+            .collect();
+        levels.sort_by_key(|(ordinal, _, _)| *ordinal);
+        table.hierarchies.push(RawHierarchy {
+            name: name.to_string(),
+            description: opt_expression_field(h, "description"),
+            display_folder: opt_string_field(h, "displayFolder"),
+            is_hidden: h.get("isHidden").and_then(|x| x.as_bool()),
+            levels: levels
+                .into_iter()
+                .map(|(_, name, column)| (name, column))
+                .collect(),
+        });
+    }
+
+    for p in partitions.unwrap_or_default() {
+        let Some(name) = p.get("name").and_then(|x| x.as_str()) else {
+            continue;
+        };
+        let source = p.get("source");
+        table.partitions.push(RawPartition {
+            name: name.to_string(),
+            mode: opt_string_field(p, "mode"),
+            source_type: source.and_then(|s| opt_string_field(s, "type")),
+            source: source.and_then(|s| {
+                opt_expression_field(s, "expression").or_else(|| opt_expression_field(s, "query"))
+            }),
+        });
+    }
+
+    if let Some(group) = calculation_group {
+        let items = array_field(group, "calculationItems")
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|item| {
+                let name = item.get("name").and_then(|x| x.as_str())?;
+                Some(RawCalculationItem {
+                    name: name.to_string(),
+                    expression: opt_expression_field(item, "expression").unwrap_or_default(),
+                    format_string_expression: item
+                        .get("formatStringDefinition")
+                        .and_then(|d| opt_expression_field(d, "expression")),
+                    ordinal: item.get("ordinal").and_then(|x| x.as_i64()),
+                    description: opt_expression_field(item, "description"),
+                })
+            })
+            .collect();
+        table.calculation_group = Some(RawCalculationGroup {
+            precedence: group.get("precedence").and_then(|x| x.as_i64()),
+            description: opt_expression_field(group, "description"),
+            items,
+        });
+    }
+}
+
+fn collect_model_objects(
+    roles: Option<&[Value]>,
+    perspectives: Option<&[Value]>,
+    cultures: Option<&[Value]>,
+    out: &mut RawTabularModel,
+) {
+    out.roles
+        .extend(roles.unwrap_or_default().iter().filter_map(parse_role_obj));
+    out.perspectives.extend(
+        perspectives
+            .unwrap_or_default()
+            .iter()
+            .filter_map(parse_perspective_obj),
+    );
+    out.cultures.extend(
+        cultures
+            .unwrap_or_default()
+            .iter()
+            .filter_map(parse_culture_obj),
+    );
+}
+
+fn parse_role_obj(v: &Value) -> Option<RawRole> {
+    let name = v.get("name").and_then(|x| x.as_str())?;
+    let members = array_field(v, "members")
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|m| opt_string_field(m, "memberName"))
+        .collect();
+    let table_permissions = array_field(v, "tablePermissions")
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|tp| {
+            let table = tp.get("name").and_then(|x| x.as_str())?;
+            let column_permissions = array_field(tp, "columnPermissions")
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|cp| {
+                    let column = cp.get("name").and_then(|x| x.as_str())?;
+                    let permission = opt_string_field(cp, "metadataPermission")?;
+                    Some((column.to_string(), permission))
+                })
+                .collect();
+            Some(RawTablePermission {
+                table: table.to_string(),
+                filter_expression: opt_expression_field(tp, "filterExpression"),
+                metadata_permission: opt_string_field(tp, "metadataPermission"),
+                column_permissions,
+            })
+        })
+        .collect();
+
+    Some(RawRole {
+        name: name.to_string(),
+        description: opt_expression_field(v, "description"),
+        model_permission: opt_string_field(v, "modelPermission"),
+        members,
+        table_permissions,
+    })
+}
+
+fn parse_perspective_obj(v: &Value) -> Option<RawPerspective> {
+    let name = v.get("name").and_then(|x| x.as_str())?;
+    let mut members = Vec::new();
+    for table in array_field(v, "tables").unwrap_or_default() {
+        let Some(table_name) = table.get("name").and_then(|x| x.as_str()) else {
+            continue;
+        };
+        members.push(format!("table {}", table_name));
+        for (key, kind) in [
+            ("columns", "column"),
+            ("measures", "measure"),
+            ("hierarchies", "hierarchy"),
+        ] {
+            for member in array_field(table, key).unwrap_or_default() {
+                if let Some(member_name) = member.get("name").and_then(|x| x.as_str()) {
+                    members.push(format!("{} {}[{}]", kind, table_name, member_name));
+                }
+            }
+        }
+    }
+    Some(RawPerspective {
+        name: name.to_string(),
+        members,
+    })
+}
+
+fn parse_culture_obj(v: &Value) -> Option<RawCulture> {
+    let name = v.get("name").and_then(|x| x.as_str())?;
+    let mut translations = Vec::new();
+    if let Some(model) = v.get("translations").and_then(|t| t.get("model")) {
+        push_translations(model, "model", &mut translations);
+        for table in array_field(model, "tables").unwrap_or_default() {
+            let Some(table_name) = table.get("name").and_then(|x| x.as_str()) else {
+                continue;
+            };
+            push_translations(table, &format!("table {}", table_name), &mut translations);
+            for (key, kind) in [
+                ("columns", "column"),
+                ("measures", "measure"),
+                ("hierarchies", "hierarchy"),
+            ] {
+                for member in array_field(table, key).unwrap_or_default() {
+                    if let Some(member_name) = member.get("name").and_then(|x| x.as_str()) {
+                        let object = format!("{} {}[{}]", kind, table_name, member_name);
+                        push_translations(member, &object, &mut translations);
+                    }
+                }
+            }
+        }
+    }
+    Some(RawCulture {
+        name: name.to_string(),
+        translations,
     })
 }
 
+fn push_translations(v: &Value, object: &str, out: &mut Vec<(String, String)>) {
+    for (key, property) in [
+        ("translatedCaption", "caption"),
+        ("translatedDescription", "description"),
+        ("translatedDisplayFolder", "display folder"),
+    ] {
+        if let Some(text) = opt_string_field(v, key) {
+            out.push((format!("{} {}", object, property), text));
+        }
+    }
+}
+
 fn parse_column_obj(v: &Value) -> Option<RawColumn> {
     let name = v.get("name").and_then(|x| x.as_str())?;
     let data_type = opt_string_field(v, "dataType");
@@ -389,7 +729,7 @@ fn collect_measures_anywhere(v: &Value, table_name: &str, out: &mut RawTabularMo
     }
 }
 
-fn normalize(out: &mut RawTabularModel) {
+pub(crate) fn normalize(out: &mut RawTabularModel) {
     out.measures
         .sort_by(|a, b| cmp_case_insensitive(&a.full_name, &b.full_name));
     out.measures
@@ -404,6 +744,40 @@ fn normalize(out: &mut RawTabularModel) {
     }
 
     out.relationships.sort_by(|a, b| cmp_relationship_key(a, b));
+
+    for table in &mut out.tables {
+        table
+            .hierarchies
+            .sort_by(|a, b| cmp_case_insensitive(&a.name, &b.name));
+        table
+            .partitions
+            .sort_by(|a, b| cmp_case_insensitive(&a.name, &b.name));
+        if let Some(group) = &mut table.calculation_group {
+            group
+                .items
+                .sort_by(|a, b| cmp_case_insensitive(&a.name, &b.name));
+        }
+    }
+    out.roles
+        .sort_by(|a, b| cmp_case_insensitive(&a.name, &b.name));
+    for role in &mut out.roles {
+        role.members.sort();
+        role.table_permissions
+            .sort_by(|a, b| cmp_case_insensitive(&a.table, &b.table));
+        for permission in &mut role.table_permissions {
+            permission.column_permissions.sort();
+        }
+    }
+    out.perspectives
+        .sort_by(|a, b| cmp_case_insensitive(&a.name, &b.name));
+    for perspective in &mut out.perspectives {
+        perspective.members.sort();
+    }
+    out.cultures
+        .sort_by(|a, b| cmp_case_insensitive(&a.name, &b.name));
+    for culture in &mut out.cultures {
+        culture.translations.sort();
+    }
 }
 
 pub(crate) fn build_model(raw: &RawTabularModel, pool: &mut StringPool) -> Model {
@@ -429,7 +803,59 @@ pub(crate) fn build_model(raw: &RawTabularModel, pool: &mut StringPool) -> Model
                 expression,
             });
         }
-        m.tables.push(ModelTable { name, columns });
+        let hierarchies = rt
+            .hierarchies
+            .iter()
+            .map(|h| ModelHierarchy {
+                name: pool.intern(&h.name),
+                description: intern_opt(pool, &h.description),
+                display_folder: intern_opt(pool, &h.display_folder),
+                is_hidden: h.is_hidden,
+                levels: h
+                    .levels
+                    .iter()
+                    .map(|(level, column)| ModelLevel {
+                        name: pool.intern(level),
+                        column: pool.intern(column),
+                    })
+                    .collect(),
+            })
+            .collect();
+        let partitions = rt
+            .partitions
+            .iter()
+            .map(|p| ModelPartition {
+                name: pool.intern(&p.name),
+                mode: intern_opt(pool, &p.mode),
+                source_type: intern_opt(pool, &p.source_type),
+                source: intern_opt(pool, &p.source),
+            })
+            .collect();
+        let calculation_group = rt
+            .calculation_group
+            .as_ref()
+            .map(|g| ModelCalculationGroup {
+                precedence: g.precedence,
+                description: intern_opt(pool, &g.description),
+                items: g
+                    .items
+                    .iter()
+                    .map(|item| ModelCalculationItem {
+                        name: pool.intern(&item.name),
+                        expression: pool.intern(&item.expression),
+                        format_string_expression: intern_opt(pool, &item.format_string_expression),
+                        ordinal: item.ordinal,
+                        description: intern_opt(pool, &item.description),
+                    })
+                    .collect(),
+            });
+        m.tables.push(ModelTable {
+            name,
+            columns,
+            hierarchies,
+            partitions,
+            calculation_group,
+        });
     }
 
     for rr in &raw.relationships {
@@ -458,14 +884,93 @@ pub(crate) fn build_model(raw: &RawTabularModel, pool: &mut StringPool) -> Model
     for rm in &raw.measures {
         let name = pool.intern(&rm.full_name);
         let expr = pool.intern(&rm.expression);
+        let kpi = rm.kpi.as_ref().map(|k| MeasureKpi {
+            target_expression: intern_opt(pool, &k.target_expression),
+            status_expression: intern_opt(pool, &k.status_expression),
+            trend_expression: intern_opt(pool, &k.trend_expression),
+        });
         m.measures.push(Measure {
             name,
             expression: expr,
+            format_string: intern_opt(pool, &rm.format_string),
+            display_folder: intern_opt(pool, &rm.display_folder),
+            description: intern_opt(pool, &rm.description),
+            kpi,
+        });
+    }
+
+    for role in &raw.roles {
+        let table_permissions = role
+            .table_permissions
+            .iter()
+            .map(|tp| ModelTablePermission {
+                table: pool.intern(&tp.table),
+                filter_expression: intern_opt(pool, &tp.filter_expression),
+                metadata_permission: intern_opt(pool, &tp.metadata_permission),
+                column_permissions: tp
+                    .column_permissions
+                    .iter()
+                    .map(|(column, permission)| (pool.intern(column), pool.intern(permission)))
+                    .collect(),
+            })
+            .collect();
+        m.roles.push(ModelRole {
+            name: pool.intern(&role.name),
+            description: intern_opt(pool, &role.description),
+            model_permission: intern_opt(pool, &role.model_permission),
+            members: role.members.iter().map(|s| pool.intern(s)).collect(),
+            table_permissions,
+        });
+    }
+
+    for perspective in &raw.perspectives {
+        m.perspectives.push(ModelPerspective {
+            name: pool.intern(&perspective.name),
+            members: perspective.members.iter().map(|s| pool.intern(s)).collect(),
+        });
+    }
+
+    for culture in &raw.cultures {
+        m.cultures.push(ModelCulture {
+            name: pool.intern(&culture.name),
+            translations: culture
+                .translations
+                .iter()
+                .map(|(key, text)| (pool.intern(key), pool.intern(text)))
+                .collect(),
         });
     }
     m
 }
 
+fn intern_opt(pool: &mut StringPool, s: &Option<String>) -> Option<StringId> {
+    s.as_deref().map(|s| pool.intern(s))
+}
+
+fn array_field<'a>(v: &'a Value, key: &str) -> Option<&'a [Value]> {
+    v.get(key).and_then(|x| x.as_array()).map(|a| a.as_slice())
+}
+
+fn opt_expression_field(v: &Value, key: &str) -> Option<String> {
+    v.get(key).and_then(expression_text)
+}
+
+/// Reads an expression or description that is either a string or an array of lines.
+fn expression_text(v: &Value) -> Option<String> {
+    match v {
+        Value::String(s) => Some(s.clone()),
+        Value::Array(lines) => Some(
+            lines
+                .iter()
+                .filter_map(|line| line.as_str())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        ),
+        _ => None,
+    }
+    .filter(|s| !s.trim().is_empty())
+}
+
 fn opt_string_field(v: &Value, key: &str) -> Option<String> {
     v.get(key)
         .and_then(|x| x.as_str())
@@ -617,6 +1122,185 @@ mod tests {
         assert_eq!(pool.resolve(model.measures[0].name), "Sales/Total");
     }
 
+    const FULL_MODEL_JSON: &str = r##"{
+        "model": {
+            "tables": [
+                {
+                    "name": "Sales",
+                    "measures": [
+                        {
+                            "name": "Total",
+                            "expression": "SUM(Sales[Amount])",
+                            "formatString": "#,0",
+                            "displayFolder": "KPIs",
+                            "description": ["Total sales", "before returns"],
+                            "kpi": { "targetExpression": "1000", "statusExpression": "1" }
+                        }
+                    ],
+                    "hierarchies": [
+                        {
+                            "name": "Geo",
+                            "levels": [
+                                { "name": "City", "ordinal": 1, "column": "City" },
+                                { "name": "Country", "ordinal": 0, "column": "Country" }
+                            ]
+                        }
+                    ],
+                    "partitions": [
+                        {
+                            "name": "Sales-2024",
+                            "mode": "import",
+                            "source": {
+                                "type": "m",
+                                "expression": [
+                                    "let",
+                                    "    Source = Sql.Database(\"srv\", \"db\")",
+                                    "in",
+                                    "    Source"
+                                ]
+                            }
+                        }
+                    ]
+                },
+                {
+                    "name": "Time Intelligence",
+                    "calculationGroup": {
+                        "precedence": 10,
+                        "calculationItems": [
+                            {
+                                "name": "YTD",
+                                "expression": "CALCULATE(SELECTEDMEASURE(), DATESYTD(Dates[Date]))",
+                                "ordinal": 1,
+                                "formatStringDefinition": { "expression": "\"0.0\"" }
+                            }
+                        ]
+                    }
+                }
+            ],
+            "roles": [
+                {
+                    "name": "EastOnly",
+                    "modelPermission": "read",
+                    "members": [
+                        { "memberName": "CONTOSO\\bob" },
+                        { "memberName": "CONTOSO\\alice" }
+                    ],
+                    "tablePermissions": [
+                        {
+                            "name": "Sales",
+                            "filterExpression": "[Region] = \"East\"",
+                            "columnPermissions": [{ "name": "Cost", "metadataPermission": "none" }]
+                        }
+                    ]
+                }
+            ],
+            "perspectives": [
+                {
+                    "name": "Finance",
+                    "tables": [{ "name": "Sales", "measures": [{ "name": "Total" }] }]
+                }
+            ],
+            "cultures": [
+                {
+                    "name": "fr-FR",
+                    "translations": {
+                        "model": {
+                            "name": "Model",
+                            "tables": [
+                                {
+                                    "name": "Sales",
+                                    "translatedCaption": "Ventes",
+                                    "measures": [
+                                        { "name": "Total", "translatedCaption": "Total des ventes" }
+                                    ]
+                                }
+                            ]
+                        }
+                    }
+                }
+            ]
+        }
+    }"##;
+
+    #[test]
+    fn parse_roles_hierarchies_partitions_calculation_groups_and_cultures() {
+        let raw = parse_data_model_schema(FULL_MODEL_JSON.as_bytes()).expect("parse schema");
+
+        let measure = &raw.measures[0];
+        assert_eq!(measure.format_string.as_deref(), Some("#,0"));
+        assert_eq!(measure.display_folder.as_deref(), Some("KPIs"));
+        assert_eq!(
+            measure.description.as_deref(),
+            Some("Total sales\nbefore returns")
+        );
+        let kpi = measure.kpi.as_ref().expect("kpi");
+        assert_eq!(kpi.target_expression.as_deref(), Some("1000"));
+        assert_eq!(kpi.trend_expression, None);
+
+        let sales = &raw.tables[0];
+        assert_eq!(sales.name, "Sales");
+        assert_eq!(
+            sales.hierarchies[0].levels,
+            vec![
+                ("Country".to_string(), "Country".to_string()),
+                ("City".to_string(), "City".to_string()),
+            ]
+        );
+        let partition = &sales.partitions[0];
+        assert_eq!(partition.mode.as_deref(), Some("import"));
+        assert_eq!(partition.source_type.as_deref(), Some("m"));
+        assert!(partition
+            .source
+            .as_deref()
+            .is_some_and(|s| s.starts_with("let\n    Source = Sql.Database")));
+
+        let group = raw.tables[1]
+            .calculation_group
+            .as_ref()
+            .expect("calculation group");
+        assert_eq!(group.precedence, Some(10));
+        assert_eq!(group.items[0].name, "YTD");
+        assert_eq!(
+            group.items[0].format_string_expression.as_deref(),
+            Some("\"0.0\"")
+        );
+
+        let role = &raw.roles[0];
+        assert_eq!(role.model_permission.as_deref(), Some("read"));
+        assert_eq!(role.members, vec!["CONTOSO\\alice", "CONTOSO\\bob"]);
+        assert_eq!(
+            role.table_permissions[0].filter_expression.as_deref(),
+            Some("[Region] = \"East\"")
+        );
+        assert_eq!(
+            role.table_permissions[0].column_permissions,
+            vec![("Cost".to_string(), "none".to_string())]
+        );
+
+        assert_eq!(
+            raw.perspectives[0].members,
+            vec!["measure Sales[Total]", "table Sales"]
+        );
+        assert_eq!(
+            raw.cultures[0].translations,
+            vec![
+                (
+                    "measure Sales[Total] caption".to_string(),
+                    "Total des ventes".to_string()
+                ),
+                ("table Sales caption".to_string(), "Ventes".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "custom-json-schema")]
+    fn custom_json_schema_parser_matches_value_parser_for_full_model() {
+        let baseline = parse_data_model_schema_value(FULL_MODEL_JSON).expect("baseline parse");
+        let custom = parse_data_model_schema_custom(FULL_MODEL_JSON).expect("custom parse");
+        assert_eq!(baseline, custom);
+    }
+
     #[test]
     #[cfg(feature = "custom-json-schema")]
     fn custom_json_schema_parser_matches_value_parser() {
@@ -0,0 +1,197 @@
+//! Line-level text diffs grouped into unified-diff style hunks.
+//!
+//! Common leading and trailing lines are trimmed first; the remaining middle is aligned with an
+//! LCS table. Middles too large for the table are reported as one removal plus one insertion.
+
+use crate::diff::TextLineKind;
+
+/// Largest LCS table (old lines x new lines) built for the untrimmed middle.
+const MAX_LCS_CELLS: usize = 4_000_000;
+
+/// One step of a line edit script; indices are 0-based positions in the old and new lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LineEdit {
+    Equal(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+/// A hunk before its line text is interned. Starts are 1-based; a zero-length side starts at the
+/// line before the hunk, as in unified diffs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LineHunk {
+    pub old_start: u32,
+    pub old_len: u32,
+    pub new_start: u32,
+    pub new_len: u32,
+    pub edits: Vec<LineEdit>,
+}
+
+pub(crate) fn diff_lines(old: &[&str], new: &[&str]) -> Vec<LineEdit> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    let mut edits: Vec<LineEdit> = (0..prefix).map(|idx| LineEdit::Equal(idx, idx)).collect();
+    if old_mid.len().saturating_mul(new_mid.len()) > MAX_LCS_CELLS {
+        edits.extend((0..old_mid.len()).map(|idx| LineEdit::Delete(prefix + idx)));
+        edits.extend((0..new_mid.len()).map(|idx| LineEdit::Insert(prefix + idx)));
+    } else {
+        edits.extend(
+            lcs_edits(old_mid, new_mid)
+                .into_iter()
+                .map(|edit| match edit {
+                    LineEdit::Equal(a, b) => LineEdit::Equal(prefix + a, prefix + b),
+                    LineEdit::Delete(a) => LineEdit::Delete(prefix + a),
+                    LineEdit::Insert(b) => LineEdit::Insert(prefix + b),
+                }),
+        );
+    }
+    let (old_tail, new_tail) = (old.len() - suffix, new.len() - suffix);
+    edits.extend((0..suffix).map(|idx| LineEdit::Equal(old_tail + idx, new_tail + idx)));
+    edits
+}
+
+fn lcs_edits(old: &[&str], new: &[&str]) -> Vec<LineEdit> {
+    let (n, m) = (old.len(), new.len());
+    // lengths[i][j] = LCS length of old[i..] and new[j..].
+    let width = m + 1;
+    let mut lengths = vec![0u32; (n + 1) * width];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[i * width + j] = if old[i] == new[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut edits = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i] == new[j] {
+            edits.push(LineEdit::Equal(i, j));
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+            edits.push(LineEdit::Delete(i));
+            i += 1;
+        } else {
+            edits.push(LineEdit::Insert(j));
+            j += 1;
+        }
+    }
+    edits.extend((i..n).map(LineEdit::Delete));
+    edits.extend((j..m).map(LineEdit::Insert));
+    edits
+}
+
+/// Group an edit script into hunks with up to `context` unchanged lines around each change.
+pub(crate) fn group_hunks(edits: &[LineEdit], context: usize) -> Vec<LineHunk> {
+    let changed: Vec<usize> = edits
+        .iter()
+        .enumerate()
+        .filter(|(_, edit)| !matches!(edit, LineEdit::Equal(..)))
+        .map(|(idx, _)| idx)
+        .collect();
+
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for idx in changed {
+        let start = idx.saturating_sub(context);
+        let end = (idx + context + 1).min(edits.len());
+        match ranges.last_mut() {
+            Some((_, last_end)) if start <= *last_end => *last_end = end,
+            _ => ranges.push((start, end)),
+        }
+    }
+
+    ranges
+        .into_iter()
+        .map(|(start, end)| {
+            let slice = &edits[start..end];
+            // Position of the first line of each side at or after `start`.
+            let (mut old_pos, mut new_pos) = (0usize, 0usize);
+            for edit in &edits[..start] {
+                match edit {
+                    LineEdit::Equal(..) => {
+                        old_pos += 1;
+                        new_pos += 1;
+                    }
+                    LineEdit::Delete(_) => old_pos += 1,
+                    LineEdit::Insert(_) => new_pos += 1,
+                }
+            }
+            let old_len = slice
+                .iter()
+                .filter(|edit| !matches!(edit, LineEdit::Insert(_)))
+                .count();
+            let new_len = slice
+                .iter()
+                .filter(|edit| !matches!(edit, LineEdit::Delete(_)))
+                .count();
+            let first = |pos: usize, len: usize| if len == 0 { pos } else { pos + 1 };
+            LineHunk {
+                old_start: first(old_pos, old_len) as u32,
+                old_len: old_len as u32,
+                new_start: first(new_pos, new_len) as u32,
+                new_len: new_len as u32,
+                edits: slice.to_vec(),
+            }
+        })
+        .collect()
+}
+
+impl LineEdit {
+    pub(crate) fn kind(self) -> TextLineKind {
+        match self {
+            LineEdit::Equal(..) => TextLineKind::Context,
+            LineEdit::Delete(_) => TextLineKind::Removed,
+            LineEdit::Insert(_) => TextLineKind::Added,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hunks_carry_context_and_unified_line_numbers() {
+        let old = ["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"];
+        let new = ["a", "b", "c", "D", "e", "f", "g", "h", "i", "j", "k"];
+        let edits = diff_lines(&old, &new);
+        let hunks = group_hunks(&edits, 2);
+
+        assert_eq!(hunks.len(), 2);
+        assert_eq!(
+            (
+                hunks[0].old_start,
+                hunks[0].old_len,
+                hunks[0].new_start,
+                hunks[0].new_len
+            ),
+            (2, 5, 2, 5)
+        );
+        assert_eq!(
+            hunks[0].edits[2..4],
+            [LineEdit::Delete(3), LineEdit::Insert(3)]
+        );
+        assert_eq!(
+            (
+                hunks[1].old_start,
+                hunks[1].old_len,
+                hunks[1].new_start,
+                hunks[1].new_len
+            ),
+            (9, 2, 9, 3)
+        );
+        assert_eq!(hunks[1].edits.last(), Some(&LineEdit::Insert(10)));
+    }
+}
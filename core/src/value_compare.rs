@@ -0,0 +1,320 @@
+//! Value-equivalence rules for cell comparison.
+//!
+//! [`CellComparator`] resolves the configured [`ValueRules`] for one sheet. The engine uses it
+//! for row/column signatures as well as for deciding whether a cell changed, so alignment and
+//! `CellEdited` emission always agree on what "equal" means.
+//!
+//! Tolerances are not transitive, so signatures can only approximate them: numbers are hashed
+//! after rounding to a power of ten at or below the tolerance. Equal signatures therefore always
+//! mean equal cells, which lets the engine skip row pairs by signature. Two values that straddle
+//! a rounding boundary may still hash differently (their rows then align as edited rather than
+//! identical), but the final cell comparison uses the exact tolerance and will not report them.
+
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use crate::addressing::col_letters_to_index;
+use crate::config::{DiffConfig, ValueRules};
+use crate::hashing::{hash_cell_value, normalize_float_for_hash};
+use crate::string_pool::{StringId, StringPool};
+use crate::workbook::{Cell, CellValue};
+
+pub(crate) struct CellComparator<'a> {
+    pool: &'a StringPool,
+    default_rules: ValueRules,
+    column_rules: HashMap<u32, ValueRules>,
+    exact: bool,
+}
+
+impl<'a> CellComparator<'a> {
+    pub(crate) fn exact(pool: &'a StringPool) -> Self {
+        Self {
+            pool,
+            default_rules: ValueRules::default(),
+            column_rules: HashMap::new(),
+            exact: true,
+        }
+    }
+
+    pub(crate) fn for_sheet(pool: &'a StringPool, config: &DiffConfig, sheet: StringId) -> Self {
+        let comparison = &config.comparison;
+        if comparison.is_exact() {
+            return Self::exact(pool);
+        }
+
+        let sheet_name = pool.resolve(sheet).to_lowercase();
+        let mut default_rules = comparison.value_rules;
+        let mut column_rules = HashMap::new();
+        for rule in &comparison.value_rule_overrides {
+            if let Some(name) = &rule.sheet
+                && name.to_lowercase() != sheet_name
+            {
+                continue;
+            }
+            if rule.columns.is_empty() {
+                default_rules = rule.rules;
+                column_rules.clear();
+                continue;
+            }
+            for column in &rule.columns {
+                if let Some(col) = col_letters_to_index(column.trim()) {
+                    column_rules.insert(col, rule.rules);
+                }
+            }
+        }
+
+        let exact = default_rules.is_exact() && column_rules.values().all(ValueRules::is_exact);
+        Self {
+            pool,
+            default_rules,
+            column_rules,
+            exact,
+        }
+    }
+
+    pub(crate) fn is_exact(&self) -> bool {
+        self.exact
+    }
+
+    fn rules(&self, col: u32) -> &ValueRules {
+        self.column_rules.get(&col).unwrap_or(&self.default_rules)
+    }
+
+    /// Whether two cells in column `col` hold equivalent content under the sheet's rules.
+    pub(crate) fn cells_equal(&self, a: Option<&Cell>, b: Option<&Cell>, col: u32) -> bool {
+        if self.exact {
+            return match (a, b) {
+                (None, None) => true,
+                (Some(cell), None) | (None, Some(cell)) => {
+                    cell.value.is_none() && cell.formula.is_none()
+                }
+                (Some(a), Some(b)) => a.value == b.value && a.formula == b.formula,
+            };
+        }
+
+        let formula_a = a.and_then(|cell| cell.formula);
+        let formula_b = b.and_then(|cell| cell.formula);
+        if formula_a != formula_b {
+            return false;
+        }
+        let value_a = a.and_then(|cell| cell.value.as_ref());
+        let value_b = b.and_then(|cell| cell.value.as_ref());
+        self.values_equal(value_a, value_b, self.rules(col))
+    }
+
+    fn values_equal(
+        &self,
+        a: Option<&CellValue>,
+        b: Option<&CellValue>,
+        rules: &ValueRules,
+    ) -> bool {
+        if rules.blank_equals_empty_text {
+            match (self.is_empty_value(a, rules), self.is_empty_value(b, rules)) {
+                (true, true) => return true,
+                (true, false) | (false, true) => return false,
+                (false, false) => {}
+            }
+        }
+
+        match (a, b) {
+            (Some(CellValue::Number(x)), Some(CellValue::Number(y))) => {
+                numbers_equal(*x, *y, rules)
+            }
+            (Some(CellValue::Text(x)), Some(CellValue::Text(y))) => {
+                x == y
+                    || ((rules.ignore_text_case || rules.trim_text_whitespace)
+                        && normalized_chars(self.pool.resolve(*x), rules)
+                            .eq(normalized_chars(self.pool.resolve(*y), rules)))
+            }
+            _ => a == b,
+        }
+    }
+
+    fn is_empty_value(&self, value: Option<&CellValue>, rules: &ValueRules) -> bool {
+        match value {
+            None | Some(CellValue::Blank) => true,
+            Some(CellValue::Text(id)) => normalized_text(self.pool.resolve(*id), rules).is_empty(),
+            Some(_) => false,
+        }
+    }
+
+    /// Whether the cell is indistinguishable from an absent cell under the sheet's rules.
+    /// Such cells are left out of signatures so they cannot split otherwise identical rows.
+    pub(crate) fn is_absent_equivalent(&self, cell: &Cell, col: u32) -> bool {
+        if cell.formula.is_some() {
+            return false;
+        }
+        match &cell.value {
+            None => true,
+            value => {
+                let rules = self.rules(col);
+                rules.blank_equals_empty_text && self.is_empty_value(value.as_ref(), rules)
+            }
+        }
+    }
+
+    /// Feeds the cell's canonical content into `state`; equivalent cells hash identically
+    /// (up to the tolerance rounding described in the module docs).
+    pub(crate) fn hash_cell<H: Hasher>(&self, cell: &Cell, col: u32, state: &mut H) {
+        if self.exact {
+            hash_cell_value(&cell.value, state);
+            cell.formula.hash(state);
+            return;
+        }
+
+        let rules = self.rules(col);
+        match &cell.value {
+            value
+                if rules.blank_equals_empty_text && self.is_empty_value(value.as_ref(), rules) =>
+            {
+                3u8.hash(state);
+            }
+            Some(CellValue::Number(n)) => {
+                0u8.hash(state);
+                quantize_for_hash(*n, rules).hash(state);
+            }
+            Some(CellValue::Text(id)) if rules.ignore_text_case || rules.trim_text_whitespace => {
+                1u8.hash(state);
+                for ch in normalized_chars(self.pool.resolve(*id), rules) {
+                    ch.hash(state);
+                }
+            }
+            value => hash_cell_value(value, state),
+        }
+        cell.formula.hash(state);
+    }
+}
+
+fn numbers_equal(a: f64, b: f64, rules: &ValueRules) -> bool {
+    if CellValue::Number(a) == CellValue::Number(b) {
+        return true;
+    }
+    if !a.is_finite() || !b.is_finite() {
+        return false;
+    }
+    let diff = (a - b).abs();
+    diff <= rules.numeric_abs_tolerance
+        || diff <= rules.numeric_rel_tolerance * a.abs().max(b.abs())
+}
+
+fn quantize_for_hash(n: f64, rules: &ValueRules) -> u64 {
+    let step = rules
+        .numeric_abs_tolerance
+        .max(rules.numeric_rel_tolerance * n.abs());
+    if step <= 0.0 || !n.is_finite() {
+        return normalize_float_for_hash(n);
+    }
+    let unit = 10f64.powi(step.log10().floor() as i32);
+    normalize_float_for_hash((n / unit).round() * unit)
+}
+
+fn normalized_text<'s>(text: &'s str, rules: &ValueRules) -> &'s str {
+    if rules.trim_text_whitespace {
+        text.trim()
+    } else {
+        text
+    }
+}
+
+fn normalized_chars<'s>(text: &'s str, rules: &ValueRules) -> Box<dyn Iterator<Item = char> + 's> {
+    let text = normalized_text(text, rules);
+    if rules.ignore_text_case {
+        Box::new(text.chars().flat_map(char::to_lowercase))
+    } else {
+        Box::new(text.chars())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ValueRuleOverride, ValueRules};
+
+    fn number(n: f64) -> Cell {
+        Cell {
+            value: Some(CellValue::Number(n)),
+            formula: None,
+        }
+    }
+
+    fn text(pool: &mut StringPool, s: &str) -> Cell {
+        Cell {
+            value: Some(CellValue::Text(pool.intern(s))),
+            formula: None,
+        }
+    }
+
+    fn hash_of(cmp: &CellComparator<'_>, cell: &Cell, col: u32) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        cmp.hash_cell(cell, col, &mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn tolerance_and_text_rules_make_cells_equal_and_hash_alike() {
+        let mut pool = StringPool::new();
+        let sheet = pool.intern("Sheet1");
+        let mut config = DiffConfig::default();
+        config.comparison.value_rules = ValueRules {
+            numeric_abs_tolerance: 1e-9,
+            ignore_text_case: true,
+            trim_text_whitespace: true,
+            blank_equals_empty_text: true,
+            ..ValueRules::default()
+        };
+        let upper = text(&mut pool, " Total ");
+        let lower = text(&mut pool, "total");
+        let empty = text(&mut pool, "  ");
+        let cmp = CellComparator::for_sheet(&pool, &config, sheet);
+
+        let a = number(0.1 + 0.2);
+        let b = number(0.3000000000042);
+        assert!(cmp.cells_equal(Some(&a), Some(&b), 0));
+        assert_eq!(hash_of(&cmp, &a, 0), hash_of(&cmp, &b, 0));
+        assert!(!cmp.cells_equal(Some(&a), Some(&number(0.31)), 0));
+
+        assert!(cmp.cells_equal(Some(&upper), Some(&lower), 0));
+        assert_eq!(hash_of(&cmp, &upper, 0), hash_of(&cmp, &lower, 0));
+
+        assert!(cmp.cells_equal(Some(&empty), None, 0));
+        assert!(cmp.is_absent_equivalent(&empty, 0));
+        assert!(!cmp.cells_equal(Some(&empty), Some(&lower), 0));
+    }
+
+    #[test]
+    fn later_overrides_win_for_their_sheet_and_columns() {
+        let mut pool = StringPool::new();
+        let sheet = pool.intern("Prices");
+        let other = pool.intern("Notes");
+        let mut config = DiffConfig::default();
+        let loose = ValueRules {
+            numeric_rel_tolerance: 0.01,
+            ..ValueRules::default()
+        };
+        config.comparison.value_rule_overrides = vec![
+            ValueRuleOverride {
+                sheet: Some("prices".to_string()),
+                columns: vec!["B".to_string(), "C".to_string()],
+                rules: loose,
+            },
+            ValueRuleOverride {
+                sheet: Some("Prices".to_string()),
+                columns: vec!["C".to_string()],
+                rules: ValueRules::default(),
+            },
+        ];
+
+        let a = number(100.0);
+        let b = number(100.5);
+
+        let cmp = CellComparator::for_sheet(&pool, &config, sheet);
+        assert!(!cmp.is_exact());
+        assert!(!cmp.cells_equal(Some(&a), Some(&b), 0));
+        assert!(cmp.cells_equal(Some(&a), Some(&b), 1));
+        assert!(!cmp.cells_equal(Some(&a), Some(&b), 2));
+
+        let cmp = CellComparator::for_sheet(&pool, &config, other);
+        assert!(cmp.is_exact());
+        assert!(!cmp.cells_equal(Some(&a), Some(&b), 1));
+    }
+}
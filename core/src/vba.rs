@@ -1,7 +1,8 @@
 use crate::string_pool::StringId;
 
 /// The kind of VBA module contained in an `.xlsm` workbook.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum VbaModuleType {
     /// A standard module (e.g., `Module1`).
     Standard,
@@ -23,3 +24,433 @@ pub struct VbaModule {
     /// Raw module source code.
     pub code: String,
 }
+
+/// The kind of a VBA procedure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VbaProcedureKind {
+    Sub,
+    Function,
+    PropertyGet,
+    PropertyLet,
+    PropertySet,
+}
+
+/// A `Sub`, `Function` or `Property` in module source, by 0-based code line (attribute lines
+/// excluded) from its declaration to its `End` line inclusive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct VbaProcedure {
+    pub name: String,
+    pub kind: VbaProcedureKind,
+    pub lines: std::ops::Range<usize>,
+}
+
+/// Split module source into `Attribute` lines, as `(name, value)`, and the remaining code lines.
+/// Line endings are normalised.
+pub(crate) fn split_module_source(code: &str) -> (Vec<(String, String)>, Vec<String>) {
+    let mut attributes = Vec::new();
+    let mut lines = Vec::new();
+    for line in code.replace("\r\n", "\n").replace('\r', "\n").lines() {
+        match parse_attribute(line) {
+            Some(attribute) => attributes.push(attribute),
+            None => lines.push(line.to_string()),
+        }
+    }
+    (attributes, lines)
+}
+
+fn parse_attribute(line: &str) -> Option<(String, String)> {
+    let rest = strip_keyword(line, "Attribute")?;
+    let (name, value) = rest.split_once('=')?;
+    Some((name.trim().to_string(), value.trim().to_string()))
+}
+
+/// Strip a leading case-insensitive keyword followed by whitespace.
+fn strip_keyword<'a>(text: &'a str, keyword: &str) -> Option<&'a str> {
+    let head = text.get(..keyword.len())?;
+    let rest = &text[keyword.len()..];
+    (head.eq_ignore_ascii_case(keyword) && rest.starts_with(char::is_whitespace))
+        .then(|| rest.trim_start())
+}
+
+/// Procedures declared in `lines`. A declaration without a matching `End` runs to the end.
+pub(crate) fn find_procedures(lines: &[String]) -> Vec<VbaProcedure> {
+    let mut procedures = Vec::new();
+    let mut open: Option<(String, VbaProcedureKind, usize)> = None;
+    for (idx, line) in lines.iter().enumerate() {
+        let line = line.trim();
+        if let Some((name, kind, start)) = open.take() {
+            if is_procedure_end(line, kind) {
+                procedures.push(VbaProcedure {
+                    name,
+                    kind,
+                    lines: start..idx + 1,
+                });
+            } else {
+                open = Some((name, kind, start));
+            }
+            continue;
+        }
+        if let Some((name, kind)) = parse_declaration(line) {
+            open = Some((name, kind, idx));
+        }
+    }
+    if let Some((name, kind, start)) = open {
+        procedures.push(VbaProcedure {
+            name,
+            kind,
+            lines: start..lines.len(),
+        });
+    }
+    procedures
+}
+
+fn parse_declaration(line: &str) -> Option<(String, VbaProcedureKind)> {
+    let mut rest = line;
+    for modifier in ["Public", "Private", "Friend"] {
+        if let Some(stripped) = strip_keyword(rest, modifier) {
+            rest = stripped;
+            break;
+        }
+    }
+    if let Some(stripped) = strip_keyword(rest, "Static") {
+        rest = stripped;
+    }
+    let (kind, rest) = if let Some(rest) = strip_keyword(rest, "Sub") {
+        (VbaProcedureKind::Sub, rest)
+    } else if let Some(rest) = strip_keyword(rest, "Function") {
+        (VbaProcedureKind::Function, rest)
+    } else {
+        let rest = strip_keyword(rest, "Property")?;
+        if let Some(rest) = strip_keyword(rest, "Get") {
+            (VbaProcedureKind::PropertyGet, rest)
+        } else if let Some(rest) = strip_keyword(rest, "Let") {
+            (VbaProcedureKind::PropertyLet, rest)
+        } else {
+            (VbaProcedureKind::PropertySet, strip_keyword(rest, "Set")?)
+        }
+    };
+    let name: String = rest
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_')
+        .collect();
+    (!name.is_empty()).then_some((name, kind))
+}
+
+fn is_procedure_end(line: &str, kind: VbaProcedureKind) -> bool {
+    let keyword = match kind {
+        VbaProcedureKind::Sub => "Sub",
+        VbaProcedureKind::Function => "Function",
+        _ => "Property",
+    };
+    strip_keyword(line, "End").is_some_and(|rest| {
+        rest.get(..keyword.len())
+            .is_some_and(|head| head.eq_ignore_ascii_case(keyword))
+            && rest[keyword.len()..]
+                .chars()
+                .next()
+                .is_none_or(|c| c.is_whitespace() || c == '\'')
+    })
+}
+
+/// Project-level metadata from `vbaProject.bin`: properties and references from the `dir`
+/// stream, protection from the `PROJECT` stream.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VbaProject {
+    pub name: String,
+    pub description: String,
+    pub help_file: String,
+    pub help_context: u32,
+    /// Conditional compilation constants, e.g. `DEBUG = 1 : TRACE = 0`.
+    pub constants: String,
+    pub references: Vec<VbaReference>,
+    /// "Lock project for viewing" is set.
+    pub locked_for_viewing: bool,
+    /// A project password is set.
+    pub has_password: bool,
+}
+
+/// A type library or VBA project referenced by the project.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VbaReference {
+    pub name: String,
+    /// Type library libid (`*\G{guid}#version#lcid#path#description`) or, for a reference to
+    /// another VBA project, its absolute path libid.
+    pub libid: String,
+}
+
+/// Parse the decompressed `VBA/dir` stream and the `PROJECT` stream (MS-OVBA 2.3.4.2, 2.3.1).
+/// Unknown or truncated records end parsing with whatever was read so far.
+#[cfg_attr(not(feature = "vba"), allow(dead_code))]
+pub(crate) fn parse_project_metadata(dir: &[u8], project_stream: &[u8]) -> VbaProject {
+    let mut project = VbaProject::default();
+    parse_dir_stream(dir, &mut project);
+
+    for line in String::from_utf8_lossy(project_stream).lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            break;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim().trim_matches('"');
+        match key.trim() {
+            // 0xFF when visible; 0x00 when locked for viewing.
+            "GC" => {
+                project.locked_for_viewing =
+                    decrypt_project_value(value).is_some_and(|data| data.first() == Some(&0))
+            }
+            // A single 0x00 byte when no password is set.
+            "DPB" => {
+                project.has_password =
+                    decrypt_project_value(value).is_some_and(|data| data.len() > 1)
+            }
+            _ => {}
+        }
+    }
+    project
+}
+
+fn parse_dir_stream(dir: &[u8], project: &mut VbaProject) {
+    let u32_at = |data: &[u8], pos: usize| -> Option<u32> {
+        Some(u32::from_le_bytes(data.get(pos..pos + 4)?.try_into().ok()?))
+    };
+    // Libid prefixed by its u32 length, as in REFERENCEREGISTERED and REFERENCEPROJECT.
+    let sized_text = |data: &[u8]| -> Option<String> {
+        let len = u32_at(data, 0)? as usize;
+        Some(decode_mbcs(data.get(4..4 + len)?))
+    };
+
+    let mut pending_name: Option<String> = None;
+    let mut awaiting_control = false;
+    let mut pos = 0;
+    while let (Some(id), Some(size)) = (
+        dir.get(pos..pos + 2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]])),
+        u32_at(dir, pos + 2),
+    ) {
+        pos += 6;
+        // PROJECTVERSION: the size field is a reserved 4, followed by six bytes of version.
+        if id == 0x0009 {
+            pos += 6;
+            continue;
+        }
+        let Some(data) = dir.get(pos..pos + size as usize) else {
+            break;
+        };
+        pos += size as usize;
+
+        let mut push_reference = |libid: String| {
+            let name = pending_name.take().unwrap_or_default();
+            project.references.push(VbaReference { name, libid });
+        };
+        match id {
+            0x0004 => project.name = decode_mbcs(data),
+            0x0005 => project.description = decode_mbcs(data),
+            0x0040 => project.description = decode_utf16(data),
+            0x0006 => project.help_file = decode_mbcs(data),
+            0x0007 => project.help_context = u32_at(data, 0).unwrap_or(0),
+            0x000C => project.constants = decode_mbcs(data),
+            0x003C => project.constants = decode_utf16(data),
+            0x0016 => pending_name = Some(decode_mbcs(data)),
+            0x003E => pending_name = Some(decode_utf16(data)),
+            // REFERENCEORIGINAL names the original type library; the REFERENCECONTROL that
+            // follows describes the same reference.
+            0x0033 => {
+                push_reference(decode_mbcs(data));
+                awaiting_control = true;
+            }
+            0x002F => {
+                if !std::mem::take(&mut awaiting_control) {
+                    push_reference(sized_text(data).unwrap_or_default());
+                }
+            }
+            // End of REFERENCECONTROL's extended part, which may carry its own name record.
+            0x0030 => pending_name = None,
+            0x000D | 0x000E => push_reference(sized_text(data).unwrap_or_default()),
+            // PROJECTMODULES: references are complete.
+            0x000F => break,
+            _ => {}
+        }
+    }
+}
+
+fn decode_mbcs(data: &[u8]) -> String {
+    String::from_utf8_lossy(data).into_owned()
+}
+
+fn decode_utf16(data: &[u8]) -> String {
+    let units: Vec<u16> = data
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+/// Reverse the data encryption (MS-OVBA 2.4.3) used by the `CMG`, `DPB` and `GC` entries.
+fn decrypt_project_value(hex: &str) -> Option<Vec<u8>> {
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|idx| u8::from_str_radix(hex.get(idx..idx + 2)?, 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+    let [seed, version_enc, key_enc, rest @ ..] = bytes.as_slice() else {
+        return None;
+    };
+    let mut unencrypted_1 = seed ^ key_enc;
+    let mut encrypted_1 = *key_enc;
+    let mut encrypted_2 = *version_enc;
+    let mut decoded = rest.iter().map(|&byte_enc| {
+        let byte = byte_enc ^ encrypted_2.wrapping_add(unencrypted_1);
+        encrypted_2 = encrypted_1;
+        encrypted_1 = byte_enc;
+        unencrypted_1 = byte;
+        byte
+    });
+
+    let ignored = ((seed & 6) / 2) as usize;
+    decoded.by_ref().take(ignored).for_each(drop);
+    let len_bytes: Vec<u8> = decoded.by_ref().take(4).collect();
+    let len = u32::from_le_bytes(len_bytes.try_into().ok()?) as usize;
+    let data: Vec<u8> = decoded.take(len).collect();
+    (data.len() == len).then_some(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_attributes_and_finds_procedures() {
+        let code = concat!(
+            "Attribute VB_Name = \"Module1\"\r\n",
+            "Attribute VB_PredeclaredId = True\r\n",
+            "Option Explicit\r\n",
+            "\r\n",
+            "Public Sub Run()\r\n",
+            "    Total = 1\r\n",
+            "End Sub\r\n",
+            "Private Static Function Total() As Long\r\n",
+            "End Function\r\n",
+            "Property Get Name() As String\r\n",
+            "  Name = \"x\" ' End Sub\r\n",
+            "End Property\r\n",
+        );
+        let (attributes, lines) = split_module_source(code);
+        assert_eq!(
+            attributes,
+            vec![
+                ("VB_Name".to_string(), "\"Module1\"".to_string()),
+                ("VB_PredeclaredId".to_string(), "True".to_string()),
+            ]
+        );
+        assert_eq!(lines[0], "Option Explicit");
+
+        let procedures: Vec<(String, VbaProcedureKind, std::ops::Range<usize>)> =
+            find_procedures(&lines)
+                .into_iter()
+                .map(|p| (p.name, p.kind, p.lines))
+                .collect();
+        assert_eq!(
+            procedures,
+            vec![
+                ("Run".to_string(), VbaProcedureKind::Sub, 2..5),
+                ("Total".to_string(), VbaProcedureKind::Function, 5..7),
+                ("Name".to_string(), VbaProcedureKind::PropertyGet, 7..10),
+            ]
+        );
+    }
+
+    fn record(id: u16, data: &[u8]) -> Vec<u8> {
+        let mut out = id.to_le_bytes().to_vec();
+        out.extend((data.len() as u32).to_le_bytes());
+        out.extend(data);
+        out
+    }
+
+    fn sized(text: &str) -> Vec<u8> {
+        let mut out = (text.len() as u32).to_le_bytes().to_vec();
+        out.extend(text.as_bytes());
+        out.extend([0u8; 6]);
+        out
+    }
+
+    fn encrypt(seed: u8, data: &[u8]) -> String {
+        let key: u8 = 0x5A;
+        let (version_enc, key_enc) = (seed ^ 2, seed ^ key);
+        let (mut unencrypted_1, mut encrypted_1, mut encrypted_2) = (key, key_enc, version_enc);
+        let mut plain = vec![0u8; ((seed & 6) / 2) as usize];
+        plain.extend((data.len() as u32).to_le_bytes());
+        plain.extend(data);
+        let mut out = vec![seed, version_enc, key_enc];
+        for byte in plain {
+            let byte_enc = byte ^ encrypted_2.wrapping_add(unencrypted_1);
+            encrypted_2 = encrypted_1;
+            encrypted_1 = byte_enc;
+            unencrypted_1 = byte;
+            out.push(byte_enc);
+        }
+        out.iter().map(|b| format!("{b:02X}")).collect()
+    }
+
+    #[test]
+    fn parses_project_properties_references_and_protection() {
+        let scripting = concat!(
+            r"*\G{420B2830-E718-11CF-893D-00A0C9054228}#1.0#0#",
+            r"C:\Windows\System32\scrrun.dll#Microsoft Scripting Runtime"
+        );
+        let mut dir = record(0x0001, &1u32.to_le_bytes());
+        dir.extend(record(0x0004, b"Budget"));
+        dir.extend(record(0x0005, b"?"));
+        dir.extend(record(0x0040, &[0x42, 0, 0xE9, 0]));
+        dir.extend([0x09, 0x00, 0x04, 0, 0, 0, 0x5A, 0, 0, 0, 0x10, 0]);
+        dir.extend(record(0x000C, b"DEBUG = 1"));
+        dir.extend(record(0x0016, b"Scripting"));
+        dir.extend(record(0x000D, &sized(scripting)));
+        dir.extend(record(0x0016, b"MSForms"));
+        dir.extend(record(0x0033, br"*\G{0D452EE1}#2.0#0#FM20.DLL#Forms"));
+        dir.extend(record(0x002F, &sized(r"*\G{7B020EC7}#2.0#0#twiddled")));
+        dir.extend(record(0x0016, b"extended"));
+        dir.extend(record(0x0030, &[0u8; 8]));
+        dir.extend(record(0x000F, &2u16.to_le_bytes()));
+        dir.extend(record(0x0016, b"not a reference"));
+
+        let project_stream = format!(
+            concat!(
+                "ID=\"{{00000000-0000-0000-0000-000000000000}}\"\r\n",
+                "Name=\"Budget\"\r\nDPB=\"{}\"\r\nGC=\"{}\"\r\n\r\n[Host Extender Info]\r\n"
+            ),
+            encrypt(0x0B, &[0u8; 29]),
+            encrypt(0x04, &[0x00]),
+        );
+
+        let project = parse_project_metadata(&dir, project_stream.as_bytes());
+        assert_eq!(project.name, "Budget");
+        assert_eq!(project.description, "Bé");
+        assert_eq!(project.constants, "DEBUG = 1");
+        assert_eq!(
+            project.references,
+            vec![
+                VbaReference {
+                    name: "Scripting".to_string(),
+                    libid: scripting.to_string(),
+                },
+                VbaReference {
+                    name: "MSForms".to_string(),
+                    libid: r"*\G{0D452EE1}#2.0#0#FM20.DLL#Forms".to_string(),
+                },
+            ]
+        );
+        assert!(project.locked_for_viewing);
+        assert!(project.has_password);
+
+        let unlocked = format!(
+            "DPB=\"{}\"\r\nGC=\"{}\"\r\n",
+            encrypt(0x02, &[0]),
+            encrypt(0x00, &[0xFF])
+        );
+        let project = parse_project_metadata(&[], unlocked.as_bytes());
+        assert!(!project.locked_for_viewing);
+        assert!(!project.has_password);
+    }
+}
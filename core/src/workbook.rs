@@ -12,6 +12,7 @@ use crate::string_pool::{StringId, StringPool};
 use rustc_hash::FxHashMap;
 use serde::de::Error as DeError;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
 use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 
@@ -50,6 +51,402 @@ pub struct Workbook {
     pub sheets: Vec<Sheet>,
     pub named_ranges: Vec<NamedRange>,
     pub charts: Vec<ChartObject>,
+    /// Resolved cell formats (`cellXfs`), indexed by a cell's `s` attribute.
+    pub cell_styles: Vec<CellStyle>,
+    /// Per-sheet style indices for cells that use a non-default format.
+    pub sheet_cell_styles: Vec<SheetCellStyles>,
+    /// Per-sheet notes and threaded comments, keyed by anchor cell.
+    pub sheet_comments: Vec<SheetComments>,
+    /// Per-sheet data validation and conditional formatting rules.
+    pub sheet_rules: Vec<SheetRules>,
+    /// Excel tables (ListObjects) across all sheets, in sheet order.
+    pub tables: Vec<ExcelTable>,
+    /// Per-sheet shapes, pictures, text boxes and form controls. Charts are in `charts`.
+    pub sheet_drawings: Vec<SheetDrawings>,
+    /// Pivot tables across all sheets, in sheet order.
+    pub pivot_tables: Vec<PivotTable>,
+    /// Links to other workbooks, in `<externalReferences>` order.
+    pub external_links: Vec<ExternalLink>,
+    /// Data connections from `xl/connections.xml`.
+    pub connections: Vec<DataConnection>,
+    /// Calculation options, workbook protection and document properties.
+    pub settings: WorkbookSettings,
+}
+
+/// The effective formatting of a cell, flattened from `xl/styles.xml`.
+///
+/// Each facet is a canonical descriptor string (e.g. `"0.00%"` for the number format or
+/// `"bold;sz=11;color=rgb:FFFF0000;name=Calibri"` for the font) so that two workbooks with
+/// differently ordered style tables still compare equal when the visible format is the same.
+/// `None` means the facet is unset and Excel's default applies.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct CellStyle {
+    pub number_format: Option<StringId>,
+    pub font: Option<StringId>,
+    pub fill: Option<StringId>,
+    pub border: Option<StringId>,
+    pub alignment: Option<StringId>,
+    pub protection: Option<StringId>,
+}
+
+/// Style assignments for one sheet: `(row, col)` to an index into [`Workbook::cell_styles`].
+///
+/// Cells that are absent use style index 0 (the workbook's default format).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SheetCellStyles {
+    pub sheet: StringId,
+    /// Optional workbook-internal sheet id for rename-safe matching.
+    pub workbook_sheet_id: Option<u32>,
+    pub cells: BTreeMap<(u32, u32), u32>,
+}
+
+/// Whether a comment came from a legacy note or a threaded comment conversation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommentKind {
+    Note,
+    Threaded,
+}
+
+/// A note (`xl/comments*.xml`) or threaded comment (`xl/threadedComments/*.xml`) on one cell.
+///
+/// When a cell carries both, the threaded comment wins: Excel writes a placeholder note next to
+/// every threaded comment for older clients.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CellComment {
+    pub kind: CommentKind,
+    pub author: Option<StringId>,
+    pub text: StringId,
+    /// Threaded comments only; notes are never resolved.
+    #[serde(default)]
+    pub resolved: bool,
+    /// Threaded replies in document order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub replies: Vec<CommentReply>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CommentReply {
+    pub author: Option<StringId>,
+    pub text: StringId,
+}
+
+/// Comments for one sheet, keyed by `(row, col)` of the anchor cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SheetComments {
+    pub sheet: StringId,
+    /// Optional workbook-internal sheet id for rename-safe matching.
+    pub workbook_sheet_id: Option<u32>,
+    pub comments: BTreeMap<(u32, u32), CellComment>,
+}
+
+/// Drawing objects for one sheet, in drawing order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SheetDrawings {
+    pub sheet: StringId,
+    /// Optional workbook-internal sheet id for rename-safe matching.
+    pub workbook_sheet_id: Option<u32>,
+    pub objects: Vec<DrawingObject>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DrawingObjectKind {
+    Shape,
+    TextBox,
+    Picture,
+    Connector,
+    Group,
+    /// A Forms control (button, check box, ...) drawn as a shape.
+    FormControl,
+}
+
+/// A shape, picture, text box or form control anchored on a sheet (`xl/drawings/drawing*.xml`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DrawingObject {
+    /// `cNvPr/@id`, unique within the drawing part.
+    pub id: u32,
+    pub kind: DrawingObjectKind,
+    pub name: StringId,
+    pub anchor: DrawingAnchor,
+    /// Text body, paragraphs separated by `\n`.
+    pub text: Option<StringId>,
+    /// Assigned macro as written, e.g. `[0]!Button1_Click`.
+    #[serde(rename = "macro")]
+    pub macro_name: Option<StringId>,
+    /// XXH3 hash of the picture's image part.
+    pub image_hash: Option<u64>,
+}
+
+/// Where a drawing object sits. Two-cell anchors have both markers; one-cell anchors only
+/// `from`. Absolute anchors use a zero cell with the position in the offsets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DrawingAnchor {
+    pub from: DrawingMarker,
+    pub to: Option<DrawingMarker>,
+}
+
+/// A cell corner plus an offset into that cell in EMU (1/914400 inch).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DrawingMarker {
+    pub row: u32,
+    pub col: u32,
+    pub row_offset: i64,
+    pub col_offset: i64,
+}
+
+/// A data validation rule (`<dataValidation>`), covering every range in its `sqref`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DataValidationRule {
+    pub ranges: Vec<CellRange>,
+    /// The `type` attribute (`list`, `whole`, `decimal`, `date`, `custom`, ...); `none` when
+    /// absent.
+    pub kind: StringId,
+    pub operator: Option<StringId>,
+    /// For `list` rules this is the list source: a quoted literal list or a range reference.
+    pub formula1: Option<StringId>,
+    pub formula2: Option<StringId>,
+    #[serde(default)]
+    pub allow_blank: bool,
+}
+
+/// One `<cfRule>` together with the ranges of its enclosing `<conditionalFormatting>` block.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ConditionalFormatRule {
+    pub ranges: Vec<CellRange>,
+    /// The `type` attribute (`cellIs`, `expression`, `colorScale`, `containsText`, ...).
+    pub kind: StringId,
+    pub operator: Option<StringId>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub formulas: Vec<StringId>,
+    pub priority: Option<u32>,
+    #[serde(default)]
+    pub stop_if_true: bool,
+}
+
+/// Validation and conditional formatting rules for one sheet, in document order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SheetRules {
+    pub sheet: StringId,
+    /// Optional workbook-internal sheet id for rename-safe matching.
+    pub workbook_sheet_id: Option<u32>,
+    pub data_validations: Vec<DataValidationRule>,
+    pub conditional_formats: Vec<ConditionalFormatRule>,
+}
+
+/// An Excel table (ListObject) from `xl/tables/table*.xml`.
+///
+/// `range` covers the header row, data rows and totals row. Table names are unique across the
+/// workbook; `id` survives renames.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExcelTable {
+    pub sheet: StringId,
+    /// Optional workbook-internal sheet id for rename-safe matching.
+    pub workbook_sheet_id: Option<u32>,
+    pub id: u32,
+    /// The `displayName` used in structured references.
+    pub name: StringId,
+    pub range: CellRange,
+    pub header_row_count: u32,
+    pub totals_row_count: u32,
+    pub columns: Vec<ExcelTableColumn>,
+}
+
+impl ExcelTable {
+    /// Rows holding table data, excluding the header and totals rows.
+    pub fn data_rows(&self) -> std::ops::Range<u32> {
+        let start = self.range.start.row + self.header_row_count;
+        let end = (self.range.end.row + 1).saturating_sub(self.totals_row_count);
+        start..end.max(start)
+    }
+}
+
+/// A column of an [`ExcelTable`], in table order. `id` survives renames.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExcelTableColumn {
+    pub id: u32,
+    pub name: StringId,
+}
+
+/// A pivot table (`xl/pivotTables/pivotTable*.xml`) with the source of its pivot cache.
+///
+/// Fields are named from the cache (`cacheField/@name`); the values pseudo-field in the row or
+/// column area is named `Values`. Pivot names are unique per sheet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PivotTable {
+    pub sheet: StringId,
+    /// Optional workbook-internal sheet id for rename-safe matching.
+    pub workbook_sheet_id: Option<u32>,
+    pub name: StringId,
+    /// Range the pivot occupies on its sheet, excluding page fields.
+    pub location: Option<CellRange>,
+    pub source: PivotSource,
+    pub row_fields: Vec<StringId>,
+    pub column_fields: Vec<StringId>,
+    pub page_fields: Vec<PivotPageField>,
+    pub data_fields: Vec<PivotDataField>,
+    pub filters: Vec<PivotFilter>,
+    /// Calculated fields of the pivot cache.
+    pub calculated_fields: Vec<PivotCalculatedField>,
+}
+
+/// Where a pivot cache reads its data (`cacheSource`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PivotSource {
+    /// `worksheet`, `external`, `consolidation` or `scenario`.
+    pub kind: StringId,
+    pub sheet: Option<StringId>,
+    pub range: Option<CellRange>,
+    /// Table or defined name used instead of a range.
+    pub name: Option<StringId>,
+    /// Workbook connection id for external sources.
+    pub connection_id: Option<u32>,
+}
+
+/// A field in the report filter (page) area and its selected item; `None` shows all items.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PivotPageField {
+    pub field: StringId,
+    pub item: Option<StringId>,
+}
+
+/// A field in the values area.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PivotDataField {
+    /// Caption, e.g. `Sum of Sales`.
+    pub name: StringId,
+    pub field: StringId,
+    /// Aggregation (`sum`, `count`, `average`, ...).
+    pub function: StringId,
+    /// `showDataAs`, e.g. `percentOfTotal`.
+    pub show_data_as: Option<StringId>,
+}
+
+/// An item filter on one field: `manual` with the hidden items, or a label, value or top-N
+/// filter type (`captionContains`, `valueGreaterThan`, `count`, ...) with its arguments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PivotFilter {
+    pub field: StringId,
+    pub kind: StringId,
+    pub values: Vec<StringId>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PivotCalculatedField {
+    pub name: StringId,
+    pub formula: StringId,
+}
+
+/// A link to another workbook, or to a DDE or OLE source, from an `xl/externalLinks` part.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExternalLink {
+    /// 1-based position in `<externalReferences>`: the `[n]` that formulas use for this link.
+    pub index: u32,
+    /// `externalBook`, `ddeLink` or `oleLink`.
+    pub kind: StringId,
+    /// Target as written in the part's relationship, e.g. `file:///\\share\Q3.xlsx`. DDE
+    /// links use `service|topic`.
+    pub target: StringId,
+    /// Sheet names Excel cached from the linked workbook.
+    pub sheet_names: Vec<StringId>,
+}
+
+/// A workbook data connection (`<connection>` in `xl/connections.xml`).
+///
+/// Secret values in `connection` (passwords, keys, tokens, signatures) are replaced with `***`
+/// when the part is read, so they never reach the string pool or a report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataConnection {
+    pub id: u32,
+    pub name: StringId,
+    /// `odbc`, `dao`, `file`, `web`, `oledb`, `text`, `ado` or `dsp`; the raw number otherwise.
+    pub kind: StringId,
+    /// Connection string, web query URL or source file, redacted.
+    pub connection: Option<StringId>,
+    /// Command text, e.g. a SQL statement or table name.
+    pub command: Option<StringId>,
+    pub refresh_on_load: bool,
+    pub background_refresh: bool,
+    /// Minutes between automatic refreshes; 0 when periodic refresh is off.
+    pub refresh_interval: u32,
+    pub save_password: bool,
+}
+
+/// Workbook-wide settings from `workbook.xml` and the `docProps` parts.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkbookSettings {
+    /// `date1904` on `<workbookPr>`: serial dates count from 1904-01-01.
+    pub date1904: bool,
+    /// `codeName` on `<workbookPr>`, the VBA name of the workbook object.
+    pub code_name: Option<StringId>,
+    pub calculation: CalcSettings,
+    /// `<workbookProtection>`, if it locks anything.
+    pub protection: Option<WorkbookProtection>,
+    /// Document properties as `(name, value)` sorted by name. Names carry the part they come
+    /// from: `core:title`, `app:Company`, `custom:Department`. Save metadata (timestamps,
+    /// `lastModifiedBy`, revision, application version, sheet titles) is not kept.
+    pub properties: Vec<(StringId, StringId)>,
+}
+
+/// How and when Excel recalculates (`<calcPr>`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalcSettings {
+    pub mode: CalcMode,
+    pub full_calc_on_load: bool,
+    pub calc_on_save: bool,
+    /// Iterative calculation, which lets circular references converge instead of erroring.
+    pub iterate: bool,
+    pub iterate_count: u32,
+    /// Maximum change between iterations, as written; `None` keeps Excel's 0.001.
+    pub iterate_delta: Option<StringId>,
+    /// `false` when "set precision as displayed" rounds stored values.
+    pub full_precision: bool,
+}
+
+impl Default for CalcSettings {
+    fn default() -> Self {
+        Self {
+            mode: CalcMode::Automatic,
+            full_calc_on_load: false,
+            calc_on_save: true,
+            iterate: false,
+            iterate_count: 100,
+            iterate_delta: None,
+            full_precision: true,
+        }
+    }
+}
+
+/// `calcMode` on `<calcPr>`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CalcMode {
+    #[default]
+    Automatic,
+    /// Automatic except data tables.
+    AutomaticNoTable,
+    Manual,
+}
+
+/// Workbook protection (`<workbookProtection>`). Only the presence of a password is recorded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct WorkbookProtection {
+    /// Sheets cannot be added, removed, moved, renamed, hidden or unhidden.
+    pub lock_structure: bool,
+    pub lock_windows: bool,
+    /// Change tracking cannot be turned off.
+    pub lock_revision: bool,
+    pub has_password: bool,
+}
+
+/// Worksheet protection (`<sheetProtection sheet="1">`). Only the presence of a password is
+/// recorded.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SheetProtection {
+    pub has_password: bool,
+    /// Actions users may still take while the sheet is protected, by attribute name (e.g.
+    /// `formatCells`, `selectLockedCells`), in a fixed order.
+    pub allowed: Vec<StringId>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -62,8 +459,46 @@ pub struct NamedRange {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ChartInfo {
     pub name: StringId,
+    /// First plot type in the plot area, e.g. `barChart`.
     pub chart_type: StringId,
+    /// First reference formula in the chart part.
     pub data_range: Option<StringId>,
+    /// Title text, or the formula of a title linked to a cell.
+    pub title: Option<StringId>,
+    /// Legend position (`r`, `l`, `t`, `b` or `tr`); `None` when the chart has no legend.
+    pub legend: Option<StringId>,
+    pub series: Vec<ChartSeries>,
+    pub axes: Vec<ChartAxis>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChartSeries {
+    /// `c:idx`, which stays put when other series are added or removed.
+    pub index: u32,
+    /// Plot type holding the series, e.g. `lineChart`.
+    pub chart_type: StringId,
+    /// Reference formula or literal text of the series name.
+    pub name: Option<StringId>,
+    /// Category (scatter: X value) reference formula.
+    pub categories: Option<StringId>,
+    /// Value (scatter: Y value) reference formula.
+    pub values: Option<StringId>,
+}
+
+/// A chart axis. Scaling values are kept as written in the chart part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChartAxis {
+    /// Axis element: `catAx`, `valAx`, `dateAx` or `serAx`.
+    pub kind: StringId,
+    /// `b`, `l`, `r` or `t`.
+    pub position: Option<StringId>,
+    pub title: Option<StringId>,
+    pub min: Option<StringId>,
+    pub max: Option<StringId>,
+    pub log_base: Option<StringId>,
+    /// `minMax` or `maxMin` (reversed).
+    pub orientation: Option<StringId>,
+    pub deleted: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -86,6 +521,8 @@ pub struct Sheet {
     pub kind: SheetKind,
     /// The grid of cell data.
     pub grid: Grid,
+    /// Merges, hidden and resized rows/columns, panes, and tab settings.
+    pub layout: SheetLayout,
 }
 
 /// The type of an Excel sheet.
@@ -97,6 +534,57 @@ pub enum SheetKind {
     Other,
 }
 
+/// Structural layout of a sheet: what a reader sees beyond the cell contents.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SheetLayout {
+    /// Tab visibility from the workbook's sheet list.
+    pub visibility: SheetVisibility,
+    /// Tab colour in the same `rgb:AARRGGBB` / `theme:N` / `indexed:N` form as cell formats.
+    pub tab_color: Option<StringId>,
+    /// Frozen panes of the first sheet view. Unfrozen split panes are not recorded.
+    pub freeze: Option<FreezePane>,
+    /// Sheet protection, if turned on.
+    pub protection: Option<SheetProtection>,
+    /// Merged ranges in document order.
+    pub merges: Vec<CellRange>,
+    /// Rows whose layout differs from the default, keyed by zero-based index.
+    pub rows: BTreeMap<u32, AxisLayout>,
+    /// Columns whose layout differs from the default, keyed by zero-based index.
+    pub cols: BTreeMap<u32, AxisLayout>,
+    /// Ranges filled by array formulas, including dynamic-array spills, in document order. The
+    /// formula lives on the top-left cell.
+    pub array_ranges: Vec<CellRange>,
+}
+
+/// Whether a sheet tab is shown (`state` on `<sheet>` in `workbook.xml`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SheetVisibility {
+    #[default]
+    Visible,
+    Hidden,
+    /// Hidden and only unhideable from VBA.
+    VeryHidden,
+}
+
+/// Layout of one row or column.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct AxisLayout {
+    pub hidden: bool,
+    /// Outline (grouping) level, 0 when ungrouped.
+    pub outline_level: u8,
+    /// Custom size, in twips (1/20 pt) for rows and 1/256 of a character width for columns.
+    /// `None` keeps the sheet default.
+    pub size: Option<u32>,
+}
+
+/// A frozen pane: the number of rows frozen at the top and columns frozen at the left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FreezePane {
+    pub rows: u32,
+    pub cols: u32,
+}
+
 /// A 2D grid of cells representing sheet data, stored as sparse or dense data.
 ///
 /// # Invariants
@@ -341,7 +829,7 @@ impl<'a> Iterator for DenseCellIter<'a> {
 /// A cell address representing a position in a grid.
 ///
 /// Can be parsed from A1-style strings (e.g., "B2", "AA10") and converted back.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct CellAddress {
     /// Zero-based row index.
     pub row: u32,
@@ -399,6 +887,74 @@ impl<'de> Deserialize<'de> for CellAddress {
     }
 }
 
+/// A rectangular A1 range such as `B2:D10`; a single cell when `start == end`.
+///
+/// Serialized as its A1 text, like [`CellAddress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CellRange {
+    pub start: CellAddress,
+    pub end: CellAddress,
+}
+
+impl CellRange {
+    pub fn new(start: CellAddress, end: CellAddress) -> CellRange {
+        CellRange { start, end }
+    }
+
+    pub fn to_a1(&self) -> String {
+        if self.start == self.end {
+            self.start.to_a1()
+        } else {
+            format!("{}:{}", self.start.to_a1(), self.end.to_a1())
+        }
+    }
+}
+
+impl std::str::FromStr for CellRange {
+    type Err = AddressParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some((start, end)) => Ok(CellRange {
+                start: start.parse()?,
+                end: end.parse()?,
+            }),
+            None => {
+                let cell: CellAddress = s.parse()?;
+                Ok(CellRange {
+                    start: cell,
+                    end: cell,
+                })
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for CellRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_a1())
+    }
+}
+
+impl Serialize for CellRange {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_a1())
+    }
+}
+
+impl<'de> Deserialize<'de> for CellRange {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let a1 = String::deserialize(deserializer)?;
+        CellRange::from_str(&a1).map_err(|e| DeError::custom(e.to_string()))
+    }
+}
+
 #[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
 pub enum CellValue {
     Blank,
@@ -0,0 +1,284 @@
+//! Workbook settings and document property parsing.
+//!
+//! `<workbookPr>`, `<calcPr>` and `<workbookProtection>` live in `xl/workbook.xml`. Document
+//! properties come from the three `docProps` parts; only the ones a reviewer would set by hand
+//! are kept, so saving a file does not show up as a property change.
+
+use crate::comments::push_text;
+use crate::grid_parser::{local_tag_name, xml_err, GridParseError};
+use crate::string_pool::StringPool;
+use crate::styles::{attr, collect_attrs};
+use crate::workbook::{CalcMode, CalcSettings, WorkbookProtection, WorkbookSettings};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+/// Core properties kept from `docProps/core.xml`.
+const CORE_PROPERTIES: [&str; 7] = [
+    "title",
+    "subject",
+    "creator",
+    "keywords",
+    "description",
+    "category",
+    "contentStatus",
+];
+
+/// Extended properties kept from `docProps/app.xml`.
+const APP_PROPERTIES: [&str; 3] = ["Company", "Manager", "HyperlinkBase"];
+
+/// One of the document property parts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PropertyPart {
+    Core,
+    App,
+    Custom,
+}
+
+impl PropertyPart {
+    pub(crate) const ALL: [PropertyPart; 3] =
+        [PropertyPart::Core, PropertyPart::App, PropertyPart::Custom];
+
+    pub(crate) fn path(self) -> &'static str {
+        match self {
+            PropertyPart::Core => "docProps/core.xml",
+            PropertyPart::App => "docProps/app.xml",
+            PropertyPart::Custom => "docProps/custom.xml",
+        }
+    }
+}
+
+fn parse_bool(value: Option<&str>, default: bool) -> bool {
+    match value.map(str::trim) {
+        Some("1" | "true") => true,
+        Some("0" | "false") => false,
+        _ => default,
+    }
+}
+
+fn calc_settings(attrs: &[(String, String)], pool: &mut StringPool) -> CalcSettings {
+    let defaults = CalcSettings::default();
+    CalcSettings {
+        mode: match attr(attrs, "calcMode").map(str::trim) {
+            Some("manual") => CalcMode::Manual,
+            Some("autoNoTable") => CalcMode::AutomaticNoTable,
+            _ => CalcMode::Automatic,
+        },
+        full_calc_on_load: parse_bool(attr(attrs, "fullCalcOnLoad"), defaults.full_calc_on_load),
+        calc_on_save: parse_bool(attr(attrs, "calcOnSave"), defaults.calc_on_save),
+        iterate: parse_bool(attr(attrs, "iterate"), defaults.iterate),
+        iterate_count: attr(attrs, "iterateCount")
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(defaults.iterate_count),
+        iterate_delta: attr(attrs, "iterateDelta").map(|v| pool.intern(v.trim())),
+        full_precision: parse_bool(attr(attrs, "fullPrecision"), defaults.full_precision),
+    }
+}
+
+/// `None` when the element locks nothing.
+fn workbook_protection(attrs: &[(String, String)]) -> Option<WorkbookProtection> {
+    let protection = WorkbookProtection {
+        lock_structure: parse_bool(attr(attrs, "lockStructure"), false),
+        lock_windows: parse_bool(attr(attrs, "lockWindows"), false),
+        lock_revision: parse_bool(attr(attrs, "lockRevision"), false),
+        has_password: attr(attrs, "workbookPassword").is_some()
+            || attr(attrs, "workbookHashValue").is_some(),
+    };
+    (protection.lock_structure || protection.lock_windows || protection.lock_revision)
+        .then_some(protection)
+}
+
+/// Parse the settings in `xl/workbook.xml`. `properties` is left empty.
+pub(crate) fn parse_workbook_settings(
+    xml: &[u8],
+    pool: &mut StringPool,
+) -> Result<WorkbookSettings, GridParseError> {
+    let mut reader = Reader::from_reader(xml);
+    reader.config_mut().trim_text(true);
+    let mut buf = Vec::new();
+    let mut settings = WorkbookSettings::default();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) => match local_tag_name(e.name().as_ref()) {
+                b"workbookPr" => {
+                    let attrs = collect_attrs(&reader, xml, &e)?;
+                    settings.date1904 = parse_bool(attr(&attrs, "date1904"), false);
+                    settings.code_name = attr(&attrs, "codeName").map(|v| pool.intern(v));
+                }
+                b"workbookProtection" => {
+                    let attrs = collect_attrs(&reader, xml, &e)?;
+                    settings.protection = workbook_protection(&attrs);
+                }
+                b"calcPr" => {
+                    let attrs = collect_attrs(&reader, xml, &e)?;
+                    settings.calculation = calc_settings(&attrs, pool);
+                }
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(xml_err(&reader, xml, e)),
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(settings)
+}
+
+/// Name of the kept property a top-level element holds, prefixed with its part.
+fn property_name(
+    reader: &Reader<&[u8]>,
+    xml: &[u8],
+    e: &BytesStart,
+    part: PropertyPart,
+) -> Result<Option<String>, GridParseError> {
+    let name = e.name();
+    let local = std::str::from_utf8(local_tag_name(name.as_ref())).unwrap_or_default();
+    Ok(match part {
+        PropertyPart::Core if CORE_PROPERTIES.contains(&local) => Some(format!("core:{local}")),
+        PropertyPart::App if APP_PROPERTIES.contains(&local) => Some(format!("app:{local}")),
+        PropertyPart::Custom if local == "property" => {
+            let attrs = collect_attrs(reader, xml, e)?;
+            attr(&attrs, "name").map(|name| format!("custom:{name}"))
+        }
+        _ => None,
+    })
+}
+
+/// Parse one document property part into `(name, value)` pairs. Empty properties are skipped.
+pub(crate) fn parse_document_properties(
+    xml: &[u8],
+    part: PropertyPart,
+) -> Result<Vec<(String, String)>, GridParseError> {
+    let mut reader = Reader::from_reader(xml);
+    reader.config_mut().trim_text(true);
+    let mut buf = Vec::new();
+
+    let mut properties = Vec::new();
+    let mut depth = 0usize;
+    let mut current: Option<String> = None;
+    let mut value = String::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) => {
+                depth += 1;
+                if depth == 2 {
+                    current = property_name(&reader, xml, &e, part)?;
+                    value.clear();
+                }
+            }
+            Ok(Event::Text(e)) if current.is_some() => {
+                push_text(&reader, xml, e.as_ref(), &mut value)?;
+            }
+            Ok(Event::End(_)) => {
+                if depth == 2
+                    && let Some(name) = current.take()
+                    && !value.is_empty()
+                {
+                    properties.push((name, std::mem::take(&mut value)));
+                }
+                depth = depth.saturating_sub(1);
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(xml_err(&reader, xml, e)),
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(properties)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_calculation_and_protection() {
+        let xml = concat!(
+            r#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">"#,
+            r#"<workbookPr date1904="1" codeName="ThisWorkbook"/>"#,
+            r#"<workbookProtection workbookAlgorithmName="SHA-512" workbookHashValue="a=" "#,
+            r#"lockStructure="1"/><sheets/>"#,
+            r#"<calcPr calcId="191029" calcMode="manual" iterate="1" iterateCount="50"/>"#,
+            r#"</workbook>"#,
+        );
+        let mut pool = StringPool::new();
+        let settings = parse_workbook_settings(xml.as_bytes(), &mut pool).expect("parse settings");
+
+        assert!(settings.date1904);
+        assert_eq!(
+            settings.code_name.map(|id| pool.resolve(id)),
+            Some("ThisWorkbook")
+        );
+        assert_eq!(
+            settings.calculation,
+            CalcSettings {
+                mode: CalcMode::Manual,
+                iterate: true,
+                iterate_count: 50,
+                ..CalcSettings::default()
+            }
+        );
+        assert_eq!(
+            settings.protection,
+            Some(WorkbookProtection {
+                lock_structure: true,
+                has_password: true,
+                ..WorkbookProtection::default()
+            })
+        );
+
+        let unlocked = br#"<workbook><workbookProtection lockStructure="0"/></workbook>"#;
+        let settings = parse_workbook_settings(unlocked, &mut pool).expect("parse settings");
+        assert_eq!(settings.protection, None);
+    }
+
+    #[test]
+    fn keeps_authored_document_properties_only() {
+        let core = concat!(
+            r#"<cp:coreProperties "#,
+            r#"xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/"#,
+            r#"core-properties" "#,
+            r#"xmlns:dc="http://purl.org/dc/elements/1.1/" "#,
+            r#"xmlns:dcterms="http://purl.org/dc/terms/">"#,
+            r#"<dc:title>Q3 &amp; Q4 budget</dc:title><dc:creator>Finance</dc:creator>"#,
+            r#"<cp:lastModifiedBy>someone</cp:lastModifiedBy><dc:subject/>"#,
+            r#"<dcterms:modified>2024-01-02T03:04:05Z</dcterms:modified>"#,
+            r#"</cp:coreProperties>"#,
+        );
+        let props = parse_document_properties(core.as_bytes(), PropertyPart::Core)
+            .expect("parse core properties");
+        assert_eq!(
+            props,
+            vec![
+                ("core:title".to_string(), "Q3 & Q4 budget".to_string()),
+                ("core:creator".to_string(), "Finance".to_string()),
+            ]
+        );
+
+        let app = concat!(
+            r#"<Properties><Application>Microsoft Excel</Application><TitlesOfParts>"#,
+            r#"<vt:vector size="1" baseType="lpstr"><vt:lpstr>Sheet1</vt:lpstr></vt:vector>"#,
+            r#"</TitlesOfParts><Company>Contoso</Company></Properties>"#,
+        );
+        let props = parse_document_properties(app.as_bytes(), PropertyPart::App)
+            .expect("parse app properties");
+        assert_eq!(
+            props,
+            vec![("app:Company".to_string(), "Contoso".to_string())]
+        );
+
+        let custom = concat!(
+            r#"<Properties><property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="2" "#,
+            r#"name="Department"><vt:lpwstr>Treasury</vt:lpwstr></property></Properties>"#,
+        );
+        let props = parse_document_properties(custom.as_bytes(), PropertyPart::Custom)
+            .expect("parse custom properties");
+        assert_eq!(
+            props,
+            vec![("custom:Department".to_string(), "Treasury".to_string())]
+        );
+    }
+}
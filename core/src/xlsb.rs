@@ -0,0 +1,1727 @@
+//! Binary workbook (`.xlsb`, BIFF12) parsing.
+//!
+//! XLSB packages use the same OPC layout as `.xlsx`, but the workbook, shared string table,
+//! and worksheet parts are BIFF12 record streams instead of XML. This module reads those
+//! record streams into the same IR produced by [`crate::grid_parser`]:
+//!
+//! - `xl/workbook.bin`: sheet list (`BrtBundleSh`), defined names (`BrtName`), and the
+//!   external sheet table used to resolve 3D references in formulas.
+//! - `xl/sharedStrings.bin`: shared string items (`BrtSSTItem`).
+//! - `xl/worksheets/*.bin`: cell records, shared/array formulas, and drawing relationships.
+//!
+//! Formulas are stored as parsed token streams (`Rgce`) and are decompiled back to the A1 text
+//! Excel writes into `<f>` elements, so XLSB and XLSX cells diff the same way. Token streams
+//! that use constructs we cannot render (array constants, structured table references) fall back
+//! to a stable placeholder derived from the token bytes so formula changes are still detected.
+
+use crate::addressing::address_to_index;
+use crate::grid_parser::{
+    build_grid_from_cells, quote_sheet_name, resolve_sheet_target, ParsedCell, ParsedSheetXml,
+    SheetDescriptor,
+};
+use crate::string_pool::{StringId, StringPool};
+use crate::workbook::{CellAddress, CellRange, CellValue, NamedRange, SheetVisibility};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use xxhash_rust::xxh3::xxh3_64;
+
+const BRT_ROW_HDR: u32 = 0;
+const BRT_CELL_BLANK: u32 = 1;
+const BRT_CELL_RK: u32 = 2;
+const BRT_CELL_ERROR: u32 = 3;
+const BRT_CELL_BOOL: u32 = 4;
+const BRT_CELL_REAL: u32 = 5;
+const BRT_CELL_ST: u32 = 6;
+const BRT_CELL_ISST: u32 = 7;
+const BRT_FMLA_STRING: u32 = 8;
+const BRT_FMLA_NUM: u32 = 9;
+const BRT_FMLA_BOOL: u32 = 10;
+const BRT_FMLA_ERROR: u32 = 11;
+const BRT_SST_ITEM: u32 = 19;
+const BRT_CELL_RSTRING: u32 = 62;
+const BRT_NAME: u32 = 39;
+const BRT_WS_DIM: u32 = 148;
+const BRT_BUNDLE_SH: u32 = 156;
+const BRT_SUP_SELF: u32 = 357;
+const BRT_SUP_SAME: u32 = 358;
+const BRT_SUP_TABS: u32 = 359;
+const BRT_SUP_BOOK_SRC: u32 = 360;
+const BRT_EXTERN_SHEET: u32 = 362;
+const BRT_ARR_FMLA: u32 = 426;
+const BRT_SHR_FMLA: u32 = 427;
+const BRT_DRAWING: u32 = 550;
+
+const MAX_ROW: u32 = 1_048_575;
+const MAX_COL: u32 = 16_383;
+
+/// Error raised while decoding a BIFF12 record stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct XlsbError {
+    pub(crate) offset: usize,
+    pub(crate) message: String,
+}
+
+impl XlsbError {
+    fn new(offset: usize, message: impl Into<String>) -> Self {
+        Self {
+            offset,
+            message: message.into(),
+        }
+    }
+}
+
+struct Record<'a> {
+    id: u32,
+    offset: usize,
+    data: &'a [u8],
+}
+
+struct RecordReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> RecordReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    fn read_varint(&mut self, max_bytes: usize, what: &str) -> Result<u32, XlsbError> {
+        let start = self.pos;
+        let mut value: u32 = 0;
+        for i in 0..max_bytes {
+            let Some(&byte) = self.bytes.get(self.pos) else {
+                return Err(XlsbError::new(start, format!("truncated record {what}")));
+            };
+            self.pos += 1;
+            value |= ((byte & 0x7F) as u32) << (7 * i);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(XlsbError::new(start, format!("record {what} is too long")))
+    }
+
+    fn next_record(&mut self) -> Result<Option<Record<'a>>, XlsbError> {
+        if self.pos >= self.bytes.len() {
+            return Ok(None);
+        }
+        let offset = self.pos;
+        let id = self.read_varint(2, "type")?;
+        let len = self.read_varint(4, "size")? as usize;
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| {
+                XlsbError::new(
+                    offset,
+                    format!("record {id} declares {len} bytes past end of part"),
+                )
+            })?;
+        let data = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(Some(Record { id, offset, data }))
+    }
+}
+
+struct FieldReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    base: usize,
+}
+
+impl<'a> FieldReader<'a> {
+    fn new(record: &Record<'a>) -> Self {
+        Self {
+            data: record.data,
+            pos: 0,
+            base: record.offset,
+        }
+    }
+
+    fn err(&self, message: impl Into<String>) -> XlsbError {
+        XlsbError::new(self.base + self.pos, message)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], XlsbError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| self.err("record field runs past end of record"))?;
+        let out = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(out)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, XlsbError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, XlsbError> {
+        let b = self.take(2)?;
+        Ok(u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, XlsbError> {
+        let b = self.take(4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn read_i32(&mut self) -> Result<i32, XlsbError> {
+        Ok(self.read_u32()? as i32)
+    }
+
+    fn read_f64(&mut self) -> Result<f64, XlsbError> {
+        let b = self.take(8)?;
+        let mut raw = [0u8; 8];
+        raw.copy_from_slice(b);
+        Ok(f64::from_le_bytes(raw))
+    }
+
+    fn read_utf16(&mut self, chars: usize) -> Result<String, XlsbError> {
+        let len = chars
+            .checked_mul(2)
+            .ok_or_else(|| self.err("string length overflow"))?;
+        let raw = self.take(len)?;
+        let units: Vec<u16> = raw
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+        Ok(String::from_utf16_lossy(&units))
+    }
+
+    /// `XLWideString`: 32-bit character count followed by UTF-16LE text.
+    fn read_wide_string(&mut self) -> Result<String, XlsbError> {
+        let chars = self.read_u32()? as usize;
+        self.read_utf16(chars)
+    }
+
+    /// `XLNullableWideString`: like [`Self::read_wide_string`], with `0xFFFFFFFF` meaning null.
+    fn read_nullable_wide_string(&mut self) -> Result<Option<String>, XlsbError> {
+        let chars = self.read_u32()?;
+        if chars == u32::MAX {
+            return Ok(None);
+        }
+        self.read_utf16(chars as usize).map(Some)
+    }
+
+    /// Parsed formula payload shared by cell, name, shared, and array formulas:
+    /// `cce` + `rgce` token bytes followed by `cb` + `rgcb` extra data.
+    fn read_parsed_formula(&mut self) -> Result<RawFormula, XlsbError> {
+        let cce = self.read_u32()? as usize;
+        let rgce = self.take(cce)?.to_vec();
+        let extra = if self.pos + 4 <= self.data.len() {
+            let cb = self.read_u32()? as usize;
+            self.take(cb)?.to_vec()
+        } else {
+            Vec::new()
+        };
+        Ok(RawFormula { rgce, extra })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct RawFormula {
+    rgce: Vec<u8>,
+    extra: Vec<u8>,
+}
+
+#[derive(Debug, Clone)]
+struct BinaryDefinedName {
+    name: String,
+    local_sheet: Option<usize>,
+    is_function: bool,
+    formula: RawFormula,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum SupBook {
+    SelfBook,
+    External { index: usize, sheets: Vec<String> },
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Xti {
+    sup_book: usize,
+    first_tab: i32,
+    last_tab: i32,
+}
+
+/// Parsed `xl/workbook.bin`: the sheet list plus the lookup tables formulas refer to.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct BinaryWorkbook {
+    pub(crate) sheets: Vec<SheetDescriptor>,
+    names: Vec<BinaryDefinedName>,
+    sup_books: Vec<SupBook>,
+    extern_sheets: Vec<Xti>,
+}
+
+pub(crate) fn parse_workbook_bin(bytes: &[u8]) -> Result<BinaryWorkbook, XlsbError> {
+    let mut reader = RecordReader::new(bytes);
+    let mut workbook = BinaryWorkbook::default();
+    let mut external_count = 0usize;
+
+    while let Some(record) = reader.next_record()? {
+        match record.id {
+            BRT_BUNDLE_SH => {
+                let mut fields = FieldReader::new(&record);
+                let hs_state = fields.read_u32()?;
+                let tab_id = fields.read_u32()?;
+                let rel_id = fields.read_nullable_wide_string()?;
+                let name = fields.read_wide_string()?;
+                workbook.sheets.push(SheetDescriptor {
+                    name,
+                    rel_id: rel_id.filter(|id| !id.is_empty()),
+                    sheet_id: Some(tab_id),
+                    visibility: match hs_state {
+                        1 => SheetVisibility::Hidden,
+                        2 => SheetVisibility::VeryHidden,
+                        _ => SheetVisibility::Visible,
+                    },
+                });
+            }
+            BRT_NAME => {
+                let mut fields = FieldReader::new(&record);
+                let flags = fields.read_u32()?;
+                let _ch_key = fields.read_u8()?;
+                let itab = fields.read_u32()?;
+                let mut name = fields.read_wide_string()?;
+                let formula = fields.read_parsed_formula()?;
+                let is_builtin = flags & 0x20 != 0;
+                if is_builtin && !name.starts_with("_xlnm.") {
+                    name = format!("_xlnm.{name}");
+                }
+                workbook.names.push(BinaryDefinedName {
+                    name,
+                    local_sheet: (itab != u32::MAX).then_some(itab as usize),
+                    is_function: flags & 0x2 != 0 || flags & (1 << 17) != 0,
+                    formula,
+                });
+            }
+            BRT_SUP_BOOK_SRC => {
+                external_count += 1;
+                workbook.sup_books.push(SupBook::External {
+                    index: external_count,
+                    sheets: Vec::new(),
+                });
+            }
+            BRT_SUP_SELF | BRT_SUP_SAME => workbook.sup_books.push(SupBook::SelfBook),
+            BRT_SUP_TABS => {
+                let mut fields = FieldReader::new(&record);
+                let count = fields.read_u32()? as usize;
+                let mut names = Vec::with_capacity(count.min(4096));
+                for _ in 0..count {
+                    names.push(fields.read_wide_string()?);
+                }
+                if let Some(SupBook::External { sheets, .. }) = workbook.sup_books.last_mut() {
+                    *sheets = names;
+                }
+            }
+            BRT_EXTERN_SHEET => {
+                let mut fields = FieldReader::new(&record);
+                let count = fields.read_u32()? as usize;
+                for _ in 0..count {
+                    let sup_book = fields.read_u32()? as usize;
+                    let first_tab = fields.read_i32()?;
+                    let last_tab = fields.read_i32()?;
+                    workbook.extern_sheets.push(Xti {
+                        sup_book,
+                        first_tab,
+                        last_tab,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(workbook)
+}
+
+impl BinaryWorkbook {
+    /// Defined names in the same shape `parse_defined_names` produces for `workbook.xml`.
+    ///
+    /// Names that only exist to back future/add-in functions are skipped.
+    pub(crate) fn defined_names(
+        &self,
+        pool: &mut StringPool,
+    ) -> Result<Vec<NamedRange>, XlsbError> {
+        let mut named_ranges = Vec::new();
+        for defined in &self.names {
+            if defined.is_function {
+                continue;
+            }
+            let refers_to = render_formula(&defined.formula, self, 0, 0);
+            let (qualified_name, scope) = match defined.local_sheet {
+                None => (defined.name.clone(), None),
+                Some(idx) => {
+                    let sheet_name = self.sheets.get(idx).map(|s| s.name.as_str()).ok_or_else(
+                        || {
+                            XlsbError::new(
+                                0,
+                                format!(
+                                    "defined name '{}' scoped to sheet index {idx} out of bounds (sheets={})",
+                                    defined.name,
+                                    self.sheets.len()
+                                ),
+                            )
+                        },
+                    )?;
+                    let sheet_name_id = pool.intern(sheet_name);
+                    let qualified = format!("{}!{}", quote_sheet_name(sheet_name), defined.name);
+                    (qualified, Some(sheet_name_id))
+                }
+            };
+            named_ranges.push(NamedRange {
+                name: pool.intern(&qualified_name),
+                refers_to: pool.intern(&refers_to),
+                scope,
+            });
+        }
+        Ok(named_ranges)
+    }
+}
+
+pub(crate) fn parse_shared_strings_bin(
+    bytes: &[u8],
+    pool: &mut StringPool,
+) -> Result<Vec<StringId>, XlsbError> {
+    let mut reader = RecordReader::new(bytes);
+    let mut strings = Vec::new();
+    while let Some(record) = reader.next_record()? {
+        if record.id != BRT_SST_ITEM {
+            continue;
+        }
+        let mut fields = FieldReader::new(&record);
+        let _flags = fields.read_u8()?;
+        let text = fields.read_wide_string()?;
+        strings.push(pool.intern(&text));
+    }
+    Ok(strings)
+}
+
+/// Like [`resolve_sheet_target`], but guesses `.bin` worksheet parts when the workbook has no
+/// relationship for the sheet.
+pub(crate) fn resolve_binary_sheet_target(
+    sheet: &SheetDescriptor,
+    relationships: &HashMap<String, String>,
+    index: usize,
+) -> String {
+    let target = resolve_sheet_target(sheet, relationships, index);
+    let has_relationship = sheet
+        .rel_id
+        .as_ref()
+        .is_some_and(|rel_id| relationships.contains_key(rel_id));
+    match target.strip_suffix(".xml") {
+        Some(stem) if !has_relationship => format!("{stem}.bin"),
+        _ => target,
+    }
+}
+
+/// Cheap scan used by the fast-diff fingerprinting path: does the sheet reference the SST?
+pub(crate) fn sheet_uses_shared_strings(bytes: &[u8]) -> bool {
+    let mut reader = RecordReader::new(bytes);
+    loop {
+        match reader.next_record() {
+            Ok(Some(record)) if record.id == BRT_CELL_ISST => return true,
+            Ok(Some(_)) => {}
+            Ok(None) => return false,
+            // Be conservative: an unreadable stream must be parsed (and reported) in full.
+            Err(_) => return true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RangeRef {
+    first_row: u32,
+    last_row: u32,
+    first_col: u32,
+    last_col: u32,
+}
+
+impl RangeRef {
+    fn contains(&self, row: u32, col: u32) -> bool {
+        row >= self.first_row
+            && row <= self.last_row
+            && col >= self.first_col
+            && col <= self.last_col
+    }
+}
+
+impl RangeRef {
+    fn to_cell_range(self) -> CellRange {
+        CellRange::new(
+            CellAddress::from_indices(self.first_row, self.first_col),
+            CellAddress::from_indices(self.last_row, self.last_col),
+        )
+    }
+}
+
+struct PendingFormulaCell {
+    cell_index: usize,
+    anchor_row: u32,
+}
+
+/// A `BrtShrFmla` or `BrtArrFmla` record: the range it covers and its token stream.
+struct FormulaGroup {
+    range: RangeRef,
+    raw: RawFormula,
+    array: bool,
+}
+
+pub(crate) fn parse_sheet_bin(
+    bytes: &[u8],
+    shared_strings: &[StringId],
+    workbook: &BinaryWorkbook,
+    pool: &mut StringPool,
+) -> Result<ParsedSheetXml, XlsbError> {
+    let mut reader = RecordReader::new(bytes);
+    let mut cells: Vec<ParsedCell> = Vec::new();
+    let mut pending: Vec<PendingFormulaCell> = Vec::new();
+    let mut group_formulas: Vec<FormulaGroup> = Vec::new();
+    let mut drawing_rids = Vec::new();
+    let mut dimension_hint: Option<(u32, u32)> = None;
+    let mut row: u32 = 0;
+
+    while let Some(record) = reader.next_record()? {
+        match record.id {
+            BRT_ROW_HDR => {
+                let mut fields = FieldReader::new(&record);
+                row = fields.read_u32()?;
+                if row > MAX_ROW {
+                    return Err(fields.err(format!("row index {row} out of range")));
+                }
+            }
+            BRT_WS_DIM => {
+                let range = read_range(&mut FieldReader::new(&record))?;
+                dimension_hint = Some((
+                    range
+                        .last_row
+                        .saturating_sub(range.first_row)
+                        .saturating_add(1),
+                    range
+                        .last_col
+                        .saturating_sub(range.first_col)
+                        .saturating_add(1),
+                ));
+            }
+            BRT_CELL_BLANK..=BRT_FMLA_ERROR | BRT_CELL_RSTRING => {
+                let mut fields = FieldReader::new(&record);
+                let col = fields.read_u32()?;
+                if col > MAX_COL {
+                    return Err(fields.err(format!("column index {col} out of range")));
+                }
+                let _style = fields.read_u32()?;
+                let value = match record.id {
+                    BRT_CELL_BLANK => None,
+                    BRT_CELL_RK => Some(CellValue::Number(decode_rk(fields.read_u32()?))),
+                    BRT_CELL_ERROR | BRT_FMLA_ERROR => Some(CellValue::Error(
+                        pool.intern(&error_text(fields.read_u8()?)),
+                    )),
+                    BRT_CELL_BOOL | BRT_FMLA_BOOL => Some(CellValue::Bool(fields.read_u8()? != 0)),
+                    BRT_CELL_REAL | BRT_FMLA_NUM => Some(CellValue::Number(fields.read_f64()?)),
+                    BRT_CELL_ST | BRT_FMLA_STRING => {
+                        Some(CellValue::Text(pool.intern(&fields.read_wide_string()?)))
+                    }
+                    BRT_CELL_RSTRING => {
+                        // fExtStr/fRichStr flags; the formatting and phonetic runs after the
+                        // text do not affect the value.
+                        let _flags = fields.read_u8()?;
+                        Some(CellValue::Text(pool.intern(&fields.read_wide_string()?)))
+                    }
+                    BRT_CELL_ISST => {
+                        let idx = fields.read_u32()? as usize;
+                        let text_id = *shared_strings.get(idx).ok_or_else(|| {
+                            fields.err(format!("shared string index {idx} out of bounds"))
+                        })?;
+                        Some(CellValue::Text(text_id))
+                    }
+                    _ => None,
+                };
+
+                let mut formula = None;
+                if (BRT_FMLA_STRING..=BRT_FMLA_ERROR).contains(&record.id) {
+                    let _flags = fields.read_u16()?;
+                    let raw = fields.read_parsed_formula()?;
+                    match shared_formula_anchor(&raw.rgce) {
+                        Some(anchor_row) => pending.push(PendingFormulaCell {
+                            cell_index: cells.len(),
+                            anchor_row,
+                        }),
+                        None => {
+                            let text = render_formula(&raw, workbook, row, col);
+                            formula = Some(pool.intern(&text));
+                        }
+                    }
+                }
+
+                cells.push(ParsedCell {
+                    row,
+                    col,
+                    value,
+                    formula,
+                });
+            }
+            BRT_SHR_FMLA => {
+                let mut fields = FieldReader::new(&record);
+                let range = read_range(&mut fields)?;
+                let raw = fields.read_parsed_formula()?;
+                group_formulas.push(FormulaGroup {
+                    range,
+                    raw,
+                    array: false,
+                });
+            }
+            BRT_ARR_FMLA => {
+                let mut fields = FieldReader::new(&record);
+                let range = read_range(&mut fields)?;
+                let _flags = fields.read_u8()?;
+                let raw = fields.read_parsed_formula()?;
+                group_formulas.push(FormulaGroup {
+                    range,
+                    raw,
+                    array: true,
+                });
+            }
+            BRT_DRAWING => {
+                let mut fields = FieldReader::new(&record);
+                drawing_rids.push(fields.read_wide_string()?);
+            }
+            _ => {}
+        }
+    }
+
+    // Shared formula tokens are relative to the cell that uses them, so every cell in a shared
+    // group gets its own text. Array formulas mirror the XLSX layout: the anchor cell carries
+    // the formula and the rest of the range carries values only.
+    for entry in pending {
+        let cell = &mut cells[entry.cell_index];
+        let group = group_formulas.iter().find(|group| {
+            group.range.first_row == entry.anchor_row && group.range.contains(cell.row, cell.col)
+        });
+        if let Some(group) = group
+            && (!group.array
+                || (group.range.first_row == cell.row && group.range.first_col == cell.col))
+        {
+            let text = render_formula(&group.raw, workbook, cell.row, cell.col);
+            cell.formula = Some(pool.intern(&text));
+        }
+    }
+
+    let array_ranges = group_formulas
+        .iter()
+        .filter(|group| group.array)
+        .map(|group| group.range.to_cell_range())
+        .collect();
+    let grid = build_grid_from_cells(dimension_hint, cells)
+        .map_err(|e| XlsbError::new(0, e.to_string()))?;
+    Ok(ParsedSheetXml {
+        grid,
+        drawing_rids,
+        array_ranges,
+    })
+}
+
+fn read_range(fields: &mut FieldReader<'_>) -> Result<RangeRef, XlsbError> {
+    let first_row = fields.read_u32()?;
+    let last_row = fields.read_u32()?;
+    let first_col = fields.read_u32()?;
+    let last_col = fields.read_u32()?;
+    Ok(RangeRef {
+        first_row,
+        last_row,
+        first_col,
+        last_col,
+    })
+}
+
+/// Returns the anchor row when a cell formula is only a `PtgExp` pointer into a shared or
+/// array formula group.
+fn shared_formula_anchor(rgce: &[u8]) -> Option<u32> {
+    if rgce.len() < 5 || rgce[0] != 0x01 {
+        return None;
+    }
+    Some(u32::from_le_bytes([rgce[1], rgce[2], rgce[3], rgce[4]]))
+}
+
+fn decode_rk(raw: u32) -> f64 {
+    let value = if raw & 0x2 != 0 {
+        ((raw as i32) >> 2) as f64
+    } else {
+        f64::from_bits(((raw & 0xFFFF_FFFC) as u64) << 32)
+    };
+    if raw & 0x1 != 0 {
+        value / 100.0
+    } else {
+        value
+    }
+}
+
+fn error_text(code: u8) -> Cow<'static, str> {
+    let text = match code {
+        0x00 => "#NULL!",
+        0x07 => "#DIV/0!",
+        0x0F => "#VALUE!",
+        0x17 => "#REF!",
+        0x1D => "#NAME?",
+        0x24 => "#NUM!",
+        0x2A => "#N/A",
+        0x2B => "#GETTING_DATA",
+        // Keep unknown codes apart so two different ones never compare equal.
+        _ => return Cow::Owned(format!("#ERROR{code:#04X}")),
+    };
+    Cow::Borrowed(text)
+}
+
+/// Decompile a formula token stream to A1 text, falling back to a hashed placeholder when the
+/// stream uses tokens we do not render.
+fn render_formula(raw: &RawFormula, workbook: &BinaryWorkbook, row: u32, col: u32) -> String {
+    let ctx = FormulaContext {
+        workbook,
+        base_row: row,
+        base_col: col,
+    };
+    match decompile_rgce(&raw.rgce, &ctx) {
+        Some(text) => text,
+        None => {
+            let mut bytes = raw.rgce.clone();
+            bytes.extend_from_slice(&raw.extra);
+            format!("<unsupported xlsb formula {:016x}>", xxh3_64(&bytes))
+        }
+    }
+}
+
+struct FormulaContext<'a> {
+    workbook: &'a BinaryWorkbook,
+    base_row: u32,
+    base_col: u32,
+}
+
+struct TokenReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> TokenReader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        let end = self.pos.checked_add(len)?;
+        let out = self.data.get(self.pos..end)?;
+        self.pos = end;
+        Some(out)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.take(1).map(|b| b[0])
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        self.take(2).map(|b| u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.take(4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn f64(&mut self) -> Option<f64> {
+        let b = self.take(8)?;
+        let mut raw = [0u8; 8];
+        raw.copy_from_slice(b);
+        Some(f64::from_le_bytes(raw))
+    }
+}
+
+/// One endpoint of a cell reference: index plus whether it is relative (no `$`).
+#[derive(Debug, Clone, Copy)]
+struct RefPart {
+    index: u32,
+    relative: bool,
+}
+
+fn decompile_rgce(rgce: &[u8], ctx: &FormulaContext<'_>) -> Option<String> {
+    let mut tokens = TokenReader { data: rgce, pos: 0 };
+    let mut stack: Vec<String> = Vec::new();
+
+    while tokens.pos < rgce.len() {
+        let ptg = tokens.u8()?;
+        match ptg {
+            0x03..=0x11 => {
+                let rhs = stack.pop()?;
+                let lhs = stack.pop()?;
+                let op = match ptg {
+                    0x03 => "+",
+                    0x04 => "-",
+                    0x05 => "*",
+                    0x06 => "/",
+                    0x07 => "^",
+                    0x08 => "&",
+                    0x09 => "<",
+                    0x0A => "<=",
+                    0x0B => "=",
+                    0x0C => ">=",
+                    0x0D => ">",
+                    0x0E => "<>",
+                    0x0F => " ",
+                    0x10 => ",",
+                    _ => ":",
+                };
+                stack.push(format!("{lhs}{op}{rhs}"));
+            }
+            0x12 => {
+                let operand = stack.pop()?;
+                stack.push(format!("+{operand}"));
+            }
+            0x13 => {
+                let operand = stack.pop()?;
+                stack.push(format!("-{operand}"));
+            }
+            0x14 => {
+                let operand = stack.pop()?;
+                stack.push(format!("{operand}%"));
+            }
+            0x15 => {
+                let operand = stack.pop()?;
+                stack.push(format!("({operand})"));
+            }
+            0x16 => stack.push(String::new()),
+            0x17 => {
+                let chars = tokens.u16()? as usize;
+                let raw = tokens.take(chars.checked_mul(2)?)?;
+                let units: Vec<u16> = raw
+                    .chunks_exact(2)
+                    .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                    .collect();
+                let text = String::from_utf16_lossy(&units);
+                stack.push(format!("\"{}\"", text.replace('"', "\"\"")));
+            }
+            0x19 => {
+                let grbit = tokens.u8()?;
+                if grbit & 0x04 != 0 {
+                    let cases = tokens.u16()? as usize;
+                    tokens.take(cases.checked_add(1)?.checked_mul(2)?)?;
+                } else if grbit & 0x10 != 0 {
+                    tokens.take(2)?;
+                    let operand = stack.pop()?;
+                    stack.push(format!("SUM({operand})"));
+                } else {
+                    tokens.take(2)?;
+                }
+            }
+            0x1C => stack.push(error_text(tokens.u8()?).into_owned()),
+            0x1D => stack.push(if tokens.u8()? != 0 { "TRUE" } else { "FALSE" }.to_string()),
+            0x1E => stack.push(tokens.u16()?.to_string()),
+            0x1F => stack.push(format_number(tokens.f64()?)),
+            0x20..=0x7F => {
+                let base = 0x20 | (ptg & 0x1F);
+                match base {
+                    0x21 => {
+                        let iftab = tokens.u16()?;
+                        let (name, argc) = function_info(iftab)?;
+                        let argc = argc? as usize;
+                        push_function_call(&mut stack, name, argc)?;
+                    }
+                    0x22 => {
+                        let argc = tokens.u8()? as usize;
+                        let iftab = tokens.u16()? & 0x7FFF;
+                        if iftab == 255 {
+                            if argc == 0 || stack.len() < argc {
+                                return None;
+                            }
+                            let args = stack.split_off(stack.len() - argc);
+                            let name = args[0].clone();
+                            stack.push(format!("{name}({})", args[1..].join(",")));
+                        } else {
+                            let (name, _) = function_info(iftab)?;
+                            push_function_call(&mut stack, name, argc)?;
+                        }
+                    }
+                    0x23 => {
+                        let index = tokens.u32()? as usize;
+                        let defined = ctx.workbook.names.get(index.checked_sub(1)?)?;
+                        stack.push(defined.name.clone());
+                    }
+                    0x24 | 0x2C => {
+                        let (row, col) = read_ref(&mut tokens, base == 0x2C, ctx)?;
+                        stack.push(format_cell_ref(row, col));
+                    }
+                    0x25 | 0x2D => {
+                        let area = read_area(&mut tokens, base == 0x2D, ctx)?;
+                        stack.push(format_area(area));
+                    }
+                    0x26..=0x28 => {
+                        tokens.take(6)?;
+                    }
+                    0x29 => {
+                        tokens.take(2)?;
+                    }
+                    0x2A => {
+                        tokens.take(6)?;
+                        stack.push("#REF!".to_string());
+                    }
+                    0x2B => {
+                        tokens.take(12)?;
+                        stack.push("#REF!".to_string());
+                    }
+                    0x39 => {
+                        let ixti = tokens.u16()? as usize;
+                        let index = tokens.u32()? as usize;
+                        let xti = ctx.workbook.extern_sheets.get(ixti)?;
+                        match ctx.workbook.sup_books.get(xti.sup_book) {
+                            None | Some(SupBook::SelfBook) => {
+                                let defined = ctx.workbook.names.get(index.checked_sub(1)?)?;
+                                stack.push(defined.name.clone());
+                            }
+                            Some(SupBook::External { .. }) => return None,
+                        }
+                    }
+                    0x3A => {
+                        let ixti = tokens.u16()?;
+                        let (row, col) = read_ref(&mut tokens, false, ctx)?;
+                        let prefix = sheet_prefix(ctx, ixti)?;
+                        stack.push(format!("{prefix}{}", format_cell_ref(row, col)));
+                    }
+                    0x3B => {
+                        let ixti = tokens.u16()?;
+                        let area = read_area(&mut tokens, false, ctx)?;
+                        let prefix = sheet_prefix(ctx, ixti)?;
+                        stack.push(format!("{prefix}{}", format_area(area)));
+                    }
+                    0x3C => {
+                        let ixti = tokens.u16()?;
+                        tokens.take(6)?;
+                        let prefix = sheet_prefix(ctx, ixti)?;
+                        stack.push(format!("{prefix}#REF!"));
+                    }
+                    0x3D => {
+                        let ixti = tokens.u16()?;
+                        tokens.take(12)?;
+                        let prefix = sheet_prefix(ctx, ixti)?;
+                        stack.push(format!("{prefix}#REF!"));
+                    }
+                    // PtgArray (array constants) and anything else we do not render.
+                    _ => return None,
+                }
+            }
+            // PtgExp/PtgTbl outside a shared-formula pointer, PtgList, and unknown tokens.
+            _ => return None,
+        }
+    }
+
+    if stack.len() == 1 {
+        stack.pop()
+    } else {
+        None
+    }
+}
+
+fn push_function_call(stack: &mut Vec<String>, name: &str, argc: usize) -> Option<()> {
+    if stack.len() < argc {
+        return None;
+    }
+    let args = stack.split_off(stack.len() - argc);
+    stack.push(format!("{name}({})", args.join(",")));
+    Some(())
+}
+
+fn read_ref_part_row(raw: u32, relative: bool, relative_to_base: bool, base: u32) -> Option<u32> {
+    if relative && relative_to_base {
+        let offset = raw as i32 as i64;
+        let row = (base as i64 + offset).rem_euclid(MAX_ROW as i64 + 1);
+        Some(row as u32)
+    } else if raw <= MAX_ROW {
+        Some(raw)
+    } else {
+        None
+    }
+}
+
+fn read_ref_part_col(raw: u16, relative: bool, relative_to_base: bool, base: u32) -> Option<u32> {
+    let col = (raw & 0x3FFF) as u32;
+    if relative && relative_to_base {
+        // 14-bit signed offset.
+        let offset = ((col << 18) as i32 >> 18) as i64;
+        let col = (base as i64 + offset).rem_euclid(MAX_COL as i64 + 1);
+        Some(col as u32)
+    } else if col <= MAX_COL {
+        Some(col)
+    } else {
+        None
+    }
+}
+
+fn read_ref(
+    tokens: &mut TokenReader<'_>,
+    relative_to_base: bool,
+    ctx: &FormulaContext<'_>,
+) -> Option<(RefPart, RefPart)> {
+    let row_raw = tokens.u32()?;
+    let col_raw = tokens.u16()?;
+    let col_relative = col_raw & 0x4000 != 0;
+    let row_relative = col_raw & 0x8000 != 0;
+    let row = read_ref_part_row(row_raw, row_relative, relative_to_base, ctx.base_row)?;
+    let col = read_ref_part_col(col_raw, col_relative, relative_to_base, ctx.base_col)?;
+    Some((
+        RefPart {
+            index: row,
+            relative: row_relative,
+        },
+        RefPart {
+            index: col,
+            relative: col_relative,
+        },
+    ))
+}
+
+type Area = (RefPart, RefPart, RefPart, RefPart);
+
+fn read_area(
+    tokens: &mut TokenReader<'_>,
+    relative_to_base: bool,
+    ctx: &FormulaContext<'_>,
+) -> Option<Area> {
+    let first_row_raw = tokens.u32()?;
+    let last_row_raw = tokens.u32()?;
+    let first_col_raw = tokens.u16()?;
+    let last_col_raw = tokens.u16()?;
+
+    let part = |row_raw: u32, col_raw: u16| -> Option<(RefPart, RefPart)> {
+        let col_relative = col_raw & 0x4000 != 0;
+        let row_relative = col_raw & 0x8000 != 0;
+        Some((
+            RefPart {
+                index: read_ref_part_row(row_raw, row_relative, relative_to_base, ctx.base_row)?,
+                relative: row_relative,
+            },
+            RefPart {
+                index: read_ref_part_col(col_raw, col_relative, relative_to_base, ctx.base_col)?,
+                relative: col_relative,
+            },
+        ))
+    };
+    let (first_row, first_col) = part(first_row_raw, first_col_raw)?;
+    let (last_row, last_col) = part(last_row_raw, last_col_raw)?;
+    Some((first_row, first_col, last_row, last_col))
+}
+
+fn column_label(col: u32) -> String {
+    let address = crate::addressing::index_to_address(0, col);
+    address.trim_end_matches('1').to_string()
+}
+
+fn format_row(part: RefPart) -> String {
+    let dollar = if part.relative { "" } else { "$" };
+    format!("{dollar}{}", part.index + 1)
+}
+
+fn format_col(part: RefPart) -> String {
+    let dollar = if part.relative { "" } else { "$" };
+    format!("{dollar}{}", column_label(part.index))
+}
+
+fn format_cell_ref(row: RefPart, col: RefPart) -> String {
+    format!("{}{}", format_col(col), format_row(row))
+}
+
+fn format_area((first_row, first_col, last_row, last_col): Area) -> String {
+    if first_row.index == 0 && last_row.index == MAX_ROW {
+        return format!("{}:{}", format_col(first_col), format_col(last_col));
+    }
+    if first_col.index == 0 && last_col.index == MAX_COL {
+        return format!("{}:{}", format_row(first_row), format_row(last_row));
+    }
+    format!(
+        "{}:{}",
+        format_cell_ref(first_row, first_col),
+        format_cell_ref(last_row, last_col)
+    )
+}
+
+fn format_number(value: f64) -> String {
+    if value.is_finite() && value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{}", value as i64)
+    } else {
+        format!("{value}")
+    }
+}
+
+/// Quote a sheet name the way Excel does inside formula text.
+fn quote_formula_sheet_name(name: &str) -> String {
+    let first_is_digit = name.chars().next().is_some_and(|c| c.is_ascii_digit());
+    let needs_quotes = name.is_empty()
+        || first_is_digit
+        || name
+            .chars()
+            .any(|c| !(c.is_alphanumeric() || c == '_' || c == '.'))
+        || address_to_index(name).is_some_and(|(row, col)| row <= MAX_ROW && col <= MAX_COL);
+    if needs_quotes {
+        format!("'{}'", name.replace('\'', "''"))
+    } else {
+        name.to_string()
+    }
+}
+
+fn sheet_prefix(ctx: &FormulaContext<'_>, ixti: u16) -> Option<String> {
+    let xti = ctx.workbook.extern_sheets.get(ixti as usize)?;
+    let (book_prefix, sheet_names): (String, Vec<&str>) =
+        match ctx.workbook.sup_books.get(xti.sup_book) {
+            None | Some(SupBook::SelfBook) => (
+                String::new(),
+                ctx.workbook
+                    .sheets
+                    .iter()
+                    .map(|s| s.name.as_str())
+                    .collect(),
+            ),
+            Some(SupBook::External { index, sheets }) => (
+                format!("[{index}]"),
+                sheets.iter().map(|s| s.as_str()).collect(),
+            ),
+        };
+    if xti.first_tab < 0 {
+        return Some("#REF!".to_string());
+    }
+    let first = *sheet_names.get(xti.first_tab as usize)?;
+    let label = if xti.last_tab == xti.first_tab || xti.last_tab < 0 {
+        format!("{book_prefix}{first}")
+    } else {
+        let last = *sheet_names.get(xti.last_tab as usize)?;
+        format!("{book_prefix}{first}:{last}")
+    };
+    let quoted = if label.contains(':') {
+        let needs_quotes = [first, label.rsplit(':').next().unwrap_or_default()]
+            .iter()
+            .any(|name| quote_formula_sheet_name(name).starts_with('\''));
+        if needs_quotes {
+            format!("'{}'", label.replace('\'', "''"))
+        } else {
+            label
+        }
+    } else if quote_formula_sheet_name(first).starts_with('\'') {
+        format!("'{}'", label.replace('\'', "''"))
+    } else {
+        label
+    };
+    Some(format!("{quoted}!"))
+}
+
+/// Built-in function table (`Ftab`): name plus the fixed argument count for functions that are
+/// encoded with `PtgFunc`. Variable-arity functions carry their argument count in `PtgFuncVar`.
+fn function_info(iftab: u16) -> Option<(&'static str, Option<u8>)> {
+    let info = match iftab {
+        0 => ("COUNT", None),
+        1 => ("IF", None),
+        2 => ("ISNA", Some(1)),
+        3 => ("ISERROR", Some(1)),
+        4 => ("SUM", None),
+        5 => ("AVERAGE", None),
+        6 => ("MIN", None),
+        7 => ("MAX", None),
+        8 => ("ROW", None),
+        9 => ("COLUMN", None),
+        10 => ("NA", Some(0)),
+        11 => ("NPV", None),
+        12 => ("STDEV", None),
+        13 => ("DOLLAR", None),
+        14 => ("FIXED", None),
+        15 => ("SIN", Some(1)),
+        16 => ("COS", Some(1)),
+        17 => ("TAN", Some(1)),
+        18 => ("ATAN", Some(1)),
+        19 => ("PI", Some(0)),
+        20 => ("SQRT", Some(1)),
+        21 => ("EXP", Some(1)),
+        22 => ("LN", Some(1)),
+        23 => ("LOG10", Some(1)),
+        24 => ("ABS", Some(1)),
+        25 => ("INT", Some(1)),
+        26 => ("SIGN", Some(1)),
+        27 => ("ROUND", Some(2)),
+        28 => ("LOOKUP", None),
+        29 => ("INDEX", None),
+        30 => ("REPT", Some(2)),
+        31 => ("MID", Some(3)),
+        32 => ("LEN", Some(1)),
+        33 => ("VALUE", Some(1)),
+        34 => ("TRUE", Some(0)),
+        35 => ("FALSE", Some(0)),
+        36 => ("AND", None),
+        37 => ("OR", None),
+        38 => ("NOT", Some(1)),
+        39 => ("MOD", Some(2)),
+        40 => ("DCOUNT", Some(3)),
+        41 => ("DSUM", Some(3)),
+        42 => ("DAVERAGE", Some(3)),
+        43 => ("DMIN", Some(3)),
+        44 => ("DMAX", Some(3)),
+        45 => ("DSTDEV", Some(3)),
+        46 => ("VAR", None),
+        47 => ("DVAR", Some(3)),
+        48 => ("TEXT", Some(2)),
+        49 => ("LINEST", None),
+        50 => ("TREND", None),
+        51 => ("LOGEST", None),
+        52 => ("GROWTH", None),
+        56 => ("PV", None),
+        57 => ("FV", None),
+        58 => ("NPER", None),
+        59 => ("PMT", None),
+        60 => ("RATE", None),
+        61 => ("MIRR", Some(3)),
+        62 => ("IRR", None),
+        63 => ("RAND", Some(0)),
+        64 => ("MATCH", None),
+        65 => ("DATE", Some(3)),
+        66 => ("TIME", Some(3)),
+        67 => ("DAY", Some(1)),
+        68 => ("MONTH", Some(1)),
+        69 => ("YEAR", Some(1)),
+        70 => ("WEEKDAY", None),
+        71 => ("HOUR", Some(1)),
+        72 => ("MINUTE", Some(1)),
+        73 => ("SECOND", Some(1)),
+        74 => ("NOW", Some(0)),
+        75 => ("AREAS", Some(1)),
+        76 => ("ROWS", Some(1)),
+        77 => ("COLUMNS", Some(1)),
+        78 => ("OFFSET", None),
+        82 => ("SEARCH", None),
+        83 => ("TRANSPOSE", Some(1)),
+        86 => ("TYPE", Some(1)),
+        97 => ("ATAN2", Some(2)),
+        98 => ("ASIN", Some(1)),
+        99 => ("ACOS", Some(1)),
+        100 => ("CHOOSE", None),
+        101 => ("HLOOKUP", None),
+        102 => ("VLOOKUP", None),
+        105 => ("ISREF", Some(1)),
+        109 => ("LOG", None),
+        111 => ("CHAR", Some(1)),
+        112 => ("LOWER", Some(1)),
+        113 => ("UPPER", Some(1)),
+        114 => ("PROPER", Some(1)),
+        115 => ("LEFT", None),
+        116 => ("RIGHT", None),
+        117 => ("EXACT", Some(2)),
+        118 => ("TRIM", Some(1)),
+        119 => ("REPLACE", Some(4)),
+        120 => ("SUBSTITUTE", None),
+        121 => ("CODE", Some(1)),
+        124 => ("FIND", None),
+        125 => ("CELL", None),
+        126 => ("ISERR", Some(1)),
+        127 => ("ISTEXT", Some(1)),
+        128 => ("ISNUMBER", Some(1)),
+        129 => ("ISBLANK", Some(1)),
+        130 => ("T", Some(1)),
+        131 => ("N", Some(1)),
+        140 => ("DATEVALUE", Some(1)),
+        141 => ("TIMEVALUE", Some(1)),
+        142 => ("SLN", Some(3)),
+        143 => ("SYD", Some(4)),
+        144 => ("DDB", None),
+        148 => ("INDIRECT", None),
+        162 => ("CLEAN", Some(1)),
+        163 => ("MDETERM", Some(1)),
+        164 => ("MINVERSE", Some(1)),
+        165 => ("MMULT", Some(2)),
+        167 => ("IPMT", None),
+        168 => ("PPMT", None),
+        169 => ("COUNTA", None),
+        183 => ("PRODUCT", None),
+        184 => ("FACT", Some(1)),
+        189 => ("DPRODUCT", Some(3)),
+        190 => ("ISNONTEXT", Some(1)),
+        193 => ("STDEVP", None),
+        194 => ("VARP", None),
+        195 => ("DSTDEVP", Some(3)),
+        196 => ("DVARP", Some(3)),
+        197 => ("TRUNC", None),
+        198 => ("ISLOGICAL", Some(1)),
+        199 => ("DCOUNTA", Some(3)),
+        204 => ("USDOLLAR", None),
+        205 => ("FINDB", None),
+        206 => ("SEARCHB", None),
+        207 => ("REPLACEB", Some(4)),
+        208 => ("LEFTB", None),
+        209 => ("RIGHTB", None),
+        210 => ("MIDB", Some(3)),
+        211 => ("LENB", Some(1)),
+        212 => ("ROUNDUP", Some(2)),
+        213 => ("ROUNDDOWN", Some(2)),
+        214 => ("ASC", Some(1)),
+        215 => ("DBCS", Some(1)),
+        216 => ("RANK", None),
+        219 => ("ADDRESS", None),
+        220 => ("DAYS360", None),
+        221 => ("TODAY", Some(0)),
+        222 => ("VDB", None),
+        227 => ("MEDIAN", None),
+        228 => ("SUMPRODUCT", None),
+        229 => ("SINH", Some(1)),
+        230 => ("COSH", Some(1)),
+        231 => ("TANH", Some(1)),
+        232 => ("ASINH", Some(1)),
+        233 => ("ACOSH", Some(1)),
+        234 => ("ATANH", Some(1)),
+        235 => ("DGET", Some(3)),
+        244 => ("INFO", Some(1)),
+        247 => ("DB", None),
+        252 => ("FREQUENCY", Some(2)),
+        261 => ("ERROR.TYPE", Some(1)),
+        269 => ("AVEDEV", None),
+        270 => ("BETADIST", None),
+        271 => ("GAMMALN", Some(1)),
+        272 => ("BETAINV", None),
+        273 => ("BINOMDIST", Some(4)),
+        274 => ("CHIDIST", Some(2)),
+        275 => ("CHIINV", Some(2)),
+        276 => ("COMBIN", Some(2)),
+        277 => ("CONFIDENCE", Some(3)),
+        278 => ("CRITBINOM", Some(3)),
+        279 => ("EVEN", Some(1)),
+        280 => ("EXPONDIST", Some(3)),
+        281 => ("FDIST", Some(3)),
+        282 => ("FINV", Some(3)),
+        283 => ("FISHER", Some(1)),
+        284 => ("FISHERINV", Some(1)),
+        285 => ("FLOOR", Some(2)),
+        286 => ("GAMMADIST", Some(4)),
+        287 => ("GAMMAINV", Some(3)),
+        288 => ("CEILING", Some(2)),
+        289 => ("HYPGEOMDIST", Some(4)),
+        290 => ("LOGNORMDIST", Some(3)),
+        291 => ("LOGINV", Some(3)),
+        292 => ("NEGBINOMDIST", Some(3)),
+        293 => ("NORMDIST", Some(4)),
+        294 => ("NORMSDIST", Some(1)),
+        295 => ("NORMINV", Some(3)),
+        296 => ("NORMSINV", Some(1)),
+        297 => ("STANDARDIZE", Some(3)),
+        298 => ("ODD", Some(1)),
+        299 => ("PERMUT", Some(2)),
+        300 => ("POISSON", Some(3)),
+        301 => ("TDIST", Some(3)),
+        302 => ("WEIBULL", Some(4)),
+        303 => ("SUMXMY2", Some(2)),
+        304 => ("SUMX2MY2", Some(2)),
+        305 => ("SUMX2PY2", Some(2)),
+        306 => ("CHITEST", Some(2)),
+        307 => ("CORREL", Some(2)),
+        308 => ("COVAR", Some(2)),
+        309 => ("FORECAST", Some(3)),
+        310 => ("FTEST", Some(2)),
+        311 => ("INTERCEPT", Some(2)),
+        312 => ("PEARSON", Some(2)),
+        313 => ("RSQ", Some(2)),
+        314 => ("STEYX", Some(2)),
+        315 => ("SLOPE", Some(2)),
+        316 => ("TTEST", Some(4)),
+        317 => ("PROB", None),
+        318 => ("DEVSQ", None),
+        319 => ("GEOMEAN", None),
+        320 => ("HARMEAN", None),
+        321 => ("SUMSQ", None),
+        322 => ("KURT", None),
+        323 => ("SKEW", None),
+        324 => ("ZTEST", None),
+        325 => ("LARGE", Some(2)),
+        326 => ("SMALL", Some(2)),
+        327 => ("QUARTILE", Some(2)),
+        328 => ("PERCENTILE", Some(2)),
+        329 => ("PERCENTRANK", None),
+        330 => ("MODE", None),
+        331 => ("TRIMMEAN", Some(2)),
+        332 => ("TINV", Some(2)),
+        336 => ("CONCATENATE", None),
+        337 => ("POWER", Some(2)),
+        342 => ("RADIANS", Some(1)),
+        343 => ("DEGREES", Some(1)),
+        344 => ("SUBTOTAL", None),
+        345 => ("SUMIF", None),
+        346 => ("COUNTIF", Some(2)),
+        347 => ("COUNTBLANK", Some(1)),
+        350 => ("ISPMT", Some(4)),
+        351 => ("DATEDIF", Some(3)),
+        354 => ("ROMAN", None),
+        358 => ("GETPIVOTDATA", None),
+        359 => ("HYPERLINK", None),
+        360 => ("PHONETIC", Some(1)),
+        361 => ("AVERAGEA", None),
+        362 => ("MAXA", None),
+        363 => ("MINA", None),
+        364 => ("STDEVPA", None),
+        365 => ("VARPA", None),
+        366 => ("STDEVA", None),
+        367 => ("VARA", None),
+        368 => ("BAHTTEXT", Some(1)),
+        379 => ("RTD", None),
+        380 => ("CUBEVALUE", None),
+        381 => ("CUBEMEMBER", None),
+        382 => ("CUBEMEMBERPROPERTY", None),
+        383 => ("CUBERANKEDMEMBER", None),
+        384 => ("HEX2BIN", None),
+        385 => ("HEX2DEC", None),
+        386 => ("HEX2OCT", None),
+        387 => ("DEC2BIN", None),
+        388 => ("DEC2HEX", None),
+        389 => ("DEC2OCT", None),
+        390 => ("OCT2BIN", None),
+        391 => ("OCT2HEX", None),
+        392 => ("OCT2DEC", None),
+        393 => ("BIN2DEC", None),
+        394 => ("BIN2OCT", None),
+        395 => ("BIN2HEX", None),
+        396 => ("IMSUB", None),
+        397 => ("IMDIV", None),
+        398 => ("IMPOWER", None),
+        399 => ("IMABS", None),
+        400 => ("IMSQRT", None),
+        401 => ("IMLN", None),
+        402 => ("IMLOG2", None),
+        403 => ("IMLOG10", None),
+        404 => ("IMSIN", None),
+        405 => ("IMCOS", None),
+        406 => ("IMEXP", None),
+        407 => ("IMARGUMENT", None),
+        408 => ("IMCONJUGATE", None),
+        409 => ("IMAGINARY", None),
+        410 => ("IMREAL", None),
+        411 => ("COMPLEX", None),
+        412 => ("IMSUM", None),
+        413 => ("IMPRODUCT", None),
+        414 => ("SERIESSUM", None),
+        415 => ("FACTDOUBLE", None),
+        416 => ("SQRTPI", None),
+        417 => ("QUOTIENT", None),
+        418 => ("DELTA", None),
+        419 => ("GESTEP", None),
+        420 => ("ISEVEN", None),
+        421 => ("ISODD", None),
+        422 => ("MROUND", None),
+        423 => ("ERF", None),
+        424 => ("ERFC", None),
+        425 => ("BESSELJ", None),
+        426 => ("BESSELK", None),
+        427 => ("BESSELY", None),
+        428 => ("BESSELI", None),
+        429 => ("XIRR", None),
+        430 => ("XNPV", None),
+        431 => ("PRICEMAT", None),
+        432 => ("YIELDMAT", None),
+        433 => ("INTRATE", None),
+        434 => ("RECEIVED", None),
+        435 => ("DISC", None),
+        436 => ("PRICEDISC", None),
+        437 => ("YIELDDISC", None),
+        438 => ("TBILLEQ", None),
+        439 => ("TBILLPRICE", None),
+        440 => ("TBILLYIELD", None),
+        441 => ("PRICE", None),
+        442 => ("YIELD", None),
+        443 => ("DOLLARDE", None),
+        444 => ("DOLLARFR", None),
+        445 => ("NOMINAL", None),
+        446 => ("EFFECT", None),
+        447 => ("CUMPRINC", None),
+        448 => ("CUMIPMT", None),
+        449 => ("EDATE", None),
+        450 => ("EOMONTH", None),
+        451 => ("YEARFRAC", None),
+        452 => ("COUPDAYBS", None),
+        453 => ("COUPDAYS", None),
+        454 => ("COUPDAYSNC", None),
+        455 => ("COUPNCD", None),
+        456 => ("COUPNUM", None),
+        457 => ("COUPPCD", None),
+        458 => ("DURATION", None),
+        459 => ("MDURATION", None),
+        460 => ("ODDLPRICE", None),
+        461 => ("ODDLYIELD", None),
+        462 => ("ODDFPRICE", None),
+        463 => ("ODDFYIELD", None),
+        464 => ("RANDBETWEEN", None),
+        465 => ("WEEKNUM", None),
+        466 => ("AMORDEGRC", None),
+        467 => ("AMORLINC", None),
+        468 => ("CONVERT", None),
+        469 => ("ACCRINT", None),
+        470 => ("ACCRINTM", None),
+        471 => ("WORKDAY", None),
+        472 => ("NETWORKDAYS", None),
+        473 => ("GCD", None),
+        474 => ("MULTINOMIAL", None),
+        475 => ("LCM", None),
+        476 => ("FVSCHEDULE", None),
+        477 => ("CUBEKPIMEMBER", None),
+        478 => ("CUBESET", None),
+        479 => ("CUBESETCOUNT", None),
+        480 => ("IFERROR", Some(2)),
+        481 => ("COUNTIFS", None),
+        482 => ("SUMIFS", None),
+        483 => ("AVERAGEIF", None),
+        484 => ("AVERAGEIFS", None),
+        _ => return None,
+    };
+    Some(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn varint(mut value: u32, out: &mut Vec<u8>) {
+        loop {
+            let byte = (value & 0x7F) as u8;
+            value >>= 7;
+            if value == 0 {
+                out.push(byte);
+                break;
+            }
+            out.push(byte | 0x80);
+        }
+    }
+
+    fn record(id: u32, payload: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        varint(id, &mut out);
+        varint(payload.len() as u32, &mut out);
+        out.extend_from_slice(payload);
+        out
+    }
+
+    fn wide(text: &str) -> Vec<u8> {
+        let units: Vec<u16> = text.encode_utf16().collect();
+        let mut out = (units.len() as u32).to_le_bytes().to_vec();
+        for unit in units {
+            out.extend_from_slice(&unit.to_le_bytes());
+        }
+        out
+    }
+
+    fn formula(rgce: &[u8]) -> Vec<u8> {
+        let mut out = (rgce.len() as u32).to_le_bytes().to_vec();
+        out.extend_from_slice(rgce);
+        out.extend_from_slice(&0u32.to_le_bytes());
+        out
+    }
+
+    fn ptg_ref(ptg: u8, row: u32, col: u16, row_rel: bool, col_rel: bool) -> Vec<u8> {
+        let mut out = vec![ptg];
+        out.extend_from_slice(&row.to_le_bytes());
+        let mut col = col;
+        if col_rel {
+            col |= 0x4000;
+        }
+        if row_rel {
+            col |= 0x8000;
+        }
+        out.extend_from_slice(&col.to_le_bytes());
+        out
+    }
+
+    fn cell_header(col: u32) -> Vec<u8> {
+        let mut out = col.to_le_bytes().to_vec();
+        out.extend_from_slice(&0u32.to_le_bytes());
+        out
+    }
+
+    #[test]
+    fn rk_numbers_decode_integer_and_scaled_forms() {
+        assert_eq!(decode_rk((42u32 << 2) | 0x2), 42.0);
+        assert_eq!(decode_rk((1234u32 << 2) | 0x3), 12.34);
+        let bits = (1.5f64.to_bits() >> 32) as u32;
+        assert_eq!(decode_rk(bits), 1.5);
+    }
+
+    #[test]
+    fn truncated_record_reports_offset() {
+        let err = parse_shared_strings_bin(b"XLSB-STUB", &mut StringPool::new())
+            .expect_err("garbage should not parse");
+        assert_eq!(err.offset, 0);
+    }
+
+    #[test]
+    fn formulas_decompile_refs_operators_and_functions() {
+        let workbook = BinaryWorkbook {
+            sheets: vec![SheetDescriptor {
+                name: "Data Sheet".to_string(),
+                rel_id: None,
+                sheet_id: Some(1),
+                visibility: SheetVisibility::Visible,
+            }],
+            extern_sheets: vec![Xti {
+                sup_book: 0,
+                first_tab: 0,
+                last_tab: 0,
+            }],
+            sup_books: vec![SupBook::SelfBook],
+            ..Default::default()
+        };
+
+        // SUM(A1:$B$2)*2
+        let mut rgce = vec![0x25];
+        rgce.extend_from_slice(&0u32.to_le_bytes());
+        rgce.extend_from_slice(&1u32.to_le_bytes());
+        rgce.extend_from_slice(&0xC000u16.to_le_bytes());
+        rgce.extend_from_slice(&1u16.to_le_bytes());
+        rgce.extend_from_slice(&[0x22, 1, 4, 0]);
+        rgce.extend_from_slice(&[0x1E, 2, 0, 0x05]);
+        let raw = RawFormula {
+            rgce,
+            extra: Vec::new(),
+        };
+        assert_eq!(render_formula(&raw, &workbook, 0, 0), "SUM(A1:$B$2)*2");
+
+        // 'Data Sheet'!C3&"x"
+        let mut rgce = vec![0x3A, 0, 0];
+        rgce.extend_from_slice(&ptg_ref(0, 2, 2, true, true)[1..]);
+        rgce.extend_from_slice(&[0x17, 1, 0, b'x', 0, 0x08]);
+        let raw = RawFormula {
+            rgce,
+            extra: Vec::new(),
+        };
+        assert_eq!(
+            render_formula(&raw, &workbook, 0, 0),
+            "'Data Sheet'!C3&\"x\""
+        );
+
+        // Unknown tokens fall back to a stable placeholder.
+        let raw = RawFormula {
+            rgce: vec![0x18, 0x19],
+            extra: Vec::new(),
+        };
+        let rendered = render_formula(&raw, &workbook, 0, 0);
+        assert!(rendered.starts_with("<unsupported xlsb formula "));
+        assert_eq!(rendered, render_formula(&raw, &workbook, 5, 5));
+    }
+
+    #[test]
+    fn rich_string_cells_load_their_text_and_unknown_errors_stay_distinct() {
+        let mut pool = StringPool::new();
+        let workbook = BinaryWorkbook::default();
+
+        let mut sheet = record(BRT_ROW_HDR, &0u32.to_le_bytes());
+        // A1: rich string with one formatting run after the text.
+        let mut cell = cell_header(0);
+        cell.push(0x01);
+        cell.extend(wide("Revenue"));
+        cell.extend_from_slice(&1u32.to_le_bytes());
+        cell.extend_from_slice(&[0, 0, 3, 0]);
+        sheet.extend(record(BRT_CELL_RSTRING, &cell));
+        for (col, code) in [(1u32, 0x2Au8), (2, 0x60), (3, 0x61)] {
+            let mut cell = cell_header(col);
+            cell.push(code);
+            sheet.extend(record(BRT_CELL_ERROR, &cell));
+        }
+
+        let parsed = parse_sheet_bin(&sheet, &[], &workbook, &mut pool).expect("sheet parses");
+        let value = |col| parsed.grid.get(0, col).and_then(|cell| cell.value);
+        let a1 = parsed.grid.get(0, 0).expect("A1 present");
+        assert_eq!(a1.value, Some(CellValue::Text(pool.intern("Revenue"))));
+        assert_eq!(a1.formula, None);
+        assert_eq!(value(1), Some(CellValue::Error(pool.intern("#N/A"))));
+        assert_ne!(value(2), value(1));
+        assert_ne!(value(2), value(3));
+    }
+
+    #[test]
+    fn shared_formula_children_render_relative_to_their_cell() {
+        let mut pool = StringPool::new();
+        let workbook = BinaryWorkbook::default();
+
+        let mut sheet = Vec::new();
+        for row in 0..2u32 {
+            sheet.extend(record(BRT_ROW_HDR, &row.to_le_bytes()));
+            let mut cell = cell_header(1);
+            cell.extend_from_slice(&((row as f64) + 1.0).to_le_bytes());
+            cell.extend_from_slice(&0u16.to_le_bytes());
+            let mut exp = vec![0x01];
+            exp.extend_from_slice(&0u32.to_le_bytes());
+            cell.extend(formula(&exp));
+            sheet.extend(record(BRT_FMLA_NUM, &cell));
+            if row == 0 {
+                let mut shr = Vec::new();
+                for v in [0u32, 1, 1, 1] {
+                    shr.extend_from_slice(&v.to_le_bytes());
+                }
+                // A1 relative to the anchor: PtgRefN with row/col offsets (0, -1).
+                shr.extend(formula(&ptg_ref(0x2C, 0, 0x3FFF, true, true)));
+                sheet.extend(record(BRT_SHR_FMLA, &shr));
+            }
+        }
+
+        let parsed = parse_sheet_bin(&sheet, &[], &workbook, &mut pool).expect("sheet parses");
+        let anchor = parsed.grid.get(0, 1).expect("B1 present");
+        assert_eq!(
+            anchor.formula.map(|id| pool.resolve(id).to_string()),
+            Some("A1".into())
+        );
+        let child = parsed.grid.get(1, 1).expect("B2 present");
+        assert_eq!(
+            child.formula.map(|id| pool.resolve(id).to_string()),
+            Some("A2".into())
+        );
+        assert_eq!(child.value, Some(CellValue::Number(2.0)));
+        assert!(parsed.array_ranges.is_empty());
+    }
+
+    #[test]
+    fn workbook_names_resolve_scope_and_refs() {
+        let mut pool = StringPool::new();
+        let mut bin = Vec::new();
+        let mut sheet = 0u32.to_le_bytes().to_vec();
+        sheet.extend_from_slice(&1u32.to_le_bytes());
+        sheet.extend(wide("rId1"));
+        sheet.extend(wide("Sheet1"));
+        bin.extend(record(BRT_BUNDLE_SH, &sheet));
+
+        // An external book first, so the name's XTI must skip it to reach this workbook.
+        let mut tabs = 1u32.to_le_bytes().to_vec();
+        tabs.extend(wide("Data"));
+        bin.extend(record(BRT_SUP_BOOK_SRC, &wide("other.xlsb")));
+        bin.extend(record(BRT_SUP_TABS, &tabs));
+        bin.extend(record(BRT_SUP_SELF, &[]));
+        let mut xti = 2u32.to_le_bytes().to_vec();
+        for (sup_book, tab) in [(0u32, 0i32), (1, 0)] {
+            xti.extend_from_slice(&sup_book.to_le_bytes());
+            xti.extend_from_slice(&tab.to_le_bytes());
+            xti.extend_from_slice(&tab.to_le_bytes());
+        }
+        bin.extend(record(BRT_EXTERN_SHEET, &xti));
+
+        let mut name = 0u32.to_le_bytes().to_vec();
+        name.push(0);
+        name.extend_from_slice(&0u32.to_le_bytes());
+        name.extend(wide("Local"));
+        let mut rgce = vec![0x3A, 1, 0];
+        rgce.extend_from_slice(&ptg_ref(0, 0, 0, false, false)[1..]);
+        name.extend(formula(&rgce));
+        bin.extend(record(BRT_NAME, &name));
+
+        let workbook = parse_workbook_bin(&bin).expect("workbook parses");
+        assert_eq!(workbook.sheets.len(), 1);
+        assert_eq!(
+            workbook.sup_books,
+            vec![
+                SupBook::External {
+                    index: 1,
+                    sheets: vec!["Data".to_string()],
+                },
+                SupBook::SelfBook,
+            ]
+        );
+        assert_eq!(workbook.sheets[0].rel_id.as_deref(), Some("rId1"));
+
+        let names = workbook.defined_names(&mut pool).expect("names resolve");
+        assert_eq!(names.len(), 1);
+        assert_eq!(pool.resolve(names[0].name), "Sheet1!Local");
+        assert_eq!(pool.resolve(names[0].refers_to), "Sheet1!$A$1");
+        assert_eq!(
+            names[0].scope.map(|id| pool.resolve(id).to_string()),
+            Some("Sheet1".into())
+        );
+    }
+}
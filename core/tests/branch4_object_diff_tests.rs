@@ -59,7 +59,7 @@ fn branch4_charts_emit_added_removed_changed() {
 
     for op in &report_ab.ops {
         match op {
-            DiffOp::ChartChanged { sheet, name } => {
+            DiffOp::ChartChanged { sheet, name, .. } => {
                 assert_eq!(resolve(&report_ab, *sheet), "Sheet1");
                 assert_eq!(resolve(&report_ab, *name), "Chart 1");
                 saw_changed_chart1 = true;
@@ -119,7 +119,7 @@ fn branch4_vba_modules_emit_added_removed_changed() {
     let report_changed = pkg_base.diff(&pkg_changed, &DiffConfig::default());
     let mut saw_module1_changed = false;
     for op in &report_changed.ops {
-        if let DiffOp::VbaModuleChanged { name } = op {
+        if let DiffOp::VbaModuleChanged { name, .. } = op {
             if resolve(&report_changed, *name) == "Module1" {
                 saw_module1_changed = true;
             }
@@ -0,0 +1,264 @@
+use excel_diff::{
+    with_default_session, CellComment, CommentKind, DiffConfig, DiffOp, StringPool, VecSink,
+    WorkbookPackage,
+};
+use std::io::Cursor;
+
+const PERSONS_XML: &str = r#"<personList xmlns="http://schemas.microsoft.com/office/spreadsheetml/2018/threadedcomments"><person displayName="Ann" id="{P1}"/><person displayName="Bo" id="{P2}"/></personList>"#;
+
+/// A sheet whose rows are all distinct and fully populated, so the grid diff aligns rows
+/// rather than falling back to cell edits.
+fn sheet_xml(rows: &[u32]) -> String {
+    let mut xml = String::from(
+        r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData>"#,
+    );
+    for (idx, seed) in rows.iter().enumerate() {
+        let r = idx + 1;
+        xml.push_str(&format!(
+            r#"<row r="{r}"><c r="A{r}"><v>{}</v></c><c r="B{r}"><v>{}</v></c><c r="C{r}"><v>{}</v></c></row>"#,
+            seed,
+            seed * 7 + 3,
+            seed * 13 + 5
+        ));
+    }
+    xml.push_str("</sheetData></worksheet>");
+    xml
+}
+
+fn notes_xml(notes: &[(&str, &str, &str)]) -> String {
+    let mut authors: Vec<&str> = Vec::new();
+    let mut list = String::new();
+    for (cell, author, text) in notes {
+        let id = match authors.iter().position(|a| a == author) {
+            Some(id) => id,
+            None => {
+                authors.push(author);
+                authors.len() - 1
+            }
+        };
+        list.push_str(&format!(
+            r#"<comment ref="{cell}" authorId="{id}"><text><r><t>{text}</t></r></text></comment>"#
+        ));
+    }
+    let authors: String = authors
+        .iter()
+        .map(|a| format!("<author>{a}</author>"))
+        .collect();
+    format!(
+        r#"<comments xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><authors>{authors}</authors><commentList>{list}</commentList></comments>"#
+    )
+}
+
+fn make_xlsx(sheet_xml: &str, notes_xml: Option<&str>, threads_xml: Option<&str>) -> Vec<u8> {
+    use std::io::Write;
+    use zip::write::FileOptions;
+    use zip::CompressionMethod;
+    use zip::ZipWriter;
+
+    let mut sheet_rels = String::from(
+        r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+    );
+    if notes_xml.is_some() {
+        sheet_rels.push_str(r#"<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/comments" Target="../comments1.xml"/>"#);
+    }
+    if threads_xml.is_some() {
+        sheet_rels.push_str(r#"<Relationship Id="rId2" Type="http://schemas.microsoft.com/office/2017/10/relationships/threadedComment" Target="../threadedComments/threadedComment1.xml"/>"#);
+    }
+    sheet_rels.push_str("</Relationships>");
+
+    let mut entries: Vec<(&str, &str)> = vec![
+        ("[Content_Types].xml", "<Types/>"),
+        (
+            "xl/workbook.xml",
+            r#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="Sheet1" sheetId="1" r:id="rId1"/></sheets></workbook>"#,
+        ),
+        (
+            "xl/_rels/workbook.xml.rels",
+            r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/><Relationship Id="rId9" Type="http://schemas.microsoft.com/office/2017/10/relationships/person" Target="persons/person.xml"/></Relationships>"#,
+        ),
+        ("xl/persons/person.xml", PERSONS_XML),
+        ("xl/worksheets/sheet1.xml", sheet_xml),
+        ("xl/worksheets/_rels/sheet1.xml.rels", &sheet_rels),
+    ];
+    if let Some(notes) = notes_xml {
+        entries.push(("xl/comments1.xml", notes));
+    }
+    if let Some(threads) = threads_xml {
+        entries.push(("xl/threadedComments/threadedComment1.xml", threads));
+    }
+
+    let mut buf = Vec::new();
+    {
+        let cursor = Cursor::new(&mut buf);
+        let mut zip = ZipWriter::new(cursor);
+        let options = FileOptions::default().compression_method(CompressionMethod::Stored);
+        for (name, body) in entries {
+            zip.start_file(name, options).expect("start zip entry");
+            zip.write_all(body.as_bytes()).expect("write zip entry");
+        }
+        zip.finish().expect("finish zip");
+    }
+    buf
+}
+
+fn open(bytes: Vec<u8>) -> WorkbookPackage {
+    WorkbookPackage::open(Cursor::new(bytes)).expect("open workbook")
+}
+
+fn resolve(id: excel_diff::StringId) -> String {
+    with_default_session(|session| session.strings.resolve(id).to_string())
+}
+
+#[derive(Debug, PartialEq)]
+enum Change {
+    Added(String, String),
+    Removed(String, String),
+    Changed(String, String, String),
+}
+
+fn comment_changes(ops: &[DiffOp]) -> Vec<Change> {
+    ops.iter()
+        .filter_map(|op| match op {
+            DiffOp::CommentAdded { addr, comment, .. } => {
+                Some(Change::Added(addr.to_a1(), resolve(comment.text)))
+            }
+            DiffOp::CommentRemoved { addr, comment, .. } => {
+                Some(Change::Removed(addr.to_a1(), resolve(comment.text)))
+            }
+            DiffOp::CommentChanged { addr, from, to, .. } => Some(Change::Changed(
+                addr.to_a1(),
+                resolve(from.text),
+                resolve(to.text),
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn notes_follow_inserted_rows_and_report_text_edits() {
+    with_default_session(|session| session.strings = StringPool::new());
+
+    let old = open(make_xlsx(
+        &sheet_xml(&[1, 2, 3, 4, 5, 6, 7, 8]),
+        Some(&notes_xml(&[
+            ("B3", "Ann", "Check total"),
+            ("A6", "Bo", "keep"),
+        ])),
+        None,
+    ));
+    let new = open(make_xlsx(
+        &sheet_xml(&[1, 90, 2, 3, 4, 5, 6, 7, 8]),
+        Some(&notes_xml(&[
+            ("B4", "Ann", "Check total"),
+            ("A7", "Bo", "keep?"),
+        ])),
+        None,
+    ));
+    let report = old.diff(&new, &DiffConfig::default());
+
+    let added_rows: Vec<u32> = report
+        .ops
+        .iter()
+        .filter_map(|op| match op {
+            DiffOp::RowAdded { row_idx, .. } => Some(*row_idx),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(added_rows, vec![1], "ops: {:?}", report.ops);
+    assert_eq!(
+        comment_changes(&report.ops),
+        vec![Change::Changed("A7".into(), "keep".into(), "keep?".into())]
+    );
+}
+
+#[test]
+fn removed_row_reports_its_note_as_removed_at_the_old_address() {
+    with_default_session(|session| session.strings = StringPool::new());
+
+    let old = open(make_xlsx(
+        &sheet_xml(&[1, 2, 3, 4, 5, 6]),
+        Some(&notes_xml(&[
+            ("B3", "Ann", "Check total"),
+            ("C5", "Ann", "ok"),
+        ])),
+        None,
+    ));
+    let new = open(make_xlsx(
+        &sheet_xml(&[1, 2, 4, 5, 6]),
+        Some(&notes_xml(&[("C4", "Ann", "ok"), ("A1", "Bo", "new")])),
+        None,
+    ));
+    let report = old.diff(&new, &DiffConfig::default());
+
+    assert_eq!(
+        comment_changes(&report.ops),
+        vec![
+            Change::Added("A1".into(), "new".into()),
+            Change::Removed("B3".into(), "Check total".into()),
+        ],
+        "ops: {:?}",
+        report.ops
+    );
+}
+
+#[test]
+fn threaded_replies_and_resolution_are_compared() {
+    with_default_session(|session| session.strings = StringPool::new());
+
+    let placeholder = notes_xml(&[("B2", "tc={T1}", "[Threaded comment]")]);
+    let old_threads = r#"<ThreadedComments xmlns="http://schemas.microsoft.com/office/spreadsheetml/2018/threadedcomments"><threadedComment ref="B2" personId="{P1}" id="{T1}"><text>Source?</text></threadedComment></ThreadedComments>"#;
+    let new_threads = r#"<ThreadedComments xmlns="http://schemas.microsoft.com/office/spreadsheetml/2018/threadedcomments"><threadedComment ref="B2" personId="{P1}" id="{T1}" done="1"><text>Source?</text></threadedComment><threadedComment ref="B2" personId="{P2}" id="{T2}" parentId="{T1}"><text>Ledger tab</text></threadedComment></ThreadedComments>"#;
+
+    let sheet = sheet_xml(&[1, 2, 3, 4]);
+    let old = open(make_xlsx(&sheet, Some(&placeholder), Some(old_threads)));
+    let new = open(make_xlsx(&sheet, Some(&placeholder), Some(new_threads)));
+    let report = old.diff(&new, &DiffConfig::default());
+
+    let changed: Vec<(&CellComment, &CellComment)> = report
+        .ops
+        .iter()
+        .filter_map(|op| match op {
+            DiffOp::CommentChanged { from, to, .. } => Some((from, to)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(changed.len(), 1, "ops: {:?}", report.ops);
+    let (from, to) = changed[0];
+    assert_eq!(from.kind, CommentKind::Threaded);
+    assert!(!from.resolved && from.replies.is_empty());
+    assert!(to.resolved);
+    assert_eq!(to.replies.len(), 1);
+    assert_eq!(resolve(to.replies[0].text), "Ledger tab");
+    assert_eq!(to.replies[0].author.map(resolve).as_deref(), Some("Bo"));
+}
+
+#[test]
+fn streaming_diff_aligns_comments_like_the_report() {
+    with_default_session(|session| session.strings = StringPool::new());
+
+    let old = open(make_xlsx(
+        &sheet_xml(&[1, 2, 3, 4, 5, 6]),
+        Some(&notes_xml(&[("B3", "Ann", "a"), ("C6", "Ann", "b")])),
+        None,
+    ));
+    let new = open(make_xlsx(
+        &sheet_xml(&[1, 70, 2, 3, 4, 6]),
+        Some(&notes_xml(&[("B4", "Ann", "a"), ("C6", "Ann", "b2")])),
+        None,
+    ));
+    let config = DiffConfig::default();
+    let report = old.diff(&new, &config);
+
+    let mut sink = VecSink::new();
+    old.diff_streaming(&new, &config, &mut sink)
+        .expect("streaming diff");
+    let streamed = sink.into_ops();
+
+    let expected = comment_changes(&report.ops);
+    assert_eq!(
+        expected,
+        vec![Change::Changed("C6".into(), "b".into(), "b2".into())]
+    );
+    assert_eq!(comment_changes(&streamed), expected);
+}
@@ -0,0 +1,178 @@
+use excel_diff::{
+    with_default_session, CellStyle, ContainerLimits, DiffConfig, DiffOp, StringPool, VecSink,
+    WorkbookPackage,
+};
+use std::io::Cursor;
+
+const SHEET_XML: &str = r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData><row r="1"><c r="A1" s="1"><v>0.5</v></c><c r="B1"><v>2</v></c></row><row r="2"><c r="A2" s="2"/></row></sheetData></worksheet>"#;
+
+fn styles_xml(xf1_num_fmt: u32, xf2_fill: u32) -> String {
+    format!(
+        r#"<styleSheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><fonts count="1"><font><sz val="11"/><name val="Calibri"/></font></fonts><fills count="3"><fill><patternFill patternType="none"/></fill><fill><patternFill patternType="gray125"/></fill><fill><patternFill patternType="solid"><fgColor rgb="FFFFFF00"/></patternFill></fill></fills><borders count="1"><border/></borders><cellXfs count="3"><xf numFmtId="0" fontId="0" fillId="0" borderId="0"/><xf numFmtId="{xf1_num_fmt}" fontId="0" fillId="0" borderId="0"/><xf numFmtId="0" fontId="0" fillId="{xf2_fill}" borderId="0"/></cellXfs></styleSheet>"#
+    )
+}
+
+fn make_xlsx(sheet_xml: &str, styles_xml: &str) -> Vec<u8> {
+    use std::io::Write;
+    use zip::write::FileOptions;
+    use zip::CompressionMethod;
+    use zip::ZipWriter;
+
+    let mut buf = Vec::new();
+    {
+        let cursor = Cursor::new(&mut buf);
+        let mut zip = ZipWriter::new(cursor);
+        let options = FileOptions::default().compression_method(CompressionMethod::Stored);
+
+        let entries: [(&str, &str); 5] = [
+            ("[Content_Types].xml", "<Types/>"),
+            (
+                "xl/workbook.xml",
+                r#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="Sheet1" sheetId="1" r:id="rId1"/></sheets></workbook>"#,
+            ),
+            (
+                "xl/_rels/workbook.xml.rels",
+                r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/></Relationships>"#,
+            ),
+            ("xl/worksheets/sheet1.xml", sheet_xml),
+            ("xl/styles.xml", styles_xml),
+        ];
+        for (name, body) in entries {
+            zip.start_file(name, options).expect("start zip entry");
+            zip.write_all(body.as_bytes()).expect("write zip entry");
+        }
+        zip.finish().expect("finish zip");
+    }
+    buf
+}
+
+fn open(bytes: Vec<u8>) -> WorkbookPackage {
+    WorkbookPackage::open(Cursor::new(bytes)).expect("open workbook")
+}
+
+fn resolve(id: Option<excel_diff::StringId>) -> Option<String> {
+    id.map(|id| with_default_session(|session| session.strings.resolve(id).to_string()))
+}
+
+fn style_ops(ops: &[DiffOp]) -> Vec<(String, CellStyle, CellStyle)> {
+    ops.iter()
+        .filter_map(|op| match op {
+            DiffOp::CellStyleChanged { addr, from, to, .. } => {
+                Some((addr.to_a1(), from.clone(), to.clone()))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn number_format_and_fill_changes_emit_cell_style_ops() {
+    with_default_session(|session| session.strings = StringPool::new());
+
+    let old = open(make_xlsx(SHEET_XML, &styles_xml(2, 2)));
+    let new = open(make_xlsx(SHEET_XML, &styles_xml(9, 0)));
+    let report = old.diff(&new, &DiffConfig::default());
+
+    assert!(
+        !report
+            .ops
+            .iter()
+            .any(|op| matches!(op, DiffOp::CellEdited { .. })),
+        "formatting-only change should not produce CellEdited"
+    );
+
+    let changes = style_ops(&report.ops);
+    assert_eq!(
+        changes.len(),
+        2,
+        "expected A1 and A2 style changes: {changes:?}"
+    );
+
+    let (addr, from, to) = &changes[0];
+    assert_eq!(addr, "A1");
+    assert_eq!(resolve(from.number_format).as_deref(), Some("0.00"));
+    assert_eq!(resolve(to.number_format).as_deref(), Some("0%"));
+    assert_eq!(from.fill, to.fill);
+
+    let (addr, from, to) = &changes[1];
+    assert_eq!(addr, "A2", "styled empty cells are compared too");
+    assert_eq!(
+        resolve(from.fill).as_deref(),
+        Some("pattern=solid;fg=rgb:FFFFFF00")
+    );
+    assert_eq!(resolve(to.fill).as_deref(), Some("pattern=none"));
+}
+
+#[test]
+fn cell_style_diff_can_be_disabled() {
+    with_default_session(|session| session.strings = StringPool::new());
+
+    let old = open(make_xlsx(SHEET_XML, &styles_xml(2, 2)));
+    let new = open(make_xlsx(SHEET_XML, &styles_xml(9, 0)));
+    let config = DiffConfig::builder()
+        .enable_cell_style_diff(false)
+        .build()
+        .expect("valid config should build");
+    let report = old.diff(&new, &config);
+
+    assert!(style_ops(&report.ops).is_empty());
+}
+
+#[test]
+fn fast_diff_reparses_sheets_when_only_styles_change() {
+    with_default_session(|session| session.strings = StringPool::new());
+
+    let old_bytes = make_xlsx(SHEET_XML, &styles_xml(2, 2));
+    let new_bytes = make_xlsx(SHEET_XML, &styles_xml(9, 2));
+
+    let limits = ContainerLimits {
+        max_entries: 10_000,
+        max_part_uncompressed_bytes: 128 * 1024 * 1024,
+        max_total_uncompressed_bytes: 256 * 1024 * 1024,
+    };
+
+    let mut fast_sink = VecSink::new();
+    WorkbookPackage::diff_openxml_streaming_fast_with_limits(
+        Cursor::new(old_bytes.clone()),
+        Cursor::new(new_bytes.clone()),
+        limits,
+        &DiffConfig::default(),
+        &mut fast_sink,
+    )
+    .expect("fast streaming diff should succeed");
+    let fast_ops = fast_sink.into_ops();
+
+    let mut slow_sink = VecSink::new();
+    open(old_bytes)
+        .diff_streaming(&open(new_bytes), &DiffConfig::default(), &mut slow_sink)
+        .expect("slow streaming diff should succeed");
+    let slow_ops = slow_sink.into_ops();
+
+    assert_eq!(style_ops(&fast_ops).len(), 1);
+    assert_eq!(fast_ops, slow_ops);
+}
+
+#[test]
+fn inserted_row_shifts_styles_without_style_ops() {
+    with_default_session(|session| session.strings = StringPool::new());
+
+    let old_sheet = r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData><row r="1"><c r="A1" s="1"><v>1</v></c><c r="B1" s="2"><v>10</v></c></row><row r="2"><c r="A2"><v>2</v></c><c r="B2"><v>20</v></c></row><row r="3"><c r="A3" s="1"><v>3</v></c><c r="B3" s="2"><v>30</v></c></row></sheetData></worksheet>"#;
+    // A row inserted at the top; the old A3 style also changes from xf 1 to xf 2.
+    let new_sheet = r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData><row r="1"><c r="A1"><v>100</v></c><c r="B1"><v>1000</v></c></row><row r="2"><c r="A2" s="1"><v>1</v></c><c r="B2" s="2"><v>10</v></c></row><row r="3"><c r="A3"><v>2</v></c><c r="B3"><v>20</v></c></row><row r="4"><c r="A4" s="2"><v>3</v></c><c r="B4" s="2"><v>30</v></c></row></sheetData></worksheet>"#;
+
+    let old = open(make_xlsx(old_sheet, &styles_xml(2, 2)));
+    let new = open(make_xlsx(new_sheet, &styles_xml(2, 2)));
+    let report = old.diff(&new, &DiffConfig::default());
+
+    assert!(
+        report
+            .ops
+            .iter()
+            .any(|op| matches!(op, DiffOp::RowAdded { row_idx: 0, .. })),
+        "expected the inserted row: {:?}",
+        report.ops
+    );
+    let changes = style_ops(&report.ops);
+    assert_eq!(changes.len(), 1, "expected only A4: {changes:?}");
+    assert_eq!(changes[0].0, "A4");
+}
@@ -0,0 +1,228 @@
+use excel_diff::{with_default_session, ChartField, DiffConfig, DiffOp, StringId, WorkbookPackage};
+use std::io::Cursor;
+
+/// A bar chart with one series per `(name, categories, values)`, preceded by an optional value
+/// axis `<c:max>`.
+fn chart_xml(title: &str, series: &[(&str, &str, &str)], axis_max: Option<&str>) -> String {
+    let mut xml = String::from(concat!(
+        r#"<c:chartSpace xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart" "#,
+        r#"xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main"><c:chart>"#,
+    ));
+    xml.push_str(&format!(
+        r#"<c:title><c:tx><c:rich><a:p><a:r><a:t>{title}</a:t></a:r></a:p></c:rich></c:tx></c:title>"#
+    ));
+    xml.push_str(r#"<c:plotArea><c:barChart><c:barDir val="col"/>"#);
+    for (idx, (name, categories, values)) in series.iter().enumerate() {
+        xml.push_str(&format!(
+            concat!(
+                r#"<c:ser><c:idx val="{idx}"/><c:order val="{idx}"/>"#,
+                r#"<c:tx><c:strRef><c:f>{name}</c:f><c:strCache><c:pt idx="0"><c:v>cached</c:v>"#,
+                r#"</c:pt></c:strCache></c:strRef></c:tx>"#,
+                r#"<c:cat><c:strRef><c:f>{categories}</c:f></c:strRef></c:cat>"#,
+                r#"<c:val><c:numRef><c:f>{values}</c:f></c:numRef></c:val></c:ser>"#,
+            ),
+            idx = idx,
+            name = name,
+            categories = categories,
+            values = values
+        ));
+    }
+    xml.push_str(r#"<c:axId val="10"/><c:axId val="20"/></c:barChart>"#);
+    xml.push_str(concat!(
+        r#"<c:catAx><c:axId val="10"/><c:scaling><c:orientation val="minMax"/></c:scaling>"#,
+        r#"<c:delete val="0"/><c:axPos val="b"/><c:crossAx val="20"/></c:catAx>"#,
+    ));
+    xml.push_str(r#"<c:valAx><c:axId val="20"/><c:scaling><c:orientation val="minMax"/>"#);
+    if let Some(max) = axis_max {
+        xml.push_str(&format!(r#"<c:max val="{max}"/>"#));
+    }
+    xml.push_str(concat!(
+        r#"</c:scaling><c:delete val="0"/><c:axPos val="l"/><c:crossAx val="10"/></c:valAx>"#,
+        r#"</c:plotArea><c:legend><c:legendPos val="b"/></c:legend></c:chart></c:chartSpace>"#,
+    ));
+    xml
+}
+
+fn make_xlsx(sheet_name: &str, chart_xml: &str) -> Vec<u8> {
+    use std::io::Write;
+    use zip::write::FileOptions;
+    use zip::CompressionMethod;
+    use zip::ZipWriter;
+
+    let workbook = format!(
+        concat!(
+            r#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" "#,
+            r#"xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">"#,
+            r#"<sheets><sheet name="{}" sheetId="1" r:id="rId1"/></sheets></workbook>"#,
+        ),
+        sheet_name
+    );
+    let entries: [(&str, &str); 8] = [
+        ("[Content_Types].xml", "<Types/>"),
+        ("xl/workbook.xml", &workbook),
+        (
+            "xl/_rels/workbook.xml.rels",
+            r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/></Relationships>"#,
+        ),
+        (
+            "xl/worksheets/sheet1.xml",
+            r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheetData><row r="1"><c r="A1"><v>1</v></c></row></sheetData><drawing r:id="rId1"/></worksheet>"#,
+        ),
+        (
+            "xl/worksheets/_rels/sheet1.xml.rels",
+            r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/drawing" Target="../drawings/drawing1.xml"/></Relationships>"#,
+        ),
+        (
+            "xl/drawings/drawing1.xml",
+            r#"<xdr:wsDr xmlns:xdr="http://schemas.openxmlformats.org/drawingml/2006/spreadsheetDrawing" xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><xdr:twoCellAnchor><xdr:graphicFrame><xdr:nvGraphicFramePr><xdr:cNvPr id="2" name="Chart 1"/></xdr:nvGraphicFramePr><c:chart r:id="rId1"/></xdr:graphicFrame></xdr:twoCellAnchor></xdr:wsDr>"#,
+        ),
+        (
+            "xl/drawings/_rels/drawing1.xml.rels",
+            r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/chart" Target="../charts/chart1.xml"/></Relationships>"#,
+        ),
+        ("xl/charts/chart1.xml", chart_xml),
+    ];
+
+    let mut buf = Vec::new();
+    {
+        let cursor = Cursor::new(&mut buf);
+        let mut zip = ZipWriter::new(cursor);
+        let options = FileOptions::default().compression_method(CompressionMethod::Stored);
+        for (name, body) in entries {
+            zip.start_file(name, options).expect("start zip entry");
+            zip.write_all(body.as_bytes()).expect("write zip entry");
+        }
+        zip.finish().expect("finish zip");
+    }
+    buf
+}
+
+fn open(bytes: Vec<u8>) -> WorkbookPackage {
+    WorkbookPackage::open(Cursor::new(bytes)).expect("open workbook")
+}
+
+fn resolve(id: Option<StringId>) -> Option<String> {
+    id.map(|id| with_default_session(|session| session.strings.resolve(id).to_string()))
+}
+
+fn chart_ops(old: &WorkbookPackage, new: &WorkbookPackage) -> Vec<DiffOp> {
+    old.diff(new, &DiffConfig::default())
+        .ops
+        .into_iter()
+        .filter(|op| matches!(op, DiffOp::ChartChanged { .. }))
+        .collect()
+}
+
+#[test]
+fn series_references_are_compared_through_sheet_renames() {
+    let old = open(make_xlsx(
+        "Data",
+        &chart_xml(
+            "Sales",
+            &[
+                ("Data!$B$1", "Data!$A$2:$A$40", "Data!$B$2:$B$40"),
+                ("Data!$C$1", "Data!$A$2:$A$40", "Data!$C$2:$C$40"),
+            ],
+            None,
+        ),
+    ));
+    let new = open(make_xlsx(
+        "My Inputs",
+        &chart_xml(
+            "Revenue",
+            &[
+                (
+                    "'My Inputs'!$B$1",
+                    "'My Inputs'!$A$2:$A$40",
+                    "'My Inputs'!$B$2:$B$40",
+                ),
+                (
+                    "'My Inputs'!$C$1",
+                    "'My Inputs'!$A$2:$A$40",
+                    "'My Inputs'!$C$2:$C$50",
+                ),
+            ],
+            Some("100"),
+        ),
+    ));
+
+    let ops = chart_ops(&old, &new);
+    assert_eq!(ops.len(), 1, "expected one ChartChanged, got {ops:?}");
+    let DiffOp::ChartChanged { changes, .. } = &ops[0] else {
+        unreachable!();
+    };
+    let changes: Vec<(
+        ChartField,
+        Option<u32>,
+        Option<String>,
+        Option<String>,
+        Option<String>,
+    )> = changes
+        .iter()
+        .map(|c| {
+            (
+                c.field,
+                c.series,
+                resolve(c.axis),
+                resolve(c.from),
+                resolve(c.to),
+            )
+        })
+        .collect();
+    assert_eq!(
+        changes,
+        vec![
+            (
+                ChartField::Title,
+                None,
+                None,
+                Some("Sales".to_string()),
+                Some("Revenue".to_string())
+            ),
+            (
+                ChartField::SeriesValues,
+                Some(1),
+                None,
+                Some("Data!$C$2:$C$40".to_string()),
+                Some("'My Inputs'!$C$2:$C$50".to_string())
+            ),
+            (
+                ChartField::AxisMax,
+                None,
+                Some("value axis (left)".to_string()),
+                None,
+                Some("100".to_string())
+            ),
+        ]
+    );
+}
+
+#[test]
+fn sheet_rename_alone_does_not_change_a_chart() {
+    let series = |sheet: &str| {
+        (
+            format!("{sheet}!$B$1"),
+            format!("{sheet}!$A$2:$A$9"),
+            format!("{sheet}!$B$2:$B$9"),
+        )
+    };
+    let (old_series, new_series) = (series("Data"), series("Inputs"));
+    let old = open(make_xlsx(
+        "Data",
+        &chart_xml(
+            "Sales",
+            &[(&old_series.0, &old_series.1, &old_series.2)],
+            None,
+        ),
+    ));
+    let new = open(make_xlsx(
+        "Inputs",
+        &chart_xml(
+            "Sales",
+            &[(&new_series.0, &new_series.1, &new_series.2)],
+            None,
+        ),
+    ));
+
+    assert!(chart_ops(&old, &new).is_empty());
+}
@@ -3,10 +3,11 @@
 #![allow(dead_code)]
 
 use excel_diff::{
-    with_default_session, CellSnapshot, CellValue, DiffConfig, DiffOp, DiffReport, DiffSession,
-    DiffSummary, ExtractedColumnTypeChanges, ExtractedRenamePairs, ExtractedString,
-    ExtractedStringList, Grid, QuerySemanticDetail, RenamePair, Sheet, SheetKind, StepChange,
-    StepDiff, StepParams, StepSnapshot, StringId, Workbook, WorkbookPackage,
+    with_default_session, CellComment, CellSnapshot, CellValue, ConditionalFormatRule,
+    DataValidationRule, DiffConfig, DiffOp, DiffReport, DiffSession, DiffSummary, DrawingObject,
+    ExtractedColumnTypeChanges, ExtractedRenamePairs, ExtractedString, ExtractedStringList, Grid,
+    QuerySemanticDetail, RenamePair, Sheet, SheetKind, StepChange, StepDiff, StepParams,
+    StepSnapshot, StringId, Workbook, WorkbookPackage,
 };
 use serde::Deserialize;
 use std::fs::File;
@@ -75,6 +76,7 @@ pub fn single_sheet_workbook(name: &str, grid: Grid) -> Workbook {
             workbook_sheet_id: None,
             kind: SheetKind::Worksheet,
             grid,
+            layout: Default::default(),
         }],
         ..Default::default()
     })
@@ -194,6 +196,34 @@ pub fn collect_string_ids(op: &DiffOp) -> Vec<StringId> {
         }
     }
 
+    fn collect_comment(ids: &mut Vec<StringId>, comment: &CellComment) {
+        ids.extend(comment.author);
+        ids.push(comment.text);
+        for reply in &comment.replies {
+            ids.extend(reply.author);
+            ids.push(reply.text);
+        }
+    }
+
+    fn collect_drawing_object(ids: &mut Vec<StringId>, object: &DrawingObject) {
+        ids.push(object.name);
+        ids.extend(object.text);
+        ids.extend(object.macro_name);
+    }
+
+    fn collect_validation(ids: &mut Vec<StringId>, rule: &DataValidationRule) {
+        ids.push(rule.kind);
+        ids.extend(rule.operator);
+        ids.extend(rule.formula1);
+        ids.extend(rule.formula2);
+    }
+
+    fn collect_conditional_format(ids: &mut Vec<StringId>, rule: &ConditionalFormatRule) {
+        ids.push(rule.kind);
+        ids.extend(rule.operator);
+        ids.extend(rule.formulas.iter().copied());
+    }
+
     fn collect_extracted_string(ids: &mut Vec<StringId>, value: &ExtractedString) {
         if let ExtractedString::Known { value } = value {
             ids.push(*value);
@@ -313,6 +343,20 @@ pub fn collect_string_ids(op: &DiffOp) -> Vec<StringId> {
                 collect_cell_value(&mut ids, value);
             }
         }
+        DiffOp::RecordChanged {
+            sheet, key, fields, ..
+        } => {
+            ids.push(*sheet);
+            for value in key.iter().flatten() {
+                collect_cell_value(&mut ids, value);
+            }
+            for field in fields {
+                ids.extend(field.field);
+                for value in [&field.from, &field.to].into_iter().flatten() {
+                    collect_cell_value(&mut ids, value);
+                }
+            }
+        }
         DiffOp::ColumnAdded { sheet, .. } | DiffOp::ColumnRemoved { sheet, .. } => ids.push(*sheet),
         DiffOp::BlockMovedRows { sheet, .. }
         | DiffOp::BlockMovedColumns { sheet, .. }
@@ -325,9 +369,148 @@ pub fn collect_string_ids(op: &DiffOp) -> Vec<StringId> {
             collect_snapshot(&mut ids, from);
             collect_snapshot(&mut ids, to);
         }
-        DiffOp::VbaModuleAdded { name }
-        | DiffOp::VbaModuleRemoved { name }
-        | DiffOp::VbaModuleChanged { name } => ids.push(*name),
+        DiffOp::CellStyleChanged {
+            sheet, from, to, ..
+        } => {
+            ids.push(*sheet);
+            for style in [from, to] {
+                ids.extend(
+                    [
+                        style.number_format,
+                        style.font,
+                        style.fill,
+                        style.border,
+                        style.alignment,
+                        style.protection,
+                    ]
+                    .into_iter()
+                    .flatten(),
+                );
+            }
+        }
+        DiffOp::CommentAdded { sheet, comment, .. }
+        | DiffOp::CommentRemoved { sheet, comment, .. } => {
+            ids.push(*sheet);
+            collect_comment(&mut ids, comment);
+        }
+        DiffOp::CommentChanged {
+            sheet, from, to, ..
+        } => {
+            ids.push(*sheet);
+            collect_comment(&mut ids, from);
+            collect_comment(&mut ids, to);
+        }
+        DiffOp::DataValidationAdded { sheet, rule }
+        | DiffOp::DataValidationRemoved { sheet, rule } => {
+            ids.push(*sheet);
+            collect_validation(&mut ids, rule);
+        }
+        DiffOp::DataValidationChanged { sheet, from, to } => {
+            ids.push(*sheet);
+            collect_validation(&mut ids, from);
+            collect_validation(&mut ids, to);
+        }
+        DiffOp::ConditionalFormatAdded { sheet, rule }
+        | DiffOp::ConditionalFormatRemoved { sheet, rule } => {
+            ids.push(*sheet);
+            collect_conditional_format(&mut ids, rule);
+        }
+        DiffOp::ConditionalFormatChanged { sheet, from, to } => {
+            ids.push(*sheet);
+            collect_conditional_format(&mut ids, from);
+            collect_conditional_format(&mut ids, to);
+        }
+        DiffOp::SheetTabColorChanged { sheet, from, to } => {
+            ids.push(*sheet);
+            ids.extend(from);
+            ids.extend(to);
+        }
+        DiffOp::SheetProtectionChanged { sheet, from, to } => {
+            ids.push(*sheet);
+            for protection in from.iter().chain(to) {
+                ids.extend(protection.allowed.iter().copied());
+            }
+        }
+        DiffOp::ExcelTableAdded { sheet, name, .. }
+        | DiffOp::ExcelTableRemoved { sheet, name, .. }
+        | DiffOp::ExcelTableResized { sheet, name, .. } => {
+            ids.push(*sheet);
+            ids.push(*name);
+        }
+        DiffOp::ExcelTableRenamed { sheet, from, to } => {
+            ids.push(*sheet);
+            ids.push(*from);
+            ids.push(*to);
+        }
+        DiffOp::ExcelTableColumnRenamed {
+            sheet,
+            table,
+            from,
+            to,
+        } => {
+            ids.push(*sheet);
+            ids.push(*table);
+            ids.push(*from);
+            ids.push(*to);
+        }
+        DiffOp::DrawingObjectAdded { sheet, object }
+        | DiffOp::DrawingObjectRemoved { sheet, object } => {
+            ids.push(*sheet);
+            collect_drawing_object(&mut ids, object);
+        }
+        DiffOp::DrawingObjectMoved { sheet, name, .. } => {
+            ids.push(*sheet);
+            ids.push(*name);
+        }
+        DiffOp::DrawingObjectChanged { sheet, from, to } => {
+            ids.push(*sheet);
+            collect_drawing_object(&mut ids, from);
+            collect_drawing_object(&mut ids, to);
+        }
+        DiffOp::MergedRangeAdded { sheet, .. }
+        | DiffOp::MergedRangeRemoved { sheet, .. }
+        | DiffOp::RowVisibilityChanged { sheet, .. }
+        | DiffOp::ColumnVisibilityChanged { sheet, .. }
+        | DiffOp::RowOutlineChanged { sheet, .. }
+        | DiffOp::ColumnOutlineChanged { sheet, .. }
+        | DiffOp::RowHeightChanged { sheet, .. }
+        | DiffOp::ColumnWidthChanged { sheet, .. }
+        | DiffOp::FreezePaneChanged { sheet, .. }
+        | DiffOp::SpillRangeChanged { sheet, .. }
+        | DiffOp::SheetVisibilityChanged { sheet, .. } => ids.push(*sheet),
+        DiffOp::VbaModuleAdded { name } | DiffOp::VbaModuleRemoved { name } => ids.push(*name),
+        DiffOp::VbaModuleChanged {
+            name,
+            attributes,
+            procedures,
+            hunks,
+            ..
+        } => {
+            ids.push(*name);
+            for attribute in attributes {
+                ids.push(attribute.name);
+                ids.extend(attribute.from);
+                ids.extend(attribute.to);
+            }
+            ids.extend(procedures.iter().map(|procedure| procedure.name));
+            for hunk in hunks {
+                ids.extend(hunk.procedure);
+                ids.extend(hunk.lines.iter().map(|line| line.text));
+            }
+        }
+        DiffOp::VbaReferenceAdded { name, libid } | DiffOp::VbaReferenceRemoved { name, libid } => {
+            ids.push(*name);
+            ids.push(*libid);
+        }
+        DiffOp::VbaReferenceChanged { name, from, to } => {
+            ids.push(*name);
+            ids.push(*from);
+            ids.push(*to);
+        }
+        DiffOp::VbaProjectChanged { from, to, .. } => {
+            ids.extend(*from);
+            ids.extend(*to);
+        }
         DiffOp::NamedRangeAdded { name } | DiffOp::NamedRangeRemoved { name } => ids.push(*name),
         DiffOp::NamedRangeChanged {
             name,
@@ -338,12 +521,85 @@ pub fn collect_string_ids(op: &DiffOp) -> Vec<StringId> {
             ids.push(*old_ref);
             ids.push(*new_ref);
         }
-        DiffOp::ChartAdded { sheet, name }
-        | DiffOp::ChartRemoved { sheet, name }
-        | DiffOp::ChartChanged { sheet, name } => {
+        DiffOp::ChartAdded { sheet, name } | DiffOp::ChartRemoved { sheet, name } => {
+            ids.push(*sheet);
+            ids.push(*name);
+        }
+        DiffOp::ChartChanged {
+            sheet,
+            name,
+            changes,
+        } => {
+            ids.push(*sheet);
+            ids.push(*name);
+            for change in changes {
+                ids.extend(change.axis);
+                ids.extend(change.from);
+                ids.extend(change.to);
+            }
+        }
+        DiffOp::PivotTableAdded { sheet, name } | DiffOp::PivotTableRemoved { sheet, name } => {
             ids.push(*sheet);
             ids.push(*name);
         }
+        DiffOp::PivotTableSourceChanged {
+            sheet,
+            name,
+            from,
+            to,
+            ..
+        } => {
+            ids.push(*sheet);
+            ids.push(*name);
+            for source in [from, to] {
+                ids.push(source.kind);
+                ids.extend(source.sheet);
+                ids.extend(source.name);
+            }
+        }
+        DiffOp::PivotTableLayoutChanged {
+            sheet,
+            name,
+            changes,
+        } => {
+            ids.push(*sheet);
+            ids.push(*name);
+            for change in changes {
+                ids.extend(change.field);
+                ids.extend(change.from);
+                ids.extend(change.to);
+            }
+        }
+        DiffOp::ExternalLinkAdded { target, .. } | DiffOp::ExternalLinkRemoved { target, .. } => {
+            ids.push(*target);
+        }
+        DiffOp::ExternalLinkChanged { from, to } => {
+            for link in [from, to] {
+                ids.push(link.kind);
+                ids.push(link.target);
+                ids.extend(link.sheet_names.iter().copied());
+            }
+        }
+        DiffOp::ConnectionAdded { name } | DiffOp::ConnectionRemoved { name } => ids.push(*name),
+        DiffOp::ConnectionChanged { name, changes } => {
+            ids.push(*name);
+            for change in changes {
+                ids.extend(change.from);
+                ids.extend(change.to);
+            }
+        }
+        DiffOp::WorkbookSettingsChanged { changes } => {
+            for change in changes {
+                ids.extend(change.from);
+                ids.extend(change.to);
+            }
+        }
+        DiffOp::WorkbookProtectionChanged { .. } => {}
+        DiffOp::DocumentPropertyChanged { name, from, to } => {
+            ids.push(*name);
+            ids.extend(from);
+            ids.extend(to);
+        }
         DiffOp::QueryAdded { name }
         | DiffOp::QueryRemoved { name }
         | DiffOp::QueryDefinitionChanged { name, .. } => ids.push(*name),
@@ -460,6 +716,51 @@ pub fn collect_string_ids(op: &DiffOp) -> Vec<StringId> {
         DiffOp::MeasureAdded { name }
         | DiffOp::MeasureRemoved { name }
         | DiffOp::MeasureDefinitionChanged { name, .. } => ids.push(*name),
+        #[cfg(feature = "model-diff")]
+        DiffOp::MeasurePropertyChanged { name, old, new, .. } => {
+            ids.push(*name);
+            ids.extend(*old);
+            ids.extend(*new);
+        }
+        #[cfg(feature = "model-diff")]
+        DiffOp::ModelObjectAdded { table, name, .. }
+        | DiffOp::ModelObjectRemoved { table, name, .. } => {
+            ids.extend(*table);
+            ids.push(*name);
+        }
+        #[cfg(feature = "model-diff")]
+        DiffOp::ModelObjectChanged {
+            table,
+            name,
+            changes,
+            ..
+        } => {
+            ids.extend(*table);
+            ids.push(*name);
+            for change in changes {
+                ids.extend(change.item);
+                ids.extend(change.from);
+                ids.extend(change.to);
+            }
+        }
+        DiffOp::ReportObjectAdded { page, name, .. }
+        | DiffOp::ReportObjectRemoved { page, name, .. } => {
+            ids.extend(*page);
+            ids.push(*name);
+        }
+        DiffOp::ReportObjectChanged {
+            page,
+            name,
+            changes,
+            ..
+        } => {
+            ids.extend(*page);
+            ids.push(*name);
+            for change in changes {
+                ids.extend(change.from);
+                ids.extend(change.to);
+            }
+        }
         _ => {}
     }
 
@@ -0,0 +1,258 @@
+use excel_diff::{
+    with_default_session, CellValue, DatabaseKeys, DiffConfig, DiffError, DiffOp, StringPool,
+    VecSink, WorkbookPackage,
+};
+use std::io::Cursor;
+
+/// A cell value: numbers are written as `<v>`, anything else as an inline string.
+enum V<'a> {
+    N(f64),
+    S(&'a str),
+}
+
+fn sheet_xml(rows: &[Vec<V>]) -> String {
+    let mut xml = String::from(
+        r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData>"#,
+    );
+    for (idx, row) in rows.iter().enumerate() {
+        let r = idx + 1;
+        xml.push_str(&format!(r#"<row r="{r}">"#));
+        for (col, value) in row.iter().enumerate() {
+            let cell_ref = format!("{}{r}", (b'A' + col as u8) as char);
+            match value {
+                V::N(n) => xml.push_str(&format!(r#"<c r="{cell_ref}"><v>{n}</v></c>"#)),
+                V::S(s) => xml.push_str(&format!(
+                    r#"<c r="{cell_ref}" t="inlineStr"><is><t>{s}</t></is></c>"#
+                )),
+            }
+        }
+        xml.push_str("</row>");
+    }
+    xml.push_str("</sheetData></worksheet>");
+    xml
+}
+
+fn open(rows: &[Vec<V>]) -> WorkbookPackage {
+    use std::io::Write;
+    use zip::write::FileOptions;
+    use zip::CompressionMethod;
+    use zip::ZipWriter;
+
+    let sheet = sheet_xml(rows);
+    let entries: [(&str, &str); 4] = [
+        ("[Content_Types].xml", "<Types/>"),
+        (
+            "xl/workbook.xml",
+            r#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="Data" sheetId="1" r:id="rId1"/></sheets></workbook>"#,
+        ),
+        (
+            "xl/_rels/workbook.xml.rels",
+            r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/></Relationships>"#,
+        ),
+        ("xl/worksheets/sheet1.xml", &sheet),
+    ];
+
+    let mut buf = Vec::new();
+    {
+        let cursor = Cursor::new(&mut buf);
+        let mut zip = ZipWriter::new(cursor);
+        let options = FileOptions::default().compression_method(CompressionMethod::Stored);
+        for (name, body) in entries {
+            zip.start_file(name, options).expect("start zip entry");
+            zip.write_all(body.as_bytes()).expect("write zip entry");
+        }
+        zip.finish().expect("finish zip");
+    }
+    WorkbookPackage::open(Cursor::new(buf)).expect("open workbook")
+}
+
+/// A title row, then a `Region | Id | Qty` header and one row per entry.
+fn regions(rows: &[(&'static str, u32, u32)]) -> Vec<Vec<V<'static>>> {
+    let mut out = vec![
+        vec![V::S("Quarterly orders")],
+        vec![V::S("Region"), V::S("Id"), V::S("Qty")],
+    ];
+    out.extend(
+        rows.iter()
+            .map(|&(region, id, qty)| vec![V::S(region), V::N(id as f64), V::N(qty as f64)]),
+    );
+    out
+}
+
+fn row_and_cell_changes(ops: &[DiffOp]) -> Vec<String> {
+    ops.iter()
+        .filter_map(|op| match op {
+            DiffOp::RowAdded { row_idx, .. } => Some(format!("row+ {row_idx}")),
+            DiffOp::RowRemoved { row_idx, .. } => Some(format!("row- {row_idx}")),
+            DiffOp::CellEdited { addr, .. } => Some(format!("cell {}", addr.to_a1())),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn header_named_composite_key_matches_reordered_rows() {
+    with_default_session(|session| session.strings = StringPool::new());
+
+    let old = open(&regions(&[
+        ("North", 1, 10),
+        ("South", 1, 20),
+        ("North", 2, 30),
+    ]));
+    let new = open(&regions(&[
+        ("North", 2, 30),
+        ("North", 1, 10),
+        ("South", 1, 25),
+    ]));
+    let keys: DatabaseKeys = "[region],[ ID ]".parse().expect("parse keys");
+    let keys = keys.with_header_row(1);
+
+    let report = old
+        .diff_database_mode_with_keys(&new, "Data", &keys, &DiffConfig::default())
+        .expect("database mode diff");
+
+    assert_eq!(
+        row_and_cell_changes(&report.ops),
+        vec!["cell C5".to_string()],
+        "ops: {:?}",
+        report.ops
+    );
+    match report
+        .ops
+        .iter()
+        .find(|op| matches!(op, DiffOp::CellEdited { .. }))
+    {
+        Some(DiffOp::CellEdited { from, to, .. }) => {
+            assert_eq!(from.value, Some(CellValue::Number(20.0)));
+            assert_eq!(to.value, Some(CellValue::Number(25.0)));
+        }
+        other => panic!("expected CellEdited, got {other:?}"),
+    }
+}
+
+#[test]
+fn normalised_key_parts_match_differently_formatted_keys() {
+    with_default_session(|session| session.strings = StringPool::new());
+
+    let old = open(&regions(&[("north ", 1, 10), ("South", 2, 20)]));
+    let new = open(&regions(&[("SOUTH", 2, 20), (" North", 1, 10)]));
+    let config = DiffConfig::default();
+
+    let plain = DatabaseKeys::from_headers(&["Region"]).with_header_row(1);
+    let report = old
+        .diff_database_mode_with_keys(&new, "Data", &plain, &config)
+        .expect("plain key diff");
+    let changes = row_and_cell_changes(&report.ops);
+    assert!(
+        changes.iter().any(|change| change.starts_with("row")),
+        "unnormalised keys should not match: {changes:?}"
+    );
+
+    let keys: DatabaseKeys = "trim(lower([Region]))".parse().expect("parse keys");
+    let report = old
+        .diff_database_mode_with_keys(&new, "Data", &keys.with_header_row(1), &config)
+        .expect("normalised key diff");
+    let changes = row_and_cell_changes(&report.ops);
+    assert!(
+        changes.iter().all(|change| change.starts_with("cell A")),
+        "only the key text itself should differ: {changes:?}"
+    );
+}
+
+#[test]
+fn missing_header_key_reports_the_side_and_available_headers() {
+    with_default_session(|session| session.strings = StringPool::new());
+
+    let old = open(&regions(&[("North", 1, 10)]));
+    let mut new_rows = regions(&[("North", 1, 10)]);
+    new_rows[1][1] = V::S("Order Id");
+    let new = open(&new_rows);
+
+    let keys = DatabaseKeys::from_headers(&["Id"]).with_header_row(1);
+    match old.diff_database_mode_with_keys(&new, "Data", &keys, &DiffConfig::default()) {
+        Err(DiffError::KeyColumnNotFound {
+            column,
+            side,
+            row,
+            available,
+        }) => {
+            assert_eq!((column.as_str(), side.as_str(), row), ("Id", "new", 2));
+            assert_eq!(available, vec!["Region", "Order Id", "Qty"]);
+        }
+        other => panic!("expected KeyColumnNotFound, got {other:?}"),
+    }
+}
+
+#[test]
+fn streaming_header_keys_match_the_report() {
+    with_default_session(|session| session.strings = StringPool::new());
+
+    let old = open(&regions(&[
+        ("North", 1, 10),
+        ("South", 2, 20),
+        ("East", 3, 30),
+    ]));
+    let new = open(&regions(&[
+        ("East", 3, 33),
+        ("West", 4, 40),
+        ("North", 1, 10),
+    ]));
+    let keys = DatabaseKeys::from_headers(&["Id"]).with_header_row(1);
+    let config = DiffConfig::default();
+
+    let report = old
+        .diff_database_mode_with_keys(&new, "Data", &keys, &config)
+        .expect("database mode diff");
+    let mut sink = VecSink::new();
+    let summary = old
+        .diff_database_mode_streaming_with_keys(&new, "Data", &keys, &config, &mut sink)
+        .expect("streaming database mode diff");
+
+    assert_eq!(sink.into_ops(), report.ops);
+    assert_eq!(summary.op_count, report.ops.len());
+}
+
+#[test]
+fn column_inserted_before_the_key_pairs_fields_by_header() {
+    with_default_session(|session| session.strings = StringPool::new());
+
+    let old = open(&regions(&[("North", 1, 10), ("South", 2, 20)]));
+    let mut new_rows = regions(&[("North", 1, 10), ("South", 2, 25)]);
+    for (row, note) in new_rows[1..].iter_mut().zip(["Note", "checked", "pending"]) {
+        row.insert(0, V::S(note));
+    }
+    let new = open(&new_rows);
+    let keys = DatabaseKeys::from_headers(&["Id"]).with_header_row(1);
+
+    let report = old
+        .diff_database_mode_with_keys(&new, "Data", &keys, &DiffConfig::default())
+        .expect("database mode diff");
+
+    let added: Vec<u32> = report
+        .ops
+        .iter()
+        .filter_map(|op| match op {
+            DiffOp::ColumnAdded { col_idx, .. } => Some(*col_idx),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(added, vec![0], "ops: {:?}", report.ops);
+    assert_eq!(
+        row_and_cell_changes(&report.ops),
+        vec!["cell D4".to_string()],
+        "ops: {:?}",
+        report.ops
+    );
+    match report
+        .ops
+        .iter()
+        .find(|op| matches!(op, DiffOp::RecordChanged { .. }))
+    {
+        Some(DiffOp::RecordChanged { fields, .. }) => {
+            assert_eq!(fields.len(), 1);
+            assert_eq!(fields[0].col, 3);
+            assert_eq!(fields[0].field.and_then(|id| report.resolve(id)), Some("Qty"));
+        }
+        other => panic!("expected RecordChanged, got {other:?}"),
+    }
+}
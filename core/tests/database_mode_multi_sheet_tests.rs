@@ -0,0 +1,326 @@
+use excel_diff::{
+    with_default_session, CellValue, DatabaseKeys, DiffConfig, DiffError, DiffOp, DiffReport,
+    SheetKeyMap, StringPool, VecSink, WorkbookPackage,
+};
+use std::io::Cursor;
+
+/// A cell value: numbers are written as `<v>`, anything else as an inline string.
+#[derive(Clone, Copy)]
+enum V<'a> {
+    N(f64),
+    S(&'a str),
+}
+
+fn sheet_xml(rows: &[Vec<V>], merges: &[&str]) -> String {
+    let mut xml = String::from(
+        r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData>"#,
+    );
+    for (idx, row) in rows.iter().enumerate() {
+        let r = idx + 1;
+        xml.push_str(&format!(r#"<row r="{r}">"#));
+        for (col, value) in row.iter().enumerate() {
+            let cell_ref = format!("{}{r}", (b'A' + col as u8) as char);
+            match value {
+                V::N(n) => xml.push_str(&format!(r#"<c r="{cell_ref}"><v>{n}</v></c>"#)),
+                V::S(s) => xml.push_str(&format!(
+                    r#"<c r="{cell_ref}" t="inlineStr"><is><t>{s}</t></is></c>"#
+                )),
+            }
+        }
+        xml.push_str("</row>");
+    }
+    xml.push_str("</sheetData>");
+    if !merges.is_empty() {
+        xml.push_str("<mergeCells>");
+        for range in merges {
+            xml.push_str(&format!(r#"<mergeCell ref="{range}"/>"#));
+        }
+        xml.push_str("</mergeCells>");
+    }
+    xml.push_str("</worksheet>");
+    xml
+}
+
+/// A workbook with one worksheet per `(name, rows)`.
+fn open(sheets: &[(&str, Vec<Vec<V>>)]) -> WorkbookPackage {
+    let sheets: Vec<_> = sheets
+        .iter()
+        .map(|(name, rows)| (*name, rows.clone(), &[][..]))
+        .collect();
+    open_with_merges(&sheets)
+}
+
+/// Like [`open`], with the merged ranges of each sheet.
+fn open_with_merges(sheets: &[(&str, Vec<Vec<V>>, &[&str])]) -> WorkbookPackage {
+    use std::io::Write;
+    use zip::write::FileOptions;
+    use zip::CompressionMethod;
+    use zip::ZipWriter;
+
+    let sheet_entries: String = sheets
+        .iter()
+        .enumerate()
+        .map(|(idx, (name, _, _))| {
+            let n = idx + 1;
+            format!(r#"<sheet name="{name}" sheetId="{n}" r:id="rId{n}"/>"#)
+        })
+        .collect();
+    let rels: String = (1..=sheets.len())
+        .map(|n| {
+            format!(
+                r#"<Relationship Id="rId{n}" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet{n}.xml"/>"#
+            )
+        })
+        .collect();
+    let mut entries: Vec<(String, String)> = vec![
+        ("[Content_Types].xml".into(), "<Types/>".into()),
+        (
+            "xl/workbook.xml".into(),
+            format!(
+                r#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets>{sheet_entries}</sheets></workbook>"#
+            ),
+        ),
+        (
+            "xl/_rels/workbook.xml.rels".into(),
+            format!(
+                r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">{rels}</Relationships>"#
+            ),
+        ),
+    ];
+    for (idx, (_, rows, merges)) in sheets.iter().enumerate() {
+        entries.push((
+            format!("xl/worksheets/sheet{}.xml", idx + 1),
+            sheet_xml(rows, merges),
+        ));
+    }
+
+    let mut buf = Vec::new();
+    {
+        let cursor = Cursor::new(&mut buf);
+        let mut zip = ZipWriter::new(cursor);
+        let options = FileOptions::default().compression_method(CompressionMethod::Stored);
+        for (name, body) in entries {
+            zip.start_file(name, options).expect("start zip entry");
+            zip.write_all(body.as_bytes()).expect("write zip entry");
+        }
+        zip.finish().expect("finish zip");
+    }
+    WorkbookPackage::open(Cursor::new(buf)).expect("open workbook")
+}
+
+fn table(header: &[&'static str], rows: &[&[V<'static>]]) -> Vec<Vec<V<'static>>> {
+    let mut out = vec![header.iter().map(|h| V::S(h)).collect::<Vec<_>>()];
+    out.extend(rows.iter().map(|row| row.to_vec()));
+    out
+}
+
+/// Old and new workbooks: `Customers` and `Orders_2024` are reordered with one edit each,
+/// `Notes` gains a row at the top and `Archive` is removed.
+fn workbooks() -> (WorkbookPackage, WorkbookPackage) {
+    use V::{N, S};
+    let old = open(&[
+        (
+            "Customers",
+            table(
+                &["Id", "Name"],
+                &[&[N(1.0), S("ann")], &[N(2.0), S("bob")], &[N(3.0), S("cy")]],
+            ),
+        ),
+        (
+            "Orders_2024",
+            table(
+                &["Order", "Line", "Qty"],
+                &[
+                    &[N(10.0), N(1.0), N(5.0)],
+                    &[N(10.0), N(2.0), N(6.0)],
+                    &[N(11.0), N(1.0), N(7.0)],
+                ],
+            ),
+        ),
+        ("Notes", table(&["Note"], &[&[S("first")], &[S("second")]])),
+        ("Archive", table(&["Old"], &[&[N(1.0)]])),
+    ]);
+    let new = open(&[
+        (
+            "Customers",
+            table(
+                &["Id", "Name"],
+                &[&[N(3.0), S("cy")], &[N(1.0), S("ann")], &[N(2.0), S("rob")]],
+            ),
+        ),
+        (
+            "Orders_2024",
+            table(
+                &["Order", "Line", "Qty"],
+                &[
+                    &[N(11.0), N(1.0), N(7.0)],
+                    &[N(10.0), N(2.0), N(6.0)],
+                    &[N(10.0), N(1.0), N(9.0)],
+                ],
+            ),
+        ),
+        (
+            "Notes",
+            table(&["Note"], &[&[S("zeroth")], &[S("first")], &[S("second")]]),
+        ),
+    ]);
+    (old, new)
+}
+
+fn sheet_keys() -> SheetKeyMap {
+    SheetKeyMap::new()
+        .with("customers", DatabaseKeys::from_headers(&["Id"]))
+        .with("Orders_*", "[Order],[Line]".parse().expect("parse keys"))
+}
+
+fn changes(report: &DiffReport) -> Vec<String> {
+    let name = |id| report.resolve(id).unwrap_or("<unknown>").to_string();
+    report
+        .ops
+        .iter()
+        .filter_map(|op| match op {
+            DiffOp::SheetRemoved { sheet } => Some(format!("{} removed", name(*sheet))),
+            DiffOp::RowAdded { sheet, row_idx, .. } => {
+                Some(format!("{} row+ {row_idx}", name(*sheet)))
+            }
+            DiffOp::RowRemoved { sheet, row_idx, .. } => {
+                Some(format!("{} row- {row_idx}", name(*sheet)))
+            }
+            DiffOp::CellEdited {
+                sheet, addr, to, ..
+            } => {
+                let value = match to.value {
+                    Some(CellValue::Number(n)) => n.to_string(),
+                    Some(CellValue::Text(id)) => name(id),
+                    _ => String::new(),
+                };
+                Some(format!("{} {} = {value}", name(*sheet), addr.to_a1()))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn mapped_sheets_match_by_key_and_the_rest_fall_back_to_grid_diff() {
+    with_default_session(|session| session.strings = StringPool::new());
+
+    let (old, new) = workbooks();
+    let report = old
+        .diff_database_mode_sheets(&new, &sheet_keys(), &DiffConfig::default())
+        .expect("multi-sheet database diff");
+
+    assert_eq!(
+        changes(&report),
+        vec![
+            "Archive removed".to_string(),
+            "Customers B4 = rob".to_string(),
+            "Notes row+ 1".to_string(),
+            "Orders_2024 C4 = 9".to_string(),
+        ],
+        "ops: {:?}",
+        report.ops
+    );
+    assert!(report.complete, "warnings: {:?}", report.warnings);
+}
+
+#[test]
+fn streaming_multi_sheet_diff_matches_the_report() {
+    with_default_session(|session| session.strings = StringPool::new());
+
+    let (old, new) = workbooks();
+    let config = DiffConfig::default();
+    let report = old
+        .diff_database_mode_sheets(&new, &sheet_keys(), &config)
+        .expect("multi-sheet database diff");
+
+    let mut sink = VecSink::new();
+    let summary = old
+        .diff_database_mode_sheets_streaming(&new, &sheet_keys(), &config, &mut sink)
+        .expect("streaming multi-sheet database diff");
+
+    assert_eq!(sink.into_ops(), report.ops);
+    assert_eq!(summary.op_count, report.ops.len());
+}
+
+#[test]
+fn unknown_sheet_names_and_missing_key_headers_are_errors() {
+    with_default_session(|session| session.strings = StringPool::new());
+
+    let (old, new) = workbooks();
+    let config = DiffConfig::default();
+
+    let typo = sheet_keys().with("Custmers", DatabaseKeys::from_columns(&[0]));
+    match old.diff_database_mode_sheets(&new, &typo, &config) {
+        Err(DiffError::SheetNotFound { requested, .. }) => assert_eq!(requested, "Custmers"),
+        other => panic!("expected SheetNotFound, got {other:?}"),
+    }
+
+    let unmatched_glob = sheet_keys().with("Invoices_*", DatabaseKeys::from_columns(&[0]));
+    assert!(old
+        .diff_database_mode_sheets(&new, &unmatched_glob, &config)
+        .is_ok());
+
+    let missing = SheetKeyMap::new().with("Notes", DatabaseKeys::from_headers(&["Id"]));
+    match old.diff_database_mode_sheets(&new, &missing, &config) {
+        Err(DiffError::KeyColumnNotFound { column, side, .. }) => {
+            assert_eq!((column.as_str(), side.as_str()), ("Id", "old"));
+        }
+        other => panic!("expected KeyColumnNotFound, got {other:?}"),
+    }
+}
+
+#[test]
+fn sheet_objects_follow_keyed_rows_and_are_diffed_on_fallback_sheets() {
+    with_default_session(|session| session.strings = StringPool::new());
+    use V::{N, S};
+
+    let customers = |rows: &[&[V<'static>]]| table(&["Id", "Name", "Note"], rows);
+    let old = open_with_merges(&[
+        (
+            "Customers",
+            customers(&[&[N(1.0), S("ann")], &[N(2.0), S("bob")], &[N(3.0), S("cy")]]),
+            &["B2:C2"],
+        ),
+        ("Notes", table(&["Note"], &[&[S("first")]]), &[]),
+    ]);
+    let new = open_with_merges(&[
+        (
+            "Customers",
+            customers(&[&[N(3.0), S("cy")], &[N(1.0), S("ann")], &[N(2.0), S("bob")]]),
+            &["B3:C3"],
+        ),
+        ("Notes", table(&["Note"], &[&[S("first")]]), &["A1:B1"]),
+    ]);
+    let keys = SheetKeyMap::new()
+        .with("Customers", DatabaseKeys::from_headers(&["Id"]))
+        .with("Notes", DatabaseKeys::from_columns(&[3]));
+
+    let report = old
+        .diff_database_mode_sheets(&new, &keys, &DiffConfig::default())
+        .expect("multi-sheet database diff");
+
+    let merges: Vec<String> = report
+        .ops
+        .iter()
+        .filter_map(|op| match op {
+            DiffOp::MergedRangeAdded { sheet, range } => Some(format!(
+                "{} merge+ {}",
+                report.resolve(*sheet).unwrap_or_default(),
+                range
+            )),
+            DiffOp::MergedRangeRemoved { sheet, range } => Some(format!(
+                "{} merge- {}",
+                report.resolve(*sheet).unwrap_or_default(),
+                range
+            )),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        merges,
+        vec!["Notes merge+ A1:B1".to_string()],
+        "ops: {:?}",
+        report.ops
+    );
+}
@@ -0,0 +1,176 @@
+use excel_diff::{
+    with_default_session, CellValue, DatabaseKeys, DiffConfig, DiffOp, DiffReport, StringPool,
+    VecSink, WorkbookPackage,
+};
+use std::io::Cursor;
+
+/// A cell value: numbers are written as `<v>`, anything else as an inline string.
+enum V<'a> {
+    N(f64),
+    S(&'a str),
+}
+
+fn sheet_xml(rows: &[Vec<V>]) -> String {
+    let mut xml = String::from(
+        r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData>"#,
+    );
+    for (idx, row) in rows.iter().enumerate() {
+        let r = idx + 1;
+        xml.push_str(&format!(r#"<row r="{r}">"#));
+        for (col, value) in row.iter().enumerate() {
+            let cell_ref = format!("{}{r}", (b'A' + col as u8) as char);
+            match value {
+                V::N(n) => xml.push_str(&format!(r#"<c r="{cell_ref}"><v>{n}</v></c>"#)),
+                V::S(s) => xml.push_str(&format!(
+                    r#"<c r="{cell_ref}" t="inlineStr"><is><t>{s}</t></is></c>"#
+                )),
+            }
+        }
+        xml.push_str("</row>");
+    }
+    xml.push_str("</sheetData></worksheet>");
+    xml
+}
+
+fn open(rows: &[Vec<V>]) -> WorkbookPackage {
+    use std::io::Write;
+    use zip::write::FileOptions;
+    use zip::CompressionMethod;
+    use zip::ZipWriter;
+
+    let sheet = sheet_xml(rows);
+    let entries: [(&str, &str); 4] = [
+        ("[Content_Types].xml", "<Types/>"),
+        (
+            "xl/workbook.xml",
+            r#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="Data" sheetId="1" r:id="rId1"/></sheets></workbook>"#,
+        ),
+        (
+            "xl/_rels/workbook.xml.rels",
+            r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/></Relationships>"#,
+        ),
+        ("xl/worksheets/sheet1.xml", &sheet),
+    ];
+
+    let mut buf = Vec::new();
+    {
+        let cursor = Cursor::new(&mut buf);
+        let mut zip = ZipWriter::new(cursor);
+        let options = FileOptions::default().compression_method(CompressionMethod::Stored);
+        for (name, body) in entries {
+            zip.start_file(name, options).expect("start zip entry");
+            zip.write_all(body.as_bytes()).expect("write zip entry");
+        }
+        zip.finish().expect("finish zip");
+    }
+    WorkbookPackage::open(Cursor::new(buf)).expect("open workbook")
+}
+
+/// An `Id | Name | Price | Qty` header and one row per entry.
+fn products(rows: &[(u32, &'static str, u32, u32)]) -> Vec<Vec<V<'static>>> {
+    let mut out = vec![vec![V::S("Id"), V::S("Name"), V::S("Price"), V::S("Qty")]];
+    out.extend(rows.iter().map(|&(id, name, price, qty)| {
+        vec![
+            V::N(id as f64),
+            V::S(name),
+            V::N(price as f64),
+            V::N(qty as f64),
+        ]
+    }));
+    out
+}
+
+/// Record ops rendered as `key@old->new: field old -> new; ...`.
+fn record_changes(report: &DiffReport) -> Vec<String> {
+    let value = |value: &Option<CellValue>| match value {
+        Some(CellValue::Number(n)) => n.to_string(),
+        Some(CellValue::Text(id)) => report.resolve(*id).unwrap_or("<unknown>").to_string(),
+        other => format!("{other:?}"),
+    };
+    report
+        .ops
+        .iter()
+        .filter_map(|op| match op {
+            DiffOp::RecordChanged {
+                key,
+                old_row,
+                new_row,
+                fields,
+                ..
+            } => {
+                let key: Vec<String> = key.iter().map(value).collect();
+                let fields: Vec<String> = fields
+                    .iter()
+                    .map(|field| {
+                        let name = field.field.and_then(|id| report.resolve(id)).unwrap_or("?");
+                        format!("{name} {} -> {}", value(&field.from), value(&field.to))
+                    })
+                    .collect();
+                Some(format!(
+                    "{}@{old_row}->{new_row}: {}",
+                    key.join(","),
+                    fields.join("; ")
+                ))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn matched_records_report_changed_fields_by_header() {
+    with_default_session(|session| session.strings = StringPool::new());
+
+    let old = open(&products(&[
+        (1001, "bolt", 10, 3),
+        (1002, "nut", 5, 8),
+        (1003, "gear", 40, 1),
+    ]));
+    let new = open(&products(&[
+        (1003, "gear", 40, 1),
+        (1001, "bolt", 12, 4),
+        (1002, "washer", 5, 8),
+    ]));
+    let keys = DatabaseKeys::from_headers(&["Id"]);
+
+    let report = old
+        .diff_database_mode_with_keys(&new, "Data", &keys, &DiffConfig::default())
+        .expect("database mode diff");
+
+    assert_eq!(
+        record_changes(&report),
+        vec![
+            "1001@1->2: Price 10 -> 12; Qty 3 -> 4".to_string(),
+            "1002@2->3: Name nut -> washer".to_string(),
+        ],
+        "ops: {:?}",
+        report.ops
+    );
+    let cell_edits = report
+        .ops
+        .iter()
+        .filter(|op| matches!(op, DiffOp::CellEdited { .. }))
+        .count();
+    assert_eq!(cell_edits, 3, "cell edits are still reported");
+    assert!(report.complete, "warnings: {:?}", report.warnings);
+}
+
+#[test]
+fn streaming_record_changes_match_the_report() {
+    with_default_session(|session| session.strings = StringPool::new());
+
+    let old = open(&products(&[(1, "a", 1, 1), (2, "b", 2, 2)]));
+    let new = open(&products(&[(2, "b", 2, 20), (1, "a", 10, 1)]));
+    let keys = DatabaseKeys::from_columns(&[0]);
+    let config = DiffConfig::default();
+    let report = old
+        .diff_database_mode_with_keys(&new, "Data", &keys, &config)
+        .expect("database mode diff");
+
+    let mut sink = VecSink::new();
+    old.diff_database_mode_streaming_with_keys(&new, "Data", &keys, &config, &mut sink)
+        .expect("streaming database mode diff");
+
+    assert_eq!(record_changes(&report).len(), 2);
+    assert_eq!(sink.into_ops(), report.ops);
+}
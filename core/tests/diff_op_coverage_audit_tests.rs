@@ -61,7 +61,11 @@ impl SawOps {
 
                 DiffOp::VbaModuleAdded { .. }
                 | DiffOp::VbaModuleRemoved { .. }
-                | DiffOp::VbaModuleChanged { .. } => self.vba = true,
+                | DiffOp::VbaModuleChanged { .. }
+                | DiffOp::VbaReferenceAdded { .. }
+                | DiffOp::VbaReferenceRemoved { .. }
+                | DiffOp::VbaReferenceChanged { .. }
+                | DiffOp::VbaProjectChanged { .. } => self.vba = true,
 
                 DiffOp::QueryAdded { .. }
                 | DiffOp::QueryRemoved { .. }
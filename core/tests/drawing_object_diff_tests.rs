@@ -0,0 +1,221 @@
+use excel_diff::{
+    with_default_session, DiffConfig, DiffOp, DrawingObjectKind, StringId, WorkbookPackage,
+};
+use std::io::Cursor;
+
+/// What one side of a drawing test puts on the sheet.
+struct Drawing<'a> {
+    /// Values in column A, one row each.
+    rows: &'a [u32],
+    /// Top row of the picture, text box and button anchors.
+    first_row: u32,
+    text: &'a str,
+    macro_name: &'a str,
+    image: &'a [u8],
+}
+
+fn anchor(from: (u32, u32), to: (u32, u32)) -> String {
+    format!(
+        concat!(
+            r#"<xdr:from><xdr:col>{}</xdr:col><xdr:colOff>0</xdr:colOff><xdr:row>{}</xdr:row>"#,
+            r#"<xdr:rowOff>0</xdr:rowOff></xdr:from><xdr:to><xdr:col>{}</xdr:col>"#,
+            r#"<xdr:colOff>0</xdr:colOff><xdr:row>{}</xdr:row><xdr:rowOff>0</xdr:rowOff></xdr:to>"#,
+        ),
+        from.1, from.0, to.1, to.0
+    )
+}
+
+fn drawing_xml(d: &Drawing) -> String {
+    let row = d.first_row;
+    let mut xml = String::from(concat!(
+        r#"<xdr:wsDr "#,
+        r#"xmlns:xdr="http://schemas.openxmlformats.org/drawingml/2006/spreadsheetDrawing" "#,
+        r#"xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" "#,
+        r#"xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" "#,
+        r#"xmlns:mc="http://schemas.openxmlformats.org/markup-compatibility/2006">"#,
+    ));
+    xml.push_str(r#"<xdr:twoCellAnchor editAs="oneCell">"#);
+    xml.push_str(&anchor((row, 2), (row + 4, 4)));
+    xml.push_str(concat!(
+        r#"<xdr:pic><xdr:nvPicPr><xdr:cNvPr id="2" name="Logo"/><xdr:cNvPicPr/></xdr:nvPicPr>"#,
+        r#"<xdr:blipFill><a:blip r:embed="rId1"/></xdr:blipFill></xdr:pic><xdr:clientData/>"#,
+        r#"</xdr:twoCellAnchor><xdr:twoCellAnchor>"#,
+    ));
+    xml.push_str(&anchor((row, 5), (row + 2, 7)));
+    xml.push_str(&format!(
+        concat!(
+            r#"<xdr:sp macro=""><xdr:nvSpPr><xdr:cNvPr id="3" name="Notes"/>"#,
+            r#"<xdr:cNvSpPr txBox="1"/></xdr:nvSpPr><xdr:txBody><a:p><a:r><a:t>{}</a:t></a:r>"#,
+            r#"</a:p></xdr:txBody></xdr:sp><xdr:clientData/></xdr:twoCellAnchor>"#,
+            r#"<mc:AlternateContent><mc:Choice Requires="a14"><xdr:twoCellAnchor>"#,
+        ),
+        d.text
+    ));
+    xml.push_str(&anchor((row + 3, 5), (row + 4, 6)));
+    xml.push_str(&format!(
+        concat!(
+            r#"<xdr:sp macro="{}"><xdr:nvSpPr><xdr:cNvPr id="1025" name="Button 1">"#,
+            r#"<a:extLst><a:ext uri="{{63B3BB69-23CF-44E3-9099-C40C66FF867C}}">"#,
+            r#"<a14:compatExt xmlns:a14="http://schemas.microsoft.com/office/drawing/2010/main" "#,
+            r#"spid="_x0000_s1025"/></a:ext></a:extLst></xdr:cNvPr><xdr:cNvSpPr/></xdr:nvSpPr>"#,
+            r#"<xdr:txBody><a:p><a:r><a:t>Run</a:t></a:r></a:p></xdr:txBody></xdr:sp>"#,
+            r#"<xdr:clientData/></xdr:twoCellAnchor></mc:Choice></mc:AlternateContent></xdr:wsDr>"#,
+        ),
+        d.macro_name
+    ));
+    xml
+}
+
+fn make_xlsx(d: &Drawing) -> Vec<u8> {
+    use std::io::Write;
+    use zip::write::FileOptions;
+    use zip::CompressionMethod;
+    use zip::ZipWriter;
+
+    let mut sheet = String::from(concat!(
+        r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" "#,
+        r#"xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">"#,
+        r#"<sheetData>"#,
+    ));
+    for (idx, value) in d.rows.iter().enumerate() {
+        let r = idx + 1;
+        sheet.push_str(&format!(
+            r#"<row r="{r}"><c r="A{r}"><v>{value}</v></c></row>"#
+        ));
+    }
+    sheet.push_str(r#"</sheetData><drawing r:id="rId1"/></worksheet>"#);
+    let drawing = drawing_xml(d);
+
+    let entries: [(&str, &[u8]); 8] = [
+        ("[Content_Types].xml", b"<Types/>"),
+        (
+            "xl/workbook.xml",
+            br#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="Sheet1" sheetId="1" r:id="rId1"/></sheets></workbook>"#,
+        ),
+        (
+            "xl/_rels/workbook.xml.rels",
+            br#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/></Relationships>"#,
+        ),
+        ("xl/worksheets/sheet1.xml", sheet.as_bytes()),
+        (
+            "xl/worksheets/_rels/sheet1.xml.rels",
+            br#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/drawing" Target="../drawings/drawing1.xml"/></Relationships>"#,
+        ),
+        ("xl/drawings/drawing1.xml", drawing.as_bytes()),
+        (
+            "xl/drawings/_rels/drawing1.xml.rels",
+            br#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image" Target="../media/image1.png"/></Relationships>"#,
+        ),
+        ("xl/media/image1.png", d.image),
+    ];
+
+    let mut buf = Vec::new();
+    {
+        let cursor = Cursor::new(&mut buf);
+        let mut zip = ZipWriter::new(cursor);
+        let options = FileOptions::default().compression_method(CompressionMethod::Stored);
+        for (name, body) in entries {
+            zip.start_file(name, options).expect("start zip entry");
+            zip.write_all(body).expect("write zip entry");
+        }
+        zip.finish().expect("finish zip");
+    }
+    buf
+}
+
+fn open(d: &Drawing) -> WorkbookPackage {
+    WorkbookPackage::open(Cursor::new(make_xlsx(d))).expect("open workbook")
+}
+
+fn resolve(id: Option<StringId>) -> Option<String> {
+    id.map(|id| with_default_session(|session| session.strings.resolve(id).to_string()))
+}
+
+fn drawing_ops(old: &Drawing, new: &Drawing) -> Vec<DiffOp> {
+    open(old)
+        .diff(&open(new), &DiffConfig::default())
+        .ops
+        .into_iter()
+        .filter(|op| {
+            matches!(
+                op,
+                DiffOp::DrawingObjectAdded { .. }
+                    | DiffOp::DrawingObjectRemoved { .. }
+                    | DiffOp::DrawingObjectMoved { .. }
+                    | DiffOp::DrawingObjectChanged { .. }
+            )
+        })
+        .collect()
+}
+
+const BASE: Drawing<'static> = Drawing {
+    rows: &[1, 2, 3, 4, 5, 6, 7, 8],
+    first_row: 1,
+    text: "Fill in column A",
+    macro_name: "[0]!Refresh",
+    image: b"\x89PNG old",
+};
+
+#[test]
+fn text_macro_and_image_changes_are_reported_per_object() {
+    let new = Drawing {
+        text: "Fill in column B",
+        macro_name: "[0]!RefreshAll",
+        image: b"\x89PNG new",
+        ..BASE
+    };
+
+    let ops = drawing_ops(&BASE, &new);
+    let changed: Vec<(DrawingObjectKind, Option<String>, bool)> = ops
+        .iter()
+        .map(|op| match op {
+            DiffOp::DrawingObjectChanged { from, to, .. } => (
+                to.kind,
+                resolve(Some(to.name)),
+                from.image_hash != to.image_hash,
+            ),
+            other => panic!("unexpected op {other:?}"),
+        })
+        .collect();
+    assert_eq!(
+        changed,
+        vec![
+            (DrawingObjectKind::Picture, Some("Logo".to_string()), true),
+            (DrawingObjectKind::TextBox, Some("Notes".to_string()), false),
+            (
+                DrawingObjectKind::FormControl,
+                Some("Button 1".to_string()),
+                false
+            ),
+        ]
+    );
+
+    let DiffOp::DrawingObjectChanged { from, to, .. } = &ops[2] else {
+        unreachable!();
+    };
+    assert_eq!(resolve(from.macro_name).as_deref(), Some("[0]!Refresh"));
+    assert_eq!(resolve(to.macro_name).as_deref(), Some("[0]!RefreshAll"));
+    assert_eq!(resolve(to.text).as_deref(), Some("Run"));
+}
+
+#[test]
+fn moved_objects_are_reported_but_inserted_rows_carry_them() {
+    let moved = Drawing {
+        first_row: 3,
+        ..BASE
+    };
+    let ops = drawing_ops(&BASE, &moved);
+    assert_eq!(ops.len(), 3, "expected three moves, got {ops:?}");
+    let DiffOp::DrawingObjectMoved { name, from, to, .. } = &ops[0] else {
+        panic!("expected a move, got {:?}", ops[0]);
+    };
+    assert_eq!(resolve(Some(*name)).as_deref(), Some("Logo"));
+    assert_eq!((from.from.row, to.from.row), (1, 3));
+
+    let inserted = Drawing {
+        rows: &[100, 1, 2, 3, 4, 5, 6, 7, 8],
+        first_row: 2,
+        ..BASE
+    };
+    assert_eq!(drawing_ops(&BASE, &inserted), Vec::new());
+}
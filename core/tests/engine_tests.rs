@@ -27,6 +27,7 @@ fn make_workbook(sheets: Vec<SheetSpec<'_>>) -> Workbook {
                 workbook_sheet_id: None,
                 kind: SheetKind::Worksheet,
                 grid,
+                layout: Default::default(),
             }
         })
         .collect();
@@ -64,6 +65,7 @@ fn make_sheet_with_kind_and_id(
         workbook_sheet_id,
         kind,
         grid,
+        layout: Default::default(),
     }
 }
 
@@ -183,6 +185,7 @@ fn sheet_identity_includes_kind() {
         workbook_sheet_id: None,
         kind: SheetKind::Worksheet,
         grid: grid.clone(),
+        layout: Default::default(),
     };
 
     let chart = Sheet {
@@ -190,6 +193,7 @@ fn sheet_identity_includes_kind() {
         workbook_sheet_id: None,
         kind: SheetKind::Chart,
         grid,
+        layout: Default::default(),
     };
 
     let old = Workbook {
@@ -386,6 +390,7 @@ fn sheet_identity_includes_kind_for_macro_and_other() {
         workbook_sheet_id: None,
         kind: SheetKind::Macro,
         grid: grid.clone(),
+        layout: Default::default(),
     };
 
     let other_sheet = Sheet {
@@ -393,6 +398,7 @@ fn sheet_identity_includes_kind_for_macro_and_other() {
         workbook_sheet_id: None,
         kind: SheetKind::Other,
         grid,
+        layout: Default::default(),
     };
 
     let old = Workbook {
@@ -518,6 +524,7 @@ fn move_detection_respects_column_gate() {
             workbook_sheet_id: None,
             kind: SheetKind::Worksheet,
             grid: grid_a,
+            layout: Default::default(),
         }],
         ..Default::default()
     };
@@ -527,6 +534,7 @@ fn move_detection_respects_column_gate() {
             workbook_sheet_id: None,
             kind: SheetKind::Worksheet,
             grid: grid_b,
+            layout: Default::default(),
         }],
         ..Default::default()
     };
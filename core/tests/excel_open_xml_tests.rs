@@ -2,8 +2,8 @@ mod common;
 
 use common::{fixture_path, open_fixture_workbook, sid};
 use excel_diff::{
-    open_data_mashup, CellAddress, ContainerError, ContainerLimits, DataMashupError, OpcContainer,
-    PackageError, SheetKind, WorkbookPackage,
+    open_data_mashup, CellAddress, CellValue, ContainerError, ContainerLimits, DataMashupError,
+    DiffConfig, DiffOp, OpcContainer, PackageError, SheetKind, WorkbookPackage,
 };
 use std::fs;
 use std::io::{Cursor, ErrorKind, Write};
@@ -37,6 +37,115 @@ fn write_zip(entries: &[(&str, &str)], path: &Path) {
     writer.finish().expect("finish zip");
 }
 
+fn write_zip_bytes(entries: &[(&str, &[u8])], path: &Path) {
+    let file = fs::File::create(path).expect("create temp zip");
+    let mut writer = ZipWriter::new(file);
+    let options = FileOptions::default().compression_method(CompressionMethod::Stored);
+
+    for (name, contents) in entries {
+        writer.start_file(*name, options).expect("start zip entry");
+        writer.write_all(contents).expect("write zip entry");
+    }
+
+    writer.finish().expect("finish zip");
+}
+
+fn biff12_record(id: u32, payload: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for mut value in [id, payload.len() as u32] {
+        loop {
+            let byte = (value & 0x7F) as u8;
+            value >>= 7;
+            if value == 0 {
+                out.push(byte);
+                break;
+            }
+            out.push(byte | 0x80);
+        }
+    }
+    out.extend_from_slice(payload);
+    out
+}
+
+fn biff12_wide(text: &str) -> Vec<u8> {
+    let units: Vec<u16> = text.encode_utf16().collect();
+    let mut out = (units.len() as u32).to_le_bytes().to_vec();
+    for unit in units {
+        out.extend_from_slice(&unit.to_le_bytes());
+    }
+    out
+}
+
+fn biff12_cell(col: u32) -> Vec<u8> {
+    let mut out = col.to_le_bytes().to_vec();
+    out.extend_from_slice(&0u32.to_le_bytes());
+    out
+}
+
+/// Build a one-sheet `.xlsb` with four rows of `label`, a number in column B (`b3_number` in
+/// row 3), and `=B{row}*2` in column C.
+fn write_minimal_xlsb(path: &Path, label: &str, b3_number: f64) {
+    let content_types = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+  <Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+  <Default Extension="bin" ContentType="application/vnd.ms-excel.sheet.binary.macroEnabled.main"/>
+</Types>"#;
+    let relationships = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Id="rId1"
+                Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet"
+                Target="worksheets/sheet1.bin"/>
+</Relationships>"#;
+
+    let mut bundle = 0u32.to_le_bytes().to_vec();
+    bundle.extend_from_slice(&1u32.to_le_bytes());
+    bundle.extend(biff12_wide("rId1"));
+    bundle.extend(biff12_wide("Data"));
+    let workbook = biff12_record(156, &bundle);
+
+    let mut sst_item = vec![0u8];
+    sst_item.extend(biff12_wide(label));
+    let shared_strings = biff12_record(19, &sst_item);
+
+    let mut sheet = Vec::new();
+    for row in 0..4u32 {
+        let number = if row == 2 {
+            b3_number
+        } else {
+            f64::from(row + 1) * 10.0
+        };
+        sheet.extend(biff12_record(0, &row.to_le_bytes()));
+        let mut a = biff12_cell(0);
+        a.extend_from_slice(&0u32.to_le_bytes());
+        sheet.extend(biff12_record(7, &a));
+        let mut b = biff12_cell(1);
+        b.extend_from_slice(&number.to_le_bytes());
+        sheet.extend(biff12_record(5, &b));
+        let mut c = biff12_cell(2);
+        c.extend_from_slice(&(number * 2.0).to_le_bytes());
+        c.extend_from_slice(&0u16.to_le_bytes());
+        // PtgRef(B{row}, relative) PtgInt(2) PtgMul
+        let mut rgce = vec![0x24];
+        rgce.extend_from_slice(&row.to_le_bytes());
+        rgce.extend_from_slice(&[0x01, 0xC0, 0x1E, 2, 0, 0x05]);
+        c.extend_from_slice(&(rgce.len() as u32).to_le_bytes());
+        c.extend_from_slice(&rgce);
+        c.extend_from_slice(&0u32.to_le_bytes());
+        sheet.extend(biff12_record(9, &c));
+    }
+
+    write_zip_bytes(
+        &[
+            ("[Content_Types].xml", content_types.as_bytes()),
+            ("xl/workbook.bin", &workbook),
+            ("xl/_rels/workbook.bin.rels", relationships.as_bytes()),
+            ("xl/sharedStrings.bin", &shared_strings),
+            ("xl/worksheets/sheet1.bin", &sheet),
+        ],
+        path,
+    );
+}
+
 #[test]
 fn open_minimal_workbook_succeeds() {
     let workbook = open_fixture_workbook("minimal.xlsx");
@@ -166,6 +275,93 @@ fn missing_worksheet_xml_returns_missing_part() {
     let _ = fs::remove_file(&path);
 }
 
+#[test]
+fn xlsb_workbook_parses_values_and_formulas() {
+    let path = temp_xlsx_path("xlsb_values");
+    write_minimal_xlsb(&path, "Revenue", 30.0);
+
+    let file = std::fs::File::open(&path).expect("temp file exists");
+    let package = WorkbookPackage::open(file).expect("xlsb should open");
+    let _ = fs::remove_file(&path);
+
+    assert_eq!(package.workbook.sheets.len(), 1);
+    let sheet = &package.workbook.sheets[0];
+    assert_eq!(sheet.name, sid("Data"));
+    assert_eq!((sheet.grid.nrows, sheet.grid.ncols), (4, 3));
+
+    let a1 = sheet.grid.get(0, 0).expect("A1 present");
+    assert_eq!(a1.value, Some(CellValue::Text(sid("Revenue"))));
+    let b3 = sheet.grid.get(2, 1).expect("B3 present");
+    assert_eq!(b3.value, Some(CellValue::Number(30.0)));
+    let c3 = sheet.grid.get(2, 2).expect("C3 present");
+    assert_eq!(c3.value, Some(CellValue::Number(60.0)));
+    assert_eq!(c3.formula, Some(sid("B3*2")));
+}
+
+#[test]
+fn xlsb_workbooks_diff_like_xlsx() {
+    let old_path = temp_xlsx_path("xlsb_diff_old");
+    let new_path = temp_xlsx_path("xlsb_diff_new");
+    write_minimal_xlsb(&old_path, "Revenue", 30.0);
+    write_minimal_xlsb(&new_path, "Revenue", 35.0);
+
+    let old = WorkbookPackage::open(std::fs::File::open(&old_path).expect("old exists"))
+        .expect("old xlsb should open");
+    let new = WorkbookPackage::open(std::fs::File::open(&new_path).expect("new exists"))
+        .expect("new xlsb should open");
+    let _ = fs::remove_file(&old_path);
+    let _ = fs::remove_file(&new_path);
+
+    let report = old.diff(&new, &DiffConfig::default());
+    let edited: Vec<String> = report
+        .ops
+        .iter()
+        .filter_map(|op| match op {
+            DiffOp::CellEdited { addr, .. } => Some(addr.to_a1()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(edited, vec!["B3".to_string(), "C3".to_string()]);
+}
+
+#[test]
+fn truncated_xlsb_sheet_reports_part_and_offset() {
+    let path = temp_xlsx_path("xlsb_truncated");
+    write_minimal_xlsb(&path, "Revenue", 1.0);
+
+    let bytes = fs::read(&path).expect("read temp xlsb");
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).expect("valid zip");
+    let mut entries: Vec<(String, Vec<u8>)> = Vec::new();
+    for idx in 0..archive.len() {
+        let mut entry = archive.by_index(idx).expect("zip entry");
+        let mut contents = Vec::new();
+        std::io::Read::read_to_end(&mut entry, &mut contents).expect("read entry");
+        if entry.name() == "xl/worksheets/sheet1.bin" {
+            contents.truncate(contents.len() - 3);
+        }
+        entries.push((entry.name().to_string(), contents));
+    }
+    let borrowed: Vec<(&str, &[u8])> = entries
+        .iter()
+        .map(|(name, contents)| (name.as_str(), contents.as_slice()))
+        .collect();
+    write_zip_bytes(&borrowed, &path);
+
+    let file = std::fs::File::open(&path).expect("temp file exists");
+    let err = WorkbookPackage::open(file).expect_err("truncated xlsb sheet should error");
+    let _ = fs::remove_file(&path);
+    match err {
+        PackageError::InvalidBinaryPart {
+            ref part, offset, ..
+        } => {
+            assert_eq!(part, "xl/worksheets/sheet1.bin");
+            assert!(offset > 0, "offset should point at the truncated record");
+        }
+        ref other => panic!("expected InvalidBinaryPart, got {other:?}"),
+    }
+    assert_eq!(err.code(), "EXDIFF_PKG_011");
+}
+
 #[test]
 fn truncated_zip_never_panics() {
     let valid_zip_bytes = {
@@ -0,0 +1,308 @@
+use excel_diff::{
+    with_default_session, CellValue, DiffConfig, DiffOp, DiffReport, StringPool, VecSink,
+    WorkbookPackage,
+};
+use std::io::Cursor;
+
+/// A cell value: numbers are written as `<v>`, anything else as an inline string.
+enum V<'a> {
+    N(f64),
+    S(&'a str),
+}
+
+fn sheet_xml(rows: &[Vec<V>]) -> String {
+    let mut xml = String::from(
+        r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData>"#,
+    );
+    for (idx, row) in rows.iter().enumerate() {
+        let r = idx + 1;
+        xml.push_str(&format!(r#"<row r="{r}">"#));
+        for (col, value) in row.iter().enumerate() {
+            let cell_ref = format!("{}{r}", (b'A' + col as u8) as char);
+            match value {
+                V::N(n) => xml.push_str(&format!(r#"<c r="{cell_ref}"><v>{n}</v></c>"#)),
+                V::S(s) => xml.push_str(&format!(
+                    r#"<c r="{cell_ref}" t="inlineStr"><is><t>{s}</t></is></c>"#
+                )),
+            }
+        }
+        xml.push_str("</row>");
+    }
+    xml.push_str("</sheetData></worksheet>");
+    xml
+}
+
+/// `(id, displayName, ref, [(column id, column name)])`.
+type TableSpec<'a> = (u32, &'a str, &'a str, &'a [(u32, &'a str)]);
+
+fn table_xml((id, name, range, columns): TableSpec) -> String {
+    let cols: String = columns
+        .iter()
+        .map(|(col_id, col)| format!(r#"<tableColumn id="{col_id}" name="{col}"/>"#))
+        .collect();
+    format!(
+        r#"<table xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" id="{id}" name="Table{id}" displayName="{name}" ref="{range}"><autoFilter ref="{range}"/><tableColumns count="{}">{cols}</tableColumns></table>"#,
+        columns.len()
+    )
+}
+
+fn make_xlsx(sheet_xml: &str, tables: &[TableSpec]) -> Vec<u8> {
+    use std::io::Write;
+    use zip::write::FileOptions;
+    use zip::CompressionMethod;
+    use zip::ZipWriter;
+
+    let rels: String = (1..=tables.len())
+        .map(|idx| {
+            format!(
+                r#"<Relationship Id="rId{idx}" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/table" Target="../tables/table{idx}.xml"/>"#
+            )
+        })
+        .collect();
+    let mut entries: Vec<(String, String)> = vec![
+        ("[Content_Types].xml".into(), "<Types/>".into()),
+        (
+            "xl/workbook.xml".into(),
+            r#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="Sheet1" sheetId="1" r:id="rId1"/></sheets></workbook>"#.into(),
+        ),
+        (
+            "xl/_rels/workbook.xml.rels".into(),
+            r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/></Relationships>"#.into(),
+        ),
+        ("xl/worksheets/sheet1.xml".into(), sheet_xml.to_string()),
+        (
+            "xl/worksheets/_rels/sheet1.xml.rels".into(),
+            format!(
+                r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">{rels}</Relationships>"#
+            ),
+        ),
+    ];
+    for (idx, spec) in tables.iter().enumerate() {
+        entries.push((format!("xl/tables/table{}.xml", idx + 1), table_xml(*spec)));
+    }
+
+    let mut buf = Vec::new();
+    {
+        let cursor = Cursor::new(&mut buf);
+        let mut zip = ZipWriter::new(cursor);
+        let options = FileOptions::default().compression_method(CompressionMethod::Stored);
+        for (name, body) in entries {
+            zip.start_file(name, options).expect("start zip entry");
+            zip.write_all(body.as_bytes()).expect("write zip entry");
+        }
+        zip.finish().expect("finish zip");
+    }
+    buf
+}
+
+fn open(bytes: Vec<u8>) -> WorkbookPackage {
+    WorkbookPackage::open(Cursor::new(bytes)).expect("open workbook")
+}
+
+/// Header plus one `(id, name, qty)` row per entry.
+fn orders(rows: &[(u32, &'static str, u32)]) -> Vec<Vec<V<'static>>> {
+    let mut out = vec![vec![V::S("Id"), V::S("Name"), V::S("Qty")]];
+    out.extend(
+        rows.iter()
+            .map(|&(id, name, qty)| vec![V::N(id as f64), V::S(name), V::N(qty as f64)]),
+    );
+    out
+}
+
+/// Table ops rendered as short strings for comparison.
+fn table_changes(report: &DiffReport) -> Vec<String> {
+    let name = |id| report.resolve(id).unwrap_or("<unknown>").to_string();
+    report
+        .ops
+        .iter()
+        .filter_map(|op| match op {
+            DiffOp::ExcelTableAdded { name: n, range, .. } => {
+                Some(format!("added {} {range}", name(*n)))
+            }
+            DiffOp::ExcelTableRemoved { name: n, range, .. } => {
+                Some(format!("removed {} {range}", name(*n)))
+            }
+            DiffOp::ExcelTableRenamed { from, to, .. } => {
+                Some(format!("renamed {} -> {}", name(*from), name(*to)))
+            }
+            DiffOp::ExcelTableResized {
+                name: n, from, to, ..
+            } => Some(format!("resized {} {from} -> {to}", name(*n))),
+            DiffOp::ExcelTableColumnRenamed {
+                table, from, to, ..
+            } => Some(format!(
+                "column {} {} -> {}",
+                name(*table),
+                name(*from),
+                name(*to)
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+const BASE: [(u32, &str, u32); 4] = [
+    (1, "ant", 10),
+    (2, "bee", 20),
+    (3, "cat", 30),
+    (4, "dog", 40),
+];
+
+#[test]
+fn table_add_rename_resize_and_column_rename_are_reported() {
+    with_default_session(|session| session.strings = StringPool::new());
+
+    let old = open(make_xlsx(
+        &sheet_xml(&orders(&BASE)),
+        &[(1, "Orders", "A1:C5", &[(1, "Id"), (2, "Name"), (3, "Qty")])],
+    ));
+    let mut rows = orders(&[
+        (1, "ant", 10),
+        (2, "bee", 20),
+        (3, "cat", 30),
+        (4, "dog", 40),
+        (5, "eel", 50),
+    ]);
+    rows[0][2] = V::S("Quantity");
+    let new = open(make_xlsx(
+        &sheet_xml(&rows),
+        &[
+            (
+                1,
+                "Sales",
+                "A1:C6",
+                &[(1, "Id"), (2, "Name"), (3, "Quantity")],
+            ),
+            (2, "Returns", "E1:F3", &[(1, "Id"), (2, "Reason")]),
+        ],
+    ));
+    let report = old.diff(&new, &DiffConfig::default());
+
+    assert_eq!(
+        table_changes(&report),
+        vec![
+            "renamed Orders -> Sales".to_string(),
+            "resized Sales A1:C5 -> A1:C6".to_string(),
+            "column Sales Qty -> Quantity".to_string(),
+            "added Returns E1:F3".to_string(),
+        ],
+        "ops: {:?}",
+        report.ops
+    );
+}
+
+#[test]
+fn rows_inserted_inside_a_table_do_not_report_a_resize() {
+    with_default_session(|session| session.strings = StringPool::new());
+
+    let old = open(make_xlsx(
+        &sheet_xml(&orders(&BASE)),
+        &[(1, "Orders", "A1:C5", &[(1, "Id"), (2, "Name"), (3, "Qty")])],
+    ));
+    let new = open(make_xlsx(
+        &sheet_xml(&orders(&[
+            (1, "ant", 10),
+            (2, "bee", 20),
+            (9, "fox", 90),
+            (3, "cat", 30),
+            (4, "dog", 40),
+        ])),
+        &[(1, "Orders", "A1:C6", &[(1, "Id"), (2, "Name"), (3, "Qty")])],
+    ));
+    let report = old.diff(&new, &DiffConfig::default());
+
+    assert!(table_changes(&report).is_empty(), "ops: {:?}", report.ops);
+}
+
+/// The new version moves `Qty` to the first column, inserts a row and edits one quantity.
+fn reordered_pair() -> (WorkbookPackage, WorkbookPackage) {
+    let old = open(make_xlsx(
+        &sheet_xml(&orders(&BASE)),
+        &[(1, "Orders", "A1:C5", &[(1, "Id"), (2, "Name"), (3, "Qty")])],
+    ));
+    let mut rows = vec![vec![V::S("Qty"), V::S("Id"), V::S("Name")]];
+    for (id, name, qty) in [
+        (1, "ant", 10),
+        (2, "bee", 20),
+        (9, "fox", 90),
+        (3, "cat", 30),
+        (4, "dog", 44),
+    ] {
+        rows.push(vec![V::N(qty as f64), V::N(id as f64), V::S(name)]);
+    }
+    let new = open(make_xlsx(
+        &sheet_xml(&rows),
+        &[(1, "Orders", "A1:C6", &[(3, "Qty"), (1, "Id"), (2, "Name")])],
+    ));
+    (old, new)
+}
+
+#[test]
+fn table_mode_matches_columns_by_header_and_rows_by_key() {
+    with_default_session(|session| session.strings = StringPool::new());
+
+    let (old, new) = reordered_pair();
+    let report = old
+        .diff_tables_mode(&new, &DiffConfig::default())
+        .expect("table mode diff");
+
+    // Table structure is compared without a row alignment, so the grown range is a resize.
+    let grid_ops: Vec<&DiffOp> = report
+        .ops
+        .iter()
+        .filter(|op| !matches!(op, DiffOp::ExcelTableResized { .. }))
+        .collect();
+    assert_eq!(grid_ops.len(), 3, "ops: {:?}", report.ops);
+    assert!(
+        matches!(grid_ops[0], DiffOp::RowAdded { row_idx: 3, .. }),
+        "ops: {:?}",
+        report.ops
+    );
+    match grid_ops[1] {
+        DiffOp::CellEdited { addr, from, to, .. } => {
+            assert_eq!(addr.to_a1(), "A6");
+            assert_eq!(from.value, Some(CellValue::Number(40.0)));
+            assert_eq!(to.value, Some(CellValue::Number(44.0)));
+        }
+        other => panic!("expected CellEdited, got {other:?}"),
+    }
+    match grid_ops[2] {
+        DiffOp::RecordChanged {
+            key,
+            old_row,
+            new_row,
+            fields,
+            ..
+        } => {
+            assert_eq!(key, &vec![Some(CellValue::Number(4.0))]);
+            assert_eq!((*old_row, *new_row), (4, 5));
+            assert_eq!(fields.len(), 1);
+            assert_eq!(fields[0].col, 0);
+            assert_eq!(
+                fields[0].field.and_then(|id| report.resolve(id)),
+                Some("Qty")
+            );
+        }
+        other => panic!("expected RecordChanged, got {other:?}"),
+    }
+    assert!(report.complete, "warnings: {:?}", report.warnings);
+}
+
+#[test]
+fn table_mode_streaming_matches_the_report() {
+    with_default_session(|session| session.strings = StringPool::new());
+
+    let (old, new) = reordered_pair();
+    let config = DiffConfig::default();
+    let report = old
+        .diff_tables_mode(&new, &config)
+        .expect("table mode diff");
+
+    let mut sink = VecSink::new();
+    let summary = old
+        .diff_tables_mode_streaming(&new, &config, &mut sink)
+        .expect("streaming table mode diff");
+
+    assert_eq!(sink.into_ops(), report.ops);
+    assert_eq!(summary.op_count, report.ops.len());
+}
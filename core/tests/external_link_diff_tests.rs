@@ -0,0 +1,170 @@
+use excel_diff::{
+    with_default_session, ConnectionField, DiffConfig, DiffOp, StringId, WorkbookPackage,
+};
+use std::io::Cursor;
+
+/// What one side of an external-data test contains.
+struct Sources<'a> {
+    /// Target of the single external workbook link.
+    link_target: &'a str,
+    /// ODBC connection string of the `Sales` connection, before redaction.
+    connection: &'a str,
+}
+
+fn link_rels(target: &str) -> String {
+    format!(
+        concat!(
+            r#"<Relationships "#,
+            r#"xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+            r#"<Relationship Id="rId1" "#,
+            r#"Type="http://schemas.openxmlformats.org/officeDocument/2006/"#,
+            r#"relationships/externalLinkPath" "#,
+            r#"Target="{}" TargetMode="External"/></Relationships>"#,
+        ),
+        target
+    )
+}
+
+fn connections_xml(connection: &str) -> String {
+    format!(
+        concat!(
+            r#"<connections xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">"#,
+            r#"<connection id="1" name="Sales" type="1" refreshedVersion="8" background="1">"#,
+            r#"<dbPr connection="{}" command="SELECT * FROM sales"/></connection>"#,
+            r#"</connections>"#,
+        ),
+        connection
+    )
+}
+
+fn make_xlsx(s: &Sources) -> Vec<u8> {
+    use std::io::Write;
+    use zip::write::FileOptions;
+    use zip::CompressionMethod;
+    use zip::ZipWriter;
+
+    let rels = link_rels(s.link_target);
+    let connections = connections_xml(s.connection);
+    let entries: [(&str, &str); 7] = [
+        ("[Content_Types].xml", "<Types/>"),
+        (
+            "xl/workbook.xml",
+            r#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="Summary" sheetId="1" r:id="rId1"/></sheets><externalReferences><externalReference r:id="rId2"/></externalReferences></workbook>"#,
+        ),
+        (
+            "xl/_rels/workbook.xml.rels",
+            r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/externalLink" Target="externalLinks/externalLink1.xml"/><Relationship Id="rId3" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/connections" Target="connections.xml"/></Relationships>"#,
+        ),
+        (
+            "xl/worksheets/sheet1.xml",
+            r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData/></worksheet>"#,
+        ),
+        (
+            "xl/externalLinks/externalLink1.xml",
+            r#"<externalLink xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><externalBook r:id="rId1"><sheetNames><sheetName val="Q3"/></sheetNames></externalBook></externalLink>"#,
+        ),
+        ("xl/externalLinks/_rels/externalLink1.xml.rels", &rels),
+        ("xl/connections.xml", &connections),
+    ];
+
+    let mut buf = Vec::new();
+    {
+        let cursor = Cursor::new(&mut buf);
+        let mut zip = ZipWriter::new(cursor);
+        let options = FileOptions::default().compression_method(CompressionMethod::Stored);
+        for (name, body) in entries {
+            zip.start_file(name, options).expect("start zip entry");
+            zip.write_all(body.as_bytes()).expect("write zip entry");
+        }
+        zip.finish().expect("finish zip");
+    }
+    buf
+}
+
+fn open(s: &Sources) -> WorkbookPackage {
+    WorkbookPackage::open(Cursor::new(make_xlsx(s))).expect("open workbook")
+}
+
+fn resolve(id: Option<StringId>) -> Option<String> {
+    id.map(|id| with_default_session(|session| session.strings.resolve(id).to_string()))
+}
+
+fn source_ops(old: &Sources, new: &Sources) -> Vec<DiffOp> {
+    open(old)
+        .diff(&open(new), &DiffConfig::default())
+        .ops
+        .into_iter()
+        .filter(|op| {
+            matches!(
+                op,
+                DiffOp::ExternalLinkAdded { .. }
+                    | DiffOp::ExternalLinkRemoved { .. }
+                    | DiffOp::ExternalLinkChanged { .. }
+                    | DiffOp::ConnectionAdded { .. }
+                    | DiffOp::ConnectionRemoved { .. }
+                    | DiffOp::ConnectionChanged { .. }
+            )
+        })
+        .collect()
+}
+
+const BASE: Sources<'static> = Sources {
+    link_target: r"file:///\\share\finance\Q3.xlsx",
+    connection: "DSN=Sales;UID=report;PWD=hunter2",
+};
+
+#[test]
+fn repointed_external_link_is_reported() {
+    assert_eq!(source_ops(&BASE, &BASE), Vec::new());
+
+    let local = Sources {
+        link_target: "Q3.xlsx",
+        ..BASE
+    };
+    let ops = source_ops(&BASE, &local);
+    assert_eq!(ops.len(), 1, "expected one link change, got {ops:?}");
+    let DiffOp::ExternalLinkChanged { from, to } = &ops[0] else {
+        panic!("expected a link change, got {:?}", ops[0]);
+    };
+    assert_eq!((from.index, to.index), (1, 1));
+    assert_eq!(
+        (resolve(Some(from.target)), resolve(Some(to.target))),
+        (
+            Some(r"file:///\\share\finance\Q3.xlsx".to_string()),
+            Some("Q3.xlsx".to_string())
+        )
+    );
+    assert_eq!(from.sheet_names, to.sheet_names);
+}
+
+#[test]
+fn connection_string_change_is_reported_with_secrets_redacted() {
+    let prod = Sources {
+        connection: "DSN=SalesProd;UID=report;PWD=correct horse",
+        ..BASE
+    };
+    let ops = source_ops(&BASE, &prod);
+    assert_eq!(ops.len(), 1, "expected one connection change, got {ops:?}");
+    let DiffOp::ConnectionChanged { name, changes } = &ops[0] else {
+        panic!("expected a connection change, got {:?}", ops[0]);
+    };
+    assert_eq!(resolve(Some(*name)).as_deref(), Some("Sales"));
+    let changes: Vec<(ConnectionField, Option<String>, Option<String>)> = changes
+        .iter()
+        .map(|c| (c.field, resolve(c.from), resolve(c.to)))
+        .collect();
+    assert_eq!(
+        changes,
+        vec![(
+            ConnectionField::Connection,
+            Some("DSN=Sales;UID=report;PWD=***".to_string()),
+            Some("DSN=SalesProd;UID=report;PWD=***".to_string()),
+        )]
+    );
+
+    let rotated = Sources {
+        connection: "DSN=Sales;UID=report;PWD=rotated",
+        ..BASE
+    };
+    assert_eq!(source_ops(&BASE, &rotated), Vec::new());
+}
@@ -1,6 +1,6 @@
 use excel_diff::{
-    CellValue, DiffConfig, DiffOp, FormulaDiffResult, Grid, Sheet, SheetKind, StringPool, Workbook,
-    diff_grids_database_mode, diff_workbooks_with_pool,
+    CellValue, DiffConfig, DiffOp, FormulaDiffResult, FormulaEdit, FormulaEditKind, Grid, Sheet,
+    SheetKind, StringPool, Workbook, diff_grids_database_mode, diff_workbooks_with_pool,
 };
 
 fn workbook_with_formula(
@@ -20,6 +20,7 @@ fn workbook_with_formula(
             workbook_sheet_id: None,
             kind: SheetKind::Worksheet,
             grid,
+            layout: Default::default(),
         }],
         ..Default::default()
     }
@@ -101,3 +102,110 @@ fn filled_down_formulas_detect_row_shift() {
         "expected a row insertion ahead of the filled-down formula",
     );
 }
+
+fn formula_edits(old_formula: &str, new_formula: &str) -> Vec<FormulaEdit> {
+    let mut pool = StringPool::new();
+    let sheet = pool.intern("Sheet1");
+    let old = workbook_with_formula(&mut pool, sheet, 0, 0, old_formula);
+    let new = workbook_with_formula(&mut pool, sheet, 0, 0, new_formula);
+
+    let mut config = DiffConfig::default();
+    config.semantic.enable_formula_semantic_diff = true;
+    let report = diff_workbooks_with_pool(&old, &new, &mut pool, &config);
+
+    match cell_edit_op(&report) {
+        DiffOp::CellEdited {
+            formula_diff,
+            formula_edits,
+            ..
+        } => {
+            assert_eq!(formula_diff, FormulaDiffResult::SemanticChange);
+            formula_edits
+        }
+        _ => panic!("expected CellEdited op"),
+    }
+}
+
+fn edit(
+    kind: FormulaEditKind,
+    context: Option<(&str, u32)>,
+    from: Option<&str>,
+    to: Option<&str>,
+) -> FormulaEdit {
+    FormulaEdit {
+        kind,
+        function: context.map(|(name, _)| name.to_string()),
+        argument: context.map(|(_, arg)| arg),
+        from: from.map(str::to_string),
+        to: to.map(str::to_string),
+    }
+}
+
+#[test]
+fn formula_edits_report_function_rename() {
+    assert_eq!(
+        formula_edits("SUM(A1:A10)", "AVERAGE(A1:A10)"),
+        vec![edit(
+            FormulaEditKind::FunctionRenamed,
+            None,
+            Some("SUM"),
+            Some("AVERAGE")
+        )]
+    );
+}
+
+#[test]
+fn formula_edits_locate_changed_reference_argument() {
+    assert_eq!(
+        formula_edits("SUM(A1,B2,C3)", "SUM(A1,B3,C3)"),
+        vec![edit(
+            FormulaEditKind::ReferenceChanged,
+            Some(("SUM", 2)),
+            Some("B2"),
+            Some("B3")
+        )]
+    );
+}
+
+#[test]
+fn formula_edits_report_added_if_branch() {
+    assert_eq!(
+        formula_edits("IF(A1>0,B1)", "IF(A1>0,B1,C1*2)"),
+        vec![edit(
+            FormulaEditKind::Inserted,
+            Some(("IF", 3)),
+            None,
+            Some("C1*2")
+        )]
+    );
+}
+
+#[test]
+fn formula_edits_report_operator_and_replaced_operand() {
+    assert_eq!(
+        formula_edits("A1+B1", "A1-ROUND(B1,2)"),
+        vec![
+            edit(FormulaEditKind::OperatorChanged, None, Some("+"), Some("-")),
+            edit(
+                FormulaEditKind::Replaced,
+                None,
+                Some("B1"),
+                Some("ROUND(B1,2)")
+            ),
+        ]
+    );
+}
+
+#[test]
+fn formula_edits_are_empty_without_semantic_change() {
+    let mut pool = StringPool::new();
+    let sheet = pool.intern("Sheet1");
+    let old = workbook_with_formula(&mut pool, sheet, 0, 0, "SUM(A1,B2)");
+    let new = workbook_with_formula(&mut pool, sheet, 0, 0, "SUM(A1,B3)");
+    let report = diff_workbooks_with_pool(&old, &new, &mut pool, &DiffConfig::default());
+
+    match cell_edit_op(&report) {
+        DiffOp::CellEdited { formula_edits, .. } => assert!(formula_edits.is_empty()),
+        _ => panic!("expected CellEdited op"),
+    }
+}
@@ -60,6 +60,11 @@ fn parses_expected_ast_shapes() {
                 },
             }),
         ),
+        ("A:A", FormulaExpr::NamedRef("A:A".into())),
+        (
+            "Assumptions!$B : $C",
+            FormulaExpr::NamedRef("Assumptions!$B:$C".into()),
+        ),
         (
             "A1^-1",
             FormulaExpr::BinaryOp {
@@ -95,3 +100,35 @@ fn parses_varied_syntaxes() {
         parse_formula(text).unwrap_or_else(|e| panic!("failed to parse {text}: {e}"));
     }
 }
+
+#[test]
+fn rendered_formulas_parse_back_to_the_same_ast() {
+    let samples = [
+        "SUM(A1,B1)",
+        "'My Sheet'!$A$1+'It''s'!B2",
+        "Data!A1:$C$10",
+        "SUM('My Sheet'!B:B)",
+        "[Book1.xlsx]Sheet1!A1",
+        "{1,2;3,4}",
+        "Table1[Column1]",
+        "R[2]C[-3]",
+        "(1+2)*3",
+        "1-(2-3)",
+        "2^3^2",
+        "(2^3)^2",
+        "-A1^2",
+        "-(A1^2)",
+        "(A1+B1)%",
+        "\"say \"\"hi\"\"\"&A1",
+        "IF(A1>=1,TRUE,#N/A)",
+        "A1#",
+    ];
+
+    for text in samples {
+        let parsed = parse_formula(text).unwrap_or_else(|e| panic!("failed to parse {text}: {e}"));
+        let rendered = parsed.to_string();
+        let reparsed = parse_formula(&rendered)
+            .unwrap_or_else(|e| panic!("failed to reparse {rendered} (from {text}): {e}"));
+        assert_eq!(reparsed, parsed, "'{text}' rendered as '{rendered}'");
+    }
+}
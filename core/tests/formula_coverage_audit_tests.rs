@@ -77,6 +77,7 @@ fn coverage_audit_semantic_formula_diff_classifies_formatting_only() {
             workbook_sheet_id: None,
             kind: SheetKind::Worksheet,
             grid: old_grid,
+            layout: Default::default(),
         }],
         ..Default::default()
     };
@@ -86,6 +87,7 @@ fn coverage_audit_semantic_formula_diff_classifies_formatting_only() {
             workbook_sheet_id: None,
             kind: SheetKind::Worksheet,
             grid: new_grid,
+            layout: Default::default(),
         }],
         ..Default::default()
     };
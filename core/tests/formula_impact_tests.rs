@@ -0,0 +1,145 @@
+use excel_diff::{with_default_session, DiffConfig, DiffOp, StringId, WorkbookPackage};
+use std::io::Cursor;
+
+/// Summary rows, each growing its column A figure by the `Growth` name.
+const SUMMARY_ROWS: u32 = 300;
+
+/// What one side of an impact test contains.
+struct Inputs<'a> {
+    /// `Assumptions!B4`, which the `Growth` name points at.
+    growth: &'a str,
+    /// `Assumptions!C1`, which no formula reads.
+    note: &'a str,
+}
+
+/// Labelled input rows; row 1 carries the note and row 4 the growth rate.
+fn assumptions_xml(s: &Inputs) -> String {
+    let labels = [
+        "Notes", "Currency", "Units", "Growth", "Tax rate", "Discount",
+    ];
+    let mut xml = String::from(concat!(
+        r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">"#,
+        r#"<sheetData>"#,
+    ));
+    for (idx, label) in labels.iter().enumerate() {
+        let r = idx + 1;
+        let value = match r {
+            1 => format!(r#"<c r="C1" t="inlineStr"><is><t>{}</t></is></c>"#, s.note),
+            4 => format!(r#"<c r="B4"><v>{}</v></c>"#, s.growth),
+            _ => format!(r#"<c r="B{r}"><v>{r}</v></c>"#),
+        };
+        xml.push_str(&format!(
+            r#"<row r="{r}"><c r="A{r}" t="inlineStr"><is><t>{label}</t></is></c>{value}</row>"#,
+        ));
+    }
+    xml.push_str("</sheetData></worksheet>");
+    xml
+}
+
+fn summary_xml() -> String {
+    let mut xml = String::from(concat!(
+        r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">"#,
+        r#"<sheetData>"#,
+    ));
+    for r in 1..=SUMMARY_ROWS {
+        xml.push_str(&format!(
+            r#"<row r="{r}"><c r="A{r}"><v>{r}</v></c><c r="B{r}"><f>A{r}*(1+Growth)</f></c>"#,
+        ));
+        if r == 1 {
+            xml.push_str(&format!(r#"<c r="D1"><f>SUM(B1:B{SUMMARY_ROWS})</f></c>"#));
+        }
+        xml.push_str("</row>");
+    }
+    xml.push_str("</sheetData></worksheet>");
+    xml
+}
+
+fn make_xlsx(s: &Inputs) -> Vec<u8> {
+    use std::io::Write;
+    use zip::write::FileOptions;
+    use zip::CompressionMethod;
+    use zip::ZipWriter;
+
+    let assumptions = assumptions_xml(s);
+    let summary = summary_xml();
+    let entries: [(&str, &str); 5] = [
+        ("[Content_Types].xml", "<Types/>"),
+        (
+            "xl/workbook.xml",
+            r#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="Assumptions" sheetId="1" r:id="rId1"/><sheet name="Summary" sheetId="2" r:id="rId2"/></sheets><definedNames><definedName name="Growth">Assumptions!$B$4</definedName></definedNames></workbook>"#,
+        ),
+        (
+            "xl/_rels/workbook.xml.rels",
+            r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet2.xml"/></Relationships>"#,
+        ),
+        ("xl/worksheets/sheet1.xml", &assumptions),
+        ("xl/worksheets/sheet2.xml", &summary),
+    ];
+
+    let mut buf = Vec::new();
+    {
+        let cursor = Cursor::new(&mut buf);
+        let mut zip = ZipWriter::new(cursor);
+        let options = FileOptions::default().compression_method(CompressionMethod::Stored);
+        for (name, body) in entries {
+            zip.start_file(name, options).expect("start zip entry");
+            zip.write_all(body.as_bytes()).expect("write zip entry");
+        }
+        zip.finish().expect("finish zip");
+    }
+    buf
+}
+
+fn open(s: &Inputs) -> WorkbookPackage {
+    WorkbookPackage::open(Cursor::new(make_xlsx(s))).expect("open workbook")
+}
+
+fn resolve(id: StringId) -> String {
+    with_default_session(|session| session.strings.resolve(id).to_string())
+}
+
+const BASE: Inputs<'static> = Inputs {
+    growth: "0.05",
+    note: "FY24 plan",
+};
+
+#[test]
+fn edited_input_lists_downstream_formulas_through_defined_names() {
+    let raised = Inputs {
+        growth: "0.07",
+        note: "FY25 plan",
+    };
+    let new = open(&raised);
+    let ops = open(&BASE).diff(&new, &DiffConfig::default()).ops;
+    let edited = ops
+        .iter()
+        .filter(|op| matches!(op, DiffOp::CellEdited { .. }))
+        .count();
+    assert_eq!(
+        edited, 2,
+        "expected the input and the note to change, got {ops:?}"
+    );
+
+    let impacts = with_default_session(|session| new.formula_impact(&ops, &session.strings));
+    assert_eq!(impacts.len(), 1, "only the input feeds formulas");
+    let impact = &impacts[0];
+    assert_eq!(
+        (resolve(impact.sheet), impact.addr.to_a1()),
+        ("Assumptions".to_string(), "B4".to_string())
+    );
+
+    let counts: Vec<(String, usize)> = impact
+        .counts_by_sheet()
+        .into_iter()
+        .map(|(sheet, count)| (resolve(sheet), count))
+        .collect();
+    assert_eq!(counts, vec![("Summary".to_string(), 301)]);
+
+    let first: Vec<String> = impact
+        .dependents
+        .iter()
+        .take(3)
+        .map(|cell| cell.addr.to_a1())
+        .collect();
+    assert_eq!(first, vec!["B1", "D1", "B2"]);
+}
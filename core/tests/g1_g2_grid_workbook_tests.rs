@@ -15,6 +15,7 @@ fn workbook_with_number(value: f64) -> Workbook {
             workbook_sheet_id: None,
             kind: SheetKind::Worksheet,
             grid,
+            layout: Default::default(),
         }],
         ..Default::default()
     }
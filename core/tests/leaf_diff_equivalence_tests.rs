@@ -23,6 +23,7 @@ fn grid_leaf_diff_matches_single_sheet_workbook() {
             workbook_sheet_id: None,
             kind: SheetKind::Worksheet,
             grid: grid_a.clone(),
+            layout: Default::default(),
         }],
         ..Default::default()
     };
@@ -32,6 +33,7 @@ fn grid_leaf_diff_matches_single_sheet_workbook() {
             workbook_sheet_id: None,
             kind: SheetKind::Worksheet,
             grid: grid_b.clone(),
+            layout: Default::default(),
         }],
         ..Default::default()
     };
@@ -58,12 +60,14 @@ fn sheet_leaf_diff_matches_single_sheet_workbook() {
         workbook_sheet_id: None,
         kind: SheetKind::Worksheet,
         grid: grid_a.clone(),
+        layout: Default::default(),
     };
     let sheet_b = Sheet {
         name: sheet_id,
         workbook_sheet_id: None,
         kind: SheetKind::Worksheet,
         grid: grid_b.clone(),
+        layout: Default::default(),
     };
 
     let wb_a = Workbook {
@@ -244,12 +244,14 @@ fn multiple_sheets_limit_warning_includes_sheet_name() {
                 workbook_sheet_id: None,
                 kind: excel_diff::SheetKind::Worksheet,
                 grid: grid_small.clone(),
+                layout: Default::default(),
             },
             excel_diff::Sheet {
                 name: sid("LargeSheet"),
                 workbook_sheet_id: None,
                 kind: excel_diff::SheetKind::Worksheet,
                 grid: grid_large_a,
+                layout: Default::default(),
             },
         ],
         ..Default::default()
@@ -262,12 +264,14 @@ fn multiple_sheets_limit_warning_includes_sheet_name() {
                 workbook_sheet_id: None,
                 kind: excel_diff::SheetKind::Worksheet,
                 grid: grid_small,
+                layout: Default::default(),
             },
             excel_diff::Sheet {
                 name: sid("LargeSheet"),
                 workbook_sheet_id: None,
                 kind: excel_diff::SheetKind::Worksheet,
                 grid: grid_large_b,
+                layout: Default::default(),
             },
         ],
         ..Default::default()
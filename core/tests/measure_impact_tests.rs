@@ -0,0 +1,98 @@
+#![cfg(feature = "model-diff")]
+
+use excel_diff::{DiffConfig, DiffOp, MeasureVisual, PbixPackage};
+use std::io::Cursor;
+
+/// A `Sales` table whose `Margin` reads `Revenue` and whose `Margin %` reads both.
+fn schema_json(revenue: &str) -> String {
+    format!(
+        r#"{{"model": {{"tables": [{{
+            "name": "Sales",
+            "columns": [
+                {{"name": "Amount", "dataType": "decimal"}},
+                {{"name": "Cost", "dataType": "decimal"}}
+            ],
+            "measures": [
+                {{"name": "Revenue", "expression": "{revenue}"}},
+                {{"name": "Margin", "expression": "[Revenue] - SUM(Sales[Cost])"}},
+                {{"name": "Margin %", "expression": "DIVIDE([Margin], Sales[Revenue])"}},
+                {{"name": "Units", "expression": "COUNTROWS(Sales)"}}
+            ]
+        }}]}}}}"#
+    )
+}
+
+/// One page with a card bound to `Margin %`.
+fn layout_json() -> String {
+    let visual = r#"{"name": "v1", "singleVisual": {"visualType": "card", "projections": {"Values": [{"queryRef": "Sales.Margin %"}]}, "vcObjects": {"title": [{"properties": {"text": {"expr": {"Literal": {"Value": "'Margin trend'"}}}}}]}}}"#;
+    format!(
+        r#"{{"sections": [{{"name": "s1", "displayName": "Overview", "visualContainers": [{{"config": {}}}]}}]}}"#,
+        escape_json(visual)
+    )
+}
+
+fn escape_json(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn open(revenue: &str) -> PbixPackage {
+    use std::io::Write;
+    use zip::write::FileOptions;
+    use zip::CompressionMethod;
+    use zip::ZipWriter;
+
+    let entries = [
+        ("DataModelSchema", schema_json(revenue)),
+        ("Report/Layout", layout_json()),
+    ];
+    let mut buf = Vec::new();
+    {
+        let cursor = Cursor::new(&mut buf);
+        let mut zip = ZipWriter::new(cursor);
+        let options = FileOptions::default().compression_method(CompressionMethod::Stored);
+        for (name, body) in entries {
+            zip.start_file(name, options).expect("start zip entry");
+            zip.write_all(body.as_bytes()).expect("write zip entry");
+        }
+        zip.finish().expect("finish zip");
+    }
+    PbixPackage::open(Cursor::new(buf)).expect("open pbit")
+}
+
+#[test]
+fn edited_measure_lists_dependent_measures_and_visuals() {
+    let old = open("SUM(Sales[Amount])");
+    let new = open("SUMX(Sales, Sales[Amount] * 1.1)");
+    let report = old.diff(&new, &DiffConfig::default());
+    assert!(
+        report
+            .ops
+            .iter()
+            .any(|op| matches!(op, DiffOp::MeasureDefinitionChanged { .. })),
+        "expected the Revenue edit, got {:?}",
+        report.ops
+    );
+
+    let impacts = new.measure_impact(&report);
+    assert_eq!(impacts.len(), 1);
+    assert_eq!(impacts[0].measure, "Sales/Revenue");
+    assert_eq!(
+        impacts[0].dependent_measures,
+        vec!["Sales/Margin", "Sales/Margin %"]
+    );
+    assert_eq!(
+        impacts[0].visuals,
+        vec![MeasureVisual {
+            page: "Overview".to_string(),
+            visual: "Margin trend".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn unchanged_model_has_no_impact() {
+    let old = open("SUM(Sales[Amount])");
+    let new = open("SUM(Sales[Amount])");
+    let report = old.diff(&new, &DiffConfig::default());
+    assert!(new.measure_impact(&report).is_empty());
+}
@@ -63,6 +63,7 @@ fn make_workbook(sheet_name: &str) -> Workbook {
             workbook_sheet_id: None,
             kind: SheetKind::Worksheet,
             grid: Grid::new(0, 0),
+            layout: Default::default(),
         }],
         ..Default::default()
     }
@@ -79,6 +80,7 @@ fn package_diff_streaming_does_not_emit_after_finish_and_finishes_once() {
         workbook: wb.clone(),
         data_mashup: Some(dm_a),
         vba_modules: None,
+        vba_project: None,
         #[cfg(feature = "perf-metrics")]
         parse_time_ms: 0,
     };
@@ -86,6 +88,7 @@ fn package_diff_streaming_does_not_emit_after_finish_and_finishes_once() {
         workbook: wb,
         data_mashup: Some(dm_b),
         vba_modules: None,
+        vba_project: None,
         #[cfg(feature = "perf-metrics")]
         parse_time_ms: 0,
     };
@@ -157,6 +160,7 @@ fn package_diff_streaming_finishes_on_error() {
             workbook_sheet_id: None,
             kind: SheetKind::Worksheet,
             grid: grid_a,
+            layout: Default::default(),
         }],
         ..Default::default()
     };
@@ -166,6 +170,7 @@ fn package_diff_streaming_finishes_on_error() {
             workbook_sheet_id: None,
             kind: SheetKind::Worksheet,
             grid: grid_b,
+            layout: Default::default(),
         }],
         ..Default::default()
     };
@@ -174,6 +179,7 @@ fn package_diff_streaming_finishes_on_error() {
         workbook: wb_a,
         data_mashup: None,
         vba_modules: None,
+        vba_project: None,
         #[cfg(feature = "perf-metrics")]
         parse_time_ms: 0,
     };
@@ -181,6 +187,7 @@ fn package_diff_streaming_finishes_on_error() {
         workbook: wb_b,
         data_mashup: None,
         vba_modules: None,
+        vba_project: None,
         #[cfg(feature = "perf-metrics")]
         parse_time_ms: 0,
     };
@@ -231,6 +238,7 @@ fn package_diff_streaming_finishes_on_m_emit_error() {
         workbook: wb.clone(),
         data_mashup: Some(dm_a),
         vba_modules: None,
+        vba_project: None,
         #[cfg(feature = "perf-metrics")]
         parse_time_ms: 0,
     };
@@ -238,6 +246,7 @@ fn package_diff_streaming_finishes_on_m_emit_error() {
         workbook: wb,
         data_mashup: Some(dm_b),
         vba_modules: None,
+        vba_project: None,
         #[cfg(feature = "perf-metrics")]
         parse_time_ms: 0,
     };
@@ -273,6 +282,7 @@ fn package_diff_streaming_includes_package_parse_time_in_total() {
             workbook_sheet_id: None,
             kind: SheetKind::Worksheet,
             grid: grid_a,
+            layout: Default::default(),
         }],
         ..Default::default()
     };
@@ -282,6 +292,7 @@ fn package_diff_streaming_includes_package_parse_time_in_total() {
             workbook_sheet_id: None,
             kind: SheetKind::Worksheet,
             grid: grid_b,
+            layout: Default::default(),
         }],
         ..Default::default()
     };
@@ -290,12 +301,14 @@ fn package_diff_streaming_includes_package_parse_time_in_total() {
         workbook: wb_a,
         data_mashup: None,
         vba_modules: None,
+        vba_project: None,
         parse_time_ms: 15,
     };
     let pkg_b = WorkbookPackage {
         workbook: wb_b,
         data_mashup: None,
         vba_modules: None,
+        vba_project: None,
         parse_time_ms: 25,
     };
 
@@ -341,6 +354,7 @@ fn package_streaming_json_lines_header_includes_m_strings() {
         workbook: wb.clone(),
         data_mashup: Some(dm_a),
         vba_modules: None,
+        vba_project: None,
         #[cfg(feature = "perf-metrics")]
         parse_time_ms: 0,
     };
@@ -348,6 +362,7 @@ fn package_streaming_json_lines_header_includes_m_strings() {
         workbook: wb,
         data_mashup: Some(dm_b),
         vba_modules: None,
+        vba_project: None,
         #[cfg(feature = "perf-metrics")]
         parse_time_ms: 0,
     };
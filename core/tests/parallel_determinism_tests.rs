@@ -36,6 +36,7 @@ fn make_workbook(pool: &mut StringPool, value: f64) -> Workbook {
             workbook_sheet_id: None,
             kind: SheetKind::Worksheet,
             grid,
+            layout: Default::default(),
         }],
         ..Default::default()
     }
@@ -2,11 +2,21 @@ mod common;
 
 use common::sid;
 use excel_diff::{
-    CellAddress, CellSnapshot, CellValue, ColSignature, DiffOp, DiffReport, FormulaDiffResult,
-    QueryChangeKind, QueryMetadataField, RowSignature,
+    CellAddress, CellComment, CellRange, CellSnapshot, CellStyle, CellValue, ChartChange,
+    ChartField, ColSignature, CommentKind, CommentReply, ConditionalFormatRule, ConnectionChange,
+    ConnectionField, DataValidationRule, DiffOp, DiffReport, DrawingAnchor, DrawingMarker,
+    DrawingObject, DrawingObjectKind, ExternalLink, FieldChange, FormulaDiffResult, FormulaEdit,
+    FormulaEditKind, FreezePane, PbipEntityKind, PivotArea, PivotLayoutChange, PivotSource,
+    QueryChangeKind, QueryMetadataField, ReportChange, ReportField, RowSignature, SheetProtection,
+    SheetVisibility, TextHunk, TextLine, TextLineKind, VbaAttributeChange, VbaModuleType,
+    VbaProcedureChange, VbaProcedureChangeKind, VbaProcedureKind, VbaProjectProperty,
+    WorkbookProtection, WorkbookSetting, WorkbookSettingChange,
 };
 #[cfg(feature = "model-diff")]
-use excel_diff::{ExpressionChangeKind, ModelColumnProperty, RelationshipProperty};
+use excel_diff::{
+    ExpressionChangeKind, MeasureProperty, ModelColumnProperty, ModelObjectChange,
+    ModelObjectField, ModelObjectKind, RelationshipProperty,
+};
 use serde_json::Value;
 use std::collections::BTreeSet;
 
@@ -33,6 +43,7 @@ fn sample_cell_edited() -> DiffOp {
         from: snapshot("C3", Some(CellValue::Number(1.0)), None),
         to: snapshot("C3", Some(CellValue::Number(2.0)), None),
         formula_diff: FormulaDiffResult::Unchanged,
+        formula_edits: Vec::new(),
     }
 }
 
@@ -66,6 +77,7 @@ fn op_kind(op: &DiffOp) -> &'static str {
         DiffOp::RowRemoved { .. } => "RowRemoved",
         DiffOp::RowReplaced { .. } => "RowReplaced",
         DiffOp::DuplicateKeyCluster { .. } => "DuplicateKeyCluster",
+        DiffOp::RecordChanged { .. } => "RecordChanged",
         DiffOp::ColumnAdded { .. } => "ColumnAdded",
         DiffOp::ColumnRemoved { .. } => "ColumnRemoved",
         DiffOp::BlockMovedRows { .. } => "BlockMovedRows",
@@ -445,6 +457,77 @@ fn pg4_cell_edited_json_shape() {
     assert_eq!(keys, expected);
 }
 
+#[test]
+fn pg4_cell_edited_json_formula_edits() {
+    let op = DiffOp::CellEdited {
+        sheet: sid("Sheet1"),
+        addr: addr("C3"),
+        from: snapshot("C3", Some(CellValue::Number(1.0)), Some("SUM(A1,B2)")),
+        to: snapshot("C3", Some(CellValue::Number(2.0)), Some("SUM(A1,B3,1)")),
+        formula_diff: FormulaDiffResult::SemanticChange,
+        formula_edits: vec![
+            FormulaEdit {
+                kind: FormulaEditKind::ReferenceChanged,
+                function: Some("SUM".to_string()),
+                argument: Some(2),
+                from: Some("B2".to_string()),
+                to: Some("B3".to_string()),
+            },
+            FormulaEdit {
+                kind: FormulaEditKind::Inserted,
+                function: Some("SUM".to_string()),
+                argument: Some(3),
+                from: None,
+                to: Some("1".to_string()),
+            },
+        ],
+    };
+    let json = serde_json::to_value(&op).expect("serialize");
+
+    assert_eq!(json["formula_diff"], "semantic_change");
+    assert_eq!(json["formula_edits"][0]["kind"], "reference_changed");
+    assert_eq!(json["formula_edits"][0]["function"], "SUM");
+    assert_eq!(json["formula_edits"][0]["argument"], 2);
+    assert_eq!(json["formula_edits"][0]["from"], "B2");
+    assert_eq!(json["formula_edits"][0]["to"], "B3");
+    assert_eq!(json["formula_edits"][1]["kind"], "inserted");
+    assert!(json["formula_edits"][1].get("from").is_none());
+
+    let round_tripped: DiffOp = serde_json::from_value(json).expect("deserialize");
+    assert_eq!(round_tripped, op);
+}
+
+#[test]
+fn pg4_report_object_changed_json_shape() {
+    let op = DiffOp::ReportObjectChanged {
+        object: PbipEntityKind::Visual,
+        page: Some(sid("Overview")),
+        name: sid("Sales by Region"),
+        changes: vec![ReportChange {
+            field: ReportField::Field,
+            from: Some(sid("Y: Sum(Sales.Amount)")),
+            to: Some(sid("Y: Sales.Margin")),
+        }],
+    };
+    let json = serde_json::to_value(&op).expect("serialize");
+
+    assert_eq!(json["kind"], "ReportObjectChanged");
+    assert_eq!(json["object"], "Visual");
+    assert_eq!(json["page"], sid_json("Overview"));
+    assert_eq!(json["changes"][0]["field"], "Field");
+
+    let round_tripped: DiffOp = serde_json::from_value(json).expect("deserialize");
+    assert_eq!(round_tripped, op);
+
+    let page_op = DiffOp::ReportObjectAdded {
+        object: PbipEntityKind::Page,
+        page: None,
+        name: sid("Details"),
+    };
+    let json = serde_json::to_value(&page_op).expect("serialize");
+    assert!(json.get("page").is_none());
+}
+
 #[test]
 fn pg4_row_added_json_optional_signature() {
     let op_without_sig = DiffOp::RowAdded {
@@ -605,8 +688,10 @@ fn pg4_sheet_renamed_json_shape() {
     assert_eq!(renamed_json["from"], sid_json("SheetOld"));
     assert_eq!(renamed_json["to"], sid_json("SheetNew"));
     let renamed_keys = json_keys(&renamed_json);
-    let expected_keys: BTreeSet<String> =
-        ["kind", "sheet", "from", "to"].into_iter().map(String::from).collect();
+    let expected_keys: BTreeSet<String> = ["kind", "sheet", "from", "to"]
+        .into_iter()
+        .map(String::from)
+        .collect();
     assert_eq!(renamed_keys, expected_keys);
 }
 
@@ -937,6 +1022,37 @@ fn pg4_block_rect_json_shape_and_roundtrip() {
 
 #[test]
 fn pg4_diffop_roundtrip_each_variant() {
+    let logo = DrawingObject {
+        id: 2,
+        kind: DrawingObjectKind::Picture,
+        name: sid("Logo"),
+        anchor: DrawingAnchor {
+            from: DrawingMarker {
+                row: 1,
+                col: 1,
+                row_offset: 0,
+                col_offset: 9525,
+            },
+            to: Some(DrawingMarker {
+                row: 6,
+                col: 3,
+                row_offset: -4,
+                col_offset: 0,
+            }),
+        },
+        text: None,
+        macro_name: None,
+        image_hash: Some(0xfeed_beef),
+    };
+    let button = DrawingObject {
+        id: 3,
+        kind: DrawingObjectKind::FormControl,
+        name: sid("Button 1"),
+        text: Some(sid("Run")),
+        macro_name: Some(sid("[0]!Run")),
+        image_hash: None,
+        ..logo.clone()
+    };
     let ops = vec![
         DiffOp::SheetAdded {
             sheet: sid("SheetA"),
@@ -968,6 +1084,18 @@ fn pg4_diffop_roundtrip_each_variant() {
             left_rows: vec![1, 2],
             right_rows: vec![3],
         },
+        DiffOp::RecordChanged {
+            sheet: sid("Sheet1"),
+            key: vec![Some(CellValue::Number(5.0))],
+            old_row: 1,
+            new_row: 2,
+            fields: vec![FieldChange {
+                col: 2,
+                field: Some(sid("Price")),
+                from: Some(CellValue::Number(10.0)),
+                to: Some(CellValue::Number(12.0)),
+            }],
+        },
         DiffOp::ColumnAdded {
             sheet: sid("Sheet1"),
             col_idx: 2,
@@ -1043,6 +1171,8 @@ fn pg4_diffop_roundtrip_each_variant() {
             old_hash: 0x1234567890ABCDEF,
             new_hash: 0xFEDCBA0987654321,
             semantic_detail: None,
+            downstream_queries: vec![sid("Section1/Query2")],
+            loaded_tables: vec![sid("Query2")],
         },
         DiffOp::QueryDefinitionChanged {
             name: sid("Section1/Query2"),
@@ -1050,6 +1180,8 @@ fn pg4_diffop_roundtrip_each_variant() {
             old_hash: 0xAAAABBBBCCCCDDDD,
             new_hash: 0xAAAABBBBCCCCDDDD,
             semantic_detail: None,
+            downstream_queries: Vec::new(),
+            loaded_tables: Vec::new(),
         },
         DiffOp::QueryMetadataChanged {
             name: sid("Section1/Query3"),
@@ -1075,6 +1207,277 @@ fn pg4_diffop_roundtrip_each_variant() {
             old: Some(sid("true")),
             new: Some(sid("false")),
         },
+        DiffOp::CellStyleChanged {
+            sheet: sid("Sheet1"),
+            addr: addr("B2"),
+            from: CellStyle {
+                number_format: Some(sid("0.00")),
+                ..CellStyle::default()
+            },
+            to: CellStyle {
+                number_format: Some(sid("0%")),
+                fill: Some(sid("pattern=solid;fg=rgb:FFFFFF00")),
+                ..CellStyle::default()
+            },
+        },
+        DiffOp::CommentAdded {
+            sheet: sid("Sheet1"),
+            addr: addr("C3"),
+            comment: CellComment {
+                kind: CommentKind::Note,
+                author: Some(sid("Ann")),
+                text: sid("Check this"),
+                resolved: false,
+                replies: Vec::new(),
+            },
+        },
+        DiffOp::CommentRemoved {
+            sheet: sid("Sheet1"),
+            addr: addr("D4"),
+            comment: CellComment {
+                kind: CommentKind::Note,
+                author: None,
+                text: sid("Old note"),
+                resolved: false,
+                replies: Vec::new(),
+            },
+        },
+        DiffOp::CommentChanged {
+            sheet: sid("Sheet1"),
+            addr: addr("E5"),
+            from: CellComment {
+                kind: CommentKind::Threaded,
+                author: Some(sid("Ann")),
+                text: sid("Why?"),
+                resolved: false,
+                replies: Vec::new(),
+            },
+            to: CellComment {
+                kind: CommentKind::Threaded,
+                author: Some(sid("Ann")),
+                text: sid("Why?"),
+                resolved: true,
+                replies: vec![CommentReply {
+                    author: Some(sid("Bo")),
+                    text: sid("Fixed"),
+                }],
+            },
+        },
+        DiffOp::DataValidationAdded {
+            sheet: sid("Sheet1"),
+            rule: DataValidationRule {
+                ranges: vec!["B2:B10".parse().expect("range")],
+                kind: sid("list"),
+                operator: None,
+                formula1: Some(sid("\"Yes,No\"")),
+                formula2: None,
+                allow_blank: true,
+            },
+        },
+        DiffOp::DataValidationRemoved {
+            sheet: sid("Sheet1"),
+            rule: DataValidationRule {
+                ranges: vec!["C3".parse().expect("range")],
+                kind: sid("whole"),
+                operator: Some(sid("between")),
+                formula1: Some(sid("1")),
+                formula2: Some(sid("10")),
+                allow_blank: false,
+            },
+        },
+        DiffOp::DataValidationChanged {
+            sheet: sid("Sheet1"),
+            from: DataValidationRule {
+                ranges: vec!["D2:D5".parse().expect("range")],
+                kind: sid("list"),
+                operator: None,
+                formula1: Some(sid("$F$1:$F$5")),
+                formula2: None,
+                allow_blank: true,
+            },
+            to: DataValidationRule {
+                ranges: vec!["D2:D5".parse().expect("range")],
+                kind: sid("list"),
+                operator: None,
+                formula1: Some(sid("$F$1:$F$6")),
+                formula2: None,
+                allow_blank: true,
+            },
+        },
+        DiffOp::ConditionalFormatAdded {
+            sheet: sid("Sheet1"),
+            rule: ConditionalFormatRule {
+                ranges: vec![
+                    CellRange::new(
+                        CellAddress::from_indices(1, 2),
+                        CellAddress::from_indices(9, 2),
+                    ),
+                    "E2".parse().expect("range"),
+                ],
+                kind: sid("cellIs"),
+                operator: Some(sid("greaterThan")),
+                formulas: vec![sid("100")],
+                priority: Some(1),
+                stop_if_true: true,
+            },
+        },
+        DiffOp::ConditionalFormatRemoved {
+            sheet: sid("Sheet1"),
+            rule: ConditionalFormatRule {
+                ranges: vec!["A1:A4".parse().expect("range")],
+                kind: sid("duplicateValues"),
+                operator: None,
+                formulas: Vec::new(),
+                priority: Some(2),
+                stop_if_true: false,
+            },
+        },
+        DiffOp::ConditionalFormatChanged {
+            sheet: sid("Sheet1"),
+            from: ConditionalFormatRule {
+                ranges: vec!["C2:C10".parse().expect("range")],
+                kind: sid("expression"),
+                operator: None,
+                formulas: vec![sid("$A2=\"x\"")],
+                priority: Some(3),
+                stop_if_true: false,
+            },
+            to: ConditionalFormatRule {
+                ranges: vec!["C2:C10".parse().expect("range")],
+                kind: sid("expression"),
+                operator: None,
+                formulas: vec![sid("$A2=\"y\"")],
+                priority: Some(3),
+                stop_if_true: false,
+            },
+        },
+        DiffOp::MergedRangeAdded {
+            sheet: sid("Sheet1"),
+            range: "A1:C1".parse().expect("range"),
+        },
+        DiffOp::MergedRangeRemoved {
+            sheet: sid("Sheet1"),
+            range: "B4:B6".parse().expect("range"),
+        },
+        DiffOp::RowVisibilityChanged {
+            sheet: sid("Sheet1"),
+            row_idx: 3,
+            hidden: true,
+        },
+        DiffOp::ColumnVisibilityChanged {
+            sheet: sid("Sheet1"),
+            col_idx: 4,
+            hidden: false,
+        },
+        DiffOp::RowOutlineChanged {
+            sheet: sid("Sheet1"),
+            row_idx: 3,
+            from: 0,
+            to: 1,
+        },
+        DiffOp::ColumnOutlineChanged {
+            sheet: sid("Sheet1"),
+            col_idx: 2,
+            from: 2,
+            to: 0,
+        },
+        DiffOp::RowHeightChanged {
+            sheet: sid("Sheet1"),
+            row_idx: 0,
+            from: None,
+            to: Some(600),
+        },
+        DiffOp::ColumnWidthChanged {
+            sheet: sid("Sheet1"),
+            col_idx: 1,
+            from: Some(2560),
+            to: Some(5248),
+        },
+        DiffOp::FreezePaneChanged {
+            sheet: sid("Sheet1"),
+            from: None,
+            to: Some(FreezePane { rows: 1, cols: 0 }),
+        },
+        DiffOp::SpillRangeChanged {
+            sheet: sid("Sheet1"),
+            addr: addr("C2"),
+            from: Some(CellRange::new(addr("C2"), addr("C6"))),
+            to: Some(CellRange::new(addr("C2"), addr("C9"))),
+        },
+        DiffOp::SheetVisibilityChanged {
+            sheet: sid("Sheet1"),
+            from: SheetVisibility::Visible,
+            to: SheetVisibility::VeryHidden,
+        },
+        DiffOp::SheetTabColorChanged {
+            sheet: sid("Sheet1"),
+            from: None,
+            to: Some(sid("rgb:FFFF0000")),
+        },
+        DiffOp::SheetProtectionChanged {
+            sheet: sid("Sheet1"),
+            from: Some(SheetProtection {
+                has_password: false,
+                allowed: vec![sid("selectLockedCells"), sid("selectUnlockedCells")],
+            }),
+            to: None,
+        },
+        DiffOp::ExcelTableAdded {
+            sheet: sid("Sheet1"),
+            name: sid("Orders"),
+            range: "A1:C10".parse().expect("range"),
+        },
+        DiffOp::ExcelTableRemoved {
+            sheet: sid("Sheet1"),
+            name: sid("Returns"),
+            range: "E1:F4".parse().expect("range"),
+        },
+        DiffOp::ExcelTableRenamed {
+            sheet: sid("Sheet1"),
+            from: sid("Orders"),
+            to: sid("Sales"),
+        },
+        DiffOp::ExcelTableResized {
+            sheet: sid("Sheet1"),
+            name: sid("Sales"),
+            from: "A1:C10".parse().expect("range"),
+            to: "A1:D12".parse().expect("range"),
+        },
+        DiffOp::ExcelTableColumnRenamed {
+            sheet: sid("Sheet1"),
+            table: sid("Sales"),
+            from: sid("Qty"),
+            to: sid("Quantity"),
+        },
+        DiffOp::DrawingObjectAdded {
+            sheet: sid("Sheet1"),
+            object: logo.clone(),
+        },
+        DiffOp::DrawingObjectRemoved {
+            sheet: sid("Sheet1"),
+            object: button.clone(),
+        },
+        DiffOp::DrawingObjectMoved {
+            sheet: sid("Sheet1"),
+            name: sid("Logo"),
+            from: logo.anchor,
+            to: DrawingAnchor {
+                from: DrawingMarker {
+                    row: 3,
+                    ..logo.anchor.from
+                },
+                to: None,
+            },
+        },
+        DiffOp::DrawingObjectChanged {
+            sheet: sid("Sheet1"),
+            from: button.clone(),
+            to: DrawingObject {
+                text: Some(sid("Run all")),
+                macro_name: Some(sid("[0]!RunAll")),
+                ..button.clone()
+            },
+        },
         DiffOp::NamedRangeAdded {
             name: sid("GlobalAdd"),
         },
@@ -1097,6 +1500,130 @@ fn pg4_diffop_roundtrip_each_variant() {
         DiffOp::ChartChanged {
             sheet: sid("Sheet1"),
             name: sid("Chart 1"),
+            changes: Vec::new(),
+        },
+        DiffOp::ChartChanged {
+            sheet: sid("Sheet1"),
+            name: sid("Chart 1"),
+            changes: vec![ChartChange {
+                field: ChartField::SeriesValues,
+                series: Some(1),
+                axis: None,
+                from: Some(sid("Data!$C$2:$C$40")),
+                to: Some(sid("Data!$C$2:$C$50")),
+            }],
+        },
+        DiffOp::PivotTableAdded {
+            sheet: sid("Report"),
+            name: sid("PivotTable1"),
+        },
+        DiffOp::PivotTableRemoved {
+            sheet: sid("Report"),
+            name: sid("PivotTable2"),
+        },
+        DiffOp::PivotTableSourceChanged {
+            sheet: sid("Report"),
+            name: sid("PivotTable1"),
+            from: PivotSource {
+                kind: sid("worksheet"),
+                sheet: Some(sid("Data")),
+                range: Some("A1:D10".parse().expect("range")),
+                name: None,
+                connection_id: None,
+            },
+            to: PivotSource {
+                kind: sid("worksheet"),
+                sheet: Some(sid("Data")),
+                range: Some("A1:D12".parse().expect("range")),
+                name: None,
+                connection_id: None,
+            },
+            uncovered_rows: 3,
+        },
+        DiffOp::PivotTableLayoutChanged {
+            sheet: sid("Report"),
+            name: sid("PivotTable1"),
+            changes: vec![PivotLayoutChange {
+                area: PivotArea::Values,
+                field: Some(sid("Sales")),
+                from: Some(sid("Sum of Sales: sum")),
+                to: Some(sid("Average of Sales: average")),
+            }],
+        },
+        DiffOp::ExternalLinkAdded {
+            index: 2,
+            target: sid("Budget.xlsx"),
+        },
+        DiffOp::ExternalLinkRemoved {
+            index: 3,
+            target: sid("Old.xlsx"),
+        },
+        DiffOp::ExternalLinkChanged {
+            from: ExternalLink {
+                index: 1,
+                kind: sid("externalBook"),
+                target: sid("file:///\\\\share\\Q3.xlsx"),
+                sheet_names: vec![sid("Summary")],
+            },
+            to: ExternalLink {
+                index: 1,
+                kind: sid("externalBook"),
+                target: sid("Q3.xlsx"),
+                sheet_names: vec![sid("Summary")],
+            },
+        },
+        DiffOp::ConnectionAdded { name: sid("Rates") },
+        DiffOp::ConnectionRemoved {
+            name: sid("Legacy"),
+        },
+        DiffOp::ConnectionChanged {
+            name: sid("Sales"),
+            changes: vec![ConnectionChange {
+                field: ConnectionField::Connection,
+                from: Some(sid("DSN=Sales;PWD=***")),
+                to: Some(sid("DSN=SalesProd;PWD=***")),
+            }],
+        },
+        DiffOp::ReportObjectAdded {
+            object: PbipEntityKind::Page,
+            page: None,
+            name: sid("Details"),
+        },
+        DiffOp::ReportObjectRemoved {
+            object: PbipEntityKind::Visual,
+            page: Some(sid("Overview")),
+            name: sid("Top products"),
+        },
+        DiffOp::ReportObjectChanged {
+            object: PbipEntityKind::Bookmark,
+            page: None,
+            name: sid("Q3 view"),
+            changes: vec![ReportChange {
+                field: ReportField::Page,
+                from: Some(sid("Overview")),
+                to: Some(sid("Details")),
+            }],
+        },
+        DiffOp::WorkbookSettingsChanged {
+            changes: vec![WorkbookSettingChange {
+                setting: WorkbookSetting::CalcMode,
+                from: Some(sid("automatic")),
+                to: Some(sid("manual")),
+            }],
+        },
+        DiffOp::WorkbookProtectionChanged {
+            from: None,
+            to: Some(WorkbookProtection {
+                lock_structure: true,
+                lock_windows: false,
+                lock_revision: false,
+                has_password: true,
+            }),
+        },
+        DiffOp::DocumentPropertyChanged {
+            name: sid("core:title"),
+            from: Some(sid("Q3 budget")),
+            to: None,
         },
         DiffOp::VbaModuleAdded {
             name: sid("Module1"),
@@ -1106,15 +1633,60 @@ fn pg4_diffop_roundtrip_each_variant() {
         },
         DiffOp::VbaModuleChanged {
             name: sid("Module1"),
+            module_type: Some((VbaModuleType::Standard, VbaModuleType::Class)),
+            attributes: vec![VbaAttributeChange {
+                name: sid("VB_PredeclaredId"),
+                from: Some(sid("False")),
+                to: Some(sid("True")),
+            }],
+            procedures: vec![VbaProcedureChange {
+                name: sid("Foo"),
+                kind: VbaProcedureKind::Sub,
+                change: VbaProcedureChangeKind::Modified,
+            }],
+            hunks: vec![TextHunk {
+                old_start: 2,
+                old_len: 1,
+                new_start: 2,
+                new_len: 1,
+                procedure: Some(sid("Foo")),
+                lines: vec![
+                    TextLine {
+                        kind: TextLineKind::Removed,
+                        text: sid("    x = 1"),
+                    },
+                    TextLine {
+                        kind: TextLineKind::Added,
+                        text: sid("    x = 2"),
+                    },
+                ],
+            }],
+        },
+        DiffOp::VbaReferenceAdded {
+            name: sid("Scripting"),
+            libid: sid("*\\G{420B2830-E718-11CF-893D-00A0C9054228}#1.0#0#scrrun.dll#Scripting"),
+        },
+        DiffOp::VbaReferenceRemoved {
+            name: sid("MSForms"),
+            libid: sid("*\\G{0D452EE1-E08F-101A-852E-02608C4D0BB4}#2.0#0#FM20.DLL#Forms"),
+        },
+        DiffOp::VbaReferenceChanged {
+            name: sid("Office"),
+            from: sid("*\\G{2DF8D04C}#2.7#0#MSO.DLL#Office"),
+            to: sid("*\\G{2DF8D04C}#2.8#0#MSO.DLL#Office"),
+        },
+        DiffOp::VbaProjectChanged {
+            property: VbaProjectProperty::LockedForViewing,
+            from: Some(sid("false")),
+            to: Some(sid("true")),
         },
     ];
 
     #[cfg(feature = "model-diff")]
-    {
+    let ops = {
+        let mut ops = ops;
         ops.extend([
-            DiffOp::TableAdded {
-                name: sid("Sales"),
-            },
+            DiffOp::TableAdded { name: sid("Sales") },
             DiffOp::TableRemoved {
                 name: sid("Legacy"),
             },
@@ -1146,6 +1718,7 @@ fn pg4_diffop_roundtrip_each_variant() {
                 change_kind: ExpressionChangeKind::Semantic,
                 old_hash: 1,
                 new_hash: 2,
+                edits: Vec::new(),
             },
             DiffOp::RelationshipAdded {
                 from_table: sid("Sales"),
@@ -1176,12 +1749,41 @@ fn pg4_diffop_roundtrip_each_variant() {
                 change_kind: ExpressionChangeKind::Semantic,
                 old_hash: 3,
                 new_hash: 4,
+                edits: Vec::new(),
             },
             DiffOp::MeasureRemoved {
                 name: sid("Sales/LegacyTotal"),
             },
+            DiffOp::MeasurePropertyChanged {
+                name: sid("Sales/Total"),
+                field: MeasureProperty::FormatString,
+                old: Some(sid("0.00")),
+                new: Some(sid("#,0")),
+            },
+            DiffOp::ModelObjectAdded {
+                object: ModelObjectKind::Hierarchy,
+                table: Some(sid("Calendar")),
+                name: sid("Fiscal"),
+            },
+            DiffOp::ModelObjectRemoved {
+                object: ModelObjectKind::Perspective,
+                table: None,
+                name: sid("Finance"),
+            },
+            DiffOp::ModelObjectChanged {
+                object: ModelObjectKind::Role,
+                table: None,
+                name: sid("EastOnly"),
+                changes: vec![ModelObjectChange {
+                    field: ModelObjectField::RowFilter,
+                    item: Some(sid("Sales")),
+                    from: Some(sid("[Region] = \"East\"")),
+                    to: Some(sid("[Region] = \"West\"")),
+                }],
+            },
         ]);
-    }
+        ops
+    };
 
     for original in ops {
         let serialized = serde_json::to_string(&original).expect("serialize");
@@ -1341,6 +1943,7 @@ fn pg4_cell_edited_invariant_helper_rejects_mismatched_snapshot_addr() {
         from: snapshot("D4", Some(CellValue::Number(1.0)), None),
         to: snapshot("C3", Some(CellValue::Number(2.0)), None),
         formula_diff: FormulaDiffResult::Unchanged,
+        formula_edits: Vec::new(),
     };
 
     assert_cell_edited_invariants(&op, "Sheet1", "C3");
@@ -0,0 +1,229 @@
+use excel_diff::{with_default_session, DiffConfig, DiffOp, PivotArea, StringId, WorkbookPackage};
+use std::io::Cursor;
+
+/// What one side of a pivot test contains.
+struct Pivot<'a> {
+    /// `(region, sales)` rows on the Data sheet, below a header row.
+    rows: &'a [(&'a str, u32)],
+    /// Last row (1-based) of the cache source range `Data!A1:B{n}`.
+    source_end: u32,
+    /// Aggregation of the single value field.
+    function: &'a str,
+}
+
+fn data_sheet(rows: &[(&str, u32)]) -> String {
+    let mut xml = String::from(concat!(
+        r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">"#,
+        r#"<sheetData><row r="1"><c r="A1" t="inlineStr"><is><t>Region</t></is></c>"#,
+        r#"<c r="B1" t="inlineStr"><is><t>Sales</t></is></c></row>"#,
+    ));
+    for (idx, (region, sales)) in rows.iter().enumerate() {
+        let r = idx + 2;
+        xml.push_str(&format!(
+            concat!(
+                r#"<row r="{r}"><c r="A{r}" t="inlineStr"><is><t>{region}</t></is></c>"#,
+                r#"<c r="B{r}"><v>{sales}</v></c></row>"#,
+            ),
+            r = r,
+            region = region,
+            sales = sales
+        ));
+    }
+    xml.push_str("</sheetData></worksheet>");
+    xml
+}
+
+fn cache_xml(source_end: u32) -> String {
+    format!(
+        concat!(
+            r#"<pivotCacheDefinition "#,
+            r#"xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">"#,
+            r#"<cacheSource type="worksheet"><worksheetSource ref="A1:B{}" sheet="Data"/>"#,
+            r#"</cacheSource><cacheFields count="2"><cacheField name="Region">"#,
+            r#"<sharedItems><s v="East"/><s v="West"/></sharedItems></cacheField>"#,
+            r#"<cacheField name="Sales"><sharedItems containsNumber="1"/></cacheField>"#,
+            r#"</cacheFields></pivotCacheDefinition>"#,
+        ),
+        source_end
+    )
+}
+
+fn pivot_xml(function: &str) -> String {
+    let caption = match function {
+        "sum" => "Sum of Sales",
+        "average" => "Average of Sales",
+        other => other,
+    };
+    format!(
+        concat!(
+            r#"<pivotTableDefinition "#,
+            r#"xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" "#,
+            r#"name="PivotTable1" cacheId="1"><location ref="A3:B6" firstHeaderRow="1" "#,
+            r#"firstDataRow="1" firstDataCol="1"/><pivotFields count="2">"#,
+            r#"<pivotField axis="axisRow" showAll="0"><items count="3"><item x="0"/>"#,
+            r#"<item x="1"/><item t="default"/></items></pivotField>"#,
+            r#"<pivotField dataField="1" showAll="0"/></pivotFields>"#,
+            r#"<rowFields count="1"><field x="0"/></rowFields><dataFields count="1">"#,
+            r#"<dataField name="{}" fld="1" subtotal="{}"/></dataFields></pivotTableDefinition>"#,
+        ),
+        caption, function
+    )
+}
+
+fn make_xlsx(p: &Pivot) -> Vec<u8> {
+    use std::io::Write;
+    use zip::write::FileOptions;
+    use zip::CompressionMethod;
+    use zip::ZipWriter;
+
+    let data = data_sheet(p.rows);
+    let cache = cache_xml(p.source_end);
+    let pivot = pivot_xml(p.function);
+    let entries: [(&str, &str); 9] = [
+        ("[Content_Types].xml", "<Types/>"),
+        (
+            "xl/workbook.xml",
+            r#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="Data" sheetId="1" r:id="rId1"/><sheet name="Report" sheetId="2" r:id="rId2"/></sheets></workbook>"#,
+        ),
+        (
+            "xl/_rels/workbook.xml.rels",
+            r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet2.xml"/></Relationships>"#,
+        ),
+        ("xl/worksheets/sheet1.xml", &data),
+        (
+            "xl/worksheets/sheet2.xml",
+            r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData/></worksheet>"#,
+        ),
+        (
+            "xl/worksheets/_rels/sheet2.xml.rels",
+            r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/pivotTable" Target="../pivotTables/pivotTable1.xml"/></Relationships>"#,
+        ),
+        ("xl/pivotTables/pivotTable1.xml", &pivot),
+        (
+            "xl/pivotTables/_rels/pivotTable1.xml.rels",
+            r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/pivotCacheDefinition" Target="../pivotCache/pivotCacheDefinition1.xml"/></Relationships>"#,
+        ),
+        ("xl/pivotCache/pivotCacheDefinition1.xml", &cache),
+    ];
+
+    let mut buf = Vec::new();
+    {
+        let cursor = Cursor::new(&mut buf);
+        let mut zip = ZipWriter::new(cursor);
+        let options = FileOptions::default().compression_method(CompressionMethod::Stored);
+        for (name, body) in entries {
+            zip.start_file(name, options).expect("start zip entry");
+            zip.write_all(body.as_bytes()).expect("write zip entry");
+        }
+        zip.finish().expect("finish zip");
+    }
+    buf
+}
+
+fn open(p: &Pivot) -> WorkbookPackage {
+    WorkbookPackage::open(Cursor::new(make_xlsx(p))).expect("open workbook")
+}
+
+fn resolve(id: Option<StringId>) -> Option<String> {
+    id.map(|id| with_default_session(|session| session.strings.resolve(id).to_string()))
+}
+
+fn pivot_ops(old: &Pivot, new: &Pivot) -> Vec<DiffOp> {
+    open(old)
+        .diff(&open(new), &DiffConfig::default())
+        .ops
+        .into_iter()
+        .filter(|op| {
+            matches!(
+                op,
+                DiffOp::PivotTableAdded { .. }
+                    | DiffOp::PivotTableRemoved { .. }
+                    | DiffOp::PivotTableSourceChanged { .. }
+                    | DiffOp::PivotTableLayoutChanged { .. }
+            )
+        })
+        .collect()
+}
+
+const BASE: Pivot<'static> = Pivot {
+    rows: &[("East", 10), ("West", 20), ("East", 30), ("West", 40)],
+    source_end: 5,
+    function: "sum",
+};
+
+#[test]
+fn aggregation_change_is_reported_as_a_value_field_change() {
+    assert_eq!(pivot_ops(&BASE, &BASE), Vec::new());
+
+    let average = Pivot {
+        function: "average",
+        ..BASE
+    };
+    let ops = pivot_ops(&BASE, &average);
+    assert_eq!(ops.len(), 1, "expected one layout change, got {ops:?}");
+    let DiffOp::PivotTableLayoutChanged { name, changes, .. } = &ops[0] else {
+        panic!("expected a layout change, got {:?}", ops[0]);
+    };
+    assert_eq!(resolve(Some(*name)).as_deref(), Some("PivotTable1"));
+    let changes: Vec<(PivotArea, Option<String>, Option<String>, Option<String>)> = changes
+        .iter()
+        .map(|c| (c.area, resolve(c.field), resolve(c.from), resolve(c.to)))
+        .collect();
+    assert_eq!(
+        changes,
+        vec![(
+            PivotArea::Values,
+            Some("Sales".to_string()),
+            Some("Sum of Sales: sum".to_string()),
+            Some("Average of Sales: average".to_string()),
+        )]
+    );
+}
+
+#[test]
+fn data_appended_below_the_source_range_is_flagged() {
+    let appended = Pivot {
+        rows: &[
+            ("East", 10),
+            ("West", 20),
+            ("East", 30),
+            ("West", 40),
+            ("East", 50),
+            ("West", 60),
+        ],
+        ..BASE
+    };
+    let ops = pivot_ops(&BASE, &appended);
+    assert_eq!(ops.len(), 1, "expected one source change, got {ops:?}");
+    let DiffOp::PivotTableSourceChanged {
+        from,
+        to,
+        uncovered_rows,
+        ..
+    } = &ops[0]
+    else {
+        panic!("expected a source change, got {:?}", ops[0]);
+    };
+    assert_eq!(from, to);
+    assert_eq!(*uncovered_rows, 2);
+
+    let extended = Pivot {
+        source_end: 7,
+        ..appended
+    };
+    let ops = pivot_ops(&BASE, &extended);
+    let DiffOp::PivotTableSourceChanged {
+        from,
+        to,
+        uncovered_rows,
+        ..
+    } = &ops[0]
+    else {
+        panic!("expected a source change, got {ops:?}");
+    };
+    assert_eq!(
+        (from.range.map(|r| r.to_a1()), to.range.map(|r| r.to_a1())),
+        (Some("A1:B5".to_string()), Some("A1:B7".to_string()))
+    );
+    assert_eq!(*uncovered_rows, 0);
+}
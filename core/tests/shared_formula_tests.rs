@@ -0,0 +1,205 @@
+use excel_diff::{with_default_session, DiffConfig, DiffOp, WorkbookPackage};
+use std::io::Cursor;
+
+/// Data rows, each with a `B` formula doubling its `A` value. Columns `D:G` hold fixed values
+/// so the column alignment has stable columns to anchor on.
+const ROWS: u32 = 200;
+
+/// What one side of a shared-formula test contains.
+struct Layout {
+    /// Write column B as one shared formula instead of a formula per cell.
+    shared: bool,
+    /// Row whose column B formula triples instead of doubling.
+    edited_row: Option<u32>,
+    /// Height of the `SEQUENCE` spill anchored at `C1`.
+    spill_rows: u32,
+}
+
+fn sheet_xml(s: &Layout) -> String {
+    let mut xml = String::from(concat!(
+        r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">"#,
+        r#"<sheetData>"#,
+    ));
+    for r in 1..=ROWS {
+        let factor = if s.edited_row == Some(r) { 3 } else { 2 };
+        let formula = if s.edited_row == Some(r) || !s.shared {
+            format!("<f>A{r}*{factor}</f>")
+        } else if r == 1 {
+            format!(r#"<f t="shared" ref="B1:B{ROWS}" si="0">A1*2</f>"#)
+        } else {
+            r#"<f t="shared" si="0"/>"#.to_string()
+        };
+        xml.push_str(&format!(
+            r#"<row r="{r}"><c r="A{r}"><v>{r}</v></c><c r="B{r}">{formula}<v>{}</v></c>"#,
+            r * factor
+        ));
+        if r <= s.spill_rows {
+            let spill = if r == 1 {
+                format!(
+                    r#"<f t="array" ref="C1:C{}">SEQUENCE({})</f>"#,
+                    s.spill_rows, s.spill_rows
+                )
+            } else {
+                String::new()
+            };
+            xml.push_str(&format!(r#"<c r="C{r}">{spill}<v>{r}</v></c>"#));
+        }
+        for (col, scale) in [("D", 10), ("E", 20), ("F", 30), ("G", 40)] {
+            xml.push_str(&format!(r#"<c r="{col}{r}"><v>{}</v></c>"#, r * scale));
+        }
+        xml.push_str("</row>");
+    }
+    xml.push_str("</sheetData></worksheet>");
+    xml
+}
+
+fn make_xlsx(sheet: &str) -> Vec<u8> {
+    use std::io::Write;
+    use zip::write::FileOptions;
+    use zip::CompressionMethod;
+    use zip::ZipWriter;
+
+    let entries: [(&str, &str); 4] = [
+        ("[Content_Types].xml", "<Types/>"),
+        (
+            "xl/workbook.xml",
+            r#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="Sheet1" sheetId="1" r:id="rId1"/></sheets></workbook>"#,
+        ),
+        (
+            "xl/_rels/workbook.xml.rels",
+            r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/></Relationships>"#,
+        ),
+        ("xl/worksheets/sheet1.xml", sheet),
+    ];
+
+    let mut buf = Vec::new();
+    {
+        let cursor = Cursor::new(&mut buf);
+        let mut zip = ZipWriter::new(cursor);
+        let options = FileOptions::default().compression_method(CompressionMethod::Stored);
+        for (name, body) in entries {
+            zip.start_file(name, options).expect("start zip entry");
+            zip.write_all(body.as_bytes()).expect("write zip entry");
+        }
+        zip.finish().expect("finish zip");
+    }
+    buf
+}
+
+fn diff(old: &Layout, new: &Layout) -> Vec<DiffOp> {
+    diff_sheets(&sheet_xml(old), &sheet_xml(new))
+}
+
+fn diff_sheets(old: &str, new: &str) -> Vec<DiffOp> {
+    let open = |s: &str| WorkbookPackage::open(Cursor::new(make_xlsx(s))).expect("open workbook");
+    open(old).diff(&open(new), &DiffConfig::default()).ops
+}
+
+/// Twenty rows of values in `A` and `C`, with `B` computed by `formula(row)`, either as one
+/// shared formula or as a formula per cell.
+fn formula_sheet_xml(formula: impl Fn(u32) -> String, shared: bool) -> String {
+    let mut xml = String::from(concat!(
+        r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">"#,
+        r#"<sheetData>"#,
+    ));
+    for r in 1..=20 {
+        let f = if !shared {
+            format!("<f>{}</f>", formula(r))
+        } else if r == 1 {
+            format!(r#"<f t="shared" ref="B1:B20" si="0">{}</f>"#, formula(r))
+        } else {
+            r#"<f t="shared" si="0"/>"#.to_string()
+        };
+        xml.push_str(&format!(
+            r#"<row r="{r}"><c r="A{r}"><v>{r}</v></c><c r="B{r}">{f}<v>0</v></c><c r="C{r}"><v>{}</v></c></row>"#,
+            r * 10
+        ));
+    }
+    xml.push_str("</sheetData></worksheet>");
+    xml
+}
+
+const BASE: Layout = Layout {
+    shared: true,
+    edited_row: None,
+    spill_rows: 5,
+};
+
+#[test]
+fn unsharing_a_formula_block_is_not_an_edit() {
+    let individual = Layout {
+        shared: false,
+        ..BASE
+    };
+    let ops = diff(&BASE, &individual);
+    assert!(ops.is_empty(), "expected no changes, got {ops:?}");
+}
+
+#[test]
+fn edited_shared_formula_child_is_reported_alone() {
+    let edited = Layout {
+        edited_row: Some(120),
+        ..BASE
+    };
+    let ops = diff(&BASE, &edited);
+    let edits: Vec<String> = ops
+        .iter()
+        .filter_map(|op| match op {
+            DiffOp::CellEdited { addr, to, .. } => {
+                let formula = to.formula.map(|id| {
+                    with_default_session(|session| session.strings.resolve(id).to_string())
+                });
+                Some(format!("{addr} {}", formula.unwrap_or_default()))
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(edits, vec!["B120 A120*3"], "unexpected ops: {ops:?}");
+}
+
+#[test]
+fn resized_spill_range_is_reported() {
+    let taller = Layout {
+        spill_rows: 8,
+        ..BASE
+    };
+    let ops = diff(&BASE, &taller);
+    let spills: Vec<String> = ops
+        .iter()
+        .filter_map(|op| match op {
+            DiffOp::SpillRangeChanged { addr, from, to, .. } => Some(format!(
+                "{addr} {} -> {}",
+                from.map(|r| r.to_a1()).unwrap_or_default(),
+                to.map(|r| r.to_a1()).unwrap_or_default()
+            )),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(spills, vec!["C1 C1:C5 -> C1:C8"], "unexpected ops: {ops:?}");
+}
+
+#[test]
+fn unsharing_keeps_formula_text_as_written() {
+    let formulas: [fn(u32) -> String; 4] = [
+        |r| format!("SUM(A{r}, C{r})"),
+        |r| format!("A{r} * 2"),
+        |r| format!("(A{r}+C{r})"),
+        |r| format!("A{r}+C{r}*1E+21"),
+    ];
+    for formula in formulas {
+        let shared = formula_sheet_xml(formula, true);
+        let individual = formula_sheet_xml(formula, false);
+        let ops = diff_sheets(&shared, &individual);
+        assert!(
+            ops.is_empty(),
+            "{}: expected no changes, got {ops:?}",
+            formula(1)
+        );
+        let ops = diff_sheets(&individual, &shared);
+        assert!(
+            ops.is_empty(),
+            "{}: expected no changes, got {ops:?}",
+            formula(1)
+        );
+    }
+}
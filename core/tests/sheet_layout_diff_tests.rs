@@ -0,0 +1,234 @@
+use excel_diff::{
+    with_default_session, DiffConfig, DiffOp, FreezePane, SheetVisibility, StringPool, VecSink,
+    WorkbookPackage,
+};
+use std::io::Cursor;
+
+/// A sheet whose rows are all distinct and fully populated. `row_attrs` are extra attributes for
+/// the `<row>` at the same index; `head` holds the elements before `<sheetData>` (`sheetPr`,
+/// `sheetViews`, `cols`) and `tail` those after it (`mergeCells`).
+fn sheet_xml(rows: &[u32], row_attrs: &[(usize, &str)], head: &str, tail: &str) -> String {
+    let mut xml = String::from(
+        r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">"#,
+    );
+    xml.push_str(head);
+    xml.push_str("<sheetData>");
+    for (idx, seed) in rows.iter().enumerate() {
+        let r = idx + 1;
+        let attrs = row_attrs
+            .iter()
+            .find(|(row, _)| *row == idx)
+            .map(|(_, attrs)| *attrs)
+            .unwrap_or("");
+        xml.push_str(&format!(
+            r#"<row r="{r}" {attrs}><c r="A{r}"><v>{}</v></c><c r="B{r}"><v>{}</v></c><c r="C{r}"><v>{}</v></c></row>"#,
+            seed,
+            seed * 7 + 3,
+            seed * 13 + 5
+        ));
+    }
+    xml.push_str("</sheetData>");
+    xml.push_str(tail);
+    xml.push_str("</worksheet>");
+    xml
+}
+
+fn merges(refs: &[&str]) -> String {
+    let body: String = refs
+        .iter()
+        .map(|r| format!(r#"<mergeCell ref="{r}"/>"#))
+        .collect();
+    format!(r#"<mergeCells count="{}">{body}</mergeCells>"#, refs.len())
+}
+
+const FROZEN_HEADER: &str = r#"<sheetViews><sheetView workbookViewId="0"><pane ySplit="1" topLeftCell="A2" activePane="bottomLeft" state="frozen"/></sheetView></sheetViews>"#;
+
+/// `state` is the workbook-level sheet state attribute, empty for a visible sheet.
+fn make_xlsx(sheet_xml: &str, state: &str) -> Vec<u8> {
+    use std::io::Write;
+    use zip::write::FileOptions;
+    use zip::CompressionMethod;
+    use zip::ZipWriter;
+
+    let workbook = format!(
+        r#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="Sheet1" sheetId="1" {state} r:id="rId1"/></sheets></workbook>"#
+    );
+    let entries: [(&str, &str); 4] = [
+        ("[Content_Types].xml", "<Types/>"),
+        ("xl/workbook.xml", &workbook),
+        (
+            "xl/_rels/workbook.xml.rels",
+            r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/></Relationships>"#,
+        ),
+        ("xl/worksheets/sheet1.xml", sheet_xml),
+    ];
+
+    let mut buf = Vec::new();
+    {
+        let cursor = Cursor::new(&mut buf);
+        let mut zip = ZipWriter::new(cursor);
+        let options = FileOptions::default().compression_method(CompressionMethod::Stored);
+        for (name, body) in entries {
+            zip.start_file(name, options).expect("start zip entry");
+            zip.write_all(body.as_bytes()).expect("write zip entry");
+        }
+        zip.finish().expect("finish zip");
+    }
+    buf
+}
+
+fn open(bytes: Vec<u8>) -> WorkbookPackage {
+    WorkbookPackage::open(Cursor::new(bytes)).expect("open workbook")
+}
+
+/// Layout ops rendered as short strings for comparison.
+fn layout_changes(ops: &[DiffOp]) -> Vec<String> {
+    ops.iter()
+        .filter_map(|op| match op {
+            DiffOp::MergedRangeAdded { range, .. } => Some(format!("merge+ {range}")),
+            DiffOp::MergedRangeRemoved { range, .. } => Some(format!("merge- {range}")),
+            DiffOp::RowVisibilityChanged {
+                row_idx, hidden, ..
+            } => Some(format!("row {row_idx} hidden={hidden}")),
+            DiffOp::ColumnVisibilityChanged {
+                col_idx, hidden, ..
+            } => Some(format!("col {col_idx} hidden={hidden}")),
+            DiffOp::RowOutlineChanged {
+                row_idx, from, to, ..
+            } => Some(format!("row {row_idx} outline {from}->{to}")),
+            DiffOp::ColumnOutlineChanged {
+                col_idx, from, to, ..
+            } => Some(format!("col {col_idx} outline {from}->{to}")),
+            DiffOp::RowHeightChanged {
+                row_idx, from, to, ..
+            } => Some(format!("row {row_idx} height {from:?}->{to:?}")),
+            DiffOp::ColumnWidthChanged {
+                col_idx, from, to, ..
+            } => Some(format!("col {col_idx} width {from:?}->{to:?}")),
+            DiffOp::FreezePaneChanged { from, to, .. } => Some(format!("freeze {from:?}->{to:?}")),
+            DiffOp::SheetVisibilityChanged { from, to, .. } => {
+                Some(format!("visibility {from:?}->{to:?}"))
+            }
+            DiffOp::SheetTabColorChanged { .. } => Some("tab color".to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn inserted_row_shifts_layout_without_reporting_changes() {
+    with_default_session(|session| session.strings = StringPool::new());
+
+    let cols = r#"<cols><col min="2" max="2" width="20" customWidth="1"/></cols>"#;
+    let old = open(make_xlsx(
+        &sheet_xml(
+            &[1, 2, 3, 4, 5, 6],
+            &[(3, r#"hidden="1""#), (4, r#"ht="30" customHeight="1""#)],
+            &format!("{FROZEN_HEADER}{cols}"),
+            &merges(&["A1:C1", "A6:B6"]),
+        ),
+        "",
+    ));
+    let new = open(make_xlsx(
+        &sheet_xml(
+            &[1, 90, 2, 3, 4, 5, 6],
+            &[(4, r#"hidden="1""#), (5, r#"ht="30" customHeight="1""#)],
+            &format!("{FROZEN_HEADER}{cols}"),
+            &merges(&["A1:C1", "A7:B7"]),
+        ),
+        "",
+    ));
+    let report = old.diff(&new, &DiffConfig::default());
+
+    let added_rows: Vec<u32> = report
+        .ops
+        .iter()
+        .filter_map(|op| match op {
+            DiffOp::RowAdded { row_idx, .. } => Some(*row_idx),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(added_rows, vec![1], "ops: {:?}", report.ops);
+    assert!(
+        layout_changes(&report.ops).is_empty(),
+        "ops: {:?}",
+        report.ops
+    );
+}
+
+#[test]
+fn hidden_rows_unmerged_cells_panes_and_sheet_state_are_reported() {
+    with_default_session(|session| session.strings = StringPool::new());
+
+    let rows = [1, 2, 3, 4, 5];
+    let old = open(make_xlsx(
+        &sheet_xml(
+            &rows,
+            &[(1, r#"outlineLevel="1""#)],
+            FROZEN_HEADER,
+            &merges(&["A1:C1"]),
+        ),
+        "",
+    ));
+    let new = open(make_xlsx(
+        &sheet_xml(
+            &rows,
+            &[(2, r#"hidden="1""#)],
+            r#"<cols><col min="1" max="1" width="12" customWidth="1"/></cols>"#,
+            "",
+        ),
+        r#"state="hidden""#,
+    ));
+    let report = old.diff(&new, &DiffConfig::default());
+
+    assert_eq!(
+        layout_changes(&report.ops),
+        vec![
+            format!(
+                "visibility {:?}->{:?}",
+                SheetVisibility::Visible,
+                SheetVisibility::Hidden
+            ),
+            format!("freeze {:?}->None", Some(FreezePane { rows: 1, cols: 0 })),
+            "merge- A1:C1".to_string(),
+            "row 1 outline 1->0".to_string(),
+            "row 2 hidden=true".to_string(),
+            "col 0 width None->Some(3072)".to_string(),
+        ],
+        "ops: {:?}",
+        report.ops
+    );
+}
+
+#[test]
+fn streaming_diff_reports_layout_like_the_report() {
+    with_default_session(|session| session.strings = StringPool::new());
+
+    let old = open(make_xlsx(
+        &sheet_xml(&[1, 2, 3, 4, 5], &[(3, r#"hidden="1""#)], "", ""),
+        "",
+    ));
+    let new = open(make_xlsx(
+        &sheet_xml(
+            &[1, 70, 2, 3, 4, 5],
+            &[(4, r#"hidden="1""#), (5, r#"hidden="1""#)],
+            "",
+            &merges(&["B2:C2"]),
+        ),
+        "",
+    ));
+    let config = DiffConfig::default();
+    let report = old.diff(&new, &config);
+
+    let mut sink = VecSink::new();
+    old.diff_streaming(&new, &config, &mut sink)
+        .expect("streaming diff");
+    let streamed = sink.into_ops();
+
+    let expected = layout_changes(&report.ops);
+    assert_eq!(
+        expected,
+        vec!["merge+ B2:C2".to_string(), "row 5 hidden=true".to_string()]
+    );
+    assert_eq!(layout_changes(&streamed), expected);
+}
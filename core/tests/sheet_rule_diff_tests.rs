@@ -0,0 +1,260 @@
+use excel_diff::{with_default_session, DiffConfig, DiffOp, StringPool, VecSink, WorkbookPackage};
+use std::io::Cursor;
+
+/// A sheet whose rows are all distinct and fully populated, followed by `rules_xml` (any
+/// `<conditionalFormatting>` and `<dataValidations>` elements, in schema order).
+fn sheet_xml(rows: &[u32], rules_xml: &str) -> String {
+    let mut xml = String::from(
+        r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData>"#,
+    );
+    for (idx, seed) in rows.iter().enumerate() {
+        let r = idx + 1;
+        xml.push_str(&format!(
+            r#"<row r="{r}"><c r="A{r}"><v>{}</v></c><c r="B{r}"><v>{}</v></c><c r="C{r}"><v>{}</v></c></row>"#,
+            seed,
+            seed * 7 + 3,
+            seed * 13 + 5
+        ));
+    }
+    xml.push_str("</sheetData>");
+    xml.push_str(rules_xml);
+    xml.push_str("</worksheet>");
+    xml
+}
+
+fn cf(sqref: &str, rule: &str) -> String {
+    format!(r#"<conditionalFormatting sqref="{sqref}">{rule}</conditionalFormatting>"#)
+}
+
+fn validations(items: &[(&str, &str)]) -> String {
+    let body: String = items
+        .iter()
+        .map(|(sqref, formula)| {
+            format!(
+                r#"<dataValidation type="list" allowBlank="1" sqref="{sqref}"><formula1>{formula}</formula1></dataValidation>"#
+            )
+        })
+        .collect();
+    format!(
+        r#"<dataValidations count="{}">{body}</dataValidations>"#,
+        items.len()
+    )
+}
+
+fn make_xlsx(sheet_xml: &str) -> Vec<u8> {
+    use std::io::Write;
+    use zip::write::FileOptions;
+    use zip::CompressionMethod;
+    use zip::ZipWriter;
+
+    let entries: [(&str, &str); 4] = [
+        ("[Content_Types].xml", "<Types/>"),
+        (
+            "xl/workbook.xml",
+            r#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="Sheet1" sheetId="1" r:id="rId1"/></sheets></workbook>"#,
+        ),
+        (
+            "xl/_rels/workbook.xml.rels",
+            r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/></Relationships>"#,
+        ),
+        ("xl/worksheets/sheet1.xml", sheet_xml),
+    ];
+
+    let mut buf = Vec::new();
+    {
+        let cursor = Cursor::new(&mut buf);
+        let mut zip = ZipWriter::new(cursor);
+        let options = FileOptions::default().compression_method(CompressionMethod::Stored);
+        for (name, body) in entries {
+            zip.start_file(name, options).expect("start zip entry");
+            zip.write_all(body.as_bytes()).expect("write zip entry");
+        }
+        zip.finish().expect("finish zip");
+    }
+    buf
+}
+
+fn open(bytes: Vec<u8>) -> WorkbookPackage {
+    WorkbookPackage::open(Cursor::new(bytes)).expect("open workbook")
+}
+
+fn resolve(id: excel_diff::StringId) -> String {
+    with_default_session(|session| session.strings.resolve(id).to_string())
+}
+
+/// Rule ops rendered as `(op kind, ranges of the reported rule)`.
+fn rule_changes(ops: &[DiffOp]) -> Vec<(&'static str, String)> {
+    let ranges = |ranges: &[excel_diff::CellRange]| {
+        ranges
+            .iter()
+            .map(|r| r.to_a1())
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    ops.iter()
+        .filter_map(|op| match op {
+            DiffOp::DataValidationAdded { rule, .. } => {
+                Some(("DataValidationAdded", ranges(&rule.ranges)))
+            }
+            DiffOp::DataValidationRemoved { rule, .. } => {
+                Some(("DataValidationRemoved", ranges(&rule.ranges)))
+            }
+            DiffOp::DataValidationChanged { to, .. } => {
+                Some(("DataValidationChanged", ranges(&to.ranges)))
+            }
+            DiffOp::ConditionalFormatAdded { rule, .. } => {
+                Some(("ConditionalFormatAdded", ranges(&rule.ranges)))
+            }
+            DiffOp::ConditionalFormatRemoved { rule, .. } => {
+                Some(("ConditionalFormatRemoved", ranges(&rule.ranges)))
+            }
+            DiffOp::ConditionalFormatChanged { to, .. } => {
+                Some(("ConditionalFormatChanged", ranges(&to.ranges)))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn inserted_row_shifts_rule_ranges_and_references_without_reporting_changes() {
+    with_default_session(|session| session.strings = StringPool::new());
+
+    let old_rules = cf(
+        "C2:C8",
+        r#"<cfRule type="expression" dxfId="0" priority="1"><formula>$A2&gt;5</formula></cfRule>"#,
+    ) + &validations(&[("B3:B8", "$F$1:$F$3"), ("A1", "\"Yes,No\"")]);
+    let new_rules = cf(
+        "C3:C9",
+        r#"<cfRule type="expression" dxfId="0" priority="1"><formula>$A3&gt;5</formula></cfRule>"#,
+    ) + &validations(&[("B4:B9", "$F$1:$F$4"), ("A1", "\"Yes,No\"")]);
+
+    let old = open(make_xlsx(&sheet_xml(&[1, 2, 3, 4, 5, 6, 7, 8], &old_rules)));
+    let new = open(make_xlsx(&sheet_xml(
+        &[1, 90, 2, 3, 4, 5, 6, 7, 8],
+        &new_rules,
+    )));
+    let report = old.diff(&new, &DiffConfig::default());
+
+    let added_rows: Vec<u32> = report
+        .ops
+        .iter()
+        .filter_map(|op| match op {
+            DiffOp::RowAdded { row_idx, .. } => Some(*row_idx),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(added_rows, vec![1], "ops: {:?}", report.ops);
+    assert!(
+        rule_changes(&report.ops).is_empty(),
+        "ops: {:?}",
+        report.ops
+    );
+}
+
+#[test]
+fn edited_list_source_and_deleted_format_are_reported() {
+    with_default_session(|session| session.strings = StringPool::new());
+
+    let old_rules = cf(
+        "A1:A6",
+        r#"<cfRule type="duplicateValues" dxfId="1" priority="2"/>"#,
+    ) + &cf(
+        "B2:B6",
+        r#"<cfRule type="cellIs" dxfId="0" priority="1" operator="greaterThan"><formula>10</formula></cfRule>"#,
+    ) + &validations(&[("C2:C6", "\"Yes,No\"")]);
+    let new_rules = cf(
+        "B2:B6",
+        r#"<cfRule type="cellIs" dxfId="0" priority="1" operator="greaterThan"><formula>10</formula></cfRule>"#,
+    ) + &validations(&[("C2:C6", "\"Yes,No,Maybe\"")]);
+
+    let rows = [1, 2, 3, 4, 5, 6];
+    let old = open(make_xlsx(&sheet_xml(&rows, &old_rules)));
+    let new = open(make_xlsx(&sheet_xml(&rows, &new_rules)));
+    let report = old.diff(&new, &DiffConfig::default());
+
+    assert_eq!(
+        rule_changes(&report.ops),
+        vec![
+            ("DataValidationChanged", "C2:C6".to_string()),
+            ("ConditionalFormatRemoved", "A1:A6".to_string()),
+        ],
+        "ops: {:?}",
+        report.ops
+    );
+    let changed = report
+        .ops
+        .iter()
+        .find_map(|op| match op {
+            DiffOp::DataValidationChanged { from, to, .. } => Some((from, to)),
+            _ => None,
+        })
+        .expect("validation change");
+    assert_eq!(
+        changed.0.formula1.map(resolve).as_deref(),
+        Some("\"Yes,No\"")
+    );
+    assert_eq!(
+        changed.1.formula1.map(resolve).as_deref(),
+        Some("\"Yes,No,Maybe\"")
+    );
+}
+
+#[test]
+fn streaming_diff_translates_rule_ranges_like_the_report() {
+    with_default_session(|session| session.strings = StringPool::new());
+
+    let old = open(make_xlsx(&sheet_xml(
+        &[1, 2, 3, 4, 5, 6],
+        &validations(&[("B2:B6", "\"a,b\""), ("C5", "\"x\"")]),
+    )));
+    let new = open(make_xlsx(&sheet_xml(
+        &[1, 70, 2, 3, 4, 5, 6],
+        &validations(&[("B3:B7", "\"a,b\""), ("C6", "\"y\"")]),
+    )));
+    let config = DiffConfig::default();
+    let report = old.diff(&new, &config);
+
+    let mut sink = VecSink::new();
+    old.diff_streaming(&new, &config, &mut sink)
+        .expect("streaming diff");
+    let streamed = sink.into_ops();
+
+    let expected = rule_changes(&report.ops);
+    assert_eq!(expected, vec![("DataValidationChanged", "C6".to_string())]);
+    assert_eq!(rule_changes(&streamed), expected);
+}
+
+#[test]
+fn extended_format_range_is_reported_as_a_change() {
+    with_default_session(|session| session.strings = StringPool::new());
+
+    let rule = r#"<cfRule type="cellIs" dxfId="0" priority="1" operator="greaterThan"><formula>10</formula></cfRule>"#;
+    let duplicates = cf(
+        "A1:A6",
+        r#"<cfRule type="duplicateValues" dxfId="1" priority="2"/>"#,
+    );
+    let old_rules = cf("B2:B6", rule) + &duplicates;
+    let new_rules = cf("B2:B8", rule) + &duplicates;
+
+    let rows = [1, 2, 3, 4, 5, 6, 7, 8];
+    let old = open(make_xlsx(&sheet_xml(&rows, &old_rules)));
+    let new = open(make_xlsx(&sheet_xml(&rows, &new_rules)));
+    let report = old.diff(&new, &DiffConfig::default());
+
+    assert_eq!(
+        rule_changes(&report.ops),
+        vec![("ConditionalFormatChanged", "B2:B8".to_string())],
+        "ops: {:?}",
+        report.ops
+    );
+    let from = report
+        .ops
+        .iter()
+        .find_map(|op| match op {
+            DiffOp::ConditionalFormatChanged { from, .. } => Some(from),
+            _ => None,
+        })
+        .expect("format change");
+    assert_eq!(from.ranges[0].to_a1(), "B2:B6");
+}
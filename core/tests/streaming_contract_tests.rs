@@ -138,6 +138,7 @@ fn make_workbook(pool: &mut StringPool, values: &[f64]) -> Workbook {
             workbook_sheet_id: None,
             kind: SheetKind::Worksheet,
             grid,
+            layout: Default::default(),
         }],
         ..Default::default()
     }
@@ -157,6 +158,7 @@ fn make_sheet(pool: &mut StringPool, name: &str, values: &[f64]) -> Sheet {
         workbook_sheet_id: None,
         kind: SheetKind::Worksheet,
         grid: make_grid(values),
+        layout: Default::default(),
     }
 }
 
@@ -200,9 +202,61 @@ fn is_object_op(op: &DiffOp) -> bool {
             | DiffOp::ChartAdded { .. }
             | DiffOp::ChartRemoved { .. }
             | DiffOp::ChartChanged { .. }
+            | DiffOp::PivotTableAdded { .. }
+            | DiffOp::PivotTableRemoved { .. }
+            | DiffOp::PivotTableSourceChanged { .. }
+            | DiffOp::PivotTableLayoutChanged { .. }
+            | DiffOp::ExternalLinkAdded { .. }
+            | DiffOp::ExternalLinkRemoved { .. }
+            | DiffOp::ExternalLinkChanged { .. }
+            | DiffOp::ConnectionAdded { .. }
+            | DiffOp::ConnectionRemoved { .. }
+            | DiffOp::ConnectionChanged { .. }
+            | DiffOp::ReportObjectAdded { .. }
+            | DiffOp::ReportObjectRemoved { .. }
+            | DiffOp::ReportObjectChanged { .. }
+            | DiffOp::WorkbookSettingsChanged { .. }
+            | DiffOp::WorkbookProtectionChanged { .. }
+            | DiffOp::DocumentPropertyChanged { .. }
+            | DiffOp::CellStyleChanged { .. }
+            | DiffOp::CommentAdded { .. }
+            | DiffOp::CommentRemoved { .. }
+            | DiffOp::CommentChanged { .. }
+            | DiffOp::DataValidationAdded { .. }
+            | DiffOp::DataValidationRemoved { .. }
+            | DiffOp::DataValidationChanged { .. }
+            | DiffOp::ConditionalFormatAdded { .. }
+            | DiffOp::ConditionalFormatRemoved { .. }
+            | DiffOp::ConditionalFormatChanged { .. }
+            | DiffOp::MergedRangeAdded { .. }
+            | DiffOp::MergedRangeRemoved { .. }
+            | DiffOp::RowVisibilityChanged { .. }
+            | DiffOp::ColumnVisibilityChanged { .. }
+            | DiffOp::RowOutlineChanged { .. }
+            | DiffOp::ColumnOutlineChanged { .. }
+            | DiffOp::RowHeightChanged { .. }
+            | DiffOp::ColumnWidthChanged { .. }
+            | DiffOp::FreezePaneChanged { .. }
+            | DiffOp::SpillRangeChanged { .. }
+            | DiffOp::SheetVisibilityChanged { .. }
+            | DiffOp::SheetTabColorChanged { .. }
+            | DiffOp::SheetProtectionChanged { .. }
+            | DiffOp::ExcelTableAdded { .. }
+            | DiffOp::ExcelTableRemoved { .. }
+            | DiffOp::ExcelTableRenamed { .. }
+            | DiffOp::ExcelTableResized { .. }
+            | DiffOp::ExcelTableColumnRenamed { .. }
+            | DiffOp::DrawingObjectAdded { .. }
+            | DiffOp::DrawingObjectRemoved { .. }
+            | DiffOp::DrawingObjectMoved { .. }
+            | DiffOp::DrawingObjectChanged { .. }
             | DiffOp::VbaModuleAdded { .. }
             | DiffOp::VbaModuleRemoved { .. }
             | DiffOp::VbaModuleChanged { .. }
+            | DiffOp::VbaReferenceAdded { .. }
+            | DiffOp::VbaReferenceRemoved { .. }
+            | DiffOp::VbaReferenceChanged { .. }
+            | DiffOp::VbaProjectChanged { .. }
     )
 }
 
@@ -579,6 +633,7 @@ fn workbook_package_streaming_orders_categories() {
         workbook: wb_a,
         data_mashup: Some(dm_a),
         vba_modules: None,
+        vba_project: None,
         #[cfg(feature = "perf-metrics")]
         parse_time_ms: 0,
     };
@@ -586,6 +641,7 @@ fn workbook_package_streaming_orders_categories() {
         workbook: wb_b,
         data_mashup: Some(dm_b),
         vba_modules,
+        vba_project: None,
         #[cfg(feature = "perf-metrics")]
         parse_time_ms: 0,
     };
@@ -22,6 +22,7 @@ fn make_workbook(pool: &mut StringPool, value: f64) -> Workbook {
             workbook_sheet_id: None,
             kind: SheetKind::Worksheet,
             grid,
+            layout: Default::default(),
         }],
         ..Default::default()
     }
@@ -76,6 +77,7 @@ fn build_packages(pool: &mut StringPool) -> (WorkbookPackage, WorkbookPackage) {
         workbook: wb_a,
         data_mashup: Some(dm_a),
         vba_modules: None,
+        vba_project: None,
         #[cfg(feature = "perf-metrics")]
         parse_time_ms: 0,
     };
@@ -83,6 +85,7 @@ fn build_packages(pool: &mut StringPool) -> (WorkbookPackage, WorkbookPackage) {
         workbook: wb_b,
         data_mashup: Some(dm_b),
         vba_modules,
+        vba_project: None,
         #[cfg(feature = "perf-metrics")]
         parse_time_ms: 0,
     };
@@ -20,6 +20,7 @@ fn make_test_workbook(session: &mut DiffSession, values: &[f64]) -> Workbook {
             workbook_sheet_id: None,
             kind: SheetKind::Worksheet,
             grid,
+            layout: Default::default(),
         }],
         ..Default::default()
     }
@@ -0,0 +1,199 @@
+mod common;
+
+use common::{sid, single_sheet_workbook};
+use excel_diff::{
+    CellValue, DiffConfig, DiffOp, DiffReport, Grid, ValueRuleOverride, ValueRules, Workbook,
+    WorkbookPackage,
+};
+
+fn diff_workbooks(old: &Workbook, new: &Workbook, config: &DiffConfig) -> DiffReport {
+    WorkbookPackage::from(old.clone()).diff(&WorkbookPackage::from(new.clone()), config)
+}
+
+fn grid_from_values(rows: &[Vec<Option<CellValue>>]) -> Grid {
+    let nrows = rows.len() as u32;
+    let ncols = rows.iter().map(Vec::len).max().unwrap_or(0) as u32;
+    let mut grid = Grid::new(nrows, ncols);
+    for (r, row) in rows.iter().enumerate() {
+        for (c, value) in row.iter().enumerate() {
+            if value.is_some() {
+                grid.insert_cell(r as u32, c as u32, *value, None);
+            }
+        }
+    }
+    grid
+}
+
+fn number_rows(count: usize, noise: f64) -> Vec<Vec<Option<CellValue>>> {
+    (0..count)
+        .map(|r| {
+            vec![
+                Some(CellValue::Text(sid(&format!("item-{r}")))),
+                Some(CellValue::Number(r as f64 / 3.0 + noise)),
+                Some(CellValue::Number(r as f64 * 1.1 + noise)),
+            ]
+        })
+        .collect()
+}
+
+fn edited_cells(report: &DiffReport) -> Vec<String> {
+    report
+        .ops
+        .iter()
+        .filter_map(|op| match op {
+            DiffOp::CellEdited { addr, .. } => Some(addr.to_a1()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn tolerance(abs: f64) -> ValueRules {
+    ValueRules {
+        numeric_abs_tolerance: abs,
+        ..ValueRules::default()
+    }
+}
+
+#[test]
+fn recalculation_noise_is_ignored_within_tolerance_even_with_inserted_row() {
+    let old_rows = number_rows(12, 0.0);
+    let mut new_rows = number_rows(12, 3e-12);
+    new_rows.insert(
+        5,
+        vec![
+            Some(CellValue::Text(sid("inserted"))),
+            Some(CellValue::Number(42.0)),
+            Some(CellValue::Number(43.0)),
+        ],
+    );
+    let old = single_sheet_workbook("Sheet1", grid_from_values(&old_rows));
+    let new = single_sheet_workbook("Sheet1", grid_from_values(&new_rows));
+
+    let exact = diff_workbooks(&old, &new, &DiffConfig::default());
+    assert!(
+        !edited_cells(&exact).is_empty(),
+        "exact comparison should report the recalculation noise"
+    );
+
+    let config = DiffConfig::builder()
+        .value_rules(tolerance(1e-9))
+        .build()
+        .expect("valid config");
+    let report = diff_workbooks(&old, &new, &config);
+
+    assert!(edited_cells(&report).is_empty(), "ops: {:?}", report.ops);
+    let added: Vec<u32> = report
+        .ops
+        .iter()
+        .filter_map(|op| match op {
+            DiffOp::RowAdded { row_idx, .. } => Some(*row_idx),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(added, vec![5]);
+}
+
+#[test]
+fn changes_beyond_tolerance_are_still_reported_with_original_values() {
+    let old_rows = number_rows(4, 0.0);
+    let mut new_rows = number_rows(4, 3e-12);
+    new_rows[2][1] = Some(CellValue::Number(2.0 / 3.0 + 0.01));
+    let old = single_sheet_workbook("Sheet1", grid_from_values(&old_rows));
+    let new = single_sheet_workbook("Sheet1", grid_from_values(&new_rows));
+
+    let config = DiffConfig::builder()
+        .value_rules(tolerance(1e-9))
+        .build()
+        .expect("valid config");
+    let report = diff_workbooks(&old, &new, &config);
+
+    let edits: Vec<_> = report
+        .ops
+        .iter()
+        .filter_map(|op| match op {
+            DiffOp::CellEdited { addr, from, to, .. } => Some((addr.to_a1(), from.value, to.value)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        edits,
+        vec![(
+            "B3".to_string(),
+            Some(CellValue::Number(2.0 / 3.0)),
+            Some(CellValue::Number(2.0 / 3.0 + 0.01)),
+        )]
+    );
+}
+
+#[test]
+fn text_rules_ignore_case_whitespace_and_empty_strings() {
+    let row = |label: &str, region: &str, note: Option<CellValue>| {
+        vec![
+            Some(CellValue::Text(sid(label))),
+            Some(CellValue::Text(sid(region))),
+            note,
+            Some(CellValue::Number(label.trim().len() as f64)),
+        ]
+    };
+    let old = single_sheet_workbook(
+        "Sheet1",
+        grid_from_values(&[
+            row("Total", "East", None),
+            row("Net", "West", Some(CellValue::Blank)),
+            row("Gross", "North", Some(CellValue::Text(sid("ok")))),
+            row("Tax", "East", Some(CellValue::Text(sid("ok")))),
+        ]),
+    );
+    let new = single_sheet_workbook(
+        "Sheet1",
+        grid_from_values(&[
+            row("  total ", "EAST", Some(CellValue::Text(sid("")))),
+            row("net", "West ", None),
+            row("Gross", "South", Some(CellValue::Text(sid("OK")))),
+            row("TAX", "east", Some(CellValue::Text(sid("ok ")))),
+        ]),
+    );
+
+    let config = DiffConfig::builder()
+        .value_rules(ValueRules {
+            ignore_text_case: true,
+            trim_text_whitespace: true,
+            blank_equals_empty_text: true,
+            ..ValueRules::default()
+        })
+        .build()
+        .expect("valid config");
+    let report = diff_workbooks(&old, &new, &config);
+
+    assert_eq!(
+        edited_cells(&report),
+        vec!["B3".to_string()],
+        "ops: {:?}",
+        report.ops
+    );
+}
+
+#[test]
+fn column_override_limits_tolerance_to_named_columns() {
+    let old_rows = number_rows(4, 0.0);
+    let new_rows = number_rows(4, 0.001);
+    let old = single_sheet_workbook("Prices", grid_from_values(&old_rows));
+    let new = single_sheet_workbook("Prices", grid_from_values(&new_rows));
+
+    let config = DiffConfig::builder()
+        .value_rule_override(ValueRuleOverride {
+            sheet: Some("prices".to_string()),
+            columns: vec!["C".to_string()],
+            rules: tolerance(0.01),
+        })
+        .build()
+        .expect("valid config");
+    let report = diff_workbooks(&old, &new, &config);
+
+    let edited = edited_cells(&report);
+    assert!(!edited.is_empty());
+    assert!(
+        edited.iter().all(|addr| addr.starts_with('B')),
+        "only column B lacks a tolerance: {edited:?}"
+    );
+}
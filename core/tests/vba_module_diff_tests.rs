@@ -0,0 +1,287 @@
+mod common;
+
+use common::collect_string_ids;
+use excel_diff::{
+    DiffConfig, DiffOp, DiffReport, JsonLinesSink, StringPool, TextLineKind, VbaModule,
+    VbaModuleType, VbaProcedureChangeKind, VbaProcedureKind, VbaProject, VbaProjectProperty,
+    VbaReference, Workbook, WorkbookPackage,
+};
+use serde::Deserialize;
+
+const OLD_CODE: &str = concat!(
+    "Attribute VB_Name = \"Class1\"\r\n",
+    "Attribute VB_PredeclaredId = False\r\n",
+    "Option Explicit\r\n",
+    "\r\n",
+    "Public Sub Keep()\r\n",
+    "    Debug.Print 1\r\n",
+    "End Sub\r\n",
+    "\r\n",
+    "Private Function Total(x As Long) As Long\r\n",
+    "    Total = x + 1\r\n",
+    "End Function\r\n",
+    "\r\n",
+    "Sub Gone()\r\n",
+    "End Sub\r\n",
+);
+
+const NEW_CODE: &str = concat!(
+    "Attribute VB_Name = \"Class1\"\r\n",
+    "Attribute VB_PredeclaredId = True\r\n",
+    "Option Explicit\r\n",
+    "\r\n",
+    "Public Sub Keep()\r\n",
+    "    Debug.Print 1\r\n",
+    "End Sub\r\n",
+    "\r\n",
+    "Private Function Total(x As Long) As Long\r\n",
+    "    Total = x + 2\r\n",
+    "End Function\r\n",
+    "\r\n",
+    "Public Property Get Count() As Long\r\n",
+    "    Count = 0\r\n",
+    "End Property\r\n",
+);
+
+fn package(pool: &mut StringPool, module_type: VbaModuleType, code: &str) -> WorkbookPackage {
+    let mut pkg = WorkbookPackage::from(Workbook::default());
+    pkg.vba_modules = Some(vec![VbaModule {
+        name: pool.intern("Class1"),
+        module_type,
+        code: code.to_string(),
+    }]);
+    pkg
+}
+
+fn resolve(report: &DiffReport, id: excel_diff::StringId) -> &str {
+    report.resolve(id).expect("string id should resolve")
+}
+
+#[test]
+fn vba_module_changed_carries_type_attributes_procedures_and_hunks() {
+    let mut pool = StringPool::new();
+    let old = package(&mut pool, VbaModuleType::Standard, OLD_CODE);
+    let new = package(&mut pool, VbaModuleType::Class, NEW_CODE);
+
+    let report = old.diff_with_pool(&new, &mut pool, &DiffConfig::default());
+    let op = report
+        .ops
+        .iter()
+        .find(|op| matches!(op, DiffOp::VbaModuleChanged { .. }))
+        .expect("expected VbaModuleChanged");
+    let DiffOp::VbaModuleChanged {
+        name,
+        module_type,
+        attributes,
+        procedures,
+        hunks,
+    } = op
+    else {
+        unreachable!();
+    };
+
+    assert_eq!(resolve(&report, *name), "Class1");
+    assert_eq!(
+        *module_type,
+        Some((VbaModuleType::Standard, VbaModuleType::Class))
+    );
+
+    assert_eq!(attributes.len(), 1);
+    assert_eq!(resolve(&report, attributes[0].name), "VB_PredeclaredId");
+    assert_eq!(
+        attributes[0].from.map(|id| resolve(&report, id)),
+        Some("False")
+    );
+    assert_eq!(
+        attributes[0].to.map(|id| resolve(&report, id)),
+        Some("True")
+    );
+
+    let procedures: Vec<(&str, VbaProcedureKind, VbaProcedureChangeKind)> = procedures
+        .iter()
+        .map(|p| (resolve(&report, p.name), p.kind, p.change))
+        .collect();
+    assert_eq!(
+        procedures,
+        vec![
+            (
+                "Total",
+                VbaProcedureKind::Function,
+                VbaProcedureChangeKind::Modified
+            ),
+            (
+                "Count",
+                VbaProcedureKind::PropertyGet,
+                VbaProcedureChangeKind::Added
+            ),
+            (
+                "Gone",
+                VbaProcedureKind::Sub,
+                VbaProcedureChangeKind::Removed
+            ),
+        ]
+    );
+
+    // Attribute lines are not part of the code, so line 1 is `Option Explicit`.
+    assert_eq!(hunks.len(), 1);
+    let hunk = &hunks[0];
+    assert_eq!(
+        (hunk.old_start, hunk.old_len, hunk.new_start, hunk.new_len),
+        (5, 8, 5, 9)
+    );
+    assert_eq!(hunk.procedure.map(|id| resolve(&report, id)), Some("Total"));
+    let removed: Vec<&str> = hunk
+        .lines
+        .iter()
+        .filter(|line| line.kind == TextLineKind::Removed)
+        .map(|line| resolve(&report, line.text))
+        .collect();
+    assert_eq!(removed, vec!["    Total = x + 1", "Sub Gone()", "End Sub"]);
+    let added = hunk
+        .lines
+        .iter()
+        .filter(|line| line.kind == TextLineKind::Added)
+        .count();
+    assert_eq!(added, 4);
+}
+
+#[test]
+fn vba_module_diff_ignores_line_ending_changes() {
+    let mut pool = StringPool::new();
+    let old = package(&mut pool, VbaModuleType::Standard, OLD_CODE);
+    let new = package(
+        &mut pool,
+        VbaModuleType::Standard,
+        &OLD_CODE.replace("\r\n", "\n"),
+    );
+
+    let report = old.diff_with_pool(&new, &mut pool, &DiffConfig::default());
+    assert!(
+        !report
+            .ops
+            .iter()
+            .any(|op| matches!(op, DiffOp::VbaModuleChanged { .. })),
+        "line endings alone should not change a module"
+    );
+}
+
+#[test]
+fn vba_hunk_text_is_in_streaming_header() {
+    #[derive(Deserialize)]
+    struct Header {
+        strings: Vec<String>,
+    }
+
+    let mut pool = StringPool::new();
+    let old = package(&mut pool, VbaModuleType::Standard, OLD_CODE);
+    let new = package(&mut pool, VbaModuleType::Standard, NEW_CODE);
+
+    let mut out = Vec::<u8>::new();
+    let mut sink = JsonLinesSink::new(&mut out);
+    old.diff_streaming_with_pool(&new, &mut pool, &DiffConfig::default(), &mut sink)
+        .expect("diff_streaming should succeed");
+
+    let text = std::str::from_utf8(&out).expect("output should be valid UTF-8");
+    let mut lines = text.lines().filter(|l| !l.trim().is_empty());
+    let header: Header =
+        serde_json::from_str(lines.next().expect("header line")).expect("header should parse");
+    assert!(header.strings.iter().any(|s| s == "    Total = x + 2"));
+
+    let mut saw_changed = false;
+    for line in lines {
+        let op: DiffOp = serde_json::from_str(line).expect("op line should parse");
+        saw_changed |= matches!(op, DiffOp::VbaModuleChanged { .. });
+        for id in collect_string_ids(&op) {
+            assert!((id.0 as usize) < header.strings.len());
+        }
+    }
+    assert!(saw_changed, "expected VbaModuleChanged in the stream");
+}
+
+fn reference(name: &str, libid: &str) -> VbaReference {
+    VbaReference {
+        name: name.to_string(),
+        libid: libid.to_string(),
+    }
+}
+
+#[test]
+fn vba_project_diff_reports_references_and_protection() {
+    const OFFICE_27: &str = r"*\G{2DF8D04C-5BFA-101B-BDE5-00AA0044DE52}#2.7#0#MSO.DLL#Office";
+    const OFFICE_28: &str = r"*\G{2DF8D04C-5BFA-101B-BDE5-00AA0044DE52}#2.8#0#MSO.DLL#Office";
+    const SCRIPTING: &str = concat!(
+        r"*\G{420B2830-E718-11CF-893D-00A0C9054228}#1.0#0#",
+        r"C:\Windows\System32\scrrun.dll#Microsoft Scripting Runtime"
+    );
+    const FORMS: &str = r"*\G{0D452EE1-E08F-101A-852E-02608C4D0BB4}#2.0#0#FM20.DLL#Forms";
+
+    let mut pool = StringPool::new();
+    let mut old = package(&mut pool, VbaModuleType::Standard, OLD_CODE);
+    let mut new = package(&mut pool, VbaModuleType::Standard, OLD_CODE);
+    old.vba_project = Some(VbaProject {
+        name: "VBAProject".to_string(),
+        references: vec![reference("Office", OFFICE_27), reference("MSForms", FORMS)],
+        ..Default::default()
+    });
+    new.vba_project = Some(VbaProject {
+        name: "VBAProject".to_string(),
+        constants: "DEBUG = 1".to_string(),
+        references: vec![
+            reference("office", OFFICE_28),
+            reference("Scripting", SCRIPTING),
+        ],
+        locked_for_viewing: true,
+        ..Default::default()
+    });
+
+    let report = old.diff_with_pool(&new, &mut pool, &DiffConfig::default());
+    let mut references = Vec::new();
+    let mut properties = Vec::new();
+    for op in &report.ops {
+        match op {
+            DiffOp::VbaReferenceAdded { name, libid } => references.push(format!(
+                "+{} {}",
+                resolve(&report, *name),
+                resolve(&report, *libid)
+            )),
+            DiffOp::VbaReferenceRemoved { name, .. } => {
+                references.push(format!("-{}", resolve(&report, *name)))
+            }
+            DiffOp::VbaReferenceChanged { name, from, to } => {
+                assert_eq!(resolve(&report, *from), OFFICE_27);
+                assert_eq!(resolve(&report, *to), OFFICE_28);
+                references.push(format!("~{}", resolve(&report, *name)))
+            }
+            DiffOp::VbaProjectChanged { property, from, to } => properties.push((
+                *property,
+                from.map(|id| resolve(&report, id).to_string()),
+                to.map(|id| resolve(&report, id).to_string()),
+            )),
+            _ => {}
+        }
+    }
+
+    assert_eq!(
+        references,
+        vec![
+            "-MSForms".to_string(),
+            "~office".to_string(),
+            format!("+Scripting {SCRIPTING}"),
+        ]
+    );
+    assert_eq!(
+        properties,
+        vec![
+            (
+                VbaProjectProperty::Constants,
+                None,
+                Some("DEBUG = 1".to_string())
+            ),
+            (
+                VbaProjectProperty::LockedForViewing,
+                Some("false".to_string()),
+                Some("true".to_string())
+            ),
+        ]
+    );
+}
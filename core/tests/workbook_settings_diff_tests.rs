@@ -0,0 +1,205 @@
+use excel_diff::{
+    with_default_session, DiffConfig, DiffOp, StringId, WorkbookPackage, WorkbookProtection,
+    WorkbookSetting,
+};
+use std::io::Cursor;
+
+/// What one side of a settings test contains.
+struct Settings<'a> {
+    /// `calcMode` on `<calcPr>`.
+    calc_mode: &'a str,
+    /// Raw `<workbookProtection>` element, or empty.
+    workbook_protection: &'a str,
+    /// Raw `<sheetProtection>` element, or empty.
+    sheet_protection: &'a str,
+    /// `dc:title` in the core properties.
+    title: &'a str,
+}
+
+fn workbook_xml(s: &Settings) -> String {
+    format!(
+        concat!(
+            r#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" "#,
+            r#"xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">"#,
+            r#"<workbookPr defaultThemeVersion="166925"/>{}"#,
+            r#"<sheets><sheet name="Inputs" sheetId="1" r:id="rId1"/></sheets>"#,
+            r#"<calcPr calcId="191029" calcMode="{}"/></workbook>"#,
+        ),
+        s.workbook_protection, s.calc_mode
+    )
+}
+
+fn sheet_xml(s: &Settings) -> String {
+    format!(
+        concat!(
+            r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">"#,
+            r#"<sheetData><row r="1"><c r="A1"><v>1</v></c></row></sheetData>{}</worksheet>"#,
+        ),
+        s.sheet_protection
+    )
+}
+
+/// `lastModifiedBy` follows `calc_mode`, so a changed side also looks saved by someone else.
+fn core_xml(s: &Settings) -> String {
+    format!(
+        concat!(
+            r#"<cp:coreProperties "#,
+            r#"xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/"#,
+            r#"core-properties" "#,
+            r#"xmlns:dc="http://purl.org/dc/elements/1.1/">"#,
+            r#"<dc:title>{}</dc:title><cp:lastModifiedBy>{}</cp:lastModifiedBy>"#,
+            r#"</cp:coreProperties>"#,
+        ),
+        s.title, s.calc_mode
+    )
+}
+
+fn make_xlsx(s: &Settings) -> Vec<u8> {
+    use std::io::Write;
+    use zip::write::FileOptions;
+    use zip::CompressionMethod;
+    use zip::ZipWriter;
+
+    let workbook = workbook_xml(s);
+    let sheet = sheet_xml(s);
+    let core = core_xml(s);
+    let entries: [(&str, &str); 5] = [
+        ("[Content_Types].xml", "<Types/>"),
+        ("xl/workbook.xml", &workbook),
+        (
+            "xl/_rels/workbook.xml.rels",
+            r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/></Relationships>"#,
+        ),
+        ("xl/worksheets/sheet1.xml", &sheet),
+        ("docProps/core.xml", &core),
+    ];
+
+    let mut buf = Vec::new();
+    {
+        let cursor = Cursor::new(&mut buf);
+        let mut zip = ZipWriter::new(cursor);
+        let options = FileOptions::default().compression_method(CompressionMethod::Stored);
+        for (name, body) in entries {
+            zip.start_file(name, options).expect("start zip entry");
+            zip.write_all(body.as_bytes()).expect("write zip entry");
+        }
+        zip.finish().expect("finish zip");
+    }
+    buf
+}
+
+fn open(s: &Settings) -> WorkbookPackage {
+    WorkbookPackage::open(Cursor::new(make_xlsx(s))).expect("open workbook")
+}
+
+fn resolve(id: Option<StringId>) -> Option<String> {
+    id.map(|id| with_default_session(|session| session.strings.resolve(id).to_string()))
+}
+
+fn settings_ops(old: &Settings, new: &Settings) -> Vec<DiffOp> {
+    open(old)
+        .diff(&open(new), &DiffConfig::default())
+        .ops
+        .into_iter()
+        .filter(|op| {
+            matches!(
+                op,
+                DiffOp::WorkbookSettingsChanged { .. }
+                    | DiffOp::WorkbookProtectionChanged { .. }
+                    | DiffOp::DocumentPropertyChanged { .. }
+                    | DiffOp::SheetProtectionChanged { .. }
+            )
+        })
+        .collect()
+}
+
+const BASE: Settings<'static> = Settings {
+    calc_mode: "auto",
+    workbook_protection: "",
+    sheet_protection: "",
+    title: "Q3 budget",
+};
+
+#[test]
+fn switch_to_manual_calculation_is_reported() {
+    assert_eq!(settings_ops(&BASE, &BASE), Vec::new());
+
+    let manual = Settings {
+        calc_mode: "manual",
+        ..BASE
+    };
+    let ops = settings_ops(&BASE, &manual);
+    assert_eq!(ops.len(), 1, "expected one settings change, got {ops:?}");
+    let DiffOp::WorkbookSettingsChanged { changes } = &ops[0] else {
+        panic!("expected a settings change, got {:?}", ops[0]);
+    };
+    let changes: Vec<(WorkbookSetting, Option<String>, Option<String>)> = changes
+        .iter()
+        .map(|c| (c.setting, resolve(c.from), resolve(c.to)))
+        .collect();
+    assert_eq!(
+        changes,
+        vec![(
+            WorkbookSetting::CalcMode,
+            Some("automatic".to_string()),
+            Some("manual".to_string()),
+        )]
+    );
+}
+
+#[test]
+fn protection_and_title_changes_are_reported() {
+    let locked = Settings {
+        workbook_protection: r#"<workbookProtection lockStructure="1"/>"#,
+        sheet_protection: concat!(
+            r#"<sheetProtection sheet="1" password="CC1A" objects="1" scenarios="1" "#,
+            r#"formatCells="0"/>"#,
+        ),
+        title: "Q4 budget",
+        ..BASE
+    };
+    let ops = settings_ops(&BASE, &locked);
+    assert_eq!(ops.len(), 3, "expected three changes, got {ops:?}");
+
+    assert!(ops.contains(&DiffOp::WorkbookProtectionChanged {
+        from: None,
+        to: Some(WorkbookProtection {
+            lock_structure: true,
+            ..WorkbookProtection::default()
+        }),
+    }));
+
+    let Some(DiffOp::DocumentPropertyChanged { name, from, to }) = ops
+        .iter()
+        .find(|op| matches!(op, DiffOp::DocumentPropertyChanged { .. }))
+    else {
+        panic!("expected a property change, got {ops:?}");
+    };
+    assert_eq!(
+        (resolve(Some(*name)), resolve(*from), resolve(*to)),
+        (
+            Some("core:title".to_string()),
+            Some("Q3 budget".to_string()),
+            Some("Q4 budget".to_string())
+        )
+    );
+
+    let Some(DiffOp::SheetProtectionChanged { from, to, .. }) = ops
+        .iter()
+        .find(|op| matches!(op, DiffOp::SheetProtectionChanged { .. }))
+    else {
+        panic!("expected a sheet protection change, got {ops:?}");
+    };
+    assert_eq!(*from, None);
+    let to = to.as_ref().expect("sheet is protected");
+    assert!(to.has_password);
+    let allowed: Vec<String> = to
+        .allowed
+        .iter()
+        .filter_map(|id| resolve(Some(*id)))
+        .collect();
+    assert_eq!(
+        allowed,
+        vec!["formatCells", "selectLockedCells", "selectUnlockedCells"]
+    );
+}
@@ -1779,9 +1779,9 @@ mod tests {
                 workbook_sheet_id: None,
                 kind: SheetKind::Worksheet,
                 grid,
+                layout: Default::default(),
             }],
-            named_ranges: Vec::new(),
-            charts: Vec::new(),
+            ..Default::default()
         }
     }
 
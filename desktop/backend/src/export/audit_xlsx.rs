@@ -2,7 +2,9 @@ use std::path::Path;
 
 use excel_diff::{CellAddress, CellValue, DiffOp, ExpressionChangeKind, QueryChangeKind};
 #[cfg(feature = "model-diff")]
-use excel_diff::{ModelColumnProperty, RelationshipProperty};
+use excel_diff::{
+    MeasureProperty, ModelColumnProperty, ModelObjectField, ModelObjectKind, RelationshipProperty,
+};
 use rust_xlsxwriter::{Format, Workbook, XlsxError};
 use thiserror::Error;
 
@@ -352,6 +354,45 @@ fn write_op(
                 detail,
             );
         }
+        DiffOp::RecordChanged {
+            sheet,
+            key,
+            new_row,
+            fields,
+            ..
+        } => {
+            let structure_sheet = sheet_mut(workbook, "Structure")?;
+            let changes: Vec<String> = fields
+                .iter()
+                .map(|field| {
+                    let name = match field.field {
+                        Some(id) => resolve_string(strings, id).to_string(),
+                        None => excel_diff::index_to_address(0, field.col)
+                            .trim_end_matches(|c: char| c.is_ascii_digit())
+                            .to_string(),
+                    };
+                    format!(
+                        "{name} {} -> {}",
+                        render_cell_value(strings, &field.from),
+                        render_cell_value(strings, &field.to)
+                    )
+                })
+                .collect();
+            let detail = format!(
+                "Record [{}] (row {}): {}",
+                format_key_values(strings, key),
+                new_row + 1,
+                changes.join(", ")
+            );
+            write_structure(
+                structure_sheet,
+                rows,
+                "RecordChanged",
+                strings,
+                *sheet,
+                detail,
+            );
+        }
         DiffOp::ColumnAdded { sheet, col_idx, .. } => {
             let structure_sheet = sheet_mut(workbook, "Structure")?;
             write_structure(
@@ -787,6 +828,89 @@ fn write_op(
                 &detail,
             );
         }
+        #[cfg(feature = "model-diff")]
+        DiffOp::MeasurePropertyChanged {
+            name,
+            field,
+            old,
+            new,
+        } => {
+            let old_str = old
+                .map(|id| resolve_string(strings, id))
+                .unwrap_or("<none>");
+            let new_str = new
+                .map(|id| resolve_string(strings, id))
+                .unwrap_or("<none>");
+            let detail = format!("{}: {} -> {}", measure_field_name(*field), old_str, new_str);
+            let model_sheet = sheet_mut(workbook, "Model")?;
+            write_model(
+                model_sheet,
+                rows,
+                "MeasurePropertyChanged",
+                resolve_string(strings, *name),
+                &detail,
+            );
+        }
+        #[cfg(feature = "model-diff")]
+        DiffOp::ModelObjectAdded {
+            object,
+            table,
+            name,
+        } => {
+            let model_sheet = sheet_mut(workbook, "Model")?;
+            write_model(
+                model_sheet,
+                rows,
+                "ModelObjectAdded",
+                &format_model_object(strings, *object, *table, *name),
+                "",
+            );
+        }
+        #[cfg(feature = "model-diff")]
+        DiffOp::ModelObjectRemoved {
+            object,
+            table,
+            name,
+        } => {
+            let model_sheet = sheet_mut(workbook, "Model")?;
+            write_model(
+                model_sheet,
+                rows,
+                "ModelObjectRemoved",
+                &format_model_object(strings, *object, *table, *name),
+                "",
+            );
+        }
+        #[cfg(feature = "model-diff")]
+        DiffOp::ModelObjectChanged {
+            object,
+            table,
+            name,
+            changes,
+        } => {
+            let value = |id: Option<excel_diff::StringId>| {
+                id.map(|id| resolve_string(strings, id)).unwrap_or("<none>")
+            };
+            let detail: Vec<String> = changes
+                .iter()
+                .map(|change| {
+                    let field = model_object_field_name(change.field);
+                    let label = match change.item {
+                        Some(item) => format!("{} ({})", field, resolve_string(strings, item)),
+                        None => field.to_string(),
+                    };
+                    format!("{}: {} -> {}", label, value(change.from), value(change.to))
+                })
+                .collect();
+            let model_sheet = sheet_mut(workbook, "Model")?;
+            write_model(
+                model_sheet,
+                rows,
+                "ModelObjectChanged",
+                &format_model_object(strings, *object, *table, *name),
+                &detail.join("; "),
+            );
+        }
         _ => {
             let row = rows.other + 1;
             rows.other += 1;
@@ -887,6 +1011,63 @@ fn format_relationship_ref(
     )
 }
 
+#[cfg(feature = "model-diff")]
+fn format_model_object(
+    strings: &[String],
+    object: ModelObjectKind,
+    table: Option<excel_diff::StringId>,
+    name: excel_diff::StringId,
+) -> String {
+    let kind = match object {
+        ModelObjectKind::Role => "role",
+        ModelObjectKind::Hierarchy => "hierarchy",
+        ModelObjectKind::Partition => "partition",
+        ModelObjectKind::CalculationGroup => "calculation group",
+        ModelObjectKind::CalculationItem => "calculation item",
+        ModelObjectKind::Perspective => "perspective",
+        ModelObjectKind::Culture => "culture",
+    };
+    match table {
+        Some(table) => format!("{} {}", kind, format_column_ref(strings, table, name)),
+        None => format!("{} {}", kind, resolve_string(strings, name)),
+    }
+}
+
+#[cfg(feature = "model-diff")]
+fn model_object_field_name(field: ModelObjectField) -> &'static str {
+    match field {
+        ModelObjectField::Description => "description",
+        ModelObjectField::ModelPermission => "model_permission",
+        ModelObjectField::RowFilter => "row_filter",
+        ModelObjectField::ObjectPermission => "object_permission",
+        ModelObjectField::Member => "member",
+        ModelObjectField::Hidden => "hidden",
+        ModelObjectField::DisplayFolder => "display_folder",
+        ModelObjectField::Level => "level",
+        ModelObjectField::LevelOrder => "level_order",
+        ModelObjectField::Mode => "mode",
+        ModelObjectField::SourceType => "source_type",
+        ModelObjectField::Source => "source",
+        ModelObjectField::Precedence => "precedence",
+        ModelObjectField::Expression => "expression",
+        ModelObjectField::FormatStringExpression => "format_string_expression",
+        ModelObjectField::Ordinal => "ordinal",
+        ModelObjectField::Translation => "translation",
+    }
+}
+
+#[cfg(feature = "model-diff")]
+fn measure_field_name(field: MeasureProperty) -> &'static str {
+    match field {
+        MeasureProperty::FormatString => "format_string",
+        MeasureProperty::DisplayFolder => "display_folder",
+        MeasureProperty::Description => "description",
+        MeasureProperty::KpiTarget => "kpi_target",
+        MeasureProperty::KpiStatus => "kpi_status",
+        MeasureProperty::KpiTrend => "kpi_trend",
+    }
+}
+
 #[cfg(feature = "model-diff")]
 fn column_field_name(field: ModelColumnProperty) -> &'static str {
     match field {
@@ -978,13 +1159,66 @@ fn op_kind_label(op: &DiffOp) -> &str {
         DiffOp::VbaModuleAdded { .. } => "VbaModuleAdded",
         DiffOp::VbaModuleRemoved { .. } => "VbaModuleRemoved",
         DiffOp::VbaModuleChanged { .. } => "VbaModuleChanged",
+        DiffOp::VbaReferenceAdded { .. } => "VbaReferenceAdded",
+        DiffOp::VbaReferenceRemoved { .. } => "VbaReferenceRemoved",
+        DiffOp::VbaReferenceChanged { .. } => "VbaReferenceChanged",
+        DiffOp::VbaProjectChanged { .. } => "VbaProjectChanged",
         DiffOp::NamedRangeAdded { .. } => "NamedRangeAdded",
         DiffOp::NamedRangeRemoved { .. } => "NamedRangeRemoved",
         DiffOp::NamedRangeChanged { .. } => "NamedRangeChanged",
         DiffOp::ChartAdded { .. } => "ChartAdded",
         DiffOp::ChartRemoved { .. } => "ChartRemoved",
         DiffOp::ChartChanged { .. } => "ChartChanged",
+        DiffOp::PivotTableAdded { .. } => "PivotTableAdded",
+        DiffOp::PivotTableRemoved { .. } => "PivotTableRemoved",
+        DiffOp::PivotTableSourceChanged { .. } => "PivotTableSourceChanged",
+        DiffOp::PivotTableLayoutChanged { .. } => "PivotTableLayoutChanged",
+        DiffOp::ExternalLinkAdded { .. } => "ExternalLinkAdded",
+        DiffOp::ExternalLinkRemoved { .. } => "ExternalLinkRemoved",
+        DiffOp::ExternalLinkChanged { .. } => "ExternalLinkChanged",
+        DiffOp::ConnectionAdded { .. } => "ConnectionAdded",
+        DiffOp::ConnectionRemoved { .. } => "ConnectionRemoved",
+        DiffOp::ConnectionChanged { .. } => "ConnectionChanged",
+        DiffOp::ReportObjectAdded { .. } => "ReportObjectAdded",
+        DiffOp::ReportObjectRemoved { .. } => "ReportObjectRemoved",
+        DiffOp::ReportObjectChanged { .. } => "ReportObjectChanged",
+        DiffOp::WorkbookSettingsChanged { .. } => "WorkbookSettingsChanged",
+        DiffOp::WorkbookProtectionChanged { .. } => "WorkbookProtectionChanged",
+        DiffOp::DocumentPropertyChanged { .. } => "DocumentPropertyChanged",
+        DiffOp::CellStyleChanged { .. } => "CellStyleChanged",
+        DiffOp::CommentAdded { .. } => "CommentAdded",
+        DiffOp::CommentRemoved { .. } => "CommentRemoved",
+        DiffOp::CommentChanged { .. } => "CommentChanged",
+        DiffOp::DataValidationAdded { .. } => "DataValidationAdded",
+        DiffOp::DataValidationRemoved { .. } => "DataValidationRemoved",
+        DiffOp::DataValidationChanged { .. } => "DataValidationChanged",
+        DiffOp::ConditionalFormatAdded { .. } => "ConditionalFormatAdded",
+        DiffOp::ConditionalFormatRemoved { .. } => "ConditionalFormatRemoved",
+        DiffOp::ConditionalFormatChanged { .. } => "ConditionalFormatChanged",
+        DiffOp::MergedRangeAdded { .. } => "MergedRangeAdded",
+        DiffOp::MergedRangeRemoved { .. } => "MergedRangeRemoved",
+        DiffOp::RowVisibilityChanged { .. } => "RowVisibilityChanged",
+        DiffOp::ColumnVisibilityChanged { .. } => "ColumnVisibilityChanged",
+        DiffOp::RowOutlineChanged { .. } => "RowOutlineChanged",
+        DiffOp::ColumnOutlineChanged { .. } => "ColumnOutlineChanged",
+        DiffOp::RowHeightChanged { .. } => "RowHeightChanged",
+        DiffOp::ColumnWidthChanged { .. } => "ColumnWidthChanged",
+        DiffOp::FreezePaneChanged { .. } => "FreezePaneChanged",
+        DiffOp::SpillRangeChanged { .. } => "SpillRangeChanged",
+        DiffOp::SheetVisibilityChanged { .. } => "SheetVisibilityChanged",
+        DiffOp::SheetTabColorChanged { .. } => "SheetTabColorChanged",
+        DiffOp::SheetProtectionChanged { .. } => "SheetProtectionChanged",
+        DiffOp::ExcelTableAdded { .. } => "ExcelTableAdded",
+        DiffOp::ExcelTableRemoved { .. } => "ExcelTableRemoved",
+        DiffOp::ExcelTableRenamed { .. } => "ExcelTableRenamed",
+        DiffOp::ExcelTableResized { .. } => "ExcelTableResized",
+        DiffOp::ExcelTableColumnRenamed { .. } => "ExcelTableColumnRenamed",
+        DiffOp::DrawingObjectAdded { .. } => "DrawingObjectAdded",
+        DiffOp::DrawingObjectRemoved { .. } => "DrawingObjectRemoved",
+        DiffOp::DrawingObjectMoved { .. } => "DrawingObjectMoved",
+        DiffOp::DrawingObjectChanged { .. } => "DrawingObjectChanged",
         DiffOp::DuplicateKeyCluster { .. } => "DuplicateKeyCluster",
+        DiffOp::RecordChanged { .. } => "RecordChanged",
         _ => "Other",
     }
 }
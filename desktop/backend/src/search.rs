@@ -223,6 +223,35 @@ fn match_op(op: &excel_diff::DiffOp, strings: &[String], query: &str) -> Option<
                 });
             }
         }
+        excel_diff::DiffOp::RecordChanged {
+            sheet,
+            key,
+            new_row,
+            fields,
+            ..
+        } => {
+            let sheet_name = resolve_string(strings, *sheet).to_string();
+            let key_text = key
+                .iter()
+                .map(|value| render_cell_value(strings, value))
+                .collect::<Vec<_>>()
+                .join(" ");
+            let field_text = fields
+                .iter()
+                .filter_map(|field| field.field.map(|id| resolve_string(strings, id)))
+                .collect::<Vec<_>>()
+                .join(" ");
+            let text = format!("{key_text} {field_text}").to_lowercase();
+            if text.contains(&query_lower) {
+                return Some(SearchResult {
+                    kind: "record_changed".to_string(),
+                    sheet: Some(sheet_name),
+                    address: None,
+                    label: format!("Record {key_text}"),
+                    detail: Some(format!("row {}: {field_text}", new_row + 1)),
+                });
+            }
+        }
         _ => {}
     }
 
@@ -238,6 +267,7 @@ fn op_kind(op: &excel_diff::DiffOp) -> &'static str {
         excel_diff::DiffOp::QueryDefinitionChanged { .. } => "QueryDefinitionChanged",
         excel_diff::DiffOp::QueryMetadataChanged { .. } => "QueryMetadataChanged",
         excel_diff::DiffOp::DuplicateKeyCluster { .. } => "DuplicateKeyCluster",
+        excel_diff::DiffOp::RecordChanged { .. } => "RecordChanged",
         _ => "Other",
     }
 }
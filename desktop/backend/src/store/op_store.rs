@@ -620,20 +620,58 @@ impl OpStore {
         fn classify_change_kind(kind: &str, meta_field: Option<&str>) -> Option<ChangeKind> {
             match kind {
                 "SheetAdded" | "RowAdded" | "ColumnAdded" | "NamedRangeAdded" | "ChartAdded"
-                | "VbaModuleAdded" | "QueryAdded" => Some(ChangeKind::Added),
-                "SheetRemoved" | "RowRemoved" | "ColumnRemoved" | "NamedRangeRemoved"
-                | "ChartRemoved" | "VbaModuleRemoved" | "QueryRemoved" => Some(ChangeKind::Removed),
-                "BlockMovedRows" | "BlockMovedColumns" | "BlockMovedRect" => {
-                    Some(ChangeKind::Moved)
+                | "VbaModuleAdded" | "VbaReferenceAdded" | "QueryAdded" | "CommentAdded"
+                | "DataValidationAdded" | "ConditionalFormatAdded" | "MergedRangeAdded"
+                | "ExcelTableAdded" | "DrawingObjectAdded" | "PivotTableAdded"
+                | "ExternalLinkAdded" | "ConnectionAdded" | "ReportObjectAdded" => {
+                    Some(ChangeKind::Added)
                 }
+                "SheetRemoved" | "RowRemoved" | "ColumnRemoved" | "NamedRangeRemoved"
+                | "ChartRemoved" | "VbaModuleRemoved" | "VbaReferenceRemoved" | "QueryRemoved"
+                | "CommentRemoved" | "DataValidationRemoved" | "ConditionalFormatRemoved"
+                | "MergedRangeRemoved" | "ExcelTableRemoved" | "DrawingObjectRemoved"
+                | "PivotTableRemoved" | "ExternalLinkRemoved" | "ConnectionRemoved"
+                | "ReportObjectRemoved" => Some(ChangeKind::Removed),
+                "BlockMovedRows" | "BlockMovedColumns" | "BlockMovedRect"
+                | "DrawingObjectMoved" => Some(ChangeKind::Moved),
                 "RowReplaced"
                 | "DuplicateKeyCluster"
+                | "RecordChanged"
                 | "RectReplaced"
                 | "CellEdited"
+                | "CellStyleChanged"
+                | "CommentChanged"
+                | "DataValidationChanged"
+                | "ConditionalFormatChanged"
+                | "RowVisibilityChanged"
+                | "ColumnVisibilityChanged"
+                | "RowOutlineChanged"
+                | "ColumnOutlineChanged"
+                | "RowHeightChanged"
+                | "ColumnWidthChanged"
+                | "FreezePaneChanged"
+                | "SpillRangeChanged"
+                | "SheetVisibilityChanged"
+                | "SheetTabColorChanged"
+                | "SheetProtectionChanged"
+                | "ExcelTableRenamed"
+                | "ExcelTableResized"
+                | "ExcelTableColumnRenamed"
+                | "DrawingObjectChanged"
                 | "SheetRenamed"
                 | "NamedRangeChanged"
                 | "ChartChanged"
+                | "PivotTableSourceChanged"
+                | "PivotTableLayoutChanged"
+                | "ExternalLinkChanged"
+                | "ConnectionChanged"
+                | "ReportObjectChanged"
+                | "WorkbookSettingsChanged"
+                | "WorkbookProtectionChanged"
+                | "DocumentPropertyChanged"
                 | "VbaModuleChanged"
+                | "VbaReferenceChanged"
+                | "VbaProjectChanged"
                 | "QueryRenamed"
                 | "QueryDefinitionChanged"
                 | "QueryMetadataChanged" => {
@@ -646,20 +684,25 @@ impl OpStore {
                     }
                 }
                 #[cfg(feature = "model-diff")]
-                "TableAdded" | "ModelColumnAdded" | "RelationshipAdded" | "MeasureAdded" => {
-                    Some(ChangeKind::Added)
-                }
+                "TableAdded"
+                | "ModelColumnAdded"
+                | "RelationshipAdded"
+                | "MeasureAdded"
+                | "ModelObjectAdded" => Some(ChangeKind::Added),
                 #[cfg(feature = "model-diff")]
                 "TableRemoved"
                 | "ModelColumnRemoved"
                 | "RelationshipRemoved"
-                | "MeasureRemoved" => Some(ChangeKind::Removed),
+                | "MeasureRemoved"
+                | "ModelObjectRemoved" => Some(ChangeKind::Removed),
                 #[cfg(feature = "model-diff")]
                 "ModelColumnTypeChanged"
                 | "ModelColumnPropertyChanged"
                 | "CalculatedColumnDefinitionChanged"
                 | "RelationshipPropertyChanged"
-                | "MeasureDefinitionChanged" => Some(ChangeKind::Modified),
+                | "MeasureDefinitionChanged"
+                | "MeasurePropertyChanged"
+                | "ModelObjectChanged" => Some(ChangeKind::Modified),
                 _ => None,
             }
         }
@@ -671,6 +714,7 @@ impl OpStore {
             if kind == "CalculatedColumnDefinitionChanged"
                 || kind.starts_with("Table")
                 || kind.starts_with("ModelColumn")
+                || kind.starts_with("ModelObject")
                 || kind.starts_with("Relationship")
                 || kind.starts_with("Measure")
             {
@@ -684,9 +728,33 @@ impl OpStore {
                     | "ChartAdded"
                     | "ChartRemoved"
                     | "ChartChanged"
+                    | "PivotTableAdded"
+                    | "PivotTableRemoved"
+                    | "PivotTableSourceChanged"
+                    | "PivotTableLayoutChanged"
+                    | "ExternalLinkAdded"
+                    | "ExternalLinkRemoved"
+                    | "ExternalLinkChanged"
+                    | "ConnectionAdded"
+                    | "ConnectionRemoved"
+                    | "ConnectionChanged"
+                    | "ReportObjectAdded"
+                    | "ReportObjectRemoved"
+                    | "ReportObjectChanged"
+                    | "WorkbookSettingsChanged"
+                    | "WorkbookProtectionChanged"
+                    | "DocumentPropertyChanged"
+                    | "DrawingObjectAdded"
+                    | "DrawingObjectRemoved"
+                    | "DrawingObjectMoved"
+                    | "DrawingObjectChanged"
                     | "VbaModuleAdded"
                     | "VbaModuleRemoved"
                     | "VbaModuleChanged"
+                    | "VbaReferenceAdded"
+                    | "VbaReferenceRemoved"
+                    | "VbaReferenceChanged"
+                    | "VbaProjectChanged"
             ) {
                 return OpCategory::Objects;
             }
@@ -699,6 +767,7 @@ impl OpStore {
                     | "RowRemoved"
                     | "RowReplaced"
                     | "DuplicateKeyCluster"
+                    | "RecordChanged"
                     | "ColumnAdded"
                     | "ColumnRemoved"
                     | "BlockMovedRows"
@@ -706,6 +775,34 @@ impl OpStore {
                     | "BlockMovedRect"
                     | "RectReplaced"
                     | "CellEdited"
+                    | "CellStyleChanged"
+                    | "CommentAdded"
+                    | "CommentRemoved"
+                    | "CommentChanged"
+                    | "DataValidationAdded"
+                    | "DataValidationRemoved"
+                    | "DataValidationChanged"
+                    | "ConditionalFormatAdded"
+                    | "ConditionalFormatRemoved"
+                    | "ConditionalFormatChanged"
+                    | "MergedRangeAdded"
+                    | "MergedRangeRemoved"
+                    | "RowVisibilityChanged"
+                    | "ColumnVisibilityChanged"
+                    | "RowOutlineChanged"
+                    | "ColumnOutlineChanged"
+                    | "RowHeightChanged"
+                    | "ColumnWidthChanged"
+                    | "FreezePaneChanged"
+                    | "SpillRangeChanged"
+                    | "SheetVisibilityChanged"
+                    | "SheetTabColorChanged"
+                    | "SheetProtectionChanged"
+                    | "ExcelTableAdded"
+                    | "ExcelTableRemoved"
+                    | "ExcelTableRenamed"
+                    | "ExcelTableResized"
+                    | "ExcelTableColumnRenamed"
             ) {
                 return OpCategory::Grid;
             }
@@ -745,14 +842,26 @@ impl OpStore {
                     "filled" => OpSeverity::Medium,
                     _ => OpSeverity::Medium,
                 },
-                "SheetRenamed" | "QueryRenamed" => OpSeverity::Low,
+                "SheetRenamed" | "QueryRenamed" | "CellStyleChanged" => OpSeverity::Low,
+                "CommentAdded" | "CommentRemoved" | "CommentChanged" => OpSeverity::Low,
+                "DrawingObjectMoved" | "DocumentPropertyChanged" => OpSeverity::Low,
                 "BlockMovedRows" | "BlockMovedColumns" | "BlockMovedRect" => OpSeverity::Medium,
                 "SheetAdded" | "SheetRemoved" => OpSeverity::High,
+                "VbaReferenceAdded" | "VbaReferenceChanged" => OpSeverity::High,
+                "ExternalLinkAdded" | "ExternalLinkChanged" | "ConnectionAdded"
+                | "ConnectionChanged" => OpSeverity::High,
+                "WorkbookSettingsChanged" => OpSeverity::High,
                 "RowAdded" | "RowRemoved" | "RowReplaced" | "ColumnAdded" | "ColumnRemoved"
                 | "RectReplaced" => OpSeverity::Medium,
                 "NamedRangeAdded" | "NamedRangeRemoved" | "NamedRangeChanged" | "ChartAdded"
-                | "ChartRemoved" | "ChartChanged" | "VbaModuleAdded" | "VbaModuleRemoved"
-                | "VbaModuleChanged" => OpSeverity::Medium,
+                | "ChartRemoved" | "ChartChanged" | "PivotTableAdded" | "PivotTableRemoved"
+                | "PivotTableSourceChanged" | "PivotTableLayoutChanged" | "ExternalLinkRemoved"
+                | "ConnectionRemoved" | "ReportObjectAdded" | "ReportObjectRemoved"
+                | "ReportObjectChanged" | "WorkbookProtectionChanged" | "SheetProtectionChanged"
+                | "VbaModuleAdded" | "VbaModuleRemoved" | "VbaModuleChanged"
+                | "VbaReferenceRemoved" | "VbaProjectChanged" => {
+                    OpSeverity::Medium
+                }
                 _ => OpSeverity::Medium,
             }
         }
@@ -1436,6 +1545,8 @@ mod tests {
                 old_hash: 1,
                 new_hash: 2,
                 semantic_detail: None,
+                downstream_queries: Vec::new(),
+                loaded_tables: Vec::new(),
             },
             DiffOp::QueryDefinitionChanged {
                 name: StringId(2),
@@ -1443,6 +1554,8 @@ mod tests {
                 old_hash: 3,
                 new_hash: 4,
                 semantic_detail: None,
+                downstream_queries: Vec::new(),
+                loaded_tables: Vec::new(),
             },
         ];
         let analysis = run_analysis(
@@ -1466,12 +1579,14 @@ mod tests {
                 change_kind: ExpressionChangeKind::FormattingOnly,
                 old_hash: 1,
                 new_hash: 2,
+                edits: Vec::new(),
             },
             DiffOp::MeasureDefinitionChanged {
                 name: StringId(2),
                 change_kind: ExpressionChangeKind::Semantic,
                 old_hash: 3,
                 new_hash: 4,
+                edits: Vec::new(),
             },
         ];
         let analysis = run_analysis(
@@ -1500,6 +1615,7 @@ mod tests {
                 from: from.clone(),
                 to: to.clone(),
                 formula_diff: FormulaDiffResult::FormattingOnly,
+                formula_edits: Vec::new(),
             },
             DiffOp::CellEdited {
                 sheet,
@@ -1507,6 +1623,7 @@ mod tests {
                 from,
                 to,
                 formula_diff: FormulaDiffResult::SemanticChange,
+                formula_edits: Vec::new(),
             },
         ];
         let analysis = run_analysis(
@@ -79,13 +79,46 @@ pub fn op_sheet_id(op: &DiffOp) -> Option<StringId> {
         | DiffOp::RowRemoved { sheet, .. }
         | DiffOp::RowReplaced { sheet, .. }
         | DiffOp::DuplicateKeyCluster { sheet, .. }
+        | DiffOp::RecordChanged { sheet, .. }
         | DiffOp::ColumnAdded { sheet, .. }
         | DiffOp::ColumnRemoved { sheet, .. }
         | DiffOp::BlockMovedRows { sheet, .. }
         | DiffOp::BlockMovedColumns { sheet, .. }
         | DiffOp::BlockMovedRect { sheet, .. }
         | DiffOp::RectReplaced { sheet, .. }
-        | DiffOp::CellEdited { sheet, .. } => Some(*sheet),
+        | DiffOp::CellEdited { sheet, .. }
+        | DiffOp::CellStyleChanged { sheet, .. }
+        | DiffOp::CommentAdded { sheet, .. }
+        | DiffOp::CommentRemoved { sheet, .. }
+        | DiffOp::CommentChanged { sheet, .. }
+        | DiffOp::DataValidationAdded { sheet, .. }
+        | DiffOp::DataValidationRemoved { sheet, .. }
+        | DiffOp::DataValidationChanged { sheet, .. }
+        | DiffOp::ConditionalFormatAdded { sheet, .. }
+        | DiffOp::ConditionalFormatRemoved { sheet, .. }
+        | DiffOp::ConditionalFormatChanged { sheet, .. }
+        | DiffOp::MergedRangeAdded { sheet, .. }
+        | DiffOp::MergedRangeRemoved { sheet, .. }
+        | DiffOp::RowVisibilityChanged { sheet, .. }
+        | DiffOp::ColumnVisibilityChanged { sheet, .. }
+        | DiffOp::RowOutlineChanged { sheet, .. }
+        | DiffOp::ColumnOutlineChanged { sheet, .. }
+        | DiffOp::RowHeightChanged { sheet, .. }
+        | DiffOp::ColumnWidthChanged { sheet, .. }
+        | DiffOp::FreezePaneChanged { sheet, .. }
+        | DiffOp::SpillRangeChanged { sheet, .. }
+        | DiffOp::SheetVisibilityChanged { sheet, .. }
+        | DiffOp::SheetTabColorChanged { sheet, .. }
+        | DiffOp::SheetProtectionChanged { sheet, .. }
+        | DiffOp::ExcelTableAdded { sheet, .. }
+        | DiffOp::ExcelTableRemoved { sheet, .. }
+        | DiffOp::ExcelTableRenamed { sheet, .. }
+        | DiffOp::ExcelTableResized { sheet, .. }
+        | DiffOp::ExcelTableColumnRenamed { sheet, .. }
+        | DiffOp::DrawingObjectAdded { sheet, .. }
+        | DiffOp::DrawingObjectRemoved { sheet, .. }
+        | DiffOp::DrawingObjectMoved { sheet, .. }
+        | DiffOp::DrawingObjectChanged { sheet, .. } => Some(*sheet),
         _ => None,
     }
 }
@@ -99,6 +132,7 @@ pub fn diff_op_kind(op: &DiffOp) -> &'static str {
         DiffOp::RowRemoved { .. } => "RowRemoved",
         DiffOp::RowReplaced { .. } => "RowReplaced",
         DiffOp::DuplicateKeyCluster { .. } => "DuplicateKeyCluster",
+        DiffOp::RecordChanged { .. } => "RecordChanged",
         DiffOp::ColumnAdded { .. } => "ColumnAdded",
         DiffOp::ColumnRemoved { .. } => "ColumnRemoved",
         DiffOp::BlockMovedRows { .. } => "BlockMovedRows",
@@ -106,15 +140,67 @@ pub fn diff_op_kind(op: &DiffOp) -> &'static str {
         DiffOp::BlockMovedRect { .. } => "BlockMovedRect",
         DiffOp::RectReplaced { .. } => "RectReplaced",
         DiffOp::CellEdited { .. } => "CellEdited",
+        DiffOp::CellStyleChanged { .. } => "CellStyleChanged",
+        DiffOp::CommentAdded { .. } => "CommentAdded",
+        DiffOp::CommentRemoved { .. } => "CommentRemoved",
+        DiffOp::CommentChanged { .. } => "CommentChanged",
+        DiffOp::DataValidationAdded { .. } => "DataValidationAdded",
+        DiffOp::DataValidationRemoved { .. } => "DataValidationRemoved",
+        DiffOp::DataValidationChanged { .. } => "DataValidationChanged",
+        DiffOp::ConditionalFormatAdded { .. } => "ConditionalFormatAdded",
+        DiffOp::ConditionalFormatRemoved { .. } => "ConditionalFormatRemoved",
+        DiffOp::ConditionalFormatChanged { .. } => "ConditionalFormatChanged",
+        DiffOp::MergedRangeAdded { .. } => "MergedRangeAdded",
+        DiffOp::MergedRangeRemoved { .. } => "MergedRangeRemoved",
+        DiffOp::RowVisibilityChanged { .. } => "RowVisibilityChanged",
+        DiffOp::ColumnVisibilityChanged { .. } => "ColumnVisibilityChanged",
+        DiffOp::RowOutlineChanged { .. } => "RowOutlineChanged",
+        DiffOp::ColumnOutlineChanged { .. } => "ColumnOutlineChanged",
+        DiffOp::RowHeightChanged { .. } => "RowHeightChanged",
+        DiffOp::ColumnWidthChanged { .. } => "ColumnWidthChanged",
+        DiffOp::FreezePaneChanged { .. } => "FreezePaneChanged",
+        DiffOp::SpillRangeChanged { .. } => "SpillRangeChanged",
+        DiffOp::SheetVisibilityChanged { .. } => "SheetVisibilityChanged",
+        DiffOp::SheetTabColorChanged { .. } => "SheetTabColorChanged",
+        DiffOp::SheetProtectionChanged { .. } => "SheetProtectionChanged",
+        DiffOp::ExcelTableAdded { .. } => "ExcelTableAdded",
+        DiffOp::ExcelTableRemoved { .. } => "ExcelTableRemoved",
+        DiffOp::ExcelTableRenamed { .. } => "ExcelTableRenamed",
+        DiffOp::ExcelTableResized { .. } => "ExcelTableResized",
+        DiffOp::ExcelTableColumnRenamed { .. } => "ExcelTableColumnRenamed",
+        DiffOp::DrawingObjectAdded { .. } => "DrawingObjectAdded",
+        DiffOp::DrawingObjectRemoved { .. } => "DrawingObjectRemoved",
+        DiffOp::DrawingObjectMoved { .. } => "DrawingObjectMoved",
+        DiffOp::DrawingObjectChanged { .. } => "DrawingObjectChanged",
         DiffOp::VbaModuleAdded { .. } => "VbaModuleAdded",
         DiffOp::VbaModuleRemoved { .. } => "VbaModuleRemoved",
         DiffOp::VbaModuleChanged { .. } => "VbaModuleChanged",
+        DiffOp::VbaReferenceAdded { .. } => "VbaReferenceAdded",
+        DiffOp::VbaReferenceRemoved { .. } => "VbaReferenceRemoved",
+        DiffOp::VbaReferenceChanged { .. } => "VbaReferenceChanged",
+        DiffOp::VbaProjectChanged { .. } => "VbaProjectChanged",
         DiffOp::NamedRangeAdded { .. } => "NamedRangeAdded",
         DiffOp::NamedRangeRemoved { .. } => "NamedRangeRemoved",
         DiffOp::NamedRangeChanged { .. } => "NamedRangeChanged",
         DiffOp::ChartAdded { .. } => "ChartAdded",
         DiffOp::ChartRemoved { .. } => "ChartRemoved",
         DiffOp::ChartChanged { .. } => "ChartChanged",
+        DiffOp::PivotTableAdded { .. } => "PivotTableAdded",
+        DiffOp::PivotTableRemoved { .. } => "PivotTableRemoved",
+        DiffOp::PivotTableSourceChanged { .. } => "PivotTableSourceChanged",
+        DiffOp::PivotTableLayoutChanged { .. } => "PivotTableLayoutChanged",
+        DiffOp::ExternalLinkAdded { .. } => "ExternalLinkAdded",
+        DiffOp::ExternalLinkRemoved { .. } => "ExternalLinkRemoved",
+        DiffOp::ExternalLinkChanged { .. } => "ExternalLinkChanged",
+        DiffOp::ConnectionAdded { .. } => "ConnectionAdded",
+        DiffOp::ConnectionRemoved { .. } => "ConnectionRemoved",
+        DiffOp::ConnectionChanged { .. } => "ConnectionChanged",
+        DiffOp::ReportObjectAdded { .. } => "ReportObjectAdded",
+        DiffOp::ReportObjectRemoved { .. } => "ReportObjectRemoved",
+        DiffOp::ReportObjectChanged { .. } => "ReportObjectChanged",
+        DiffOp::WorkbookSettingsChanged { .. } => "WorkbookSettingsChanged",
+        DiffOp::WorkbookProtectionChanged { .. } => "WorkbookProtectionChanged",
+        DiffOp::DocumentPropertyChanged { .. } => "DocumentPropertyChanged",
         DiffOp::QueryAdded { .. } => "QueryAdded",
         DiffOp::QueryRemoved { .. } => "QueryRemoved",
         DiffOp::QueryRenamed { .. } => "QueryRenamed",
@@ -146,6 +232,14 @@ pub fn diff_op_kind(op: &DiffOp) -> &'static str {
         DiffOp::MeasureRemoved { .. } => "MeasureRemoved",
         #[cfg(feature = "model-diff")]
         DiffOp::MeasureDefinitionChanged { .. } => "MeasureDefinitionChanged",
+        #[cfg(feature = "model-diff")]
+        DiffOp::MeasurePropertyChanged { .. } => "MeasurePropertyChanged",
+        #[cfg(feature = "model-diff")]
+        DiffOp::ModelObjectAdded { .. } => "ModelObjectAdded",
+        #[cfg(feature = "model-diff")]
+        DiffOp::ModelObjectRemoved { .. } => "ModelObjectRemoved",
+        #[cfg(feature = "model-diff")]
+        DiffOp::ModelObjectChanged { .. } => "ModelObjectChanged",
         _ => "Unknown",
     }
 }
@@ -157,7 +251,18 @@ pub fn classify_op(op: &DiffOp) -> Option<ChangeKind> {
         | DiffOp::ColumnAdded { .. }
         | DiffOp::NamedRangeAdded { .. }
         | DiffOp::ChartAdded { .. }
+        | DiffOp::PivotTableAdded { .. }
+        | DiffOp::ExternalLinkAdded { .. }
+        | DiffOp::ConnectionAdded { .. }
+        | DiffOp::ReportObjectAdded { .. }
         | DiffOp::VbaModuleAdded { .. }
+        | DiffOp::VbaReferenceAdded { .. }
+        | DiffOp::CommentAdded { .. }
+        | DiffOp::DataValidationAdded { .. }
+        | DiffOp::ConditionalFormatAdded { .. }
+        | DiffOp::MergedRangeAdded { .. }
+        | DiffOp::ExcelTableAdded { .. }
+        | DiffOp::DrawingObjectAdded { .. }
         | DiffOp::QueryAdded { .. }
         | DiffOp::QueryMetadataChanged {
             field: excel_diff::QueryMetadataField::LoadToSheet,
@@ -168,19 +273,61 @@ pub fn classify_op(op: &DiffOp) -> Option<ChangeKind> {
         | DiffOp::ColumnRemoved { .. }
         | DiffOp::NamedRangeRemoved { .. }
         | DiffOp::ChartRemoved { .. }
+        | DiffOp::PivotTableRemoved { .. }
+        | DiffOp::ExternalLinkRemoved { .. }
+        | DiffOp::ConnectionRemoved { .. }
+        | DiffOp::ReportObjectRemoved { .. }
         | DiffOp::VbaModuleRemoved { .. }
+        | DiffOp::VbaReferenceRemoved { .. }
+        | DiffOp::CommentRemoved { .. }
+        | DiffOp::DataValidationRemoved { .. }
+        | DiffOp::ConditionalFormatRemoved { .. }
+        | DiffOp::MergedRangeRemoved { .. }
+        | DiffOp::ExcelTableRemoved { .. }
+        | DiffOp::DrawingObjectRemoved { .. }
         | DiffOp::QueryRemoved { .. } => Some(ChangeKind::Removed),
         DiffOp::BlockMovedRows { .. }
         | DiffOp::BlockMovedColumns { .. }
-        | DiffOp::BlockMovedRect { .. } => Some(ChangeKind::Moved),
+        | DiffOp::BlockMovedRect { .. }
+        | DiffOp::DrawingObjectMoved { .. } => Some(ChangeKind::Moved),
         DiffOp::RowReplaced { .. }
         | DiffOp::DuplicateKeyCluster { .. }
+        | DiffOp::RecordChanged { .. }
         | DiffOp::RectReplaced { .. }
         | DiffOp::CellEdited { .. }
+        | DiffOp::CellStyleChanged { .. }
+        | DiffOp::CommentChanged { .. }
+        | DiffOp::DataValidationChanged { .. }
+        | DiffOp::ConditionalFormatChanged { .. }
+        | DiffOp::RowVisibilityChanged { .. }
+        | DiffOp::ColumnVisibilityChanged { .. }
+        | DiffOp::RowOutlineChanged { .. }
+        | DiffOp::ColumnOutlineChanged { .. }
+        | DiffOp::RowHeightChanged { .. }
+        | DiffOp::ColumnWidthChanged { .. }
+        | DiffOp::FreezePaneChanged { .. }
+        | DiffOp::SpillRangeChanged { .. }
+        | DiffOp::SheetVisibilityChanged { .. }
+        | DiffOp::SheetTabColorChanged { .. }
+        | DiffOp::SheetProtectionChanged { .. }
+        | DiffOp::ExcelTableRenamed { .. }
+        | DiffOp::ExcelTableResized { .. }
+        | DiffOp::ExcelTableColumnRenamed { .. }
+        | DiffOp::DrawingObjectChanged { .. }
         | DiffOp::SheetRenamed { .. }
         | DiffOp::NamedRangeChanged { .. }
         | DiffOp::ChartChanged { .. }
+        | DiffOp::PivotTableSourceChanged { .. }
+        | DiffOp::PivotTableLayoutChanged { .. }
+        | DiffOp::ExternalLinkChanged { .. }
+        | DiffOp::ConnectionChanged { .. }
+        | DiffOp::ReportObjectChanged { .. }
+        | DiffOp::WorkbookSettingsChanged { .. }
+        | DiffOp::WorkbookProtectionChanged { .. }
+        | DiffOp::DocumentPropertyChanged { .. }
         | DiffOp::VbaModuleChanged { .. }
+        | DiffOp::VbaReferenceChanged { .. }
+        | DiffOp::VbaProjectChanged { .. }
         | DiffOp::QueryRenamed { .. }
         | DiffOp::QueryDefinitionChanged { .. }
         | DiffOp::QueryMetadataChanged { .. } => Some(ChangeKind::Modified),
@@ -188,18 +335,22 @@ pub fn classify_op(op: &DiffOp) -> Option<ChangeKind> {
         DiffOp::TableAdded { .. }
         | DiffOp::ModelColumnAdded { .. }
         | DiffOp::RelationshipAdded { .. }
-        | DiffOp::MeasureAdded { .. } => Some(ChangeKind::Added),
+        | DiffOp::MeasureAdded { .. }
+        | DiffOp::ModelObjectAdded { .. } => Some(ChangeKind::Added),
         #[cfg(feature = "model-diff")]
         DiffOp::TableRemoved { .. }
         | DiffOp::ModelColumnRemoved { .. }
         | DiffOp::RelationshipRemoved { .. }
-        | DiffOp::MeasureRemoved { .. } => Some(ChangeKind::Removed),
+        | DiffOp::MeasureRemoved { .. }
+        | DiffOp::ModelObjectRemoved { .. } => Some(ChangeKind::Removed),
         #[cfg(feature = "model-diff")]
         DiffOp::ModelColumnTypeChanged { .. }
         | DiffOp::ModelColumnPropertyChanged { .. }
         | DiffOp::CalculatedColumnDefinitionChanged { .. }
         | DiffOp::RelationshipPropertyChanged { .. }
-        | DiffOp::MeasureDefinitionChanged { .. } => Some(ChangeKind::Modified),
+        | DiffOp::MeasureDefinitionChanged { .. }
+        | DiffOp::MeasurePropertyChanged { .. }
+        | DiffOp::ModelObjectChanged { .. } => Some(ChangeKind::Modified),
         _ => None,
     }
 }
@@ -233,6 +384,10 @@ pub fn op_index_fields(op: &DiffOp) -> OpIndexFields {
                 fields.row_end = Some(*rows.last().unwrap_or(first));
             }
         }
+        DiffOp::RecordChanged { new_row, .. } => {
+            fields.row = Some(*new_row);
+            fields.row_end = Some(*new_row);
+        }
         DiffOp::ColumnAdded { col_idx, .. } | DiffOp::ColumnRemoved { col_idx, .. } => {
             fields.col = Some(*col_idx);
             fields.col_end = Some(*col_idx);
@@ -306,7 +461,11 @@ pub fn op_index_fields(op: &DiffOp) -> OpIndexFields {
             fields.row_end = Some(start_row.saturating_add(*row_count).saturating_sub(1));
             fields.col_end = Some(start_col.saturating_add(*col_count).saturating_sub(1));
         }
-        DiffOp::CellEdited { addr, .. } => {
+        DiffOp::CellEdited { addr, .. }
+        | DiffOp::CellStyleChanged { addr, .. }
+        | DiffOp::CommentAdded { addr, .. }
+        | DiffOp::CommentRemoved { addr, .. }
+        | DiffOp::CommentChanged { addr, .. } => {
             fields.row = Some(addr.row);
             fields.col = Some(addr.col);
             fields.row_end = Some(addr.row);
@@ -61,6 +61,7 @@ pub(crate) fn build_cell_explain_text(
             from,
             to,
             formula_diff,
+            ..
         } = op
         {
             let sheet_resolved = report.resolve(*sheet).unwrap_or("<unknown>");
@@ -7,7 +7,8 @@ from .base import BaseGenerator
 
 class XlsbStubGenerator(BaseGenerator):
     """
-    Create a minimal OPC container with xl/workbook.bin to exercise XLSB detection.
+    Create a minimal OPC container with a truncated xl/workbook.bin record stream to exercise
+    XLSB binary-record error reporting.
     """
 
     def generate(self, output_dir: Path, output_names: Union[str, List[str]]):
@@ -111,13 +111,46 @@ fn group_ops_by_sheet<'a>(
             | excel_diff::DiffOp::RowRemoved { sheet, .. }
             | excel_diff::DiffOp::RowReplaced { sheet, .. }
             | excel_diff::DiffOp::DuplicateKeyCluster { sheet, .. }
+            | excel_diff::DiffOp::RecordChanged { sheet, .. }
             | excel_diff::DiffOp::ColumnAdded { sheet, .. }
             | excel_diff::DiffOp::ColumnRemoved { sheet, .. }
             | excel_diff::DiffOp::BlockMovedRows { sheet, .. }
             | excel_diff::DiffOp::BlockMovedColumns { sheet, .. }
             | excel_diff::DiffOp::BlockMovedRect { sheet, .. }
             | excel_diff::DiffOp::RectReplaced { sheet, .. }
-            | excel_diff::DiffOp::CellEdited { sheet, .. } => Some(*sheet),
+            | excel_diff::DiffOp::CellEdited { sheet, .. }
+            | excel_diff::DiffOp::CellStyleChanged { sheet, .. }
+            | excel_diff::DiffOp::CommentAdded { sheet, .. }
+            | excel_diff::DiffOp::CommentRemoved { sheet, .. }
+            | excel_diff::DiffOp::CommentChanged { sheet, .. }
+            | excel_diff::DiffOp::DataValidationAdded { sheet, .. }
+            | excel_diff::DiffOp::DataValidationRemoved { sheet, .. }
+            | excel_diff::DiffOp::DataValidationChanged { sheet, .. }
+            | excel_diff::DiffOp::ConditionalFormatAdded { sheet, .. }
+            | excel_diff::DiffOp::ConditionalFormatRemoved { sheet, .. }
+            | excel_diff::DiffOp::ConditionalFormatChanged { sheet, .. }
+            | excel_diff::DiffOp::MergedRangeAdded { sheet, .. }
+            | excel_diff::DiffOp::MergedRangeRemoved { sheet, .. }
+            | excel_diff::DiffOp::RowVisibilityChanged { sheet, .. }
+            | excel_diff::DiffOp::ColumnVisibilityChanged { sheet, .. }
+            | excel_diff::DiffOp::RowOutlineChanged { sheet, .. }
+            | excel_diff::DiffOp::ColumnOutlineChanged { sheet, .. }
+            | excel_diff::DiffOp::RowHeightChanged { sheet, .. }
+            | excel_diff::DiffOp::ColumnWidthChanged { sheet, .. }
+            | excel_diff::DiffOp::FreezePaneChanged { sheet, .. }
+            | excel_diff::DiffOp::SpillRangeChanged { sheet, .. }
+            | excel_diff::DiffOp::SheetVisibilityChanged { sheet, .. }
+            | excel_diff::DiffOp::SheetTabColorChanged { sheet, .. }
+            | excel_diff::DiffOp::SheetProtectionChanged { sheet, .. }
+            | excel_diff::DiffOp::ExcelTableAdded { sheet, .. }
+            | excel_diff::DiffOp::ExcelTableRemoved { sheet, .. }
+            | excel_diff::DiffOp::ExcelTableRenamed { sheet, .. }
+            | excel_diff::DiffOp::ExcelTableResized { sheet, .. }
+            | excel_diff::DiffOp::ExcelTableColumnRenamed { sheet, .. }
+            | excel_diff::DiffOp::DrawingObjectAdded { sheet, .. }
+            | excel_diff::DiffOp::DrawingObjectRemoved { sheet, .. }
+            | excel_diff::DiffOp::DrawingObjectMoved { sheet, .. }
+            | excel_diff::DiffOp::DrawingObjectChanged { sheet, .. } => Some(*sheet),
             _ => None,
         };
 
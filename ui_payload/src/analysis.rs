@@ -136,9 +136,33 @@ fn classify_op(op: &DiffOp) -> OpClass {
             | DiffOp::ChartAdded { .. }
             | DiffOp::ChartRemoved { .. }
             | DiffOp::ChartChanged { .. }
+            | DiffOp::PivotTableAdded { .. }
+            | DiffOp::PivotTableRemoved { .. }
+            | DiffOp::PivotTableSourceChanged { .. }
+            | DiffOp::PivotTableLayoutChanged { .. }
+            | DiffOp::ExternalLinkAdded { .. }
+            | DiffOp::ExternalLinkRemoved { .. }
+            | DiffOp::ExternalLinkChanged { .. }
+            | DiffOp::ConnectionAdded { .. }
+            | DiffOp::ConnectionRemoved { .. }
+            | DiffOp::ConnectionChanged { .. }
+            | DiffOp::ReportObjectAdded { .. }
+            | DiffOp::ReportObjectRemoved { .. }
+            | DiffOp::ReportObjectChanged { .. }
+            | DiffOp::WorkbookSettingsChanged { .. }
+            | DiffOp::WorkbookProtectionChanged { .. }
+            | DiffOp::DocumentPropertyChanged { .. }
+            | DiffOp::DrawingObjectAdded { .. }
+            | DiffOp::DrawingObjectRemoved { .. }
+            | DiffOp::DrawingObjectMoved { .. }
+            | DiffOp::DrawingObjectChanged { .. }
             | DiffOp::VbaModuleAdded { .. }
             | DiffOp::VbaModuleRemoved { .. }
             | DiffOp::VbaModuleChanged { .. }
+            | DiffOp::VbaReferenceAdded { .. }
+            | DiffOp::VbaReferenceRemoved { .. }
+            | DiffOp::VbaReferenceChanged { .. }
+            | DiffOp::VbaProjectChanged { .. }
     ) {
         OpCategory::Objects
     } else if matches!(
@@ -150,6 +174,7 @@ fn classify_op(op: &DiffOp) -> OpClass {
             | DiffOp::RowRemoved { .. }
             | DiffOp::RowReplaced { .. }
             | DiffOp::DuplicateKeyCluster { .. }
+            | DiffOp::RecordChanged { .. }
             | DiffOp::ColumnAdded { .. }
             | DiffOp::ColumnRemoved { .. }
             | DiffOp::BlockMovedRows { .. }
@@ -157,6 +182,34 @@ fn classify_op(op: &DiffOp) -> OpClass {
             | DiffOp::BlockMovedRect { .. }
             | DiffOp::RectReplaced { .. }
             | DiffOp::CellEdited { .. }
+            | DiffOp::CellStyleChanged { .. }
+            | DiffOp::CommentAdded { .. }
+            | DiffOp::CommentRemoved { .. }
+            | DiffOp::CommentChanged { .. }
+            | DiffOp::DataValidationAdded { .. }
+            | DiffOp::DataValidationRemoved { .. }
+            | DiffOp::DataValidationChanged { .. }
+            | DiffOp::ConditionalFormatAdded { .. }
+            | DiffOp::ConditionalFormatRemoved { .. }
+            | DiffOp::ConditionalFormatChanged { .. }
+            | DiffOp::MergedRangeAdded { .. }
+            | DiffOp::MergedRangeRemoved { .. }
+            | DiffOp::RowVisibilityChanged { .. }
+            | DiffOp::ColumnVisibilityChanged { .. }
+            | DiffOp::RowOutlineChanged { .. }
+            | DiffOp::ColumnOutlineChanged { .. }
+            | DiffOp::RowHeightChanged { .. }
+            | DiffOp::ColumnWidthChanged { .. }
+            | DiffOp::FreezePaneChanged { .. }
+            | DiffOp::SpillRangeChanged { .. }
+            | DiffOp::SheetVisibilityChanged { .. }
+            | DiffOp::SheetTabColorChanged { .. }
+            | DiffOp::SheetProtectionChanged { .. }
+            | DiffOp::ExcelTableAdded { .. }
+            | DiffOp::ExcelTableRemoved { .. }
+            | DiffOp::ExcelTableRenamed { .. }
+            | DiffOp::ExcelTableResized { .. }
+            | DiffOp::ExcelTableColumnRenamed { .. }
     ) {
         OpCategory::Grid
     } else {
@@ -197,7 +250,14 @@ fn classify_op(op: &DiffOp) -> OpClass {
             // Unknown/TextChange may still be material; keep medium by default.
             _ => OpSeverity::Medium,
         },
-        DiffOp::SheetRenamed { .. } | DiffOp::QueryRenamed { .. } => OpSeverity::Low,
+        DiffOp::SheetRenamed { .. }
+        | DiffOp::QueryRenamed { .. }
+        | DiffOp::CellStyleChanged { .. }
+        | DiffOp::CommentAdded { .. }
+        | DiffOp::CommentRemoved { .. }
+        | DiffOp::CommentChanged { .. }
+        | DiffOp::DrawingObjectMoved { .. }
+        | DiffOp::DocumentPropertyChanged { .. } => OpSeverity::Low,
         DiffOp::BlockMovedRows { .. }
         | DiffOp::BlockMovedColumns { .. }
         | DiffOp::BlockMovedRect { .. } => OpSeverity::Medium,
@@ -214,9 +274,31 @@ fn classify_op(op: &DiffOp) -> OpClass {
         | DiffOp::ChartAdded { .. }
         | DiffOp::ChartRemoved { .. }
         | DiffOp::ChartChanged { .. }
+        | DiffOp::PivotTableAdded { .. }
+        | DiffOp::PivotTableRemoved { .. }
+        | DiffOp::PivotTableSourceChanged { .. }
+        | DiffOp::PivotTableLayoutChanged { .. }
+        | DiffOp::ExternalLinkRemoved { .. }
+        | DiffOp::ConnectionRemoved { .. }
+        | DiffOp::ReportObjectAdded { .. }
+        | DiffOp::ReportObjectRemoved { .. }
+        | DiffOp::ReportObjectChanged { .. }
+        | DiffOp::WorkbookProtectionChanged { .. }
+        | DiffOp::SheetProtectionChanged { .. }
         | DiffOp::VbaModuleAdded { .. }
         | DiffOp::VbaModuleRemoved { .. }
-        | DiffOp::VbaModuleChanged { .. } => OpSeverity::Medium,
+        | DiffOp::VbaModuleChanged { .. }
+        | DiffOp::VbaReferenceRemoved { .. }
+        | DiffOp::VbaProjectChanged { .. } => OpSeverity::Medium,
+        // A new or retargeted reference can pull in external code.
+        DiffOp::VbaReferenceAdded { .. } | DiffOp::VbaReferenceChanged { .. } => OpSeverity::High,
+        // So does a new or repointed link or connection change where data comes from.
+        DiffOp::ExternalLinkAdded { .. }
+        | DiffOp::ExternalLinkChanged { .. }
+        | DiffOp::ConnectionAdded { .. }
+        | DiffOp::ConnectionChanged { .. } => OpSeverity::High,
+        // Manual calculation leaves stale results on screen.
+        DiffOp::WorkbookSettingsChanged { .. } => OpSeverity::High,
         _ => OpSeverity::Medium,
     };
 
@@ -278,13 +360,46 @@ fn op_sheet_name(report: &DiffReport, op: &DiffOp) -> Option<String> {
         | DiffOp::RowRemoved { sheet, .. }
         | DiffOp::RowReplaced { sheet, .. }
         | DiffOp::DuplicateKeyCluster { sheet, .. }
+        | DiffOp::RecordChanged { sheet, .. }
         | DiffOp::ColumnAdded { sheet, .. }
         | DiffOp::ColumnRemoved { sheet, .. }
         | DiffOp::BlockMovedRows { sheet, .. }
         | DiffOp::BlockMovedColumns { sheet, .. }
         | DiffOp::BlockMovedRect { sheet, .. }
         | DiffOp::RectReplaced { sheet, .. }
-        | DiffOp::CellEdited { sheet, .. } => Some(*sheet),
+        | DiffOp::CellEdited { sheet, .. }
+        | DiffOp::CellStyleChanged { sheet, .. }
+        | DiffOp::CommentAdded { sheet, .. }
+        | DiffOp::CommentRemoved { sheet, .. }
+        | DiffOp::CommentChanged { sheet, .. }
+        | DiffOp::DataValidationAdded { sheet, .. }
+        | DiffOp::DataValidationRemoved { sheet, .. }
+        | DiffOp::DataValidationChanged { sheet, .. }
+        | DiffOp::ConditionalFormatAdded { sheet, .. }
+        | DiffOp::ConditionalFormatRemoved { sheet, .. }
+        | DiffOp::ConditionalFormatChanged { sheet, .. }
+        | DiffOp::MergedRangeAdded { sheet, .. }
+        | DiffOp::MergedRangeRemoved { sheet, .. }
+        | DiffOp::RowVisibilityChanged { sheet, .. }
+        | DiffOp::ColumnVisibilityChanged { sheet, .. }
+        | DiffOp::RowOutlineChanged { sheet, .. }
+        | DiffOp::ColumnOutlineChanged { sheet, .. }
+        | DiffOp::RowHeightChanged { sheet, .. }
+        | DiffOp::ColumnWidthChanged { sheet, .. }
+        | DiffOp::FreezePaneChanged { sheet, .. }
+        | DiffOp::SpillRangeChanged { sheet, .. }
+        | DiffOp::SheetVisibilityChanged { sheet, .. }
+        | DiffOp::SheetTabColorChanged { sheet, .. }
+        | DiffOp::SheetProtectionChanged { sheet, .. }
+        | DiffOp::ExcelTableAdded { sheet, .. }
+        | DiffOp::ExcelTableRemoved { sheet, .. }
+        | DiffOp::ExcelTableRenamed { sheet, .. }
+        | DiffOp::ExcelTableResized { sheet, .. }
+        | DiffOp::ExcelTableColumnRenamed { sheet, .. }
+        | DiffOp::DrawingObjectAdded { sheet, .. }
+        | DiffOp::DrawingObjectRemoved { sheet, .. }
+        | DiffOp::DrawingObjectMoved { sheet, .. }
+        | DiffOp::DrawingObjectChanged { sheet, .. } => Some(*sheet),
         _ => None,
     }?;
     Some(report.resolve(id).unwrap_or("<unknown>").to_string())
@@ -408,7 +523,8 @@ pub fn analyze_report(report: &DiffReport, filters: NoiseFilters) -> DiffAnalysi
 mod tests {
     use super::*;
     use excel_diff::{
-        CellAddress, CellSnapshot, DiffOp, DiffReport, FormulaDiffResult, SheetId, StringId,
+        CellAddress, CellSnapshot, CellValue, DiffOp, DiffReport, FieldChange, FormulaDiffResult,
+        SheetId, StringId,
     };
 
     fn make_sheet_id() -> SheetId {
@@ -429,6 +545,7 @@ mod tests {
                 from: from.clone(),
                 to: to.clone(),
                 formula_diff: FormulaDiffResult::FormattingOnly,
+                formula_edits: Vec::new(),
             },
             DiffOp::CellEdited {
                 sheet,
@@ -436,6 +553,7 @@ mod tests {
                 from,
                 to,
                 formula_diff: FormulaDiffResult::SemanticChange,
+                formula_edits: Vec::new(),
             },
         ];
         let report = DiffReport::new(ops);
@@ -480,4 +598,39 @@ mod tests {
         assert_eq!(analysis.op_count, 1);
         assert_eq!(analysis.counts.moved, 1);
     }
+
+    #[test]
+    fn record_changes_do_not_count_twice() {
+        let sheet = make_sheet_id();
+        let addr = CellAddress::from_indices(3, 2);
+        let mut from = CellSnapshot::empty(addr);
+        from.value = Some(CellValue::Number(10.0));
+        let mut to = CellSnapshot::empty(addr);
+        to.value = Some(CellValue::Number(12.0));
+        let ops = vec![
+            DiffOp::CellEdited {
+                sheet,
+                addr,
+                from,
+                to,
+                formula_diff: FormulaDiffResult::Unchanged,
+                formula_edits: Vec::new(),
+            },
+            DiffOp::RecordChanged {
+                sheet,
+                key: vec![Some(CellValue::Number(1001.0))],
+                old_row: 3,
+                new_row: 3,
+                fields: vec![FieldChange {
+                    col: 2,
+                    field: None,
+                    from: Some(CellValue::Number(10.0)),
+                    to: Some(CellValue::Number(12.0)),
+                }],
+            },
+        ];
+        let analysis = analyze_report(&DiffReport::new(ops), NoiseFilters::default());
+        assert_eq!(analysis.op_count, 2);
+        assert_eq!(analysis.counts.modified, 1);
+    }
 }
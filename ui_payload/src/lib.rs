@@ -9,6 +9,7 @@ mod capabilities;
 mod domain;
 mod options;
 mod outcome;
+mod records;
 
 pub use alignment::SheetAlignment;
 pub use analysis::{
@@ -22,6 +23,7 @@ pub use outcome::{
     summarize_report, ChangeCounts, DiffOutcome, DiffOutcomeConfig, DiffOutcomeMode,
     DiffOutcomeSummary, SheetSummary, SummaryMeta, SummarySink,
 };
+pub use records::{build_record_changes, RecordChange, RecordFieldChange};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum HostKind {
@@ -90,6 +92,9 @@ pub struct DiffWithSheets {
     pub alignments: Vec<SheetAlignment>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub interest_rects: Vec<SheetInterestRects>,
+    /// Keyed record changes from database or table mode, with display labels.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub records: Vec<RecordChange>,
 }
 
 pub const MAX_SNAPSHOT_CELLS_PER_SHEET: usize = 50_000;
@@ -172,6 +177,7 @@ pub fn build_payload_from_pbix_report(report: excel_diff::DiffReport) -> DiffWit
         },
         alignments: Vec::new(),
         interest_rects: Vec::new(),
+        records: Vec::new(),
     }
 }
 
@@ -238,11 +244,13 @@ pub fn build_payload_from_workbook_report(
             rects,
         });
     }
+    let records = build_record_changes(&report);
     DiffWithSheets {
         report,
         sheets,
         alignments,
         interest_rects,
+        records,
     }
 }
 
@@ -294,13 +302,46 @@ fn collect_sheet_ids(ops: &[excel_diff::DiffOp]) -> HashSet<excel_diff::StringId
             | excel_diff::DiffOp::RowRemoved { sheet, .. }
             | excel_diff::DiffOp::RowReplaced { sheet, .. }
             | excel_diff::DiffOp::DuplicateKeyCluster { sheet, .. }
+            | excel_diff::DiffOp::RecordChanged { sheet, .. }
             | excel_diff::DiffOp::ColumnAdded { sheet, .. }
             | excel_diff::DiffOp::ColumnRemoved { sheet, .. }
             | excel_diff::DiffOp::BlockMovedRows { sheet, .. }
             | excel_diff::DiffOp::BlockMovedColumns { sheet, .. }
             | excel_diff::DiffOp::BlockMovedRect { sheet, .. }
             | excel_diff::DiffOp::RectReplaced { sheet, .. }
-            | excel_diff::DiffOp::CellEdited { sheet, .. } => Some(*sheet),
+            | excel_diff::DiffOp::CellEdited { sheet, .. }
+            | excel_diff::DiffOp::CellStyleChanged { sheet, .. }
+            | excel_diff::DiffOp::CommentAdded { sheet, .. }
+            | excel_diff::DiffOp::CommentRemoved { sheet, .. }
+            | excel_diff::DiffOp::CommentChanged { sheet, .. }
+            | excel_diff::DiffOp::DataValidationAdded { sheet, .. }
+            | excel_diff::DiffOp::DataValidationRemoved { sheet, .. }
+            | excel_diff::DiffOp::DataValidationChanged { sheet, .. }
+            | excel_diff::DiffOp::ConditionalFormatAdded { sheet, .. }
+            | excel_diff::DiffOp::ConditionalFormatRemoved { sheet, .. }
+            | excel_diff::DiffOp::ConditionalFormatChanged { sheet, .. }
+            | excel_diff::DiffOp::MergedRangeAdded { sheet, .. }
+            | excel_diff::DiffOp::MergedRangeRemoved { sheet, .. }
+            | excel_diff::DiffOp::RowVisibilityChanged { sheet, .. }
+            | excel_diff::DiffOp::ColumnVisibilityChanged { sheet, .. }
+            | excel_diff::DiffOp::RowOutlineChanged { sheet, .. }
+            | excel_diff::DiffOp::ColumnOutlineChanged { sheet, .. }
+            | excel_diff::DiffOp::RowHeightChanged { sheet, .. }
+            | excel_diff::DiffOp::ColumnWidthChanged { sheet, .. }
+            | excel_diff::DiffOp::FreezePaneChanged { sheet, .. }
+            | excel_diff::DiffOp::SpillRangeChanged { sheet, .. }
+            | excel_diff::DiffOp::SheetVisibilityChanged { sheet, .. }
+            | excel_diff::DiffOp::SheetTabColorChanged { sheet, .. }
+            | excel_diff::DiffOp::SheetProtectionChanged { sheet, .. }
+            | excel_diff::DiffOp::ExcelTableAdded { sheet, .. }
+            | excel_diff::DiffOp::ExcelTableRemoved { sheet, .. }
+            | excel_diff::DiffOp::ExcelTableRenamed { sheet, .. }
+            | excel_diff::DiffOp::ExcelTableResized { sheet, .. }
+            | excel_diff::DiffOp::ExcelTableColumnRenamed { sheet, .. }
+            | excel_diff::DiffOp::DrawingObjectAdded { sheet, .. }
+            | excel_diff::DiffOp::DrawingObjectRemoved { sheet, .. }
+            | excel_diff::DiffOp::DrawingObjectMoved { sheet, .. }
+            | excel_diff::DiffOp::DrawingObjectChanged { sheet, .. } => Some(*sheet),
             _ => None,
         };
         if let Some(sheet_id) = sheet {
@@ -323,13 +364,46 @@ fn group_ops_by_sheet(
             | excel_diff::DiffOp::RowRemoved { sheet, .. }
             | excel_diff::DiffOp::RowReplaced { sheet, .. }
             | excel_diff::DiffOp::DuplicateKeyCluster { sheet, .. }
+            | excel_diff::DiffOp::RecordChanged { sheet, .. }
             | excel_diff::DiffOp::ColumnAdded { sheet, .. }
             | excel_diff::DiffOp::ColumnRemoved { sheet, .. }
             | excel_diff::DiffOp::BlockMovedRows { sheet, .. }
             | excel_diff::DiffOp::BlockMovedColumns { sheet, .. }
             | excel_diff::DiffOp::BlockMovedRect { sheet, .. }
             | excel_diff::DiffOp::RectReplaced { sheet, .. }
-            | excel_diff::DiffOp::CellEdited { sheet, .. } => Some(*sheet),
+            | excel_diff::DiffOp::CellEdited { sheet, .. }
+            | excel_diff::DiffOp::CellStyleChanged { sheet, .. }
+            | excel_diff::DiffOp::CommentAdded { sheet, .. }
+            | excel_diff::DiffOp::CommentRemoved { sheet, .. }
+            | excel_diff::DiffOp::CommentChanged { sheet, .. }
+            | excel_diff::DiffOp::DataValidationAdded { sheet, .. }
+            | excel_diff::DiffOp::DataValidationRemoved { sheet, .. }
+            | excel_diff::DiffOp::DataValidationChanged { sheet, .. }
+            | excel_diff::DiffOp::ConditionalFormatAdded { sheet, .. }
+            | excel_diff::DiffOp::ConditionalFormatRemoved { sheet, .. }
+            | excel_diff::DiffOp::ConditionalFormatChanged { sheet, .. }
+            | excel_diff::DiffOp::MergedRangeAdded { sheet, .. }
+            | excel_diff::DiffOp::MergedRangeRemoved { sheet, .. }
+            | excel_diff::DiffOp::RowVisibilityChanged { sheet, .. }
+            | excel_diff::DiffOp::ColumnVisibilityChanged { sheet, .. }
+            | excel_diff::DiffOp::RowOutlineChanged { sheet, .. }
+            | excel_diff::DiffOp::ColumnOutlineChanged { sheet, .. }
+            | excel_diff::DiffOp::RowHeightChanged { sheet, .. }
+            | excel_diff::DiffOp::ColumnWidthChanged { sheet, .. }
+            | excel_diff::DiffOp::FreezePaneChanged { sheet, .. }
+            | excel_diff::DiffOp::SpillRangeChanged { sheet, .. }
+            | excel_diff::DiffOp::SheetVisibilityChanged { sheet, .. }
+            | excel_diff::DiffOp::SheetTabColorChanged { sheet, .. }
+            | excel_diff::DiffOp::SheetProtectionChanged { sheet, .. }
+            | excel_diff::DiffOp::ExcelTableAdded { sheet, .. }
+            | excel_diff::DiffOp::ExcelTableRemoved { sheet, .. }
+            | excel_diff::DiffOp::ExcelTableRenamed { sheet, .. }
+            | excel_diff::DiffOp::ExcelTableResized { sheet, .. }
+            | excel_diff::DiffOp::ExcelTableColumnRenamed { sheet, .. }
+            | excel_diff::DiffOp::DrawingObjectAdded { sheet, .. }
+            | excel_diff::DiffOp::DrawingObjectRemoved { sheet, .. }
+            | excel_diff::DiffOp::DrawingObjectMoved { sheet, .. }
+            | excel_diff::DiffOp::DrawingObjectChanged { sheet, .. } => Some(*sheet),
             _ => None,
         };
         let Some(sheet_id) = sheet else {
@@ -447,7 +521,11 @@ fn collect_interest_rects(
 
     for op in ops {
         match op {
-            excel_diff::DiffOp::CellEdited { addr, .. } => {
+            excel_diff::DiffOp::CellEdited { addr, .. }
+            | excel_diff::DiffOp::CellStyleChanged { addr, .. }
+            | excel_diff::DiffOp::CommentAdded { addr, .. }
+            | excel_diff::DiffOp::CommentRemoved { addr, .. }
+            | excel_diff::DiffOp::CommentChanged { addr, .. } => {
                 if addr.row < nrows && addr.col < ncols {
                     if let Some(rect) = rect_from_range(addr.row, 1, addr.col, 1, nrows, ncols) {
                         rects.push(rect);
@@ -654,7 +732,11 @@ fn collect_interest_rects_payload(
 
     for op in ops {
         match op {
-            excel_diff::DiffOp::CellEdited { addr, .. } => {
+            excel_diff::DiffOp::CellEdited { addr, .. }
+            | excel_diff::DiffOp::CellStyleChanged { addr, .. }
+            | excel_diff::DiffOp::CommentAdded { addr, .. }
+            | excel_diff::DiffOp::CommentRemoved { addr, .. }
+            | excel_diff::DiffOp::CommentChanged { addr, .. } => {
                 if let Some(rect) =
                     rect_with_context(addr.row, 1, addr.col, 1, min_rows, min_cols, caps)
                 {
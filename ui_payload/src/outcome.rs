@@ -229,13 +229,46 @@ fn op_sheet_id(op: &DiffOp) -> Option<StringId> {
         | DiffOp::RowRemoved { sheet, .. }
         | DiffOp::RowReplaced { sheet, .. }
         | DiffOp::DuplicateKeyCluster { sheet, .. }
+        | DiffOp::RecordChanged { sheet, .. }
         | DiffOp::ColumnAdded { sheet, .. }
         | DiffOp::ColumnRemoved { sheet, .. }
         | DiffOp::BlockMovedRows { sheet, .. }
         | DiffOp::BlockMovedColumns { sheet, .. }
         | DiffOp::BlockMovedRect { sheet, .. }
         | DiffOp::RectReplaced { sheet, .. }
-        | DiffOp::CellEdited { sheet, .. } => Some(*sheet),
+        | DiffOp::CellEdited { sheet, .. }
+        | DiffOp::CellStyleChanged { sheet, .. }
+        | DiffOp::CommentAdded { sheet, .. }
+        | DiffOp::CommentRemoved { sheet, .. }
+        | DiffOp::CommentChanged { sheet, .. }
+        | DiffOp::DataValidationAdded { sheet, .. }
+        | DiffOp::DataValidationRemoved { sheet, .. }
+        | DiffOp::DataValidationChanged { sheet, .. }
+        | DiffOp::ConditionalFormatAdded { sheet, .. }
+        | DiffOp::ConditionalFormatRemoved { sheet, .. }
+        | DiffOp::ConditionalFormatChanged { sheet, .. }
+        | DiffOp::MergedRangeAdded { sheet, .. }
+        | DiffOp::MergedRangeRemoved { sheet, .. }
+        | DiffOp::RowVisibilityChanged { sheet, .. }
+        | DiffOp::ColumnVisibilityChanged { sheet, .. }
+        | DiffOp::RowOutlineChanged { sheet, .. }
+        | DiffOp::ColumnOutlineChanged { sheet, .. }
+        | DiffOp::RowHeightChanged { sheet, .. }
+        | DiffOp::ColumnWidthChanged { sheet, .. }
+        | DiffOp::FreezePaneChanged { sheet, .. }
+        | DiffOp::SpillRangeChanged { sheet, .. }
+        | DiffOp::SheetVisibilityChanged { sheet, .. }
+        | DiffOp::SheetTabColorChanged { sheet, .. }
+        | DiffOp::SheetProtectionChanged { sheet, .. }
+        | DiffOp::ExcelTableAdded { sheet, .. }
+        | DiffOp::ExcelTableRemoved { sheet, .. }
+        | DiffOp::ExcelTableRenamed { sheet, .. }
+        | DiffOp::ExcelTableResized { sheet, .. }
+        | DiffOp::ExcelTableColumnRenamed { sheet, .. }
+        | DiffOp::DrawingObjectAdded { sheet, .. }
+        | DiffOp::DrawingObjectRemoved { sheet, .. }
+        | DiffOp::DrawingObjectMoved { sheet, .. }
+        | DiffOp::DrawingObjectChanged { sheet, .. } => Some(*sheet),
         _ => None,
     }
 }
@@ -247,7 +280,18 @@ fn classify_op(op: &DiffOp) -> Option<ChangeKind> {
         | DiffOp::ColumnAdded { .. }
         | DiffOp::NamedRangeAdded { .. }
         | DiffOp::ChartAdded { .. }
+        | DiffOp::PivotTableAdded { .. }
+        | DiffOp::ExternalLinkAdded { .. }
+        | DiffOp::ConnectionAdded { .. }
+        | DiffOp::ReportObjectAdded { .. }
         | DiffOp::VbaModuleAdded { .. }
+        | DiffOp::VbaReferenceAdded { .. }
+        | DiffOp::CommentAdded { .. }
+        | DiffOp::DataValidationAdded { .. }
+        | DiffOp::ConditionalFormatAdded { .. }
+        | DiffOp::MergedRangeAdded { .. }
+        | DiffOp::ExcelTableAdded { .. }
+        | DiffOp::DrawingObjectAdded { .. }
         | DiffOp::QueryAdded { .. }
         | DiffOp::QueryMetadataChanged {
             field: QueryMetadataField::LoadToSheet,
@@ -258,19 +302,60 @@ fn classify_op(op: &DiffOp) -> Option<ChangeKind> {
         | DiffOp::ColumnRemoved { .. }
         | DiffOp::NamedRangeRemoved { .. }
         | DiffOp::ChartRemoved { .. }
+        | DiffOp::PivotTableRemoved { .. }
+        | DiffOp::ExternalLinkRemoved { .. }
+        | DiffOp::ConnectionRemoved { .. }
+        | DiffOp::ReportObjectRemoved { .. }
         | DiffOp::VbaModuleRemoved { .. }
+        | DiffOp::VbaReferenceRemoved { .. }
+        | DiffOp::CommentRemoved { .. }
+        | DiffOp::DataValidationRemoved { .. }
+        | DiffOp::ConditionalFormatRemoved { .. }
+        | DiffOp::MergedRangeRemoved { .. }
+        | DiffOp::ExcelTableRemoved { .. }
+        | DiffOp::DrawingObjectRemoved { .. }
         | DiffOp::QueryRemoved { .. } => Some(ChangeKind::Removed),
         DiffOp::BlockMovedRows { .. }
         | DiffOp::BlockMovedColumns { .. }
-        | DiffOp::BlockMovedRect { .. } => Some(ChangeKind::Moved),
+        | DiffOp::BlockMovedRect { .. }
+        | DiffOp::DrawingObjectMoved { .. } => Some(ChangeKind::Moved),
         DiffOp::RowReplaced { .. }
         | DiffOp::DuplicateKeyCluster { .. }
         | DiffOp::RectReplaced { .. }
         | DiffOp::CellEdited { .. }
+        | DiffOp::CellStyleChanged { .. }
+        | DiffOp::CommentChanged { .. }
+        | DiffOp::DataValidationChanged { .. }
+        | DiffOp::ConditionalFormatChanged { .. }
+        | DiffOp::RowVisibilityChanged { .. }
+        | DiffOp::ColumnVisibilityChanged { .. }
+        | DiffOp::RowOutlineChanged { .. }
+        | DiffOp::ColumnOutlineChanged { .. }
+        | DiffOp::RowHeightChanged { .. }
+        | DiffOp::ColumnWidthChanged { .. }
+        | DiffOp::FreezePaneChanged { .. }
+        | DiffOp::SpillRangeChanged { .. }
+        | DiffOp::SheetVisibilityChanged { .. }
+        | DiffOp::SheetTabColorChanged { .. }
+        | DiffOp::SheetProtectionChanged { .. }
+        | DiffOp::ExcelTableRenamed { .. }
+        | DiffOp::ExcelTableResized { .. }
+        | DiffOp::ExcelTableColumnRenamed { .. }
+        | DiffOp::DrawingObjectChanged { .. }
         | DiffOp::SheetRenamed { .. }
         | DiffOp::NamedRangeChanged { .. }
         | DiffOp::ChartChanged { .. }
+        | DiffOp::PivotTableSourceChanged { .. }
+        | DiffOp::PivotTableLayoutChanged { .. }
+        | DiffOp::ExternalLinkChanged { .. }
+        | DiffOp::ConnectionChanged { .. }
+        | DiffOp::ReportObjectChanged { .. }
+        | DiffOp::WorkbookSettingsChanged { .. }
+        | DiffOp::WorkbookProtectionChanged { .. }
+        | DiffOp::DocumentPropertyChanged { .. }
         | DiffOp::VbaModuleChanged { .. }
+        | DiffOp::VbaReferenceChanged { .. }
+        | DiffOp::VbaProjectChanged { .. }
         | DiffOp::QueryRenamed { .. }
         | DiffOp::QueryDefinitionChanged { .. }
         | DiffOp::QueryMetadataChanged { .. } => Some(ChangeKind::Modified),
@@ -278,18 +363,24 @@ fn classify_op(op: &DiffOp) -> Option<ChangeKind> {
         DiffOp::TableAdded { .. }
         | DiffOp::ModelColumnAdded { .. }
         | DiffOp::RelationshipAdded { .. }
-        | DiffOp::MeasureAdded { .. } => Some(ChangeKind::Added),
+        | DiffOp::MeasureAdded { .. }
+        | DiffOp::ModelObjectAdded { .. } => Some(ChangeKind::Added),
         #[cfg(feature = "model-diff")]
         DiffOp::TableRemoved { .. }
         | DiffOp::ModelColumnRemoved { .. }
         | DiffOp::RelationshipRemoved { .. }
-        | DiffOp::MeasureRemoved { .. } => Some(ChangeKind::Removed),
+        | DiffOp::MeasureRemoved { .. }
+        | DiffOp::ModelObjectRemoved { .. } => Some(ChangeKind::Removed),
         #[cfg(feature = "model-diff")]
         DiffOp::ModelColumnTypeChanged { .. }
         | DiffOp::ModelColumnPropertyChanged { .. }
         | DiffOp::CalculatedColumnDefinitionChanged { .. }
         | DiffOp::RelationshipPropertyChanged { .. }
-        | DiffOp::MeasureDefinitionChanged { .. } => Some(ChangeKind::Modified),
+        | DiffOp::MeasureDefinitionChanged { .. }
+        | DiffOp::MeasurePropertyChanged { .. }
+        | DiffOp::ModelObjectChanged { .. } => Some(ChangeKind::Modified),
+        // Summarises the record's `CellEdited` ops, which are already counted.
+        DiffOp::RecordChanged { .. } => None,
         _ => None,
     }
 }
@@ -0,0 +1,153 @@
+use serde::Serialize;
+
+use excel_diff::{CellValue, DiffOp, DiffReport};
+
+/// One keyed record whose fields changed, as reported by database or table mode.
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordChange {
+    pub sheet: String,
+    pub key: Vec<String>,
+    pub old_row: u32,
+    pub new_row: u32,
+    pub fields: Vec<RecordFieldChange>,
+    /// Ready-to-display summary, e.g. `record 1001: Price 10 → 12, Qty 3 → 4`.
+    pub label: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordFieldChange {
+    /// Header text, or the column letter when the column has no text header.
+    pub field: String,
+    pub col: u32,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+pub fn build_record_changes(report: &DiffReport) -> Vec<RecordChange> {
+    report
+        .ops
+        .iter()
+        .filter_map(|op| match op {
+            DiffOp::RecordChanged {
+                sheet,
+                key,
+                old_row,
+                new_row,
+                fields,
+            } => Some(record_change(
+                report, *sheet, key, *old_row, *new_row, fields,
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+fn record_change(
+    report: &DiffReport,
+    sheet: excel_diff::SheetId,
+    key: &[Option<CellValue>],
+    old_row: u32,
+    new_row: u32,
+    fields: &[excel_diff::FieldChange],
+) -> RecordChange {
+    let key: Vec<String> = key
+        .iter()
+        .map(|value| render_value(report, value).unwrap_or_default())
+        .collect();
+    let fields: Vec<RecordFieldChange> = fields
+        .iter()
+        .map(|field| RecordFieldChange {
+            field: match field.field {
+                Some(id) => report.resolve(id).unwrap_or("<unknown>").to_string(),
+                None => col_letters(field.col),
+            },
+            col: field.col,
+            old_value: render_value(report, &field.from),
+            new_value: render_value(report, &field.to),
+        })
+        .collect();
+    let changes: Vec<String> = fields
+        .iter()
+        .map(|field| {
+            format!(
+                "{} {} → {}",
+                field.field,
+                display(&field.old_value),
+                display(&field.new_value)
+            )
+        })
+        .collect();
+    RecordChange {
+        sheet: report.resolve(sheet).unwrap_or("<unknown>").to_string(),
+        label: format!("record {}: {}", key.join(", "), changes.join(", ")),
+        key,
+        old_row,
+        new_row,
+        fields,
+    }
+}
+
+fn render_value(report: &DiffReport, value: &Option<CellValue>) -> Option<String> {
+    match value {
+        None => None,
+        Some(CellValue::Blank) => Some(String::new()),
+        Some(CellValue::Number(n)) => Some(n.to_string()),
+        Some(CellValue::Text(id)) | Some(CellValue::Error(id)) => {
+            Some(report.resolve(*id).unwrap_or("<unknown>").to_string())
+        }
+        Some(CellValue::Bool(b)) => Some(if *b { "TRUE" } else { "FALSE" }.to_string()),
+    }
+}
+
+fn display(value: &Option<String>) -> &str {
+    match value.as_deref() {
+        None | Some("") => "(empty)",
+        Some(text) => text,
+    }
+}
+
+fn col_letters(col: u32) -> String {
+    excel_diff::index_to_address(0, col)
+        .trim_end_matches(|c: char| c.is_ascii_digit())
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use excel_diff::{FieldChange, StringId};
+
+    #[test]
+    fn record_label_names_fields_and_falls_back_to_column_letters() {
+        let mut report = DiffReport::new(vec![DiffOp::RecordChanged {
+            sheet: StringId(0),
+            key: vec![Some(CellValue::Number(1001.0))],
+            old_row: 4,
+            new_row: 5,
+            fields: vec![
+                FieldChange {
+                    col: 2,
+                    field: Some(StringId(1)),
+                    from: Some(CellValue::Number(10.0)),
+                    to: Some(CellValue::Number(12.0)),
+                },
+                FieldChange {
+                    col: 3,
+                    field: None,
+                    from: Some(CellValue::Number(3.0)),
+                    to: None,
+                },
+            ],
+        }]);
+        report.strings = vec!["Orders".to_string(), "Price".to_string()];
+
+        let records = build_record_changes(&report);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].sheet, "Orders");
+        assert_eq!(records[0].key, vec!["1001".to_string()]);
+        assert_eq!(
+            records[0].label,
+            "record 1001: Price 10 → 12, D 3 → (empty)"
+        );
+    }
+}
@@ -411,9 +411,9 @@ fn single_sheet_workbook(pool: &mut StringPool, grid: Grid) -> Workbook {
             workbook_sheet_id: None,
             kind: SheetKind::Worksheet,
             grid,
+            layout: Default::default(),
         }],
-        named_ranges: Vec::new(),
-        charts: Vec::new(),
+        ..Default::default()
     }
 }
 
@@ -474,9 +474,9 @@ mod tests {
                 workbook_sheet_id: None,
                 kind: SheetKind::Worksheet,
                 grid,
+                layout: Default::default(),
             }],
-            named_ranges: Vec::new(),
-            charts: Vec::new(),
+            ..Default::default()
         }
     }
 